@@ -1,3 +1,4 @@
+use std::{fmt::Display, ops::Range};
 use thiserror::Error;
 
 /// Errors encountered during the parsing process
@@ -17,3 +18,67 @@ pub enum ParseError {
 /// General parsing error that may occur, for example if the provided schema/object_name strings
 /// aren't valid postgres-style identifiers (excluding dollar signs).
 pub type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// A syntax error in a query, along with where in the query it occurred.
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+#[error("{message} at byte {}", span.start)]
+pub struct SyntaxError {
+    /// What went wrong
+    pub message: String,
+    /// The byte range of the query where it went wrong
+    pub span: Range<usize>,
+    /// The tokens that would have been accepted at `span`, if known
+    pub expected: Vec<String>,
+}
+
+impl SyntaxError {
+    /// Convert an error from the generated parser of `query`.
+    pub(crate) fn from_lalrpop<T: Display>(
+        query: &str,
+        error: lalrpop_util::ParseError<usize, T, &'static str>,
+    ) -> Self {
+        // The expected tokens are quoted, e.g. `"\"from\""`
+        let unquote = |expected: Vec<String>| {
+            expected
+                .into_iter()
+                .map(|token| token.trim_matches('"').to_string())
+                .collect()
+        };
+        match error {
+            lalrpop_util::ParseError::InvalidToken { location } => Self {
+                message: "invalid token".to_string(),
+                span: location
+                    ..query[location..]
+                        .chars()
+                        .next()
+                        .map_or(location, |c| location + c.len_utf8()),
+                expected: Vec::new(),
+            },
+            lalrpop_util::ParseError::UnrecognizedEof { location, expected } => Self {
+                message: "unexpected end of query".to_string(),
+                span: location..location,
+                expected: unquote(expected),
+            },
+            lalrpop_util::ParseError::UnrecognizedToken {
+                token: (start, token, end),
+                expected,
+            } => Self {
+                message: format!("unexpected token `{token}`"),
+                span: start..end,
+                expected: unquote(expected),
+            },
+            lalrpop_util::ParseError::ExtraToken {
+                token: (start, token, end),
+            } => Self {
+                message: format!("extra token `{token}`"),
+                span: start..end,
+                expected: Vec::new(),
+            },
+            lalrpop_util::ParseError::User { error } => Self {
+                message: error.to_string(),
+                span: 0..query.len(),
+                expected: Vec::new(),
+            },
+        }
+    }
+}
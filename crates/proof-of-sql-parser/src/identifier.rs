@@ -1,11 +1,14 @@
 use crate::{sql::IdentifierParser, ParseError, ParseResult};
 use arrayvec::ArrayString;
+use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, fmt, str::FromStr};
 
 /// Top-level unique identifier.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Ord, PartialOrd, Copy)]
 pub struct Identifier {
     name: ArrayString<64>,
+    /// Whether the name is displayed with quotes, which is decided once when it is constructed
+    quoted: bool,
 }
 
 impl Identifier {
@@ -18,14 +21,38 @@ impl Identifier {
     pub(crate) fn new<S: AsRef<str>>(string: S) -> Self {
         Self {
             name: ArrayString::from(&string.as_ref().to_lowercase()).expect("Identifier too long"),
+            quoted: false,
         }
     }
 
+    /// Constructor for [Identifier]s that keep their case, like quoted identifiers in SQL.
+    ///
+    /// The identifier is displayed with quotes if the name would not parse back to it without them,
+    /// because it is not lowercase or is a reserved keyword.
+    ///
+    /// Note: this constructor should be private within the proof_of_sql_parser crate, for the same
+    /// reason as [Identifier::new].
+    pub(crate) fn new_case_sensitive<S: AsRef<str>>(string: S) -> Self {
+        let name = ArrayString::from(string.as_ref()).expect("Identifier too long");
+        let quoted = name
+            .bytes()
+            .any(|byte| !matches!(byte, b'a'..=b'z' | b'0'..=b'9' | b'_'))
+            || IdentifierParser::new().parse(name.as_str()).is_err();
+        Self { name, quoted }
+    }
+
     /// An alias for [Identifier::from_str], provided for convenience.
     pub fn try_new<S: AsRef<str>>(string: S) -> ParseResult<Self> {
         Self::from_str(string.as_ref())
     }
 
+    /// Parse a name as a quoted identifier, so that it keeps its case.
+    ///
+    /// Unlike [Identifier::try_new], this also accepts names that are reserved keywords.
+    pub fn try_new_case_sensitive<S: AsRef<str>>(string: S) -> ParseResult<Self> {
+        Self::from_str(&format!("\"{}\"", string.as_ref()))
+    }
+
     /// The name of this [Identifier]
     /// It already implements [Deref] to [str], so this method is not necessary for most use cases.
     pub fn name(&self) -> &str {
@@ -36,6 +63,12 @@ impl Identifier {
     pub fn as_str(&self) -> &str {
         self.name()
     }
+
+    /// Whether this [Identifier] has to be quoted to be parsed back, because it is not lowercase or
+    /// is a reserved keyword, like `"timestamp"`.
+    pub fn needs_quotes(&self) -> bool {
+        self.quoted
+    }
 }
 
 impl FromStr for Identifier {
@@ -47,14 +80,20 @@ impl FromStr for Identifier {
             .map_err(|e| ParseError::IdentifierParseError(
                 format!("failed to parse identifier, (you may have used a reserved keyword as an ID, i.e. 'timestamp') {:?}", e)))?;
 
-        Ok(Identifier::new(name))
+        Ok(name)
     }
 }
 crate::impl_serde_from_str!(Identifier);
 
+/// Identifiers that are not lowercase or are reserved keywords are displayed with quotes, so that
+/// they are the same identifiers when they are parsed back.
 impl fmt::Display for Identifier {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.name.fmt(f)
+        if self.needs_quotes() {
+            write!(f, "\"{}\"", self.name)
+        } else {
+            self.name.fmt(f)
+        }
     }
 }
 
@@ -84,6 +123,30 @@ impl AsRef<str> for Identifier {
     }
 }
 
+/// How names from data sources, such as the column names of a record batch, become [Identifier]s.
+///
+/// Identifiers in SQL follow Postgres: unquoted identifiers are folded to lowercase and quoted
+/// identifiers keep their case. The policy decides which of the two a name from a data source is
+/// treated as.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IdentifierPolicy {
+    /// Names are folded to lowercase, so that they match unquoted identifiers regardless of case.
+    #[default]
+    CaseInsensitive,
+    /// Names keep their case, so that they match quoted identifiers exactly.
+    Exact,
+}
+
+impl IdentifierPolicy {
+    /// Convert a name to an [Identifier] under this policy.
+    pub fn identifier(self, name: &str) -> ParseResult<Identifier> {
+        match self {
+            IdentifierPolicy::CaseInsensitive => Identifier::try_new(name),
+            IdentifierPolicy::Exact => Identifier::try_new_case_sensitive(name),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,4 +328,96 @@ mod tests {
         Identifier::new("t".repeat(64));
         Identifier::new("茶".repeat(21));
     }
+    #[test]
+    fn quoted_identifiers_keep_their_case() {
+        let identifier = Identifier::from_str(r#""Good_Identifier""#).unwrap();
+        assert_eq!(identifier.name(), "Good_Identifier");
+        assert_ne!(identifier, Identifier::from_str("Good_Identifier").unwrap());
+        assert_eq!(
+            Identifier::from_str(r#""good_identifier""#).unwrap(),
+            Identifier::from_str("Good_Identifier").unwrap()
+        );
+        assert_eq!(
+            Identifier::try_new_case_sensitive("Good_Identifier").unwrap(),
+            identifier
+        );
+    }
+
+    #[test]
+    fn quoted_identifiers_can_be_reserved_keywords() {
+        assert!(Identifier::from_str("timestamp").is_err());
+        let identifier = Identifier::from_str(r#""timestamp""#).unwrap();
+        assert_eq!(identifier.name(), "timestamp");
+        assert!(identifier.needs_quotes());
+        assert_eq!(identifier.to_string(), r#""timestamp""#);
+        assert_eq!(
+            Identifier::from_str(&format!("{identifier}")).unwrap(),
+            identifier
+        );
+        let identifier = Identifier::from_str(r#""exists""#).unwrap();
+        assert!(!identifier.needs_quotes());
+        assert_eq!(identifier, Identifier::from_str("exists").unwrap());
+    }
+
+    #[test]
+    fn keyword_identifiers_can_be_serialized_and_deserialized() {
+        let identifier = Identifier::try_new_case_sensitive("timestamp").unwrap();
+        let serialized = serde_json::to_string(&identifier).unwrap();
+        assert_eq!(serialized, r#""\"timestamp\"""#);
+        let deserialized: Identifier = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, identifier);
+    }
+
+    #[test]
+    fn invalid_quoted_identifiers_fail() {
+        assert!(Identifier::from_str(r#""BAD IDENTIFIER""#).is_err());
+        assert!(Identifier::from_str(r#""13AD_IDENTIFIER""#).is_err());
+        assert!(Identifier::from_str(r#""""#).is_err());
+        assert!(Identifier::from_str(r#""UNTERMINATED"#).is_err());
+        assert!(Identifier::try_new_case_sensitive("t".repeat(65)).is_err());
+        assert!(Identifier::try_new_case_sensitive("t".repeat(64)).is_ok());
+    }
+
+    #[test]
+    fn identifiers_are_displayed_with_quotes_only_when_needed() {
+        let identifier = Identifier::try_new_case_sensitive("Good_Identifier").unwrap();
+        assert!(identifier.needs_quotes());
+        assert_eq!(identifier.to_string(), r#""Good_Identifier""#);
+        assert_eq!(
+            Identifier::from_str(&format!("{identifier}")).unwrap(),
+            identifier
+        );
+        let identifier = Identifier::try_new_case_sensitive("good_identifier").unwrap();
+        assert!(!identifier.needs_quotes());
+        assert_eq!(identifier.to_string(), "good_identifier");
+    }
+
+    #[test]
+    fn case_sensitive_identifiers_can_be_serialized_and_deserialized() {
+        let identifier = Identifier::try_new_case_sensitive("Good_Identifier").unwrap();
+        let serialized = serde_json::to_string(&identifier).unwrap();
+        assert_eq!(serialized, r#""\"Good_Identifier\"""#);
+        let deserialized: Identifier = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, identifier);
+    }
+
+    #[test]
+    fn identifier_policies_fold_or_keep_the_case_of_names() {
+        let folded = IdentifierPolicy::default()
+            .identifier("Good_Identifier")
+            .unwrap();
+        assert_eq!(folded.name(), "good_identifier");
+        let exact = IdentifierPolicy::Exact
+            .identifier("Good_Identifier")
+            .unwrap();
+        assert_eq!(exact.name(), "Good_Identifier");
+        let keyword = IdentifierPolicy::Exact.identifier("timestamp").unwrap();
+        assert_eq!(keyword.name(), "timestamp");
+        assert!(IdentifierPolicy::CaseInsensitive
+            .identifier("timestamp")
+            .is_err());
+        assert!(IdentifierPolicy::Exact
+            .identifier("BAD IDENTIFIER")
+            .is_err());
+    }
 }
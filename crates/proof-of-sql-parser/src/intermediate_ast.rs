@@ -63,7 +63,7 @@ impl AliasedResultExpr {
 }
 
 /// Representations of base queries
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum TableExpression {
     /// The row set of a given table; possibly providing an alias
     Named {
@@ -89,6 +89,9 @@ pub enum BinaryOperator {
     /// Numeric division
     Division,
 
+    /// Numeric remainder, whose sign is that of the dividend
+    Modulo,
+
     /// Logical And
     And,
 
@@ -140,6 +143,25 @@ impl std::fmt::Display for AggregationOperator {
     }
 }
 
+/// Values that are not part of the query text but are bound from the evaluation context
+/// when the query is proven
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ContextVariable {
+    /// The current time, i.e. `NOW()`
+    Now,
+    /// The current height of the chain, i.e. `BLOCK_HEIGHT()`
+    BlockHeight,
+}
+
+impl std::fmt::Display for ContextVariable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContextVariable::Now => write!(f, "now()"),
+            ContextVariable::BlockHeight => write!(f, "block_height()"),
+        }
+    }
+}
+
 /// Boolean Expressions
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Expression {
@@ -177,6 +199,30 @@ pub enum Expression {
         /// The expression to aggregate
         expr: Box<Expression>,
     },
+
+    /// A correlated subquery of the bounded form
+    /// `EXISTS (SELECT 1 FROM <table> WHERE <table>.<column> = <outer_table>.<outer_column>)`
+    Exists {
+        /// The table of the subquery
+        table: Box<TableExpression>,
+        /// The column of the subquery's table that is matched
+        column: Identifier,
+        /// The name of the outer table, which qualifies the outer column
+        outer_table: Identifier,
+        /// The column of the outer table that is matched
+        outer_column: Identifier,
+    },
+
+    /// A call of a scalar function, e.g. `time_bucket(60, ts)`
+    ///
+    /// Any name is accepted here. Functions are resolved against the registered ones when the
+    /// query is planned.
+    Function {
+        /// The name of the function
+        name: Identifier,
+        /// The arguments of the function
+        args: Vec<Expression>,
+    },
 }
 
 impl Expression {
@@ -316,6 +362,112 @@ pub struct Slice {
     pub offset_value: i64,
 }
 
+/// A column qualified by the name of its table e.g. `a.amount`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct QualifiedColumn {
+    /// The name of the table
+    pub table: Identifier,
+    /// The name of the column
+    pub column: Identifier,
+}
+
+/// An aggregation over a join e.g. `SUM(a.amount) AS total`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct JoinAggregation {
+    /// The aggregation, which is either `SUM` or `COUNT`
+    pub op: AggregationOperator,
+    /// The aggregated column, or `None` for `COUNT(*)`
+    pub column: Option<QualifiedColumn>,
+    /// The alias of the aggregation
+    pub alias: Identifier,
+}
+
+/// A predicate of a join on the columns of one of its tables e.g. `b.flag = true`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct JoinPredicate {
+    /// The name of the table whose column the predicate compares
+    pub table: Identifier,
+    /// The predicate, in which the column is not qualified
+    pub expr: Box<Expression>,
+}
+
+impl JoinPredicate {
+    /// Compare a qualified column with a literal, negating the comparison if `negated` is set.
+    pub(crate) fn comparison(
+        column: QualifiedColumn,
+        op: BinaryOperator,
+        negated: bool,
+        literal: Literal,
+    ) -> Self {
+        let expr = Box::new(Expression::Binary {
+            op,
+            left: Box::new(Expression::Column(column.column)),
+            right: Box::new(Expression::Literal(literal)),
+        });
+        Self {
+            table: column.table,
+            expr: if negated {
+                Box::new(Expression::Unary {
+                    op: UnaryOperator::Not,
+                    expr,
+                })
+            } else {
+                expr
+            },
+        }
+    }
+}
+
+/// A column of a lookup join, qualified by its table, e.g. `v.label AS label`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LookupColumn {
+    /// The column
+    pub column: QualifiedColumn,
+    /// The alias of the column in the result
+    pub alias: Identifier,
+}
+
+/// A table whose rows are given in the query, e.g. `(VALUES (1, 'a'), (2, 'b')) AS v(k, label)`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct InlineTable {
+    /// The name of the table, which qualifies its columns
+    pub alias: Identifier,
+    /// The names of the columns
+    pub columns: Vec<Identifier>,
+    /// The rows, each of which has a value for every column
+    pub rows: Vec<Vec<Literal>>,
+}
+
+impl InlineTable {
+    /// Check that the columns have distinct names and that every row has a value for each of them.
+    pub(crate) fn try_new(
+        alias: Identifier,
+        columns: Vec<Identifier>,
+        rows: Vec<Vec<Literal>>,
+    ) -> Result<Self, &'static str> {
+        if columns
+            .iter()
+            .enumerate()
+            .any(|(index, column)| columns[..index].contains(column))
+        {
+            return Err("the columns of a VALUES table must have distinct names");
+        }
+        if rows.iter().any(|row| row.len() != columns.len()) {
+            return Err("every row of a VALUES table must have a value for each of its columns");
+        }
+        Ok(Self {
+            alias,
+            columns,
+            rows,
+        })
+    }
+
+    /// Returns the index of a column.
+    pub fn column_index(&self, column: Identifier) -> Option<usize> {
+        self.columns.iter().position(|&c| c == column)
+    }
+}
+
 /// Literal values
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Literal {
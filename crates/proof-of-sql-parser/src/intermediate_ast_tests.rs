@@ -1441,3 +1441,219 @@ fn we_can_use_aggregation_inside_another_aggregation() {
     );
     assert_eq!(ast, expected_ast);
 }
+
+#[test]
+fn we_can_parse_context_variables_as_calls_of_functions_without_arguments() {
+    let ast = "select a from tab where expiry >= NOW() and height <= block_height()"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                ge(col("expiry"), func("now", vec![])),
+                le(col("height"), func("block_height", vec![])),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_the_names_of_context_variables_as_column_names() {
+    let ast = "select now, block_height from tab where now >= block_height"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["now", "block_height"]),
+            tab(None, "tab"),
+            ge(col("now"), col("block_height")),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_function_calls() {
+    let ast = "select time_bucket(60, ts) as b from tab where Clamp(a + 1, 0, 10) = f()"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(func("time_bucket", vec![lit(60), col("ts")]), "b")],
+            tab(None, "tab"),
+            equal(
+                func("clamp", vec![add(col("a"), lit(1)), lit(0), lit(10)]),
+                func("f", vec![]),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_the_modulo_operator_with_the_precedence_of_multiplication() {
+    let ast = "select a % 7 as r from tab where b - a % 3 * 2 = abs(c % -4)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(modulo(col("a"), lit(7)), "r")],
+            tab(None, "tab"),
+            equal(
+                sub(col("b"), mul(modulo(col("a"), lit(3)), lit(2))),
+                func("abs", vec![modulo(col("c"), lit(-4))]),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_function_calls_with_invalid_arguments() {
+    for sql in [
+        "select f(,) as b from tab",
+        "select f(a,) as b from tab",
+        "select f(*) as b from tab",
+        "select tab.f(a) as b from tab",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
+
+#[test]
+fn we_can_parse_bounded_exists_subqueries() {
+    let ast = "select a from tab where exists (select 1 from sxt.other where other.k = tab.a) \
+        and not EXISTS (SELECT 1 FROM other WHERE tab.b = other.k)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                exists(tab(Some("sxt"), "other"), "k", "tab", "a"),
+                not(exists(tab(None, "other"), "k", "tab", "b")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_exists_subqueries_outside_of_the_bounded_form() {
+    for sql in [
+        "select a from tab where exists (select 2 from other where other.k = tab.a)",
+        "select a from tab where exists (select k from other where other.k = tab.a)",
+        "select a from tab where exists (select 1 from other where other.k = other.j)",
+        "select a from tab where exists (select 1 from other where tab.k = tab.a)",
+        "select a from tab where exists (select 1 from other where other.k >= tab.a)",
+        "select a from tab where exists (select 1 from other where k = a)",
+        "select a from tab where exists (select 1 from other)",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
+
+#[test]
+fn we_can_use_exists_as_an_identifier() {
+    let ast = "select exists from exists \
+        where exists = 1 and exists (select 1 from other where other.k = exists.exists)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["exists"]),
+            tab(None, "exists"),
+            and(
+                equal(col("exists"), lit(1)),
+                exists(tab(None, "other"), "k", "exists", "exists"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_queries_of_a_table_snapshot() {
+    let ast = "SELECT a FROM sxt.tab AS OF 3 WHERE b = 4 ORDER BY a LIMIT 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let mut expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(Some("sxt"), "tab"),
+            equal(col("b"), lit(4)),
+            vec![],
+        ),
+        order("a", Asc),
+        slice(2, 0),
+    );
+    expected_ast.as_of = Some(3);
+    assert_eq!(ast, expected_ast);
+    assert_eq!(
+        "select a from tab as of 0"
+            .parse::<SelectStatement>()
+            .unwrap()
+            .as_of,
+        Some(0)
+    );
+    assert_eq!(
+        "select a from tab"
+            .parse::<SelectStatement>()
+            .unwrap()
+            .as_of,
+        None
+    );
+}
+
+#[test]
+fn we_can_use_of_as_an_identifier() {
+    let ast = "select of as of from of as of 3 where of = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let mut expected_ast = select(
+        query(
+            vec![col_res(col("of"), "of")],
+            tab(None, "of"),
+            equal(col("of"), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    expected_ast.as_of = Some(3);
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_invalid_snapshots() {
+    for sql in [
+        "select a from tab as of -1",
+        "select a from tab as of 1.5",
+        "select a from tab as of",
+        "select a from tab where a = 1 as of 3",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err(), "{sql}");
+    }
+}
@@ -0,0 +1,609 @@
+use super::intermediate_ast::{
+    InlineTable, JoinAggregation, JoinPredicate, LookupColumn, QualifiedColumn, TableExpression,
+};
+use crate::{
+    sql::{InListJoinStatementParser, JoinAggregateStatementParser, LookupJoinStatementParser},
+    Identifier, ParseError, ParseResult, ResourceId,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Representation of an aggregation over the inner join of two tables on a single key, of the
+/// form
+/// ```ignore
+///     SELECT <aggregations> FROM <left> JOIN <right> ON <left>.<key> = <right>.<key>
+///     WHERE <predicate1> AND ... AND <predicateN>
+/// ```
+/// where each aggregation is either `SUM(<table>.<column>)` or `COUNT(*)`, and each predicate
+/// compares a column of one of the tables with a literal, e.g.
+/// ```ignore
+///     SELECT SUM(a.amount) FROM a JOIN b ON a.k = b.k WHERE b.flag = true
+/// ```
+///
+/// Columns are qualified by the names of their tables, so the tables must have different names.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct JoinAggregateStatement {
+    /// The aggregations of the result, in order
+    pub aggregations: Vec<JoinAggregation>,
+    /// The left table of the join
+    pub left: Box<TableExpression>,
+    /// The right table of the join
+    pub right: Box<TableExpression>,
+    /// The column of the left table that the tables are joined on
+    pub left_key: Identifier,
+    /// The column of the right table that the tables are joined on
+    pub right_key: Identifier,
+    /// The predicates of the `WHERE` clause, all of which a pair of rows has to satisfy
+    pub predicates: Vec<JoinPredicate>,
+}
+
+impl JoinAggregateStatement {
+    /// Check that the columns of a parsed join refer to its tables, and orient the `ON` clause.
+    pub(crate) fn try_new(
+        aggregations: Vec<JoinAggregation>,
+        left: Box<TableExpression>,
+        right: Box<TableExpression>,
+        on: (QualifiedColumn, QualifiedColumn),
+        predicates: Vec<JoinPredicate>,
+    ) -> Result<Self, &'static str> {
+        let left_table = table_name(&left);
+        let right_table = table_name(&right);
+        if left_table == right_table {
+            return Err("the tables of a join must have different names");
+        }
+        let (left_key, right_key) = match on {
+            (l, r) if l.table == left_table && r.table == right_table => (l.column, r.column),
+            (r, l) if l.table == left_table && r.table == right_table => (l.column, r.column),
+            _ => return Err("a join must equate a column of each of its tables"),
+        };
+        let is_joined = |table: Identifier| table == left_table || table == right_table;
+        if !aggregations
+            .iter()
+            .filter_map(|aggregation| aggregation.column)
+            .all(|column| is_joined(column.table))
+            || !predicates
+                .iter()
+                .all(|predicate| is_joined(predicate.table))
+        {
+            return Err("the columns of a join must be qualified by one of its tables");
+        }
+        Ok(Self {
+            aggregations,
+            left,
+            right,
+            left_key,
+            right_key,
+            predicates,
+        })
+    }
+
+    /// Returns the name of the left table, which qualifies its columns.
+    pub fn left_table(&self) -> Identifier {
+        table_name(&self.left)
+    }
+
+    /// Returns the name of the right table, which qualifies its columns.
+    pub fn right_table(&self) -> Identifier {
+        table_name(&self.right)
+    }
+
+    /// Returns the left and the right table, with `default_schema` used for a table without a
+    /// schema. See [`crate::SelectStatement::get_table_references`].
+    pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
+        [&self.left, &self.right]
+            .into_iter()
+            .map(|table| match table.as_ref() {
+                TableExpression::Named { table, schema } => {
+                    ResourceId::new(schema.unwrap_or(default_schema), *table)
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for JoinAggregateStatement {
+    type Err = ParseError;
+
+    fn from_str(query: &str) -> ParseResult<Self> {
+        JoinAggregateStatementParser::new()
+            .parse(query)
+            .map_err(|e| ParseError::QueryParseError(e.to_string()))
+    }
+}
+
+/// Representation of the inner join of a table with a table whose rows are given in the query, of
+/// the form
+/// ```ignore
+///     SELECT <columns> FROM <table>
+///     JOIN (VALUES (<value>, ...), ...) AS <alias>(<column>, ...) ON <table>.<key> = <alias>.<key>
+///     WHERE <predicate1> AND ... AND <predicateN>
+/// ```
+/// where each column is a column of either table and each predicate compares a column of the
+/// table with a literal, e.g.
+/// ```ignore
+///     SELECT t.amount, v.label FROM t JOIN (VALUES (1, 'a'), (2, 'b')) AS v(k, label)
+///     ON t.k = v.k WHERE t.amount > 5
+/// ```
+///
+/// Columns are qualified by the name of the table or the alias of the `VALUES` table, so the two
+/// must differ.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct LookupJoinStatement {
+    /// The columns of the result, in order
+    pub columns: Vec<LookupColumn>,
+    /// The table that is joined
+    pub table: Box<TableExpression>,
+    /// The `VALUES` table that the table is joined with
+    pub values: InlineTable,
+    /// The column of the table that the tables are joined on
+    pub table_key: Identifier,
+    /// The column of the `VALUES` table that the tables are joined on
+    pub values_key: Identifier,
+    /// The predicates of the `WHERE` clause, all of which a row of the table has to satisfy
+    pub predicates: Vec<JoinPredicate>,
+}
+
+impl LookupJoinStatement {
+    /// Check that the columns of a parsed lookup join refer to its tables, and orient the `ON`
+    /// clause.
+    pub(crate) fn try_new(
+        columns: Vec<LookupColumn>,
+        table: Box<TableExpression>,
+        values: InlineTable,
+        on: (QualifiedColumn, QualifiedColumn),
+        predicates: Vec<JoinPredicate>,
+    ) -> Result<Self, &'static str> {
+        let table_name = table_name(&table);
+        if table_name == values.alias {
+            return Err("the tables of a join must have different names");
+        }
+        let (table_key, values_key) = match on {
+            (t, v) if t.table == table_name && v.table == values.alias => (t.column, v.column),
+            (v, t) if t.table == table_name && v.table == values.alias => (t.column, v.column),
+            _ => return Err("a join must equate a column of each of its tables"),
+        };
+        let is_values_column =
+            |column: &QualifiedColumn| values.column_index(column.column).is_some();
+        if values.column_index(values_key).is_none()
+            || !columns.iter().all(|c| {
+                c.column.table == table_name
+                    || (c.column.table == values.alias && is_values_column(&c.column))
+            })
+        {
+            return Err("the columns of a join must be qualified by one of its tables");
+        }
+        if !predicates
+            .iter()
+            .all(|predicate| predicate.table == table_name)
+        {
+            return Err("the predicates of a lookup join can only filter its table");
+        }
+        Ok(Self {
+            columns,
+            table,
+            values,
+            table_key,
+            values_key,
+            predicates,
+        })
+    }
+
+    /// Returns the name of the table, which qualifies its columns.
+    pub fn table_name(&self) -> Identifier {
+        table_name(&self.table)
+    }
+
+    /// Returns the table, with `default_schema` used if it has no schema.
+    /// See [`crate::SelectStatement::get_table_references`].
+    pub fn get_table_reference(&self, default_schema: Identifier) -> ResourceId {
+        match self.table.as_ref() {
+            TableExpression::Named { table, schema } => {
+                ResourceId::new(schema.unwrap_or(default_schema), *table)
+            }
+        }
+    }
+}
+
+impl FromStr for LookupJoinStatement {
+    type Err = ParseError;
+
+    fn from_str(query: &str) -> ParseResult<Self> {
+        LookupJoinStatementParser::new()
+            .parse(query)
+            .map_err(|e| ParseError::QueryParseError(e.to_string()))
+    }
+}
+
+/// Representation of the inner join of a table with a small table on a single key, of the form
+/// ```ignore
+///     SELECT <columns> FROM <probe> JOIN <build> ON <probe>.<key> = <build>.<key>
+///     WHERE <predicate1> AND ... AND <predicateN>
+/// ```
+/// where each column is a column of either table and each predicate compares a column of one of
+/// the tables with a literal, e.g.
+/// ```ignore
+///     SELECT sales.amount, dim.label FROM sales JOIN dim ON sales.k = dim.k WHERE dim.active = true
+/// ```
+///
+/// The table after `JOIN` is the build side, which is expected to be the small one, such as a
+/// dimension table. The keys of its rows are turned into an `IN` list that the probe side is
+/// filtered with, so its rows are revealed to the verifier. Columns are qualified by the names of their tables, so the tables must have
+/// different names.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct InListJoinStatement {
+    /// The columns of the result, in order
+    pub columns: Vec<LookupColumn>,
+    /// The table whose rows probe the build side
+    pub probe: Box<TableExpression>,
+    /// The table that the probe side is joined with
+    pub build: Box<TableExpression>,
+    /// The column of the probe side that the tables are joined on
+    pub probe_key: Identifier,
+    /// The column of the build side that the tables are joined on
+    pub build_key: Identifier,
+    /// The predicates of the `WHERE` clause, all of which a pair of rows has to satisfy
+    pub predicates: Vec<JoinPredicate>,
+}
+
+impl InListJoinStatement {
+    /// Check that the columns of a parsed IN-list join refer to its tables, and orient the `ON`
+    /// clause.
+    pub(crate) fn try_new(
+        columns: Vec<LookupColumn>,
+        probe: Box<TableExpression>,
+        build: Box<TableExpression>,
+        on: (QualifiedColumn, QualifiedColumn),
+        predicates: Vec<JoinPredicate>,
+    ) -> Result<Self, &'static str> {
+        let probe_table = table_name(&probe);
+        let build_table = table_name(&build);
+        if probe_table == build_table {
+            return Err("the tables of a join must have different names");
+        }
+        let (probe_key, build_key) = match on {
+            (p, b) if p.table == probe_table && b.table == build_table => (p.column, b.column),
+            (b, p) if p.table == probe_table && b.table == build_table => (p.column, b.column),
+            _ => return Err("a join must equate a column of each of its tables"),
+        };
+        let is_joined = |table: Identifier| table == probe_table || table == build_table;
+        if !columns.iter().all(|column| is_joined(column.column.table))
+            || !predicates
+                .iter()
+                .all(|predicate| is_joined(predicate.table))
+        {
+            return Err("the columns of a join must be qualified by one of its tables");
+        }
+        Ok(Self {
+            columns,
+            probe,
+            build,
+            probe_key,
+            build_key,
+            predicates,
+        })
+    }
+
+    /// Returns the name of the probe side, which qualifies its columns.
+    pub fn probe_table(&self) -> Identifier {
+        table_name(&self.probe)
+    }
+
+    /// Returns the name of the build side, which qualifies its columns.
+    pub fn build_table(&self) -> Identifier {
+        table_name(&self.build)
+    }
+
+    /// Returns the probe and the build side, with `default_schema` used for a table without a
+    /// schema. See [`crate::SelectStatement::get_table_references`].
+    pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
+        [&self.probe, &self.build]
+            .into_iter()
+            .map(|table| match table.as_ref() {
+                TableExpression::Named { table, schema } => {
+                    ResourceId::new(schema.unwrap_or(default_schema), *table)
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for InListJoinStatement {
+    type Err = ParseError;
+
+    fn from_str(query: &str) -> ParseResult<Self> {
+        InListJoinStatementParser::new()
+            .parse(query)
+            .map_err(|e| ParseError::QueryParseError(e.to_string()))
+    }
+}
+
+fn table_name(table: &TableExpression) -> Identifier {
+    match table {
+        TableExpression::Named { table, .. } => *table,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        intermediate_ast::{AggregationOperator, Literal},
+        utility::*,
+    };
+
+    fn qualified(table: &str, column: &str) -> QualifiedColumn {
+        QualifiedColumn {
+            table: ident(table),
+            column: ident(column),
+        }
+    }
+
+    #[test]
+    fn we_can_parse_an_aggregation_over_a_join() {
+        let ast: JoinAggregateStatement =
+            "SELECT SUM(a.amount), count(*) AS n FROM sxt.a INNER JOIN b ON b.k = a.k \
+             WHERE b.flag = true AND a.amount > 5"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            ast,
+            JoinAggregateStatement {
+                aggregations: vec![
+                    JoinAggregation {
+                        op: AggregationOperator::Sum,
+                        column: Some(QualifiedColumn {
+                            table: ident("a"),
+                            column: ident("amount"),
+                        }),
+                        alias: ident("__sum__"),
+                    },
+                    JoinAggregation {
+                        op: AggregationOperator::Count,
+                        column: None,
+                        alias: ident("n"),
+                    },
+                ],
+                left: tab(Some("sxt"), "a"),
+                right: tab(None, "b"),
+                left_key: ident("k"),
+                right_key: ident("k"),
+                predicates: vec![
+                    JoinPredicate {
+                        table: ident("b"),
+                        expr: equal(col("flag"), lit(true)),
+                    },
+                    JoinPredicate {
+                        table: ident("a"),
+                        expr: not(le(col("amount"), lit(5))),
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            ast.get_table_references(ident("eth")),
+            [
+                ResourceId::try_new("sxt", "a").unwrap(),
+                ResourceId::try_new("eth", "b").unwrap()
+            ]
+        );
+        assert_eq!(
+            ast.predicates[1],
+            JoinPredicate::comparison(
+                QualifiedColumn {
+                    table: ident("a"),
+                    column: ident("amount"),
+                },
+                crate::intermediate_ast::BinaryOperator::LessThanOrEqual,
+                true,
+                Literal::BigInt(5),
+            )
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_joins_outside_of_the_supported_form() {
+        for query in [
+            "select sum(a.x) from a join a on a.k = a.k",
+            "select sum(a.x) from a join b on a.k = a.j",
+            "select sum(a.x) from a join b on a.k = c.k",
+            "select sum(c.x) from a join b on a.k = b.k",
+            "select sum(a.x) from a join b on a.k = b.k where c.flag = true",
+            "select sum(a.x) from a join b on a.k >= b.k",
+            "select max(a.x) from a join b on a.k = b.k",
+            "select a.x from a join b on a.k = b.k",
+            "select sum(a.x) from a join b on a.k = b.k where a.x = b.y",
+            "select sum(a.x) from a join b on a.k = b.k where a.x = 1 or b.y = 2",
+            "select sum(x) from a join b on a.k = b.k",
+        ] {
+            assert!(query.parse::<JoinAggregateStatement>().is_err(), "{query}");
+        }
+    }
+
+    #[test]
+    fn we_can_use_the_keywords_of_joins_as_identifiers() {
+        let ast: JoinAggregateStatement =
+            "select sum(inner.on) as join from inner join on on on.join = inner.join"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            ast,
+            JoinAggregateStatement {
+                aggregations: vec![JoinAggregation {
+                    op: AggregationOperator::Sum,
+                    column: Some(qualified("inner", "on")),
+                    alias: ident("join"),
+                }],
+                left: tab(None, "inner"),
+                right: tab(None, "on"),
+                left_key: ident("join"),
+                right_key: ident("join"),
+                predicates: vec![],
+            }
+        );
+        assert_eq!(
+            "select inner, join from on where on = 1"
+                .parse::<crate::SelectStatement>()
+                .unwrap(),
+            select(
+                query(
+                    cols_res(&["inner", "join"]),
+                    tab(None, "on"),
+                    equal(col("on"), lit(1)),
+                    vec![],
+                ),
+                vec![],
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn we_can_parse_a_lookup_join_with_a_values_table() {
+        let ast: LookupJoinStatement = "SELECT t.amount, v.label AS name FROM sxt.t \
+             JOIN (VALUES (1, 'a'), (2, 'b')) AS v(k, label) ON v.k = t.key \
+             WHERE t.amount >= 5"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            ast,
+            LookupJoinStatement {
+                columns: vec![
+                    LookupColumn {
+                        column: qualified("t", "amount"),
+                        alias: ident("amount"),
+                    },
+                    LookupColumn {
+                        column: qualified("v", "label"),
+                        alias: ident("name"),
+                    },
+                ],
+                table: tab(Some("sxt"), "t"),
+                values: InlineTable {
+                    alias: ident("v"),
+                    columns: vec![ident("k"), ident("label")],
+                    rows: vec![
+                        vec![Literal::BigInt(1), Literal::VarChar("a".to_string())],
+                        vec![Literal::BigInt(2), Literal::VarChar("b".to_string())],
+                    ],
+                },
+                table_key: ident("key"),
+                values_key: ident("k"),
+                predicates: vec![JoinPredicate {
+                    table: ident("t"),
+                    expr: ge(col("amount"), lit(5)),
+                }],
+            }
+        );
+        assert_eq!(ast.table_name(), ident("t"));
+        assert_eq!(
+            ast.get_table_reference(ident("eth")),
+            ResourceId::try_new("sxt", "t").unwrap()
+        );
+    }
+
+    #[test]
+    fn we_can_use_values_as_an_identifier_in_a_lookup_join() {
+        let ast: LookupJoinStatement = "select values.values from values \
+             join (values (1)) as v(values) on v.values = values.values"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            ast,
+            LookupJoinStatement {
+                columns: vec![LookupColumn {
+                    column: qualified("values", "values"),
+                    alias: ident("values"),
+                }],
+                table: tab(None, "values"),
+                values: InlineTable {
+                    alias: ident("v"),
+                    columns: vec![ident("values")],
+                    rows: vec![vec![Literal::BigInt(1)]],
+                },
+                table_key: ident("values"),
+                values_key: ident("values"),
+                predicates: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_lookup_joins_outside_of_the_supported_form() {
+        for query in [
+            "select t.x from t join (values (1)) as t(k) on t.k = t.k",
+            "select t.x from t join (values (1)) as v(k) on t.k = t.j",
+            "select t.x from t join (values (1)) as v(k) on t.k = v.j",
+            "select v.y from t join (values (1)) as v(k) on t.k = v.k",
+            "select c.x from t join (values (1)) as v(k) on t.k = v.k",
+            "select t.x from t join (values (1)) as v(k) on t.k = v.k where v.k = 1",
+            "select t.x from t join (values (1, 2), (3)) as v(k, y) on t.k = v.k",
+            "select t.x from t join (values (1, 2)) as v(k, k) on t.k = v.k",
+            "select t.x from t join (values) as v(k) on t.k = v.k",
+            "select sum(t.x) from t join (values (1)) as v(k) on t.k = v.k",
+        ] {
+            assert!(query.parse::<LookupJoinStatement>().is_err(), "{query}");
+        }
+    }
+
+    #[test]
+    fn we_can_parse_an_in_list_join_with_a_build_side() {
+        let ast: InListJoinStatement = "SELECT s.amount, d.label AS name FROM sxt.s \
+             JOIN d ON d.k = s.key WHERE d.active = true AND s.amount >= 5"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            ast,
+            InListJoinStatement {
+                columns: vec![
+                    LookupColumn {
+                        column: qualified("s", "amount"),
+                        alias: ident("amount"),
+                    },
+                    LookupColumn {
+                        column: qualified("d", "label"),
+                        alias: ident("name"),
+                    },
+                ],
+                probe: tab(Some("sxt"), "s"),
+                build: tab(None, "d"),
+                probe_key: ident("key"),
+                build_key: ident("k"),
+                predicates: vec![
+                    JoinPredicate {
+                        table: ident("d"),
+                        expr: equal(col("active"), lit(true)),
+                    },
+                    JoinPredicate {
+                        table: ident("s"),
+                        expr: ge(col("amount"), lit(5)),
+                    },
+                ],
+            }
+        );
+        assert_eq!(ast.probe_table(), ident("s"));
+        assert_eq!(ast.build_table(), ident("d"));
+        assert_eq!(
+            ast.get_table_references(ident("eth")),
+            [
+                ResourceId::try_new("sxt", "s").unwrap(),
+                ResourceId::try_new("eth", "d").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_in_list_joins_outside_of_the_supported_form() {
+        for query in [
+            "select a.x from a join a on a.k = a.k",
+            "select a.x from a join b on a.k = a.j",
+            "select a.x from a join b on a.k = c.k",
+            "select c.x from a join b on a.k = b.k",
+            "select a.x from a join b on a.k = b.k where c.flag = true",
+            "select a.x from a join b on a.k >= b.k",
+            "select sum(a.x) from a join b on a.k = b.k",
+            "select x from a join b on a.k = b.k",
+        ] {
+            assert!(query.parse::<InListJoinStatement>().is_err(), "{query}");
+        }
+    }
+}
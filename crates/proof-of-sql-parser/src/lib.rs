@@ -18,17 +18,28 @@ pub mod utility;
 pub(crate) mod select_statement;
 pub use select_statement::SelectStatement;
 
+pub(crate) mod join_statement;
+pub use join_statement::{InListJoinStatement, JoinAggregateStatement, LookupJoinStatement};
+
 /// Error definitions for proof-of-sql-parser
 pub mod error;
-pub use error::ParseError;
 pub(crate) use error::ParseResult;
+pub use error::{ParseError, SyntaxError};
 
 pub(crate) mod identifier;
-pub use identifier::Identifier;
+pub use identifier::{Identifier, IdentifierPolicy};
 
 pub mod resource_id;
 pub use resource_id::ResourceId;
 
+/// Module for lowering statements parsed by `sqlparser` into the intermediate AST.
+#[cfg(feature = "sqlparser")]
+pub mod sqlparser_adapter;
+#[cfg(all(test, feature = "sqlparser"))]
+mod sqlparser_adapter_tests;
+#[cfg(feature = "sqlparser")]
+pub use sqlparser;
+
 // lalrpop-generated code is not clippy-compliant
 lalrpop_mod!(#[allow(clippy::all, missing_docs)] pub sql);
 
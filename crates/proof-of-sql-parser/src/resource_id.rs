@@ -91,10 +91,9 @@ impl FromStr for ResourceId {
             .parse(string)
             .map_err(|e| ParseError::ResourceIdParseError(format!("{:?}", e)))?;
 
-        // use unsafe `Identifier::new` to prevent double parsing the ids
         Ok(ResourceId {
-            schema: Identifier::new(schema),
-            object_name: Identifier::new(object_name),
+            schema,
+            object_name,
         })
     }
 }
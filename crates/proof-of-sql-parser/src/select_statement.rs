@@ -1,5 +1,7 @@
 use super::intermediate_ast::{OrderBy, SetExpression, Slice, TableExpression};
-use crate::{sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId};
+use crate::{
+    sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId, SyntaxError,
+};
 use serde::{Deserialize, Serialize};
 use std::{fmt, ops::Deref, str::FromStr};
 
@@ -15,14 +17,19 @@ pub struct SelectStatement {
     /// an optional slice clause, which can restrict the rows returned to a window within the
     /// set of rows as generated by `expr` and `order_by`.
     pub slice: Option<Slice>,
+
+    /// if present, the snapshot of the table that is read instead of its latest version,
+    /// e.g. `3` in `SELECT a FROM t AS OF 3`
+    #[serde(default)]
+    pub as_of: Option<u64>,
 }
 
 impl fmt::Debug for SelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "SelectStatement \n[{:#?},\n{:#?},\n{:#?}\n]",
-            self.expr, self.order_by, self.slice
+            "SelectStatement \n[{:#?},\n{:#?},\n{:#?},\n{:#?}\n]",
+            self.expr, self.order_by, self.slice, self.as_of
         )
     }
 }
@@ -53,6 +60,15 @@ impl SelectStatement {
             } => convert_table_expr_to_resource_id_vector(&from[..], default_schema),
         }
     }
+
+    /// Parse a query, reporting where in the query parsing failed and what was expected there.
+    ///
+    /// This is the same as [SelectStatement::from_str], but with a more detailed error.
+    pub fn try_parse(query: &str) -> Result<Self, SyntaxError> {
+        SelectStatementParser::new()
+            .parse(query)
+            .map_err(|e| SyntaxError::from_lalrpop(query, e))
+    }
 }
 
 impl FromStr for SelectStatement {
@@ -76,12 +92,7 @@ fn convert_table_expr_to_resource_id_vector(
 
         match table_ref {
             TableExpression::Named { table, schema } => {
-                let schema = schema
-                    .as_ref()
-                    .map(|schema| schema.as_str())
-                    .unwrap_or_else(|| default_schema.name());
-
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+                tables.push(ResourceId::new(schema.unwrap_or(default_schema), *table));
             }
         }
     }
@@ -129,4 +140,40 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_table_references_with_quoted_identifiers() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse(r#"SELECT A FROM "Schema"."Tab" WHERE C = 3"#)
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(ref_tables, [r#""Schema"."Tab""#.parse().unwrap()]);
+    }
+
+    #[test]
+    fn we_can_get_the_position_and_expected_tokens_of_a_syntax_error() {
+        let error = SelectStatement::try_parse("select a b c from tab").unwrap_err();
+        assert_eq!(error.span, 11..12);
+        assert!(error.expected.contains(&"from".to_string()));
+        assert_eq!(error.message, "unexpected token `c`");
+
+        let error = SelectStatement::try_parse("select a from").unwrap_err();
+        assert_eq!(error.span, 13..13);
+        assert_eq!(error.message, "unexpected end of query");
+
+        let error = SelectStatement::try_parse("select a from tab #").unwrap_err();
+        assert_eq!(error.span, 18..19);
+        assert!(error.expected.is_empty());
+    }
+
+    #[test]
+    fn we_can_parse_valid_queries_with_try_parse() {
+        let query = "SELECT a FROM tab WHERE b = 3";
+        assert_eq!(
+            SelectStatement::try_parse(query).unwrap(),
+            query.parse().unwrap()
+        );
+    }
 }
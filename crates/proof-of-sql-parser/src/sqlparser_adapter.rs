@@ -0,0 +1,475 @@
+use crate::{
+    intermediate_ast::{
+        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
+        OrderByDirection, SelectResultExpr, SetExpression, Slice, TableExpression, UnaryOperator,
+    },
+    intermediate_decimal::IntermediateDecimal,
+    posql_time::PoSQLTimestamp,
+    Identifier, SelectStatement,
+};
+use sqlparser::{
+    ast::{
+        self, DataType, Expr, FunctionArg, FunctionArgExpr, GroupByExpr, Ident, ObjectName, Query,
+        Select, SelectItem, SetExpr, Statement, TableFactor, Value, WildcardAdditionalOptions,
+    },
+    dialect::GenericDialect,
+    parser::Parser,
+};
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+/// Errors encountered when lowering a statement parsed by `sqlparser` into the intermediate AST
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum SqlParserConversionError {
+    #[error("Unable to parse query: {0}")]
+    /// `sqlparser` cannot parse the query
+    ParserError(String),
+    #[error("Expected exactly one statement but found {0}")]
+    /// The query does not consist of exactly one statement
+    StatementCount(usize),
+    #[error("Unsupported SQL: {0}")]
+    /// The statement uses SQL that Proof of SQL does not support
+    Unsupported(String),
+    #[error("Invalid identifier: {0}")]
+    /// An identifier is not a valid Proof of SQL identifier, e.g. because it is a keyword
+    InvalidIdentifier(String),
+    #[error("Invalid literal: {0}")]
+    /// A literal is out of range or malformed
+    InvalidLiteral(String),
+}
+
+/// Result of lowering a statement parsed by `sqlparser` into the intermediate AST
+pub type SqlParserConversionResult<T> = std::result::Result<T, SqlParserConversionError>;
+
+fn unsupported<T>(sql: impl Display) -> SqlParserConversionResult<T> {
+    Err(SqlParserConversionError::Unsupported(sql.to_string()))
+}
+
+/// Parse a query with the generic dialect of `sqlparser` and lower it into a [SelectStatement].
+///
+/// This accepts the same queries as [SelectStatement::from_str], but integrators that already
+/// use `sqlparser` can instead lower their parsed statements with [SelectStatement::try_from].
+pub fn parse_generic_select_statement(sql: &str) -> SqlParserConversionResult<SelectStatement> {
+    let statements = Parser::parse_sql(&GenericDialect {}, sql)
+        .map_err(|e| SqlParserConversionError::ParserError(e.to_string()))?;
+    match statements.as_slice() {
+        [statement] => statement.try_into(),
+        _ => Err(SqlParserConversionError::StatementCount(statements.len())),
+    }
+}
+
+/// Lowers the subset of `sqlparser` statements that the Proof of SQL grammar supports.
+///
+/// Comparisons such as `>` and `!=` are lowered the same way the Proof of SQL grammar lowers
+/// them, so both parsers produce the same intermediate AST for the same query.
+impl TryFrom<&Statement> for SelectStatement {
+    type Error = SqlParserConversionError;
+
+    fn try_from(statement: &Statement) -> SqlParserConversionResult<Self> {
+        match statement {
+            Statement::Query(query) => lower_query(query),
+            _ => unsupported(statement),
+        }
+    }
+}
+
+fn lower_query(query: &Query) -> SqlParserConversionResult<SelectStatement> {
+    if query.with.is_some() || query.fetch.is_some() || !query.locks.is_empty() {
+        return unsupported(query);
+    }
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return unsupported(&query.body);
+    };
+    Ok(SelectStatement {
+        expr: Box::new(lower_select(select)?),
+        order_by: query
+            .order_by
+            .iter()
+            .map(lower_order_by)
+            .collect::<SqlParserConversionResult<_>>()?,
+        slice: lower_slice(
+            query.limit.as_ref(),
+            query.offset.as_ref().map(|o| &o.value),
+        )?,
+        as_of: None,
+    })
+}
+
+fn lower_select(select: &Select) -> SqlParserConversionResult<SetExpression> {
+    if select.distinct.is_some()
+        || select.top.is_some()
+        || select.into.is_some()
+        || !select.lateral_views.is_empty()
+        || select.having.is_some()
+        || select.qualify.is_some()
+        || !select.cluster_by.is_empty()
+        || !select.distribute_by.is_empty()
+        || !select.sort_by.is_empty()
+    {
+        return unsupported(select);
+    }
+    let from = match select.from.as_slice() {
+        [table] if table.joins.is_empty() => vec![lower_table_factor(&table.relation)?],
+        _ => return unsupported(select),
+    };
+    let group_by = match &select.group_by {
+        GroupByExpr::Expressions(exprs) => exprs
+            .iter()
+            .map(|expr| match expr {
+                Expr::Identifier(ident) => lower_identifier(ident),
+                _ => unsupported(expr),
+            })
+            .collect::<SqlParserConversionResult<_>>()?,
+        GroupByExpr::All => return unsupported(&select.group_by),
+    };
+    Ok(SetExpression::Query {
+        result_exprs: select
+            .projection
+            .iter()
+            .map(lower_select_item)
+            .collect::<SqlParserConversionResult<_>>()?,
+        from,
+        where_expr: select.selection.as_ref().map(lower_expr).transpose()?,
+        group_by,
+    })
+}
+
+fn lower_table_factor(relation: &TableFactor) -> SqlParserConversionResult<Box<TableExpression>> {
+    let TableFactor::Table {
+        name: ObjectName(idents),
+        alias: None,
+        args: None,
+        ..
+    } = relation
+    else {
+        return unsupported(relation);
+    };
+    let (schema, table) = match idents.as_slice() {
+        [table] => (None, table),
+        [schema, table] => (Some(lower_identifier(schema)?), table),
+        _ => return unsupported(relation),
+    };
+    Ok(Box::new(TableExpression::Named {
+        table: lower_identifier(table)?,
+        schema,
+    }))
+}
+
+fn lower_select_item(item: &SelectItem) -> SqlParserConversionResult<SelectResultExpr> {
+    let (expr, alias) = match item {
+        SelectItem::Wildcard(options) if *options == WildcardAdditionalOptions::default() => {
+            return Ok(SelectResultExpr::ALL)
+        }
+        SelectItem::UnnamedExpr(expr) => {
+            let expr = lower_expr(expr)?;
+            let alias = default_alias(&expr);
+            (expr, alias)
+        }
+        SelectItem::ExprWithAlias { expr, alias } => (lower_expr(expr)?, lower_identifier(alias)?),
+        _ => return unsupported(item),
+    };
+    Ok(SelectResultExpr::AliasedResultExpr(AliasedResultExpr {
+        expr,
+        alias,
+    }))
+}
+
+/// The alias that the Proof of SQL grammar gives to a result expression without one.
+fn default_alias(expr: &Expression) -> Identifier {
+    match expr {
+        Expression::Column(identifier) => *identifier,
+        Expression::Aggregation { op, .. } => Identifier::new(format!("__{op}__")),
+        _ => Identifier::new("__expr__"),
+    }
+}
+
+fn lower_order_by(order_by: &ast::OrderByExpr) -> SqlParserConversionResult<OrderBy> {
+    let Expr::Identifier(ident) = &order_by.expr else {
+        return unsupported(order_by);
+    };
+    if order_by.nulls_first.is_some() {
+        return unsupported(order_by);
+    }
+    Ok(OrderBy {
+        expr: lower_identifier(ident)?,
+        direction: match order_by.asc {
+            Some(false) => OrderByDirection::Desc,
+            _ => OrderByDirection::Asc,
+        },
+    })
+}
+
+fn lower_slice(
+    limit: Option<&Expr>,
+    offset: Option<&Expr>,
+) -> SqlParserConversionResult<Option<Slice>> {
+    let number_rows = limit.map(lower_integer::<u64>).transpose()?;
+    let offset_value = offset.map(lower_integer::<i64>).transpose()?;
+    if number_rows.is_none() && offset_value.is_none() {
+        return Ok(None);
+    }
+    Ok(Some(Slice {
+        number_rows: number_rows.unwrap_or(u64::MAX),
+        offset_value: offset_value.unwrap_or(0),
+    }))
+}
+
+fn lower_identifier(ident: &Ident) -> SqlParserConversionResult<Identifier> {
+    match ident.quote_style {
+        Some('"') => Identifier::try_new_case_sensitive(&ident.value),
+        _ => Identifier::try_new(&ident.value),
+    }
+    .map_err(|_| SqlParserConversionError::InvalidIdentifier(ident.value.clone()))
+}
+
+/// Returns the digits of a numeric literal, including its sign.
+fn signed_number(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Value(Value::Number(number, _)) => Some(number.clone()),
+        Expr::UnaryOp {
+            op: ast::UnaryOperator::Minus,
+            expr,
+        } => match expr.as_ref() {
+            Expr::Value(Value::Number(number, _)) => Some(format!("-{number}")),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn lower_integer<T: FromStr>(expr: &Expr) -> SqlParserConversionResult<T> {
+    let Some(number) = signed_number(expr) else {
+        return unsupported(expr);
+    };
+    number
+        .parse()
+        .map_err(|_| SqlParserConversionError::InvalidLiteral(number))
+}
+
+/// Lowers a numeric literal the way the Proof of SQL grammar does: integers become `BIGINT`
+/// literals if they fit and `DECIMAL` literals otherwise.
+fn lower_number(number: &str) -> SqlParserConversionResult<Literal> {
+    let invalid_literal = || SqlParserConversionError::InvalidLiteral(number.to_string());
+    if number.contains('.') {
+        return Ok(Literal::Decimal(
+            IntermediateDecimal::try_from(number).map_err(|_| invalid_literal())?,
+        ));
+    }
+    let value: i128 = number.parse().map_err(|_| invalid_literal())?;
+    Ok(match i64::try_from(value) {
+        Ok(value) => Literal::BigInt(value),
+        Err(_) => Literal::Int128(value),
+    })
+}
+
+fn lower_expr(expr: &Expr) -> SqlParserConversionResult<Box<Expression>> {
+    if let Some(number) = signed_number(expr) {
+        return Ok(Box::new(Expression::Literal(lower_number(&number)?)));
+    }
+    Ok(Box::new(match expr {
+        Expr::Identifier(ident) => Expression::Column(lower_identifier(ident)?),
+        Expr::Nested(expr) => return lower_expr(expr),
+        Expr::Value(Value::SingleQuotedString(string)) => {
+            Expression::Literal(Literal::VarChar(string.clone()))
+        }
+        Expr::Value(Value::Boolean(value)) => Expression::Literal(Literal::Boolean(*value)),
+        Expr::TypedString {
+            data_type: DataType::Timestamp(..),
+            value,
+        } => Expression::Literal(Literal::Timestamp(
+            PoSQLTimestamp::try_from(value.trim())
+                .map_err(|_| SqlParserConversionError::InvalidLiteral(value.clone()))?,
+        )),
+        Expr::UnaryOp { op, expr } => match op {
+            ast::UnaryOperator::Not => Expression::Unary {
+                op: UnaryOperator::Not,
+                expr: lower_expr(expr)?,
+            },
+            ast::UnaryOperator::Minus => Expression::Binary {
+                op: BinaryOperator::Multiply,
+                left: Box::new(Expression::Literal(Literal::BigInt(-1))),
+                right: lower_expr(expr)?,
+            },
+            _ => return unsupported(op),
+        },
+        Expr::BinaryOp { left, op, right } => {
+            lower_binary_op(op, lower_expr(left)?, lower_expr(right)?)?
+        }
+        Expr::Function(function) => lower_function(function)?,
+        Expr::Exists { subquery, negated } => {
+            let exists = lower_exists(subquery)?;
+            if *negated {
+                Expression::Unary {
+                    op: UnaryOperator::Not,
+                    expr: Box::new(exists),
+                }
+            } else {
+                exists
+            }
+        }
+        _ => return unsupported(expr),
+    }))
+}
+
+/// Lowers the bounded form of `EXISTS` subqueries that the Proof of SQL grammar supports, i.e.
+/// `EXISTS (SELECT 1 FROM other WHERE other.k = t.k)`.
+fn lower_exists(query: &Query) -> SqlParserConversionResult<Expression> {
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return unsupported(query);
+    };
+    let Some(Expr::BinaryOp {
+        left,
+        op: ast::BinaryOperator::Eq,
+        right,
+    }) = &select.selection
+    else {
+        return unsupported(query);
+    };
+    // The rest of the subquery is an ordinary query that selects `1`
+    let mut unfiltered_query = query.clone();
+    unfiltered_query.body = Box::new(SetExpr::Select(Box::new(Select {
+        selection: None,
+        ..select.as_ref().clone()
+    })));
+    let SelectStatement {
+        expr,
+        order_by,
+        slice,
+        ..
+    } = lower_query(&unfiltered_query)?;
+    let SetExpression::Query {
+        result_exprs,
+        from,
+        group_by,
+        ..
+    } = *expr;
+    let one = SelectResultExpr::AliasedResultExpr(AliasedResultExpr::new(
+        Expression::Literal(Literal::BigInt(1)),
+        Identifier::new("__expr__"),
+    ));
+    if result_exprs != [one] || !group_by.is_empty() || !order_by.is_empty() || slice.is_some() {
+        return unsupported(query);
+    }
+    let Ok([table]) = <[_; 1]>::try_from(from) else {
+        return unsupported(query);
+    };
+    let subquery_table = match table.as_ref() {
+        TableExpression::Named { table, .. } => *table,
+    };
+    let (column, (outer_table, outer_column)) = match (
+        lower_qualified_column(left)?,
+        lower_qualified_column(right)?,
+    ) {
+        ((left_table, column), outer) if left_table == subquery_table && outer.0 != left_table => {
+            (column, outer)
+        }
+        (outer, (right_table, column))
+            if right_table == subquery_table && outer.0 != right_table =>
+        {
+            (column, outer)
+        }
+        _ => return unsupported(query),
+    };
+    Ok(Expression::Exists {
+        table,
+        column,
+        outer_table,
+        outer_column,
+    })
+}
+
+/// Lowers a column qualified by its table, e.g. `tab.a`, into the table and column names.
+fn lower_qualified_column(expr: &Expr) -> SqlParserConversionResult<(Identifier, Identifier)> {
+    let Expr::CompoundIdentifier(idents) = expr else {
+        return unsupported(expr);
+    };
+    let [table, column] = idents.as_slice() else {
+        return unsupported(expr);
+    };
+    Ok((lower_identifier(table)?, lower_identifier(column)?))
+}
+
+fn lower_binary_op(
+    op: &ast::BinaryOperator,
+    left: Box<Expression>,
+    right: Box<Expression>,
+) -> SqlParserConversionResult<Expression> {
+    let binary = |op, left, right| Expression::Binary { op, left, right };
+    let not = |expr| Expression::Unary {
+        op: UnaryOperator::Not,
+        expr: Box::new(expr),
+    };
+    Ok(match op {
+        ast::BinaryOperator::Plus => binary(BinaryOperator::Add, left, right),
+        ast::BinaryOperator::Minus => binary(BinaryOperator::Subtract, left, right),
+        ast::BinaryOperator::Multiply => binary(BinaryOperator::Multiply, left, right),
+        ast::BinaryOperator::Divide => binary(BinaryOperator::Division, left, right),
+        ast::BinaryOperator::Modulo => binary(BinaryOperator::Modulo, left, right),
+        ast::BinaryOperator::And => binary(BinaryOperator::And, left, right),
+        ast::BinaryOperator::Or => binary(BinaryOperator::Or, left, right),
+        ast::BinaryOperator::Eq => binary(BinaryOperator::Equal, left, right),
+        ast::BinaryOperator::GtEq => binary(BinaryOperator::GreaterThanOrEqual, left, right),
+        ast::BinaryOperator::LtEq => binary(BinaryOperator::LessThanOrEqual, left, right),
+        ast::BinaryOperator::Gt => not(binary(BinaryOperator::LessThanOrEqual, left, right)),
+        ast::BinaryOperator::Lt => not(binary(BinaryOperator::GreaterThanOrEqual, left, right)),
+        ast::BinaryOperator::NotEq => not(binary(BinaryOperator::Equal, left, right)),
+        _ => return unsupported(op),
+    })
+}
+
+fn lower_function(function: &ast::Function) -> SqlParserConversionResult<Expression> {
+    let ObjectName(name) = &function.name;
+    let ([name], None, None, false) = (
+        name.as_slice(),
+        &function.over,
+        &function.filter,
+        function.distinct,
+    ) else {
+        return unsupported(function);
+    };
+    let aggregation = |op, expr: &Expr| -> SqlParserConversionResult<Expression> {
+        Ok(Expression::Aggregation {
+            op,
+            expr: lower_expr(expr)?,
+        })
+    };
+    match (name.value.to_lowercase().as_str(), function.args.as_slice()) {
+        ("count", [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)]) => {
+            Ok(Expression::Aggregation {
+                op: AggregationOperator::Count,
+                expr: Box::new(Expression::Wildcard),
+            })
+        }
+        ("count", [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))]) => {
+            aggregation(AggregationOperator::Count, expr)
+        }
+        ("sum", [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))]) => {
+            aggregation(AggregationOperator::Sum, expr)
+        }
+        ("max", [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))]) => {
+            aggregation(AggregationOperator::Max, expr)
+        }
+        ("min", [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))]) => {
+            aggregation(AggregationOperator::Min, expr)
+        }
+        ("to_timestamp", [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))]) => {
+            let epoch = lower_integer(expr)?;
+            let timestamp = PoSQLTimestamp::to_timestamp(epoch)
+                .map_err(|_| SqlParserConversionError::InvalidLiteral(epoch.to_string()))?;
+            Ok(Expression::Literal(Literal::Timestamp(timestamp)))
+        }
+        // The names of aggregations and `to_timestamp` are reserved, so calls of them that don't
+        // match the arms above are not mistaken for scalar functions.
+        ("count" | "sum" | "max" | "min" | "avg" | "to_timestamp", _) => unsupported(function),
+        (_, args) => Ok(Expression::Function {
+            name: lower_identifier(name)?,
+            args: args
+                .iter()
+                .map(|arg| match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(*lower_expr(expr)?),
+                    _ => unsupported(arg),
+                })
+                .collect::<SqlParserConversionResult<_>>()?,
+        }),
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    sqlparser_adapter::{parse_generic_select_statement, SqlParserConversionError},
+    SelectStatement,
+};
+use sqlparser::{dialect::GenericDialect, parser::Parser};
+
+/// Both parsers have to lower the query into the same intermediate AST.
+fn assert_same_as_proof_of_sql_parser(sql: &str) {
+    let expected: SelectStatement = sql.parse().unwrap();
+    assert_eq!(
+        parse_generic_select_statement(sql).unwrap(),
+        expected,
+        "{sql}"
+    );
+}
+
+#[test]
+fn we_can_lower_simple_queries() {
+    for sql in [
+        "select * from tab",
+        "SELECT a, b FROM sxt.tab",
+        "select a as c, b from sxt.tab;",
+        "select a + 1, sum(b) as s, count(*) from tab group by a",
+        "select a from tab where b = 'it''s' and c >= 3",
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_quoted_identifiers() {
+    for sql in [
+        r#"select "A", a from "Sxt"."Tab""#,
+        r#"select a as "Total", "timestamp" from tab where "B" = 1"#,
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_comparisons_and_arithmetic() {
+    for sql in [
+        "select * from tab where a > 1 or b < -2",
+        "select * from tab where not (a != b)",
+        "select * from tab where a <= 1.5 and b <> 7",
+        "select a * (b - c) / 2 as d from tab",
+        "select a % 7 as d from tab where b - a % 3 * 2 = 0",
+        "select -(a + b) as c from tab",
+        "select * from tab where a = 170141183460469231731687303715884105727",
+        "select * from tab where a = true or b = false",
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_aggregations_and_context_variables() {
+    for sql in [
+        "select max(a), min(a), sum(a * 2), count(a) from tab",
+        "select a, count(*) as c from tab group by a, b",
+        "select * from tab where t >= now() and h <= block_height()",
+        "select time_bucket(60, t) as b from tab where clamp(a, 0, 10) = f()",
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_timestamps() {
+    for sql in [
+        "select * from tab where t = timestamp '2024-06-20T12:34:56Z'",
+        "select * from tab where t >= to_timestamp(1718886896)",
+        "select * from tab where t >= to_timestamp(-1)",
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_order_by_and_slices() {
+    for sql in [
+        "select a from tab order by a desc, b",
+        "select a from tab order by a asc limit 3",
+        "select a from tab offset -2",
+        "select a from tab limit 5 offset 2",
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_bounded_exists_subqueries() {
+    for sql in [
+        "select a from tab where exists (select 1 from other where other.k = tab.a)",
+        "select a from tab where not exists (select 1 from sxt.other where tab.a = other.k)",
+        "select * from tab where b = 1 and exists (select 1 from other where other.k = tab.a)",
+    ] {
+        assert_same_as_proof_of_sql_parser(sql);
+    }
+}
+
+#[test]
+fn we_can_lower_statements_that_were_parsed_by_sqlparser() {
+    let sql = "select a from sxt.tab where b >= 3";
+    let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+    let expected: SelectStatement = sql.parse().unwrap();
+    assert_eq!(SelectStatement::try_from(&statements[0]).unwrap(), expected);
+}
+
+#[test]
+fn we_cannot_lower_unsupported_sql() {
+    for sql in [
+        "select distinct a from tab",
+        "select a from tab join other on tab.a = other.a",
+        "select a from tab as t",
+        "select tab.a from tab",
+        "select a from tab where a in (1, 2)",
+        "select a from tab group by a having count(*) >= 2",
+        "select a from tab order by a + 1",
+        "select a from tab order by a nulls first",
+        "select sum(distinct a) from tab",
+        "select avg(a) from tab",
+        "with t as (select a from tab) select a from t",
+        "select a from tab union select a from other",
+        "insert into tab values (1)",
+        "select a from tab where exists (select k from other where other.k = tab.a)",
+        "select a from tab where exists (select 1 from other where other.k = other.j)",
+        "select a from tab where exists (select 1 from other where other.k = tab.a limit 1)",
+        "select a from tab where exists (select 1 from other where other.k = tab.a and b = 1)",
+    ] {
+        assert!(
+            matches!(
+                parse_generic_select_statement(sql),
+                Err(SqlParserConversionError::Unsupported(_))
+            ),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_lower_invalid_queries() {
+    assert!(matches!(
+        parse_generic_select_statement("select a from"),
+        Err(SqlParserConversionError::ParserError(_))
+    ));
+    assert_eq!(
+        parse_generic_select_statement("select a from tab; select b from tab"),
+        Err(SqlParserConversionError::StatementCount(2))
+    );
+    assert_eq!(
+        parse_generic_select_statement("select a from tab where b = 1e400"),
+        Err(SqlParserConversionError::InvalidLiteral(
+            "1e400".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_generic_select_statement("select a from tab limit -1"),
+        Err(SqlParserConversionError::InvalidLiteral("-1".to_string()))
+    );
+    assert_eq!(
+        parse_generic_select_statement("select \"bad identifier\" from tab"),
+        Err(SqlParserConversionError::InvalidIdentifier(
+            "bad identifier".to_string()
+        ))
+    );
+}
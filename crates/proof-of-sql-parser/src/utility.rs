@@ -94,6 +94,15 @@ pub fn div(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A % B
+pub fn modulo(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::Modulo,
+        left,
+        right,
+    })
+}
+
 /// Get table from schema and name.
 ///
 /// If the schema is `None`, the table is assumed to be in the default schema.
@@ -114,6 +123,29 @@ pub fn lit<L: Into<Literal>>(literal: L) -> Box<Expression> {
     Box::new(Expression::Literal(literal.into()))
 }
 
+/// Construct a new boxed `Expression` for a correlated EXISTS subquery
+pub fn exists(
+    table: Box<TableExpression>,
+    column: &str,
+    outer_table: &str,
+    outer_column: &str,
+) -> Box<Expression> {
+    Box::new(Expression::Exists {
+        table,
+        column: column.parse().unwrap(),
+        outer_table: outer_table.parse().unwrap(),
+        outer_column: outer_column.parse().unwrap(),
+    })
+}
+
+/// Construct a new boxed `Expression` for a call of a scalar function
+pub fn func(name: &str, args: Vec<Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::Function {
+        name: name.parse().unwrap(),
+        args: args.into_iter().map(|arg| *arg).collect(),
+    })
+}
+
 /// Compute the sum of an expression
 pub fn sum(expr: Box<Expression>) -> Box<Expression> {
     Box::new(Expression::Aggregation {
@@ -259,6 +291,7 @@ pub fn select(
         expr,
         order_by,
         slice,
+        as_of: None,
     }
 }
 
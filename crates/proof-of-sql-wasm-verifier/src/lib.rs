@@ -0,0 +1,39 @@
+//! A minimal verifier of Proof of SQL query results for WebAssembly.
+//!
+//! This crate is not published. CI builds it for `wasm32-unknown-unknown` without the default
+//! features of `proof-of-sql` and fails if the compressed binary grows past its size budget.
+use proof_of_sql::{
+    base::commitment::QueryCommitments,
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryVerifierPublicSetup, VerifierSetup,
+    },
+    sql::{ast::ProofPlan, proof::VerifiableQueryResult},
+};
+use serde::Deserialize;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Everything needed to verify the result of a query.
+#[derive(Deserialize)]
+struct VerificationRequest {
+    proof_plan: ProofPlan<DoryCommitment>,
+    result: VerifiableQueryResult<DoryEvaluationProof>,
+    commitments: QueryCommitments<DoryCommitment>,
+    verifier_setup: VerifierSetup,
+    sigma: usize,
+}
+
+/// Verify a postcard-encoded [`VerificationRequest`].
+///
+/// Returns whether the query result is valid. Requests that fail to deserialize are not.
+#[wasm_bindgen]
+#[must_use]
+pub fn verify(request: &[u8]) -> bool {
+    let Ok(request) = postcard::from_bytes::<VerificationRequest>(request) else {
+        return false;
+    };
+    let setup = DoryVerifierPublicSetup::new(&request.verifier_setup, request.sigma);
+    request
+        .result
+        .verify(&request.proof_plan, &request.commitments, &setup)
+        .is_ok()
+}
@@ -0,0 +1,206 @@
+//! Comparison of the available commitment schemes on a table of random data.
+//! To run, execute the following command:
+//! ```bash
+//! cargo bench -p proof-of-sql --bench commitment_comparison --features="test" -- \
+//!     --schema "a:bigint,b:varchar" --rows 100000
+//! ```
+//! For each scheme, this reports the time it takes to commit to the table, the serialized size of
+//! the table commitment, and the time it takes to prove and verify the query along with the
+//! serialized size of the proof.
+use blitzar::{compute::init_backend, proof::InnerProductProof};
+use bumpalo::Bump;
+use clap::Parser;
+#[cfg(feature = "test")]
+use proof_of_sql::proof_primitive::dory::{
+    test_rng, DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
+    ProverGeneratorCache, ProverSetup, PublicParameters, VerifierSetup,
+};
+use proof_of_sql::{
+    base::{
+        commitment::{CommitmentEvaluationProof, TableCommitment},
+        database::ColumnType,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+#[allow(dead_code)]
+mod scaffold;
+use scaffold::{
+    benchmark_accessor::BenchmarkAccessor,
+    random_util::{generate_random_columns, OptionalRandBound},
+};
+
+#[derive(Parser, Debug)]
+#[command(about = "Compare the commitment schemes on a table of random data")]
+struct Args {
+    /// The columns of the table, as comma separated `name:type` pairs.
+    /// The supported types are `bigint`, `int128`, `boolean`, and `varchar`.
+    #[arg(long, default_value = "a:bigint,b:varchar")]
+    schema: String,
+
+    /// The number of rows of the table
+    #[arg(long, default_value_t = 100_000)]
+    rows: usize,
+
+    /// The query to prove and verify against the table, which is named `table`
+    #[arg(long, default_value = "SELECT * FROM table")]
+    query: String,
+
+    /// The `nu` of the Dory public parameters, which support tables of up to `2^(2 * nu)` rows
+    #[cfg(feature = "test")]
+    #[arg(long, default_value_t = 10)]
+    dory_nu: usize,
+
+    /// Ignored. `cargo bench` passes this flag to every benchmark.
+    #[arg(long, hide = true)]
+    #[allow(dead_code)]
+    bench: bool,
+}
+
+/// The measurements of a single commitment scheme.
+struct Report {
+    scheme: &'static str,
+    commit_time: Duration,
+    commitment_size: usize,
+    prove_time: Duration,
+    proof_size: usize,
+    verify_time: Duration,
+}
+
+fn parse_schema(schema: &str) -> Vec<(&str, ColumnType, OptionalRandBound)> {
+    schema
+        .split(',')
+        .map(|column| {
+            let (name, column_type) = column
+                .split_once(':')
+                .unwrap_or_else(|| panic!("expected `name:type`, found `{column}`"));
+            let column_type = match column_type.trim().to_lowercase().as_str() {
+                "bigint" => ColumnType::BigInt,
+                "int128" => ColumnType::Int128,
+                "boolean" => ColumnType::Boolean,
+                "varchar" => ColumnType::VarChar,
+                _ => panic!("unsupported column type `{column_type}`"),
+            };
+            (name.trim(), column_type, None)
+        })
+        .collect()
+}
+
+fn serialized_size(value: &impl Serialize) -> usize {
+    postcard::to_allocvec(value).unwrap().len()
+}
+
+fn compare<CP: CommitmentEvaluationProof>(
+    scheme: &'static str,
+    args: &Args,
+    columns: &[(&str, ColumnType, OptionalRandBound)],
+    prover_setup: &CP::ProverPublicSetup<'_>,
+    verifier_setup: &CP::VerifierPublicSetup<'_>,
+) -> Report
+where
+    CP: Serialize,
+    CP::Commitment: Serialize,
+{
+    let alloc = Bump::new();
+    let mut rng = rand::thread_rng();
+    let table = generate_random_columns(&alloc, &mut rng, columns, args.rows);
+
+    let start = Instant::now();
+    let table_commitment = TableCommitment::<CP::Commitment>::try_from_columns_with_offset(
+        table
+            .iter()
+            .map(|(identifier, column)| (identifier, column)),
+        0,
+        prover_setup,
+    )
+    .unwrap();
+    let commit_time = start.elapsed();
+
+    let mut accessor = BenchmarkAccessor::default();
+    accessor.insert_table("bench.table".parse().unwrap(), &table, prover_setup);
+    let query = QueryExpr::try_new(
+        args.query.parse().unwrap(),
+        "bench".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+
+    let start = Instant::now();
+    let result = VerifiableQueryResult::<CP>::new(query.proof_expr(), &accessor, prover_setup);
+    let prove_time = start.elapsed();
+
+    let start = Instant::now();
+    result
+        .verify(query.proof_expr(), &accessor, verifier_setup)
+        .unwrap();
+    let verify_time = start.elapsed();
+
+    Report {
+        scheme,
+        commit_time,
+        commitment_size: serialized_size(&table_commitment),
+        prove_time,
+        proof_size: serialized_size(&result),
+        verify_time,
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let columns = parse_schema(&args.schema);
+    init_backend();
+
+    let mut reports = Vec::new();
+    reports.push(compare::<InnerProductProof>(
+        "InnerProductProof",
+        &args,
+        &columns,
+        &(),
+        &(),
+    ));
+    #[cfg(feature = "test")]
+    {
+        let pp = PublicParameters::rand(args.dory_nu, &mut test_rng());
+        let ps = ProverSetup::from(&pp);
+        let prover_setup = DoryProverPublicSetup::new(&ps, args.dory_nu);
+        let vs = VerifierSetup::from(&pp);
+        let verifier_setup = DoryVerifierPublicSetup::new(&vs, args.dory_nu);
+        reports.push(compare::<DoryEvaluationProof>(
+            "Dory",
+            &args,
+            &columns,
+            &prover_setup,
+            &verifier_setup,
+        ));
+        // The prepared generators are reused across proofs, so they are not part of the prove time.
+        let cache = ProverGeneratorCache::new(&ps, args.dory_nu);
+        reports.push(compare::<DoryEvaluationProof>(
+            "Dory (cached)",
+            &args,
+            &columns,
+            &prover_setup.with_generator_cache(&cache),
+            &verifier_setup,
+        ));
+    }
+
+    println!(
+        "{} rows of ({}), proving `{}`",
+        args.rows, args.schema, args.query
+    );
+    println!(
+        "{:<20}{:>16}{:>20}{:>16}{:>16}{:>16}",
+        "scheme", "commit time", "commitment bytes", "prove time", "proof bytes", "verify time"
+    );
+    for report in reports {
+        println!(
+            "{:<20}{:>16}{:>20}{:>16}{:>16}{:>16}",
+            report.scheme,
+            format!("{:.2?}", report.commit_time),
+            report.commitment_size,
+            format!("{:.2?}", report.prove_time),
+            report.proof_size,
+            format!("{:.2?}", report.verify_time),
+        );
+    }
+}
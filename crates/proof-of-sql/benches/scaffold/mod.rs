@@ -6,10 +6,10 @@ use proof_of_sql::{
     sql::{parse::QueryExpr, proof::VerifiableQueryResult},
 };
 use rand::prelude::Rng;
-mod benchmark_accessor;
+pub mod benchmark_accessor;
 use benchmark_accessor::BenchmarkAccessor;
 pub mod querys;
-mod random_util;
+pub mod random_util;
 use random_util::{generate_random_columns, OptionalRandBound};
 
 fn scaffold<'a, CP: CommitmentEvaluationProof>(
@@ -0,0 +1,274 @@
+use crate::record_batch_accessor::RecordBatchAccessor;
+use arrow::{compute::concat_batches, record_batch::RecordBatch};
+use arrow_flight::{
+    decode::FlightRecordBatchStream, encode::FlightDataEncoderBuilder, error::FlightError,
+    flight_service_server::FlightService, Action, ActionType, Criteria, Empty, FlightData,
+    FlightDescriptor, FlightInfo, HandshakeRequest, HandshakeResponse, PollInfo, PutResult,
+    Result as FlightResult, SchemaResult, Ticket,
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+use indexmap::IndexMap;
+use proof_of_sql::{
+    base::{
+        commitment::{QueryCommitments, TableCommitment},
+        database::TableRef,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use proof_of_sql_parser::{Identifier, SelectStatement};
+use std::sync::RwLock;
+use tonic::{Request, Response, Status, Streaming};
+
+/// The type of the action that returns the commitment of the table named in its body.
+pub const GET_COMMITMENT_ACTION: &str = "get_commitment";
+
+struct StoredTable {
+    batch: RecordBatch,
+    commitment: TableCommitment<RistrettoPoint>,
+}
+
+/// An Arrow Flight service for an in-memory database with Proof of SQL capabilities.
+///
+/// * `DoGet` takes a query as the ticket and streams its result as record batches. The app
+///   metadata of the first message is the serialized `VerifiableQueryResult` of the query.
+/// * `DoPut` appends the record batches to the table named by the path of the flight descriptor,
+///   e.g. `["sxt.table"]` or `["sxt", "table"]`, creating the table if it does not exist. The app
+///   metadata of the result is the serialized updated `TableCommitment` of the table.
+/// * The `get_commitment` action returns the serialized `TableCommitment` of the table named in
+///   its body, which clients need in order to verify the results of `DoGet`.
+///
+/// All serialization uses `postcard`.
+pub struct PosqlFlightService {
+    default_schema: Identifier,
+    tables: RwLock<IndexMap<TableRef, StoredTable>>,
+}
+
+impl PosqlFlightService {
+    /// Create a service without any tables, which resolves tables without a schema in
+    /// `default_schema`.
+    pub fn new(default_schema: Identifier) -> Self {
+        Self {
+            default_schema,
+            tables: RwLock::new(IndexMap::new()),
+        }
+    }
+
+    /// Returns the data and the commitment of the table.
+    fn table(
+        &self,
+        table_ref: TableRef,
+    ) -> Result<(RecordBatch, TableCommitment<RistrettoPoint>), Status> {
+        self.tables
+            .read()
+            .expect("the table lock should not be poisoned")
+            .get(&table_ref)
+            .map(|table| (table.batch.clone(), table.commitment.clone()))
+            .ok_or_else(|| Status::not_found(format!("table {table_ref} does not exist")))
+    }
+
+    /// Prove the query, returning the serialized proof along with the result.
+    fn prove(&self, query: &str) -> Result<(Vec<u8>, RecordBatch), Status> {
+        let statement: SelectStatement = query.parse().map_err(invalid_argument)?;
+        let mut accessor = RecordBatchAccessor::default();
+        let mut commitments = QueryCommitments::default();
+        for table_ref in statement
+            .get_table_references(self.default_schema)
+            .into_iter()
+            .map(TableRef::new)
+        {
+            let (batch, commitment) = self.table(table_ref)?;
+            accessor.insert_table(table_ref, batch);
+            commitments.insert(table_ref, commitment);
+        }
+        let query = QueryExpr::try_new(statement, self.default_schema, &commitments)
+            .map_err(invalid_argument)?;
+        let proof =
+            VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        let result = proof
+            .verify(query.proof_expr(), &commitments, &())
+            .map_err(internal)?;
+        let batch = query
+            .result()
+            .transform_results(RecordBatch::try_from(result).map_err(internal)?)
+            .ok_or_else(|| Status::internal("failed to transform the query result"))?;
+        Ok((postcard::to_allocvec(&proof).map_err(internal)?, batch))
+    }
+
+    /// Append the batches to the table, returning its updated commitment.
+    fn append(
+        &self,
+        table_ref: TableRef,
+        batches: &[RecordBatch],
+    ) -> Result<TableCommitment<RistrettoPoint>, Status> {
+        let first = batches
+            .first()
+            .ok_or_else(|| Status::invalid_argument("there are no record batches to append"))?;
+        let batch = concat_batches(&first.schema(), batches).map_err(invalid_argument)?;
+        let mut tables = self
+            .tables
+            .write()
+            .expect("the table lock should not be poisoned");
+        let table = match tables.get(&table_ref) {
+            Some(table) => {
+                let mut commitment = table.commitment.clone();
+                commitment
+                    .try_append_record_batch(&batch, &())
+                    .map_err(invalid_argument)?;
+                StoredTable {
+                    batch: concat_batches(&table.batch.schema(), [&table.batch, &batch])
+                        .map_err(invalid_argument)?,
+                    commitment,
+                }
+            }
+            None => StoredTable {
+                commitment: TableCommitment::try_from_record_batch(&batch, &())
+                    .map_err(invalid_argument)?,
+                batch,
+            },
+        };
+        let commitment = table.commitment.clone();
+        tables.insert(table_ref, table);
+        Ok(commitment)
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for PosqlFlightService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<FlightResult, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake is not supported"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("list_flights is not supported"))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented("get_flight_info is not supported"))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("poll_flight_info is not supported"))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        Err(Status::unimplemented("get_schema is not supported"))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let query =
+            String::from_utf8(request.into_inner().ticket.to_vec()).map_err(invalid_argument)?;
+        let (proof, batch) = self.prove(&query)?;
+        let flight_data = FlightDataEncoderBuilder::new()
+            .with_metadata(proof.into())
+            .build(stream::iter([Ok(batch)]))
+            .map_err(Status::from);
+        Ok(Response::new(flight_data.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let mut flight_data = request.into_inner();
+        let first = flight_data
+            .message()
+            .await?
+            .ok_or_else(|| Status::invalid_argument("the stream has no messages"))?;
+        let table_ref: TableRef = first
+            .flight_descriptor
+            .as_ref()
+            .map(|descriptor| descriptor.path.join("."))
+            .unwrap_or_default()
+            .parse()
+            .map_err(invalid_argument)?;
+        let batches: Vec<RecordBatch> = FlightRecordBatchStream::new_from_flight_data(
+            stream::once(async { Ok(first) }).chain(flight_data.map_err(FlightError::from)),
+        )
+        .try_collect()
+        .await?;
+        let commitment = self.append(table_ref, &batches)?;
+        let result = PutResult {
+            app_metadata: postcard::to_allocvec(&commitment).map_err(internal)?.into(),
+        };
+        Ok(Response::new(stream::iter([Ok(result)]).boxed()))
+    }
+
+    async fn do_action(
+        &self,
+        request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        let action = request.into_inner();
+        if action.r#type != GET_COMMITMENT_ACTION {
+            return Err(Status::unimplemented(format!(
+                "action {} is not supported",
+                action.r#type
+            )));
+        }
+        let table_ref: TableRef = std::str::from_utf8(&action.body)
+            .map_err(invalid_argument)?
+            .parse()
+            .map_err(invalid_argument)?;
+        let (_, commitment) = self.table(table_ref)?;
+        let result = FlightResult {
+            body: postcard::to_allocvec(&commitment).map_err(internal)?.into(),
+        };
+        Ok(Response::new(stream::iter([Ok(result)]).boxed()))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        let action_type = ActionType {
+            r#type: GET_COMMITMENT_ACTION.to_string(),
+            description: "Returns the commitment of the table named in the body".to_string(),
+        };
+        Ok(Response::new(stream::iter([Ok(action_type)]).boxed()))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do_exchange is not supported"))
+    }
+}
+
+fn invalid_argument(error: impl ToString) -> Status {
+    Status::invalid_argument(error.to_string())
+}
+
+fn internal(error: impl ToString) -> Status {
+    Status::internal(error.to_string())
+}
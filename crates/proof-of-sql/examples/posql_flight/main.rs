@@ -0,0 +1,38 @@
+#![doc = include_str!("README.md")]
+mod flight_service;
+#[path = "../posql_db/record_batch_accessor.rs"]
+mod record_batch_accessor;
+use arrow_flight::flight_service_server::FlightServiceServer;
+use clap::Parser;
+use flight_service::PosqlFlightService;
+use proof_of_sql_parser::Identifier;
+use std::{error::Error, net::SocketAddr};
+use tonic::transport::Server;
+
+/// Arrow Flight server demonstrating an implementation of a simple in-memory database with Proof of SQL capabilities.
+#[derive(Parser, Debug)]
+#[command()]
+struct CliArgs {
+    /// The address to listen on.
+    #[arg(short, long, default_value = "127.0.0.1:50051")]
+    address: SocketAddr,
+    /// The schema of the tables that queries reference without a schema.
+    #[arg(short, long, default_value = "example")]
+    schema: Identifier,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = CliArgs::parse();
+    println!("Warming up GPU...");
+    blitzar::compute::init_backend();
+    println!("Done.");
+    println!("Listening on {}.", args.address);
+    Server::builder()
+        .add_service(FlightServiceServer::new(PosqlFlightService::new(
+            args.schema,
+        )))
+        .serve(args.address)
+        .await?;
+    Ok(())
+}
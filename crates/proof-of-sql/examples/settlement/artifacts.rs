@@ -0,0 +1,150 @@
+//! The artifacts that the nodes of the settlement example exchange through a directory.
+//!
+//! Each node only uses some of the artifacts.
+#![allow(dead_code)]
+
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use proof_of_sql::{
+    base::{
+        commitment::{CommitmentRegistry, TableCommitment},
+        database::{owned_table_utility::*, OwnedTable, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::proof::SignedQueryProof,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The schema of the settlement tables. Queries may leave it out.
+pub const SCHEMA: &str = "settlement";
+
+/// The query that the settlement contract settles by default.
+pub const DEFAULT_QUERY: &str =
+    "SELECT account, SUM(amount) AS balance FROM trades GROUP BY account";
+
+/// The table that the ingest node appends trades to.
+pub fn trades_table() -> TableRef {
+    "settlement.trades".parse().unwrap()
+}
+
+/// A trade, as it is received by the ingest node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    /// The account that the trade is settled with
+    pub account: String,
+    /// The amount that the account receives, or pays if it is negative
+    pub amount: i64,
+}
+
+/// Converts trades to the rows of the trades table.
+pub fn trades_to_table(trades: &[Trade]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        varchar("account", trades.iter().map(|trade| trade.account.clone())),
+        bigint("amount", trades.iter().map(|trade| trade.amount)),
+    ])
+}
+
+/// A signed proof of a query, along with the version of the trades table that it was proven
+/// against.
+#[derive(Serialize, Deserialize)]
+pub struct ProofArtifact {
+    /// The version of the trades table that the query was proven against
+    pub version: u64,
+    /// The proof of the query, signed by the prover node
+    pub proof: SignedQueryProof<InnerProductProof>,
+}
+
+/// The directory through which the nodes exchange artifacts.
+///
+/// * `batch-<version>.trades` holds the trades that were appended in a version of the table.
+/// * `commitment-<version>.commit` holds the commitment to the whole table at that version.
+/// * `prover.key` and `prover.pub` hold the signing key of the prover and its public key.
+/// * `settlement.proof` holds the latest [`ProofArtifact`].
+pub struct ArtifactDir {
+    path: PathBuf,
+}
+
+impl ArtifactDir {
+    /// Open the directory, creating it if it does not exist.
+    pub fn new(path: PathBuf) -> Self {
+        fs::create_dir_all(&path).expect("Failed to create artifact directory");
+        Self { path }
+    }
+
+    /// Writes the trades that are appended in a version of the table.
+    pub fn write_batch(&self, version: u64, trades: &[Trade]) {
+        write(&self.path.join(format!("batch-{version}.trades")), trades);
+    }
+
+    /// Reads the trades that were appended in a version of the table.
+    pub fn read_batch(&self, version: u64) -> Vec<Trade> {
+        read(&self.path.join(format!("batch-{version}.trades")))
+    }
+
+    /// Writes the commitment to a version of the table.
+    pub fn write_commitment(&self, version: u64, commitment: &TableCommitment<RistrettoPoint>) {
+        write(
+            &self.path.join(format!("commitment-{version}.commit")),
+            commitment,
+        );
+    }
+
+    /// Registers the commitment of every version of the trades table that has been published.
+    pub fn registry(&self) -> CommitmentRegistry<RistrettoPoint> {
+        let mut registry = CommitmentRegistry::new();
+        for version in 0.. {
+            let path = self.path.join(format!("commitment-{version}.commit"));
+            if !path.exists() {
+                break;
+            }
+            registry
+                .register(trades_table(), read(&path))
+                .expect("Each version should extend the previous one");
+        }
+        registry
+    }
+
+    /// Returns the signing key of the prover, creating one if it does not exist yet.
+    pub fn prover_key(&self) -> SigningKey {
+        let path = self.path.join("prover.key");
+        if !path.exists() {
+            let signing_key = SigningKey::from_bytes(&rand::random());
+            write(&path, &signing_key.to_bytes());
+            write(&self.path.join("prover.pub"), &signing_key.verifying_key());
+        }
+        SigningKey::from_bytes(&read(&path))
+    }
+
+    /// Returns the public key of the prover.
+    pub fn prover_public_key(&self) -> VerifyingKey {
+        read(&self.path.join("prover.pub"))
+    }
+
+    /// Writes the proof of a query.
+    pub fn write_proof(&self, proof: &ProofArtifact) {
+        write(&self.path.join("settlement.proof"), proof);
+    }
+
+    /// Reads the proof of a query.
+    pub fn read_proof(&self) -> ProofArtifact {
+        read(&self.path.join("settlement.proof"))
+    }
+}
+
+fn write<T: Serialize + ?Sized>(path: &Path, value: &T) {
+    fs::write(
+        path,
+        postcard::to_allocvec(value).expect("Failed to serialize artifact"),
+    )
+    .expect("Failed to write artifact");
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> T {
+    postcard::from_bytes(&fs::read(path).expect("Failed to read artifact"))
+        .expect("Failed to deserialize artifact")
+}
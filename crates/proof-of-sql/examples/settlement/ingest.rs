@@ -0,0 +1,61 @@
+#![doc = include_str!("README.md")]
+mod artifacts;
+use artifacts::{trades_table, trades_to_table, ArtifactDir, Trade};
+use clap::Parser;
+use proof_of_sql::base::commitment::TableCommitment;
+use std::path::PathBuf;
+
+/// The ingest node of the settlement example.
+///
+/// Appends a batch of trades to the trades table and publishes the commitment to the new version
+/// of the table.
+///
+/// Example: `settlement_ingest -t alice:100,bob:-40`
+#[derive(Parser, Debug)]
+#[command()]
+struct CliArgs {
+    /// The directory through which the nodes exchange artifacts.
+    #[arg(short, long, default_value = "settlement-artifacts")]
+    dir: PathBuf,
+    /// The comma delimited trades to append, each in the format `account:amount`.
+    #[arg(short, long, value_parser = parse_trade, num_args = 1.., value_delimiter = ',')]
+    trades: Vec<Trade>,
+}
+
+fn parse_trade(trade: &str) -> Result<Trade, String> {
+    let (account, amount) = trade
+        .split_once(':')
+        .ok_or_else(|| format!("the trade {trade} is not in the format `account:amount`"))?;
+    Ok(Trade {
+        account: account.to_string(),
+        amount: amount.parse().map_err(|e| format!("invalid amount: {e}"))?,
+    })
+}
+
+fn main() {
+    let args = CliArgs::parse();
+    blitzar::compute::init_backend();
+    let dir = ArtifactDir::new(args.dir);
+    let table_ref = trades_table();
+    let mut registry = dir.registry();
+    let batch = trades_to_table(&args.trades);
+    let commitment = match registry.latest(table_ref) {
+        Some(latest) => {
+            let mut commitment = latest.clone();
+            commitment
+                .append_owned_table(&batch, &())
+                .expect("Failed to append trades");
+            commitment
+        }
+        None => TableCommitment::from_owned_table_with_offset(&batch, 0, &()),
+    };
+    let version = registry
+        .register(table_ref, commitment.clone())
+        .expect("Failed to register commitment");
+    dir.write_batch(version, &args.trades);
+    dir.write_commitment(version, &commitment);
+    println!(
+        "Published version {version} of {table_ref} with {} trades.",
+        commitment.num_rows()
+    );
+}
@@ -0,0 +1,82 @@
+#![doc = include_str!("README.md")]
+mod artifacts;
+use artifacts::{trades_to_table, ArtifactDir, ProofArtifact, DEFAULT_QUERY, SCHEMA};
+use blitzar::proof::InnerProductProof;
+use clap::Parser;
+use proof_of_sql::{
+    base::database::OwnedTableTestAccessor,
+    sql::{
+        parse::SnapshotQueryExpr,
+        proof::{SignedQueryProof, VerifiableQueryResult},
+    },
+};
+use proof_of_sql_parser::SelectStatement;
+use std::{
+    io::{stdout, Write},
+    path::PathBuf,
+    time::Instant,
+};
+
+/// The prover node of the settlement example.
+///
+/// Proves a query against a version of the trades table, the latest one unless the query reads
+/// an earlier one with `AS OF`, and signs the proof.
+///
+/// Example: `settlement_prover -q "SELECT account, amount FROM trades AS OF 0 WHERE amount < 0"`
+#[derive(Parser, Debug)]
+#[command()]
+struct CliArgs {
+    /// The directory through which the nodes exchange artifacts.
+    #[arg(short, long, default_value = "settlement-artifacts")]
+    dir: PathBuf,
+    /// The query to prove. Note: the default schema is `settlement`.
+    #[arg(short, long, default_value = DEFAULT_QUERY)]
+    query: SelectStatement,
+}
+
+fn start_timer(message: &str) -> Instant {
+    print!("{}...", message);
+    stdout().flush().unwrap();
+    Instant::now()
+}
+fn end_timer(instant: Instant) {
+    println!(" {:?}", instant.elapsed());
+}
+
+fn main() {
+    let args = CliArgs::parse();
+    blitzar::compute::init_backend();
+    let dir = ArtifactDir::new(args.dir);
+    let registry = dir.registry();
+    let expr = SnapshotQueryExpr::try_new(args.query, SCHEMA.parse().unwrap(), &registry)
+        .expect("Failed to plan query");
+
+    // Only the batches up to the version that the query reads are loaded, so that the proof is
+    // for exactly the rows that the commitment of that version commits to.
+    let timer = start_timer("Loading trades");
+    let trades: Vec<_> = (0..=expr.version())
+        .flat_map(|version| dir.read_batch(version))
+        .collect();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        expr.table_ref(),
+        trades_to_table(&trades),
+        0,
+        (),
+    );
+    end_timer(timer);
+
+    let timer = start_timer("Generating Proof");
+    let proof_expr = expr.query().proof_expr();
+    let result = VerifiableQueryResult::<InnerProductProof>::new(proof_expr, &accessor, &());
+    let proof = SignedQueryProof::sign(result, proof_expr, expr.commitments(), &dir.prover_key());
+    end_timer(timer);
+    dir.write_proof(&ProofArtifact {
+        version: expr.version(),
+        proof,
+    });
+    println!(
+        "Proved the query against version {} of {}.",
+        expr.version(),
+        expr.table_ref()
+    );
+}
@@ -0,0 +1,66 @@
+#![doc = include_str!("README.md")]
+mod artifacts;
+use arrow::record_batch::RecordBatch;
+use artifacts::{ArtifactDir, DEFAULT_QUERY, SCHEMA};
+use clap::Parser;
+use proof_of_sql::sql::parse::SnapshotQueryExpr;
+use proof_of_sql_parser::SelectStatement;
+use std::{path::PathBuf, process::exit};
+
+/// The verifier contract simulator of the settlement example.
+///
+/// Holds the state that a settlement contract would keep on chain, i.e. the commitment to every
+/// version of the trades table and the public key of the prover, and only settles the result of
+/// a query whose proof was signed by that prover and verifies against the retained commitment.
+///
+/// Example: `settlement_verifier`
+#[derive(Parser, Debug)]
+#[command()]
+struct CliArgs {
+    /// The directory through which the nodes exchange artifacts.
+    #[arg(short, long, default_value = "settlement-artifacts")]
+    dir: PathBuf,
+    /// The query to settle. Note: the default schema is `settlement`.
+    #[arg(short, long, default_value = DEFAULT_QUERY)]
+    query: SelectStatement,
+}
+
+fn main() {
+    let mut args = CliArgs::parse();
+    blitzar::compute::init_backend();
+    let dir = ArtifactDir::new(args.dir);
+    let registry = dir.registry();
+    let prover = dir.prover_public_key();
+    let artifact = dir.read_proof();
+
+    // A query without `AS OF` is settled at the version that the prover proved it against,
+    // which the contract checks against the commitment it retained for that version.
+    args.query.as_of.get_or_insert(artifact.version);
+    let expr = SnapshotQueryExpr::try_new(args.query, SCHEMA.parse().unwrap(), &registry)
+        .expect("Failed to plan query");
+    if artifact.proof.signer != prover {
+        eprintln!("Rejected: the proof was not signed by the registered prover.");
+        exit(1);
+    }
+    let query_data = match artifact
+        .proof
+        .verify(expr.query().proof_expr(), expr.commitments(), &())
+    {
+        Ok(query_data) => query_data,
+        Err(e) => {
+            eprintln!("Rejected: {e}");
+            exit(1);
+        }
+    };
+    let result = expr
+        .query()
+        .result()
+        .transform_results(RecordBatch::try_from(query_data).unwrap())
+        .expect("Failed to transform result");
+    println!(
+        "Settled version {} of {}: {:?}",
+        expr.version(),
+        expr.table_ref(),
+        result
+    );
+}
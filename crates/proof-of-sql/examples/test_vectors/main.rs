@@ -0,0 +1,14 @@
+#![doc = include_str!("README.md")]
+
+use blitzar::compute::init_backend;
+use proof_of_sql::proof_primitive::test_vectors::{test_vectors, test_vectors_to_json};
+use std::{env, fs};
+
+fn main() {
+    init_backend();
+    let json = test_vectors_to_json(&test_vectors());
+    match env::args().nth(1) {
+        Some(path) => fs::write(path, json).expect("failed to write the test vectors"),
+        None => println!("{json}"),
+    }
+}
@@ -1,4 +1,8 @@
-use crate::base::scalar::Scalar;
+#[cfg(feature = "rayon")]
+use crate::base::ComputeConfig;
+use crate::base::{if_rayon, scalar::Scalar};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 pub fn make_abs_bit_mask<S: Scalar>(x: S) -> [u64; 4] {
     let (sign, x) = if S::MAX_SIGNED < x { (1, -x) } else { (0, x) };
@@ -6,3 +10,15 @@ pub fn make_abs_bit_mask<S: Scalar>(x: S) -> [u64; 4] {
     res[3] |= sign << 63;
     res
 }
+
+/// Compute [`make_abs_bit_mask`] for every value, splitting the work between threads for long
+/// slices.
+pub fn make_abs_bit_masks<S: Scalar>(xs: &[S]) -> Vec<[u64; 4]> {
+    if_rayon!(
+        xs.par_iter()
+            .with_min_len(ComputeConfig::current().min_len(xs.len())),
+        xs.iter()
+    )
+    .map(|x| make_abs_bit_mask(*x))
+    .collect()
+}
@@ -19,16 +19,26 @@ pub struct BitDistribution {
 
 impl BitDistribution {
     pub fn new<S: Scalar, T: Into<S> + Clone>(data: &[T]) -> Self {
-        if data.is_empty() {
+        Self::from_abs_bit_mask_iter(data.iter().map(|x| make_abs_bit_mask(x.clone().into())))
+    }
+
+    /// Compute the distribution of values from their [`make_abs_bit_mask`]s.
+    ///
+    /// This avoids converting the values again when their masks were already computed, e.g. with
+    /// [`make_abs_bit_masks`](crate::base::bit::make_abs_bit_masks).
+    pub fn from_abs_bit_masks(masks: &[[u64; 4]]) -> Self {
+        Self::from_abs_bit_mask_iter(masks.iter().copied())
+    }
+
+    fn from_abs_bit_mask_iter(mut masks: impl Iterator<Item = [u64; 4]>) -> Self {
+        let Some(mut or_all) = masks.next() else {
             return Self {
                 or_all: [0; 4],
                 vary_mask: [0; 4],
             };
-        }
-        let mut or_all = make_abs_bit_mask(data[0].clone().into());
+        };
         let mut vary_mask = [0; 4];
-        for x in data.iter().skip(1) {
-            let mask = make_abs_bit_mask((*x).clone().into());
+        for mask in masks {
             for i in 0..4 {
                 vary_mask[i] |= or_all[i] ^ mask[i];
                 or_all[i] |= mask[i];
@@ -301,3 +301,21 @@ fn we_can_detect_invalid_bit_distributions() {
     };
     assert!(!dist.is_valid());
 }
+
+#[test]
+fn we_can_compute_the_bit_distribution_of_precomputed_abs_bit_masks() {
+    let data: Vec<Curve25519Scalar> = (-300..300_i64)
+        .map(|x| Curve25519Scalar::from(x * x * x))
+        .collect();
+    let masks = make_abs_bit_masks(&data);
+    assert_eq!(masks.len(), data.len());
+    assert_eq!(masks[7], make_abs_bit_mask(data[7]));
+    assert_eq!(
+        BitDistribution::from_abs_bit_masks(&masks),
+        BitDistribution::new::<Curve25519Scalar, _>(&data)
+    );
+    assert_eq!(
+        BitDistribution::from_abs_bit_masks(&[]),
+        BitDistribution::new::<Curve25519Scalar, Curve25519Scalar>(&[])
+    );
+}
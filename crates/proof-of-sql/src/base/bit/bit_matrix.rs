@@ -1,5 +1,5 @@
 use crate::base::{
-    bit::{make_abs_bit_mask, BitDistribution},
+    bit::{make_abs_bit_masks, BitDistribution},
     scalar::Scalar,
 };
 use bumpalo::Bump;
@@ -16,13 +16,22 @@ pub fn compute_varying_bit_matrix<'a, S: Scalar>(
     vals: &[S],
     dist: &BitDistribution,
 ) -> Vec<&'a [bool]> {
-    let n = vals.len();
+    compute_varying_bit_matrix_from_abs_bit_masks(alloc, &make_abs_bit_masks(vals), dist)
+}
+
+/// Compute the same matrix as [`compute_varying_bit_matrix`] from the
+/// [`make_abs_bit_mask`](crate::base::bit::make_abs_bit_mask)s of the values.
+pub fn compute_varying_bit_matrix_from_abs_bit_masks<'a>(
+    alloc: &'a Bump,
+    masks: &[[u64; 4]],
+    dist: &BitDistribution,
+) -> Vec<&'a [bool]> {
+    let n = masks.len();
     let num_varying_bits = dist.num_varying_bits();
     let data: &'a mut [bool] = alloc.alloc_slice_fill_default(n * num_varying_bits);
 
     // decompose
-    for (i, val) in vals.iter().enumerate() {
-        let mask = make_abs_bit_mask(*val);
+    for (i, mask) in masks.iter().enumerate() {
         let mut offset = i;
         dist.for_each_varying_bit(|int_index: usize, bit_index: usize| {
             data[offset] = (mask[int_index] & (1u64 << bit_index)) != 0;
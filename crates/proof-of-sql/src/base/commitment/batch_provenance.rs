@@ -0,0 +1,408 @@
+use super::ColumnCommitmentsMismatch;
+#[cfg(feature = "signed-proofs")]
+use super::KeccakChecksum;
+#[cfg(feature = "signed-proofs")]
+use crate::base::{database::OwnedTable, scalar::Scalar};
+use core::ops::Range;
+#[cfg(feature = "signed-proofs")]
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// The domain separator that is prepended to the description of a batch before it is signed.
+#[cfg(feature = "signed-proofs")]
+const BATCH_DOMAIN: &[u8] = b"proof-of-sql signed batch v1";
+/// The domain separator of the digest of a [`SignerSet`].
+const SIGNER_SET_DOMAIN: &[u8] = b"proof-of-sql signer set v1";
+
+/// Errors that can occur when appending a signed batch or checking the provenance of a table.
+#[derive(Error, Debug)]
+pub enum BatchProvenanceError {
+    /// A signed batch has to be attested by at least one producer.
+    #[error("the batch is not attested by any producer")]
+    NoAttestations,
+    /// An attestation is not a valid signature of the batch by its signer.
+    #[error("attestation {index} of the batch has an invalid signature")]
+    InvalidSignature {
+        /// The index of the attestation
+        index: usize,
+    },
+    /// The batch does not match the committed table.
+    #[error(transparent)]
+    Mismatch(#[from] ColumnCommitmentsMismatch),
+    /// Some rows of the table were not appended in a signed batch.
+    #[error("rows {rows:?} of the table are not attested")]
+    UnattestedRows {
+        /// The rows that are not attested
+        rows: Range<usize>,
+    },
+    /// Some rows of the table were attested by other producers than the expected ones.
+    #[error("rows {rows:?} of the table are attested by other producers")]
+    UnexpectedSigners {
+        /// The rows that are attested by other producers
+        rows: Range<usize>,
+    },
+}
+
+/// A producer's signature of a batch of rows that is appended to a table.
+///
+/// The signature covers the column names and values of the rows, along with the rows of the
+/// table that they are appended as, so it cannot be replayed for other data or another position
+/// in the table.
+///
+/// Note: Because the struct is deserialized from untrusted data, it cannot maintain any invariant
+/// on its data members; hence, they are all public so as to allow for easy manipulation for
+/// testing.
+#[cfg(feature = "signed-proofs")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchAttestation {
+    /// The public key of the producer.
+    pub signer: VerifyingKey,
+    /// The signature of the batch by the producer.
+    pub signature: Signature,
+}
+
+#[cfg(feature = "signed-proofs")]
+impl BatchAttestation {
+    /// Sign the batch, which is to be appended to a table as the rows starting at `offset`.
+    pub fn sign<S: Scalar>(batch: &OwnedTable<S>, offset: usize, signing_key: &SigningKey) -> Self {
+        Self {
+            signer: signing_key.verifying_key(),
+            signature: signing_key.sign(&batch_message(batch, offset)),
+        }
+    }
+
+    /// Returns whether this is a valid signature of the batch, appended at `offset`, by
+    /// [`BatchAttestation::signer`].
+    pub fn is_valid<S: Scalar>(&self, batch: &OwnedTable<S>, offset: usize) -> bool {
+        self.signer
+            .verify_strict(&batch_message(batch, offset), &self.signature)
+            .is_ok()
+    }
+}
+
+/// Returns the message that the producers of a batch sign.
+#[cfg(feature = "signed-proofs")]
+fn batch_message<S: Scalar>(batch: &OwnedTable<S>, offset: usize) -> Vec<u8> {
+    let mut message = BATCH_DOMAIN.to_vec();
+    message.extend_from_slice(&(offset as u64).to_le_bytes());
+    message.extend_from_slice(&(batch.num_rows() as u64).to_le_bytes());
+    for identifier in batch.column_names() {
+        message.extend_from_slice(&(identifier.as_str().len() as u64).to_le_bytes());
+        message.extend_from_slice(identifier.as_str().as_bytes());
+    }
+    message.extend_from_slice(&KeccakChecksum::from_owned_table(batch).0);
+    message
+}
+
+/// A set of producers, identified by their public keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignerSet(BTreeSet<[u8; 32]>);
+
+impl SignerSet {
+    /// Returns the set of the given producers.
+    #[cfg(feature = "signed-proofs")]
+    pub fn new(signers: impl IntoIterator<Item = VerifyingKey>) -> Self {
+        Self(
+            signers
+                .into_iter()
+                .map(|signer| signer.to_bytes())
+                .collect(),
+        )
+    }
+
+    /// Returns the number of producers in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether the set has no producers.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns whether the producer is in the set.
+    #[cfg(feature = "signed-proofs")]
+    pub fn contains(&self, signer: &VerifyingKey) -> bool {
+        self.0.contains(signer.as_bytes())
+    }
+
+    /// Returns the digest of the set, which does not depend on the order of the producers.
+    pub fn digest(&self) -> SignerSetDigest {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(SIGNER_SET_DOMAIN);
+        for signer in &self.0 {
+            hasher.update(signer);
+        }
+        SignerSetDigest(*hasher.finalize().as_bytes())
+    }
+}
+
+/// The Blake3 digest of a [`SignerSet`], see [`SignerSet::digest`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignerSetDigest(pub [u8; 32]);
+
+/// The record of a signed batch in the metadata of a
+/// [`TableCommitment`](super::TableCommitment): the rows of the batch, and the digest of the
+/// producers that attested them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchProvenance {
+    rows: Range<usize>,
+    signers: SignerSetDigest,
+}
+
+impl BatchProvenance {
+    #[cfg(feature = "signed-proofs")]
+    pub(super) fn new(rows: Range<usize>, signers: SignerSetDigest) -> Self {
+        Self { rows, signers }
+    }
+
+    /// Returns the rows of the table that the batch was appended as.
+    pub fn rows(&self) -> &Range<usize> {
+        &self.rows
+    }
+
+    /// Returns the digest of the producers that attested the batch.
+    pub fn signers(&self) -> SignerSetDigest {
+        self.signers
+    }
+}
+
+/// Check that the attestations are valid signatures of the batch, appended at `offset`, and
+/// return the digest of their signers.
+#[cfg(feature = "signed-proofs")]
+pub(super) fn verify_attestations<S: Scalar>(
+    batch: &OwnedTable<S>,
+    offset: usize,
+    attestations: &[BatchAttestation],
+) -> Result<SignerSetDigest, BatchProvenanceError> {
+    if attestations.is_empty() {
+        return Err(BatchProvenanceError::NoAttestations);
+    }
+    if let Some(index) = attestations
+        .iter()
+        .position(|attestation| !attestation.is_valid(batch, offset))
+    {
+        return Err(BatchProvenanceError::InvalidSignature { index });
+    }
+    Ok(SignerSet::new(attestations.iter().map(|attestation| attestation.signer)).digest())
+}
+
+#[cfg(all(test, feature = "blitzar", feature = "signed-proofs"))]
+mod tests {
+    use super::*;
+    use crate::base::{
+        commitment::{TableCommitment, VersionedTableCommitment},
+        database::owned_table_utility::*,
+        scalar::Curve25519Scalar,
+    };
+    use curve25519_dalek::RistrettoPoint;
+
+    fn rows(a: &[i64], b: &[&str]) -> OwnedTable<Curve25519Scalar> {
+        owned_table([bigint("a", a.to_vec()), varchar("b", b.to_vec())])
+    }
+
+    fn keys() -> [SigningKey; 3] {
+        [1, 2, 3].map(|seed| SigningKey::from_bytes(&[seed; 32]))
+    }
+
+    fn empty_table_commitment() -> TableCommitment<RistrettoPoint> {
+        TableCommitment::from_owned_table_with_offset(&rows(&[], &[]), 0, &())
+    }
+
+    #[test]
+    fn we_can_record_the_producers_of_signed_batches() {
+        let [alice, bob, _] = keys();
+        let signers = SignerSet::new([alice.verifying_key(), bob.verifying_key()]);
+        let mut table_commitment = empty_table_commitment();
+
+        let first = rows(&[1, 2], &["x", "y"]);
+        let second = rows(&[3], &["z"]);
+        table_commitment
+            .try_append_signed_owned_table(
+                &first,
+                &[
+                    BatchAttestation::sign(&first, 0, &alice),
+                    BatchAttestation::sign(&first, 0, &bob),
+                ],
+                &(),
+            )
+            .unwrap();
+        table_commitment
+            .try_append_signed_owned_table(
+                &second,
+                &[
+                    BatchAttestation::sign(&second, 2, &bob),
+                    BatchAttestation::sign(&second, 2, &alice),
+                ],
+                &(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            table_commitment.provenance(),
+            &[
+                BatchProvenance::new(0..2, signers.digest()),
+                BatchProvenance::new(2..3, signers.digest()),
+            ]
+        );
+        assert_eq!(
+            table_commitment.column_commitments().commitments(),
+            TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+                &rows(&[1, 2, 3], &["x", "y", "z"]),
+                0,
+                &()
+            )
+            .column_commitments()
+            .commitments()
+        );
+        table_commitment.verify_provenance(&signers).unwrap();
+        assert!(matches!(
+            table_commitment.verify_provenance(&SignerSet::new([alice.verifying_key()])),
+            Err(BatchProvenanceError::UnexpectedSigners { rows }) if rows == (0..2)
+        ));
+    }
+
+    #[test]
+    fn the_digest_of_a_signer_set_does_not_depend_on_the_order_of_its_producers() {
+        let [alice, bob, carol] = keys();
+        let signers = SignerSet::new([alice.verifying_key(), bob.verifying_key()]);
+        assert_eq!(
+            signers.digest(),
+            SignerSet::new([
+                bob.verifying_key(),
+                alice.verifying_key(),
+                bob.verifying_key()
+            ])
+            .digest()
+        );
+        assert_eq!(signers.len(), 2);
+        assert!(signers.contains(&alice.verifying_key()));
+        assert!(!signers.contains(&carol.verifying_key()));
+        assert_ne!(
+            signers.digest(),
+            SignerSet::new([alice.verifying_key()]).digest()
+        );
+        assert_ne!(signers.digest(), SignerSet::default().digest());
+    }
+
+    #[test]
+    fn we_cannot_append_a_batch_with_invalid_attestations() {
+        let [alice, bob, _] = keys();
+        let mut table_commitment = empty_table_commitment();
+        let batch = rows(&[1, 2], &["x", "y"]);
+        let original = table_commitment.clone();
+
+        assert!(matches!(
+            table_commitment.try_append_signed_owned_table(&batch, &[], &()),
+            Err(BatchProvenanceError::NoAttestations)
+        ));
+        let forged = BatchAttestation {
+            signer: bob.verifying_key(),
+            ..BatchAttestation::sign(&batch, 0, &alice)
+        };
+        for attestation in [
+            forged,
+            BatchAttestation::sign(&batch, 1, &bob),
+            BatchAttestation::sign(&rows(&[1, 3], &["x", "y"]), 0, &bob),
+            BatchAttestation::sign(
+                &owned_table::<Curve25519Scalar>([bigint("a", [1, 2])]),
+                0,
+                &bob,
+            ),
+        ] {
+            assert!(matches!(
+                table_commitment.try_append_signed_owned_table(
+                    &batch,
+                    &[BatchAttestation::sign(&batch, 0, &alice), attestation],
+                    &()
+                ),
+                Err(BatchProvenanceError::InvalidSignature { index: 1 })
+            ));
+        }
+        assert_eq!(table_commitment, original);
+    }
+
+    #[test]
+    fn we_detect_rows_that_are_not_attested() {
+        let [alice, _, _] = keys();
+        let signers = SignerSet::new([alice.verifying_key()]);
+        let mut table_commitment = empty_table_commitment();
+        table_commitment
+            .append_owned_table(&rows(&[1], &["x"]), &())
+            .unwrap();
+        let batch = rows(&[2, 3], &["y", "z"]);
+        table_commitment
+            .try_append_signed_owned_table(
+                &batch,
+                &[BatchAttestation::sign(&batch, 1, &alice)],
+                &(),
+            )
+            .unwrap();
+        assert!(matches!(
+            table_commitment.verify_provenance(&signers),
+            Err(BatchProvenanceError::UnattestedRows { rows }) if rows == (0..1)
+        ));
+
+        let signed = table_commitment
+            .clone()
+            .try_sub(TableCommitment::from_owned_table_with_offset(
+                &rows(&[1], &["x"]),
+                0,
+                &(),
+            ))
+            .unwrap();
+        signed.verify_provenance(&signers).unwrap();
+
+        let attested = table_commitment.clone();
+        table_commitment
+            .append_owned_table(&rows(&[4], &["w"]), &())
+            .unwrap();
+        let unsigned_tail = table_commitment.try_sub(attested).unwrap();
+        assert!(unsigned_tail.provenance().is_empty());
+
+        let mut extended = empty_table_commitment();
+        extended
+            .try_append_signed_owned_table(
+                &batch,
+                &[BatchAttestation::sign(&batch, 0, &alice)],
+                &(),
+            )
+            .unwrap();
+        extended
+            .try_extend_columns(
+                owned_table::<Curve25519Scalar>([bigint("c", [5, 6])]).inner_table(),
+                &(),
+            )
+            .unwrap();
+        assert!(matches!(
+            extended.verify_provenance(&signers),
+            Err(BatchProvenanceError::UnattestedRows { rows }) if rows == (0..2)
+        ));
+    }
+
+    #[test]
+    fn we_can_round_trip_the_provenance_of_a_table_commitment() {
+        let [alice, _, _] = keys();
+        let mut table_commitment = empty_table_commitment();
+        let batch = rows(&[1, 2], &["x", "y"]);
+        table_commitment
+            .try_append_signed_owned_table(
+                &batch,
+                &[BatchAttestation::sign(&batch, 0, &alice)],
+                &(),
+            )
+            .unwrap();
+
+        let versioned = VersionedTableCommitment::from(table_commitment.clone());
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let deserialized = deserialized.into_latest().unwrap();
+        assert_eq!(deserialized.provenance(), table_commitment.provenance());
+        deserialized
+            .verify_provenance(&SignerSet::new([alice.verifying_key()]))
+            .unwrap();
+    }
+}
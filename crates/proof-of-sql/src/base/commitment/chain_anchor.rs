@@ -0,0 +1,69 @@
+use crate::base::database::TableRef;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A reference to a block of an external blockchain, e.g. the block whose state a table
+/// commitment was taken at.
+///
+/// The anchor is not interpreted by this crate. It is recorded in the metadata of a
+/// [`TableCommitment`](super::TableCommitment), so that verifiers can check that the commitments
+/// they verify a query against are those of a specific chain state.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ChainAnchor {
+    block_hash: [u8; 32],
+    block_height: u64,
+    block_timestamp: u64,
+}
+
+impl ChainAnchor {
+    /// Create a reference to the block with the given hash, height and timestamp.
+    ///
+    /// The timestamp is in seconds since the Unix epoch.
+    pub fn new(block_hash: [u8; 32], block_height: u64, block_timestamp: u64) -> Self {
+        Self {
+            block_hash,
+            block_height,
+            block_timestamp,
+        }
+    }
+
+    /// Returns the hash of the block.
+    pub fn block_hash(&self) -> &[u8; 32] {
+        &self.block_hash
+    }
+
+    /// Returns the height of the block.
+    pub fn block_height(&self) -> u64 {
+        self.block_height
+    }
+
+    /// Returns the timestamp of the block, in seconds since the Unix epoch.
+    pub fn block_timestamp(&self) -> u64 {
+        self.block_timestamp
+    }
+}
+
+/// Errors from checking the [`ChainAnchor`]s of the commitments of a query.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ChainAnchorError {
+    /// The commitment of a table is not anchored to any block.
+    #[error("the commitment of table {table_ref} is not anchored to a block")]
+    Unanchored {
+        /// The table whose commitment is not anchored
+        table_ref: TableRef,
+    },
+    /// The commitment of a table is anchored to a different block.
+    #[error(
+        "the commitment of table {table_ref} is anchored to the block at height {} rather than {}",
+        .actual.block_height,
+        .expected.block_height
+    )]
+    Mismatch {
+        /// The table whose commitment is anchored to a different block
+        table_ref: TableRef,
+        /// The block that the commitment was expected to be anchored to
+        expected: ChainAnchor,
+        /// The block that the commitment is anchored to
+        actual: ChainAnchor,
+    },
+}
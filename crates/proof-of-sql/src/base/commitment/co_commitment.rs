@@ -0,0 +1,297 @@
+use super::{
+    AppendColumnCommitmentsError, AppendTableCommitmentError, ColumnCommitmentsMismatch,
+    Commitment, CommittableColumn, TableCommitment, TableCommitmentFromColumnsError,
+};
+use crate::base::{database::OwnedTable, scalar::Scalar};
+use core::ops::Range;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+/// A Keccak-256 hash chain over the rows of a table, which is cheap to maintain in an EVM
+/// contract.
+///
+/// Starting from 32 zero bytes, every row updates the checksum to
+/// `keccak256(checksum ++ word_1 ++ ... ++ word_n)`, where `word_i` is the 32 byte big-endian
+/// encoding of the value of the `i`th column of the row, like `abi.encodePacked` of `int256` or
+/// `uint256` values:
+/// - booleans are `0` or `1`,
+/// - integers and timestamps are sign extended two's complement,
+/// - decimals, scalars and varchars are their canonical scalar, where varchars are hashed to a
+///   scalar like for the commitments.
+///
+/// Since it chains rows rather than batches, the checksum of a table does not depend on how its
+/// rows were appended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeccakChecksum(pub [u8; 32]);
+
+impl KeccakChecksum {
+    /// Returns the checksum of the rows of the table.
+    pub fn from_owned_table<S: Scalar>(owned_table: &OwnedTable<S>) -> Self {
+        let committable_columns: Vec<CommittableColumn> = owned_table
+            .inner_table()
+            .values()
+            .map(CommittableColumn::from)
+            .collect();
+        let mut checksum = Self::default();
+        checksum.append_rows(&committable_columns);
+        checksum
+    }
+
+    /// Chain the rows of the columns, which must have the same length, onto the checksum.
+    pub(crate) fn append_rows(&mut self, committable_columns: &[CommittableColumn]) {
+        let num_rows = committable_columns
+            .first()
+            .map_or(0, CommittableColumn::len);
+        for row in 0..num_rows {
+            let mut hasher = Keccak256::new();
+            hasher.update(self.0);
+            for column in committable_columns {
+                hasher.update(word(column, row));
+            }
+            self.0 = hasher.finalize().into();
+        }
+    }
+}
+
+/// Returns the 32 byte big-endian encoding of an integer.
+fn int_word(value: i128) -> [u8; 32] {
+    let mut word = if value < 0 { [0xff; 32] } else { [0; 32] };
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Returns the 32 byte big-endian encoding of little-endian limbs.
+fn limbs_word(limbs: &[u64; 4]) -> [u8; 32] {
+    let mut word = [0; 32];
+    for (chunk, limb) in word.chunks_exact_mut(8).zip(limbs.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    word
+}
+
+/// Returns the checksum word of a row of the column.
+fn word(column: &CommittableColumn, row: usize) -> [u8; 32] {
+    match column {
+        CommittableColumn::Boolean(col) => int_word(col[row].into()),
+        CommittableColumn::SmallInt(col) => int_word(col[row].into()),
+        CommittableColumn::Int(col) => int_word(col[row].into()),
+        CommittableColumn::BigInt(col) | CommittableColumn::TimestampTZ(_, _, col) => {
+            int_word(col[row].into())
+        }
+        CommittableColumn::Int128(col) => int_word(col[row]),
+        CommittableColumn::Decimal75(_, _, col)
+        | CommittableColumn::Scalar(col)
+        | CommittableColumn::VarChar(col) => limbs_word(&col[row]),
+    }
+}
+
+/// A [`TableCommitment`] along with a [`KeccakChecksum`] of the same rows.
+///
+/// This lets a table be committed to under two schemes at once, e.g. Dory commitments to prove
+/// queries against and a checksum that EVM contracts can check appended rows against. Rows are
+/// only ever appended to both together, so the two always cover the same range.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoCommitment<C: Commitment> {
+    table_commitment: TableCommitment<C>,
+    checksum: KeccakChecksum,
+}
+
+impl<C: Commitment> CoCommitment<C> {
+    /// Returns the [`TableCommitment`] to the rows.
+    pub fn table_commitment(&self) -> &TableCommitment<C> {
+        &self.table_commitment
+    }
+
+    /// Returns the [`KeccakChecksum`] of the rows.
+    pub fn checksum(&self) -> KeccakChecksum {
+        self.checksum
+    }
+
+    /// Returns a reference to the range of rows this type commits to.
+    pub fn range(&self) -> &Range<usize> {
+        self.table_commitment.range()
+    }
+
+    /// Returns a [`CoCommitment`] to the provided columns with the given row offset.
+    ///
+    /// Provided columns must have the same length and no duplicate identifiers.
+    pub fn try_from_columns_with_offset<'a, COL>(
+        columns: impl IntoIterator<Item = (&'a Identifier, COL)>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<Self, TableCommitmentFromColumnsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        let (identifiers, committable_columns): (Vec<&Identifier>, Vec<CommittableColumn>) =
+            columns
+                .into_iter()
+                .map(|(identifier, column)| (identifier, column.into()))
+                .unzip();
+        let table_commitment = TableCommitment::try_from_columns_with_offset(
+            identifiers
+                .into_iter()
+                .zip(committable_columns.iter().cloned()),
+            offset,
+            setup,
+        )?;
+        let mut checksum = KeccakChecksum::default();
+        checksum.append_rows(&committable_columns);
+        Ok(Self {
+            table_commitment,
+            checksum,
+        })
+    }
+
+    /// Returns a [`CoCommitment`] to the provided table with the given row offset.
+    pub fn from_owned_table_with_offset<S: Scalar>(
+        owned_table: &OwnedTable<S>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Self {
+        Self::try_from_columns_with_offset(owned_table.inner_table(), offset, setup)
+            .expect("OwnedTables cannot have columns of mixed length or duplicate identifiers")
+    }
+
+    /// Append rows of data from the provided columns to both the commitment and the checksum.
+    ///
+    /// On error, neither of them is changed.
+    /// See [`TableCommitment::try_append_rows`] for the possible errors.
+    pub fn try_append_rows<'a, COL>(
+        &mut self,
+        columns: impl IntoIterator<Item = (&'a Identifier, COL)>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), AppendTableCommitmentError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        let (identifiers, committable_columns): (Vec<&Identifier>, Vec<CommittableColumn>) =
+            columns
+                .into_iter()
+                .map(|(identifier, column)| (identifier, column.into()))
+                .unzip();
+        self.table_commitment.try_append_rows(
+            identifiers
+                .into_iter()
+                .zip(committable_columns.iter().cloned()),
+            setup,
+        )?;
+        self.checksum.append_rows(&committable_columns);
+        Ok(())
+    }
+
+    /// Append data of the provided table to both the commitment and the checksum.
+    ///
+    /// Will error on a variety of mismatches.
+    /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
+    pub fn append_owned_table<S: Scalar>(
+        &mut self,
+        owned_table: &OwnedTable<S>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), ColumnCommitmentsMismatch> {
+        self.try_append_rows(owned_table.inner_table(), setup)
+            .map_err(|e| match e {
+                AppendTableCommitmentError::AppendColumnCommitments(e) => match e {
+                    AppendColumnCommitmentsError::Mismatch(e) => e,
+                    AppendColumnCommitmentsError::DuplicateIdentifiers(_) => {
+                        panic!("OwnedTables cannot have duplicate identifiers");
+                    }
+                },
+                AppendTableCommitmentError::MixedLengthColumns(_) => {
+                    panic!("OwnedTables cannot have columns of mixed length");
+                }
+            })
+    }
+}
+
+#[cfg(all(test, feature = "blitzar"))]
+mod tests {
+    use super::*;
+    use crate::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+    use curve25519_dalek::RistrettoPoint;
+    use proof_of_sql_parser::utility::ident;
+
+    fn rows(a: &[i64], b: &[&str]) -> OwnedTable<Curve25519Scalar> {
+        owned_table([bigint("a", a.to_vec()), varchar("b", b.to_vec())])
+    }
+
+    #[test]
+    fn we_can_append_rows_to_the_commitment_and_the_checksum_together() {
+        let mut co_commitment = CoCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &rows(&[1], &["x"]),
+            2,
+            &(),
+        );
+        co_commitment
+            .append_owned_table(&rows(&[2, 3], &["y", "z"]), &())
+            .unwrap();
+        co_commitment
+            .append_owned_table(&rows(&[4], &["x"]), &())
+            .unwrap();
+
+        let table = rows(&[1, 2, 3, 4], &["x", "y", "z", "x"]);
+        assert_eq!(
+            co_commitment,
+            CoCommitment::from_owned_table_with_offset(&table, 2, &())
+        );
+        assert_eq!(
+            co_commitment.table_commitment(),
+            &TableCommitment::from_owned_table_with_offset(&table, 2, &())
+        );
+        assert_eq!(
+            co_commitment.checksum(),
+            KeccakChecksum::from_owned_table(&table)
+        );
+        assert_eq!(co_commitment.range(), &(2..6));
+    }
+
+    #[test]
+    fn the_checksum_chains_the_encoded_rows() {
+        let table: OwnedTable<Curve25519Scalar> =
+            owned_table([bigint("a", [-1, 2]), boolean("b", [true, false])]);
+        let mut first_row = [0; 32 * 3];
+        first_row[32..64].copy_from_slice(&[0xff; 32]);
+        first_row[95] = 1;
+        let checksum: [u8; 32] = Keccak256::digest(first_row).into();
+        let mut second_row = [0; 32 * 3];
+        second_row[..32].copy_from_slice(&checksum);
+        second_row[63] = 2;
+        let checksum: [u8; 32] = Keccak256::digest(second_row).into();
+        assert_eq!(
+            KeccakChecksum::from_owned_table(&table),
+            KeccakChecksum(checksum)
+        );
+
+        let reordered: OwnedTable<Curve25519Scalar> =
+            owned_table([bigint("a", [2, -1]), boolean("b", [false, true])]);
+        assert_ne!(
+            KeccakChecksum::from_owned_table(&reordered),
+            KeccakChecksum(checksum)
+        );
+        assert_eq!(
+            KeccakChecksum::from_owned_table(&rows(&[], &[])),
+            KeccakChecksum::default()
+        );
+    }
+
+    #[test]
+    fn we_cannot_append_mismatched_rows_to_either_scheme() {
+        let mut co_commitment = CoCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &rows(&[1], &["x"]),
+            0,
+            &(),
+        );
+        let original = co_commitment.clone();
+        assert!(matches!(
+            co_commitment.append_owned_table(&owned_table([bigint("a", [2])]), &()),
+            Err(ColumnCommitmentsMismatch::NumColumns)
+        ));
+        let (a, b) = (ident("a"), ident("b"));
+        assert!(matches!(
+            co_commitment.try_append_rows([(&a, &[2_i64, 3][..]), (&b, &[4_i64][..])], &()),
+            Err(AppendTableCommitmentError::MixedLengthColumns(_))
+        ));
+        assert_eq!(co_commitment, original);
+    }
+}
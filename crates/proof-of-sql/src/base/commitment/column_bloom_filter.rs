@@ -0,0 +1,376 @@
+use super::CommittableColumn;
+use crate::base::{database::LiteralValue, scalar::Scalar};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when constructing invalid [`BloomFilterParams`] or [`ColumnBloomFilter`]s.
+#[derive(Debug, Error, PartialEq)]
+pub enum InvalidBloomFilterParams {
+    /// A bloom filter must have at least one bit.
+    #[error("bloom filters must have at least one bit")]
+    NoBits,
+    /// A bloom filter must use at least one hash function.
+    #[error("bloom filters must use at least one hash function")]
+    NoHashes,
+    /// The target false positive rate is not strictly between 0 and 1.
+    #[error("false positive rate must be strictly between 0 and 1, got {0}")]
+    FalsePositiveRateOutOfRange(f64),
+}
+
+/// Bloom filters with different parameters cannot be combined.
+#[derive(Debug, Error)]
+#[error("bloom filters with different parameters cannot be unioned")]
+pub struct BloomFilterParamsMismatch;
+
+/// The size and number of hash functions of a [`ColumnBloomFilter`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BloomFilterParams {
+    num_words: usize,
+    num_hashes: u32,
+}
+
+impl BloomFilterParams {
+    /// Construct new [`BloomFilterParams`].
+    ///
+    /// `num_bits` is rounded up to a multiple of 64.
+    pub fn try_new(num_bits: usize, num_hashes: u32) -> Result<Self, InvalidBloomFilterParams> {
+        if num_bits == 0 {
+            return Err(InvalidBloomFilterParams::NoBits);
+        }
+        if num_hashes == 0 {
+            return Err(InvalidBloomFilterParams::NoHashes);
+        }
+        Ok(Self {
+            num_words: num_bits.div_ceil(u64::BITS as usize),
+            num_hashes,
+        })
+    }
+
+    /// Construct the [`BloomFilterParams`] that achieve the given false positive rate once
+    /// `expected_items` distinct values have been inserted.
+    pub fn for_false_positive_rate(
+        expected_items: usize,
+        false_positive_rate: f64,
+    ) -> Result<Self, InvalidBloomFilterParams> {
+        if !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(InvalidBloomFilterParams::FalsePositiveRateOutOfRange(
+                false_positive_rate,
+            ));
+        }
+        let n = expected_items.max(1) as f64;
+        let ln_2 = core::f64::consts::LN_2;
+        let num_bits = (-n * false_positive_rate.ln() / (ln_2 * ln_2)).ceil();
+        let num_hashes = (num_bits / n * ln_2).round().max(1.0);
+        Self::try_new(num_bits as usize, num_hashes as u32)
+    }
+
+    /// Returns the number of bits of a filter with these parameters.
+    pub fn num_bits(&self) -> usize {
+        self.num_words * u64::BITS as usize
+    }
+
+    /// Returns the number of hash functions of a filter with these parameters.
+    pub fn num_hashes(&self) -> u32 {
+        self.num_hashes
+    }
+}
+
+/// The result of looking up a value in a [`ColumnBloomFilter`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BloomFilterLookup {
+    /// The value does not occur in the column.
+    DefinitelyAbsent,
+    /// The value may occur in the column, so a query proof is needed to decide.
+    PossiblyPresent {
+        /// The probability that a value absent from the column would also have been reported as
+        /// possibly present, estimated from the fill ratio of the filter.
+        false_positive_rate: f64,
+    },
+}
+
+/// A bloom filter over the values of a column, computed at commitment time.
+///
+/// Like [`ColumnBounds`](super::ColumnBounds), this is metadata about committed data that the
+/// verifier trusts because it comes from the same source as the commitments. It should be stored
+/// alongside the [`TableCommitment`](super::TableCommitment) it was computed with.
+/// This lets a verifier settle that a value is absent from a column (e.g. for `WHERE a = 5`)
+/// without any query proof, while values that are reported as possibly present still need one.
+///
+/// Values are inserted as the scalars they are committed as, so the filter must be built and
+/// queried with the same [`Scalar`] as the commitments.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawColumnBloomFilter")]
+pub struct ColumnBloomFilter {
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+/// [`ColumnBloomFilter`] before its invariants have been checked.
+#[derive(Deserialize)]
+struct RawColumnBloomFilter {
+    num_hashes: u32,
+    bits: Vec<u64>,
+}
+
+impl TryFrom<RawColumnBloomFilter> for ColumnBloomFilter {
+    type Error = InvalidBloomFilterParams;
+
+    fn try_from(raw: RawColumnBloomFilter) -> Result<Self, Self::Error> {
+        BloomFilterParams::try_new(raw.bits.len() * u64::BITS as usize, raw.num_hashes)?;
+        Ok(ColumnBloomFilter {
+            num_hashes: raw.num_hashes,
+            bits: raw.bits,
+        })
+    }
+}
+
+impl ColumnBloomFilter {
+    /// Construct an empty [`ColumnBloomFilter`].
+    pub fn new(params: BloomFilterParams) -> Self {
+        ColumnBloomFilter {
+            num_hashes: params.num_hashes,
+            bits: vec![0; params.num_words],
+        }
+    }
+
+    /// Construct a [`ColumnBloomFilter`] containing every value of a column.
+    pub fn from_column<S: Scalar>(column: &CommittableColumn, params: BloomFilterParams) -> Self {
+        let mut filter = Self::new(params);
+        filter.insert_column::<S>(column);
+        filter
+    }
+
+    /// Returns the parameters of this filter.
+    pub fn params(&self) -> BloomFilterParams {
+        BloomFilterParams {
+            num_words: self.bits.len(),
+            num_hashes: self.num_hashes,
+        }
+    }
+
+    /// Insert every value of a column, e.g. of rows being appended to the committed table.
+    pub fn insert_column<S: Scalar>(&mut self, column: &CommittableColumn) {
+        match column {
+            CommittableColumn::Boolean(values) => {
+                values.iter().for_each(|v| self.insert_scalar(S::from(v)))
+            }
+            CommittableColumn::SmallInt(values) => {
+                values.iter().for_each(|v| self.insert_scalar(S::from(v)))
+            }
+            CommittableColumn::Int(values) => {
+                values.iter().for_each(|v| self.insert_scalar(S::from(v)))
+            }
+            CommittableColumn::BigInt(values) | CommittableColumn::TimestampTZ(_, _, values) => {
+                values.iter().for_each(|v| self.insert_scalar(S::from(v)))
+            }
+            CommittableColumn::Int128(values) => {
+                values.iter().for_each(|v| self.insert_scalar(S::from(v)))
+            }
+            CommittableColumn::Decimal75(_, _, limbs)
+            | CommittableColumn::Scalar(limbs)
+            | CommittableColumn::VarChar(limbs) => {
+                limbs.iter().for_each(|limbs| self.insert_limbs(limbs))
+            }
+        }
+    }
+
+    /// Insert a single value given as the scalar it is committed as.
+    pub fn insert_scalar<S: Scalar>(&mut self, value: S) {
+        self.insert_limbs(&value.into());
+    }
+
+    fn insert_limbs(&mut self, limbs: &[u64; 4]) {
+        for index in self.bit_indexes(limbs) {
+            self.bits[index / u64::BITS as usize] |= 1 << (index % u64::BITS as usize);
+        }
+    }
+
+    fn contains_limbs(&self, limbs: &[u64; 4]) -> bool {
+        self.bit_indexes(limbs).all(|index| {
+            self.bits[index / u64::BITS as usize] & (1 << (index % u64::BITS as usize)) != 0
+        })
+    }
+
+    /// Double hashing of a blake3 digest, as described by Kirsch and Mitzenmacher.
+    fn bit_indexes(&self, limbs: &[u64; 4]) -> impl Iterator<Item = usize> {
+        let mut hasher = blake3::Hasher::new();
+        for limb in limbs {
+            hasher.update(&limb.to_le_bytes());
+        }
+        let hash = hasher.finalize();
+        let (h1, h2) = hash.as_bytes().split_at(8);
+        let h1 = u64::from_le_bytes(h1.try_into().expect("the first 8 bytes are a u64"));
+        // The step must be nonzero, or every hash function would pick the same bit.
+        let h2 = u64::from_le_bytes(h2[..8].try_into().expect("the next 8 bytes are a u64")) | 1;
+        let num_bits = (self.bits.len() * u64::BITS as usize) as u64;
+        (0..u64::from(self.num_hashes))
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+    }
+
+    /// Returns true if the value, given as the scalar it is committed as, may occur in the column.
+    pub fn might_contain_scalar<S: Scalar>(&self, value: S) -> bool {
+        self.contains_limbs(&value.into())
+    }
+
+    /// Look up a literal in this filter.
+    ///
+    /// If the literal is [`BloomFilterLookup::DefinitelyAbsent`], any predicate requiring the
+    /// column to equal it selects no rows. Otherwise the caller has to fall back to a query proof.
+    pub fn lookup<S: Scalar>(&self, value: &LiteralValue<S>) -> BloomFilterLookup {
+        if self.might_contain_scalar(value.to_scalar()) {
+            BloomFilterLookup::PossiblyPresent {
+                false_positive_rate: self.estimated_false_positive_rate(),
+            }
+        } else {
+            BloomFilterLookup::DefinitelyAbsent
+        }
+    }
+
+    /// Estimate the false positive rate of this filter from the fraction of bits that are set.
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        let set_bits: u32 = self.bits.iter().map(|word| word.count_ones()).sum();
+        let fill_ratio = f64::from(set_bits) / (self.bits.len() * u64::BITS as usize) as f64;
+        fill_ratio.powi(self.num_hashes as i32)
+    }
+
+    /// Combine two filters as if their source collections are being unioned.
+    ///
+    /// There is no counterpart for differences, since values cannot be removed from a bloom filter.
+    pub fn try_union(self, other: Self) -> Result<Self, BloomFilterParamsMismatch> {
+        if self.params() != other.params() {
+            return Err(BloomFilterParamsMismatch);
+        }
+        let bits = self
+            .bits
+            .iter()
+            .zip(&other.bits)
+            .map(|(a, b)| a | b)
+            .collect();
+        Ok(ColumnBloomFilter {
+            num_hashes: self.num_hashes,
+            bits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{database::OwnedColumn, scalar::Curve25519Scalar};
+
+    fn varchar_column(values: &[&str]) -> OwnedColumn<Curve25519Scalar> {
+        OwnedColumn::VarChar(values.iter().map(ToString::to_string).collect())
+    }
+
+    #[test]
+    fn we_can_construct_params_for_a_false_positive_rate() {
+        let params = BloomFilterParams::for_false_positive_rate(1000, 0.01).unwrap();
+        // the optimal filter has ~9586 bits and 7 hash functions
+        assert_eq!(params.num_bits(), 9600);
+        assert_eq!(params.num_hashes(), 7);
+
+        assert_eq!(
+            BloomFilterParams::try_new(0, 1),
+            Err(InvalidBloomFilterParams::NoBits)
+        );
+        assert_eq!(
+            BloomFilterParams::try_new(1, 0),
+            Err(InvalidBloomFilterParams::NoHashes)
+        );
+        assert_eq!(BloomFilterParams::try_new(65, 1).unwrap().num_bits(), 128);
+        for rate in [0.0, 1.0, -0.5, f64::NAN] {
+            assert!(matches!(
+                BloomFilterParams::for_false_positive_rate(10, rate),
+                Err(InvalidBloomFilterParams::FalsePositiveRateOutOfRange(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn we_never_report_inserted_values_as_absent() {
+        let params = BloomFilterParams::for_false_positive_rate(100, 0.01).unwrap();
+        let values: Vec<i64> = (0..100).map(|i| i * 7 - 300).collect();
+        let filter = ColumnBloomFilter::from_column::<Curve25519Scalar>(
+            &CommittableColumn::BigInt(&values),
+            params,
+        );
+        for value in values {
+            assert!(matches!(
+                filter.lookup(&LiteralValue::<Curve25519Scalar>::BigInt(value)),
+                BloomFilterLookup::PossiblyPresent { .. }
+            ));
+        }
+
+        let strings = varchar_column(&["a", "bc", "ünïcödé"]);
+        let filter = ColumnBloomFilter::from_column::<Curve25519Scalar>(&(&strings).into(), params);
+        assert!(filter.might_contain_scalar(Curve25519Scalar::from("ünïcödé")));
+    }
+
+    #[test]
+    fn we_can_rule_out_most_absent_values() {
+        let params = BloomFilterParams::for_false_positive_rate(1000, 0.01).unwrap();
+        let values: Vec<i64> = (0..1000).collect();
+        let filter = ColumnBloomFilter::from_column::<Curve25519Scalar>(
+            &CommittableColumn::BigInt(&values),
+            params,
+        );
+        let false_positives = (1000..11000i64)
+            .filter(|&value| filter.might_contain_scalar(Curve25519Scalar::from(value)))
+            .count();
+        assert!(false_positives < 300);
+
+        let estimated_rate = filter.estimated_false_positive_rate();
+        assert!(estimated_rate > 0.001 && estimated_rate < 0.03);
+        assert_eq!(
+            ColumnBloomFilter::new(params).estimated_false_positive_rate(),
+            0.0
+        );
+        assert_eq!(
+            ColumnBloomFilter::new(params).lookup(&LiteralValue::<Curve25519Scalar>::BigInt(0)),
+            BloomFilterLookup::DefinitelyAbsent
+        );
+    }
+
+    #[test]
+    fn we_can_union_filters_of_appended_rows() {
+        let params = BloomFilterParams::try_new(256, 3).unwrap();
+        let filter_a = ColumnBloomFilter::from_column::<Curve25519Scalar>(
+            &CommittableColumn::Int(&[1, 2]),
+            params,
+        );
+        let filter_b = ColumnBloomFilter::from_column::<Curve25519Scalar>(
+            &CommittableColumn::Int(&[3]),
+            params,
+        );
+        let union = filter_a.try_union(filter_b).unwrap();
+        let expected = ColumnBloomFilter::from_column::<Curve25519Scalar>(
+            &CommittableColumn::Int(&[1, 2, 3]),
+            params,
+        );
+        assert_eq!(union, expected);
+
+        let other_params = BloomFilterParams::try_new(512, 3).unwrap();
+        assert!(union
+            .try_union(ColumnBloomFilter::new(other_params))
+            .is_err());
+    }
+
+    #[test]
+    fn we_can_round_trip_a_filter_and_reject_invalid_serialized_filters() {
+        let params = BloomFilterParams::try_new(128, 2).unwrap();
+        let filter = ColumnBloomFilter::from_column::<Curve25519Scalar>(
+            &CommittableColumn::SmallInt(&[4, 5]),
+            params,
+        );
+        let bytes = postcard::to_allocvec(&filter).unwrap();
+        assert_eq!(
+            postcard::from_bytes::<ColumnBloomFilter>(&bytes).unwrap(),
+            filter
+        );
+
+        let no_bits = serde_json::json!({ "num_hashes": 2, "bits": [] });
+        assert!(serde_json::from_value::<ColumnBloomFilter>(no_bits).is_err());
+        let no_hashes = serde_json::json!({ "num_hashes": 0, "bits": [1] });
+        assert!(serde_json::from_value::<ColumnBloomFilter>(no_hashes).is_err());
+    }
+}
@@ -285,6 +285,102 @@ impl ColumnBounds {
     }
 }
 
+/// How the bounds of a column are recorded in the metadata of its commitment.
+///
+/// Tighter bounds allow for smaller proofs of inequalities on the column, while looser bounds
+/// reveal less about the data and change less often as rows are appended.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundsStrategy {
+    /// The exact minimum and maximum of the data.
+    #[default]
+    Exact,
+    /// The smallest range of a signed integer of some bit width, i.e. `[-2^k, 2^k - 1]`, that
+    /// contains the data.
+    Padded,
+    /// The widest bounds of the column type, no matter the data.
+    Max,
+}
+
+impl BoundsStrategy {
+    /// Apply this strategy to the exact bounds of a column.
+    ///
+    /// Columns without order have no bounds, so they are left as they are.
+    pub fn apply(self, bounds: ColumnBounds) -> ColumnBounds {
+        match (self, bounds) {
+            (BoundsStrategy::Exact, _) | (_, ColumnBounds::NoOrder) => bounds,
+            (BoundsStrategy::Padded, ColumnBounds::SmallInt(bounds)) => {
+                ColumnBounds::SmallInt(padded_bounds(bounds))
+            }
+            (BoundsStrategy::Padded, ColumnBounds::Int(bounds)) => {
+                ColumnBounds::Int(padded_bounds(bounds))
+            }
+            (BoundsStrategy::Padded, ColumnBounds::BigInt(bounds)) => {
+                ColumnBounds::BigInt(padded_bounds(bounds))
+            }
+            (BoundsStrategy::Padded, ColumnBounds::Int128(bounds)) => {
+                ColumnBounds::Int128(padded_bounds(bounds))
+            }
+            (BoundsStrategy::Padded, ColumnBounds::TimestampTZ(bounds)) => {
+                ColumnBounds::TimestampTZ(padded_bounds(bounds))
+            }
+            (BoundsStrategy::Max, ColumnBounds::SmallInt(_)) => {
+                ColumnBounds::SmallInt(max_bounds())
+            }
+            (BoundsStrategy::Max, ColumnBounds::Int(_)) => ColumnBounds::Int(max_bounds()),
+            (BoundsStrategy::Max, ColumnBounds::BigInt(_)) => ColumnBounds::BigInt(max_bounds()),
+            (BoundsStrategy::Max, ColumnBounds::Int128(_)) => ColumnBounds::Int128(max_bounds()),
+            (BoundsStrategy::Max, ColumnBounds::TimestampTZ(_)) => {
+                ColumnBounds::TimestampTZ(max_bounds())
+            }
+        }
+    }
+}
+
+/// Widen the bounds to the smallest range `[-2^k, 2^k - 1]` that contains them.
+fn padded_bounds<T>(bounds: Bounds<T>) -> Bounds<T>
+where
+    T: Ord + Copy + Into<i128> + TryFrom<i128>,
+{
+    match bounds {
+        Bounds::Empty => Bounds::Empty,
+        Bounds::Bounded(inner) | Bounds::Sharp(inner) => {
+            // `!value` maps `-2^k` to `2^k - 1`, so both bounds are covered by the same `k`.
+            let magnitude = [*inner.min(), *inner.max()]
+                .into_iter()
+                .map(|value| {
+                    let value: i128 = value.into();
+                    if value < 0 {
+                        !value
+                    } else {
+                        value
+                    }
+                })
+                .max()
+                .expect("there are two bounds");
+            let max = ((1u128 << (128 - magnitude.leading_zeros())) - 1) as i128;
+            let convert = |value: i128| match T::try_from(value) {
+                Ok(value) => value,
+                Err(_) => panic!("padded bounds fit into the type of the original bounds"),
+            };
+            Bounds::Bounded(BoundsInner {
+                min: convert(!max),
+                max: convert(max),
+            })
+        }
+    }
+}
+
+/// Returns the widest bounds of the type.
+fn max_bounds<T>() -> Bounds<T>
+where
+    T: Ord + num_traits::Bounded,
+{
+    Bounds::Bounded(BoundsInner {
+        min: T::min_value(),
+        max: T::max_value(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -679,4 +775,48 @@ mod tests {
         assert!(smallint.try_difference(timestamp).is_err());
         assert!(timestamp.try_difference(smallint).is_err());
     }
+
+    #[test]
+    fn we_can_apply_bounds_strategies() {
+        let bigint = ColumnBounds::BigInt(Bounds::sharp(-3, 100).unwrap());
+        assert_eq!(BoundsStrategy::Exact.apply(bigint), bigint);
+        assert_eq!(
+            BoundsStrategy::Padded.apply(bigint),
+            ColumnBounds::BigInt(Bounds::bounded(-128, 127).unwrap())
+        );
+        assert_eq!(
+            BoundsStrategy::Max.apply(bigint),
+            ColumnBounds::BigInt(Bounds::bounded(i64::MIN, i64::MAX).unwrap())
+        );
+
+        let smallint = ColumnBounds::SmallInt(Bounds::sharp(-129, -5).unwrap());
+        assert_eq!(
+            BoundsStrategy::Padded.apply(smallint),
+            ColumnBounds::SmallInt(Bounds::bounded(-256, 255).unwrap())
+        );
+        let int = ColumnBounds::Int(Bounds::sharp(0, 0).unwrap());
+        assert_eq!(
+            BoundsStrategy::Padded.apply(int),
+            ColumnBounds::Int(Bounds::bounded(-1, 0).unwrap())
+        );
+        let int128 = ColumnBounds::Int128(Bounds::sharp(i128::MIN, 0).unwrap());
+        assert_eq!(
+            BoundsStrategy::Padded.apply(int128),
+            ColumnBounds::Int128(Bounds::bounded(i128::MIN, i128::MAX).unwrap())
+        );
+        let timestamp = ColumnBounds::TimestampTZ(Bounds::Empty);
+        assert_eq!(BoundsStrategy::Padded.apply(timestamp), timestamp);
+        assert_eq!(
+            BoundsStrategy::Max.apply(timestamp),
+            ColumnBounds::TimestampTZ(Bounds::bounded(i64::MIN, i64::MAX).unwrap())
+        );
+
+        for strategy in [
+            BoundsStrategy::Exact,
+            BoundsStrategy::Padded,
+            BoundsStrategy::Max,
+        ] {
+            assert_eq!(strategy.apply(ColumnBounds::NoOrder), ColumnBounds::NoOrder);
+        }
+    }
 }
@@ -1,5 +1,7 @@
-use super::{column_bounds::BoundsInner, committable_column::CommittableColumn, ColumnBounds};
-use crate::base::database::ColumnType;
+use super::{
+    column_bounds::BoundsInner, committable_column::CommittableColumn, BoundsStrategy, ColumnBounds,
+};
+use crate::base::database::{ColumnType, VarCharNormalization};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use thiserror::Error;
@@ -10,12 +12,26 @@ pub enum InvalidColumnCommitmentMetadata {
     /// Column of this type cannot have these bounds.
     #[error("column of type {0} cannot have bounds like {1:?}")]
     TypeBoundsMismatch(ColumnType, ColumnBounds),
+    /// Only VarChar columns can be normalized.
+    #[error("column of type {0} cannot have a varchar normalization")]
+    NormalizationOfNonVarChar(ColumnType),
 }
 
 /// During column operation, metadata indicates that the operand columns cannot be the same.
 #[derive(Debug, Error)]
-#[error("column with type {0} cannot operate with column with type {1}")]
-pub struct ColumnCommitmentMetadataMismatch(ColumnType, ColumnType);
+pub enum ColumnCommitmentMetadataMismatch {
+    /// The operand columns have different types.
+    #[error("column with type {0} cannot operate with column with type {1}")]
+    ColumnType(ColumnType, ColumnType),
+    /// The strings of the operand columns were normalized differently.
+    #[error("column with normalization {0:?} cannot operate with column with normalization {1:?}")]
+    VarCharNormalization(VarCharNormalization, VarCharNormalization),
+    /// The bounds of the operand columns were committed with different strategies.
+    #[error(
+        "column with bounds strategy {0:?} cannot operate with column with bounds strategy {1:?}"
+    )]
+    BoundsStrategy(BoundsStrategy, BoundsStrategy),
+}
 
 const EXPECT_BOUNDS_MATCH_MESSAGE: &str = "we've already checked the column types match, which is a stronger requirement (mapping of type variants to bounds variants is surjective)";
 
@@ -24,6 +40,8 @@ const EXPECT_BOUNDS_MATCH_MESSAGE: &str = "we've already checked the column type
 pub struct ColumnCommitmentMetadata {
     column_type: ColumnType,
     bounds: ColumnBounds,
+    varchar_normalization: VarCharNormalization,
+    bounds_strategy: BoundsStrategy,
 }
 
 impl ColumnCommitmentMetadata {
@@ -50,6 +68,8 @@ impl ColumnCommitmentMetadata {
             ) => Ok(ColumnCommitmentMetadata {
                 column_type,
                 bounds,
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }),
             _ => Err(InvalidColumnCommitmentMetadata::TypeBoundsMismatch(
                 column_type,
@@ -83,7 +103,63 @@ impl ColumnCommitmentMetadata {
             )),
             _ => ColumnBounds::NoOrder,
         };
-        Self::try_new(column_type, bounds).expect("default bounds for column type are valid")
+        Self::try_new(column_type, bounds)
+            .expect("default bounds for column type are valid")
+            .with_bounds_strategy(BoundsStrategy::Max)
+    }
+
+    /// Record that the strings of this column were normalized before they were committed.
+    ///
+    /// Will error if the column is not a VarChar column, unless `varchar_normalization` is
+    /// [`VarCharNormalization::None`].
+    pub fn try_with_varchar_normalization(
+        self,
+        varchar_normalization: VarCharNormalization,
+    ) -> Result<ColumnCommitmentMetadata, InvalidColumnCommitmentMetadata> {
+        match (self.column_type, varchar_normalization) {
+            (ColumnType::VarChar, _) | (_, VarCharNormalization::None) => {
+                Ok(ColumnCommitmentMetadata {
+                    varchar_normalization,
+                    ..self
+                })
+            }
+            _ => Err(InvalidColumnCommitmentMetadata::NormalizationOfNonVarChar(
+                self.column_type,
+            )),
+        }
+    }
+
+    /// Widen the bounds of this column according to the given strategy, and record the strategy
+    /// so that the bounds of appended rows are widened the same way.
+    ///
+    /// Columns without an order have no bounds to widen, so they always keep
+    /// [`BoundsStrategy::Exact`].
+    pub fn with_bounds_strategy(self, bounds_strategy: BoundsStrategy) -> ColumnCommitmentMetadata {
+        if self.bounds == ColumnBounds::NoOrder {
+            return self;
+        }
+        ColumnCommitmentMetadata {
+            bounds: bounds_strategy.apply(self.bounds),
+            bounds_strategy,
+            ..self
+        }
+    }
+
+    /// Record the strategy that the stored bounds were already widened with.
+    ///
+    /// Unlike [`ColumnCommitmentMetadata::with_bounds_strategy`], the bounds are left as they
+    /// are, so this is only meant for restoring metadata from an older layout.
+    pub(super) fn with_recorded_bounds_strategy(
+        self,
+        bounds_strategy: BoundsStrategy,
+    ) -> ColumnCommitmentMetadata {
+        if self.bounds == ColumnBounds::NoOrder {
+            return self;
+        }
+        ColumnCommitmentMetadata {
+            bounds_strategy,
+            ..self
+        }
     }
 
     #[cfg(test)]
@@ -91,6 +167,11 @@ impl ColumnCommitmentMetadata {
         &mut self.bounds
     }
 
+    #[cfg(test)]
+    pub(super) fn bounds_strategy_mut(&mut self) -> &mut BoundsStrategy {
+        &mut self.bounds_strategy
+    }
+
     /// Immutable reference to this column's type.
     pub fn column_type(&self) -> &ColumnType {
         &self.column_type
@@ -101,12 +182,49 @@ impl ColumnCommitmentMetadata {
         &self.bounds
     }
 
+    /// The normalization applied to this column's strings before they were committed.
+    pub fn varchar_normalization(&self) -> VarCharNormalization {
+        self.varchar_normalization
+    }
+
+    /// The strategy this column's bounds were committed with.
+    pub fn bounds_strategy(&self) -> BoundsStrategy {
+        self.bounds_strategy
+    }
+
     /// Contruct a [`ColumnCommitmentMetadata`] by analyzing a column.
     pub fn from_column(column: &CommittableColumn) -> ColumnCommitmentMetadata {
         ColumnCommitmentMetadata {
             column_type: column.column_type(),
             bounds: ColumnBounds::from_column(column),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
+        }
+    }
+
+    fn check_operable_with(
+        &self,
+        other: &ColumnCommitmentMetadata,
+    ) -> Result<(), ColumnCommitmentMetadataMismatch> {
+        if self.column_type != other.column_type {
+            return Err(ColumnCommitmentMetadataMismatch::ColumnType(
+                self.column_type,
+                other.column_type,
+            ));
+        }
+        if self.varchar_normalization != other.varchar_normalization {
+            return Err(ColumnCommitmentMetadataMismatch::VarCharNormalization(
+                self.varchar_normalization,
+                other.varchar_normalization,
+            ));
+        }
+        if self.bounds_strategy != other.bounds_strategy {
+            return Err(ColumnCommitmentMetadataMismatch::BoundsStrategy(
+                self.bounds_strategy,
+                other.bounds_strategy,
+            ));
         }
+        Ok(())
     }
 
     /// Combine two [`ColumnCommitmentMetadata`] as if their source collections are being unioned.
@@ -116,12 +234,7 @@ impl ColumnCommitmentMetadata {
         self,
         other: ColumnCommitmentMetadata,
     ) -> Result<ColumnCommitmentMetadata, ColumnCommitmentMetadataMismatch> {
-        if self.column_type != other.column_type {
-            return Err(ColumnCommitmentMetadataMismatch(
-                self.column_type,
-                other.column_type,
-            ));
-        }
+        self.check_operable_with(&other)?;
 
         let bounds = self
             .bounds
@@ -129,8 +242,8 @@ impl ColumnCommitmentMetadata {
             .expect(EXPECT_BOUNDS_MATCH_MESSAGE);
 
         Ok(ColumnCommitmentMetadata {
-            bounds,
-            column_type: self.column_type,
+            bounds: self.bounds_strategy.apply(bounds),
+            ..self
         })
     }
 
@@ -142,12 +255,7 @@ impl ColumnCommitmentMetadata {
         self,
         other: ColumnCommitmentMetadata,
     ) -> Result<ColumnCommitmentMetadata, ColumnCommitmentMetadataMismatch> {
-        if self.column_type != other.column_type {
-            return Err(ColumnCommitmentMetadataMismatch(
-                self.column_type,
-                other.column_type,
-            ));
-        }
+        self.check_operable_with(&other)?;
 
         let bounds = self
             .bounds
@@ -155,8 +263,8 @@ impl ColumnCommitmentMetadata {
             .expect(EXPECT_BOUNDS_MATCH_MESSAGE);
 
         Ok(ColumnCommitmentMetadata {
-            bounds,
-            column_type: self.column_type,
+            bounds: self.bounds_strategy.apply(bounds),
+            ..self
         })
     }
 }
@@ -181,7 +289,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::SmallInt,
-                bounds: ColumnBounds::SmallInt(Bounds::Empty)
+                bounds: ColumnBounds::SmallInt(Bounds::Empty),
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -190,7 +300,9 @@ mod tests {
                 .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Int,
-                bounds: ColumnBounds::Int(Bounds::Empty)
+                bounds: ColumnBounds::Int(Bounds::Empty),
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -202,7 +314,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::BigInt,
-                bounds: ColumnBounds::BigInt(Bounds::Empty)
+                bounds: ColumnBounds::BigInt(Bounds::Empty),
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -211,6 +325,8 @@ mod tests {
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Boolean,
                 bounds: ColumnBounds::NoOrder,
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -223,6 +339,8 @@ mod tests {
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Decimal75(Precision::new(10).unwrap(), 0),
                 bounds: ColumnBounds::NoOrder,
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -235,6 +353,8 @@ mod tests {
             ColumnCommitmentMetadata {
                 column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
                 bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -246,7 +366,9 @@ mod tests {
             .unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::Int128,
-                bounds: ColumnBounds::Int128(Bounds::sharp(-5, 10).unwrap())
+                bounds: ColumnBounds::Int128(Bounds::sharp(-5, 10).unwrap()),
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
 
@@ -254,7 +376,9 @@ mod tests {
             ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap(),
             ColumnCommitmentMetadata {
                 column_type: ColumnType::VarChar,
-                bounds: ColumnBounds::NoOrder
+                bounds: ColumnBounds::NoOrder,
+                varchar_normalization: VarCharNormalization::None,
+                bounds_strategy: BoundsStrategy::Exact,
             }
         );
     }
@@ -454,6 +578,8 @@ mod tests {
         let boolean_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Boolean,
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         assert_eq!(
             boolean_metadata.try_union(boolean_metadata).unwrap(),
@@ -463,6 +589,8 @@ mod tests {
         let decimal_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Decimal75(Precision::new(12).unwrap(), 0),
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         assert_eq!(
             decimal_metadata.try_union(decimal_metadata).unwrap(),
@@ -472,6 +600,8 @@ mod tests {
         let varchar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::VarChar,
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         assert_eq!(
             varchar_metadata.try_union(varchar_metadata).unwrap(),
@@ -481,6 +611,8 @@ mod tests {
         let scalar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Scalar,
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         assert_eq!(
             scalar_metadata.try_union(scalar_metadata).unwrap(),
@@ -707,34 +839,50 @@ mod tests {
         let boolean_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Boolean,
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let varchar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::VarChar,
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let scalar_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Scalar,
             bounds: ColumnBounds::NoOrder,
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let smallint_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::SmallInt,
             bounds: ColumnBounds::SmallInt(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let int_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Int,
             bounds: ColumnBounds::Int(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let bigint_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::BigInt,
             bounds: ColumnBounds::BigInt(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let int128_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Int128,
             bounds: ColumnBounds::Int128(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
         let decimal75_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Decimal75(Precision::new(4).unwrap(), 8),
             bounds: ColumnBounds::Int128(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
 
         assert!(smallint_metadata.try_union(scalar_metadata).is_err());
@@ -839,6 +987,8 @@ mod tests {
         let different_decimal75_metadata = ColumnCommitmentMetadata {
             column_type: ColumnType::Decimal75(Precision::new(75).unwrap(), 0),
             bounds: ColumnBounds::Int128(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
 
         assert!(decimal75_metadata
@@ -858,11 +1008,15 @@ mod tests {
         let timestamp_tz_metadata_a = ColumnCommitmentMetadata {
             column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
             bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
 
         let timestamp_tz_metadata_b = ColumnCommitmentMetadata {
             column_type: ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::Utc),
             bounds: ColumnBounds::TimestampTZ(Bounds::Empty),
+            varchar_normalization: VarCharNormalization::None,
+            bounds_strategy: BoundsStrategy::Exact,
         };
 
         // Tests for union operations
@@ -893,4 +1047,96 @@ mod tests {
             .try_difference(timestamp_tz_metadata_a)
             .is_err());
     }
+
+    #[test]
+    fn we_can_record_varchar_normalization_and_cannot_operate_on_mismatched_normalizations() {
+        let plain =
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap();
+        let nfc = plain
+            .try_with_varchar_normalization(VarCharNormalization::Nfc)
+            .unwrap();
+        assert_eq!(plain.varchar_normalization(), VarCharNormalization::None);
+        assert_eq!(nfc.varchar_normalization(), VarCharNormalization::Nfc);
+        assert_eq!(nfc.try_union(nfc).unwrap(), nfc);
+        assert_eq!(nfc.try_difference(nfc).unwrap(), nfc);
+        assert!(matches!(
+            nfc.try_union(plain),
+            Err(ColumnCommitmentMetadataMismatch::VarCharNormalization(
+                VarCharNormalization::Nfc,
+                VarCharNormalization::None
+            ))
+        ));
+        assert!(matches!(
+            plain.try_difference(nfc),
+            Err(ColumnCommitmentMetadataMismatch::VarCharNormalization(..))
+        ));
+
+        let bigint = ColumnCommitmentMetadata::from_column_type_with_max_bounds(ColumnType::BigInt);
+        assert_eq!(
+            bigint
+                .try_with_varchar_normalization(VarCharNormalization::None)
+                .unwrap(),
+            bigint
+        );
+        assert!(matches!(
+            bigint.try_with_varchar_normalization(VarCharNormalization::Nfkc),
+            Err(InvalidColumnCommitmentMetadata::NormalizationOfNonVarChar(
+                ColumnType::BigInt
+            ))
+        ));
+    }
+
+    #[test]
+    fn we_can_record_the_bounds_strategy_of_metadata() {
+        let exact = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::sharp(-3, 9).unwrap()),
+        )
+        .unwrap();
+        assert_eq!(exact.bounds_strategy(), BoundsStrategy::Exact);
+
+        let padded = exact.with_bounds_strategy(BoundsStrategy::Padded);
+        assert_eq!(padded.bounds_strategy(), BoundsStrategy::Padded);
+        assert_eq!(
+            padded.bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-16, 15).unwrap())
+        );
+
+        // the union is widened again, since its bounds may have grown
+        let other = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::sharp(0, 20).unwrap()),
+        )
+        .unwrap()
+        .with_bounds_strategy(BoundsStrategy::Padded);
+        assert_eq!(
+            padded.try_union(other).unwrap().bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-32, 31).unwrap())
+        );
+        assert!(matches!(
+            padded.try_union(exact),
+            Err(ColumnCommitmentMetadataMismatch::BoundsStrategy(
+                BoundsStrategy::Padded,
+                BoundsStrategy::Exact
+            ))
+        ));
+        assert!(matches!(
+            exact.try_difference(padded),
+            Err(ColumnCommitmentMetadataMismatch::BoundsStrategy(..))
+        ));
+
+        let max = ColumnCommitmentMetadata::from_column_type_with_max_bounds(ColumnType::BigInt);
+        assert_eq!(max.bounds_strategy(), BoundsStrategy::Max);
+        assert_eq!(exact.with_bounds_strategy(BoundsStrategy::Max), max);
+
+        // columns without an order have no bounds to widen
+        let varchar =
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap();
+        assert_eq!(varchar.with_bounds_strategy(BoundsStrategy::Max), varchar);
+        assert_eq!(
+            ColumnCommitmentMetadata::from_column_type_with_max_bounds(ColumnType::VarChar)
+                .bounds_strategy(),
+            BoundsStrategy::Exact
+        );
+    }
 }
@@ -2,7 +2,7 @@ use super::{
     column_commitment_metadata::ColumnCommitmentMetadataMismatch, ColumnCommitmentMetadata,
     CommittableColumn,
 };
-use crate::base::database::ColumnField;
+use crate::base::database::{ColumnField, VarCharNormalization};
 use indexmap::IndexMap;
 use proof_of_sql_parser::Identifier;
 use thiserror::Error;
@@ -24,6 +24,13 @@ pub enum ColumnCommitmentsMismatch {
     /// Strings are used here instead of Identifiers to decrease the size of this variant
     #[error("column with identifier {0} cannot operate with column with identifier {1}")]
     Identifier(String, String),
+    /// The strings of the column were normalized in a way that this build cannot apply, see
+    /// [`VarCharNormalization::is_supported`].
+    #[error(
+        "column {0} was committed with {1:?} normalization, which requires the \
+         `varchar-normalization` feature"
+    )]
+    UnsupportedVarCharNormalization(String, VarCharNormalization),
 }
 
 /// Extension trait intended for [`ColumnCommitmentMetadataMap`].
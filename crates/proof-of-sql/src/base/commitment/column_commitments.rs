@@ -1,6 +1,7 @@
 use super::{
     committable_column::CommittableColumn, ColumnCommitmentMetadata, ColumnCommitmentMetadataMap,
-    ColumnCommitmentMetadataMapExt, ColumnCommitmentsMismatch, Commitment, VecCommitmentExt,
+    ColumnCommitmentMetadataMapExt, ColumnCommitmentsMismatch, Commitment, SchemaEvolutionError,
+    VecCommitmentExt,
 };
 use crate::base::database::{ColumnField, ColumnRef, CommitmentAccessor, TableRef};
 use indexmap::IndexSet;
@@ -142,6 +143,11 @@ impl<C: Commitment> ColumnCommitments<C> {
     /// The given generator offset will be used for committing to the new rows.
     /// You most likely want this to be equal to the 0-indexed row number of the first new row.
     ///
+    /// The strings of new rows of VarChar columns must already be normalized like the existing
+    /// rows, see [`ColumnCommitmentMetadata::varchar_normalization`]. The bounds of new rows are
+    /// widened with the strategy of the existing rows, see
+    /// [`ColumnCommitmentMetadata::bounds_strategy`].
+    ///
     /// Will error on a variety of mismatches.
     /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
     pub fn try_append_rows_with_offset<'a, COL>(
@@ -179,6 +185,26 @@ impl<C: Commitment> ColumnCommitments<C> {
             identifiers.into_iter().zip(committable_columns.iter()),
         );
 
+        // New rows inherit the normalization and bounds strategy of the existing rows.
+        // A mismatch in column type is reported by the union below.
+        let column_metadata = column_metadata
+            .into_iter()
+            .map(|(identifier, metadata)| {
+                let existing = self.column_metadata.get(&identifier);
+                let varchar_normalization = existing
+                    .map(ColumnCommitmentMetadata::varchar_normalization)
+                    .unwrap_or_default();
+                let bounds_strategy = existing
+                    .map(ColumnCommitmentMetadata::bounds_strategy)
+                    .unwrap_or_default();
+                let metadata = metadata
+                    .try_with_varchar_normalization(varchar_normalization)
+                    .unwrap_or(metadata)
+                    .with_bounds_strategy(bounds_strategy);
+                (identifier, metadata)
+            })
+            .collect::<ColumnCommitmentMetadataMap>();
+
         self.column_metadata = self.column_metadata.to_owned().try_union(column_metadata)?;
 
         self.commitments
@@ -227,6 +253,45 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(())
     }
 
+    /// Rename a column, keeping its commitment and position.
+    pub fn try_rename_column(
+        &mut self,
+        from: &Identifier,
+        to: Identifier,
+    ) -> Result<(), SchemaEvolutionError> {
+        if !self.column_metadata.contains_key(from) {
+            return Err(SchemaEvolutionError::MissingColumn(*from));
+        }
+        if self.column_metadata.contains_key(&to) {
+            Err(DuplicateIdentifiers(to.to_string()))?;
+        }
+        self.column_metadata = core::mem::take(&mut self.column_metadata)
+            .into_iter()
+            .map(|(identifier, metadata)| {
+                if identifier == *from {
+                    (to, metadata)
+                } else {
+                    (identifier, metadata)
+                }
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Remove a column along with its commitment, keeping the order of the other columns.
+    ///
+    /// Returns the metadata and commitment of the removed column.
+    pub fn try_drop_column(
+        &mut self,
+        identifier: &Identifier,
+    ) -> Result<(ColumnCommitmentMetadata, C), SchemaEvolutionError> {
+        let (index, _, metadata) = self
+            .column_metadata
+            .shift_remove_full(identifier)
+            .ok_or(SchemaEvolutionError::MissingColumn(*identifier))?;
+        Ok((metadata, self.commitments.remove(index)))
+    }
+
     /// Add two [`ColumnCommitments`] together.
     ///
     /// Will error on a variety of mismatches.
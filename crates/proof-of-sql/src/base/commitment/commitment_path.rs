@@ -0,0 +1,394 @@
+use super::{Commitment, CommittableColumn};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The largest number of columns that are packed into a single multi-scalar multiplication by
+/// default.
+///
+/// `blitzar` packs the columns of a call side by side, so the packed data of very wide tables
+/// can exceed what it supports. Wider tables are committed to in batches of columns.
+pub const DEFAULT_MAX_PACKED_COLUMNS: usize = 64;
+
+/// The largest number of bytes of column data that are moved to the GPU for a single
+/// multi-scalar multiplication by default.
+///
+/// Long tables whose packed columns take up more than this are committed to in chunks of rows
+/// that fit, rather than running the device out of memory.
+pub const DEFAULT_MAX_GPU_BYTES: usize = 1 << 31;
+
+/// A way of computing commitments to columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CommitmentPath {
+    /// Multi-scalar multiplications on the CPU.
+    ///
+    /// Commitment schemes without a dedicated CPU implementation use
+    /// [`Commitment::compute_commitments`], which `blitzar` runs on its CPU backend when there is
+    /// no GPU.
+    Cpu,
+    /// Multi-scalar multiplications with `blitzar`, which packs the columns for the GPU.
+    ///
+    /// Without the `blitzar` feature, this falls back to [`CommitmentPath::Cpu`].
+    PackedGpu,
+    /// Commit to the rows in chunks and add up the commitments to the chunks.
+    ///
+    /// This bounds the memory that the multi-scalar multiplications of large tables need at once.
+    ChunkedStreaming,
+}
+
+/// The size of the columns that commitments are computed for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentWorkload {
+    /// The number of columns
+    pub num_columns: usize,
+    /// The length of the longest column
+    pub num_rows: usize,
+    /// The number of bytes that one row of all columns takes up in committable form
+    pub row_width: usize,
+}
+
+impl CommitmentWorkload {
+    /// Returns the workload of committing to the given columns.
+    pub fn new(committable_columns: &[CommittableColumn]) -> Self {
+        Self {
+            num_columns: committable_columns.len(),
+            num_rows: committable_columns
+                .iter()
+                .map(CommittableColumn::len)
+                .max()
+                .unwrap_or(0),
+            row_width: committable_columns
+                .iter()
+                .map(CommittableColumn::element_size)
+                .sum(),
+        }
+    }
+
+    fn num_elements(&self) -> usize {
+        self.num_rows.saturating_mul(self.num_columns)
+    }
+
+    fn num_bytes(&self) -> usize {
+        self.num_rows.saturating_mul(self.row_width)
+    }
+}
+
+/// The hardware that commitments can be computed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommitmentEnvironment {
+    /// Whether multi-scalar multiplications can run on a GPU
+    pub gpu_available: bool,
+}
+
+impl CommitmentEnvironment {
+    /// Detects the environment of this process.
+    ///
+    /// A GPU is considered available if the `blitzar` feature is enabled, `blitzar` has not been
+    /// pointed at its CPU backend with `BLITZAR_BACKEND=cpu`, and an NVIDIA device is present.
+    pub fn detect() -> Self {
+        Self {
+            gpu_available: cfg!(feature = "blitzar")
+                && std::env::var("BLITZAR_BACKEND").as_deref() != Ok("cpu")
+                && Path::new("/dev/nvidiactl").exists(),
+        }
+    }
+}
+
+/// Configuration of the prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverConfig {
+    /// The path to compute commitments on, or `None` to select one for each workload
+    pub commitment_path: Option<CommitmentPath>,
+    /// The smallest number of elements for which the GPU is worth the cost of moving data to it
+    pub min_gpu_elements: usize,
+    /// The largest number of bytes that are committed to without splitting the rows into chunks
+    pub max_unchunked_bytes: usize,
+    /// The number of rows in each chunk of [`CommitmentPath::ChunkedStreaming`]
+    pub chunk_rows: usize,
+    /// The largest number of columns that are packed into a single multi-scalar multiplication
+    #[serde(default = "default_max_packed_columns")]
+    pub max_packed_columns: usize,
+    /// The largest number of bytes of column data in a single multi-scalar multiplication on the
+    /// GPU
+    #[serde(default = "default_max_gpu_bytes")]
+    pub max_gpu_bytes: usize,
+}
+
+fn default_max_packed_columns() -> usize {
+    DEFAULT_MAX_PACKED_COLUMNS
+}
+
+fn default_max_gpu_bytes() -> usize {
+    DEFAULT_MAX_GPU_BYTES
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            commitment_path: None,
+            min_gpu_elements: 1 << 12,
+            max_unchunked_bytes: 1 << 30,
+            chunk_rows: 1 << 20,
+            max_packed_columns: DEFAULT_MAX_PACKED_COLUMNS,
+            max_gpu_bytes: DEFAULT_MAX_GPU_BYTES,
+        }
+    }
+}
+
+/// The path that commitments were computed on, along with what it was selected for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentPathReport {
+    /// The workload that the path was selected for
+    pub workload: CommitmentWorkload,
+    /// Whether the path was set in the [`ProverConfig`] rather than selected automatically
+    pub overridden: bool,
+    /// The path that was taken
+    pub path: CommitmentPath,
+    /// The path that each chunk was committed on if the rows were committed to in chunks
+    ///
+    /// This is always the case for [`CommitmentPath::ChunkedStreaming`], and for
+    /// [`CommitmentPath::PackedGpu`] if the columns do not fit in
+    /// [`ProverConfig::max_gpu_bytes`].
+    pub chunk_path: Option<CommitmentPath>,
+    /// The number of chunks that were committed to
+    pub num_chunks: usize,
+    /// The number of batches of columns that each chunk was committed to in
+    #[serde(default)]
+    pub num_column_batches: usize,
+}
+
+/// Selects the path to compute commitments on, unless the configuration overrides it.
+///
+/// Workloads that take up more than [`ProverConfig::max_unchunked_bytes`] are chunked. Otherwise
+/// the GPU is used if it is available and the workload is large enough to be worth it.
+pub fn select_commitment_path(
+    workload: &CommitmentWorkload,
+    environment: &CommitmentEnvironment,
+    config: &ProverConfig,
+) -> CommitmentPath {
+    if let Some(path) = config.commitment_path {
+        path
+    } else if workload.num_bytes() > config.max_unchunked_bytes
+        && workload.num_rows > config.chunk_rows
+    {
+        CommitmentPath::ChunkedStreaming
+    } else if environment.gpu_available && workload.num_elements() >= config.min_gpu_elements {
+        CommitmentPath::PackedGpu
+    } else {
+        CommitmentPath::Cpu
+    }
+}
+
+/// Replaces paths that this build cannot take with the one that is taken instead.
+fn available_path(path: CommitmentPath) -> CommitmentPath {
+    match path {
+        CommitmentPath::PackedGpu if cfg!(not(feature = "blitzar")) => CommitmentPath::Cpu,
+        _ => path,
+    }
+}
+
+/// Computes the commitments on the path, with at most `max_packed_columns` columns in each
+/// multi-scalar multiplication, and returns the number of batches of columns.
+fn compute_commitments_on_path<C: Commitment>(
+    path: CommitmentPath,
+    commitments: &mut [C],
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+    max_packed_columns: usize,
+) -> usize {
+    let max_packed_columns = max_packed_columns.max(1);
+    for (commitments, committable_columns) in commitments
+        .chunks_mut(max_packed_columns)
+        .zip(committable_columns.chunks(max_packed_columns))
+    {
+        match path {
+            CommitmentPath::Cpu => {
+                C::compute_commitments_on_cpu(commitments, committable_columns, offset, setup);
+            }
+            CommitmentPath::PackedGpu | CommitmentPath::ChunkedStreaming => {
+                C::compute_commitments(commitments, committable_columns, offset, setup);
+            }
+        }
+    }
+    committable_columns.len().div_ceil(max_packed_columns)
+}
+
+/// Returns the largest number of rows whose columns fit in `max_gpu_bytes` when they are packed
+/// in batches of at most `max_packed_columns` columns.
+fn max_gpu_chunk_rows(
+    committable_columns: &[CommittableColumn],
+    max_packed_columns: usize,
+    max_gpu_bytes: usize,
+) -> usize {
+    let batch_row_width = committable_columns
+        .chunks(max_packed_columns.max(1))
+        .map(|batch| batch.iter().map(CommittableColumn::element_size).sum())
+        .max()
+        .unwrap_or(0);
+    (max_gpu_bytes / batch_row_width.max(1)).max(1)
+}
+
+/// Computes the commitments on the path in chunks of `chunk_rows` rows, and adds up the
+/// commitments to the chunks.
+///
+/// Returns the number of chunks and the number of batches of columns that each chunk was
+/// committed to in.
+#[allow(clippy::too_many_arguments)]
+fn compute_commitments_in_chunks<C: Commitment>(
+    path: CommitmentPath,
+    commitments: &mut [C],
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+    num_rows: usize,
+    chunk_rows: usize,
+    max_packed_columns: usize,
+) -> (usize, usize) {
+    let chunk_rows = chunk_rows.max(1);
+    let mut num_chunks = 0;
+    let mut num_column_batches = 0;
+    for start in (0..num_rows).step_by(chunk_rows) {
+        let chunk: Vec<_> = committable_columns
+            .iter()
+            .map(|column| column.slice(start..start + chunk_rows))
+            .collect();
+        let mut chunk_commitments = vec![C::default(); chunk.len()];
+        num_column_batches = compute_commitments_on_path(
+            path,
+            &mut chunk_commitments,
+            &chunk,
+            offset + start,
+            setup,
+            max_packed_columns,
+        );
+        commitments
+            .iter_mut()
+            .zip(chunk_commitments)
+            .for_each(|(commitment, chunk_commitment)| *commitment += chunk_commitment);
+        num_chunks += 1;
+    }
+    (num_chunks, num_column_batches)
+}
+
+/// Computes the commitments to the given columns, committing to at most
+/// [`DEFAULT_MAX_PACKED_COLUMNS`] of them, and at most [`DEFAULT_MAX_GPU_BYTES`] of their data,
+/// at once.
+///
+/// Each commitment only depends on its own column, and is the sum of the commitments to chunks of
+/// its rows at their offsets, so the commitments are the same as the ones computed by
+/// [`Commitment::compute_commitments`] for all columns at once.
+pub(super) fn compute_commitments_in_column_batches<C: Commitment>(
+    commitments: &mut [C],
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+) {
+    let num_rows = CommitmentWorkload::new(committable_columns).num_rows;
+    let chunk_rows = max_gpu_chunk_rows(
+        committable_columns,
+        DEFAULT_MAX_PACKED_COLUMNS,
+        DEFAULT_MAX_GPU_BYTES,
+    );
+    if num_rows > chunk_rows {
+        compute_commitments_in_chunks(
+            CommitmentPath::PackedGpu,
+            commitments,
+            committable_columns,
+            offset,
+            setup,
+            num_rows,
+            chunk_rows,
+            DEFAULT_MAX_PACKED_COLUMNS,
+        );
+    } else {
+        compute_commitments_on_path(
+            CommitmentPath::PackedGpu,
+            commitments,
+            committable_columns,
+            offset,
+            setup,
+            DEFAULT_MAX_PACKED_COLUMNS,
+        );
+    }
+}
+
+/// Computes the commitments to the given columns, and reports the path that they were computed on.
+///
+/// The path is selected with [`select_commitment_path`]. Chunks are committed to on the GPU if it
+/// is available and on the CPU otherwise. Tables with more than
+/// [`ProverConfig::max_packed_columns`] columns are committed to in batches of columns, and the
+/// rows of tables that take up more than [`ProverConfig::max_gpu_bytes`] on the GPU are committed
+/// to in chunks that fit.
+///
+/// `blitzar` overlaps the transfer of each multi-scalar multiplication's data to the device with
+/// its computation, so smaller chunks only add the cost of launching more of them.
+pub fn compute_commitments_with_config<C: Commitment>(
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+    setup: &C::PublicSetup<'_>,
+    environment: &CommitmentEnvironment,
+    config: &ProverConfig,
+) -> (Vec<C>, CommitmentPathReport) {
+    let workload = CommitmentWorkload::new(committable_columns);
+    let path = available_path(select_commitment_path(&workload, environment, config));
+    let max_gpu_chunk_rows = max_gpu_chunk_rows(
+        committable_columns,
+        config.max_packed_columns,
+        config.max_gpu_bytes,
+    );
+    let chunking = match path {
+        CommitmentPath::ChunkedStreaming => {
+            let chunk_path = available_path(if environment.gpu_available {
+                CommitmentPath::PackedGpu
+            } else {
+                CommitmentPath::Cpu
+            });
+            let chunk_rows = if chunk_path == CommitmentPath::PackedGpu {
+                config.chunk_rows.min(max_gpu_chunk_rows)
+            } else {
+                config.chunk_rows
+            };
+            Some((chunk_path, chunk_rows))
+        }
+        CommitmentPath::PackedGpu if workload.num_rows > max_gpu_chunk_rows => {
+            Some((CommitmentPath::PackedGpu, max_gpu_chunk_rows))
+        }
+        CommitmentPath::PackedGpu | CommitmentPath::Cpu => None,
+    };
+    let mut commitments = vec![C::default(); committable_columns.len()];
+    let (chunk_path, num_chunks, num_column_batches) =
+        if let Some((chunk_path, chunk_rows)) = chunking {
+            let (num_chunks, num_column_batches) = compute_commitments_in_chunks(
+                chunk_path,
+                &mut commitments,
+                committable_columns,
+                offset,
+                setup,
+                workload.num_rows,
+                chunk_rows,
+                config.max_packed_columns,
+            );
+            (Some(chunk_path), num_chunks, num_column_batches)
+        } else {
+            let num_column_batches = compute_commitments_on_path(
+                path,
+                &mut commitments,
+                committable_columns,
+                offset,
+                setup,
+                config.max_packed_columns,
+            );
+            (None, 1, num_column_batches)
+        };
+    let report = CommitmentPathReport {
+        workload,
+        overridden: config.commitment_path.is_some(),
+        path,
+        chunk_path,
+        num_chunks,
+        num_column_batches,
+    };
+    #[cfg(feature = "tracing")]
+    tracing::debug!(?report, "computed commitments");
+    (commitments, report)
+}
@@ -0,0 +1,291 @@
+use super::{
+    compute_commitments_with_config, select_commitment_path, CommitmentEnvironment, CommitmentPath,
+    CommitmentWorkload, CommittableColumn, ProverConfig, VecCommitmentExt,
+};
+use crate::proof_primitive::dory::{
+    DoryCommitment, DoryProverPublicSetup, ProverSetup, PublicParameters,
+};
+use ark_std::test_rng;
+
+const NO_GPU: CommitmentEnvironment = CommitmentEnvironment {
+    gpu_available: false,
+};
+const GPU: CommitmentEnvironment = CommitmentEnvironment {
+    gpu_available: true,
+};
+
+fn workload(num_rows: usize) -> CommitmentWorkload {
+    CommitmentWorkload {
+        num_columns: 2,
+        num_rows,
+        row_width: 16,
+    }
+}
+
+#[test]
+fn we_can_get_the_workload_of_columns() {
+    let workload = CommitmentWorkload::new(&[
+        CommittableColumn::BigInt(&[1, 2, 3]),
+        CommittableColumn::Boolean(&[true]),
+        CommittableColumn::VarChar(vec![[0; 4]; 2]),
+    ]);
+    assert_eq!(
+        workload,
+        CommitmentWorkload {
+            num_columns: 3,
+            num_rows: 3,
+            row_width: 8 + 1 + 32,
+        }
+    );
+    assert_eq!(
+        CommitmentWorkload::new(&[]),
+        CommitmentWorkload {
+            num_columns: 0,
+            num_rows: 0,
+            row_width: 0,
+        }
+    );
+}
+
+#[test]
+fn we_can_select_a_commitment_path_for_a_workload() {
+    let config = ProverConfig {
+        min_gpu_elements: 100,
+        max_unchunked_bytes: 1000,
+        chunk_rows: 10,
+        ..Default::default()
+    };
+    // Small workloads are not worth moving to the GPU
+    assert_eq!(
+        select_commitment_path(&workload(49), &GPU, &config),
+        CommitmentPath::Cpu
+    );
+    assert_eq!(
+        select_commitment_path(&workload(50), &GPU, &config),
+        CommitmentPath::PackedGpu
+    );
+    assert_eq!(
+        select_commitment_path(&workload(50), &NO_GPU, &config),
+        CommitmentPath::Cpu
+    );
+    // Large workloads are chunked whether or not there is a GPU
+    assert_eq!(
+        select_commitment_path(&workload(63), &GPU, &config),
+        CommitmentPath::ChunkedStreaming
+    );
+    assert_eq!(
+        select_commitment_path(&workload(63), &NO_GPU, &config),
+        CommitmentPath::ChunkedStreaming
+    );
+}
+
+#[test]
+fn we_can_override_the_commitment_path() {
+    let config = ProverConfig {
+        commitment_path: Some(CommitmentPath::Cpu),
+        ..Default::default()
+    };
+    assert_eq!(
+        select_commitment_path(&workload(1 << 40), &GPU, &config),
+        CommitmentPath::Cpu
+    );
+}
+
+#[test]
+fn we_get_the_same_dory_commitments_on_every_path() {
+    let public_parameters = PublicParameters::rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let setup = DoryProverPublicSetup::new(&prover_setup, 2);
+    let bigints = [1, -2, 3, 4, -5, 6, 7];
+    let columns = [
+        CommittableColumn::BigInt(&bigints),
+        CommittableColumn::Boolean(&[true, false, true]),
+        CommittableColumn::VarChar(vec![[1, 2, 3, 4]; 5]),
+    ];
+
+    let (expected, report) = compute_commitments_with_config::<DoryCommitment>(
+        &columns,
+        3,
+        &setup,
+        &NO_GPU,
+        &ProverConfig::default(),
+    );
+    assert_eq!(report.path, CommitmentPath::Cpu);
+    assert!(!report.overridden);
+    assert_eq!(report.num_chunks, 1);
+
+    for path in [CommitmentPath::PackedGpu, CommitmentPath::ChunkedStreaming] {
+        let config = ProverConfig {
+            commitment_path: Some(path),
+            chunk_rows: 2,
+            ..Default::default()
+        };
+        let (commitments, report) = compute_commitments_with_config::<DoryCommitment>(
+            &columns, 3, &setup, &NO_GPU, &config,
+        );
+        assert_eq!(commitments, expected);
+        assert!(report.overridden);
+        assert_eq!(report.workload, CommitmentWorkload::new(&columns));
+    }
+
+    let config = ProverConfig {
+        commitment_path: Some(CommitmentPath::ChunkedStreaming),
+        chunk_rows: 2,
+        ..Default::default()
+    };
+    let (_, report) =
+        compute_commitments_with_config::<DoryCommitment>(&columns, 3, &setup, &NO_GPU, &config);
+    assert_eq!(report.path, CommitmentPath::ChunkedStreaming);
+    assert_eq!(report.chunk_path, Some(CommitmentPath::Cpu));
+    assert_eq!(report.num_chunks, 4);
+}
+
+#[cfg(feature = "blitzar")]
+#[test]
+fn we_get_the_same_ristretto_commitments_when_chunking() {
+    use curve25519_dalek::RistrettoPoint;
+
+    let columns = [
+        CommittableColumn::Int128(&[1, -2, 3, 4, -5]),
+        CommittableColumn::Scalar(vec![[5, 0, 0, 0]; 4]),
+    ];
+    let (expected, _) = compute_commitments_with_config::<RistrettoPoint>(
+        &columns,
+        1,
+        &(),
+        &NO_GPU,
+        &ProverConfig::default(),
+    );
+    let config = ProverConfig {
+        commitment_path: Some(CommitmentPath::ChunkedStreaming),
+        chunk_rows: 3,
+        ..Default::default()
+    };
+    let (commitments, report) =
+        compute_commitments_with_config::<RistrettoPoint>(&columns, 1, &(), &NO_GPU, &config);
+    assert_eq!(commitments, expected);
+    assert_eq!(report.num_chunks, 2);
+}
+
+#[test]
+fn we_get_the_same_commitments_when_batching_the_columns_of_wide_tables() {
+    let public_parameters = PublicParameters::rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let setup = DoryProverPublicSetup::new(&prover_setup, 2);
+    let data: Vec<Vec<i64>> = (0..150).map(|i| vec![i, -i, 2 * i]).collect();
+    let columns: Vec<_> = data
+        .iter()
+        .map(|column| CommittableColumn::BigInt(column))
+        .collect();
+
+    let unbatched_config = ProverConfig {
+        max_packed_columns: usize::MAX,
+        ..Default::default()
+    };
+    let (expected, report) = compute_commitments_with_config::<DoryCommitment>(
+        &columns,
+        1,
+        &setup,
+        &NO_GPU,
+        &unbatched_config,
+    );
+    assert_eq!(report.num_column_batches, 1);
+
+    let (commitments, report) = compute_commitments_with_config::<DoryCommitment>(
+        &columns,
+        1,
+        &setup,
+        &NO_GPU,
+        &ProverConfig::default(),
+    );
+    assert_eq!(commitments, expected);
+    assert_eq!(report.num_column_batches, 3);
+
+    let config = ProverConfig {
+        commitment_path: Some(CommitmentPath::ChunkedStreaming),
+        chunk_rows: 2,
+        max_packed_columns: 100,
+        ..Default::default()
+    };
+    let (commitments, report) =
+        compute_commitments_with_config::<DoryCommitment>(&columns, 1, &setup, &NO_GPU, &config);
+    assert_eq!(commitments, expected);
+    assert_eq!(report.num_chunks, 2);
+    assert_eq!(report.num_column_batches, 2);
+
+    assert_eq!(
+        Vec::<DoryCommitment>::from_commitable_columns_with_offset(&columns, 1, &setup),
+        expected
+    );
+}
+
+#[cfg(feature = "blitzar")]
+#[test]
+fn we_get_the_same_commitments_when_chunking_the_rows_to_fit_gpu_memory() {
+    let public_parameters = PublicParameters::rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let setup = DoryProverPublicSetup::new(&prover_setup, 2);
+    let columns = [
+        CommittableColumn::BigInt(&[1, -2, 3, 4, -5, 6, 7]),
+        CommittableColumn::Int(&[8, 9, -10]),
+        CommittableColumn::Boolean(&[true, false, true, true]),
+    ];
+    let (expected, _) = compute_commitments_with_config::<DoryCommitment>(
+        &columns,
+        2,
+        &setup,
+        &NO_GPU,
+        &ProverConfig::default(),
+    );
+
+    // Each row takes up 13 bytes, so 2 rows fit in 30 bytes
+    let config = ProverConfig {
+        commitment_path: Some(CommitmentPath::PackedGpu),
+        max_gpu_bytes: 30,
+        ..Default::default()
+    };
+    let (commitments, report) =
+        compute_commitments_with_config::<DoryCommitment>(&columns, 2, &setup, &GPU, &config);
+    assert_eq!(commitments, expected);
+    assert_eq!(report.path, CommitmentPath::PackedGpu);
+    assert_eq!(report.chunk_path, Some(CommitmentPath::PackedGpu));
+    assert_eq!(report.num_chunks, 4);
+
+    // Only the widest batch of columns has to fit, so 3 rows fit in 30 bytes
+    let config = ProverConfig {
+        max_packed_columns: 1,
+        ..config
+    };
+    let (commitments, report) =
+        compute_commitments_with_config::<DoryCommitment>(&columns, 2, &setup, &GPU, &config);
+    assert_eq!(commitments, expected);
+    assert_eq!(report.num_chunks, 3);
+    assert_eq!(report.num_column_batches, 3);
+
+    // Chunks of rows are no larger than fits in memory
+    let config = ProverConfig {
+        commitment_path: Some(CommitmentPath::ChunkedStreaming),
+        chunk_rows: 5,
+        max_gpu_bytes: 30,
+        ..Default::default()
+    };
+    let (commitments, report) =
+        compute_commitments_with_config::<DoryCommitment>(&columns, 2, &setup, &GPU, &config);
+    assert_eq!(commitments, expected);
+    assert_eq!(report.chunk_path, Some(CommitmentPath::PackedGpu));
+    assert_eq!(report.num_chunks, 4);
+
+    // Tables that fit are not chunked
+    let (_, report) = compute_commitments_with_config::<DoryCommitment>(
+        &columns,
+        2,
+        &setup,
+        &GPU,
+        &ProverConfig {
+            commitment_path: Some(CommitmentPath::PackedGpu),
+            ..Default::default()
+        },
+    );
+    assert_eq!(report.chunk_path, None);
+    assert_eq!(report.num_chunks, 1);
+}
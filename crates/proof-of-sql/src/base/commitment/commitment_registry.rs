@@ -0,0 +1,139 @@
+use super::{Commitment, TableCommitment};
+use crate::base::database::TableRef;
+use indexmap::IndexMap;
+use std::ops::Range;
+use thiserror::Error;
+
+/// Cannot register a [`TableCommitment`] that does not extend the latest version of its table.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("the commitment to rows {new:?} does not extend the latest version of rows {latest:?}")]
+pub struct NonExtendingVersion {
+    /// The rows that the latest version of the table commits to
+    pub latest: Range<usize>,
+    /// The rows that the rejected commitment commits to
+    pub new: Range<usize>,
+}
+
+/// A registry of table commitments that retains every version of each table.
+///
+/// Tables are only ever appended to, so every version commits to a prefix of the rows of the
+/// versions after it. This lets a query read an earlier version of a table with
+/// ```ignore
+///     SELECT ... FROM <table> AS OF <version>
+/// ```
+/// which the prover proves against the rows of that version and the verifier verifies against
+/// its retained commitment. Versions are numbered from `0` in the order they are registered.
+#[derive(Debug, Clone)]
+pub struct CommitmentRegistry<C: Commitment> {
+    versions: IndexMap<TableRef, Vec<TableCommitment<C>>>,
+}
+
+impl<C: Commitment> Default for CommitmentRegistry<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Commitment> CommitmentRegistry<C> {
+    /// Create a registry without any tables.
+    pub fn new() -> Self {
+        Self {
+            versions: IndexMap::new(),
+        }
+    }
+
+    /// Register a new version of the table, returning its version number.
+    ///
+    /// The new version has to commit to the rows of the latest version and possibly more.
+    pub fn register(
+        &mut self,
+        table_ref: TableRef,
+        commitment: TableCommitment<C>,
+    ) -> Result<u64, NonExtendingVersion> {
+        let versions = self.versions.entry(table_ref).or_default();
+        if let Some(latest) = versions.last() {
+            let (latest, new) = (latest.range(), commitment.range());
+            if new.start != latest.start || new.end < latest.end {
+                return Err(NonExtendingVersion {
+                    latest: latest.clone(),
+                    new: new.clone(),
+                });
+            }
+        }
+        versions.push(commitment);
+        Ok(versions.len() as u64 - 1)
+    }
+
+    /// Returns the number of the latest version of the table, if it has been registered.
+    pub fn latest_version(&self, table_ref: TableRef) -> Option<u64> {
+        self.versions
+            .get(&table_ref)
+            .map(|versions| versions.len() as u64 - 1)
+    }
+
+    /// Returns the commitment of a version of the table.
+    pub fn get(&self, table_ref: TableRef, version: u64) -> Option<&TableCommitment<C>> {
+        self.versions
+            .get(&table_ref)?
+            .get(usize::try_from(version).ok()?)
+    }
+
+    /// Returns the commitment of the latest version of the table.
+    pub fn latest(&self, table_ref: TableRef) -> Option<&TableCommitment<C>> {
+        self.versions.get(&table_ref)?.last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+    use curve25519_dalek::RistrettoPoint;
+
+    fn commitment(values: &[i64], offset: usize) -> TableCommitment<RistrettoPoint> {
+        TableCommitment::from_owned_table_with_offset(
+            &owned_table::<Curve25519Scalar>([bigint("a", values.to_vec())]),
+            offset,
+            &(),
+        )
+    }
+
+    #[test]
+    fn we_can_register_and_look_up_versions_of_a_table() {
+        let t = "sxt.t".parse().unwrap();
+        let u = "sxt.u".parse().unwrap();
+        let mut registry = CommitmentRegistry::new();
+        assert_eq!(registry.latest_version(t), None);
+        assert_eq!(registry.register(t, commitment(&[1, 2], 0)), Ok(0));
+        assert_eq!(registry.register(t, commitment(&[1, 2, 3], 0)), Ok(1));
+        assert_eq!(registry.register(u, commitment(&[4], 3)), Ok(0));
+        assert_eq!(registry.latest_version(t), Some(1));
+        assert_eq!(registry.get(t, 0), Some(&commitment(&[1, 2], 0)));
+        assert_eq!(registry.latest(t), Some(&commitment(&[1, 2, 3], 0)));
+        assert_eq!(registry.latest(u), Some(&commitment(&[4], 3)));
+        assert_eq!(registry.get(t, 2), None);
+        assert_eq!(registry.get("sxt.v".parse().unwrap(), 0), None);
+    }
+
+    #[test]
+    fn we_cannot_register_versions_that_do_not_extend_the_latest_one() {
+        let t = "sxt.t".parse().unwrap();
+        let mut registry = CommitmentRegistry::new();
+        registry.register(t, commitment(&[1, 2], 1)).unwrap();
+        assert_eq!(
+            registry.register(t, commitment(&[1], 1)),
+            Err(NonExtendingVersion {
+                latest: 1..3,
+                new: 1..2,
+            })
+        );
+        assert_eq!(
+            registry.register(t, commitment(&[1, 2, 3], 0)),
+            Err(NonExtendingVersion {
+                latest: 1..3,
+                new: 0..3,
+            })
+        );
+        assert_eq!(registry.latest_version(t), Some(0));
+    }
+}
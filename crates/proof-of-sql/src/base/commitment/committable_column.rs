@@ -1,12 +1,18 @@
+#[cfg(feature = "rayon")]
+use crate::base::ComputeConfig;
 use crate::base::{
     database::{Column, ColumnType, OwnedColumn},
+    if_rayon,
     math::decimal::Precision,
     ref_into::RefInto,
     scalar::Scalar,
 };
 #[cfg(feature = "blitzar")]
 use blitzar::sequence::Sequence;
+use core::{mem::size_of, ops::Range};
 use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// Column data in "committable form".
 ///
@@ -67,6 +73,44 @@ impl<'a> CommittableColumn<'a> {
     pub fn column_type(&self) -> ColumnType {
         self.into()
     }
+
+    /// Returns the number of bytes that one element of the column takes up.
+    pub(crate) fn element_size(&self) -> usize {
+        match self {
+            CommittableColumn::Boolean(_) => size_of::<bool>(),
+            CommittableColumn::SmallInt(_) => size_of::<i16>(),
+            CommittableColumn::Int(_) => size_of::<i32>(),
+            CommittableColumn::BigInt(_) | CommittableColumn::TimestampTZ(_, _, _) => {
+                size_of::<i64>()
+            }
+            CommittableColumn::Int128(_) => size_of::<i128>(),
+            CommittableColumn::Decimal75(_, _, _)
+            | CommittableColumn::Scalar(_)
+            | CommittableColumn::VarChar(_) => size_of::<[u64; 4]>(),
+        }
+    }
+
+    /// Returns the rows of the column in the given range, clamped to the length of the column.
+    ///
+    /// Borrowed data stays borrowed, while owned data is copied.
+    pub(crate) fn slice(&self, range: Range<usize>) -> CommittableColumn<'a> {
+        let range = range.start.min(self.len())..range.end.min(self.len());
+        match self {
+            CommittableColumn::Boolean(col) => CommittableColumn::Boolean(&col[range]),
+            CommittableColumn::SmallInt(col) => CommittableColumn::SmallInt(&col[range]),
+            CommittableColumn::Int(col) => CommittableColumn::Int(&col[range]),
+            CommittableColumn::BigInt(col) => CommittableColumn::BigInt(&col[range]),
+            CommittableColumn::Int128(col) => CommittableColumn::Int128(&col[range]),
+            CommittableColumn::Decimal75(precision, scale, col) => {
+                CommittableColumn::Decimal75(*precision, *scale, col[range].to_vec())
+            }
+            CommittableColumn::Scalar(col) => CommittableColumn::Scalar(col[range].to_vec()),
+            CommittableColumn::VarChar(col) => CommittableColumn::VarChar(col[range].to_vec()),
+            CommittableColumn::TimestampTZ(tu, tz, col) => {
+                CommittableColumn::TimestampTZ(*tu, *tz, &col[range])
+            }
+        }
+    }
 }
 
 impl<'a> From<&CommittableColumn<'a>> for ColumnType {
@@ -96,14 +140,10 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for CommittableColumn<'a> {
             Column::BigInt(ints) => CommittableColumn::BigInt(ints),
             Column::Int128(ints) => CommittableColumn::Int128(ints),
             Column::Decimal75(precision, scale, decimals) => {
-                let as_limbs: Vec<_> = decimals.iter().map(RefInto::<[u64; 4]>::ref_into).collect();
-                CommittableColumn::Decimal75(*precision, *scale, as_limbs)
+                CommittableColumn::Decimal75(*precision, *scale, batch_into_limbs(decimals))
             }
             Column::Scalar(scalars) => (scalars as &[_]).into(),
-            Column::VarChar((_, scalars)) => {
-                let as_limbs: Vec<_> = scalars.iter().map(RefInto::<[u64; 4]>::ref_into).collect();
-                CommittableColumn::VarChar(as_limbs)
-            }
+            Column::VarChar((_, scalars)) => CommittableColumn::VarChar(batch_into_limbs(scalars)),
             Column::TimestampTZ(tu, tz, times) => CommittableColumn::TimestampTZ(*tu, *tz, times),
         }
     }
@@ -165,7 +205,7 @@ impl<'a> From<&'a [i128]> for CommittableColumn<'a> {
 }
 impl<'a, S: Scalar> From<&'a [S]> for CommittableColumn<'a> {
     fn from(value: &'a [S]) -> Self {
-        CommittableColumn::Scalar(value.iter().map(RefInto::<[u64; 4]>::ref_into).collect())
+        CommittableColumn::Scalar(batch_into_limbs(value))
     }
 }
 impl<'a> From<&'a [bool]> for CommittableColumn<'a> {
@@ -174,6 +214,21 @@ impl<'a> From<&'a [bool]> for CommittableColumn<'a> {
     }
 }
 
+/// Convert `scalars` to the little-endian limbs that are passed to multi-scalar multiplications.
+///
+/// Converting a scalar out of Montgomery form costs a field multiplication, so the conversion is
+/// split between threads for long slices.
+fn batch_into_limbs<S: Scalar>(scalars: &[S]) -> Vec<[u64; 4]> {
+    if_rayon!(
+        scalars
+            .par_iter()
+            .with_min_len(ComputeConfig::current().min_len(scalars.len())),
+        scalars.iter()
+    )
+    .map(RefInto::<[u64; 4]>::ref_into)
+    .collect()
+}
+
 #[cfg(feature = "blitzar")]
 impl<'a, 'b> From<&'a CommittableColumn<'b>> for Sequence<'a> {
     fn from(value: &'a CommittableColumn<'b>) -> Self {
@@ -198,6 +253,14 @@ mod tests {
     use blitzar::compute::compute_curve25519_commitments;
     use curve25519_dalek::ristretto::CompressedRistretto;
 
+    #[test]
+    fn we_can_batch_convert_scalars_to_limbs() {
+        assert!(batch_into_limbs::<Curve25519Scalar>(&[]).is_empty());
+        let scalars: Vec<Curve25519Scalar> = (-500..500_i64).map(Curve25519Scalar::from).collect();
+        let expected: Vec<[u64; 4]> = scalars.iter().map(|&scalar| scalar.into()).collect();
+        assert_eq!(batch_into_limbs(&scalars), expected);
+    }
+
     #[test]
     fn we_can_convert_from_owned_decimal75_column_to_committable_column() {
         let decimals = vec![
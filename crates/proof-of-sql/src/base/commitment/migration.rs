@@ -0,0 +1,295 @@
+//! Upgrades of stored commitment metadata from older serialization layouts.
+use super::{
+    ColumnCommitmentMetadataV1, Commitment, CommitmentLayoutVersion,
+    InvalidColumnCommitmentMetadata, NegativeRange, TableCommitmentV1,
+    VersionedColumnCommitmentMetadata, VersionedTableCommitment,
+};
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+/// Errors that can occur when migrating stored commitment metadata to the latest layout.
+#[derive(Debug, Error)]
+pub enum CommitmentMigrationError {
+    /// The stored data could not be deserialized in the given layout.
+    #[error("failed to deserialize stored commitment: {0}")]
+    Deserialization(String),
+    /// The stored column metadata is invalid.
+    #[error(transparent)]
+    InvalidMetadata(#[from] InvalidColumnCommitmentMetadata),
+    /// The stored table commitment has a negative range.
+    #[error(transparent)]
+    NegativeRange(#[from] NegativeRange),
+    /// The stored number of commitments differs from the stored number of column metadata.
+    #[error(
+        "stored table commitment has {commitments} commitments but metadata for {columns} columns"
+    )]
+    ColumnCountMismatch {
+        /// The number of stored commitments
+        commitments: usize,
+        /// The number of stored column metadata
+        columns: usize,
+    },
+}
+
+/// Deserialize a [`TableCommitment`](super::TableCommitment) stored in the given layout and
+/// upgrade it to the latest layout.
+///
+/// Data in older layouts is validated while it is upgraded,
+/// since it was never checked against the current invariants.
+pub fn migrate_table_commitment<'de, C, D>(
+    deserializer: D,
+    stored_version: CommitmentLayoutVersion,
+) -> Result<VersionedTableCommitment<C>, CommitmentMigrationError>
+where
+    C: Commitment + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let stored = match stored_version {
+        // The unversioned layout is the V1 layout without a version tag
+        CommitmentLayoutVersion::Unversioned => {
+            TableCommitmentV1::<C>::deserialize(deserializer).map(VersionedTableCommitment::V1)
+        }
+        CommitmentLayoutVersion::V1
+        | CommitmentLayoutVersion::V2
+        | CommitmentLayoutVersion::V3
+        | CommitmentLayoutVersion::V4
+        | CommitmentLayoutVersion::V5
+        | CommitmentLayoutVersion::V6 => VersionedTableCommitment::<C>::deserialize(deserializer),
+    }
+    .map_err(|e| CommitmentMigrationError::Deserialization(e.to_string()))?;
+    Ok(stored.into_latest()?.into())
+}
+
+/// Deserialize a [`ColumnCommitmentMetadata`](super::ColumnCommitmentMetadata) stored in the
+/// given layout and upgrade it to the latest layout.
+pub fn migrate_column_commitment_metadata<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    stored_version: CommitmentLayoutVersion,
+) -> Result<VersionedColumnCommitmentMetadata, CommitmentMigrationError> {
+    let stored = match stored_version {
+        CommitmentLayoutVersion::Unversioned => {
+            ColumnCommitmentMetadataV1::deserialize(deserializer)
+                .map(VersionedColumnCommitmentMetadata::V1)
+        }
+        CommitmentLayoutVersion::V1
+        | CommitmentLayoutVersion::V2
+        | CommitmentLayoutVersion::V3
+        | CommitmentLayoutVersion::V4
+        | CommitmentLayoutVersion::V5
+        | CommitmentLayoutVersion::V6 => {
+            VersionedColumnCommitmentMetadata::deserialize(deserializer)
+        }
+    }
+    .map_err(|e| CommitmentMigrationError::Deserialization(e.to_string()))?;
+    Ok(stored.into_latest()?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        commitment::{
+            Bounds, ColumnBounds, ColumnCommitmentMetadata, ColumnCommitments, ColumnCommitmentsV1,
+            TableCommitment,
+        },
+        database::ColumnType,
+    };
+    use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, RistrettoPoint};
+    use indexmap::IndexMap;
+
+    fn sample_table_commitment() -> TableCommitment<RistrettoPoint> {
+        let bigint_metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+        )
+        .unwrap();
+        let varchar_metadata =
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap();
+        let column_commitments = ColumnCommitments::from_iter([
+            (
+                "a".parse().unwrap(),
+                bigint_metadata,
+                RISTRETTO_BASEPOINT_POINT,
+            ),
+            (
+                "b".parse().unwrap(),
+                varchar_metadata,
+                RISTRETTO_BASEPOINT_POINT + RISTRETTO_BASEPOINT_POINT,
+            ),
+        ]);
+        TableCommitment::try_new(column_commitments, 2..6).unwrap()
+    }
+
+    /// [`sample_table_commitment`] in the [`CommitmentLayoutVersion::V1`] layout.
+    fn sample_table_commitment_v1() -> TableCommitmentV1<RistrettoPoint> {
+        TableCommitmentV1 {
+            column_commitments: ColumnCommitmentsV1 {
+                commitments: vec![
+                    RISTRETTO_BASEPOINT_POINT,
+                    RISTRETTO_BASEPOINT_POINT + RISTRETTO_BASEPOINT_POINT,
+                ],
+                column_metadata: IndexMap::from_iter([
+                    (
+                        "a".parse().unwrap(),
+                        ColumnCommitmentMetadataV1 {
+                            column_type: ColumnType::BigInt,
+                            bounds: ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+                        },
+                    ),
+                    (
+                        "b".parse().unwrap(),
+                        ColumnCommitmentMetadataV1 {
+                            column_type: ColumnType::VarChar,
+                            bounds: ColumnBounds::NoOrder,
+                        },
+                    ),
+                ]),
+            },
+            range: 2..6,
+        }
+    }
+
+    #[test]
+    fn we_can_migrate_an_unversioned_table_commitment() {
+        let table_commitment = sample_table_commitment();
+
+        // Before versioning, table commitments were serialized directly
+        let bytes = postcard::to_allocvec(&sample_table_commitment_v1()).unwrap();
+        let migrated = migrate_table_commitment::<RistrettoPoint, _>(
+            &mut postcard::Deserializer::from_bytes(&bytes),
+            CommitmentLayoutVersion::Unversioned,
+        )
+        .unwrap();
+        assert_eq!(migrated.version(), CommitmentLayoutVersion::LATEST);
+        assert_eq!(migrated.into_latest().unwrap(), table_commitment);
+
+        let json = serde_json::to_string(&sample_table_commitment_v1()).unwrap();
+        let migrated = migrate_table_commitment::<RistrettoPoint, _>(
+            &mut serde_json::Deserializer::from_str(&json),
+            CommitmentLayoutVersion::Unversioned,
+        )
+        .unwrap();
+        assert_eq!(migrated.into_latest().unwrap(), table_commitment);
+    }
+
+    #[test]
+    fn we_can_migrate_a_v1_table_commitment() {
+        let versioned = VersionedTableCommitment::V1(sample_table_commitment_v1());
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let migrated = migrate_table_commitment::<RistrettoPoint, _>(
+            &mut postcard::Deserializer::from_bytes(&bytes),
+            CommitmentLayoutVersion::V1,
+        )
+        .unwrap();
+        assert_eq!(migrated.version(), CommitmentLayoutVersion::LATEST);
+        assert_eq!(migrated.into_latest().unwrap(), sample_table_commitment());
+    }
+
+    #[test]
+    fn we_can_migrate_a_table_commitment_in_the_latest_layout() {
+        let versioned = VersionedTableCommitment::from(sample_table_commitment());
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let migrated = migrate_table_commitment::<RistrettoPoint, _>(
+            &mut postcard::Deserializer::from_bytes(&bytes),
+            CommitmentLayoutVersion::LATEST,
+        )
+        .unwrap();
+        assert_eq!(migrated, versioned);
+    }
+
+    #[test]
+    fn we_can_migrate_unversioned_column_commitment_metadata() {
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::Int128,
+            ColumnBounds::Int128(Bounds::bounded(0, 100).unwrap()),
+        )
+        .unwrap();
+        let bytes = postcard::to_allocvec(&ColumnCommitmentMetadataV1 {
+            column_type: ColumnType::Int128,
+            bounds: ColumnBounds::Int128(Bounds::bounded(0, 100).unwrap()),
+        })
+        .unwrap();
+        let migrated = migrate_column_commitment_metadata(
+            &mut postcard::Deserializer::from_bytes(&bytes),
+            CommitmentLayoutVersion::Unversioned,
+        )
+        .unwrap();
+        assert_eq!(migrated.into_latest().unwrap(), metadata);
+    }
+
+    #[test]
+    fn we_cannot_migrate_invalid_unversioned_table_commitments() {
+        // metadata that does not match its column type
+        let invalid_metadata = TableCommitmentV1 {
+            column_commitments: ColumnCommitmentsV1 {
+                commitments: vec![RISTRETTO_BASEPOINT_POINT],
+                column_metadata: IndexMap::from_iter([(
+                    "a".parse().unwrap(),
+                    ColumnCommitmentMetadataV1 {
+                        column_type: ColumnType::BigInt,
+                        bounds: ColumnBounds::NoOrder,
+                    },
+                )]),
+            },
+            range: 0..1,
+        };
+        let bytes = postcard::to_allocvec(&invalid_metadata).unwrap();
+        assert!(matches!(
+            migrate_table_commitment::<RistrettoPoint, _>(
+                &mut postcard::Deserializer::from_bytes(&bytes),
+                CommitmentLayoutVersion::Unversioned,
+            ),
+            Err(CommitmentMigrationError::InvalidMetadata(_))
+        ));
+
+        // more commitments than columns
+        let column_count_mismatch = TableCommitmentV1 {
+            column_commitments: ColumnCommitmentsV1::<RistrettoPoint> {
+                commitments: vec![RISTRETTO_BASEPOINT_POINT],
+                column_metadata: IndexMap::new(),
+            },
+            range: 0..1,
+        };
+        let bytes = postcard::to_allocvec(&column_count_mismatch).unwrap();
+        assert!(matches!(
+            migrate_table_commitment::<RistrettoPoint, _>(
+                &mut postcard::Deserializer::from_bytes(&bytes),
+                CommitmentLayoutVersion::Unversioned,
+            ),
+            Err(CommitmentMigrationError::ColumnCountMismatch {
+                commitments: 1,
+                columns: 0
+            })
+        ));
+
+        // negative range
+        #[allow(clippy::reversed_empty_ranges)]
+        let negative_range = TableCommitmentV1 {
+            column_commitments: ColumnCommitmentsV1::<RistrettoPoint> {
+                commitments: vec![],
+                column_metadata: IndexMap::new(),
+            },
+            range: 1..0,
+        };
+        let bytes = postcard::to_allocvec(&negative_range).unwrap();
+        assert!(matches!(
+            migrate_table_commitment::<RistrettoPoint, _>(
+                &mut postcard::Deserializer::from_bytes(&bytes),
+                CommitmentLayoutVersion::Unversioned,
+            ),
+            Err(CommitmentMigrationError::NegativeRange(_))
+        ));
+    }
+
+    #[test]
+    fn we_cannot_migrate_data_stored_in_a_different_layout() {
+        let bytes = postcard::to_allocvec(&sample_table_commitment_v1()).unwrap();
+        assert!(matches!(
+            migrate_table_commitment::<RistrettoPoint, _>(
+                &mut postcard::Deserializer::from_bytes(&bytes),
+                CommitmentLayoutVersion::V1,
+            ),
+            Err(CommitmentMigrationError::Deserialization(_))
+        ));
+    }
+}
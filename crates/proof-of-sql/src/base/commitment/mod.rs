@@ -16,10 +16,16 @@ pub use vec_commitment_ext::{NumColumnsMismatch, VecCommitmentExt};
 
 mod column_bounds;
 use super::scalar::Curve25519Scalar;
-pub use column_bounds::{Bounds, ColumnBounds, NegativeBounds};
+pub use column_bounds::{Bounds, BoundsStrategy, ColumnBounds, NegativeBounds};
+
+mod column_bloom_filter;
+pub use column_bloom_filter::{
+    BloomFilterLookup, BloomFilterParams, BloomFilterParamsMismatch, ColumnBloomFilter,
+    InvalidBloomFilterParams,
+};
 
 mod column_commitment_metadata;
-pub use column_commitment_metadata::ColumnCommitmentMetadata;
+pub use column_commitment_metadata::{ColumnCommitmentMetadata, InvalidColumnCommitmentMetadata};
 
 mod column_commitment_metadata_map;
 pub use column_commitment_metadata_map::{
@@ -31,15 +37,66 @@ pub use column_commitments::{
     AppendColumnCommitmentsError, ColumnCommitments, DuplicateIdentifiers,
 };
 
+mod schema_evolution;
+pub use schema_evolution::{SchemaChange, SchemaEvolutionError};
+
+mod chain_anchor;
+pub use chain_anchor::{ChainAnchor, ChainAnchorError};
+
+mod batch_provenance;
+#[cfg(feature = "signed-proofs")]
+pub use batch_provenance::BatchAttestation;
+pub use batch_provenance::{BatchProvenance, BatchProvenanceError, SignerSet, SignerSetDigest};
+
 mod table_commitment;
 pub use table_commitment::{
     AppendTableCommitmentError, MixedLengthColumns, NegativeRange, TableCommitment,
     TableCommitmentArithmeticError, TableCommitmentFromColumnsError,
 };
 
+#[cfg(feature = "keccak-checksums")]
+mod co_commitment;
+#[cfg(feature = "keccak-checksums")]
+pub use co_commitment::{CoCommitment, KeccakChecksum};
+
+mod partitioned_table_commitment;
+pub use partitioned_table_commitment::{
+    PartitionPruningError, PartitionedTableCommitment, PartitionedTableCommitmentError,
+};
+
+mod timestamp_range_index;
+pub use timestamp_range_index::{TimestampRangeIndex, TimestampRangeIndexError};
+
+mod versioned_commitment;
+#[cfg(test)]
+use versioned_commitment::ColumnCommitmentsV1;
+pub use versioned_commitment::{
+    ColumnCommitmentMetadataV1, ColumnCommitmentMetadataV2, ColumnCommitmentMetadataV4,
+    CommitmentLayoutVersion, TableCommitmentV1, TableCommitmentV2, TableCommitmentV3,
+    TableCommitmentV4, TableCommitmentV5, VersionedColumnCommitmentMetadata,
+    VersionedTableCommitment,
+};
+
+mod migration;
+pub use migration::{
+    migrate_column_commitment_metadata, migrate_table_commitment, CommitmentMigrationError,
+};
+
+mod commitment_path;
+pub use commitment_path::{
+    compute_commitments_with_config, select_commitment_path, CommitmentEnvironment, CommitmentPath,
+    CommitmentPathReport, CommitmentWorkload, ProverConfig, DEFAULT_MAX_GPU_BYTES,
+    DEFAULT_MAX_PACKED_COLUMNS,
+};
+#[cfg(test)]
+mod commitment_path_test;
+
 mod query_commitments;
 pub use query_commitments::{QueryCommitments, QueryCommitmentsExt};
 
+mod commitment_registry;
+pub use commitment_registry::{CommitmentRegistry, NonExtendingVersion};
+
 /// A trait for using commitment schemes generically.
 pub trait Commitment:
     AddAssign
@@ -66,12 +123,28 @@ pub trait Commitment:
     type PublicSetup<'a>;
 
     /// Compute the commitments for the given columns.
+    ///
+    /// The commitment to an empty column is the identity, i.e. `Self::default()`, no matter the
+    /// offset.
     fn compute_commitments(
         commitments: &mut [Self],
         committable_columns: &[CommittableColumn],
         offset: usize,
         setup: &Self::PublicSetup<'_>,
     );
+
+    /// Compute the commitments for the given columns with multi-scalar multiplications on the CPU.
+    ///
+    /// Commitment schemes without a dedicated CPU implementation use
+    /// [`Commitment::compute_commitments`].
+    fn compute_commitments_on_cpu(
+        commitments: &mut [Self],
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) {
+        Self::compute_commitments(commitments, committable_columns, offset, setup);
+    }
 }
 
 impl Commitment for RistrettoPoint {
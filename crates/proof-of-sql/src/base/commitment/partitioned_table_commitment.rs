@@ -0,0 +1,341 @@
+use super::{
+    Bounds, ColumnBounds, ColumnCommitmentsMismatch, Commitment, TableCommitment,
+    TableCommitmentArithmeticError,
+};
+use crate::base::{
+    database::{ColumnType, OwnedTable},
+    scalar::Scalar,
+};
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::ops::{Range, RangeInclusive};
+use thiserror::Error;
+
+/// Errors that can occur when constructing or extending a [`PartitionedTableCommitment`].
+#[derive(Debug, Error)]
+pub enum PartitionedTableCommitmentError {
+    /// A partitioned table needs at least one partition.
+    #[error("a partitioned table needs at least one partition")]
+    NoPartitions,
+    /// The partition key is not an integer or timestamp column of a partition.
+    #[error("the partition key {0} is not an integer or timestamp column of every partition")]
+    InvalidPartitionKey(Identifier),
+    /// The partitions cannot be combined into one table commitment.
+    #[error(transparent)]
+    Arithmetic(#[from] TableCommitmentArithmeticError),
+}
+
+/// Errors that can occur when checking that partitions were pruned correctly.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PartitionPruningError {
+    /// The kept partitions are not partitions of the table.
+    #[error("partitions {0:?} are out of range")]
+    OutOfRange(Range<usize>),
+    /// A pruned partition may contain rows with relevant keys.
+    #[error("partition {0} was pruned but may contain relevant keys")]
+    RelevantPartitionPruned(usize),
+}
+
+/// Commitment to a table whose rows are split into contiguous partitions, e.g. by date.
+///
+/// Each partition is committed to separately, and the bounds in its column metadata describe the
+/// values of the partition key in it. Queries that only select some values of the partition key
+/// can be proven over the partitions that contain these values, rather than the entire table.
+///
+/// The bounds are trusted in the same way as the commitments are, so a partition whose key bounds
+/// are unknown is never pruned.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionedTableCommitment<C: Commitment> {
+    partition_key: Identifier,
+    partitions: Vec<TableCommitment<C>>,
+}
+
+/// Converts bounds to a range, or `None` if the bounds are empty.
+fn bounds_to_range<T>(bounds: &Bounds<T>) -> Option<RangeInclusive<i128>>
+where
+    T: Ord + Copy + Into<i128>,
+{
+    match bounds {
+        Bounds::Empty => None,
+        Bounds::Bounded(inner) | Bounds::Sharp(inner) => {
+            Some((*inner.min()).into()..=(*inner.max()).into())
+        }
+    }
+}
+
+/// Returns whether two ranges of keys, where `None` is empty, have a key in common.
+fn ranges_intersect(a: &Option<RangeInclusive<i128>>, b: &Option<RangeInclusive<i128>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.start() <= b.end() && b.start() <= a.end(),
+        _ => false,
+    }
+}
+
+impl<C: Commitment> PartitionedTableCommitment<C> {
+    /// Construct a new [`PartitionedTableCommitment`] from partitions in the order of their rows.
+    ///
+    /// Each partition has to start where the previous one ends, all partitions need the same
+    /// columns, and the partition key has to be an integer or timestamp column.
+    pub fn try_new(
+        partition_key: Identifier,
+        partitions: Vec<TableCommitment<C>>,
+    ) -> Result<Self, PartitionedTableCommitmentError> {
+        let mut partitions = partitions.into_iter();
+        let first = partitions
+            .next()
+            .ok_or(PartitionedTableCommitmentError::NoPartitions)?;
+        let mut partitioned_table_commitment = Self {
+            partition_key,
+            partitions: vec![],
+        };
+        partitioned_table_commitment.check_partition_key(&first)?;
+        partitioned_table_commitment.partitions.push(first);
+        for partition in partitions {
+            partitioned_table_commitment.try_append_partition(partition)?;
+        }
+        Ok(partitioned_table_commitment)
+    }
+
+    /// Append a partition, which has to start where the last partition ends.
+    pub fn try_append_partition(
+        &mut self,
+        partition: TableCommitment<C>,
+    ) -> Result<(), PartitionedTableCommitmentError> {
+        self.check_partition_key(&partition)?;
+        let last = self
+            .partitions
+            .last()
+            .expect("there is at least one partition");
+        if last.range().end != partition.range().start {
+            return Err(TableCommitmentArithmeticError::NonContiguous.into());
+        }
+        // Adding the partitions checks that their columns match
+        last.clone().try_add(partition.clone())?;
+        self.partitions.push(partition);
+        Ok(())
+    }
+
+    /// Append rows to the last partition, which widens the bounds of its partition key.
+    pub fn append_to_last_partition<S: Scalar>(
+        &mut self,
+        owned_table: &OwnedTable<S>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), ColumnCommitmentsMismatch> {
+        self.partitions
+            .last_mut()
+            .expect("there is at least one partition")
+            .append_owned_table(owned_table, setup)
+    }
+
+    fn check_partition_key(
+        &self,
+        partition: &TableCommitment<C>,
+    ) -> Result<(), PartitionedTableCommitmentError> {
+        match partition
+            .column_commitments()
+            .get_metadata(&self.partition_key)
+            .map(|metadata| metadata.bounds())
+        {
+            Some(ColumnBounds::NoOrder) | None => Err(
+                PartitionedTableCommitmentError::InvalidPartitionKey(self.partition_key),
+            ),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Returns the partition key.
+    pub fn partition_key(&self) -> Identifier {
+        self.partition_key
+    }
+
+    /// Returns the type of the partition key.
+    pub fn partition_key_type(&self) -> ColumnType {
+        *self.partitions[0]
+            .column_commitments()
+            .get_metadata(&self.partition_key)
+            .expect("every partition contains the partition key")
+            .column_type()
+    }
+
+    /// Returns the partitions in the order of their rows.
+    pub fn partitions(&self) -> &[TableCommitment<C>] {
+        &self.partitions
+    }
+
+    /// Returns the range of the partition keys in a partition, or `None` if it is empty.
+    fn partition_key_range(&self, index: usize) -> Option<RangeInclusive<i128>> {
+        let metadata = self.partitions[index]
+            .column_commitments()
+            .get_metadata(&self.partition_key)
+            .expect("every partition contains the partition key");
+        match metadata.bounds() {
+            ColumnBounds::SmallInt(bounds) => bounds_to_range(bounds),
+            ColumnBounds::Int(bounds) => bounds_to_range(bounds),
+            ColumnBounds::BigInt(bounds) => bounds_to_range(bounds),
+            ColumnBounds::Int128(bounds) => bounds_to_range(bounds),
+            ColumnBounds::TimestampTZ(bounds) => bounds_to_range(bounds),
+            ColumnBounds::NoOrder => unreachable!("the partition key is ordered"),
+        }
+    }
+
+    /// Returns the partitions that may contain rows whose partition key is in the given range.
+    ///
+    /// `None` is the empty range of keys. Partitions in between relevant ones are kept, so that
+    /// the kept partitions are contiguous. If no partition is relevant, the range is empty.
+    pub fn prune(&self, key_range: &Option<RangeInclusive<i128>>) -> Range<usize> {
+        let mut relevant_partitions = (0..self.partitions.len())
+            .filter(|&index| ranges_intersect(&self.partition_key_range(index), key_range));
+        match relevant_partitions.next() {
+            Some(first) => first..relevant_partitions.last().unwrap_or(first) + 1,
+            None => 0..0,
+        }
+    }
+
+    /// Checks that the partitions outside of `kept_partitions` cannot contain rows whose partition
+    /// key is in the given range.
+    pub fn verify_pruning(
+        &self,
+        key_range: &Option<RangeInclusive<i128>>,
+        kept_partitions: Range<usize>,
+    ) -> Result<(), PartitionPruningError> {
+        if kept_partitions.start > kept_partitions.end
+            || kept_partitions.end > self.partitions.len()
+        {
+            return Err(PartitionPruningError::OutOfRange(kept_partitions));
+        }
+        match (0..self.partitions.len())
+            .filter(|index| !kept_partitions.contains(index))
+            .find(|&index| ranges_intersect(&self.partition_key_range(index), key_range))
+        {
+            Some(index) => Err(PartitionPruningError::RelevantPartitionPruned(index)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the commitment to the rows of the given partitions.
+    ///
+    /// An empty range of partitions gives a commitment to no rows.
+    ///
+    /// # Panics
+    /// Panics if the partitions are out of range.
+    pub fn table_commitment(&self, partitions: Range<usize>) -> TableCommitment<C> {
+        let first = &self.partitions[0];
+        self.partitions[partitions]
+            .iter()
+            .cloned()
+            .reduce(|table_commitment, partition| {
+                table_commitment
+                    .try_add(partition)
+                    .expect("partitions are contiguous and have matching columns")
+            })
+            .unwrap_or_else(|| {
+                first
+                    .clone()
+                    .try_sub(first.clone())
+                    .expect("a partition can be subtracted from itself")
+            })
+    }
+}
+
+#[cfg(all(test, feature = "blitzar"))]
+mod tests {
+    use super::*;
+    use crate::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+    use curve25519_dalek::RistrettoPoint;
+    use proof_of_sql_parser::utility::ident;
+
+    fn partition(days: &[i64], offset: usize) -> TableCommitment<RistrettoPoint> {
+        TableCommitment::from_owned_table_with_offset(
+            &owned_table::<Curve25519Scalar>([
+                bigint("day", days.to_vec()),
+                varchar("name", days.iter().map(|day| day.to_string())),
+            ]),
+            offset,
+            &(),
+        )
+    }
+
+    fn partitioned_table() -> PartitionedTableCommitment<RistrettoPoint> {
+        PartitionedTableCommitment::try_new(
+            ident("day"),
+            vec![
+                partition(&[1, 1, 2], 0),
+                partition(&[3, 4], 3),
+                partition(&[], 5),
+                partition(&[5, 6, 6], 5),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn we_can_prune_partitions_by_their_key_bounds() {
+        let table = partitioned_table();
+        assert_eq!(table.partition_key_type(), ColumnType::BigInt);
+        assert_eq!(table.prune(&Some(i128::MIN..=i128::MAX)), 0..4);
+        assert_eq!(table.prune(&Some(3..=3)), 1..2);
+        assert_eq!(table.prune(&Some(2..=5)), 0..4);
+        assert_eq!(table.prune(&Some(6..=i128::MAX)), 3..4);
+        assert_eq!(table.prune(&Some(7..=9)), 0..0);
+        assert_eq!(table.prune(&None), 0..0);
+    }
+
+    #[test]
+    fn we_can_verify_pruned_partitions() {
+        let table = partitioned_table();
+        let key_range = Some(3..=4);
+        assert_eq!(table.verify_pruning(&key_range, 1..2), Ok(()));
+        // Keeping more partitions than necessary is fine
+        assert_eq!(table.verify_pruning(&key_range, 0..3), Ok(()));
+        assert_eq!(table.verify_pruning(&None, 0..0), Ok(()));
+        assert_eq!(
+            table.verify_pruning(&key_range, 2..4),
+            Err(PartitionPruningError::RelevantPartitionPruned(1))
+        );
+        assert_eq!(
+            table.verify_pruning(&key_range, 1..5),
+            Err(PartitionPruningError::OutOfRange(1..5))
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_commitment_to_kept_partitions() {
+        let table = partitioned_table();
+        let all_rows = partition(&[1, 1, 2, 3, 4, 5, 6, 6], 0);
+        assert_eq!(
+            table
+                .table_commitment(0..4)
+                .column_commitments()
+                .commitments(),
+            all_rows.column_commitments().commitments()
+        );
+        let kept = table.table_commitment(1..3);
+        assert_eq!(kept.range(), &(3..5));
+        assert_eq!(
+            kept.column_commitments().commitments(),
+            partition(&[3, 4], 3).column_commitments().commitments()
+        );
+        assert!(table.table_commitment(0..0).range().is_empty());
+    }
+
+    #[test]
+    fn we_cannot_create_a_partitioned_table_with_invalid_partitions() {
+        assert!(matches!(
+            PartitionedTableCommitment::<RistrettoPoint>::try_new(ident("day"), vec![]),
+            Err(PartitionedTableCommitmentError::NoPartitions)
+        ));
+        assert!(matches!(
+            PartitionedTableCommitment::try_new(ident("name"), vec![partition(&[1], 0)]),
+            Err(PartitionedTableCommitmentError::InvalidPartitionKey(_))
+        ));
+        assert!(matches!(
+            PartitionedTableCommitment::try_new(
+                ident("day"),
+                vec![partition(&[1], 0), partition(&[2], 2)]
+            ),
+            Err(PartitionedTableCommitmentError::Arithmetic(
+                TableCommitmentArithmeticError::NonContiguous
+            ))
+        ));
+    }
+}
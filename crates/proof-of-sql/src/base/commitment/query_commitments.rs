@@ -1,7 +1,7 @@
-use super::{Commitment, TableCommitment};
+use super::{ChainAnchor, ChainAnchorError, Commitment, TableCommitment};
 use crate::base::database::{
-    ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor, SchemaAccessor,
-    TableRef,
+    AccessorError, AccessorResult, ColumnField, ColumnRef, ColumnType, CommitmentAccessor,
+    MetadataAccessor, SchemaAccessor, TableRef, VarCharNormalization,
 };
 use indexmap::IndexMap;
 use proof_of_sql_parser::Identifier;
@@ -24,6 +24,10 @@ where
         columns: impl IntoIterator<Item = ColumnRef>,
         accessor: &(impl CommitmentAccessor<C> + SchemaAccessor),
     ) -> Self;
+
+    /// Check that the commitment of every table is anchored to the given block, i.e. that a
+    /// query verified against these commitments is a query of the chain state at that block.
+    fn verify_anchors(&self, anchor: &ChainAnchor) -> Result<(), ChainAnchorError>;
 }
 
 impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
@@ -58,6 +62,20 @@ impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
             })
             .collect()
     }
+
+    fn verify_anchors(&self, anchor: &ChainAnchor) -> Result<(), ChainAnchorError> {
+        self.iter().try_for_each(
+            |(&table_ref, table_commitment)| match table_commitment.anchor() {
+                None => Err(ChainAnchorError::Unanchored { table_ref }),
+                Some(actual) if actual != anchor => Err(ChainAnchorError::Mismatch {
+                    table_ref,
+                    expected: *anchor,
+                    actual: *actual,
+                }),
+                Some(_) => Ok(()),
+            },
+        )
+    }
 }
 
 impl<C: Commitment> MetadataAccessor for QueryCommitments<C> {
@@ -72,6 +90,18 @@ impl<C: Commitment> MetadataAccessor for QueryCommitments<C> {
 
         table_commitment.range().start
     }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.get(&table_ref)
+            .map(TableCommitment::num_rows)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.get(&table_ref)
+            .map(|table_commitment| table_commitment.range().start)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
 }
 
 impl<C: Commitment> CommitmentAccessor<C> for QueryCommitments<C> {
@@ -112,6 +142,21 @@ impl<C: Commitment> SchemaAccessor for QueryCommitments<C> {
             .map(|(identifier, column_metadata)| (*identifier, *column_metadata.column_type()))
             .collect()
     }
+
+    fn lookup_varchar_normalization(
+        &self,
+        table_ref: TableRef,
+        column_id: Identifier,
+    ) -> VarCharNormalization {
+        self.get(&table_ref)
+            .and_then(|table_commitment| {
+                table_commitment
+                    .column_commitments()
+                    .get_metadata(&column_id)
+            })
+            .map(|column_metadata| column_metadata.varchar_normalization())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(all(test, feature = "blitzar"))]
@@ -119,7 +164,7 @@ mod tests {
     use super::*;
     use crate::{
         base::{
-            commitment::{Bounds, ColumnBounds},
+            commitment::{Bounds, BoundsStrategy, ColumnBounds},
             database::{
                 owned_table_utility::*, OwnedColumn, OwnedTable, OwnedTableTestAccessor,
                 TestAccessor,
@@ -329,22 +374,26 @@ mod tests {
         let mut table_a_commitment =
             TableCommitment::from_owned_table_with_offset(&table_a, 0, &setup);
         let table_a_id = "table.a".parse().unwrap();
-        *table_a_commitment
+        let table_a_metadata = table_a_commitment
             .column_commitments_mut()
             .column_metadata_mut()
             .get_mut(&column_a_id)
-            .unwrap()
-            .bounds_mut() = ColumnBounds::BigInt(Bounds::bounded(i64::MIN, i64::MAX).unwrap());
+            .unwrap();
+        *table_a_metadata.bounds_mut() =
+            ColumnBounds::BigInt(Bounds::bounded(i64::MIN, i64::MAX).unwrap());
+        *table_a_metadata.bounds_strategy_mut() = BoundsStrategy::Max;
 
         let mut table_b_commitment =
             TableCommitment::from_owned_table_with_offset(&table_b, 0, &setup);
         let table_b_id = "table.b".parse().unwrap();
-        *table_b_commitment
+        let table_b_metadata = table_b_commitment
             .column_commitments_mut()
             .column_metadata_mut()
             .get_mut(&column_b_id)
-            .unwrap()
-            .bounds_mut() = ColumnBounds::Int128(Bounds::bounded(i128::MIN, i128::MAX).unwrap());
+            .unwrap();
+        *table_b_metadata.bounds_mut() =
+            ColumnBounds::Int128(Bounds::bounded(i128::MIN, i128::MAX).unwrap());
+        *table_b_metadata.bounds_strategy_mut() = BoundsStrategy::Max;
 
         let expected_query_commitments = QueryCommitments::from_iter([
             (table_a_id, table_a_commitment.clone()),
@@ -367,4 +416,43 @@ mod tests {
         );
         assert_eq!(query_commitments, expected_query_commitments);
     }
+
+    #[test]
+    fn we_can_verify_that_query_commitments_are_anchored_to_a_block() {
+        let table_a: OwnedTable<Curve25519Scalar> = owned_table([bigint("column_a", [1, 2, 3])]);
+        let table_b: OwnedTable<Curve25519Scalar> = owned_table([scalar("column_c", [1, 2])]);
+        let table_a_id = "table.a".parse().unwrap();
+        let table_b_id = "table.b".parse().unwrap();
+        let anchor = ChainAnchor::new([1; 32], 10, 1_700_000_000);
+        let other_anchor = ChainAnchor::new([2; 32], 11, 1_700_000_012);
+
+        let commitment_a =
+            TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(&table_a, 0, &());
+        let commitment_b = TableCommitment::from_owned_table_with_offset(&table_b, 0, &());
+
+        let query_commitments = QueryCommitments::from_iter([
+            (table_a_id, commitment_a.clone().with_anchor(anchor)),
+            (table_b_id, commitment_b.clone().with_anchor(anchor)),
+        ]);
+        assert_eq!(query_commitments.verify_anchors(&anchor), Ok(()));
+        assert_eq!(
+            query_commitments.verify_anchors(&other_anchor),
+            Err(ChainAnchorError::Mismatch {
+                table_ref: table_a_id,
+                expected: other_anchor,
+                actual: anchor,
+            })
+        );
+
+        let query_commitments = QueryCommitments::from_iter([
+            (table_a_id, commitment_a.with_anchor(anchor)),
+            (table_b_id, commitment_b),
+        ]);
+        assert_eq!(
+            query_commitments.verify_anchors(&anchor),
+            Err(ChainAnchorError::Unanchored {
+                table_ref: table_b_id
+            })
+        );
+    }
 }
@@ -0,0 +1,49 @@
+use super::DuplicateIdentifiers;
+use crate::base::{database::LiteralValue, scalar::Scalar};
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use thiserror::Error;
+
+/// A change to the schema of a [`TableCommitment`](super::TableCommitment).
+///
+/// Table commitments record their schema changes, so that a verifier that knows the commitment
+/// from before the changes can check the commitment from after them. Renaming and dropping
+/// columns leaves the commitments to the remaining columns unchanged, and the commitment to an
+/// added column can be recomputed from its default and rows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaChange<S: Scalar> {
+    /// A column was renamed, keeping its commitment and position.
+    RenameColumn {
+        /// The previous name of the column
+        from: Identifier,
+        /// The new name of the column
+        to: Identifier,
+    },
+    /// A column was added, with the default value in every row that was committed to.
+    AddColumn {
+        /// The name of the column
+        identifier: Identifier,
+        /// The value of the column in the rows that were committed to before it was added
+        default: LiteralValue<S>,
+        /// The rows that were committed to when the column was added
+        rows: Range<usize>,
+    },
+    /// A column was dropped along with its commitment.
+    DropColumn {
+        /// The name of the column
+        identifier: Identifier,
+    },
+}
+
+/// Errors that can occur when changing the schema of a
+/// [`TableCommitment`](super::TableCommitment).
+#[derive(Debug, Error)]
+pub enum SchemaEvolutionError {
+    /// The column to change is not committed to.
+    #[error("cannot change the schema of missing column: {0}")]
+    MissingColumn(Identifier),
+    /// The new name of a column is already committed to.
+    #[error(transparent)]
+    DuplicateIdentifiers(#[from] DuplicateIdentifiers),
+}
@@ -1,19 +1,26 @@
+#[cfg(feature = "signed-proofs")]
+use super::{batch_provenance::verify_attestations, BatchAttestation};
 use super::{
-    committable_column::CommittableColumn, AppendColumnCommitmentsError, ColumnCommitments,
-    ColumnCommitmentsMismatch, Commitment, DuplicateIdentifiers,
+    committable_column::CommittableColumn, AppendColumnCommitmentsError, BatchProvenance,
+    BatchProvenanceError, BoundsStrategy, ChainAnchor, ColumnCommitmentMetadataMapExt,
+    ColumnCommitments, ColumnCommitmentsMismatch, Commitment, DuplicateIdentifiers, SchemaChange,
+    SchemaEvolutionError, SignerSet,
 };
+#[cfg(feature = "varchar-normalization")]
+use crate::base::database::VarCharNormalization;
 use crate::base::{
     database::{
         ArrayRefExt, ArrowArrayToColumnConversionError, Column, ColumnField, CommitmentAccessor,
-        OwnedTable, TableRef,
+        LiteralValue, OwnedColumn, OwnedTable, TableRef,
     },
     scalar::Scalar,
 };
 use arrow::record_batch::RecordBatch;
 use bumpalo::Bump;
-use proof_of_sql_parser::{Identifier, ParseError};
+use indexmap::IndexMap;
+use proof_of_sql_parser::{Identifier, IdentifierPolicy, ParseError};
 use serde::{Deserialize, Serialize};
-use std::ops::Range;
+use std::{borrow::Cow, ops::Range};
 use thiserror::Error;
 
 /// Cannot create a [`TableCommitment`] with a negative range.
@@ -94,6 +101,9 @@ where
 {
     column_commitments: ColumnCommitments<C>,
     range: Range<usize>,
+    schema_history: Vec<SchemaChange<C::Scalar>>,
+    anchor: Option<ChainAnchor>,
+    provenance: Vec<BatchProvenance>,
 }
 
 impl<C: Commitment> TableCommitment<C> {
@@ -128,12 +138,27 @@ impl<C: Commitment> TableCommitment<C> {
             Ok(TableCommitment {
                 column_commitments,
                 range,
+                schema_history: Vec::new(),
+                anchor: None,
+                provenance: Vec::new(),
             })
         } else {
             Err(NegativeRange)
         }
     }
 
+    /// Replace the schema history of this [`TableCommitment`], e.g. when upgrading a stored
+    /// commitment.
+    pub(super) fn with_schema_history(
+        self,
+        schema_history: Vec<SchemaChange<C::Scalar>>,
+    ) -> TableCommitment<C> {
+        TableCommitment {
+            schema_history,
+            ..self
+        }
+    }
+
     /// Returns a reference to this type's internal [`ColumnCommitments`].
     pub fn column_commitments(&self) -> &ColumnCommitments<C> {
         &self.column_commitments
@@ -154,6 +179,65 @@ impl<C: Commitment> TableCommitment<C> {
         self.range.len()
     }
 
+    /// Returns the changes to the schema of the table, in the order that they were made.
+    pub fn schema_history(&self) -> &[SchemaChange<C::Scalar>] {
+        &self.schema_history
+    }
+
+    /// Returns the block that this commitment is anchored to, if any.
+    ///
+    /// See [`TableCommitment::with_anchor`].
+    pub fn anchor(&self) -> Option<&ChainAnchor> {
+        self.anchor.as_ref()
+    }
+
+    /// Anchor this commitment to a block of an external blockchain, e.g. the block whose state of
+    /// the table it commits to.
+    ///
+    /// The anchor describes the commitment as it is, so any later change to the rows or the
+    /// schema of the commitment removes it, and the changed commitment has to be anchored again.
+    pub fn with_anchor(self, anchor: ChainAnchor) -> TableCommitment<C> {
+        TableCommitment {
+            anchor: Some(anchor),
+            ..self
+        }
+    }
+
+    /// Returns the batches that were appended with `TableCommitment::try_append_signed_owned_table`,
+    /// which requires the `signed-proofs` feature, in the order of their rows.
+    pub fn provenance(&self) -> &[BatchProvenance] {
+        &self.provenance
+    }
+
+    /// Check that every row of this commitment was appended in a batch that was attested by
+    /// exactly the given producers.
+    ///
+    /// Tables whose batches are attested by varying producers can be checked against
+    /// [`TableCommitment::provenance`] instead.
+    pub fn verify_provenance(&self, signers: &SignerSet) -> Result<(), BatchProvenanceError> {
+        let digest = signers.digest();
+        let mut attested_until = self.range.start;
+        for batch in &self.provenance {
+            if batch.rows().start != attested_until {
+                return Err(BatchProvenanceError::UnattestedRows {
+                    rows: attested_until..batch.rows().start,
+                });
+            }
+            if batch.signers() != digest {
+                return Err(BatchProvenanceError::UnexpectedSigners {
+                    rows: batch.rows().clone(),
+                });
+            }
+            attested_until = batch.rows().end;
+        }
+        if attested_until != self.range.end {
+            return Err(BatchProvenanceError::UnattestedRows {
+                rows: attested_until..self.range.end,
+            });
+        }
+        Ok(())
+    }
+
     /// Returns a [`TableCommitment`] to the provided columns with the given row offset.
     ///
     /// Provided columns must have the same length and no duplicate identifiers.
@@ -182,6 +266,9 @@ impl<C: Commitment> TableCommitment<C> {
         Ok(TableCommitment {
             column_commitments,
             range: offset..offset + num_rows,
+            schema_history: Vec::new(),
+            anchor: None,
+            provenance: Vec::new(),
         })
     }
 
@@ -198,10 +285,99 @@ impl<C: Commitment> TableCommitment<C> {
             .expect("OwnedTables cannot have columns of mixed length or duplicate identifiers")
     }
 
+    /// Returns a [`TableCommitment`] to the provided table with the given row offset, after
+    /// normalizing the strings of its VarChar columns.
+    ///
+    /// The normalization is recorded in the metadata of the VarChar columns, so that rows appended
+    /// with [`TableCommitment::append_owned_table`] are normalized the same way.
+    #[cfg(feature = "varchar-normalization")]
+    pub fn from_owned_table_with_offset_and_varchar_normalization<S>(
+        owned_table: &OwnedTable<S>,
+        offset: usize,
+        varchar_normalization: VarCharNormalization,
+        setup: &C::PublicSetup<'_>,
+    ) -> TableCommitment<C>
+    where
+        S: Scalar,
+    {
+        let normalized_columns: Vec<_> = owned_table
+            .inner_table()
+            .iter()
+            .map(|(identifier, column)| {
+                (identifier, varchar_normalization.normalize_column(column))
+            })
+            .collect();
+        let table_commitment = Self::try_from_columns_with_offset(
+            normalized_columns
+                .iter()
+                .map(|(identifier, column)| (*identifier, column.as_ref())),
+            offset,
+            setup,
+        )
+        .expect("OwnedTables cannot have columns of mixed length or duplicate identifiers");
+        let column_commitments = table_commitment
+            .column_commitments
+            .into_iter()
+            .map(|(identifier, metadata, commitment)| {
+                let metadata = metadata
+                    .try_with_varchar_normalization(varchar_normalization)
+                    .unwrap_or(metadata);
+                (identifier, metadata, commitment)
+            })
+            .collect();
+        TableCommitment {
+            column_commitments,
+            range: table_commitment.range,
+            schema_history: table_commitment.schema_history,
+            anchor: table_commitment.anchor,
+            provenance: table_commitment.provenance,
+        }
+    }
+
+    /// Returns a [`TableCommitment`] to the provided table with the given row offset, where the
+    /// bounds of each listed column are widened with its [`BoundsStrategy`].
+    ///
+    /// Columns that are not listed keep [`BoundsStrategy::Exact`] bounds, which produce the
+    /// smallest inequality proofs. The strategies are recorded in the metadata of the columns, so
+    /// that the bounds of appended rows are widened the same way.
+    pub fn from_owned_table_with_offset_and_bounds_strategies<S>(
+        owned_table: &OwnedTable<S>,
+        offset: usize,
+        bounds_strategies: &IndexMap<Identifier, BoundsStrategy>,
+        setup: &C::PublicSetup<'_>,
+    ) -> TableCommitment<C>
+    where
+        S: Scalar,
+    {
+        let table_commitment = Self::from_owned_table_with_offset(owned_table, offset, setup);
+        let column_commitments = table_commitment
+            .column_commitments
+            .into_iter()
+            .map(|(identifier, metadata, commitment)| {
+                let metadata = bounds_strategies
+                    .get(&identifier)
+                    .map_or(metadata, |&bounds_strategy| {
+                        metadata.with_bounds_strategy(bounds_strategy)
+                    });
+                (identifier, metadata, commitment)
+            })
+            .collect();
+        TableCommitment {
+            column_commitments,
+            range: table_commitment.range,
+            schema_history: table_commitment.schema_history,
+            anchor: table_commitment.anchor,
+            provenance: table_commitment.provenance,
+        }
+    }
+
     /// Append rows of data from the provided columns to the existing [`TableCommitment`].
     ///
     /// The row offset is assumed to be the end of the [`TableCommitment`]'s current range.
     ///
+    /// The strings of VarChar columns must already be normalized like the existing rows.
+    /// [`TableCommitment::append_owned_table`] takes care of this.
+    ///
     /// Will error on a variety of mismatches, or if the provided columns have mixed length.
     pub fn try_append_rows<'a, COL>(
         &mut self,
@@ -225,12 +401,15 @@ impl<C: Commitment> TableCommitment<C> {
             setup,
         )?;
         self.range.end += num_rows;
+        self.anchor = None;
 
         Ok(())
     }
 
     /// Append data of the provided table to the exiting [`TableCommitment`].
     ///
+    /// The strings of VarChar columns are normalized as recorded in their metadata.
+    ///
     /// Will error on a variety of mismatches.
     /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
     pub fn append_owned_table<S>(
@@ -241,23 +420,138 @@ impl<C: Commitment> TableCommitment<C> {
     where
         S: Scalar,
     {
-        self.try_append_rows(owned_table.inner_table(), setup)
-            .map_err(|e| match e {
-                AppendTableCommitmentError::AppendColumnCommitments(e) => match e {
-                    AppendColumnCommitmentsError::Mismatch(e) => e,
-                    AppendColumnCommitmentsError::DuplicateIdentifiers(_) => {
-                        panic!("OwnedTables cannot have duplicate identifiers");
-                    }
-                },
-                AppendTableCommitmentError::MixedLengthColumns(_) => {
-                    panic!("OwnedTables cannot have columns of mixed length");
+        let normalized_columns = self.try_normalize_owned_table(owned_table)?;
+        self.try_append_rows(
+            normalized_columns
+                .iter()
+                .map(|(identifier, column)| (*identifier, column.as_ref())),
+            setup,
+        )
+        .map_err(|e| match e {
+            AppendTableCommitmentError::AppendColumnCommitments(e) => match e {
+                AppendColumnCommitmentsError::Mismatch(e) => e,
+                AppendColumnCommitmentsError::DuplicateIdentifiers(_) => {
+                    panic!("OwnedTables cannot have duplicate identifiers");
+                }
+            },
+            AppendTableCommitmentError::MixedLengthColumns(_) => {
+                panic!("OwnedTables cannot have columns of mixed length");
+            }
+        })
+    }
+
+    /// Returns the commitment to the rows of the provided table as
+    /// [`TableCommitment::append_owned_table`] would append them, without changing this
+    /// [`TableCommitment`].
+    ///
+    /// The result starts where this commitment ends, so it can be added to it with
+    /// [`TableCommitment::try_add`], or handed to holders of this commitment in place of the rows.
+    ///
+    /// Will error on a variety of mismatches.
+    /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
+    pub fn try_owned_table_delta<S>(
+        &self,
+        owned_table: &OwnedTable<S>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<TableCommitment<C>, ColumnCommitmentsMismatch>
+    where
+        S: Scalar,
+    {
+        let normalized_columns = self.try_normalize_owned_table(owned_table)?;
+        let delta = Self::try_from_columns_with_offset(
+            normalized_columns
+                .iter()
+                .map(|(identifier, column)| (*identifier, column.as_ref())),
+            self.range.end,
+            setup,
+        )
+        .expect("OwnedTables cannot have columns of mixed length or duplicate identifiers");
+        // The new rows inherit the normalization and bounds strategy of the existing rows.
+        let column_commitments: ColumnCommitments<C> = delta
+            .column_commitments
+            .into_iter()
+            .map(|(identifier, metadata, commitment)| {
+                let metadata = match self.column_commitments.get_metadata(&identifier) {
+                    Some(existing) => metadata
+                        .try_with_varchar_normalization(existing.varchar_normalization())
+                        .unwrap_or(metadata)
+                        .with_bounds_strategy(existing.bounds_strategy()),
+                    None => metadata,
+                };
+                (identifier, metadata, commitment)
+            })
+            .collect();
+        self.column_commitments
+            .column_metadata()
+            .clone()
+            .try_union(column_commitments.column_metadata().clone())?;
+        Ok(TableCommitment {
+            column_commitments,
+            range: delta.range,
+            schema_history: self.schema_history.clone(),
+            anchor: None,
+            provenance: Vec::new(),
+        })
+    }
+
+    /// Normalizes the strings of the VarChar columns of the provided table as recorded in the
+    /// metadata of this [`TableCommitment`].
+    ///
+    /// Will error if a column was committed with a normalization that this build does not
+    /// support.
+    fn try_normalize_owned_table<'a, S: Scalar>(
+        &self,
+        owned_table: &'a OwnedTable<S>,
+    ) -> Result<Vec<(&'a Identifier, Cow<'a, OwnedColumn<S>>)>, ColumnCommitmentsMismatch> {
+        owned_table
+            .inner_table()
+            .iter()
+            .map(|(identifier, column)| {
+                let varchar_normalization = self
+                    .column_commitments
+                    .get_metadata(identifier)
+                    .map(|metadata| metadata.varchar_normalization())
+                    .unwrap_or_default();
+                if !varchar_normalization.is_supported() {
+                    return Err(ColumnCommitmentsMismatch::UnsupportedVarCharNormalization(
+                        identifier.to_string(),
+                        varchar_normalization,
+                    ));
                 }
+                Ok((identifier, varchar_normalization.normalize_column(column)))
             })
+            .collect()
+    }
+
+    /// Append a batch that is attested by its producers, see [`BatchAttestation::sign`], and
+    /// record the digest of the producers in the [`TableCommitment::provenance`].
+    ///
+    /// Every attestation must be a valid signature of the batch as the rows that it is appended
+    /// as. On error, the commitment is not changed.
+    #[cfg(feature = "signed-proofs")]
+    pub fn try_append_signed_owned_table<S>(
+        &mut self,
+        batch: &OwnedTable<S>,
+        attestations: &[BatchAttestation],
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), BatchProvenanceError>
+    where
+        S: Scalar,
+    {
+        let start = self.range.end;
+        let signers = verify_attestations(batch, start, attestations)?;
+        self.append_owned_table(batch, setup)?;
+        self.provenance
+            .push(BatchProvenance::new(start..self.range.end, signers));
+        Ok(())
     }
 
     /// Add new columns to this [`TableCommitment`].
     ///
     /// Columns must have the same length as the current commitment and no duplicate identifiers.
+    ///
+    /// Since the producers of the committed rows did not attest the new columns, this removes
+    /// the [`TableCommitment::provenance`] of the rows.
     pub fn try_extend_columns<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = (&'a Identifier, COL)>,
@@ -284,7 +578,65 @@ impl<C: Commitment> TableCommitment<C> {
             self.range.start,
             setup,
         )?;
+        self.anchor = None;
+        self.provenance.clear();
+
+        Ok(())
+    }
+
+    /// Rename a column without recommitting to it.
+    ///
+    /// The column keeps its commitment, metadata and position, and the change is recorded in the
+    /// [`TableCommitment::schema_history`].
+    pub fn try_rename_column(
+        &mut self,
+        from: &Identifier,
+        to: Identifier,
+    ) -> Result<(), SchemaEvolutionError> {
+        self.column_commitments.try_rename_column(from, to)?;
+        self.schema_history
+            .push(SchemaChange::RenameColumn { from: *from, to });
+        self.anchor = None;
+        Ok(())
+    }
+
+    /// Add a column whose value is the default in every row that has been committed to.
+    ///
+    /// Only the new column is committed to, and the change is recorded in the
+    /// [`TableCommitment::schema_history`]. Later rows are appended with values for the new column
+    /// like for any other column.
+    pub fn try_add_column_with_default(
+        &mut self,
+        identifier: Identifier,
+        default: LiteralValue<C::Scalar>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), SchemaEvolutionError> {
+        let alloc = Bump::new();
+        let column = Column::from_literal_with_length(&default, self.num_rows(), &alloc);
+        self.try_extend_columns([(&identifier, &column)], setup)
+            .map_err(|e| match e {
+                TableCommitmentFromColumnsError::DuplicateIdentifiers(e) => e,
+                TableCommitmentFromColumnsError::MixedLengthColumns(_) => {
+                    panic!("the default column has as many rows as the table commitment")
+                }
+            })?;
+        self.schema_history.push(SchemaChange::AddColumn {
+            identifier,
+            default,
+            rows: self.range.clone(),
+        });
+        Ok(())
+    }
 
+    /// Drop a column along with its commitment, without recommitting to the other columns.
+    ///
+    /// The change is recorded in the [`TableCommitment::schema_history`].
+    pub fn try_drop_column(&mut self, identifier: &Identifier) -> Result<(), SchemaEvolutionError> {
+        self.column_commitments.try_drop_column(identifier)?;
+        self.schema_history.push(SchemaChange::DropColumn {
+            identifier: *identifier,
+        });
+        self.anchor = None;
         Ok(())
     }
 
@@ -308,10 +660,16 @@ impl<C: Commitment> TableCommitment<C> {
         };
 
         let column_commitments = self.column_commitments.try_add(other.column_commitments)?;
+        let mut provenance = self.provenance;
+        provenance.extend(other.provenance);
+        provenance.sort_by_key(|batch| batch.rows().start);
 
         Ok(TableCommitment {
             column_commitments,
             range,
+            schema_history: self.schema_history,
+            anchor: None,
+            provenance,
         })
     }
 
@@ -342,10 +700,18 @@ impl<C: Commitment> TableCommitment<C> {
         };
 
         let column_commitments = self.column_commitments.try_sub(other.column_commitments)?;
+        let provenance = self
+            .provenance
+            .into_iter()
+            .filter(|batch| range.start <= batch.rows().start && batch.rows().end <= range.end)
+            .collect();
 
         Ok(TableCommitment {
             column_commitments,
             range,
+            schema_history: self.schema_history,
+            anchor: None,
+            provenance,
         })
     }
 
@@ -358,9 +724,22 @@ impl<C: Commitment> TableCommitment<C> {
         &mut self,
         batch: &RecordBatch,
         setup: &C::PublicSetup<'_>,
+    ) -> Result<(), AppendRecordBatchTableCommitmentError> {
+        self.try_append_record_batch_with_policy(batch, IdentifierPolicy::default(), setup)
+    }
+
+    /// Append an arrow [`RecordBatch`] to the existing [`TableCommitment`], using the policy to
+    /// turn field names into identifiers.
+    ///
+    /// See [`TableCommitment::try_append_record_batch`].
+    pub fn try_append_record_batch_with_policy(
+        &mut self,
+        batch: &RecordBatch,
+        policy: IdentifierPolicy,
+        setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendRecordBatchTableCommitmentError> {
         match self.try_append_rows(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
+            batch_to_columns::<C::Scalar>(batch, policy, &Bump::new())?
                 .iter()
                 .map(|(a, b)| (a, b)),
             setup,
@@ -392,9 +771,25 @@ impl<C: Commitment> TableCommitment<C> {
         batch: &RecordBatch,
         offset: usize,
         setup: &C::PublicSetup<'_>,
+    ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
+        Self::try_from_record_batch_with_offset_and_policy(
+            batch,
+            offset,
+            IdentifierPolicy::default(),
+            setup,
+        )
+    }
+
+    /// Returns a [`TableCommitment`] to the provided arrow [`RecordBatch`] with the given row
+    /// offset, using the policy to turn field names into identifiers.
+    pub fn try_from_record_batch_with_offset_and_policy(
+        batch: &RecordBatch,
+        offset: usize,
+        policy: IdentifierPolicy,
+        setup: &C::PublicSetup<'_>,
     ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
         match Self::try_from_columns_with_offset(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
+            batch_to_columns::<C::Scalar>(batch, policy, &Bump::new())?
                 .iter()
                 .map(|(a, b)| (a, b)),
             offset,
@@ -413,6 +808,7 @@ impl<C: Commitment> TableCommitment<C> {
 
 fn batch_to_columns<'a, S: Scalar + 'a>(
     batch: &'a RecordBatch,
+    policy: IdentifierPolicy,
     alloc: &'a Bump,
 ) -> Result<Vec<(Identifier, Column<'a, S>)>, RecordBatchToColumnsError> {
     batch
@@ -421,7 +817,7 @@ fn batch_to_columns<'a, S: Scalar + 'a>(
         .into_iter()
         .zip(batch.columns())
         .map(|(field, array)| {
-            let identifier: Identifier = field.name().parse()?;
+            let identifier = policy.identifier(field.name())?;
             let column: Column<S> = array.to_column(alloc, &(0..array.len()), None)?;
             Ok((identifier, column))
         })
@@ -451,6 +847,7 @@ mod tests {
     use super::*;
     use crate::{
         base::{
+            commitment::{Bounds, ColumnBounds},
             database::{owned_table_utility::*, OwnedColumn},
             scalar::Curve25519Scalar,
         },
@@ -699,6 +1096,130 @@ mod tests {
         assert_eq!(table_commitment, table_commitment_clone)
     }
 
+    #[test]
+    #[cfg(feature = "varchar-normalization")]
+    fn we_can_create_and_append_to_table_commitments_with_varchar_normalization() {
+        let precomposed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+
+        let initial_columns: OwnedTable<Curve25519Scalar> =
+            owned_table([bigint("id", [1, 2]), varchar("name", [decomposed, "tea"])]);
+        let mut table_commitment: TableCommitment<RistrettoPoint> =
+            TableCommitment::from_owned_table_with_offset_and_varchar_normalization(
+                &initial_columns,
+                0,
+                VarCharNormalization::Nfc,
+                &(),
+            );
+        let metadata = table_commitment.column_commitments().column_metadata();
+        assert_eq!(
+            metadata[&"name".parse::<Identifier>().unwrap()].varchar_normalization(),
+            VarCharNormalization::Nfc
+        );
+        assert_eq!(
+            metadata[&"id".parse::<Identifier>().unwrap()].varchar_normalization(),
+            VarCharNormalization::None
+        );
+
+        let append_columns: OwnedTable<Curve25519Scalar> =
+            owned_table([bigint("id", [3]), varchar("name", [decomposed])]);
+        table_commitment
+            .append_owned_table(&append_columns, &())
+            .unwrap();
+
+        // the strings were committed in their normalized form
+        let normalized_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint("id", [1, 2, 3]),
+            varchar("name", [precomposed, "tea", precomposed]),
+        ]);
+        let expected_commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &normalized_columns,
+            0,
+            &(),
+        );
+        assert_eq!(
+            table_commitment.column_commitments().commitments(),
+            expected_commitment.column_commitments().commitments()
+        );
+
+        // commitments with different normalizations cannot be combined
+        let other_commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &append_columns,
+            3,
+            &(),
+        );
+        assert!(matches!(
+            table_commitment.try_add(other_commitment),
+            Err(TableCommitmentArithmeticError::ColumnMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn we_can_create_and_append_to_table_commitments_with_bounds_strategies() {
+        let id: Identifier = "id".parse().unwrap();
+        let amount: Identifier = "amount".parse().unwrap();
+        let name: Identifier = "name".parse().unwrap();
+
+        let initial_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint(id, [1, 2]),
+            bigint(amount, [1, 2]),
+            varchar(name, ["a", "b"]),
+        ]);
+        let bounds_strategies = IndexMap::from_iter([
+            (amount, BoundsStrategy::Padded),
+            (name, BoundsStrategy::Max),
+        ]);
+        let mut table_commitment: TableCommitment<RistrettoPoint> =
+            TableCommitment::from_owned_table_with_offset_and_bounds_strategies(
+                &initial_columns,
+                0,
+                &bounds_strategies,
+                &(),
+            );
+        let metadata = table_commitment.column_commitments().column_metadata();
+        assert_eq!(metadata[&id].bounds_strategy(), BoundsStrategy::Exact);
+        assert_eq!(
+            metadata[&id].bounds(),
+            &ColumnBounds::BigInt(Bounds::sharp(1, 2).unwrap())
+        );
+        assert_eq!(metadata[&amount].bounds_strategy(), BoundsStrategy::Padded);
+        assert_eq!(
+            metadata[&amount].bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-4, 3).unwrap())
+        );
+        // columns without an order have no bounds to widen
+        assert_eq!(metadata[&name].bounds_strategy(), BoundsStrategy::Exact);
+
+        let append_columns: OwnedTable<Curve25519Scalar> =
+            owned_table([bigint(id, [5]), bigint(amount, [5]), varchar(name, ["c"])]);
+        table_commitment
+            .append_owned_table(&append_columns, &())
+            .unwrap();
+
+        // appended rows are widened with the recorded strategy
+        let metadata = table_commitment.column_commitments().column_metadata();
+        assert_eq!(
+            metadata[&id].bounds(),
+            &ColumnBounds::BigInt(Bounds::sharp(1, 5).unwrap())
+        );
+        assert_eq!(metadata[&amount].bounds_strategy(), BoundsStrategy::Padded);
+        assert_eq!(
+            metadata[&amount].bounds(),
+            &ColumnBounds::BigInt(Bounds::bounded(-8, 7).unwrap())
+        );
+
+        // commitments with different strategies cannot be combined
+        let other_commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &append_columns,
+            3,
+            &(),
+        );
+        assert!(matches!(
+            table_commitment.try_add(other_commitment),
+            Err(TableCommitmentArithmeticError::ColumnMismatch(_))
+        ));
+    }
+
     #[test]
     fn we_cannot_append_mismatched_columns_to_table_commitment() {
         let base_table: OwnedTable<Curve25519Scalar> = owned_table([
@@ -843,6 +1364,205 @@ mod tests {
         assert_eq!(table_commitment, expected_table_commitment);
     }
 
+    #[test]
+    fn we_can_rename_and_drop_columns_of_table_commitment_without_recommitting() {
+        let bigint_id: Identifier = "bigint_column".parse().unwrap();
+        let bigint_data = [1i64, 5, -5, 0, 10];
+
+        let varchar_id: Identifier = "varchar_column".parse().unwrap();
+        let renamed_id: Identifier = "renamed_column".parse().unwrap();
+        let varchar_data = ["Lorem", "ipsum", "dolor", "sit", "amet"];
+
+        let scalar_id: Identifier = "scalar_column".parse().unwrap();
+        let scalar_data = [1000, 2000, 3000, -1000, 0];
+
+        let initial_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint(bigint_id, bigint_data[..2].to_vec()),
+            varchar(varchar_id, varchar_data[..2].to_vec()),
+            scalar(scalar_id, scalar_data[..2].to_vec()),
+        ]);
+        let mut table_commitment = TableCommitment::<RistrettoPoint>::try_from_columns_with_offset(
+            initial_columns.inner_table(),
+            2,
+            &(),
+        )
+        .unwrap();
+        let varchar_commitment = table_commitment
+            .column_commitments()
+            .get_commitment(&varchar_id)
+            .unwrap();
+
+        table_commitment
+            .try_rename_column(&varchar_id, renamed_id)
+            .unwrap();
+        table_commitment.try_drop_column(&bigint_id).unwrap();
+        assert_eq!(
+            table_commitment
+                .column_commitments()
+                .get_commitment(&renamed_id),
+            Some(varchar_commitment)
+        );
+        assert_eq!(
+            table_commitment.schema_history(),
+            [
+                SchemaChange::RenameColumn {
+                    from: varchar_id,
+                    to: renamed_id,
+                },
+                SchemaChange::DropColumn {
+                    identifier: bigint_id,
+                },
+            ]
+        );
+
+        // Later rows are appended under the new schema
+        let append_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            varchar(renamed_id, varchar_data[2..].to_vec()),
+            scalar(scalar_id, scalar_data[2..].to_vec()),
+        ]);
+        table_commitment
+            .append_owned_table(&append_columns, &())
+            .unwrap();
+
+        let expected_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            varchar(renamed_id, varchar_data),
+            scalar(scalar_id, scalar_data),
+        ]);
+        let expected_table_commitment =
+            TableCommitment::try_from_columns_with_offset(expected_columns.inner_table(), 2, &())
+                .unwrap();
+        assert_eq!(
+            table_commitment.column_commitments(),
+            expected_table_commitment.column_commitments()
+        );
+        assert_eq!(table_commitment.range(), &(2..7));
+    }
+
+    #[test]
+    fn we_can_add_a_column_with_a_default_to_table_commitment() {
+        let bigint_id: Identifier = "bigint_column".parse().unwrap();
+        let bigint_data = [1i64, 5, -5, 0, 10];
+
+        let boolean_id: Identifier = "boolean_column".parse().unwrap();
+        let boolean_data = [true, true, true, false, true];
+
+        let initial_columns: OwnedTable<Curve25519Scalar> =
+            owned_table([bigint(bigint_id, bigint_data[..3].to_vec())]);
+        let mut table_commitment = TableCommitment::<RistrettoPoint>::try_from_columns_with_offset(
+            initial_columns.inner_table(),
+            2,
+            &(),
+        )
+        .unwrap();
+        let bigint_commitment = table_commitment.column_commitments().commitments()[0];
+
+        table_commitment
+            .try_add_column_with_default(boolean_id, LiteralValue::Boolean(true), &())
+            .unwrap();
+        assert_eq!(
+            table_commitment.column_commitments().commitments()[0],
+            bigint_commitment
+        );
+        assert_eq!(
+            table_commitment.schema_history(),
+            [SchemaChange::AddColumn {
+                identifier: boolean_id,
+                default: LiteralValue::Boolean(true),
+                rows: 2..5,
+            }]
+        );
+
+        let append_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint(bigint_id, bigint_data[3..].to_vec()),
+            boolean(boolean_id, boolean_data[3..].to_vec()),
+        ]);
+        table_commitment
+            .append_owned_table(&append_columns, &())
+            .unwrap();
+
+        let expected_columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint(bigint_id, bigint_data),
+            boolean(boolean_id, boolean_data),
+        ]);
+        let expected_table_commitment =
+            TableCommitment::try_from_columns_with_offset(expected_columns.inner_table(), 2, &())
+                .unwrap();
+        assert_eq!(
+            table_commitment.column_commitments(),
+            expected_table_commitment.column_commitments()
+        );
+    }
+
+    #[test]
+    fn we_can_anchor_a_table_commitment_until_it_changes() {
+        let bigint_id: Identifier = "bigint_column".parse().unwrap();
+        let varchar_id: Identifier = "varchar_column".parse().unwrap();
+        let anchor = ChainAnchor::new([7; 32], 100, 1_700_000_000);
+
+        let columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint(bigint_id, [1i64, 5, -5]),
+            varchar(varchar_id, ["Lorem", "ipsum", "dolor"]),
+        ]);
+        let table_commitment =
+            TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(&columns, 2, &());
+        assert_eq!(table_commitment.anchor(), None);
+        let table_commitment = table_commitment.with_anchor(anchor);
+        assert_eq!(table_commitment.anchor(), Some(&anchor));
+
+        let mut appended = table_commitment.clone();
+        appended.append_owned_table(&columns, &()).unwrap();
+        assert_eq!(appended.anchor(), None);
+        let appended = appended.with_anchor(anchor);
+        assert_eq!(appended.anchor().map(ChainAnchor::block_height), Some(100));
+
+        let mut renamed = table_commitment.clone();
+        renamed
+            .try_rename_column(&varchar_id, "renamed_column".parse().unwrap())
+            .unwrap();
+        assert_eq!(renamed.anchor(), None);
+
+        let mut dropped = table_commitment.clone();
+        dropped.try_drop_column(&varchar_id).unwrap();
+        assert_eq!(dropped.anchor(), None);
+
+        let next = TableCommitment::from_owned_table_with_offset(&columns, 5, &());
+        let sum = table_commitment.clone().try_add(next).unwrap();
+        assert_eq!(sum.anchor(), None);
+    }
+
+    #[test]
+    fn we_cannot_change_the_schema_of_missing_or_duplicate_columns() {
+        let bigint_id: Identifier = "bigint_column".parse().unwrap();
+        let varchar_id: Identifier = "varchar_column".parse().unwrap();
+        let missing_id: Identifier = "missing_column".parse().unwrap();
+
+        let columns: OwnedTable<Curve25519Scalar> = owned_table([
+            bigint(bigint_id, [1i64, 5]),
+            varchar(varchar_id, ["Lorem", "ipsum"]),
+        ]);
+        let mut table_commitment =
+            TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(&columns, 0, &());
+        let original_table_commitment = table_commitment.clone();
+
+        assert!(matches!(
+            table_commitment.try_rename_column(&missing_id, bigint_id),
+            Err(SchemaEvolutionError::MissingColumn(_))
+        ));
+        assert!(matches!(
+            table_commitment.try_rename_column(&bigint_id, varchar_id),
+            Err(SchemaEvolutionError::DuplicateIdentifiers(_))
+        ));
+        assert!(matches!(
+            table_commitment.try_drop_column(&missing_id),
+            Err(SchemaEvolutionError::MissingColumn(_))
+        ));
+        assert!(matches!(
+            table_commitment.try_add_column_with_default(varchar_id, LiteralValue::Int(0), &()),
+            Err(SchemaEvolutionError::DuplicateIdentifiers(_))
+        ));
+        assert_eq!(table_commitment, original_table_commitment);
+    }
+
     #[test]
     fn we_can_add_table_commitments() {
         let bigint_id: Identifier = "bigint_column".parse().unwrap();
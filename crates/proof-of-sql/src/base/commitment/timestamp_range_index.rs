@@ -0,0 +1,303 @@
+use super::{
+    ColumnCommitmentsMismatch, Commitment, PartitionedTableCommitment,
+    PartitionedTableCommitmentError, TableCommitment,
+};
+use crate::base::{
+    database::{ColumnType, OwnedColumn, OwnedTable},
+    scalar::Scalar,
+};
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use thiserror::Error;
+
+/// Errors that can occur when building, extending or checking a [`TimestampRangeIndex`].
+#[derive(Debug, Error)]
+pub enum TimestampRangeIndexError {
+    /// The blocks of an index need at least one row.
+    #[error("the blocks of a range index need at least one row")]
+    EmptyBlocks,
+    /// The indexed column is not a timestamp column of the table.
+    #[error("{0} is not a timestamp column of the table")]
+    InvalidIndexedColumn(Identifier),
+    /// The appended rows do not match the columns of the table.
+    #[error(transparent)]
+    Mismatch(#[from] ColumnCommitmentsMismatch),
+    /// The appended rows cannot be added as a new block.
+    #[error(transparent)]
+    Blocks(#[from] PartitionedTableCommitmentError),
+    /// The blocks do not add up to the commitment to the table.
+    #[error("the blocks of the range index do not add up to the commitment to the table")]
+    TableCommitmentMismatch,
+}
+
+/// A coarse index of a timestamp column, which lets the prover skip the blocks of rows that
+/// cannot match a time-range query.
+///
+/// The rows of the table are split into blocks of `block_rows` rows, e.g. 1000, and each block is
+/// committed to separately. The bounds in the metadata of a block are the earliest and latest
+/// timestamp in it, so the blocks are the partitions of a [`PartitionedTableCommitment`]. The
+/// prover chooses the blocks to prove over with [`crate::sql::ast::ProofPlan::prune_partitions`],
+/// and the verifier checks that the skipped blocks could not contain matches with
+/// [`crate::sql::ast::ProofPlan::verify_partition_pruning`].
+///
+/// The index is optional and is kept alongside the commitment to the table by whoever maintains
+/// that commitment. Its bounds are trusted in the same way as the commitment is, while
+/// [`TimestampRangeIndex::verify_table_commitment`] checks that its blocks are the rows of the
+/// table.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampRangeIndex<C: Commitment> {
+    block_rows: usize,
+    blocks: PartitionedTableCommitment<C>,
+}
+
+fn slice_owned_table<S: Scalar>(owned_table: &OwnedTable<S>, rows: Range<usize>) -> OwnedTable<S> {
+    OwnedTable::try_from_iter(
+        owned_table
+            .inner_table()
+            .iter()
+            .map(|(identifier, column)| (*identifier, column.slice(rows.start, rows.end))),
+    )
+    .expect("slices of the columns of a table have the same length")
+}
+
+fn check_indexed_column<S: Scalar>(
+    owned_table: &OwnedTable<S>,
+    indexed_column: Identifier,
+) -> Result<(), TimestampRangeIndexError> {
+    match owned_table
+        .inner_table()
+        .get(&indexed_column)
+        .map(OwnedColumn::column_type)
+    {
+        Some(ColumnType::TimestampTZ(_, _)) => Ok(()),
+        _ => Err(TimestampRangeIndexError::InvalidIndexedColumn(
+            indexed_column,
+        )),
+    }
+}
+
+impl<C: Commitment> TimestampRangeIndex<C> {
+    /// Build the index of a timestamp column of a table whose rows start at the given offset.
+    ///
+    /// A table without rows has a single empty block.
+    pub fn try_from_owned_table_with_offset<S: Scalar>(
+        owned_table: &OwnedTable<S>,
+        offset: usize,
+        indexed_column: Identifier,
+        block_rows: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<Self, TimestampRangeIndexError> {
+        if block_rows == 0 {
+            return Err(TimestampRangeIndexError::EmptyBlocks);
+        }
+        check_indexed_column(owned_table, indexed_column)?;
+        let num_rows = owned_table.num_rows();
+        let blocks = (0..num_rows.max(1))
+            .step_by(block_rows)
+            .map(|start| {
+                let block = slice_owned_table(owned_table, start..num_rows.min(start + block_rows));
+                TableCommitment::from_owned_table_with_offset(&block, offset + start, setup)
+            })
+            .collect();
+        Ok(Self {
+            block_rows,
+            blocks: PartitionedTableCommitment::try_new(indexed_column, blocks)?,
+        })
+    }
+
+    /// Append rows to the table, filling up the last block before starting new ones.
+    ///
+    /// The index is unchanged if the rows cannot be appended.
+    pub fn append_owned_table<S: Scalar>(
+        &mut self,
+        owned_table: &OwnedTable<S>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), TimestampRangeIndexError> {
+        check_indexed_column(owned_table, self.indexed_column())?;
+        let mut blocks = self.blocks.clone();
+        let num_rows = owned_table.num_rows();
+        let mut start = 0;
+        while start < num_rows {
+            let last_block = blocks
+                .partitions()
+                .last()
+                .expect("there is at least one block");
+            let (last_block_rows, offset) = (last_block.num_rows(), last_block.range().end);
+            if last_block_rows < self.block_rows {
+                let end = num_rows.min(start + self.block_rows - last_block_rows);
+                let block = slice_owned_table(owned_table, start..end);
+                blocks.append_to_last_partition(&block, setup)?;
+                start = end;
+            } else {
+                let end = num_rows.min(start + self.block_rows);
+                let block = slice_owned_table(owned_table, start..end);
+                let block = TableCommitment::from_owned_table_with_offset(&block, offset, setup);
+                blocks.try_append_partition(block)?;
+                start = end;
+            }
+        }
+        self.blocks = blocks;
+        Ok(())
+    }
+
+    /// Returns the indexed timestamp column.
+    pub fn indexed_column(&self) -> Identifier {
+        self.blocks.partition_key()
+    }
+
+    /// Returns the largest number of rows in a block.
+    pub fn block_rows(&self) -> usize {
+        self.block_rows
+    }
+
+    /// Returns the blocks as the partitions of a table, for pruning them.
+    pub fn blocks(&self) -> &PartitionedTableCommitment<C> {
+        &self.blocks
+    }
+
+    /// Returns the rows of the given blocks, which the prover needs to hold to prove a query over
+    /// them.
+    pub fn rows(&self, blocks: Range<usize>) -> Range<usize> {
+        self.blocks.table_commitment(blocks).range().clone()
+    }
+
+    /// Checks that the blocks of the index are the rows of the table with the given commitment,
+    /// so that the commitment to the blocks that a query is proven over is a part of it.
+    pub fn verify_table_commitment(
+        &self,
+        table_commitment: &TableCommitment<C>,
+    ) -> Result<(), TimestampRangeIndexError> {
+        let blocks = self
+            .blocks
+            .table_commitment(0..self.blocks.partitions().len());
+        if blocks.range() == table_commitment.range()
+            && blocks.column_commitments().commitments()
+                == table_commitment.column_commitments().commitments()
+            && blocks
+                .column_commitments()
+                .column_metadata()
+                .keys()
+                .eq(table_commitment
+                    .column_commitments()
+                    .column_metadata()
+                    .keys())
+        {
+            Ok(())
+        } else {
+            Err(TimestampRangeIndexError::TableCommitmentMismatch)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "blitzar"))]
+mod tests {
+    use super::*;
+    use crate::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+    use curve25519_dalek::RistrettoPoint;
+    use proof_of_sql_parser::{
+        posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+        utility::ident,
+    };
+
+    fn events(times: impl IntoIterator<Item = i64>) -> OwnedTable<Curve25519Scalar> {
+        let times: Vec<_> = times.into_iter().collect();
+        owned_table([
+            timestamptz(
+                "time",
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::Utc,
+                times.clone(),
+            ),
+            bigint("value", times.iter().map(|time| time % 7)),
+        ])
+    }
+
+    fn range_index(
+        times: impl IntoIterator<Item = i64>,
+        block_rows: usize,
+    ) -> TimestampRangeIndex<RistrettoPoint> {
+        TimestampRangeIndex::try_from_owned_table_with_offset(
+            &events(times),
+            0,
+            ident("time"),
+            block_rows,
+            &(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn we_can_index_a_timestamp_column_in_blocks() {
+        let index = range_index(0..10, 4);
+        assert_eq!(index.indexed_column(), ident("time"));
+        assert_eq!(index.block_rows(), 4);
+        let block_ranges: Vec<_> = index
+            .blocks()
+            .partitions()
+            .iter()
+            .map(|block| block.range().clone())
+            .collect();
+        assert_eq!(block_ranges, [0..4, 4..8, 8..10]);
+        assert_eq!(index.rows(1..3), 4..10);
+
+        let table_commitment =
+            TableCommitment::from_owned_table_with_offset(&events(0..10), 0, &());
+        assert!(index.verify_table_commitment(&table_commitment).is_ok());
+        let other_table_commitment =
+            TableCommitment::from_owned_table_with_offset(&events(1..11), 0, &());
+        assert!(matches!(
+            index.verify_table_commitment(&other_table_commitment),
+            Err(TimestampRangeIndexError::TableCommitmentMismatch)
+        ));
+
+        let empty_index = range_index([], 4);
+        assert_eq!(empty_index.blocks().partitions().len(), 1);
+        assert_eq!(empty_index.rows(0..1), 0..0);
+    }
+
+    #[test]
+    fn appending_rows_fills_up_the_last_block_first() {
+        let mut index = range_index(0..6, 4);
+        index.append_owned_table(&events(6..13), &()).unwrap();
+        assert_eq!(index, range_index(0..13, 4));
+
+        let mut index = range_index([], 4);
+        index.append_owned_table(&events(0..5), &()).unwrap();
+        assert_eq!(index, range_index(0..5, 4));
+    }
+
+    #[test]
+    fn we_cannot_index_invalid_columns_or_append_mismatched_rows() {
+        assert!(matches!(
+            TimestampRangeIndex::<RistrettoPoint>::try_from_owned_table_with_offset(
+                &events(0..4),
+                0,
+                ident("value"),
+                2,
+                &()
+            ),
+            Err(TimestampRangeIndexError::InvalidIndexedColumn(_))
+        ));
+        assert!(matches!(
+            TimestampRangeIndex::<RistrettoPoint>::try_from_owned_table_with_offset(
+                &events(0..4),
+                0,
+                ident("time"),
+                0,
+                &()
+            ),
+            Err(TimestampRangeIndexError::EmptyBlocks)
+        ));
+
+        let mut index = range_index(0..3, 2);
+        let mismatched = owned_table::<Curve25519Scalar>([timestamptz(
+            "time",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [3, 4, 5],
+        )]);
+        assert!(index.append_owned_table(&mismatched, &()).is_err());
+        assert_eq!(index, range_index(0..3, 2));
+    }
+}
@@ -1,4 +1,4 @@
-use super::Commitment;
+use super::{commitment_path::compute_commitments_in_column_batches, Commitment};
 use crate::base::commitment::committable_column::CommittableColumn;
 use thiserror::Error;
 
@@ -102,7 +102,7 @@ impl<C: Commitment> VecCommitmentExt for Vec<C> {
         setup: &Self::CommitmentPublicSetup<'_>,
     ) -> Self {
         let mut commitments = vec![C::default(); committable_columns.len()];
-        C::compute_commitments(&mut commitments, committable_columns, offset, setup);
+        compute_commitments_in_column_batches(&mut commitments, committable_columns, offset, setup);
 
         commitments
     }
@@ -226,6 +226,20 @@ mod tests {
         assert_eq!(commitments, expected_commitments);
     }
 
+    #[test]
+    fn the_commitments_to_empty_columns_are_the_identity_at_any_offset() {
+        let columns = vec![
+            OwnedColumn::<Curve25519Scalar>::BigInt(vec![]),
+            OwnedColumn::VarChar(vec![]),
+            OwnedColumn::Boolean(vec![]),
+        ];
+        for offset in [0, 5, 1 << 20] {
+            let commitments =
+                Vec::<RistrettoPoint>::from_columns_with_offset(&columns, offset, &());
+            assert_eq!(commitments, vec![RistrettoPoint::default(); 3]);
+        }
+    }
+
     #[test]
     fn we_can_append_rows() {
         let column_a = [12i64, 34, 56, 78, 90];
@@ -0,0 +1,673 @@
+use super::{
+    BoundsStrategy, ChainAnchor, ColumnBounds, ColumnCommitmentMetadata, ColumnCommitments,
+    Commitment, CommitmentMigrationError, InvalidColumnCommitmentMetadata, SchemaChange,
+    TableCommitment,
+};
+use crate::base::database::{ColumnType, VarCharNormalization};
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// The serialization layouts that commitment metadata has been stored in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommitmentLayoutVersion {
+    /// The [`CommitmentLayoutVersion::V1`] layout of a bare [`TableCommitment`] or
+    /// [`ColumnCommitmentMetadata`], as serialized before versioning was introduced.
+    ///
+    /// Data in this layout carries no version tag, so it can only be read through
+    /// [`migrate_table_commitment`](super::migrate_table_commitment) or
+    /// [`migrate_column_commitment_metadata`](super::migrate_column_commitment_metadata).
+    Unversioned,
+    /// The first versioned layout.
+    V1,
+    /// The layout that records the varchar normalization of each column.
+    V2,
+    /// The layout that records the schema history of each table.
+    V3,
+    /// The layout that records the bounds strategy of each column.
+    V4,
+    /// The layout that records the chain anchor of each table.
+    V5,
+    /// The layout that records the provenance of the signed batches of each table.
+    V6,
+}
+
+impl CommitmentLayoutVersion {
+    /// The layout used when serializing new commitments.
+    pub const LATEST: CommitmentLayoutVersion = CommitmentLayoutVersion::V6;
+}
+
+/// The [`CommitmentLayoutVersion::V1`] layout of [`ColumnCommitmentMetadata`].
+///
+/// This must not change when [`ColumnCommitmentMetadata`] changes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnCommitmentMetadataV1 {
+    pub(super) column_type: ColumnType,
+    pub(super) bounds: ColumnBounds,
+}
+
+impl TryFrom<ColumnCommitmentMetadataV1> for ColumnCommitmentMetadata {
+    type Error = InvalidColumnCommitmentMetadata;
+
+    fn try_from(metadata: ColumnCommitmentMetadataV1) -> Result<Self, Self::Error> {
+        ColumnCommitmentMetadata::try_new(metadata.column_type, metadata.bounds)
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V1`] layout of [`ColumnCommitments`].
+///
+/// This must not change when [`ColumnCommitments`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct ColumnCommitmentsV1<C> {
+    pub(super) commitments: Vec<C>,
+    pub(super) column_metadata: IndexMap<Identifier, ColumnCommitmentMetadataV1>,
+}
+
+/// The [`CommitmentLayoutVersion::V1`] layout of [`TableCommitment`].
+///
+/// This must not change when [`TableCommitment`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCommitmentV1<C> {
+    pub(super) column_commitments: ColumnCommitmentsV1<C>,
+    pub(super) range: Range<usize>,
+}
+
+/// Validate the stored commitments and metadata of an older layout and zip them into
+/// [`ColumnCommitments`].
+fn upgrade_column_commitments<C, M>(
+    commitments: Vec<C>,
+    column_metadata: IndexMap<Identifier, M>,
+) -> Result<ColumnCommitments<C>, CommitmentMigrationError>
+where
+    C: Commitment,
+    M: TryInto<ColumnCommitmentMetadata, Error = InvalidColumnCommitmentMetadata>,
+{
+    if commitments.len() != column_metadata.len() {
+        return Err(CommitmentMigrationError::ColumnCountMismatch {
+            commitments: commitments.len(),
+            columns: column_metadata.len(),
+        });
+    }
+    Ok(column_metadata
+        .into_iter()
+        .zip(commitments)
+        .map(|((identifier, metadata), commitment)| {
+            let metadata: ColumnCommitmentMetadata = metadata.try_into()?;
+            Ok((identifier, metadata, commitment))
+        })
+        .collect::<Result<ColumnCommitments<C>, InvalidColumnCommitmentMetadata>>()?)
+}
+
+impl<C: Commitment> TryFrom<TableCommitmentV1<C>> for TableCommitment<C> {
+    type Error = CommitmentMigrationError;
+
+    fn try_from(table_commitment: TableCommitmentV1<C>) -> Result<Self, Self::Error> {
+        let ColumnCommitmentsV1 {
+            commitments,
+            column_metadata,
+        } = table_commitment.column_commitments;
+        let column_commitments = upgrade_column_commitments(commitments, column_metadata)?;
+        Ok(TableCommitment::try_new(
+            column_commitments,
+            table_commitment.range,
+        )?)
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V2`] and [`CommitmentLayoutVersion::V3`] layout of
+/// [`ColumnCommitmentMetadata`].
+///
+/// This must not change when [`ColumnCommitmentMetadata`] changes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnCommitmentMetadataV2 {
+    pub(super) column_type: ColumnType,
+    pub(super) bounds: ColumnBounds,
+    pub(super) varchar_normalization: VarCharNormalization,
+}
+
+impl TryFrom<ColumnCommitmentMetadataV2> for ColumnCommitmentMetadata {
+    type Error = InvalidColumnCommitmentMetadata;
+
+    fn try_from(metadata: ColumnCommitmentMetadataV2) -> Result<Self, Self::Error> {
+        ColumnCommitmentMetadata::try_new(metadata.column_type, metadata.bounds)?
+            .try_with_varchar_normalization(metadata.varchar_normalization)
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V2`] and [`CommitmentLayoutVersion::V3`] layout of
+/// [`ColumnCommitments`].
+///
+/// This must not change when [`ColumnCommitments`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct ColumnCommitmentsV2<C> {
+    pub(super) commitments: Vec<C>,
+    pub(super) column_metadata: IndexMap<Identifier, ColumnCommitmentMetadataV2>,
+}
+
+/// The [`CommitmentLayoutVersion::V2`] layout of [`TableCommitment`].
+///
+/// This must not change when [`TableCommitment`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCommitmentV2<C> {
+    pub(super) column_commitments: ColumnCommitmentsV2<C>,
+    pub(super) range: Range<usize>,
+}
+
+impl<C: Commitment> TryFrom<TableCommitmentV2<C>> for TableCommitment<C> {
+    type Error = CommitmentMigrationError;
+
+    fn try_from(table_commitment: TableCommitmentV2<C>) -> Result<Self, Self::Error> {
+        let ColumnCommitmentsV2 {
+            commitments,
+            column_metadata,
+        } = table_commitment.column_commitments;
+        let column_commitments = upgrade_column_commitments(commitments, column_metadata)?;
+        Ok(TableCommitment::try_new(
+            column_commitments,
+            table_commitment.range,
+        )?)
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V3`] layout of [`TableCommitment`].
+///
+/// This must not change when [`TableCommitment`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCommitmentV3<C>
+where
+    C: Commitment,
+{
+    pub(super) column_commitments: ColumnCommitmentsV2<C>,
+    pub(super) range: Range<usize>,
+    pub(super) schema_history: Vec<SchemaChange<C::Scalar>>,
+}
+
+impl<C: Commitment> TryFrom<TableCommitmentV3<C>> for TableCommitment<C> {
+    type Error = CommitmentMigrationError;
+
+    fn try_from(table_commitment: TableCommitmentV3<C>) -> Result<Self, Self::Error> {
+        let ColumnCommitmentsV2 {
+            commitments,
+            column_metadata,
+        } = table_commitment.column_commitments;
+        let column_commitments = upgrade_column_commitments(commitments, column_metadata)?;
+        Ok(
+            TableCommitment::try_new(column_commitments, table_commitment.range)?
+                .with_schema_history(table_commitment.schema_history),
+        )
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V4`], [`CommitmentLayoutVersion::V5`] and
+/// [`CommitmentLayoutVersion::V6`] layout of [`ColumnCommitmentMetadata`].
+///
+/// This must not change when [`ColumnCommitmentMetadata`] changes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnCommitmentMetadataV4 {
+    pub(super) column_type: ColumnType,
+    pub(super) bounds: ColumnBounds,
+    pub(super) varchar_normalization: VarCharNormalization,
+    pub(super) bounds_strategy: BoundsStrategy,
+}
+
+impl TryFrom<ColumnCommitmentMetadataV4> for ColumnCommitmentMetadata {
+    type Error = InvalidColumnCommitmentMetadata;
+
+    fn try_from(metadata: ColumnCommitmentMetadataV4) -> Result<Self, Self::Error> {
+        Ok(
+            ColumnCommitmentMetadata::try_new(metadata.column_type, metadata.bounds)?
+                .try_with_varchar_normalization(metadata.varchar_normalization)?
+                .with_recorded_bounds_strategy(metadata.bounds_strategy),
+        )
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V4`] and [`CommitmentLayoutVersion::V5`] layout of
+/// [`ColumnCommitments`].
+///
+/// This must not change when [`ColumnCommitments`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct ColumnCommitmentsV4<C> {
+    pub(super) commitments: Vec<C>,
+    pub(super) column_metadata: IndexMap<Identifier, ColumnCommitmentMetadataV4>,
+}
+
+/// The [`CommitmentLayoutVersion::V4`] layout of [`TableCommitment`].
+///
+/// This must not change when [`TableCommitment`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCommitmentV4<C>
+where
+    C: Commitment,
+{
+    pub(super) column_commitments: ColumnCommitmentsV4<C>,
+    pub(super) range: Range<usize>,
+    pub(super) schema_history: Vec<SchemaChange<C::Scalar>>,
+}
+
+impl<C: Commitment> TryFrom<TableCommitmentV4<C>> for TableCommitment<C> {
+    type Error = CommitmentMigrationError;
+
+    fn try_from(table_commitment: TableCommitmentV4<C>) -> Result<Self, Self::Error> {
+        let ColumnCommitmentsV4 {
+            commitments,
+            column_metadata,
+        } = table_commitment.column_commitments;
+        let column_commitments = upgrade_column_commitments(commitments, column_metadata)?;
+        Ok(
+            TableCommitment::try_new(column_commitments, table_commitment.range)?
+                .with_schema_history(table_commitment.schema_history),
+        )
+    }
+}
+
+/// The [`CommitmentLayoutVersion::V5`] layout of [`TableCommitment`].
+///
+/// This must not change when [`TableCommitment`] changes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableCommitmentV5<C>
+where
+    C: Commitment,
+{
+    pub(super) column_commitments: ColumnCommitmentsV4<C>,
+    pub(super) range: Range<usize>,
+    pub(super) schema_history: Vec<SchemaChange<C::Scalar>>,
+    pub(super) anchor: Option<ChainAnchor>,
+}
+
+impl<C: Commitment> TryFrom<TableCommitmentV5<C>> for TableCommitment<C> {
+    type Error = CommitmentMigrationError;
+
+    fn try_from(table_commitment: TableCommitmentV5<C>) -> Result<Self, Self::Error> {
+        let ColumnCommitmentsV4 {
+            commitments,
+            column_metadata,
+        } = table_commitment.column_commitments;
+        let column_commitments = upgrade_column_commitments(commitments, column_metadata)?;
+        let upgraded = TableCommitment::try_new(column_commitments, table_commitment.range)?
+            .with_schema_history(table_commitment.schema_history);
+        Ok(match table_commitment.anchor {
+            Some(anchor) => upgraded.with_anchor(anchor),
+            None => upgraded,
+        })
+    }
+}
+
+/// A [`ColumnCommitmentMetadata`] tagged with the version of its serialization layout.
+///
+/// This is the type that should be serialized for long-term storage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionedColumnCommitmentMetadata {
+    /// Metadata in the [`CommitmentLayoutVersion::V1`] layout.
+    V1(ColumnCommitmentMetadataV1),
+    /// Metadata in the [`CommitmentLayoutVersion::V2`] layout.
+    V2(ColumnCommitmentMetadataV2),
+    /// Metadata in the [`CommitmentLayoutVersion::V3`] layout, which is the same as the
+    /// [`CommitmentLayoutVersion::V2`] layout.
+    V3(ColumnCommitmentMetadataV2),
+    /// Metadata in the [`CommitmentLayoutVersion::V4`] layout.
+    V4(ColumnCommitmentMetadataV4),
+    /// Metadata in the [`CommitmentLayoutVersion::V5`] layout, which is the same as the
+    /// [`CommitmentLayoutVersion::V4`] layout.
+    V5(ColumnCommitmentMetadataV4),
+    /// Metadata in the [`CommitmentLayoutVersion::V6`] layout, which is the same as the
+    /// [`CommitmentLayoutVersion::V4`] layout.
+    V6(ColumnCommitmentMetadata),
+}
+
+impl VersionedColumnCommitmentMetadata {
+    /// Returns the layout version of this metadata.
+    pub fn version(&self) -> CommitmentLayoutVersion {
+        match self {
+            VersionedColumnCommitmentMetadata::V1(_) => CommitmentLayoutVersion::V1,
+            VersionedColumnCommitmentMetadata::V2(_) => CommitmentLayoutVersion::V2,
+            VersionedColumnCommitmentMetadata::V3(_) => CommitmentLayoutVersion::V3,
+            VersionedColumnCommitmentMetadata::V4(_) => CommitmentLayoutVersion::V4,
+            VersionedColumnCommitmentMetadata::V5(_) => CommitmentLayoutVersion::V5,
+            VersionedColumnCommitmentMetadata::V6(_) => CommitmentLayoutVersion::V6,
+        }
+    }
+
+    /// Upgrade this metadata to the latest layout and unwrap it.
+    ///
+    /// Metadata in older layouts is validated while it is upgraded.
+    pub fn into_latest(self) -> Result<ColumnCommitmentMetadata, CommitmentMigrationError> {
+        match self {
+            VersionedColumnCommitmentMetadata::V1(metadata) => Ok(metadata.try_into()?),
+            VersionedColumnCommitmentMetadata::V2(metadata)
+            | VersionedColumnCommitmentMetadata::V3(metadata) => Ok(metadata.try_into()?),
+            VersionedColumnCommitmentMetadata::V4(metadata)
+            | VersionedColumnCommitmentMetadata::V5(metadata) => Ok(metadata.try_into()?),
+            VersionedColumnCommitmentMetadata::V6(metadata) => Ok(metadata),
+        }
+    }
+}
+
+impl From<ColumnCommitmentMetadata> for VersionedColumnCommitmentMetadata {
+    fn from(metadata: ColumnCommitmentMetadata) -> Self {
+        VersionedColumnCommitmentMetadata::V6(metadata)
+    }
+}
+
+/// A [`TableCommitment`] tagged with the version of its serialization layout.
+///
+/// This is the type that should be serialized for long-term storage.
+/// Future layouts are added as new variants, so previously stored commitments stay readable
+/// and can be upgraded with [`VersionedTableCommitment::into_latest`] without recommitting.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VersionedTableCommitment<C>
+where
+    C: Commitment,
+{
+    /// A table commitment in the [`CommitmentLayoutVersion::V1`] layout.
+    V1(TableCommitmentV1<C>),
+    /// A table commitment in the [`CommitmentLayoutVersion::V2`] layout.
+    V2(TableCommitmentV2<C>),
+    /// A table commitment in the [`CommitmentLayoutVersion::V3`] layout.
+    V3(TableCommitmentV3<C>),
+    /// A table commitment in the [`CommitmentLayoutVersion::V4`] layout.
+    V4(TableCommitmentV4<C>),
+    /// A table commitment in the [`CommitmentLayoutVersion::V5`] layout.
+    V5(TableCommitmentV5<C>),
+    /// A table commitment in the [`CommitmentLayoutVersion::V6`] layout.
+    V6(TableCommitment<C>),
+}
+
+impl<C: Commitment> VersionedTableCommitment<C> {
+    /// Returns the layout version of this commitment.
+    pub fn version(&self) -> CommitmentLayoutVersion {
+        match self {
+            VersionedTableCommitment::V1(_) => CommitmentLayoutVersion::V1,
+            VersionedTableCommitment::V2(_) => CommitmentLayoutVersion::V2,
+            VersionedTableCommitment::V3(_) => CommitmentLayoutVersion::V3,
+            VersionedTableCommitment::V4(_) => CommitmentLayoutVersion::V4,
+            VersionedTableCommitment::V5(_) => CommitmentLayoutVersion::V5,
+            VersionedTableCommitment::V6(_) => CommitmentLayoutVersion::V6,
+        }
+    }
+
+    /// Upgrade this commitment to the latest layout and unwrap it.
+    ///
+    /// Commitments in older layouts are validated while they are upgraded.
+    pub fn into_latest(self) -> Result<TableCommitment<C>, CommitmentMigrationError> {
+        match self {
+            VersionedTableCommitment::V1(table_commitment) => table_commitment.try_into(),
+            VersionedTableCommitment::V2(table_commitment) => table_commitment.try_into(),
+            VersionedTableCommitment::V3(table_commitment) => table_commitment.try_into(),
+            VersionedTableCommitment::V4(table_commitment) => table_commitment.try_into(),
+            VersionedTableCommitment::V5(table_commitment) => table_commitment.try_into(),
+            VersionedTableCommitment::V6(table_commitment) => Ok(table_commitment),
+        }
+    }
+}
+
+impl<C: Commitment> From<TableCommitment<C>> for VersionedTableCommitment<C> {
+    fn from(table_commitment: TableCommitment<C>) -> Self {
+        VersionedTableCommitment::V6(table_commitment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::commitment::{Bounds, BoundsStrategy, ChainAnchor};
+    use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, RistrettoPoint};
+
+    fn sample_table_commitment() -> TableCommitment<RistrettoPoint> {
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+        )
+        .unwrap();
+        let column_commitments = ColumnCommitments::from_iter([(
+            "a".parse().unwrap(),
+            metadata,
+            RISTRETTO_BASEPOINT_POINT,
+        )]);
+        TableCommitment::try_new(column_commitments, 2..6).unwrap()
+    }
+
+    /// The columns of [`sample_table_commitment`] in the [`CommitmentLayoutVersion::V2`] layout.
+    fn sample_column_commitments_v2() -> ColumnCommitmentsV2<RistrettoPoint> {
+        ColumnCommitmentsV2 {
+            commitments: vec![RISTRETTO_BASEPOINT_POINT],
+            column_metadata: IndexMap::from_iter([(
+                "a".parse().unwrap(),
+                ColumnCommitmentMetadataV2 {
+                    column_type: ColumnType::BigInt,
+                    bounds: ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+                    varchar_normalization: VarCharNormalization::None,
+                },
+            )]),
+        }
+    }
+
+    /// The columns of [`sample_table_commitment`] in the [`CommitmentLayoutVersion::V4`] layout,
+    /// with the column stored under the given name.
+    fn sample_column_commitments_v4(name: &str) -> ColumnCommitmentsV4<RistrettoPoint> {
+        ColumnCommitmentsV4 {
+            commitments: vec![RISTRETTO_BASEPOINT_POINT],
+            column_metadata: IndexMap::from_iter([(
+                name.parse().unwrap(),
+                ColumnCommitmentMetadataV4 {
+                    column_type: ColumnType::BigInt,
+                    bounds: ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+                    varchar_normalization: VarCharNormalization::None,
+                    bounds_strategy: BoundsStrategy::Exact,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn we_can_round_trip_a_versioned_table_commitment() {
+        let table_commitment = sample_table_commitment();
+        let versioned = VersionedTableCommitment::from(table_commitment.clone());
+        assert_eq!(versioned.version(), CommitmentLayoutVersion::LATEST);
+
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, versioned);
+        assert_eq!(deserialized.into_latest().unwrap(), table_commitment);
+
+        let json = serde_json::to_string(&versioned).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, versioned);
+    }
+
+    #[test]
+    fn we_can_round_trip_versioned_column_commitment_metadata() {
+        let metadata =
+            ColumnCommitmentMetadata::try_new(ColumnType::VarChar, ColumnBounds::NoOrder).unwrap();
+        let versioned = VersionedColumnCommitmentMetadata::from(metadata);
+        assert_eq!(versioned.version(), CommitmentLayoutVersion::LATEST);
+
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let deserialized: VersionedColumnCommitmentMetadata = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.into_latest().unwrap(), metadata);
+    }
+
+    #[test]
+    fn we_can_round_trip_the_bounds_strategy_of_column_commitment_metadata() {
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+        )
+        .unwrap()
+        .with_bounds_strategy(BoundsStrategy::Padded);
+        let versioned = VersionedColumnCommitmentMetadata::from(metadata);
+
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let deserialized: VersionedColumnCommitmentMetadata = postcard::from_bytes(&bytes).unwrap();
+        let deserialized = deserialized.into_latest().unwrap();
+        assert_eq!(deserialized.bounds_strategy(), BoundsStrategy::Padded);
+        assert_eq!(deserialized, metadata);
+    }
+
+    #[test]
+    fn we_can_upgrade_v4_column_commitment_metadata_without_widening_its_bounds_again() {
+        let metadata = ColumnCommitmentMetadata::try_new(
+            ColumnType::BigInt,
+            ColumnBounds::BigInt(Bounds::sharp(-5, 5).unwrap()),
+        )
+        .unwrap()
+        .with_bounds_strategy(BoundsStrategy::Padded);
+        let v4 = VersionedColumnCommitmentMetadata::V4(ColumnCommitmentMetadataV4 {
+            column_type: *metadata.column_type(),
+            bounds: *metadata.bounds(),
+            varchar_normalization: metadata.varchar_normalization(),
+            bounds_strategy: metadata.bounds_strategy(),
+        });
+        assert_eq!(v4.version(), CommitmentLayoutVersion::V4);
+
+        let bytes = postcard::to_allocvec(&v4).unwrap();
+        let deserialized: VersionedColumnCommitmentMetadata = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.into_latest().unwrap(), metadata);
+    }
+
+    #[test]
+    fn we_can_upgrade_a_v2_table_commitment() {
+        let table_commitment = sample_table_commitment();
+        let v2 = VersionedTableCommitment::V2(TableCommitmentV2 {
+            column_commitments: sample_column_commitments_v2(),
+            range: table_commitment.range().clone(),
+        });
+        assert_eq!(v2.version(), CommitmentLayoutVersion::V2);
+
+        let bytes = postcard::to_allocvec(&v2).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let upgraded = deserialized.into_latest().unwrap();
+        assert!(upgraded.schema_history().is_empty());
+        assert_eq!(upgraded, table_commitment);
+    }
+
+    #[test]
+    fn we_can_upgrade_a_v3_table_commitment() {
+        let mut table_commitment = sample_table_commitment();
+        table_commitment
+            .try_rename_column(&"a".parse().unwrap(), "b".parse().unwrap())
+            .unwrap();
+        let v3 = VersionedTableCommitment::V3(TableCommitmentV3 {
+            column_commitments: sample_column_commitments_v2(),
+            range: table_commitment.range().clone(),
+            schema_history: table_commitment.schema_history().to_vec(),
+        });
+        assert_eq!(v3.version(), CommitmentLayoutVersion::V3);
+
+        let bytes = postcard::to_allocvec(&v3).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let upgraded = deserialized.into_latest().unwrap();
+        assert_eq!(upgraded.schema_history(), table_commitment.schema_history());
+        assert_eq!(
+            upgraded.column_commitments().column_metadata()[0].bounds_strategy(),
+            BoundsStrategy::Exact
+        );
+    }
+
+    #[test]
+    fn we_can_round_trip_the_schema_history_of_a_table_commitment() {
+        let mut table_commitment = sample_table_commitment();
+        table_commitment
+            .try_rename_column(&"a".parse().unwrap(), "b".parse().unwrap())
+            .unwrap();
+        table_commitment
+            .try_drop_column(&"b".parse().unwrap())
+            .unwrap();
+        let versioned = VersionedTableCommitment::from(table_commitment.clone());
+
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let deserialized = deserialized.into_latest().unwrap();
+        assert_eq!(deserialized.schema_history().len(), 2);
+        assert_eq!(deserialized, table_commitment);
+    }
+
+    #[test]
+    fn we_can_upgrade_a_v4_table_commitment() {
+        let mut table_commitment = sample_table_commitment();
+        table_commitment
+            .try_rename_column(&"a".parse().unwrap(), "b".parse().unwrap())
+            .unwrap();
+        let v4 = VersionedTableCommitment::V4(TableCommitmentV4 {
+            column_commitments: sample_column_commitments_v4("b"),
+            range: table_commitment.range().clone(),
+            schema_history: table_commitment.schema_history().to_vec(),
+        });
+        assert_eq!(v4.version(), CommitmentLayoutVersion::V4);
+
+        let bytes = postcard::to_allocvec(&v4).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let upgraded = deserialized.into_latest().unwrap();
+        assert_eq!(upgraded.anchor(), None);
+        assert_eq!(upgraded, table_commitment);
+    }
+
+    #[test]
+    fn we_can_upgrade_a_v5_table_commitment() {
+        let anchor = ChainAnchor::new([3; 32], 42, 1_700_000_000);
+        let table_commitment = sample_table_commitment().with_anchor(anchor);
+        let v5 = VersionedTableCommitment::V5(TableCommitmentV5 {
+            column_commitments: sample_column_commitments_v4("a"),
+            range: table_commitment.range().clone(),
+            schema_history: table_commitment.schema_history().to_vec(),
+            anchor: Some(anchor),
+        });
+        assert_eq!(v5.version(), CommitmentLayoutVersion::V5);
+
+        let bytes = postcard::to_allocvec(&v5).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let upgraded = deserialized.into_latest().unwrap();
+        assert!(upgraded.provenance().is_empty());
+        assert_eq!(upgraded, table_commitment);
+    }
+
+    #[test]
+    fn we_can_round_trip_the_anchor_of_a_table_commitment() {
+        let anchor = ChainAnchor::new([3; 32], 42, 1_700_000_000);
+        let table_commitment = sample_table_commitment().with_anchor(anchor);
+        let versioned = VersionedTableCommitment::from(table_commitment.clone());
+
+        let bytes = postcard::to_allocvec(&versioned).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let deserialized = deserialized.into_latest().unwrap();
+        assert_eq!(deserialized.anchor(), Some(&anchor));
+        assert_eq!(deserialized, table_commitment);
+    }
+
+    #[test]
+    fn we_can_upgrade_a_v1_table_commitment() {
+        let metadata = ColumnCommitmentMetadataV1 {
+            column_type: ColumnType::VarChar,
+            bounds: ColumnBounds::NoOrder,
+        };
+        let v1 = VersionedTableCommitment::V1(TableCommitmentV1 {
+            column_commitments: ColumnCommitmentsV1 {
+                commitments: vec![RISTRETTO_BASEPOINT_POINT],
+                column_metadata: IndexMap::from_iter([("a".parse().unwrap(), metadata)]),
+            },
+            range: 0..3,
+        });
+        assert_eq!(v1.version(), CommitmentLayoutVersion::V1);
+
+        let bytes = postcard::to_allocvec(&v1).unwrap();
+        let deserialized: VersionedTableCommitment<RistrettoPoint> =
+            postcard::from_bytes(&bytes).unwrap();
+        let upgraded = deserialized.into_latest().unwrap();
+        let upgraded_metadata = upgraded.column_commitments().column_metadata()[0];
+        assert_eq!(upgraded_metadata.column_type(), &ColumnType::VarChar);
+        assert_eq!(
+            upgraded_metadata.varchar_normalization(),
+            VarCharNormalization::None
+        );
+        assert_eq!(upgraded.range(), &(0..3));
+    }
+}
@@ -0,0 +1,138 @@
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+use std::{
+    cell::Cell,
+    sync::{PoisonError, RwLock},
+};
+
+/// How the library splits work on slices between threads.
+///
+/// Work on a slice is only split between threads if the slice has at least `parallel_threshold`
+/// elements, and then every task processes at least `min_chunk_len` elements. Tiny inputs are
+/// usually faster with a higher threshold, since splitting them costs more than it saves, while
+/// huge inputs are usually faster with longer chunks.
+///
+/// The global config applies unless another config is installed for a computation with
+/// [`ComputeConfig::install`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputeConfig {
+    num_threads: Option<usize>,
+    min_chunk_len: usize,
+    parallel_threshold: usize,
+}
+
+static GLOBAL_COMPUTE_CONFIG: RwLock<ComputeConfig> = RwLock::new(ComputeConfig::DEFAULT);
+
+thread_local! {
+    static INSTALLED_COMPUTE_CONFIG: Cell<Option<ComputeConfig>> = const { Cell::new(None) };
+}
+
+impl Default for ComputeConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ComputeConfig {
+    const DEFAULT: Self = Self {
+        num_threads: None,
+        min_chunk_len: 1 << 8,
+        parallel_threshold: 0,
+    };
+
+    /// Create the default `ComputeConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the given number of threads instead of rayon's default of one per CPU.
+    pub fn with_num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = Some(num_threads);
+        self
+    }
+
+    /// Have every parallel task process at least the given number of elements.
+    pub fn with_min_chunk_len(mut self, min_chunk_len: usize) -> Self {
+        self.min_chunk_len = min_chunk_len.max(1);
+        self
+    }
+
+    /// Only split work on slices with at least the given number of elements between threads.
+    pub fn with_parallel_threshold(mut self, parallel_threshold: usize) -> Self {
+        self.parallel_threshold = parallel_threshold;
+        self
+    }
+
+    /// Returns the number of threads, if it is not rayon's default.
+    pub fn num_threads(&self) -> Option<usize> {
+        self.num_threads
+    }
+
+    /// Returns the minimum number of elements that every parallel task processes.
+    pub fn min_chunk_len(&self) -> usize {
+        self.min_chunk_len
+    }
+
+    /// Returns the minimum length of a slice whose work is split between threads.
+    pub fn parallel_threshold(&self) -> usize {
+        self.parallel_threshold
+    }
+
+    /// Returns the global `ComputeConfig`.
+    pub fn global() -> Self {
+        *GLOBAL_COMPUTE_CONFIG
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Make this the global `ComputeConfig`.
+    ///
+    /// The number of threads of rayon's global thread pool can only be set before the pool is
+    /// first used, so this fails if the config sets the number of threads after that.
+    pub fn set_global(self) -> Result<(), ThreadPoolBuildError> {
+        if let Some(num_threads) = self.num_threads {
+            ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build_global()?;
+        }
+        *GLOBAL_COMPUTE_CONFIG
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = self;
+        Ok(())
+    }
+
+    /// Returns the `ComputeConfig` that applies on the current thread.
+    pub fn current() -> Self {
+        INSTALLED_COMPUTE_CONFIG
+            .with(Cell::get)
+            .unwrap_or_else(Self::global)
+    }
+
+    /// Run `op` in a new thread pool that uses this config instead of the global one.
+    ///
+    /// Creating the thread pool spawns its threads, so this is meant for expensive computations
+    /// such as proving a query rather than for individual slice operations.
+    pub fn install<R: Send>(
+        self,
+        op: impl FnOnce() -> R + Send,
+    ) -> Result<R, ThreadPoolBuildError> {
+        let mut builder = ThreadPoolBuilder::new().start_handler(move |_| {
+            INSTALLED_COMPUTE_CONFIG.with(|config| config.set(Some(self)));
+        });
+        if let Some(num_threads) = self.num_threads {
+            builder = builder.num_threads(num_threads);
+        }
+        Ok(builder.build()?.install(op))
+    }
+
+    /// Returns the minimum number of elements that every parallel task should process when
+    /// working on a slice of the given length.
+    ///
+    /// This is suitable for [`rayon::iter::IndexedParallelIterator::with_min_len`].
+    pub(crate) fn min_len(&self, len: usize) -> usize {
+        if len < self.parallel_threshold {
+            usize::MAX
+        } else {
+            self.min_chunk_len
+        }
+    }
+}
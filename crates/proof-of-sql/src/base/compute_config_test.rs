@@ -0,0 +1,51 @@
+use super::ComputeConfig;
+use crate::base::slice_ops;
+
+#[test]
+fn we_can_configure_how_work_is_split_between_threads() {
+    let config = ComputeConfig::new();
+    assert_eq!(config, ComputeConfig::default());
+    assert_eq!(config.num_threads(), None);
+    assert_eq!(config.min_chunk_len(), 1 << 8);
+    assert_eq!(config.parallel_threshold(), 0);
+    assert_eq!(config.min_len(0), 1 << 8);
+
+    let config = ComputeConfig::new()
+        .with_num_threads(2)
+        .with_min_chunk_len(0)
+        .with_parallel_threshold(1000);
+    assert_eq!(config.num_threads(), Some(2));
+    assert_eq!(config.min_chunk_len(), 1);
+    assert_eq!(config.min_len(999), usize::MAX);
+    assert_eq!(config.min_len(1000), 1);
+}
+
+#[test]
+fn we_can_install_a_compute_config_for_a_computation() {
+    let config = ComputeConfig::new()
+        .with_num_threads(2)
+        .with_min_chunk_len(3);
+    let (installed_config, num_threads) = config
+        .install(|| (ComputeConfig::current(), rayon::current_num_threads()))
+        .unwrap();
+    assert_eq!(installed_config, config);
+    assert_eq!(num_threads, 2);
+    assert_eq!(ComputeConfig::current(), ComputeConfig::global());
+}
+
+#[test]
+fn slice_operations_give_the_same_results_with_any_compute_config() {
+    let a: Vec<i64> = (0..1000).collect();
+    let b: Vec<i64> = (0..1000).map(|x| 3 * x - 7).collect();
+    let expected = slice_ops::inner_product(&a, &b);
+    for config in [
+        ComputeConfig::new().with_min_chunk_len(1),
+        ComputeConfig::new().with_parallel_threshold(usize::MAX),
+        ComputeConfig::new()
+            .with_num_threads(3)
+            .with_min_chunk_len(7),
+    ] {
+        let result = config.install(|| slice_ops::inner_product(&a, &b)).unwrap();
+        assert_eq!(result, expected);
+    }
+}
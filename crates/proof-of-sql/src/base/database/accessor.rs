@@ -1,6 +1,6 @@
 use crate::base::{
     commitment::Commitment,
-    database::{Column, ColumnRef, ColumnType, TableRef},
+    database::{AccessorResult, Column, ColumnRef, ColumnType, TableRef, VarCharNormalization},
     scalar::Scalar,
 };
 use proof_of_sql_parser::Identifier;
@@ -11,6 +11,8 @@ use proof_of_sql_parser::Identifier;
 ///
 /// Note: we assume that the query has already been validated so that we
 /// will only be accessing information about tables that exist in the database.
+/// The `try_` methods return an [`AccessorError`](super::AccessorError) instead for tables
+/// that do not exist.
 pub trait MetadataAccessor {
     /// Return the data span's length in the table (not the full table length)
     fn get_length(&self, table_ref: TableRef) -> usize;
@@ -20,6 +22,22 @@ pub trait MetadataAccessor {
     /// If the data span has its first row starting at the ith table row,
     /// this `get_offset` should then return `i`.
     fn get_offset(&self, table_ref: TableRef) -> usize;
+
+    /// Return the data span's length in the table, or an error if the table does not exist
+    ///
+    /// Accessors that can look up tables which do not exist should override this, since the
+    /// default falls back on `get_length`.
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.get_length(table_ref))
+    }
+
+    /// Return the data span's offset in the table, or an error if the table does not exist
+    ///
+    /// Accessors that can look up tables which do not exist should override this, since the
+    /// default falls back on `get_offset`.
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.get_offset(table_ref))
+    }
 }
 
 /// Access commitments of database columns.
@@ -81,9 +99,22 @@ pub trait CommitmentAccessor<C: Commitment>: MetadataAccessor {
 ///
 /// Note: we assume that the query has already been validated so that we
 /// will only be accessing information about columns that exist in the database.
+/// [`DataAccessor::try_get_column`] returns an [`AccessorError`](super::AccessorError) instead
+/// for columns that do not exist.
 pub trait DataAccessor<S: Scalar>: MetadataAccessor {
     /// Return the data span in the table (not the full-table data)
     fn get_column(&self, column: ColumnRef) -> Column<S>;
+
+    /// Return the data span in the table, or an error if the table or column does not exist or
+    /// its data cannot be read
+    ///
+    /// The prover checks the columns of a query with this before proving it, so that a query
+    /// that references a missing column fails instead of panicking. Accessors that can look up
+    /// columns which do not exist should override this, since the default falls back on
+    /// `get_column`.
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<S>> {
+        Ok(self.get_column(column))
+    }
 }
 
 /// Access tables and their schemas in a database.
@@ -110,4 +141,19 @@ pub trait SchemaAccessor {
     /// Precondition 1: the table must exist and be tamperproof.
     /// Precondition 2: `table_name` must be lowercase.
     fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)>;
+
+    /// Lookup the normalization applied to the strings of a VarChar column when they were committed
+    ///
+    /// String literals compared against the column are normalized the same way.
+    /// Accessors for tables that were committed without normalization can rely on the default.
+    ///
+    /// Precondition 1: the table must exist and be tamperproof.
+    /// Precondition 2: `table_ref` and `column_id` must always be lowercase.
+    fn lookup_varchar_normalization(
+        &self,
+        _table_ref: TableRef,
+        _column_id: Identifier,
+    ) -> VarCharNormalization {
+        VarCharNormalization::None
+    }
 }
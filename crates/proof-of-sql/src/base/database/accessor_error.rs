@@ -0,0 +1,56 @@
+use super::{ColumnRef, TableRef};
+use proof_of_sql_parser::Identifier;
+use thiserror::Error;
+
+/// Errors from looking up tables and columns in an accessor.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccessorError {
+    /// The table does not exist in the database
+    #[error("table {table_ref} does not exist")]
+    TableNotFound {
+        /// The table that was looked up
+        table_ref: TableRef,
+    },
+
+    /// The column does not exist in the table
+    #[error("column {column_id} does not exist in table {table_ref}")]
+    ColumnNotFound {
+        /// The table that was looked up
+        table_ref: TableRef,
+        /// The column that was looked up
+        column_id: Identifier,
+    },
+
+    /// The column exists but its data could not be read, e.g. because it could not be decrypted
+    #[error("column {column_id} of table {table_ref} is unavailable: {reason}")]
+    ColumnUnavailable {
+        /// The table that was looked up
+        table_ref: TableRef,
+        /// The column that was looked up
+        column_id: Identifier,
+        /// Why the data of the column could not be read
+        reason: String,
+    },
+}
+
+impl AccessorError {
+    /// The error for a column that does not exist in its table
+    pub fn column_not_found(column: ColumnRef) -> Self {
+        Self::ColumnNotFound {
+            table_ref: column.table_ref(),
+            column_id: column.column_id(),
+        }
+    }
+
+    /// The error for a column whose data could not be read
+    pub fn column_unavailable(column: ColumnRef, reason: impl ToString) -> Self {
+        Self::ColumnUnavailable {
+            table_ref: column.table_ref(),
+            column_id: column.column_id(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Result type for accessor lookups
+pub type AccessorResult<T> = std::result::Result<T, AccessorError>;
@@ -0,0 +1,201 @@
+use super::{
+    AccessorError, AccessorResult, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, TableRef,
+};
+use crate::base::{
+    commitment::{ColumnCommitmentsMismatch, Commitment, QueryCommitments, TableCommitment},
+    scalar::Scalar,
+};
+use bumpalo::Bump;
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+
+/// A table that is only ever appended to, along with the commitment to its rows.
+///
+/// The offset of a table has to be the same when committing to it, when appending to the
+/// commitment, and when proving queries against it. Rather than leaving this to the caller, this
+/// keeps the rows and the commitment together, and takes the offset from the range of the
+/// commitment. So the commitment returned by [`AppendOnlyTable::query_commitments`] is always for
+/// the rows that this accessor proves against.
+///
+/// This implements the accessor traits for the single table it holds, so it can be passed to the
+/// prover as is.
+pub struct AppendOnlyTable<C: Commitment> {
+    table_ref: TableRef,
+    table: OwnedTable<C::Scalar>,
+    commitment: TableCommitment<C>,
+    alloc: Bump,
+}
+
+impl<C: Commitment> AppendOnlyTable<C> {
+    /// Create a table from its first rows, which start at the given row offset.
+    pub fn new(
+        table_ref: TableRef,
+        table: OwnedTable<C::Scalar>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Self {
+        let commitment = TableCommitment::from_owned_table_with_offset(&table, offset, setup);
+        Self {
+            table_ref,
+            table,
+            commitment,
+            alloc: Bump::new(),
+        }
+    }
+
+    /// Append rows to the table and to its commitment.
+    ///
+    /// The rows have to have the same columns, in the same order, as the table. Otherwise, neither
+    /// the table nor the commitment are changed.
+    pub fn try_append(
+        &mut self,
+        rows: &OwnedTable<C::Scalar>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), ColumnCommitmentsMismatch> {
+        self.commitment.append_owned_table(rows, setup)?;
+        extend_table(&mut self.table, rows);
+        Ok(())
+    }
+
+    /// Returns the table that this holds.
+    pub fn table_ref(&self) -> TableRef {
+        self.table_ref
+    }
+
+    /// Returns the rows of the table.
+    pub fn table(&self) -> &OwnedTable<C::Scalar> {
+        &self.table
+    }
+
+    /// Returns the commitment to the rows of the table.
+    pub fn commitment(&self) -> &TableCommitment<C> {
+        &self.commitment
+    }
+
+    /// Returns the commitments that a query against the table is verified with.
+    pub fn query_commitments(&self) -> QueryCommitments<C> {
+        QueryCommitments::from_iter([(self.table_ref, self.commitment.clone())])
+    }
+
+    /// Returns the row offset of the table.
+    pub fn offset(&self) -> usize {
+        self.commitment.range().start
+    }
+
+    /// Returns the number of rows of the table.
+    pub fn num_rows(&self) -> usize {
+        self.commitment.num_rows()
+    }
+
+    fn try_get_column_data(&self, column: ColumnRef) -> AccessorResult<&OwnedColumn<C::Scalar>> {
+        self.check_table_ref(column.table_ref())?;
+        self.table
+            .inner_table()
+            .get(&column.column_id())
+            .ok_or_else(|| AccessorError::column_not_found(column))
+    }
+
+    fn check_table_ref(&self, table_ref: TableRef) -> AccessorResult<()> {
+        if table_ref == self.table_ref {
+            Ok(())
+        } else {
+            Err(AccessorError::TableNotFound { table_ref })
+        }
+    }
+}
+
+/// Appends rows to a table.
+///
+/// The rows have to have the same columns, in the same order, as the table, which is checked by
+/// appending them to the commitment first.
+pub(super) fn extend_table<S: Scalar>(table: &mut OwnedTable<S>, rows: &OwnedTable<S>) {
+    let empty_table = OwnedTable::try_new(IndexMap::new()).expect("a table can have no columns");
+    let mut columns = core::mem::replace(table, empty_table).into_inner();
+    for (column, new_rows) in columns.values_mut().zip(rows.inner_table().values()) {
+        extend_column(column, new_rows);
+    }
+    *table = OwnedTable::try_new(columns)
+        .expect("every column has been extended by the same number of rows");
+}
+
+/// Appends the rows of a column of the same type to a column.
+fn extend_column<S: Scalar>(column: &mut OwnedColumn<S>, rows: &OwnedColumn<S>) {
+    match (column, rows) {
+        (OwnedColumn::Boolean(col), OwnedColumn::Boolean(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::SmallInt(col), OwnedColumn::SmallInt(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::Int(col), OwnedColumn::Int(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::BigInt(col), OwnedColumn::BigInt(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::VarChar(col), OwnedColumn::VarChar(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::Int128(col), OwnedColumn::Int128(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::Decimal75(_, _, col), OwnedColumn::Decimal75(_, _, rows)) => {
+            col.extend_from_slice(rows);
+        }
+        (OwnedColumn::Scalar(col), OwnedColumn::Scalar(rows)) => col.extend_from_slice(rows),
+        (OwnedColumn::TimestampTZ(_, _, col), OwnedColumn::TimestampTZ(_, _, rows)) => {
+            col.extend_from_slice(rows);
+        }
+        _ => panic!("the commitment has already checked that the column types match"),
+    }
+}
+
+impl<C: Commitment> MetadataAccessor for AppendOnlyTable<C> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.try_get_length(table_ref).unwrap()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.try_get_offset(table_ref).unwrap()
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.check_table_ref(table_ref)?;
+        Ok(self.num_rows())
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.check_table_ref(table_ref)?;
+        Ok(self.offset())
+    }
+}
+
+impl<C: Commitment> CommitmentAccessor<C> for AppendOnlyTable<C> {
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        self.try_get_column_data(column).unwrap();
+        self.commitment
+            .column_commitments()
+            .get_commitment(&column.column_id())
+            .expect("the commitment has a column for every column of the table")
+    }
+}
+
+impl<C: Commitment> DataAccessor<C::Scalar> for AppendOnlyTable<C> {
+    fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
+        self.try_get_column(column).unwrap()
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<C::Scalar>> {
+        Ok(Column::from_owned_column(
+            self.try_get_column_data(column)?,
+            &self.alloc,
+        ))
+    }
+}
+
+impl<C: Commitment> SchemaAccessor for AppendOnlyTable<C> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.check_table_ref(table_ref).ok()?;
+        Some(self.table.inner_table().get(&column_id)?.column_type())
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        if self.check_table_ref(table_ref).is_err() {
+            return Vec::new();
+        }
+        self.table
+            .inner_table()
+            .iter()
+            .map(|(&identifier, column)| (identifier, column.column_type()))
+            .collect()
+    }
+}
@@ -0,0 +1,136 @@
+use super::{
+    owned_table_utility::*, AccessorError, AppendOnlyTable, Column, ColumnRef, ColumnType,
+    CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable, SchemaAccessor, TableRef,
+};
+use crate::{
+    base::{
+        commitment::{ColumnCommitmentsMismatch, InnerProductProof, TableCommitment},
+        scalar::Curve25519Scalar,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn table_ref() -> TableRef {
+    "sxt.t".parse().unwrap()
+}
+
+fn rows(a: &[i64], b: &[&str]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([bigint("a", a.to_vec()), varchar("b", b.to_vec())])
+}
+
+#[test]
+fn we_can_append_rows_to_a_table_and_its_commitment() {
+    let mut table =
+        AppendOnlyTable::<RistrettoPoint>::new(table_ref(), rows(&[1, 2], &["x", "y"]), 3, &());
+    table.try_append(&rows(&[3], &["z"]), &()).unwrap();
+    table.try_append(&rows(&[4, 5], &["y", "x"]), &()).unwrap();
+
+    let expected_rows = rows(&[1, 2, 3, 4, 5], &["x", "y", "z", "y", "x"]);
+    assert_eq!(table.table(), &expected_rows);
+    assert_eq!(
+        table.commitment(),
+        &TableCommitment::from_owned_table_with_offset(&expected_rows, 3, &())
+    );
+    assert_eq!(table.offset(), 3);
+    assert_eq!(table.num_rows(), 5);
+    assert_eq!(table.get_offset(table_ref()), 3);
+    assert_eq!(table.get_length(table_ref()), 5);
+    assert_eq!(
+        table.query_commitments()[&table_ref()],
+        table.commitment().clone()
+    );
+}
+
+#[test]
+fn we_cannot_append_rows_with_other_columns() {
+    let mut table =
+        AppendOnlyTable::<RistrettoPoint>::new(table_ref(), rows(&[1, 2], &["x", "y"]), 0, &());
+    let commitment = table.commitment().clone();
+    assert!(matches!(
+        table.try_append(&owned_table([bigint("a", [3])]), &()),
+        Err(ColumnCommitmentsMismatch::NumColumns)
+    ));
+    assert!(matches!(
+        table.try_append(&owned_table([varchar("b", ["z"]), bigint("a", [3])]), &()),
+        Err(ColumnCommitmentsMismatch::Identifier(..))
+    ));
+    assert!(matches!(
+        table.try_append(&owned_table([int128("a", [3]), varchar("b", ["z"])]), &()),
+        Err(ColumnCommitmentsMismatch::ColumnCommitmentMetadata(..))
+    ));
+    assert_eq!(table.table(), &rows(&[1, 2], &["x", "y"]));
+    assert_eq!(table.commitment(), &commitment);
+}
+
+#[test]
+fn we_can_access_the_columns_of_the_table() {
+    let table =
+        AppendOnlyTable::<RistrettoPoint>::new(table_ref(), rows(&[1, 2], &["x", "y"]), 0, &());
+    let a = ColumnRef::new(table_ref(), ident("a"), ColumnType::BigInt);
+    match table.get_column(a) {
+        Column::BigInt(col) => assert_eq!(col.to_vec(), vec![1, 2]),
+        _ => panic!("Invalid column type"),
+    };
+    assert_eq!(
+        table.get_commitment(a),
+        table
+            .commitment()
+            .column_commitments()
+            .get_commitment(&ident("a"))
+            .unwrap()
+    );
+    assert_eq!(
+        table.lookup_schema(table_ref()),
+        vec![
+            (ident("a"), ColumnType::BigInt),
+            (ident("b"), ColumnType::VarChar)
+        ]
+    );
+    assert_eq!(
+        table.lookup_column(table_ref(), ident("b")),
+        Some(ColumnType::VarChar)
+    );
+
+    let other_table_ref: TableRef = "sxt.u".parse().unwrap();
+    let c = ColumnRef::new(table_ref(), ident("c"), ColumnType::BigInt);
+    assert_eq!(
+        table.try_get_column(c).unwrap_err(),
+        AccessorError::column_not_found(c)
+    );
+    assert_eq!(
+        table.try_get_length(other_table_ref),
+        Err(AccessorError::TableNotFound {
+            table_ref: other_table_ref
+        })
+    );
+    assert!(table.lookup_schema(other_table_ref).is_empty());
+    assert_eq!(table.lookup_column(other_table_ref, ident("a")), None);
+}
+
+#[test]
+fn we_can_prove_and_verify_queries_against_an_appended_table() {
+    let mut table =
+        AppendOnlyTable::<RistrettoPoint>::new(table_ref(), rows(&[1, 2], &["x", "y"]), 7, &());
+    table
+        .try_append(&rows(&[3, 4, 5], &["z", "y", "x"]), &())
+        .unwrap();
+    let query = QueryExpr::try_new(
+        "SELECT a FROM t WHERE b = 'y'".parse().unwrap(),
+        ident("sxt"),
+        &table,
+    )
+    .unwrap();
+    let proof = VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &table, &());
+    let data = proof
+        .verify(query.proof_expr(), &table.query_commitments(), &())
+        .unwrap();
+    let result: OwnedTable<Curve25519Scalar> = query
+        .result()
+        .transform_results(data.table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(result, owned_table([bigint("a", [2, 4])]));
+}
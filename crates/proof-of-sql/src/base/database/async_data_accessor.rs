@@ -0,0 +1,278 @@
+use super::{
+    AccessorError, AccessorResult, Column, ColumnRef, ColumnType, DataAccessor, MetadataAccessor,
+    OwnedColumn, TableRef,
+};
+use crate::base::{
+    commitment::{Commitment, TableCommitment, VecCommitmentExt},
+    scalar::Scalar,
+};
+use bumpalo::Bump;
+use core::{future::Future, ops::Range};
+use indexmap::IndexMap;
+use thiserror::Error;
+
+/// Errors that can occur when fetching columns from an [`AsyncDataAccessor`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AsyncDataAccessorError {
+    /// The underlying storage failed to return the requested rows
+    #[error("failed to fetch rows {rows:?} of column {column:?}: {reason}")]
+    FetchFailed {
+        /// The column that was being fetched
+        column: ColumnRef,
+        /// The rows that were being fetched
+        rows: Range<usize>,
+        /// A description of the failure
+        reason: String,
+    },
+    /// The storage returned a chunk with the wrong number of rows
+    #[error("expected {expected} rows in a chunk of column {column:?} but received {actual}")]
+    ChunkLengthMismatch {
+        /// The column that was being fetched
+        column: ColumnRef,
+        /// The number of rows that were requested
+        expected: usize,
+        /// The number of rows that were returned
+        actual: usize,
+    },
+    /// The storage returned a chunk whose type is not the column's type
+    #[error("expected column {column:?} to have type {expected} but received {actual}")]
+    ChunkTypeMismatch {
+        /// The column that was being fetched
+        column: ColumnRef,
+        /// The type of the column
+        expected: ColumnType,
+        /// The type of the returned chunk
+        actual: ColumnType,
+    },
+    /// There is no stored commitment to check the fetched column against
+    #[error("no stored commitment found for column {0:?}")]
+    MissingCommitment(ColumnRef),
+    /// The stored commitment does not cover the same rows as the data span
+    #[error("the stored commitment covers rows {committed:?} but the data span is rows {span:?}")]
+    RangeMismatch {
+        /// The rows covered by the stored commitment
+        committed: Range<usize>,
+        /// The rows of the data span
+        span: Range<usize>,
+    },
+    /// The fetched data does not match the stored commitment
+    #[error("fetched data for column {0:?} does not match its stored commitment")]
+    CommitmentMismatch(ColumnRef),
+}
+
+/// Access database columns whose data must be fetched asynchronously, e.g. over HTTP or from S3.
+///
+/// Proving is synchronous, so the columns needed by a query are first fetched into a
+/// [`FetchedDataAccessor`], which then serves as the prover's [`DataAccessor`].
+///
+/// Note: we assume that the query has already been validated so that we
+/// will only be accessing information about columns that exist in the database.
+pub trait AsyncDataAccessor<S: Scalar>: MetadataAccessor {
+    /// Fetch the given rows of a column.
+    ///
+    /// `rows` are indexes into the full table rather than into the data span,
+    /// so that they line up with the rows of a [`TableCommitment`].
+    fn fetch_column_chunk(
+        &self,
+        column: ColumnRef,
+        rows: Range<usize>,
+    ) -> impl Future<Output = Result<OwnedColumn<S>, AsyncDataAccessorError>> + Send;
+}
+
+/// An in-memory [`DataAccessor`] holding columns fetched from an [`AsyncDataAccessor`].
+pub struct FetchedDataAccessor<S: Scalar> {
+    spans: IndexMap<TableRef, Range<usize>>,
+    columns: IndexMap<ColumnRef, OwnedColumn<S>>,
+    alloc: Bump,
+}
+
+impl<S: Scalar> FetchedDataAccessor<S> {
+    /// Fetch the data spans of `columns` in chunks of at most `chunk_size` rows.
+    pub async fn fetch(
+        accessor: &impl AsyncDataAccessor<S>,
+        columns: impl IntoIterator<Item = ColumnRef>,
+        chunk_size: usize,
+    ) -> Result<Self, AsyncDataAccessorError> {
+        let mut fetched = Self::new_empty();
+        for column in columns {
+            let span = fetched.add_span(accessor, column.table_ref());
+            let mut data = empty_column(*column.column_type());
+            for rows in chunk_ranges(span, chunk_size) {
+                let chunk = fetch_checked_chunk(accessor, column, rows).await?;
+                append_chunk(&mut data, chunk);
+            }
+            fetched.columns.insert(column, data);
+        }
+        Ok(fetched)
+    }
+
+    /// Fetch the data spans of `columns` in chunks of at most `chunk_size` rows,
+    /// checking the fetched data against the stored table commitments.
+    ///
+    /// The commitment of every fetched chunk is recomputed, and the chunk commitments of
+    /// each column must add up to the column's commitment in `table_commitments`.
+    /// This defends the prover against corrupted storage, since a proof over corrupted data
+    /// would otherwise only be rejected by the verifier.
+    pub async fn fetch_with_integrity_check<C: Commitment<Scalar = S>>(
+        accessor: &impl AsyncDataAccessor<S>,
+        columns: impl IntoIterator<Item = ColumnRef>,
+        chunk_size: usize,
+        table_commitments: &IndexMap<TableRef, TableCommitment<C>>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<Self, AsyncDataAccessorError> {
+        let mut fetched = Self::new_empty();
+        for column in columns {
+            let span = fetched.add_span(accessor, column.table_ref());
+            let table_commitment = table_commitments
+                .get(&column.table_ref())
+                .ok_or(AsyncDataAccessorError::MissingCommitment(column))?;
+            if *table_commitment.range() != span {
+                return Err(AsyncDataAccessorError::RangeMismatch {
+                    committed: table_commitment.range().clone(),
+                    span,
+                });
+            }
+            let stored_commitment = table_commitment
+                .column_commitments()
+                .get_commitment(&column.column_id())
+                .ok_or(AsyncDataAccessorError::MissingCommitment(column))?;
+
+            let mut data = empty_column(*column.column_type());
+            let mut recomputed_commitment = C::default();
+            for rows in chunk_ranges(span, chunk_size) {
+                let chunk_offset = rows.start;
+                let chunk = fetch_checked_chunk(accessor, column, rows).await?;
+                recomputed_commitment +=
+                    Vec::<C>::from_columns_with_offset([&chunk], chunk_offset, setup)[0];
+                append_chunk(&mut data, chunk);
+            }
+            if recomputed_commitment != stored_commitment {
+                return Err(AsyncDataAccessorError::CommitmentMismatch(column));
+            }
+            fetched.columns.insert(column, data);
+        }
+        Ok(fetched)
+    }
+
+    fn new_empty() -> Self {
+        Self {
+            spans: IndexMap::new(),
+            columns: IndexMap::new(),
+            alloc: Bump::new(),
+        }
+    }
+
+    fn try_get_span(&self, table_ref: TableRef) -> AccessorResult<&Range<usize>> {
+        self.spans
+            .get(&table_ref)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+
+    /// Record the data span of `table_ref` and return it.
+    fn add_span(&mut self, accessor: &impl MetadataAccessor, table_ref: TableRef) -> Range<usize> {
+        let offset = accessor.get_offset(table_ref);
+        let span = offset..offset + accessor.get_length(table_ref);
+        self.spans.insert(table_ref, span.clone());
+        span
+    }
+}
+
+impl<S: Scalar> MetadataAccessor for FetchedDataAccessor<S> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.spans.get(&table_ref).unwrap().len()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.spans.get(&table_ref).unwrap().start
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_span(table_ref)?.len())
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_span(table_ref)?.start)
+    }
+}
+
+impl<S: Scalar> DataAccessor<S> for FetchedDataAccessor<S> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        Column::from_owned_column(self.columns.get(&column).unwrap(), &self.alloc)
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<S>> {
+        self.try_get_span(column.table_ref())?;
+        let owned_column = self
+            .columns
+            .get(&column)
+            .ok_or_else(|| AccessorError::column_not_found(column))?;
+        Ok(Column::from_owned_column(owned_column, &self.alloc))
+    }
+}
+
+fn chunk_ranges(span: Range<usize>, chunk_size: usize) -> impl Iterator<Item = Range<usize>> {
+    assert!(chunk_size > 0, "chunk size must be positive");
+    span.clone()
+        .step_by(chunk_size)
+        .map(move |start| start..(start + chunk_size).min(span.end))
+}
+
+async fn fetch_checked_chunk<S: Scalar>(
+    accessor: &impl AsyncDataAccessor<S>,
+    column: ColumnRef,
+    rows: Range<usize>,
+) -> Result<OwnedColumn<S>, AsyncDataAccessorError> {
+    let expected = rows.len();
+    let chunk = accessor.fetch_column_chunk(column, rows).await?;
+    if chunk.column_type() != *column.column_type() {
+        return Err(AsyncDataAccessorError::ChunkTypeMismatch {
+            column,
+            expected: *column.column_type(),
+            actual: chunk.column_type(),
+        });
+    }
+    if chunk.len() != expected {
+        return Err(AsyncDataAccessorError::ChunkLengthMismatch {
+            column,
+            expected,
+            actual: chunk.len(),
+        });
+    }
+    Ok(chunk)
+}
+
+fn empty_column<S: Scalar>(column_type: ColumnType) -> OwnedColumn<S> {
+    match column_type {
+        ColumnType::Boolean => OwnedColumn::Boolean(Vec::new()),
+        ColumnType::SmallInt => OwnedColumn::SmallInt(Vec::new()),
+        ColumnType::Int => OwnedColumn::Int(Vec::new()),
+        ColumnType::BigInt => OwnedColumn::BigInt(Vec::new()),
+        ColumnType::Int128 => OwnedColumn::Int128(Vec::new()),
+        ColumnType::VarChar => OwnedColumn::VarChar(Vec::new()),
+        ColumnType::Decimal75(precision, scale) => {
+            OwnedColumn::Decimal75(precision, scale, Vec::new())
+        }
+        ColumnType::Scalar => OwnedColumn::Scalar(Vec::new()),
+        ColumnType::TimestampTZ(tu, tz) => OwnedColumn::TimestampTZ(tu, tz, Vec::new()),
+    }
+}
+
+/// Append `chunk` to `column`. Both are expected to have the same type.
+fn append_chunk<S: Scalar>(column: &mut OwnedColumn<S>, chunk: OwnedColumn<S>) {
+    match (column, chunk) {
+        (OwnedColumn::Boolean(col), OwnedColumn::Boolean(chunk)) => col.extend(chunk),
+        (OwnedColumn::SmallInt(col), OwnedColumn::SmallInt(chunk)) => col.extend(chunk),
+        (OwnedColumn::Int(col), OwnedColumn::Int(chunk)) => col.extend(chunk),
+        (OwnedColumn::BigInt(col), OwnedColumn::BigInt(chunk)) => col.extend(chunk),
+        (OwnedColumn::Int128(col), OwnedColumn::Int128(chunk)) => col.extend(chunk),
+        (OwnedColumn::VarChar(col), OwnedColumn::VarChar(chunk)) => col.extend(chunk),
+        (OwnedColumn::Decimal75(_, _, col), OwnedColumn::Decimal75(_, _, chunk)) => {
+            col.extend(chunk)
+        }
+        (OwnedColumn::Scalar(col), OwnedColumn::Scalar(chunk)) => col.extend(chunk),
+        (OwnedColumn::TimestampTZ(_, _, col), OwnedColumn::TimestampTZ(_, _, chunk)) => {
+            col.extend(chunk)
+        }
+        _ => panic!("chunk types are checked when fetched"),
+    }
+}
@@ -0,0 +1,180 @@
+use super::{
+    owned_table_utility::*, AsyncDataAccessor, AsyncDataAccessorError, Column, ColumnRef,
+    ColumnType, DataAccessor, FetchedDataAccessor, MetadataAccessor, OwnedColumn, OwnedTable,
+    TableRef,
+};
+use crate::base::{commitment::TableCommitment, scalar::Curve25519Scalar};
+use core::{
+    future::{ready, Future},
+    ops::Range,
+    pin::pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+use curve25519_dalek::RistrettoPoint;
+use indexmap::{indexmap, IndexMap};
+
+/// Drive a future that never needs to be woken up to completion.
+fn block_on<F: Future>(future: F) -> F::Output {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(core::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+/// An in-memory stand-in for remote storage that records the chunks requested from it.
+struct InMemoryAsyncAccessor {
+    table_ref: TableRef,
+    table: OwnedTable<Curve25519Scalar>,
+    offset: usize,
+    corrupted_row: Option<usize>,
+}
+
+impl MetadataAccessor for InMemoryAsyncAccessor {
+    fn get_length(&self, _table_ref: TableRef) -> usize {
+        self.table.num_rows()
+    }
+
+    fn get_offset(&self, _table_ref: TableRef) -> usize {
+        self.offset
+    }
+}
+
+impl AsyncDataAccessor<Curve25519Scalar> for InMemoryAsyncAccessor {
+    fn fetch_column_chunk(
+        &self,
+        column: ColumnRef,
+        rows: Range<usize>,
+    ) -> impl Future<Output = Result<OwnedColumn<Curve25519Scalar>, AsyncDataAccessorError>> + Send
+    {
+        let data = self.table.inner_table().get(&column.column_id()).unwrap();
+        let mut chunk = data.slice(rows.start - self.offset, rows.end - self.offset);
+        if let (Some(row), OwnedColumn::BigInt(values)) = (self.corrupted_row, &mut chunk) {
+            if rows.contains(&row) {
+                values[row - rows.start] += 1;
+            }
+        }
+        ready(Ok(chunk))
+    }
+}
+
+fn accessor_and_columns(
+    offset: usize,
+    corrupted_row: Option<usize>,
+) -> (InMemoryAsyncAccessor, Vec<ColumnRef>) {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let table = owned_table([
+        bigint("a", [1, 2, 3, 4, 5]),
+        varchar("b", ["t", "u", "v", "w", "x"]),
+    ]);
+    let columns = vec![
+        ColumnRef::new(table_ref, "a".parse().unwrap(), ColumnType::BigInt),
+        ColumnRef::new(table_ref, "b".parse().unwrap(), ColumnType::VarChar),
+    ];
+    let accessor = InMemoryAsyncAccessor {
+        table_ref,
+        table,
+        offset,
+        corrupted_row,
+    };
+    (accessor, columns)
+}
+
+fn table_commitments(
+    accessor: &InMemoryAsyncAccessor,
+) -> IndexMap<TableRef, TableCommitment<RistrettoPoint>> {
+    indexmap! {
+        accessor.table_ref => TableCommitment::from_owned_table_with_offset(
+            &accessor.table,
+            accessor.offset,
+            &(),
+        )
+    }
+}
+
+#[test]
+fn we_can_fetch_columns_in_chunks() {
+    let (accessor, columns) = accessor_and_columns(3, None);
+    let fetched = block_on(FetchedDataAccessor::fetch(&accessor, columns.clone(), 2)).unwrap();
+    assert_eq!(fetched.get_length(accessor.table_ref), 5);
+    assert_eq!(fetched.get_offset(accessor.table_ref), 3);
+    assert_eq!(
+        fetched.get_column(columns[0]),
+        Column::BigInt(&[1, 2, 3, 4, 5])
+    );
+    assert!(matches!(
+        fetched.get_column(columns[1]),
+        Column::VarChar((["t", "u", "v", "w", "x"], _))
+    ));
+}
+
+#[test]
+fn we_can_fetch_columns_with_an_integrity_check() {
+    for chunk_size in [1, 2, 5, 10] {
+        let (accessor, columns) = accessor_and_columns(3, None);
+        let commitments = table_commitments(&accessor);
+        let fetched = block_on(FetchedDataAccessor::fetch_with_integrity_check(
+            &accessor,
+            columns.clone(),
+            chunk_size,
+            &commitments,
+            &(),
+        ))
+        .unwrap();
+        assert_eq!(fetched.get_length(accessor.table_ref), 5);
+    }
+}
+
+#[test]
+fn we_cannot_fetch_corrupted_columns_with_an_integrity_check() {
+    let (accessor, columns) = accessor_and_columns(3, Some(5));
+    let commitments = table_commitments(&accessor);
+    assert_eq!(
+        block_on(FetchedDataAccessor::fetch_with_integrity_check(
+            &accessor,
+            columns.clone(),
+            2,
+            &commitments,
+            &(),
+        ))
+        .err(),
+        Some(AsyncDataAccessorError::CommitmentMismatch(columns[0]))
+    );
+    // Without the integrity check the corruption goes unnoticed
+    assert!(block_on(FetchedDataAccessor::fetch(&accessor, columns, 2)).is_ok());
+}
+
+#[test]
+fn we_cannot_check_integrity_against_a_commitment_to_different_rows() {
+    let (accessor, columns) = accessor_and_columns(3, None);
+    let commitments = indexmap! {
+        accessor.table_ref => TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+            &accessor.table,
+            0,
+            &(),
+        )
+    };
+    assert_eq!(
+        block_on(FetchedDataAccessor::fetch_with_integrity_check(
+            &accessor,
+            columns,
+            2,
+            &commitments,
+            &(),
+        ))
+        .err(),
+        Some(AsyncDataAccessorError::RangeMismatch {
+            committed: 0..5,
+            span: 3..8,
+        })
+    );
+}
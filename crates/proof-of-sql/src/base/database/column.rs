@@ -1,5 +1,6 @@
 use super::{LiteralValue, OwnedColumn, TableRef};
 use crate::base::{
+    if_rayon,
     math::decimal::{scale_scalar, Precision},
     scalar::Scalar,
 };
@@ -9,6 +10,7 @@ use proof_of_sql_parser::{
     posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
     Identifier,
 };
+#[cfg(feature = "rayon")]
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -177,45 +179,57 @@ impl<'a, S: Scalar> Column<'a, S> {
         })
     }
 
+    /// Returns the rows `start..end` of the column.
+    pub(crate) fn slice(&self, start: usize, end: usize) -> Self {
+        match self {
+            Self::Boolean(col) => Self::Boolean(&col[start..end]),
+            Self::SmallInt(col) => Self::SmallInt(&col[start..end]),
+            Self::Int(col) => Self::Int(&col[start..end]),
+            Self::BigInt(col) => Self::BigInt(&col[start..end]),
+            Self::Int128(col) => Self::Int128(&col[start..end]),
+            Self::Decimal75(precision, scale, col) => {
+                Self::Decimal75(*precision, *scale, &col[start..end])
+            }
+            Self::Scalar(col) => Self::Scalar(&col[start..end]),
+            Self::VarChar((strings, scalars)) => {
+                Self::VarChar((&strings[start..end], &scalars[start..end]))
+            }
+            Self::TimestampTZ(time_unit, time_zone, col) => {
+                Self::TimestampTZ(*time_unit, *time_zone, &col[start..end])
+            }
+        }
+    }
+
     /// Convert a column to a vector of Scalar values with scaling
     pub(crate) fn to_scalar_with_scaling(&self, scale: i8) -> Vec<S> {
         let scale_factor = scale_scalar(S::ONE, scale).expect("Invalid scale factor");
         match self {
-            Self::Boolean(col) => col
-                .par_iter()
+            Self::Boolean(col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|b| S::from(b) * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::Decimal75(_, _, col) => col
-                .par_iter()
+            Self::Decimal75(_, _, col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|s| *s * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::VarChar((_, scals)) => scals
-                .par_iter()
+            Self::VarChar((_, scals)) => if_rayon!(scals.par_iter(), scals.iter())
                 .map(|s| *s * scale_factor)
                 .collect::<Vec<_>>(),
 
-            Self::SmallInt(col) => col
-                .par_iter()
+            Self::SmallInt(col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|i| S::from(i) * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::Int(col) => col
-                .par_iter()
+            Self::Int(col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|i| S::from(i) * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::BigInt(col) => col
-                .par_iter()
+            Self::BigInt(col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|i| S::from(i) * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::Int128(col) => col
-                .par_iter()
+            Self::Int128(col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|i| S::from(i) * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::Scalar(col) => col
-                .par_iter()
+            Self::Scalar(col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|s| *s * scale_factor)
                 .collect::<Vec<_>>(),
-            Self::TimestampTZ(_, _, col) => col
-                .par_iter()
+            Self::TimestampTZ(_, _, col) => if_rayon!(col.par_iter(), col.iter())
                 .map(|i| S::from(i) * scale_factor)
                 .collect::<Vec<_>>(),
         }
@@ -902,4 +916,16 @@ mod tests {
         let new_owned_col = (&col).into();
         assert_eq!(owned_col, new_owned_col);
     }
+
+    #[test]
+    fn we_can_serialize_and_deserialize_a_column_ref_to_a_keyword_column() {
+        let column_ref = ColumnRef::new(
+            "sxt.t".parse().unwrap(),
+            Identifier::try_new_case_sensitive("timestamp").unwrap(),
+            ColumnType::BigInt,
+        );
+        let serialized = serde_json::to_string(&column_ref).unwrap();
+        let deserialized: ColumnRef = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, column_ref);
+    }
 }
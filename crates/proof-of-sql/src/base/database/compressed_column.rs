@@ -0,0 +1,446 @@
+use super::{
+    AccessorError, AccessorResult, Column, ColumnRef, ColumnType, DataAccessor, MetadataAccessor,
+    OwnedColumn, OwnedTable, SchemaAccessor, TableRef,
+};
+use crate::base::{commitment::CommittableColumn, scalar::Scalar};
+use bumpalo::Bump;
+use core::mem::size_of;
+use indexmap::IndexMap;
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    Identifier,
+};
+use serde::{Deserialize, Serialize};
+
+/// The encoding of a [`CompressedColumn`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnCodec {
+    /// The values are stored as is.
+    Plain,
+    /// The values are stored as runs of equal values, for booleans and low-cardinality integers.
+    RunLength,
+    /// The values are stored as the differences between consecutive values, for sorted bigints
+    /// and timestamps.
+    Delta,
+}
+
+/// Runs of equal values, along with the length of each run.
+type Runs<T> = Vec<(T, u64)>;
+
+/// A bigint column as its first value and the differences between consecutive values.
+///
+/// The differences are zigzag encoded and then LEB128 encoded, so that small differences take a
+/// single byte.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Deltas {
+    len: usize,
+    first: i64,
+    bytes: Vec<u8>,
+}
+
+impl Deltas {
+    fn encode(values: &[i64]) -> Self {
+        let mut bytes = Vec::new();
+        for window in values.windows(2) {
+            let delta = window[1].wrapping_sub(window[0]);
+            let mut zigzag = ((delta << 1) ^ (delta >> 63)) as u64;
+            while zigzag >= 0x80 {
+                bytes.push(zigzag as u8 | 0x80);
+                zigzag >>= 7;
+            }
+            bytes.push(zigzag as u8);
+        }
+        Self {
+            len: values.len(),
+            first: values.first().copied().unwrap_or(0),
+            bytes,
+        }
+    }
+
+    fn decode(&self) -> Vec<i64> {
+        let mut values = Vec::with_capacity(self.len);
+        if self.len == 0 {
+            return values;
+        }
+        let mut value = self.first;
+        values.push(value);
+        let mut bytes = self.bytes.iter();
+        while values.len() < self.len {
+            let mut zigzag = 0u64;
+            for shift in (0..64).step_by(7) {
+                let byte = *bytes.next().expect("there are as many deltas as values");
+                zigzag |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+            value = value.wrapping_add(delta);
+            values.push(value);
+        }
+        values
+    }
+
+    fn encoded_size(&self) -> usize {
+        size_of::<i64>() + self.bytes.len()
+    }
+}
+
+fn encode_runs<T: Copy + PartialEq>(values: &[T]) -> Runs<T> {
+    let mut runs: Runs<T> = Vec::new();
+    for &value in values {
+        match runs.last_mut() {
+            Some((run_value, run_len)) if *run_value == value => *run_len += 1,
+            _ => runs.push((value, 1)),
+        }
+    }
+    runs
+}
+
+fn runs_len<T>(runs: &Runs<T>) -> usize {
+    runs.iter().map(|&(_, run_len)| run_len as usize).sum()
+}
+
+fn runs_size<T>(runs: &Runs<T>) -> usize {
+    runs.len() * size_of::<(T, u64)>()
+}
+
+fn decode_runs<T: Copy>(runs: &Runs<T>) -> Vec<T> {
+    runs.iter()
+        .flat_map(|&(value, run_len)| core::iter::repeat(value).take(run_len as usize))
+        .collect()
+}
+
+/// Returns the runs of the values if they take less memory than the values themselves.
+fn encode_runs_if_smaller<T: Copy + PartialEq>(values: &[T]) -> Option<Runs<T>> {
+    let runs = encode_runs(values);
+    (runs_size(&runs) < values.len() * size_of::<T>()).then_some(runs)
+}
+
+/// Returns the deltas of the values if they take less memory than the values themselves.
+fn encode_deltas_if_smaller(values: &[i64]) -> Option<Deltas> {
+    let deltas = Deltas::encode(values);
+    (deltas.encoded_size() < values.len() * size_of::<i64>()).then_some(deltas)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum Encoding<S: Scalar> {
+    Plain(OwnedColumn<S>),
+    BooleanRuns(Runs<bool>),
+    SmallIntRuns(Runs<i16>),
+    IntRuns(Runs<i32>),
+    BigIntRuns(Runs<i64>),
+    BigIntDeltas(Deltas),
+    TimestampTZDeltas(PoSQLTimeUnit, PoSQLTimeZone, Deltas),
+}
+
+/// An [`OwnedColumn`] that is compressed in memory.
+///
+/// Booleans and low-cardinality integers are run-length encoded and sorted bigints and timestamps
+/// are delta encoded, whenever that takes less memory than the column itself. Other columns are
+/// stored as is. The column is decompressed when the prover reads it, so commitments and proofs
+/// are the same as for the uncompressed column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressedColumn<S: Scalar> {
+    encoding: Encoding<S>,
+}
+
+impl<S: Scalar> CompressedColumn<S> {
+    /// Compress the column with the codec that takes the least memory.
+    pub fn compress(column: &OwnedColumn<S>) -> Self {
+        let encoding = match column {
+            OwnedColumn::Boolean(col) => encode_runs_if_smaller(col).map(Encoding::BooleanRuns),
+            OwnedColumn::SmallInt(col) => encode_runs_if_smaller(col).map(Encoding::SmallIntRuns),
+            OwnedColumn::Int(col) => encode_runs_if_smaller(col).map(Encoding::IntRuns),
+            OwnedColumn::BigInt(col) => {
+                let runs = encode_runs_if_smaller(col)
+                    .map(|runs| (runs_size(&runs), Encoding::BigIntRuns(runs)));
+                let deltas = encode_deltas_if_smaller(col)
+                    .map(|deltas| (deltas.encoded_size(), Encoding::BigIntDeltas(deltas)));
+                runs.into_iter()
+                    .chain(deltas)
+                    .min_by_key(|(size, _)| *size)
+                    .map(|(_, encoding)| encoding)
+            }
+            OwnedColumn::TimestampTZ(tu, tz, col) => encode_deltas_if_smaller(col)
+                .map(|deltas| Encoding::TimestampTZDeltas(*tu, *tz, deltas)),
+            _ => None,
+        };
+        Self {
+            encoding: encoding.unwrap_or_else(|| Encoding::Plain(column.clone())),
+        }
+    }
+
+    /// Returns the codec that the column is compressed with.
+    pub fn codec(&self) -> ColumnCodec {
+        match self.encoding {
+            Encoding::Plain(_) => ColumnCodec::Plain,
+            Encoding::BooleanRuns(_)
+            | Encoding::SmallIntRuns(_)
+            | Encoding::IntRuns(_)
+            | Encoding::BigIntRuns(_) => ColumnCodec::RunLength,
+            Encoding::BigIntDeltas(_) | Encoding::TimestampTZDeltas(_, _, _) => ColumnCodec::Delta,
+        }
+    }
+
+    /// Returns the length of the column.
+    pub fn len(&self) -> usize {
+        match &self.encoding {
+            Encoding::Plain(col) => col.len(),
+            Encoding::BooleanRuns(runs) => runs_len(runs),
+            Encoding::SmallIntRuns(runs) => runs_len(runs),
+            Encoding::IntRuns(runs) => runs_len(runs),
+            Encoding::BigIntRuns(runs) => runs_len(runs),
+            Encoding::BigIntDeltas(deltas) | Encoding::TimestampTZDeltas(_, _, deltas) => {
+                deltas.len
+            }
+        }
+    }
+
+    /// Returns `true` if the column has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type of the column.
+    pub fn column_type(&self) -> ColumnType {
+        match &self.encoding {
+            Encoding::Plain(col) => col.column_type(),
+            Encoding::BooleanRuns(_) => ColumnType::Boolean,
+            Encoding::SmallIntRuns(_) => ColumnType::SmallInt,
+            Encoding::IntRuns(_) => ColumnType::Int,
+            Encoding::BigIntRuns(_) | Encoding::BigIntDeltas(_) => ColumnType::BigInt,
+            Encoding::TimestampTZDeltas(tu, tz, _) => ColumnType::TimestampTZ(*tu, *tz),
+        }
+    }
+
+    /// Decompress the column.
+    pub fn decompress(&self) -> OwnedColumn<S> {
+        match &self.encoding {
+            Encoding::Plain(col) => col.clone(),
+            Encoding::BooleanRuns(runs) => OwnedColumn::Boolean(decode_runs(runs)),
+            Encoding::SmallIntRuns(runs) => OwnedColumn::SmallInt(decode_runs(runs)),
+            Encoding::IntRuns(runs) => OwnedColumn::Int(decode_runs(runs)),
+            Encoding::BigIntRuns(runs) => OwnedColumn::BigInt(decode_runs(runs)),
+            Encoding::BigIntDeltas(deltas) => OwnedColumn::BigInt(deltas.decode()),
+            Encoding::TimestampTZDeltas(tu, tz, deltas) => {
+                OwnedColumn::TimestampTZ(*tu, *tz, deltas.decode())
+            }
+        }
+    }
+
+    /// Decompress the column into memory allocated by `alloc`.
+    pub fn decompress_in<'a>(&self, alloc: &'a Bump) -> Column<'a, S> {
+        match self.decompress() {
+            OwnedColumn::Boolean(col) => Column::Boolean(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::SmallInt(col) => Column::SmallInt(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::Int(col) => Column::Int(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::BigInt(col) => Column::BigInt(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::Int128(col) => Column::Int128(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::Decimal75(precision, scale, col) => {
+                Column::Decimal75(precision, scale, alloc.alloc_slice_copy(&col))
+            }
+            OwnedColumn::Scalar(col) => Column::Scalar(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::VarChar(col) => Column::VarChar((
+                alloc.alloc_slice_fill_iter(col.iter().map(|s| alloc.alloc_str(s) as &str)),
+                alloc.alloc_slice_fill_iter(col.iter().map(Into::into)),
+            )),
+            OwnedColumn::TimestampTZ(tu, tz, col) => {
+                Column::TimestampTZ(tu, tz, alloc.alloc_slice_copy(&col))
+            }
+        }
+    }
+
+    /// Decompress the column into its committable form, in memory allocated by `alloc`.
+    pub fn committable_column_in<'a>(&self, alloc: &'a Bump) -> CommittableColumn<'a> {
+        CommittableColumn::from(&self.decompress_in(alloc))
+    }
+}
+
+impl<S: Scalar> From<&OwnedColumn<S>> for CompressedColumn<S> {
+    fn from(column: &OwnedColumn<S>) -> Self {
+        Self::compress(column)
+    }
+}
+
+/// A table whose columns are compressed in memory, see [`CompressedColumn`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompressedTable<S: Scalar> {
+    num_rows: usize,
+    columns: IndexMap<Identifier, CompressedColumn<S>>,
+}
+
+impl<S: Scalar> CompressedTable<S> {
+    /// Returns the number of rows of the table.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the compressed columns of the table.
+    pub fn columns(&self) -> &IndexMap<Identifier, CompressedColumn<S>> {
+        &self.columns
+    }
+
+    /// Decompress the table.
+    pub fn decompress(&self) -> OwnedTable<S> {
+        let columns = self
+            .columns
+            .iter()
+            .map(|(id, column)| (*id, column.decompress()));
+        OwnedTable::try_from_iter(columns)
+            .expect("the columns of a compressed table have the same length")
+    }
+}
+
+impl<S: Scalar> From<&OwnedTable<S>> for CompressedTable<S> {
+    fn from(table: &OwnedTable<S>) -> Self {
+        Self {
+            num_rows: table.num_rows(),
+            columns: table
+                .inner_table()
+                .iter()
+                .map(|(id, column)| (*id, CompressedColumn::compress(column)))
+                .collect(),
+        }
+    }
+}
+
+/// A store of tables whose columns are compressed in memory, for provers with large tables.
+///
+/// The prover reads the tables through a [`DecompressingDataAccessor`], which only decompresses the
+/// columns that a query references.
+#[derive(Debug, Clone)]
+pub struct CompressedTableStore<S: Scalar> {
+    tables: IndexMap<TableRef, (CompressedTable<S>, usize)>,
+}
+
+impl<S: Scalar> Default for CompressedTableStore<S> {
+    fn default() -> Self {
+        Self {
+            tables: IndexMap::new(),
+        }
+    }
+}
+
+impl<S: Scalar> CompressedTableStore<S> {
+    /// Create a new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compress the table and add it to the store.
+    pub fn add_table(&mut self, table_ref: TableRef, table: &OwnedTable<S>, offset: usize) {
+        self.tables
+            .insert(table_ref, (CompressedTable::from(table), offset));
+    }
+
+    /// Returns the compressed table.
+    pub fn table(&self, table_ref: TableRef) -> Option<&CompressedTable<S>> {
+        Some(&self.tables.get(&table_ref)?.0)
+    }
+
+    fn try_table(&self, table_ref: TableRef) -> AccessorResult<&CompressedTable<S>> {
+        self.table(table_ref)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+}
+
+impl<S: Scalar> MetadataAccessor for CompressedTableStore<S> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.tables.get(&table_ref).unwrap().0.num_rows()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.tables.get(&table_ref).unwrap().1
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_table(table_ref)?.num_rows())
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.tables
+            .get(&table_ref)
+            .map(|(_, offset)| *offset)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+}
+
+impl<S: Scalar> SchemaAccessor for CompressedTableStore<S> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        Some(
+            self.table(table_ref)?
+                .columns()
+                .get(&column_id)?
+                .column_type(),
+        )
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.table(table_ref)
+            .unwrap()
+            .columns()
+            .iter()
+            .map(|(&id, column)| (id, column.column_type()))
+            .collect()
+    }
+}
+
+/// A [`DataAccessor`] that decompresses the columns of a [`CompressedTableStore`] when the prover
+/// reads them.
+///
+/// The decompressed columns only live as long as the accessor.
+pub struct DecompressingDataAccessor<'a, S: Scalar> {
+    store: &'a CompressedTableStore<S>,
+    alloc: Bump,
+}
+
+impl<'a, S: Scalar> DecompressingDataAccessor<'a, S> {
+    /// Create a new accessor that decompresses the columns of the store.
+    pub fn new(store: &'a CompressedTableStore<S>) -> Self {
+        Self {
+            store,
+            alloc: Bump::new(),
+        }
+    }
+}
+
+impl<S: Scalar> MetadataAccessor for DecompressingDataAccessor<'_, S> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.store.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.store.get_offset(table_ref)
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.store.try_get_length(table_ref)
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.store.try_get_offset(table_ref)
+    }
+}
+
+impl<S: Scalar> DataAccessor<S> for DecompressingDataAccessor<'_, S> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        self.store
+            .table(column.table_ref())
+            .unwrap()
+            .columns()
+            .get(&column.column_id())
+            .unwrap()
+            .decompress_in(&self.alloc)
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<S>> {
+        Ok(self
+            .store
+            .try_table(column.table_ref())?
+            .columns()
+            .get(&column.column_id())
+            .ok_or_else(|| AccessorError::column_not_found(column))?
+            .decompress_in(&self.alloc))
+    }
+}
@@ -0,0 +1,157 @@
+use super::{
+    owned_table_utility::*, Column, ColumnCodec, ColumnRef, ColumnType, CompressedColumn,
+    CompressedTable, CompressedTableStore, DataAccessor, DecompressingDataAccessor,
+    MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, TableRef,
+};
+use crate::base::{commitment::CommittableColumn, scalar::Curve25519Scalar};
+use bumpalo::Bump;
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    utility::ident,
+};
+
+fn assert_round_trip(column: &OwnedColumn<Curve25519Scalar>, codec: ColumnCodec) {
+    let compressed = CompressedColumn::compress(column);
+    assert_eq!(compressed.codec(), codec);
+    assert_eq!(compressed.len(), column.len());
+    assert_eq!(compressed.is_empty(), column.is_empty());
+    assert_eq!(compressed.column_type(), column.column_type());
+    assert_eq!(&compressed.decompress(), column);
+    let alloc = Bump::new();
+    assert_eq!(
+        compressed.committable_column_in(&alloc),
+        CommittableColumn::from(column)
+    );
+}
+
+#[test]
+fn we_can_run_length_encode_low_cardinality_columns() {
+    let flags = [true; 50].into_iter().chain([false; 50]);
+    assert_round_trip(
+        &OwnedColumn::Boolean(flags.collect()),
+        ColumnCodec::RunLength,
+    );
+    assert_round_trip(
+        &OwnedColumn::SmallInt([1; 40].into_iter().chain([2; 60]).collect()),
+        ColumnCodec::RunLength,
+    );
+    assert_round_trip(
+        &OwnedColumn::Int([7; 100].into_iter().chain([-3; 100]).collect()),
+        ColumnCodec::RunLength,
+    );
+    assert_round_trip(&OwnedColumn::BigInt(vec![42; 100]), ColumnCodec::RunLength);
+}
+
+#[test]
+fn we_can_delta_encode_sorted_bigints_and_timestamps() {
+    assert_round_trip(
+        &OwnedColumn::BigInt((0..100).map(|i| 1_000_000 + 3 * i).collect()),
+        ColumnCodec::Delta,
+    );
+    assert_round_trip(
+        &OwnedColumn::BigInt((0..100).map(|i| 500 - i * i).collect()),
+        ColumnCodec::Delta,
+    );
+    assert_round_trip(
+        &OwnedColumn::TimestampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            (0..100).map(|i| 1_625_072_400 + 12 * i).collect(),
+        ),
+        ColumnCodec::Delta,
+    );
+}
+
+#[test]
+fn we_can_delta_encode_bigints_with_extreme_differences() {
+    let mut values: Vec<i64> = (0..100).collect();
+    values.extend([i64::MAX, i64::MIN, i64::MAX, 0, -1]);
+    assert_round_trip(&OwnedColumn::BigInt(values), ColumnCodec::Delta);
+}
+
+#[test]
+fn we_store_columns_that_do_not_compress_as_is() {
+    assert_round_trip(
+        &OwnedColumn::BigInt(vec![0, i64::MAX / 2, i64::MIN / 2, i64::MAX / 2, 0]),
+        ColumnCodec::Plain,
+    );
+    assert_round_trip(
+        &OwnedColumn::Boolean(vec![true, false, true, false]),
+        ColumnCodec::Plain,
+    );
+    assert_round_trip(
+        &OwnedColumn::VarChar(vec!["a".to_string(); 100]),
+        ColumnCodec::Plain,
+    );
+    assert_round_trip(&OwnedColumn::Int128((0..100).collect()), ColumnCodec::Plain);
+    assert_round_trip(&OwnedColumn::BigInt(vec![]), ColumnCodec::Plain);
+}
+
+fn table() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("a", 0..100),
+        boolean("b", (0..100).map(|i| i < 30)),
+        varchar("c", (0..100).map(|i| i.to_string())),
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            (0..100).map(|i| 1_625_072_400_000 + i),
+        ),
+    ])
+}
+
+#[test]
+fn we_can_compress_and_decompress_a_table() {
+    let compressed = CompressedTable::from(&table());
+    assert_eq!(compressed.num_rows(), 100);
+    let codecs: Vec<_> = compressed
+        .columns()
+        .values()
+        .map(CompressedColumn::codec)
+        .collect();
+    assert_eq!(
+        codecs,
+        [
+            ColumnCodec::Delta,
+            ColumnCodec::RunLength,
+            ColumnCodec::Plain,
+            ColumnCodec::Delta,
+        ]
+    );
+    assert_eq!(compressed.decompress(), table());
+}
+
+#[test]
+fn we_can_decompress_the_columns_of_a_compressed_table_on_read() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let mut store = CompressedTableStore::new();
+    store.add_table(table_ref, &table(), 2);
+    assert_eq!(store.get_length(table_ref), 100);
+    assert_eq!(store.get_offset(table_ref), 2);
+    assert_eq!(
+        store.lookup_column(table_ref, ident("a")),
+        Some(ColumnType::BigInt)
+    );
+    assert_eq!(store.lookup_column(table_ref, ident("e")), None);
+    assert_eq!(
+        store.lookup_schema(table_ref),
+        table()
+            .inner_table()
+            .iter()
+            .map(|(&id, column)| (id, column.column_type()))
+            .collect::<Vec<_>>()
+    );
+
+    let accessor = DecompressingDataAccessor::new(&store);
+    assert_eq!(accessor.get_length(table_ref), 100);
+    assert_eq!(accessor.get_offset(table_ref), 2);
+    let expected: Vec<i64> = (0..100).collect();
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+    assert_eq!(accessor.get_column(column), Column::BigInt(&expected));
+    let column = ColumnRef::new(table_ref, ident("c"), ColumnType::VarChar);
+    assert!(matches!(
+        accessor.get_column(column),
+        Column::VarChar((["0", "1", "2", ..], _))
+    ));
+}
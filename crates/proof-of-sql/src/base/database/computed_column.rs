@@ -0,0 +1,127 @@
+use super::{ExpressionEvaluationError, ExpressionEvaluationResult, OwnedColumn, OwnedTable};
+use crate::base::scalar::Scalar;
+use proof_of_sql_parser::{
+    intermediate_ast::{Expression, SelectResultExpr, SetExpression},
+    Identifier, SelectStatement,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A column whose values are derived from the other columns of its table when rows are ingested.
+///
+/// The values are committed along with the rest of the row, so a query that uses the derivation,
+/// e.g. `amount * 100`, can read the precommitted column instead of proving the computation.
+/// The derivation is recorded so that it can be published alongside the table commitment, and
+/// verifiers can see what the column means. Derivations may use any expression that
+/// [`OwnedTable::evaluate`] supports, including other computed columns declared before them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComputedColumn {
+    name: Identifier,
+    derivation: Expression,
+}
+
+/// Errors that can occur when adding computed columns to rows.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ComputedColumnError {
+    /// The rows already have a column with the name of the computed column.
+    #[error("the rows already have a column named {0}")]
+    DuplicateColumnName(Identifier),
+    /// The derivation cannot be evaluated on the rows.
+    #[error(transparent)]
+    EvaluationError(#[from] ExpressionEvaluationError),
+}
+
+impl ComputedColumn {
+    /// Declare a column named `name` whose values are given by `derivation`.
+    pub fn new(name: Identifier, derivation: Expression) -> Self {
+        Self { name, derivation }
+    }
+
+    /// Returns the name of the column.
+    pub fn name(&self) -> Identifier {
+        self.name
+    }
+
+    /// Returns the expression that the column is derived by.
+    pub fn derivation(&self) -> &Expression {
+        &self.derivation
+    }
+
+    /// Compute the values of the column for the rows of `table`.
+    pub fn evaluate<S: Scalar>(
+        &self,
+        table: &OwnedTable<S>,
+    ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
+        table.evaluate(&self.derivation)
+    }
+
+    /// Replace every occurrence of the derivation in `expr` with a reference to the column.
+    pub fn substitute(&self, expr: &mut Expression) {
+        if *expr == self.derivation {
+            *expr = Expression::Column(self.name);
+            return;
+        }
+        match expr {
+            Expression::Unary { expr, .. } | Expression::Aggregation { expr, .. } => {
+                self.substitute(expr)
+            }
+            Expression::Binary { left, right, .. } => {
+                self.substitute(left);
+                self.substitute(right);
+            }
+            Expression::Function { args, .. } => {
+                for arg in args {
+                    self.substitute(arg);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl<S: Scalar> OwnedTable<S> {
+    /// Append the computed columns to the rows, in order, before the rows are committed.
+    pub fn try_with_computed_columns(
+        self,
+        computed_columns: &[ComputedColumn],
+    ) -> Result<Self, ComputedColumnError> {
+        computed_columns
+            .iter()
+            .try_fold(self, |table, computed_column| {
+                let column = computed_column.evaluate(&table)?;
+                let mut columns = table.into_inner();
+                if columns.insert(computed_column.name, column).is_some() {
+                    return Err(ComputedColumnError::DuplicateColumnName(
+                        computed_column.name,
+                    ));
+                }
+                Ok(OwnedTable::try_new(columns)
+                    .expect("the computed column has as many rows as the table"))
+            })
+    }
+}
+
+/// Rewrite the query so that the result and where expressions read the computed columns of the
+/// queried table wherever they use their derivations.
+///
+/// Note that `SELECT *` also returns the computed columns, since they are part of the table.
+pub fn substitute_computed_columns(
+    statement: &mut SelectStatement,
+    computed_columns: &[ComputedColumn],
+) {
+    let SetExpression::Query {
+        result_exprs,
+        where_expr,
+        ..
+    } = statement.expr.as_mut();
+    for computed_column in computed_columns {
+        for result_expr in result_exprs.iter_mut() {
+            if let SelectResultExpr::AliasedResultExpr(aliased_expr) = result_expr {
+                computed_column.substitute(&mut aliased_expr.expr);
+            }
+        }
+        if let Some(where_expr) = where_expr {
+            computed_column.substitute(where_expr);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use super::{
+    owned_table_utility::*, substitute_computed_columns, ComputedColumn, ComputedColumnError,
+    ExpressionEvaluationError, OwnedTable,
+};
+use crate::base::scalar::Curve25519Scalar;
+use proof_of_sql_parser::{utility::*, SelectStatement};
+
+fn cents() -> ComputedColumn {
+    ComputedColumn::new(ident("cents"), *mul(col("amount"), lit(100)))
+}
+
+fn is_large() -> ComputedColumn {
+    ComputedColumn::new(ident("is_large"), *ge(col("cents"), lit(1000)))
+}
+
+#[test]
+fn we_can_add_computed_columns_to_rows() {
+    let rows: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("amount", [3, 12]), varchar("name", ["a", "b"])]);
+    assert_eq!(
+        rows.try_with_computed_columns(&[cents(), is_large()])
+            .unwrap(),
+        owned_table([
+            bigint("amount", [3, 12]),
+            varchar("name", ["a", "b"]),
+            bigint("cents", [300, 1200]),
+            boolean("is_large", [false, true]),
+        ])
+    );
+}
+
+#[test]
+fn we_cannot_add_computed_columns_that_clash_or_cannot_be_evaluated() {
+    let rows: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("amount", [3, 12]), bigint("cents", [1, 2])]);
+    assert_eq!(
+        rows.try_with_computed_columns(&[cents()]),
+        Err(ComputedColumnError::DuplicateColumnName(ident("cents")))
+    );
+    let rows: OwnedTable<Curve25519Scalar> = owned_table([bigint("amount", [3, 12])]);
+    assert_eq!(
+        rows.try_with_computed_columns(&[is_large(), cents()]),
+        Err(ComputedColumnError::EvaluationError(
+            ExpressionEvaluationError::ColumnNotFound("cents".to_string())
+        ))
+    );
+}
+
+#[test]
+fn we_can_substitute_computed_columns_into_a_query() {
+    let mut statement: SelectStatement =
+        "SELECT name, amount * 100 AS c, sum(amount * 100 + 1) AS s FROM t \
+            WHERE amount * 100 >= 1000 GROUP BY name"
+            .parse()
+            .unwrap();
+    substitute_computed_columns(&mut statement, &[cents()]);
+    let expected: SelectStatement =
+        "SELECT name, cents AS c, sum(cents + 1) AS s FROM t WHERE cents >= 1000 GROUP BY name"
+            .parse()
+            .unwrap();
+    assert_eq!(statement, expected);
+}
+
+#[test]
+fn we_do_not_substitute_into_queries_that_do_not_use_the_derivation() {
+    let mut statement: SelectStatement =
+        "SELECT amount, amount * 10 AS c FROM t WHERE 100 * amount = 5"
+            .parse()
+            .unwrap();
+    let expected = statement.clone();
+    substitute_computed_columns(&mut statement, &[cents()]);
+    assert_eq!(statement, expected);
+}
+
+#[test]
+fn we_can_serialize_the_derivation_of_a_computed_column() {
+    let bytes = postcard::to_allocvec(&cents()).unwrap();
+    let computed_column: ComputedColumn = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(computed_column, cents());
+    assert_eq!(computed_column.name(), ident("cents"));
+    assert_eq!(computed_column.derivation(), &*mul(col("amount"), lit(100)));
+}
+
+#[cfg(feature = "blitzar")]
+#[test]
+fn we_can_prove_a_query_that_reads_a_computed_column() {
+    use crate::{
+        base::{
+            commitment::InnerProductProof,
+            database::{OwnedTableTestAccessor, TestAccessor},
+        },
+        sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+    };
+
+    let rows: OwnedTable<Curve25519Scalar> = owned_table([bigint("amount", [3, 12, 40])]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        rows.try_with_computed_columns(&[cents()]).unwrap(),
+        0,
+    );
+    let mut statement: SelectStatement =
+        "SELECT amount * 100 AS c FROM t WHERE amount * 100 >= 1000"
+            .parse()
+            .unwrap();
+    substitute_computed_columns(&mut statement, &[cents()]);
+    let query = QueryExpr::try_new(statement, ident("sxt"), &accessor).unwrap();
+    let table = VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &())
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    assert_eq!(table, owned_table([bigint("c", [1200, 4000])]));
+}
@@ -0,0 +1,406 @@
+use super::{
+    AccessorError, AccessorResult, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, TableRef,
+};
+use crate::base::{
+    commitment::{Commitment, TableCommitment},
+    scalar::Scalar,
+};
+use bumpalo::Bump;
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when decrypting the columns of an [`EncryptedTableStore`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ColumnEncryptionError {
+    /// The store has no encrypted data for the column
+    #[error("no encrypted data found for column {0:?}")]
+    MissingColumn(ColumnRef),
+    /// The stored ciphertext does not match the digest that was recorded when it was ingested
+    #[error("the ciphertext of column {0:?} does not match its recorded digest")]
+    CiphertextMismatch(ColumnRef),
+    /// The cipher could not decrypt the column, e.g. because it does not have the key
+    #[error("failed to decrypt column {column:?} with key {key_id}")]
+    DecryptionFailed {
+        /// The column that was being decrypted
+        column: ColumnRef,
+        /// The id of the key that the column was encrypted with
+        key_id: String,
+    },
+    /// The decrypted data is not the data that was committed to when it was ingested
+    #[error("the decrypted data of column {0:?} does not match its committed data")]
+    PlaintextMismatch(ColumnRef),
+    /// The column was encrypted with a different key than the one recorded with its commitment
+    #[error("column {0:?} was not encrypted with the key recorded with its commitment")]
+    KeyIdMismatch(ColumnRef),
+}
+
+/// A cipher used to encrypt columns at rest.
+///
+/// Key management is left to the implementor. Every key has an id, which is recorded with the
+/// columns that it encrypted and in their [`EncryptedTableCommitment`], so that keys can be
+/// rotated without re-encrypting old columns.
+pub trait ColumnCipher {
+    /// Returns the id of the key used to encrypt new columns.
+    fn key_id(&self) -> &str;
+
+    /// Encrypt the plaintext with the key identified by [`ColumnCipher::key_id`].
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8>;
+
+    /// Decrypt a ciphertext that was encrypted with the key identified by `key_id`.
+    ///
+    /// Returns `None` if the key is unavailable or the ciphertext cannot be decrypted.
+    fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// An encrypted column, along with the metadata needed to decrypt it and link it to its
+/// commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedColumn {
+    key_id: String,
+    column_type: ColumnType,
+    ciphertext: Vec<u8>,
+    ciphertext_digest: [u8; 32],
+    plaintext_checksum: [u8; 32],
+}
+
+impl EncryptedColumn {
+    /// Encrypt the column with the current key of the cipher.
+    pub fn encrypt<S: Scalar + Serialize>(
+        column: &OwnedColumn<S>,
+        cipher: &impl ColumnCipher,
+    ) -> Self {
+        let plaintext = postcard::to_allocvec(column).expect("columns are always serializable");
+        let ciphertext = cipher.encrypt(&plaintext);
+        Self {
+            key_id: cipher.key_id().to_string(),
+            column_type: column.column_type(),
+            ciphertext_digest: *blake3::hash(&ciphertext).as_bytes(),
+            ciphertext,
+            plaintext_checksum: column.checksum(),
+        }
+    }
+
+    /// Returns the id of the key that the column was encrypted with.
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// Returns the type of the column.
+    pub fn column_type(&self) -> ColumnType {
+        self.column_type
+    }
+
+    /// Returns the encrypted data of the column.
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.ciphertext
+    }
+
+    /// Returns the Blake3 checksum of the column before it was encrypted.
+    ///
+    /// See [`OwnedColumn::checksum`].
+    pub fn plaintext_checksum(&self) -> [u8; 32] {
+        self.plaintext_checksum
+    }
+
+    /// Decrypt the column, checking that the result is the data that was encrypted.
+    ///
+    /// `column_ref` is only used to report errors.
+    pub fn decrypt<S: Scalar + for<'de> Deserialize<'de>>(
+        &self,
+        column_ref: ColumnRef,
+        cipher: &impl ColumnCipher,
+    ) -> Result<OwnedColumn<S>, ColumnEncryptionError> {
+        if *blake3::hash(&self.ciphertext).as_bytes() != self.ciphertext_digest {
+            return Err(ColumnEncryptionError::CiphertextMismatch(column_ref));
+        }
+        let plaintext = cipher
+            .decrypt(&self.key_id, &self.ciphertext)
+            .ok_or_else(|| ColumnEncryptionError::DecryptionFailed {
+                column: column_ref,
+                key_id: self.key_id.clone(),
+            })?;
+        postcard::from_bytes::<OwnedColumn<S>>(&plaintext)
+            .ok()
+            .filter(|column| column.checksum() == self.plaintext_checksum)
+            .ok_or(ColumnEncryptionError::PlaintextMismatch(column_ref))
+    }
+}
+
+/// The commitment to the plaintext of an encrypted table, along with the id of the key that
+/// each of its columns was encrypted with.
+///
+/// The key ids are serialized with the commitment, so that anyone holding the commitment knows
+/// which keys are needed to read the table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedTableCommitment<C: Commitment> {
+    table_commitment: TableCommitment<C>,
+    key_ids: IndexMap<Identifier, String>,
+}
+
+impl<C: Commitment> EncryptedTableCommitment<C> {
+    /// Returns the commitment to the plaintext of the table.
+    pub fn table_commitment(&self) -> &TableCommitment<C> {
+        &self.table_commitment
+    }
+
+    /// Returns the id of the key that each column was encrypted with.
+    pub fn key_ids(&self) -> &IndexMap<Identifier, String> {
+        &self.key_ids
+    }
+
+    /// Returns the id of the key that the column was encrypted with.
+    pub fn key_id(&self, column_id: Identifier) -> Option<&str> {
+        self.key_ids.get(&column_id).map(String::as_str)
+    }
+}
+
+/// A table whose commitments were computed from its plaintext but whose data is encrypted.
+#[derive(Debug, Clone)]
+struct EncryptedTable<C: Commitment> {
+    commitment: EncryptedTableCommitment<C>,
+    columns: IndexMap<Identifier, EncryptedColumn>,
+}
+
+/// A store of tables that are encrypted at rest.
+///
+/// The commitments to a table are computed from its plaintext when it is ingested, so they are
+/// the same commitments that would be computed for an unencrypted table, and queries against
+/// the store verify as usual. The store serves as the verifier's accessor, while the prover
+/// reads the plaintext through a [`DecryptingDataAccessor`].
+#[derive(Debug, Clone)]
+pub struct EncryptedTableStore<C: Commitment> {
+    tables: IndexMap<TableRef, EncryptedTable<C>>,
+}
+
+impl<C: Commitment> Default for EncryptedTableStore<C> {
+    fn default() -> Self {
+        Self {
+            tables: IndexMap::new(),
+        }
+    }
+}
+
+impl<C: Commitment> EncryptedTableStore<C> {
+    /// Create a new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Commit to the plaintext of the table and store its columns encrypted with the current key
+    /// of the cipher.
+    pub fn add_table(
+        &mut self,
+        table_ref: TableRef,
+        table: &OwnedTable<C::Scalar>,
+        offset: usize,
+        cipher: &impl ColumnCipher,
+        setup: &C::PublicSetup<'_>,
+    ) {
+        let table_commitment = TableCommitment::from_owned_table_with_offset(table, offset, setup);
+        let columns: IndexMap<_, _> = table
+            .inner_table()
+            .iter()
+            .map(|(identifier, column)| (*identifier, EncryptedColumn::encrypt(column, cipher)))
+            .collect();
+        let key_ids = columns
+            .iter()
+            .map(|(identifier, column)| (*identifier, column.key_id().to_string()))
+            .collect();
+        let commitment = EncryptedTableCommitment {
+            table_commitment,
+            key_ids,
+        };
+        self.tables.insert(
+            table_ref,
+            EncryptedTable {
+                commitment,
+                columns,
+            },
+        );
+    }
+
+    /// Returns the commitment to the plaintext of the table.
+    pub fn table_commitment(&self, table_ref: TableRef) -> Option<&TableCommitment<C>> {
+        Some(self.tables.get(&table_ref)?.commitment.table_commitment())
+    }
+
+    /// Returns the commitment to the plaintext of the table along with the key ids of its
+    /// columns.
+    pub fn encrypted_table_commitment(
+        &self,
+        table_ref: TableRef,
+    ) -> Option<&EncryptedTableCommitment<C>> {
+        Some(&self.tables.get(&table_ref)?.commitment)
+    }
+
+    /// Returns the encrypted data of the column.
+    pub fn encrypted_column(
+        &self,
+        table_ref: TableRef,
+        column_id: Identifier,
+    ) -> Option<&EncryptedColumn> {
+        self.tables.get(&table_ref)?.columns.get(&column_id)
+    }
+
+    /// Decrypt the column, checking that the result is the data that was committed to.
+    pub fn try_decrypt_column(
+        &self,
+        column: ColumnRef,
+        cipher: &impl ColumnCipher,
+    ) -> Result<OwnedColumn<C::Scalar>, ColumnEncryptionError> {
+        let table = self
+            .tables
+            .get(&column.table_ref())
+            .ok_or(ColumnEncryptionError::MissingColumn(column))?;
+        let encrypted_column = table
+            .columns
+            .get(&column.column_id())
+            .ok_or(ColumnEncryptionError::MissingColumn(column))?;
+        if table.commitment.key_id(column.column_id()) != Some(encrypted_column.key_id()) {
+            return Err(ColumnEncryptionError::KeyIdMismatch(column));
+        }
+        encrypted_column.decrypt(column, cipher)
+    }
+}
+
+impl<C: Commitment> MetadataAccessor for EncryptedTableStore<C> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.tables
+            .get(&table_ref)
+            .unwrap()
+            .commitment
+            .table_commitment
+            .num_rows()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.tables
+            .get(&table_ref)
+            .unwrap()
+            .commitment
+            .table_commitment
+            .range()
+            .start
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.tables
+            .get(&table_ref)
+            .map(|table| table.commitment.table_commitment.num_rows())
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.tables
+            .get(&table_ref)
+            .map(|table| table.commitment.table_commitment.range().start)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+}
+
+impl<C: Commitment> CommitmentAccessor<C> for EncryptedTableStore<C> {
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        self.tables
+            .get(&column.table_ref())
+            .unwrap()
+            .commitment
+            .table_commitment
+            .column_commitments()
+            .get_commitment(&column.column_id())
+            .unwrap()
+    }
+}
+
+impl<C: Commitment> SchemaAccessor for EncryptedTableStore<C> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        Some(self.encrypted_column(table_ref, column_id)?.column_type())
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.tables
+            .get(&table_ref)
+            .unwrap()
+            .columns
+            .iter()
+            .map(|(&id, column)| (id, column.column_type()))
+            .collect()
+    }
+}
+
+/// A [`DataAccessor`] that decrypts the columns of an [`EncryptedTableStore`] when the prover
+/// reads them.
+///
+/// The plaintext only lives as long as the accessor.
+pub struct DecryptingDataAccessor<'a, C: Commitment, E: ColumnCipher> {
+    store: &'a EncryptedTableStore<C>,
+    cipher: &'a E,
+    alloc: Bump,
+}
+
+impl<'a, C: Commitment, E: ColumnCipher> DecryptingDataAccessor<'a, C, E> {
+    /// Create a new accessor that decrypts the columns of the store with the cipher.
+    pub fn new(store: &'a EncryptedTableStore<C>, cipher: &'a E) -> Self {
+        Self {
+            store,
+            cipher,
+            alloc: Bump::new(),
+        }
+    }
+}
+
+impl<C: Commitment, E: ColumnCipher> MetadataAccessor for DecryptingDataAccessor<'_, C, E> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.store.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.store.get_offset(table_ref)
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.store.try_get_length(table_ref)
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.store.try_get_offset(table_ref)
+    }
+}
+
+impl<C: Commitment, E: ColumnCipher> DataAccessor<C::Scalar> for DecryptingDataAccessor<'_, C, E> {
+    fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
+        self.try_get_column(column).unwrap()
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<C::Scalar>> {
+        let owned_column = match self.store.try_decrypt_column(column, self.cipher) {
+            Ok(owned_column) => owned_column,
+            Err(ColumnEncryptionError::MissingColumn(_)) => {
+                self.store.try_get_length(column.table_ref())?;
+                return Err(AccessorError::column_not_found(column));
+            }
+            Err(error) => return Err(AccessorError::column_unavailable(column, error)),
+        };
+        let alloc = &self.alloc;
+        Ok(match owned_column {
+            OwnedColumn::Boolean(col) => Column::Boolean(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::SmallInt(col) => Column::SmallInt(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::Int(col) => Column::Int(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::BigInt(col) => Column::BigInt(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::Int128(col) => Column::Int128(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::Decimal75(precision, scale, col) => {
+                Column::Decimal75(precision, scale, alloc.alloc_slice_copy(&col))
+            }
+            OwnedColumn::Scalar(col) => Column::Scalar(alloc.alloc_slice_copy(&col)),
+            OwnedColumn::VarChar(col) => Column::VarChar((
+                alloc.alloc_slice_fill_iter(col.iter().map(|s| alloc.alloc_str(s) as &str)),
+                alloc.alloc_slice_fill_iter(col.iter().map(Into::into)),
+            )),
+            OwnedColumn::TimestampTZ(tu, tz, col) => {
+                Column::TimestampTZ(tu, tz, alloc.alloc_slice_copy(&col))
+            }
+        })
+    }
+}
@@ -0,0 +1,276 @@
+use super::{
+    owned_table_utility::*, AccessorError, Column, ColumnCipher, ColumnEncryptionError, ColumnRef,
+    ColumnType, CommitmentAccessor, DataAccessor, DecryptingDataAccessor, EncryptedColumn,
+    EncryptedTableCommitment, EncryptedTableStore, MetadataAccessor, OwnedTable, SchemaAccessor,
+    TableRef,
+};
+use crate::base::{commitment::TableCommitment, scalar::Curve25519Scalar};
+use curve25519_dalek::RistrettoPoint;
+use indexmap::{indexmap, IndexMap};
+use proof_of_sql_parser::utility::ident;
+
+/// A toy cipher that xors the plaintext with a keystream derived from the key.
+struct XorCipher {
+    keys: IndexMap<String, [u8; 32]>,
+    key_id: String,
+}
+
+impl XorCipher {
+    fn new(key_id: &str) -> Self {
+        let key = *blake3::hash(key_id.as_bytes()).as_bytes();
+        Self {
+            keys: indexmap! { key_id.to_string() => key },
+            key_id: key_id.to_string(),
+        }
+    }
+
+    fn apply_keystream(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+        let mut keystream = vec![0; data.len()];
+        blake3::Hasher::new_keyed(key)
+            .finalize_xof()
+            .fill(&mut keystream);
+        data.iter().zip(keystream).map(|(a, b)| a ^ b).collect()
+    }
+}
+
+impl ColumnCipher for XorCipher {
+    fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        Self::apply_keystream(&self.keys[&self.key_id], plaintext)
+    }
+
+    fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        Some(Self::apply_keystream(self.keys.get(key_id)?, ciphertext))
+    }
+}
+
+fn table() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("a", [1, 2, 3]),
+        varchar("b", ["x", "y", "z"]),
+        decimal75("c", 10, 2, [4, 5, 6]),
+    ])
+}
+
+fn store_with_table(
+    table_ref: TableRef,
+    cipher: &XorCipher,
+) -> EncryptedTableStore<RistrettoPoint> {
+    let mut store = EncryptedTableStore::new();
+    store.add_table(table_ref, &table(), 2, cipher, &());
+    store
+}
+
+#[test]
+fn we_can_commit_to_the_plaintext_of_an_encrypted_table() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let cipher = XorCipher::new("key_1");
+    let store = store_with_table(table_ref, &cipher);
+
+    let table_commitment = TableCommitment::from_owned_table_with_offset(&table(), 2, &());
+    assert_eq!(store.table_commitment(table_ref), Some(&table_commitment));
+    let column = ColumnRef::new(table_ref, ident("b"), ColumnType::VarChar);
+    assert_eq!(
+        Some(store.get_commitment(column)),
+        table_commitment
+            .column_commitments()
+            .get_commitment(&ident("b"))
+    );
+    assert_eq!(store.get_length(table_ref), 3);
+    assert_eq!(store.get_offset(table_ref), 2);
+    assert_eq!(
+        store.lookup_schema(table_ref),
+        vec![
+            (ident("a"), ColumnType::BigInt),
+            (ident("b"), ColumnType::VarChar),
+            (ident("c"), table()["c"].column_type()),
+        ]
+    );
+    assert_eq!(store.lookup_column(table_ref, ident("d")), None);
+
+    let encrypted_column = store.encrypted_column(table_ref, ident("b")).unwrap();
+    assert_eq!(encrypted_column.key_id(), "key_1");
+    assert_eq!(
+        encrypted_column.plaintext_checksum(),
+        table()["b"].checksum()
+    );
+    assert_ne!(
+        encrypted_column.ciphertext(),
+        postcard::to_allocvec(&table()["b"]).unwrap()
+    );
+}
+
+#[test]
+fn the_key_ids_of_the_columns_are_recorded_with_the_commitment() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let mut store = store_with_table(table_ref, &XorCipher::new("key_1"));
+    let other_table_ref: TableRef = "sxt.u".parse().unwrap();
+    store.add_table(other_table_ref, &table(), 0, &XorCipher::new("key_2"), &());
+
+    let encrypted_table_commitment = store.encrypted_table_commitment(table_ref).unwrap();
+    assert_eq!(
+        encrypted_table_commitment.table_commitment(),
+        store.table_commitment(table_ref).unwrap()
+    );
+    assert_eq!(
+        encrypted_table_commitment.key_ids(),
+        &indexmap! {
+            ident("a") => "key_1".to_string(),
+            ident("b") => "key_1".to_string(),
+            ident("c") => "key_1".to_string(),
+        }
+    );
+    assert_eq!(encrypted_table_commitment.key_id(ident("d")), None);
+    assert_eq!(
+        store
+            .encrypted_table_commitment(other_table_ref)
+            .unwrap()
+            .key_id(ident("b")),
+        Some("key_2")
+    );
+}
+
+#[test]
+fn the_key_ids_of_the_columns_survive_serialization() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let store = store_with_table(table_ref, &XorCipher::new("key_1"));
+    let encrypted_table_commitment = store.encrypted_table_commitment(table_ref).unwrap();
+
+    let bytes = postcard::to_allocvec(encrypted_table_commitment).unwrap();
+    let deserialized: EncryptedTableCommitment<RistrettoPoint> =
+        postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(&deserialized, encrypted_table_commitment);
+    assert_eq!(deserialized.key_id(ident("a")), Some("key_1"));
+
+    let json = serde_json::to_value(encrypted_table_commitment).unwrap();
+    assert_eq!(json["key_ids"]["b"], "key_1");
+    let deserialized: EncryptedTableCommitment<RistrettoPoint> =
+        serde_json::from_value(json).unwrap();
+    assert_eq!(&deserialized, encrypted_table_commitment);
+}
+
+#[test]
+fn we_can_decrypt_the_columns_of_an_encrypted_table_on_read() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let cipher = XorCipher::new("key_1");
+    let store = store_with_table(table_ref, &cipher);
+    let accessor = DecryptingDataAccessor::new(&store, &cipher);
+
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+    assert_eq!(accessor.get_column(column), Column::BigInt(&[1, 2, 3]));
+    let column = ColumnRef::new(table_ref, ident("b"), ColumnType::VarChar);
+    assert!(matches!(
+        accessor.get_column(column),
+        Column::VarChar((["x", "y", "z"], _))
+    ));
+    assert_eq!(accessor.get_length(table_ref), 3);
+    assert_eq!(accessor.get_offset(table_ref), 2);
+    assert_eq!(
+        store.try_decrypt_column(column, &cipher).unwrap(),
+        table()["b"]
+    );
+}
+
+#[test]
+fn we_can_decrypt_columns_encrypted_with_an_older_key() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let old_cipher = XorCipher::new("key_1");
+    let store = store_with_table(table_ref, &old_cipher);
+
+    let mut cipher = XorCipher::new("key_2");
+    cipher.keys.extend(old_cipher.keys);
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+    assert_eq!(
+        store.try_decrypt_column(column, &cipher).unwrap(),
+        table()["a"]
+    );
+}
+
+#[test]
+fn we_cannot_decrypt_a_column_without_its_key() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let store = store_with_table(table_ref, &XorCipher::new("key_1"));
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+    assert_eq!(
+        store.try_decrypt_column(column, &XorCipher::new("key_2")),
+        Err(ColumnEncryptionError::DecryptionFailed {
+            column,
+            key_id: "key_1".to_string(),
+        })
+    );
+    let missing_column = ColumnRef::new(table_ref, ident("d"), ColumnType::BigInt);
+    assert_eq!(
+        store.try_decrypt_column(missing_column, &XorCipher::new("key_1")),
+        Err(ColumnEncryptionError::MissingColumn(missing_column))
+    );
+}
+
+#[test]
+fn we_cannot_decrypt_a_column_with_the_wrong_key() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let store = store_with_table(table_ref, &XorCipher::new("key_1"));
+
+    // A cipher that has a different key under the same id
+    let mut cipher = XorCipher::new("key_1");
+    cipher.keys.insert("key_1".to_string(), [0; 32]);
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+    assert_eq!(
+        store.try_decrypt_column(column, &cipher),
+        Err(ColumnEncryptionError::PlaintextMismatch(column))
+    );
+}
+
+#[test]
+fn we_cannot_decrypt_a_tampered_ciphertext() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let cipher = XorCipher::new("key_1");
+    let store = store_with_table(table_ref, &cipher);
+    let encrypted_column = store.encrypted_column(table_ref, ident("a")).unwrap();
+
+    let mut serialized = serde_json::to_value(encrypted_column).unwrap();
+    let byte = serialized["ciphertext"][0].as_u64().unwrap();
+    serialized["ciphertext"][0] = (byte ^ 1).into();
+    let tampered_column: EncryptedColumn = serde_json::from_value(serialized).unwrap();
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+    assert_eq!(
+        tampered_column.decrypt::<Curve25519Scalar>(column, &cipher),
+        Err(ColumnEncryptionError::CiphertextMismatch(column))
+    );
+}
+
+#[test]
+fn we_get_an_error_instead_of_a_panic_when_the_prover_cannot_decrypt_a_column() {
+    let table_ref: TableRef = "sxt.t".parse().unwrap();
+    let store = store_with_table(table_ref, &XorCipher::new("key_1"));
+    let column = ColumnRef::new(table_ref, ident("a"), ColumnType::BigInt);
+
+    let missing_key = XorCipher::new("key_2");
+    let accessor = DecryptingDataAccessor::new(&store, &missing_key);
+    assert_eq!(
+        accessor.try_get_column(column),
+        Err(AccessorError::column_unavailable(
+            column,
+            ColumnEncryptionError::DecryptionFailed {
+                column,
+                key_id: "key_1".to_string(),
+            }
+        ))
+    );
+
+    let mut wrong_key = XorCipher::new("key_1");
+    wrong_key.keys.insert("key_1".to_string(), [0; 32]);
+    let accessor = DecryptingDataAccessor::new(&store, &wrong_key);
+    assert!(matches!(
+        accessor.try_get_column(column),
+        Err(AccessorError::ColumnUnavailable { .. })
+    ));
+
+    let missing_column = ColumnRef::new(table_ref, ident("d"), ColumnType::BigInt);
+    assert_eq!(
+        accessor.try_get_column(missing_column),
+        Err(AccessorError::column_not_found(missing_column))
+    );
+}
@@ -87,6 +87,9 @@ impl<S: Scalar> OwnedTable<S> {
             BinaryOperator::Subtract => Ok((left - right)?),
             BinaryOperator::Multiply => Ok((left * right)?),
             BinaryOperator::Division => Ok((left / right)?),
+            BinaryOperator::Modulo => Err(ExpressionEvaluationError::Unsupported(
+                "the % operator is only supported in provable expressions".to_string(),
+            )),
         }
     }
 }
@@ -1,12 +1,15 @@
 //! Contains the utility functions for the `GroupByExpr` node.
 
 use crate::base::{
-    database::{filter_util::filter_column_by_index, Column, OwnedColumn},
+    database::{filter_util::filter_column_by_index, Column, ColumnType, OwnedColumn},
+    if_rayon,
     scalar::Scalar,
 };
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+#[cfg(feature = "rayon")]
 use rayon::prelude::ParallelSliceMut;
 use thiserror::Error;
 
@@ -71,8 +74,11 @@ pub fn aggregate_columns<'a, S: Scalar>(
             .filter(|&(_, &b)| b)
             .map(|(i, _)| i),
     );
-    filtered_indexes
-        .par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(group_by_columns_in, a, b));
+    let compare = |&a: &usize, &b: &usize| compare_indexes_by_columns(group_by_columns_in, a, b);
+    if_rayon!(
+        filtered_indexes.par_sort_unstable_by(compare),
+        filtered_indexes.sort_unstable_by(compare)
+    );
 
     // `group_by_result_indexes` gives a single index for each group in `filtered_indexes`. It does
     // not matter which index is chosen for each group, so we choose the first one. This is only used
@@ -115,6 +121,19 @@ pub fn aggregate_columns<'a, S: Scalar>(
     })
 }
 
+/// Whether an aggregation of a column of the given type can be computed after the proof.
+///
+/// This has to agree with the column types that the `*_aggregate_column_by_index_counts`
+/// functions below accept. `FIRST` is not supported at all.
+pub(crate) fn is_aggregation_supported(op: AggregationOperator, column_type: ColumnType) -> bool {
+    match op {
+        AggregationOperator::Sum => column_type.is_numeric(),
+        AggregationOperator::Max | AggregationOperator::Min => column_type != ColumnType::VarChar,
+        AggregationOperator::Count => true,
+        AggregationOperator::First => false,
+    }
+}
+
 /// Returns a slice with the lifetime of `alloc` that contains the grouped sums of `column`.
 /// The `counts` slice contains the number of elements in each group and the `indexes` slice
 /// contains the indexes of the elements in `column`.
@@ -0,0 +1,181 @@
+use super::{ColumnType, LiteralValue, OwnedColumn, OwnedTable};
+use crate::base::{
+    commitment::{ColumnCommitmentsMismatch, Commitment, TableCommitment},
+    scalar::Scalar,
+};
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+use std::ops::Range;
+use thiserror::Error;
+
+/// Errors that can occur when assembling an [`IngestBatch`].
+#[derive(Debug, Error)]
+pub enum IngestBatchError {
+    /// A row does not have a value for every column
+    #[error("row {row} has {actual} values, but the table has {expected} columns")]
+    RowLength {
+        /// The index of the row in the batch
+        row: usize,
+        /// The number of columns of the table
+        expected: usize,
+        /// The number of values of the row
+        actual: usize,
+    },
+    /// A value does not have the type of its column
+    #[error(
+        "the value of {column} in row {row} has type {actual}, but the column has type {expected}"
+    )]
+    ValueType {
+        /// The index of the row in the batch
+        row: usize,
+        /// The column of the value
+        column: Identifier,
+        /// The type of the column
+        expected: ColumnType,
+        /// The type of the value
+        actual: ColumnType,
+    },
+    /// The rows cannot be appended to the commitment
+    #[error(transparent)]
+    ColumnCommitmentsMismatch(#[from] ColumnCommitmentsMismatch),
+}
+
+/// Rows to append to a table, along with the commitment to them.
+///
+/// Ingestion has to append the same rows, at the same offset, to both the table and the
+/// commitment to it. Rather than leaving this to the caller, this takes typed rows and turns them
+/// into both the [`OwnedTable`] chunk and the [`TableCommitment`] delta at once. The delta starts
+/// where the commitment that the rows are appended to ends, and can be added to it with
+/// [`TableCommitment::try_add`].
+pub struct IngestBatch<C: Commitment> {
+    table: OwnedTable<C::Scalar>,
+    delta: TableCommitment<C>,
+}
+
+impl<C: Commitment> IngestBatch<C> {
+    /// Assemble rows to append to the table of the given commitment.
+    ///
+    /// The values of each row have to be in the order, and of the types, of the columns of the
+    /// commitment. VarChar values are normalized like the existing rows of their column.
+    pub fn try_new(
+        commitment: &TableCommitment<C>,
+        rows: impl IntoIterator<Item = Vec<LiteralValue<C::Scalar>>>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<Self, IngestBatchError> {
+        let schema = commitment
+            .column_commitments()
+            .column_metadata()
+            .iter()
+            .map(|(identifier, metadata)| (*identifier, *metadata.column_type()))
+            .collect();
+        let table = try_table_from_rows(&schema, rows)?;
+        let delta = commitment.try_owned_table_delta(&table, setup)?;
+        Ok(Self { table, delta })
+    }
+
+    /// Assemble the first rows of a new table with the given schema, which start at the given
+    /// row offset.
+    pub fn try_new_with_offset(
+        schema: &IndexMap<Identifier, ColumnType>,
+        rows: impl IntoIterator<Item = Vec<LiteralValue<C::Scalar>>>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<Self, IngestBatchError> {
+        let table = try_table_from_rows(schema, rows)?;
+        let delta = TableCommitment::from_owned_table_with_offset(&table, offset, setup);
+        Ok(Self { table, delta })
+    }
+
+    /// Returns the rows of the batch.
+    pub fn table(&self) -> &OwnedTable<C::Scalar> {
+        &self.table
+    }
+
+    /// Returns the commitment to the rows of the batch.
+    pub fn delta(&self) -> &TableCommitment<C> {
+        &self.delta
+    }
+
+    /// Returns the range of rows of the table that the batch is appended as.
+    pub fn range(&self) -> &Range<usize> {
+        self.delta.range()
+    }
+
+    /// Returns the rows of the batch and the commitment to them.
+    pub fn into_parts(self) -> (OwnedTable<C::Scalar>, TableCommitment<C>) {
+        (self.table, self.delta)
+    }
+}
+
+/// Collects rows of values into a table with the given schema.
+fn try_table_from_rows<S: Scalar>(
+    schema: &IndexMap<Identifier, ColumnType>,
+    rows: impl IntoIterator<Item = Vec<LiteralValue<S>>>,
+) -> Result<OwnedTable<S>, IngestBatchError> {
+    let mut columns: Vec<_> = schema
+        .values()
+        .map(|&column_type| empty_column(column_type))
+        .collect();
+    for (row, values) in rows.into_iter().enumerate() {
+        if values.len() != schema.len() {
+            return Err(IngestBatchError::RowLength {
+                row,
+                expected: schema.len(),
+                actual: values.len(),
+            });
+        }
+        for ((column, (&identifier, &expected)), value) in
+            columns.iter_mut().zip(schema).zip(values)
+        {
+            let actual = value.column_type();
+            if actual != expected {
+                return Err(IngestBatchError::ValueType {
+                    row,
+                    column: identifier,
+                    expected,
+                    actual,
+                });
+            }
+            push_value(column, value);
+        }
+    }
+    let columns = schema.keys().copied().zip(columns).collect();
+    Ok(OwnedTable::try_new(columns).expect("every column has a value for every row"))
+}
+
+/// Returns a column of the given type without rows.
+fn empty_column<S: Scalar>(column_type: ColumnType) -> OwnedColumn<S> {
+    match column_type {
+        ColumnType::Boolean => OwnedColumn::Boolean(Vec::new()),
+        ColumnType::SmallInt => OwnedColumn::SmallInt(Vec::new()),
+        ColumnType::Int => OwnedColumn::Int(Vec::new()),
+        ColumnType::BigInt => OwnedColumn::BigInt(Vec::new()),
+        ColumnType::Int128 => OwnedColumn::Int128(Vec::new()),
+        ColumnType::Decimal75(precision, scale) => {
+            OwnedColumn::Decimal75(precision, scale, Vec::new())
+        }
+        ColumnType::Scalar => OwnedColumn::Scalar(Vec::new()),
+        ColumnType::VarChar => OwnedColumn::VarChar(Vec::new()),
+        ColumnType::TimestampTZ(tu, tz) => OwnedColumn::TimestampTZ(tu, tz, Vec::new()),
+    }
+}
+
+/// Appends a value of the same type to a column.
+fn push_value<S: Scalar>(column: &mut OwnedColumn<S>, value: LiteralValue<S>) {
+    match (column, value) {
+        (OwnedColumn::Boolean(col), LiteralValue::Boolean(value)) => col.push(value),
+        (OwnedColumn::SmallInt(col), LiteralValue::SmallInt(value)) => col.push(value),
+        (OwnedColumn::Int(col), LiteralValue::Int(value)) => col.push(value),
+        (OwnedColumn::BigInt(col), LiteralValue::BigInt(value)) => col.push(value),
+        (OwnedColumn::VarChar(col), LiteralValue::VarChar((value, _))) => col.push(value),
+        (OwnedColumn::Int128(col), LiteralValue::Int128(value)) => col.push(value),
+        (OwnedColumn::Decimal75(_, _, col), LiteralValue::Decimal75(_, _, value)) => {
+            col.push(value);
+        }
+        (OwnedColumn::Scalar(col), LiteralValue::Scalar(value)) => col.push(value),
+        (OwnedColumn::TimestampTZ(_, _, col), LiteralValue::TimeStampTZ(_, _, value)) => {
+            col.push(value);
+        }
+        _ => panic!("the type of the value has already been checked"),
+    }
+}
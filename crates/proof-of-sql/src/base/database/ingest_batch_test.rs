@@ -0,0 +1,117 @@
+#[cfg(feature = "varchar-normalization")]
+use super::VarCharNormalization;
+use super::{
+    owned_table_utility::*, ColumnType, IngestBatch, IngestBatchError, LiteralValue, OwnedTable,
+};
+use crate::base::{commitment::TableCommitment, scalar::Curve25519Scalar};
+use curve25519_dalek::RistrettoPoint;
+use indexmap::IndexMap;
+use proof_of_sql_parser::{utility::ident, Identifier};
+
+fn row(a: i64, b: &str) -> Vec<LiteralValue<Curve25519Scalar>> {
+    vec![
+        LiteralValue::BigInt(a),
+        LiteralValue::VarChar((b.to_string(), b.into())),
+    ]
+}
+
+fn rows(a: &[i64], b: &[&str]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([bigint("a", a.to_vec()), varchar("b", b.to_vec())])
+}
+
+fn schema() -> IndexMap<Identifier, ColumnType> {
+    IndexMap::from_iter([
+        (ident("a"), ColumnType::BigInt),
+        (ident("b"), ColumnType::VarChar),
+    ])
+}
+
+#[test]
+fn we_can_ingest_rows_as_a_table_chunk_and_a_commitment_delta() {
+    let first =
+        IngestBatch::<RistrettoPoint>::try_new_with_offset(&schema(), [row(1, "x")], 3, &())
+            .unwrap();
+    assert_eq!(first.table(), &rows(&[1], &["x"]));
+    assert_eq!(first.range(), &(3..4));
+    let (_, commitment) = first.into_parts();
+
+    let batch = IngestBatch::try_new(&commitment, [row(2, "y"), row(3, "z")], &()).unwrap();
+    assert_eq!(batch.table(), &rows(&[2, 3], &["y", "z"]));
+    assert_eq!(batch.range(), &(4..6));
+    assert_eq!(
+        batch.delta(),
+        &TableCommitment::from_owned_table_with_offset(batch.table(), 4, &())
+    );
+    assert_eq!(
+        commitment.try_add(batch.delta().clone()).unwrap(),
+        TableCommitment::from_owned_table_with_offset(&rows(&[1, 2, 3], &["x", "y", "z"]), 3, &())
+    );
+}
+
+#[test]
+#[cfg(feature = "varchar-normalization")]
+fn the_commitment_delta_inherits_the_metadata_of_the_commitment() {
+    let mut commitment =
+        TableCommitment::<RistrettoPoint>::from_owned_table_with_offset_and_varchar_normalization(
+            &rows(&[1], &["x"]),
+            0,
+            VarCharNormalization::Nfkc,
+            &(),
+        );
+    let batch = IngestBatch::try_new(&commitment, [row(2, "ﬁ")], &()).unwrap();
+    assert_eq!(batch.table(), &rows(&[2], &["ﬁ"]));
+    let with_delta = commitment.clone().try_add(batch.delta().clone()).unwrap();
+    commitment.append_owned_table(batch.table(), &()).unwrap();
+    assert_eq!(with_delta, commitment);
+}
+
+#[test]
+fn we_cannot_ingest_rows_that_do_not_match_the_schema() {
+    let commitment = TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(
+        &rows(&[1], &["x"]),
+        0,
+        &(),
+    );
+    assert!(matches!(
+        IngestBatch::try_new(
+            &commitment,
+            [row(2, "y"), vec![LiteralValue::BigInt(3)]],
+            &()
+        ),
+        Err(IngestBatchError::RowLength {
+            row: 1,
+            expected: 2,
+            actual: 1
+        })
+    ));
+    let swapped = vec![
+        LiteralValue::VarChar(("y".to_string(), "y".into())),
+        LiteralValue::BigInt(2),
+    ];
+    match IngestBatch::try_new(&commitment, [swapped], &()) {
+        Err(IngestBatchError::ValueType {
+            row,
+            column,
+            expected,
+            actual,
+        }) => {
+            assert_eq!(row, 0);
+            assert_eq!(column, ident("a"));
+            assert_eq!(expected, ColumnType::BigInt);
+            assert_eq!(actual, ColumnType::VarChar);
+        }
+        _ => panic!("expected a value of the wrong type"),
+    }
+    assert!(matches!(
+        IngestBatch::<RistrettoPoint>::try_new_with_offset(
+            &schema(),
+            [vec![
+                LiteralValue::Int(1),
+                LiteralValue::VarChar(("x".to_string(), "x".into()))
+            ]],
+            0,
+            &()
+        ),
+        Err(IngestBatchError::ValueType { row: 0, .. })
+    ));
+}
@@ -0,0 +1,288 @@
+use super::{
+    AccessorError, AccessorResult, Column, ColumnRef, ColumnType, DataAccessor, MetadataAccessor,
+    OwnedColumn, SchemaAccessor, TableRef,
+};
+use crate::base::scalar::Scalar;
+use bytemuck::Pod;
+use core::{mem::size_of, slice};
+use indexmap::IndexMap;
+use memmap2::Mmap;
+use proof_of_sql_parser::Identifier;
+use std::{borrow::Cow, fs, fs::File, io, path::Path};
+use thiserror::Error;
+
+/// Errors that can occur when memory-mapping the column files of an [`MmapAccessor`].
+#[derive(Error, Debug)]
+pub enum MmapAccessorError {
+    /// The column file could not be read, written or mapped
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Only columns whose values have a fixed width can be memory-mapped
+    #[error("columns of type {0} cannot be memory-mapped")]
+    UnsupportedColumnType(ColumnType),
+    /// The size of the column file is not a multiple of the size of a value
+    #[error("the file of column {column_id} is not a whole number of {column_type} values")]
+    InvalidFileLength {
+        /// The column whose file was mapped
+        column_id: Identifier,
+        /// The type of the column
+        column_type: ColumnType,
+    },
+    /// A byte of a boolean column file is neither 0 nor 1
+    #[error("the file of boolean column {0} holds bytes other than 0 and 1")]
+    InvalidBoolean(Identifier),
+    /// The columns of a table have different numbers of rows
+    #[error("column {column_id} has {actual} rows rather than the {expected} rows of its table")]
+    RowCountMismatch {
+        /// The column whose file was mapped
+        column_id: Identifier,
+        /// The number of rows of the previous columns of the table
+        expected: usize,
+        /// The number of rows in the column file
+        actual: usize,
+    },
+}
+
+/// The size in bytes of a value of a column type, for the types that can be memory-mapped.
+fn value_size(column_type: ColumnType) -> Option<usize> {
+    match column_type {
+        ColumnType::Boolean => Some(size_of::<bool>()),
+        ColumnType::SmallInt => Some(size_of::<i16>()),
+        ColumnType::Int => Some(size_of::<i32>()),
+        ColumnType::BigInt | ColumnType::TimestampTZ(_, _) => Some(size_of::<i64>()),
+        ColumnType::Int128 => Some(size_of::<i128>()),
+        _ => None,
+    }
+}
+
+/// Reinterprets the bytes of a column file as its values.
+///
+/// Mapped files are page aligned, but an empty file is not mapped at all.
+fn cast_values<T: Pod>(bytes: &[u8]) -> &[T] {
+    if bytes.is_empty() {
+        &[]
+    } else {
+        bytemuck::cast_slice(bytes)
+    }
+}
+
+/// A memory-mapped column file.
+struct MmapColumn {
+    column_type: ColumnType,
+    num_rows: usize,
+    mmap: Option<Mmap>,
+}
+
+impl MmapColumn {
+    /// Map a column file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified while it is mapped, see [`MmapAccessor::try_add_table`].
+    unsafe fn try_map(
+        column_id: Identifier,
+        column_type: ColumnType,
+        path: &Path,
+    ) -> Result<Self, MmapAccessorError> {
+        let value_size =
+            value_size(column_type).ok_or(MmapAccessorError::UnsupportedColumnType(column_type))?;
+        let file = File::open(path)?;
+        // SAFETY: the caller guarantees that the file is not modified while it is mapped
+        let mmap = (file.metadata()?.len() > 0)
+            .then(|| unsafe { Mmap::map(&file) })
+            .transpose()?;
+        let bytes = mmap.as_deref().unwrap_or_default();
+        if bytes.len() % value_size != 0 {
+            return Err(MmapAccessorError::InvalidFileLength {
+                column_id,
+                column_type,
+            });
+        }
+        if column_type == ColumnType::Boolean && bytes.iter().any(|&byte| byte > 1) {
+            return Err(MmapAccessorError::InvalidBoolean(column_id));
+        }
+        Ok(Self {
+            column_type,
+            num_rows: bytes.len() / value_size,
+            mmap,
+        })
+    }
+
+    fn column<S: Scalar>(&self) -> Column<S> {
+        let bytes = self.mmap.as_deref().unwrap_or_default();
+        match self.column_type {
+            ColumnType::Boolean => {
+                // SAFETY: every byte was checked to be 0 or 1 when the file was mapped, and the
+                // file is not modified while it is mapped
+                Column::Boolean(unsafe {
+                    slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len())
+                })
+            }
+            ColumnType::SmallInt => Column::SmallInt(cast_values(bytes)),
+            ColumnType::Int => Column::Int(cast_values(bytes)),
+            ColumnType::BigInt => Column::BigInt(cast_values(bytes)),
+            ColumnType::Int128 => Column::Int128(cast_values(bytes)),
+            ColumnType::TimestampTZ(tu, tz) => Column::TimestampTZ(tu, tz, cast_values(bytes)),
+            _ => unreachable!("only columns of fixed-width types are mapped"),
+        }
+    }
+}
+
+struct MmapTable {
+    num_rows: usize,
+    offset: usize,
+    columns: IndexMap<Identifier, MmapColumn>,
+}
+
+/// A [`DataAccessor`] that memory-maps a file for each column, rather than reading the columns
+/// into memory.
+///
+/// The prover reads the columns without copying them, so the operating system pages the data in
+/// as the prover touches it and can page it out again. This makes it possible to prove queries
+/// over tables that are larger than the memory of the prover.
+///
+/// A column file holds the values of the column back to back, in the native byte order, and a
+/// boolean as a single byte that is 0 or 1. Such a file can be written with
+/// [`MmapAccessor::write_column_file`]. Only columns of fixed-width types can be mapped, i.e.
+/// booleans, integers and timestamps.
+///
+/// The column files must not be modified while they are mapped, since the accessor hands out
+/// the mapped memory as plain slices. This is why mapping them is unsafe.
+#[derive(Default)]
+pub struct MmapAccessor {
+    tables: IndexMap<TableRef, MmapTable>,
+}
+
+impl MmapAccessor {
+    /// Create a new accessor without any tables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map the column files of a table whose rows start at the given offset.
+    ///
+    /// All of the columns need to have the same number of rows. A table that already exists is
+    /// replaced.
+    ///
+    /// # Safety
+    ///
+    /// The column files must not be modified, truncated or replaced in place until the accessor
+    /// and every column that was borrowed from it are dropped. Otherwise the slices that the
+    /// accessor hands out may change under the prover, or a boolean column may hold bytes other
+    /// than 0 and 1, which is undefined behavior.
+    pub unsafe fn try_add_table<P: AsRef<Path>>(
+        &mut self,
+        table_ref: TableRef,
+        columns: impl IntoIterator<Item = (Identifier, ColumnType, P)>,
+        offset: usize,
+    ) -> Result<(), MmapAccessorError> {
+        let mut num_rows = None;
+        let mut mapped_columns = IndexMap::new();
+        for (column_id, column_type, path) in columns {
+            // SAFETY: the caller guarantees that the files are not modified while they are mapped
+            let column = unsafe { MmapColumn::try_map(column_id, column_type, path.as_ref())? };
+            let expected = *num_rows.get_or_insert(column.num_rows);
+            if column.num_rows != expected {
+                return Err(MmapAccessorError::RowCountMismatch {
+                    column_id,
+                    expected,
+                    actual: column.num_rows,
+                });
+            }
+            mapped_columns.insert(column_id, column);
+        }
+        self.tables.insert(
+            table_ref,
+            MmapTable {
+                num_rows: num_rows.unwrap_or(0),
+                offset,
+                columns: mapped_columns,
+            },
+        );
+        Ok(())
+    }
+
+    /// Write a column to a file in the format that [`MmapAccessor::try_add_table`] maps.
+    pub fn write_column_file<S: Scalar>(
+        path: impl AsRef<Path>,
+        column: &OwnedColumn<S>,
+    ) -> Result<(), MmapAccessorError> {
+        let bytes: Cow<[u8]> = match column {
+            OwnedColumn::Boolean(col) => col.iter().map(|&b| u8::from(b)).collect(),
+            OwnedColumn::SmallInt(col) => Cow::Borrowed(bytemuck::cast_slice(col)),
+            OwnedColumn::Int(col) => Cow::Borrowed(bytemuck::cast_slice(col)),
+            OwnedColumn::BigInt(col) | OwnedColumn::TimestampTZ(_, _, col) => {
+                Cow::Borrowed(bytemuck::cast_slice(col))
+            }
+            OwnedColumn::Int128(col) => Cow::Borrowed(bytemuck::cast_slice(col)),
+            _ => {
+                return Err(MmapAccessorError::UnsupportedColumnType(
+                    column.column_type(),
+                ))
+            }
+        };
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    fn try_get_table(&self, table_ref: TableRef) -> AccessorResult<&MmapTable> {
+        self.tables
+            .get(&table_ref)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+}
+
+impl MetadataAccessor for MmapAccessor {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.try_get_length(table_ref).unwrap()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.try_get_offset(table_ref).unwrap()
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_table(table_ref)?.num_rows)
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_table(table_ref)?.offset)
+    }
+}
+
+impl<S: Scalar> DataAccessor<S> for MmapAccessor {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        self.try_get_column(column).unwrap()
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<S>> {
+        Ok(self
+            .try_get_table(column.table_ref())?
+            .columns
+            .get(&column.column_id())
+            .ok_or_else(|| AccessorError::column_not_found(column))?
+            .column())
+    }
+}
+
+impl SchemaAccessor for MmapAccessor {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        Some(
+            self.tables
+                .get(&table_ref)?
+                .columns
+                .get(&column_id)?
+                .column_type,
+        )
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.tables
+            .get(&table_ref)
+            .unwrap()
+            .columns
+            .iter()
+            .map(|(&id, column)| (id, column.column_type))
+            .collect()
+    }
+}
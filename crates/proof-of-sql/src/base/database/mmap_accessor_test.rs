@@ -0,0 +1,177 @@
+use super::{
+    owned_table_utility::*, AccessorError, ColumnRef, ColumnType, DataAccessor, MetadataAccessor,
+    MmapAccessor, MmapAccessorError, OwnedTable, OwnedTableTestAccessor, SchemaAccessor, TableRef,
+};
+use crate::{
+    base::{commitment::InnerProductProof, scalar::Curve25519Scalar},
+    sql::{ast::test_utility::*, proof::VerifiableQueryResult},
+};
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    utility::ident,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A fresh directory for the column files of a test.
+fn column_dir(test_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("posql-mmap-{}-{test_name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Write the columns of the table to files and map them.
+fn map_table(
+    accessor: &mut MmapAccessor,
+    dir: &Path,
+    table_ref: TableRef,
+    table: &OwnedTable<Curve25519Scalar>,
+    offset: usize,
+) -> Result<(), MmapAccessorError> {
+    let mut columns = Vec::new();
+    for (&column_id, column) in table.inner_table() {
+        let path = dir.join(column_id.as_str());
+        MmapAccessor::write_column_file(&path, column)?;
+        columns.push((column_id, column.column_type(), path));
+    }
+    // SAFETY: the files of each test are only written before they are mapped
+    unsafe { accessor.try_add_table(table_ref, columns, offset) }
+}
+
+fn sample_table(num_rows: i64) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        boolean("a", (0..num_rows).map(|i| i % 2 == 0)),
+        smallint("b", (0..num_rows).map(|i| i as i16)),
+        int("c", (0..num_rows).map(|i| i as i32 * 3)),
+        bigint("d", (0..num_rows).map(|i| i % 5)),
+        int128("e", (0..num_rows).map(|i| i as i128 - 4)),
+        timestamptz("f", PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, 0..num_rows),
+    ])
+}
+
+#[test]
+fn we_can_map_the_columns_of_a_table() {
+    let dir = column_dir("map");
+    let t = "sxt.t".parse().unwrap();
+    let table = sample_table(10);
+    let mut accessor = MmapAccessor::new();
+    map_table(&mut accessor, &dir, t, &table, 3).unwrap();
+    let expected = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, table, 3, ());
+
+    assert_eq!(accessor.get_length(t), 10);
+    assert_eq!(accessor.get_offset(t), 3);
+    assert_eq!(accessor.lookup_schema(t), expected.lookup_schema(t));
+    for (column_id, column_type) in expected.lookup_schema(t) {
+        assert_eq!(accessor.lookup_column(t, column_id), Some(column_type));
+        let column = ColumnRef::new(t, column_id, column_type);
+        assert_eq!(
+            DataAccessor::<Curve25519Scalar>::get_column(&accessor, column),
+            expected.get_column(column)
+        );
+    }
+
+    let empty_table = sample_table(0);
+    map_table(&mut accessor, &dir, t, &empty_table, 0).unwrap();
+    assert_eq!(accessor.get_length(t), 0);
+    let column = ColumnRef::new(t, ident("d"), ColumnType::BigInt);
+    assert!(DataAccessor::<Curve25519Scalar>::get_column(&accessor, column).is_empty());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn we_can_prove_a_query_over_mapped_columns() {
+    let dir = column_dir("prove");
+    let t = "sxt.t".parse().unwrap();
+    let table = sample_table(20);
+    let mut accessor = MmapAccessor::new();
+    map_table(&mut accessor, &dir, t, &table, 0).unwrap();
+    let expected = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, table, 0, ());
+
+    let expr = dense_filter(
+        cols_expr_plan(t, &["b", "e", "f"], &accessor),
+        tab(t),
+        and(
+            equal(column(t, "d", &accessor), const_bigint(1)),
+            column(t, "a", &accessor),
+        ),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert_eq!(
+        postcard::to_allocvec(&res).unwrap(),
+        postcard::to_allocvec(&VerifiableQueryResult::<InnerProductProof>::new(
+            &expr,
+            &expected,
+            &()
+        ))
+        .unwrap()
+    );
+    let table = res.verify(&expr, &expected, &()).unwrap().table;
+    assert_eq!(
+        table,
+        owned_table([
+            smallint("b", [6_i16, 16]),
+            int128("e", [2_i128, 12]),
+            timestamptz("f", PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, [6, 16]),
+        ])
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn we_cannot_map_invalid_column_files() {
+    let dir = column_dir("invalid");
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = MmapAccessor::new();
+
+    let strings = owned_table([varchar("s", ["a", "b"])]);
+    assert!(matches!(
+        map_table(&mut accessor, &dir, t, &strings, 0),
+        Err(MmapAccessorError::UnsupportedColumnType(
+            ColumnType::VarChar
+        ))
+    ));
+
+    // SAFETY: none of these files stay mapped, since mapping them fails
+    let path = dir.join("x");
+    fs::write(&path, [1_u8, 2, 3]).unwrap();
+    assert!(matches!(
+        unsafe { accessor.try_add_table(t, [(ident("x"), ColumnType::SmallInt, &path)], 0) },
+        Err(MmapAccessorError::InvalidFileLength { .. })
+    ));
+    assert!(matches!(
+        unsafe { accessor.try_add_table(t, [(ident("x"), ColumnType::Boolean, &path)], 0) },
+        Err(MmapAccessorError::InvalidBoolean(_))
+    ));
+    assert!(matches!(
+        unsafe {
+            accessor.try_add_table(t, [(ident("x"), ColumnType::Int, dir.join("missing"))], 0)
+        },
+        Err(MmapAccessorError::Io(_))
+    ));
+
+    let mismatched = [
+        (ident("x"), ColumnType::Boolean, dir.join("y")),
+        (ident("y"), ColumnType::Boolean, dir.join("z")),
+    ];
+    fs::write(&mismatched[0].2, [1_u8, 0]).unwrap();
+    fs::write(&mismatched[1].2, [1_u8, 0, 1]).unwrap();
+    assert!(matches!(
+        unsafe { accessor.try_add_table(t, mismatched, 0) },
+        Err(MmapAccessorError::RowCountMismatch {
+            expected: 2,
+            actual: 3,
+            ..
+        })
+    ));
+
+    // None of the tables were added
+    let column = ColumnRef::new(t, ident("x"), ColumnType::Boolean);
+    assert_eq!(
+        DataAccessor::<Curve25519Scalar>::try_get_column(&accessor, column),
+        Err(AccessorError::TableNotFound { table_ref: t })
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -4,6 +4,29 @@
 mod accessor;
 pub use accessor::{CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAccessor};
 
+mod accessor_error;
+pub use accessor_error::{AccessorError, AccessorResult};
+
+mod async_data_accessor;
+pub use async_data_accessor::{AsyncDataAccessor, AsyncDataAccessorError, FetchedDataAccessor};
+#[cfg(all(test, feature = "blitzar"))]
+mod async_data_accessor_test;
+
+mod encrypted_table_store;
+pub use encrypted_table_store::{
+    ColumnCipher, ColumnEncryptionError, DecryptingDataAccessor, EncryptedColumn,
+    EncryptedTableCommitment, EncryptedTableStore,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod encrypted_table_store_test;
+
+mod compressed_column;
+pub use compressed_column::{
+    ColumnCodec, CompressedColumn, CompressedTable, CompressedTableStore, DecompressingDataAccessor,
+};
+#[cfg(test)]
+mod compressed_column_test;
+
 mod column;
 pub use column::{Column, ColumnField, ColumnRef, ColumnType};
 
@@ -21,6 +44,32 @@ pub use literal_value::LiteralValue;
 mod table_ref;
 pub use table_ref::TableRef;
 
+mod append_only_table;
+pub use append_only_table::AppendOnlyTable;
+#[cfg(all(test, feature = "blitzar"))]
+mod append_only_table_test;
+
+mod ingest_batch;
+pub use ingest_batch::{IngestBatch, IngestBatchError};
+#[cfg(all(test, feature = "blitzar"))]
+mod ingest_batch_test;
+
+mod snapshot_store;
+pub use snapshot_store::{SnapshotAccessor, SnapshotStore, SnapshotStoreError};
+#[cfg(all(test, feature = "blitzar"))]
+mod snapshot_store_test;
+
+mod varchar_normalization;
+pub use varchar_normalization::VarCharNormalization;
+
+mod retraction;
+pub use retraction::{
+    insertion_rows, lookup_multiplicity_column, multiplicity_column_id, retraction_rows,
+    RetractionError, MULTIPLICITY_COLUMN_NAME,
+};
+#[cfg(test)]
+mod retraction_test;
+
 mod arrow_array_to_column_conversion;
 pub use arrow_array_to_column_conversion::{ArrayRefExt, ArrowArrayToColumnConversionError};
 
@@ -51,6 +100,7 @@ pub use owned_table::OwnedTable;
 pub(crate) use owned_table::OwnedTableError;
 #[cfg(test)]
 mod owned_table_test;
+#[cfg(any(test, feature = "test"))]
 pub mod owned_table_utility;
 
 pub(crate) mod expression_evaluation;
@@ -59,11 +109,30 @@ mod expression_evaluation_error;
 mod expression_evaluation_test;
 pub use expression_evaluation_error::{ExpressionEvaluationError, ExpressionEvaluationResult};
 
+mod computed_column;
+pub use computed_column::{substitute_computed_columns, ComputedColumn, ComputedColumnError};
+#[cfg(test)]
+mod computed_column_test;
+
 mod owned_and_arrow_conversions;
 pub use owned_and_arrow_conversions::OwnedArrowConversionError;
 #[cfg(test)]
 mod owned_and_arrow_conversions_test;
 
+#[cfg(feature = "mmap")]
+mod mmap_accessor;
+#[cfg(feature = "mmap")]
+pub use mmap_accessor::{MmapAccessor, MmapAccessorError};
+#[cfg(all(test, feature = "mmap", feature = "blitzar"))]
+mod mmap_accessor_test;
+
+#[cfg(feature = "polars-conversions")]
+mod owned_and_polars_conversions;
+#[cfg(feature = "polars-conversions")]
+pub use owned_and_polars_conversions::OwnedPolarsConversionError;
+#[cfg(all(test, feature = "polars-conversions"))]
+mod owned_and_polars_conversions_test;
+
 #[cfg(any(test, feature = "test"))]
 mod test_accessor;
 #[cfg(any(test, feature = "test"))]
@@ -79,7 +148,7 @@ pub(crate) use test_schema_accessor::TestSchemaAccessor;
 #[cfg(any(test, feature = "test"))]
 mod owned_table_test_accessor;
 #[cfg(any(test, feature = "test"))]
-pub use owned_table_test_accessor::OwnedTableTestAccessor;
+pub use owned_table_test_accessor::{OwnedTableTestAccessor, OwnedTableTestAccessorState};
 #[cfg(all(test, feature = "blitzar"))]
 mod owned_table_test_accessor_test;
 /// Contains traits for scalar <-> i256 conversions
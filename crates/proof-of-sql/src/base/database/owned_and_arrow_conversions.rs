@@ -34,7 +34,7 @@ use arrow::{
 use indexmap::IndexMap;
 use proof_of_sql_parser::{
     posql_time::{PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestampError},
-    Identifier, ParseError,
+    IdentifierPolicy, ParseError,
 };
 use std::sync::Arc;
 use thiserror::Error;
@@ -255,15 +255,25 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
 impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
     type Error = OwnedArrowConversionError;
     fn try_from(value: RecordBatch) -> Result<Self, Self::Error> {
+        Self::try_from_record_batch_with_policy(value, IdentifierPolicy::default())
+    }
+}
+
+impl<S: Scalar> OwnedTable<S> {
+    /// Convert a record batch to a table, using the policy to turn field names into identifiers.
+    pub fn try_from_record_batch_with_policy(
+        value: RecordBatch,
+        policy: IdentifierPolicy,
+    ) -> Result<Self, OwnedArrowConversionError> {
         let num_columns = value.num_columns();
-        let table: Result<IndexMap<_, _>, Self::Error> = value
+        let table: Result<IndexMap<_, _>, OwnedArrowConversionError> = value
             .schema()
             .fields()
             .iter()
             .zip(value.columns())
             .map(|(field, array_ref)| {
                 let owned_column = OwnedColumn::try_from(array_ref)?;
-                let identifier = Identifier::try_new(field.name())?; //This may always succeed.
+                let identifier = policy.identifier(field.name())?;
                 Ok((identifier, owned_column))
             })
             .collect();
@@ -12,6 +12,7 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use indexmap::IndexMap;
+use proof_of_sql_parser::IdentifierPolicy;
 use std::sync::Arc;
 
 fn we_can_convert_between_owned_column_and_array_ref_impl(
@@ -129,6 +130,24 @@ fn we_can_convert_between_owned_table_and_record_batch() {
     );
 }
 
+#[test]
+fn we_can_convert_a_record_batch_with_case_sensitive_column_names() {
+    let record_batch = record_batch!(
+        "a" => [1_i64],
+        "A" => [2_i64],
+        "timestamp" => [3_i64],
+    );
+    let table = OwnedTable::<Curve25519Scalar>::try_from_record_batch_with_policy(
+        record_batch.clone(),
+        IdentifierPolicy::Exact,
+    )
+    .unwrap();
+    assert_eq!(table["a"], OwnedColumn::BigInt(vec![1]));
+    assert_eq!(table[r#""A""#], OwnedColumn::BigInt(vec![2]));
+    assert_eq!(table[r#""timestamp""#], OwnedColumn::BigInt(vec![3]));
+    assert_eq!(RecordBatch::try_from(table).unwrap(), record_batch);
+}
+
 #[test]
 fn we_cannot_convert_a_record_batch_if_it_has_repeated_column_names() {
     let record_batch = record_batch!(
@@ -0,0 +1,225 @@
+//! This module provides conversions between polars and owned types.
+//! The mapping is as follows:
+//! OwnedTable <-> DataFrame
+//! Boolean <-> Boolean
+//! SmallInt <-> Int16
+//! Int <-> Int32
+//! BigInt <-> Int64
+//! VarChar <-> Utf8
+//! Int128 <-> Decimal(38, 0)
+//! Decimal75 <-> Decimal(precision, scale)
+//! TimestampTZ <-> Datetime
+//!
+//! Note: polars decimals are backed by `i128`, so only `Decimal75` columns with a precision of at
+//! most 38 and a non-negative scale can be converted.
+//! Polars has no timestamps in seconds, so `TimestampTZ` columns in seconds are converted to
+//! milliseconds. Likewise, a `Decimal75` column with a precision of 38 and a scale of 0 converts
+//! back to an `Int128` column.
+use super::{
+    scalar_and_i256_conversions::convert_scalar_to_i256, ColumnType, OwnedColumn, OwnedTable,
+    OwnedTableError,
+};
+use crate::base::{math::decimal::Precision, scalar::Scalar};
+use polars::{
+    datatypes::{DataType, TimeUnit},
+    error::PolarsError,
+    frame::DataFrame,
+    prelude::{ChunkedArray, Int128Chunked, Int64Chunked, NamedFrom, PolarsNumericType},
+    series::{IntoSeries, Series},
+};
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestampError},
+    IdentifierPolicy, ParseError,
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+/// Errors caused by conversions between polars and owned types.
+pub enum OwnedPolarsConversionError {
+    /// This error occurs when trying to convert from an unsupported polars type.
+    #[error("unsupported type: attempted conversion from Series of type {0} to OwnedColumn")]
+    UnsupportedPolarsType(DataType),
+    /// This error occurs when trying to convert a column that has no polars equivalent.
+    #[error("unsupported type: attempted conversion from OwnedColumn of type {0} to Series")]
+    UnsupportedColumnType(ColumnType),
+    /// This error occurs when trying to convert from a data frame with duplicate identifiers (e.g. `"a"` and `"A"`).
+    #[error("conversion resulted in duplicate identifiers")]
+    DuplicateIdentifiers,
+    #[error(transparent)]
+    /// This error occurs when converting from a series name to an identifier fails.
+    FieldParseFail(#[from] ParseError),
+    #[error(transparent)]
+    /// This error occurs when creating an owned table fails, which should only occur when there are zero columns.
+    InvalidTable(#[from] OwnedTableError),
+    /// This error occurs when trying to convert from a series with nulls.
+    #[error("null values are not supported in OwnedColumn yet")]
+    NullNotSupportedYet,
+    /// This error occurs when a timestamp in seconds overflows when converted to milliseconds.
+    #[error("timestamp overflowed when converted to milliseconds")]
+    TimestampOverflow,
+    /// Using TimeError to handle all time-related errors
+    #[error(transparent)]
+    TimestampConversionError(#[from] PoSQLTimestampError),
+    #[error(transparent)]
+    /// This error occurs when polars fails to build or read a series or data frame.
+    PolarsError(#[from] PolarsError),
+}
+
+/// Collect the values of a polars array, failing if there are nulls.
+fn collect_values<T: PolarsNumericType>(
+    array: &ChunkedArray<T>,
+) -> Result<Vec<T::Native>, OwnedPolarsConversionError> {
+    array
+        .into_iter()
+        .collect::<Option<_>>()
+        .ok_or(OwnedPolarsConversionError::NullNotSupportedYet)
+}
+
+fn column_to_series<S: Scalar>(
+    name: &str,
+    column: &OwnedColumn<S>,
+) -> Result<Series, OwnedPolarsConversionError> {
+    Ok(match column {
+        OwnedColumn::Boolean(col) => Series::new(name, col),
+        OwnedColumn::SmallInt(col) => Series::new(name, col),
+        OwnedColumn::Int(col) => Series::new(name, col),
+        OwnedColumn::BigInt(col) => Series::new(name, col),
+        OwnedColumn::VarChar(col) => Series::new(name, col),
+        OwnedColumn::Int128(col) => Int128Chunked::from_vec(name, col.clone())
+            .into_decimal_unchecked(Some(38), 0)
+            .into_series(),
+        OwnedColumn::Decimal75(precision, scale, col) if precision.value() <= 38 && *scale >= 0 => {
+            let values = col
+                .iter()
+                .map(|value| convert_scalar_to_i256(value).to_i128())
+                .collect::<Option<_>>()
+                .ok_or_else(|| {
+                    OwnedPolarsConversionError::UnsupportedColumnType(column.column_type())
+                })?;
+            Int128Chunked::from_vec(name, values)
+                .into_decimal_unchecked(Some(precision.value().into()), *scale as usize)
+                .into_series()
+        }
+        OwnedColumn::TimestampTZ(time_unit, timezone, col) => {
+            let (time_unit, values) = match time_unit {
+                PoSQLTimeUnit::Second => (
+                    TimeUnit::Milliseconds,
+                    col.iter()
+                        .map(|value| value.checked_mul(1000))
+                        .collect::<Option<_>>()
+                        .ok_or(OwnedPolarsConversionError::TimestampOverflow)?,
+                ),
+                PoSQLTimeUnit::Millisecond => (TimeUnit::Milliseconds, col.clone()),
+                PoSQLTimeUnit::Microsecond => (TimeUnit::Microseconds, col.clone()),
+                PoSQLTimeUnit::Nanosecond => (TimeUnit::Nanoseconds, col.clone()),
+            };
+            let timezone = match timezone {
+                PoSQLTimeZone::Utc => "UTC".to_string(),
+                PoSQLTimeZone::FixedOffset(_) => timezone.to_string(),
+            };
+            Int64Chunked::from_vec(name, values)
+                .into_datetime(time_unit, Some(timezone))
+                .into_series()
+        }
+        OwnedColumn::Decimal75(..) | OwnedColumn::Scalar(_) => {
+            return Err(OwnedPolarsConversionError::UnsupportedColumnType(
+                column.column_type(),
+            ))
+        }
+    })
+}
+
+fn series_to_column<S: Scalar>(
+    series: &Series,
+) -> Result<OwnedColumn<S>, OwnedPolarsConversionError> {
+    Ok(match series.dtype() {
+        DataType::Boolean => OwnedColumn::Boolean(
+            series
+                .bool()?
+                .into_iter()
+                .collect::<Option<_>>()
+                .ok_or(OwnedPolarsConversionError::NullNotSupportedYet)?,
+        ),
+        DataType::Int16 => OwnedColumn::SmallInt(collect_values(series.i16()?)?),
+        DataType::Int32 => OwnedColumn::Int(collect_values(series.i32()?)?),
+        DataType::Int64 => OwnedColumn::BigInt(collect_values(series.i64()?)?),
+        DataType::Utf8 => OwnedColumn::VarChar(
+            series
+                .utf8()?
+                .into_iter()
+                .map(|value| value.map(str::to_string))
+                .collect::<Option<_>>()
+                .ok_or(OwnedPolarsConversionError::NullNotSupportedYet)?,
+        ),
+        DataType::Decimal(Some(38), Some(0)) => {
+            OwnedColumn::Int128(collect_values(series.decimal()?)?)
+        }
+        data_type @ DataType::Decimal(Some(precision), Some(scale)) => {
+            let unsupported =
+                || OwnedPolarsConversionError::UnsupportedPolarsType(data_type.clone());
+            OwnedColumn::Decimal75(
+                u8::try_from(*precision)
+                    .ok()
+                    .and_then(|precision| Precision::new(precision).ok())
+                    .ok_or_else(unsupported)?,
+                i8::try_from(*scale).map_err(|_| unsupported())?,
+                collect_values(series.decimal()?)?
+                    .into_iter()
+                    .map(S::from)
+                    .collect(),
+            )
+        }
+        DataType::Datetime(time_unit, timezone) => OwnedColumn::TimestampTZ(
+            match time_unit {
+                TimeUnit::Milliseconds => PoSQLTimeUnit::Millisecond,
+                TimeUnit::Microseconds => PoSQLTimeUnit::Microsecond,
+                TimeUnit::Nanoseconds => PoSQLTimeUnit::Nanosecond,
+            },
+            PoSQLTimeZone::try_from(&timezone.as_deref().map(Arc::from))?,
+            collect_values(series.datetime()?)?,
+        ),
+        data_type => {
+            return Err(OwnedPolarsConversionError::UnsupportedPolarsType(
+                data_type.clone(),
+            ))
+        }
+    })
+}
+
+impl<S: Scalar> OwnedTable<S> {
+    /// Convert the table to a polars [`DataFrame`].
+    pub fn to_polars(&self) -> Result<DataFrame, OwnedPolarsConversionError> {
+        let series = self
+            .inner_table()
+            .iter()
+            .map(|(identifier, column)| column_to_series(identifier.as_str(), column))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DataFrame::new(series)?)
+    }
+
+    /// Convert a polars [`DataFrame`] to a table.
+    pub fn from_polars(data_frame: &DataFrame) -> Result<Self, OwnedPolarsConversionError> {
+        Self::from_polars_with_policy(data_frame, IdentifierPolicy::default())
+    }
+
+    /// Convert a polars [`DataFrame`] to a table, using the policy to turn series names into
+    /// identifiers.
+    pub fn from_polars_with_policy(
+        data_frame: &DataFrame,
+        policy: IdentifierPolicy,
+    ) -> Result<Self, OwnedPolarsConversionError> {
+        let table = data_frame
+            .get_columns()
+            .iter()
+            .map(|series| Ok((policy.identifier(series.name())?, series_to_column(series)?)))
+            .collect::<Result<_, OwnedPolarsConversionError>>()?;
+        let owned_table = Self::try_new(table)?;
+        if data_frame.width() == owned_table.num_columns() {
+            Ok(owned_table)
+        } else {
+            Err(OwnedPolarsConversionError::DuplicateIdentifiers)
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use super::{owned_table_utility::*, OwnedPolarsConversionError, OwnedTable};
+use crate::base::scalar::Curve25519Scalar;
+use polars::{
+    datatypes::{DataType, TimeUnit},
+    df,
+    frame::DataFrame,
+    prelude::Int128Chunked,
+    series::IntoSeries,
+};
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+#[test]
+fn we_can_convert_between_owned_tables_and_data_frames() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        boolean("boolean", [true, false, true]),
+        smallint("smallint", [1_i16, -2, i16::MAX]),
+        int("int", [1_i32, -2, i32::MIN]),
+        bigint("bigint", [1_i64, -2, i64::MAX]),
+        varchar("varchar", ["a", "bc", ""]),
+        int128("int128", [1_i128, i128::MIN, i128::MAX]),
+        decimal75("decimal", 12, 2, [123_i64, -456, 0]),
+        timestamptz(
+            "utc",
+            PoSQLTimeUnit::Nanosecond,
+            PoSQLTimeZone::Utc,
+            [1_625_072_400_000_000_000, 0, -1],
+        ),
+        timestamptz(
+            "offset",
+            PoSQLTimeUnit::Microsecond,
+            PoSQLTimeZone::FixedOffset(-3600),
+            [1_625_072_400_000_000, 0, -1],
+        ),
+    ]);
+    let data_frame = table.to_polars().unwrap();
+    assert_eq!(data_frame.shape(), (3, 9));
+    assert_eq!(
+        data_frame.dtypes(),
+        [
+            DataType::Boolean,
+            DataType::Int16,
+            DataType::Int32,
+            DataType::Int64,
+            DataType::Utf8,
+            DataType::Decimal(Some(38), Some(0)),
+            DataType::Decimal(Some(12), Some(2)),
+            DataType::Datetime(TimeUnit::Nanoseconds, Some("UTC".to_string())),
+            DataType::Datetime(TimeUnit::Microseconds, Some("-01:00".to_string())),
+        ]
+    );
+    assert_eq!(OwnedTable::from_polars(&data_frame).unwrap(), table);
+}
+
+#[test]
+fn we_can_convert_timestamps_in_seconds_to_milliseconds() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([timestamptz(
+        "time",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::Utc,
+        [1_625_072_400, -1],
+    )]);
+    let expected: OwnedTable<Curve25519Scalar> = owned_table([timestamptz(
+        "time",
+        PoSQLTimeUnit::Millisecond,
+        PoSQLTimeZone::Utc,
+        [1_625_072_400_000, -1000],
+    )]);
+    let data_frame = table.to_polars().unwrap();
+    assert_eq!(OwnedTable::from_polars(&data_frame).unwrap(), expected);
+
+    let table: OwnedTable<Curve25519Scalar> = owned_table([timestamptz(
+        "time",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::Utc,
+        [i64::MAX],
+    )]);
+    assert!(matches!(
+        table.to_polars(),
+        Err(OwnedPolarsConversionError::TimestampOverflow)
+    ));
+}
+
+#[test]
+fn we_cannot_convert_columns_that_do_not_fit_in_polars_decimals() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([decimal75("a", 39, 0, [1_i64])]);
+    assert!(matches!(
+        table.to_polars(),
+        Err(OwnedPolarsConversionError::UnsupportedColumnType(_))
+    ));
+    let table: OwnedTable<Curve25519Scalar> = owned_table([decimal75("a", 10, -1, [1_i64])]);
+    assert!(matches!(
+        table.to_polars(),
+        Err(OwnedPolarsConversionError::UnsupportedColumnType(_))
+    ));
+    let table: OwnedTable<Curve25519Scalar> = owned_table([scalar("a", [1_i64])]);
+    assert!(matches!(
+        table.to_polars(),
+        Err(OwnedPolarsConversionError::UnsupportedColumnType(_))
+    ));
+}
+
+#[test]
+fn we_can_convert_decimals_from_polars() {
+    let series = Int128Chunked::from_vec("a", vec![-12_345, 0])
+        .into_decimal_unchecked(Some(5), 3)
+        .into_series();
+    let data_frame = DataFrame::new(vec![series]).unwrap();
+    assert_eq!(
+        OwnedTable::from_polars(&data_frame).unwrap(),
+        owned_table::<Curve25519Scalar>([decimal75("a", 5, 3, [-12_345_i64, 0])])
+    );
+}
+
+#[test]
+fn we_cannot_convert_data_frames_with_nulls_or_unsupported_types() {
+    let data_frame = df!("a" => [Some(1_i64), None]).unwrap();
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::from_polars(&data_frame),
+        Err(OwnedPolarsConversionError::NullNotSupportedYet)
+    ));
+    let data_frame = df!("a" => [Some("a"), None]).unwrap();
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::from_polars(&data_frame),
+        Err(OwnedPolarsConversionError::NullNotSupportedYet)
+    ));
+    let data_frame = df!("a" => [1.0_f64, 2.0]).unwrap();
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::from_polars(&data_frame),
+        Err(OwnedPolarsConversionError::UnsupportedPolarsType(
+            DataType::Float64
+        ))
+    ));
+}
+
+#[test]
+fn we_cannot_convert_data_frames_with_duplicate_identifiers() {
+    let data_frame = df!("a" => [1_i64], "A" => [2_i64]).unwrap();
+    assert!(matches!(
+        OwnedTable::<Curve25519Scalar>::from_polars(&data_frame),
+        Err(OwnedPolarsConversionError::DuplicateIdentifiers)
+    ));
+}
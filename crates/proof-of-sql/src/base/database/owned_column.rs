@@ -15,8 +15,9 @@ use proof_of_sql_parser::{
     intermediate_ast::OrderByDirection,
     posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
 };
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Clone, Eq)]
+#[derive(Debug, PartialEq, Clone, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 /// Supported types for OwnedColumn
 pub enum OwnedColumn<S: Scalar> {
@@ -94,6 +95,30 @@ impl<S: Scalar> OwnedColumn<S> {
         }
     }
 
+    /// Returns the column with the entries at the given rows, in the given order.
+    ///
+    /// Rows may be repeated.
+    pub(crate) fn select_rows(&self, rows: &[usize]) -> Self {
+        fn select<T: Clone>(col: &[T], rows: &[usize]) -> Vec<T> {
+            rows.iter().map(|&row| col[row].clone()).collect()
+        }
+        match self {
+            OwnedColumn::Boolean(col) => OwnedColumn::Boolean(select(col, rows)),
+            OwnedColumn::SmallInt(col) => OwnedColumn::SmallInt(select(col, rows)),
+            OwnedColumn::Int(col) => OwnedColumn::Int(select(col, rows)),
+            OwnedColumn::BigInt(col) => OwnedColumn::BigInt(select(col, rows)),
+            OwnedColumn::VarChar(col) => OwnedColumn::VarChar(select(col, rows)),
+            OwnedColumn::Int128(col) => OwnedColumn::Int128(select(col, rows)),
+            OwnedColumn::Decimal75(precision, scale, col) => {
+                OwnedColumn::Decimal75(*precision, *scale, select(col, rows))
+            }
+            OwnedColumn::Scalar(col) => OwnedColumn::Scalar(select(col, rows)),
+            OwnedColumn::TimestampTZ(tu, tz, col) => {
+                OwnedColumn::TimestampTZ(*tu, *tz, select(col, rows))
+            }
+        }
+    }
+
     /// Returns true if the column is empty.
     pub fn is_empty(&self) -> bool {
         match self {
@@ -125,6 +150,47 @@ impl<S: Scalar> OwnedColumn<S> {
         }
     }
 
+    /// Computes a Blake3 checksum of the column's type and data.
+    ///
+    /// This can be used to detect corruption of a column after it has left the verifier.
+    pub fn checksum(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&postcard::to_allocvec(&self.column_type()).unwrap());
+        hasher.update(&(self.len() as u64).to_le_bytes());
+        match self {
+            OwnedColumn::Boolean(col) => col.iter().for_each(|b| {
+                hasher.update(&[*b as u8]);
+            }),
+            OwnedColumn::SmallInt(col) => col.iter().for_each(|i| {
+                hasher.update(&i.to_le_bytes());
+            }),
+            OwnedColumn::Int(col) => col.iter().for_each(|i| {
+                hasher.update(&i.to_le_bytes());
+            }),
+            OwnedColumn::BigInt(col) | OwnedColumn::TimestampTZ(_, _, col) => {
+                col.iter().for_each(|i| {
+                    hasher.update(&i.to_le_bytes());
+                })
+            }
+            OwnedColumn::Int128(col) => col.iter().for_each(|i| {
+                hasher.update(&i.to_le_bytes());
+            }),
+            OwnedColumn::VarChar(col) => col.iter().for_each(|s| {
+                hasher.update(&(s.len() as u64).to_le_bytes());
+                hasher.update(s.as_bytes());
+            }),
+            OwnedColumn::Scalar(col) | OwnedColumn::Decimal75(_, _, col) => {
+                col.iter().for_each(|s| {
+                    let limbs: [u64; 4] = (*s).into();
+                    limbs.iter().for_each(|limb| {
+                        hasher.update(&limb.to_le_bytes());
+                    });
+                })
+            }
+        }
+        hasher.finalize().into()
+    }
+
     /// Convert a slice of scalars to a vec of owned columns
     pub fn try_from_scalars(scalars: &[S], column_type: ColumnType) -> OwnedColumnResult<Self> {
         match column_type {
@@ -353,6 +419,17 @@ mod test {
         assert_eq!(col.slice(1, 4), OwnedColumn::Int128(vec![2, 3, 4]));
     }
 
+    #[test]
+    fn we_can_select_rows_of_a_column() {
+        let col: OwnedColumn<Curve25519Scalar> =
+            OwnedColumn::VarChar(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        assert_eq!(
+            col.select_rows(&[2, 0, 2]),
+            OwnedColumn::VarChar(vec!["c".to_string(), "a".to_string(), "c".to_string()])
+        );
+        assert_eq!(col.select_rows(&[]), OwnedColumn::VarChar(vec![]));
+    }
+
     #[test]
     fn we_can_permute_a_column() {
         let col: OwnedColumn<Curve25519Scalar> = OwnedColumn::Int128(vec![1, 2, 3, 4, 5]);
@@ -2,6 +2,7 @@ use super::OwnedColumn;
 use crate::base::scalar::Scalar;
 use indexmap::IndexMap;
 use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 /// An error that occurs when working with tables.
@@ -67,6 +68,13 @@ impl<S: Scalar> OwnedTable<S> {
     pub fn column_names(&self) -> impl Iterator<Item = &Identifier> {
         self.table.keys()
     }
+    /// Returns the Blake3 checksum of each column of this table, keyed by column name.
+    pub fn column_checksums(&self) -> IndexMap<Identifier, [u8; 32]> {
+        self.table
+            .iter()
+            .map(|(name, column)| (*name, column.checksum()))
+            .collect()
+    }
 }
 
 // Note: we modify the default PartialEq for IndexMap to also check for column ordering.
@@ -82,6 +90,20 @@ impl<S: Scalar> PartialEq for OwnedTable<S> {
     }
 }
 
+impl<S: Scalar + Serialize> Serialize for OwnedTable<S> {
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        self.table.serialize(serializer)
+    }
+}
+
+// Deserializing goes through `try_new` so that tables with columns of different lengths are
+// rejected.
+impl<'de, S: Scalar + Deserialize<'de>> Deserialize<'de> for OwnedTable<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_new(IndexMap::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 impl<S: Scalar> core::ops::Index<&str> for OwnedTable<S> {
     type Output = OwnedColumn<S>;
@@ -185,3 +185,61 @@ fn we_cannot_create_an_owned_table_with_differing_column_lengths() {
         Err(OwnedTableError::ColumnLengthMismatch)
     ));
 }
+#[test]
+fn we_can_compute_column_checksums_of_an_owned_table() {
+    let table = owned_table::<Curve25519Scalar>([
+        bigint("a", [1, 2, 3]),
+        varchar("b", ["x", "y", "z"]),
+        scalar("c", [1, 2, 3]),
+    ]);
+    let checksums = table.column_checksums();
+    assert_eq!(
+        checksums.keys().collect::<Vec<_>>(),
+        table.column_names().collect::<Vec<_>>()
+    );
+    assert_eq!(checksums, table.clone().column_checksums());
+    let checksums = checksums.into_values().collect::<Vec<_>>();
+    assert_ne!(checksums[0], checksums[2]);
+
+    let modified_table = owned_table::<Curve25519Scalar>([
+        bigint("a", [1, 2, 3]),
+        varchar("b", ["x", "y", "zz"]),
+        scalar("c", [1, 2, 3]),
+    ]);
+    let modified_checksums = modified_table
+        .column_checksums()
+        .into_values()
+        .collect::<Vec<_>>();
+    assert_eq!(checksums[0], modified_checksums[0]);
+    assert_ne!(checksums[1], modified_checksums[1]);
+    assert_eq!(checksums[2], modified_checksums[2]);
+}
+#[test]
+fn we_can_serialize_and_deserialize_an_owned_table() {
+    let table = owned_table::<Curve25519Scalar>([
+        bigint("bigint", [0, 1, i64::MIN]),
+        int128("int128", [0, 1, i128::MAX]),
+        varchar("varchar", ["0", "1", "2"]),
+        scalar("scalar", [0, 1, 2]),
+        boolean("boolean", [true, false, true]),
+        decimal75("decimal", 10, 2, [0, 1, -1]),
+        timestamptz(
+            "time_stamp",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            [0, 1, -1],
+        ),
+    ]);
+    let serialized = serde_json::to_string(&table).unwrap();
+    let deserialized: OwnedTable<Curve25519Scalar> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, table);
+    assert_eq!(
+        deserialized.column_names().collect::<Vec<_>>(),
+        table.column_names().collect::<Vec<_>>()
+    );
+}
+#[test]
+fn we_cannot_deserialize_an_owned_table_with_differing_column_lengths() {
+    let serialized = r#"{"a":{"BigInt":[0]},"b":{"BigInt":[]}}"#;
+    assert!(serde_json::from_str::<OwnedTable<Curve25519Scalar>>(serialized).is_err());
+}
@@ -1,11 +1,15 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
-    OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+    AccessorError, AccessorResult, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+};
+use crate::base::{
+    commitment::{CommitmentEvaluationProof, VecCommitmentExt},
+    scalar::Scalar,
 };
-use crate::base::commitment::{CommitmentEvaluationProof, VecCommitmentExt};
 use bumpalo::Bump;
 use indexmap::IndexMap;
 use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
 
 /// A test accessor that uses OwnedTable as the underlying table type.
 /// Note: this is not optimized for performance, so should not be used for benchmarks.
@@ -15,6 +19,17 @@ pub struct OwnedTableTestAccessor<'a, CP: CommitmentEvaluationProof> {
     setup: Option<CP::ProverPublicSetup<'a>>,
 }
 
+/// The tables and offsets of an [`OwnedTableTestAccessor`].
+///
+/// This can be serialized, so that a failing test case can be captured as a fixture file and
+/// replayed with [`OwnedTableTestAccessor::from_state`]. The setup is not part of the state,
+/// since it borrows the public parameters, which are best regenerated from their seed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnedTableTestAccessorState<S: Scalar> {
+    /// The tables of the accessor, along with their offsets.
+    pub tables: IndexMap<TableRef, (OwnedTable<S>, usize)>,
+}
+
 impl<CP: CommitmentEvaluationProof> Default for OwnedTableTestAccessor<'_, CP> {
     fn default() -> Self {
         Self {
@@ -64,15 +79,17 @@ impl<CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment>
 }
 impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestAccessor<'_, CP> {
     fn get_column(&self, column: ColumnRef) -> Column<CP::Scalar> {
-        match self
-            .tables
-            .get(&column.table_ref())
-            .unwrap()
+        self.try_get_column(column).unwrap()
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<CP::Scalar>> {
+        let owned_column = self
+            .try_get_table(column.table_ref())?
             .0
             .inner_table()
             .get(&column.column_id())
-            .unwrap()
-        {
+            .ok_or_else(|| AccessorError::column_not_found(column))?;
+        Ok(match owned_column {
             OwnedColumn::Boolean(col) => Column::Boolean(col),
             OwnedColumn::SmallInt(col) => Column::SmallInt(col),
             OwnedColumn::Int(col) => Column::Int(col),
@@ -92,7 +109,7 @@ impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestA
                 Column::VarChar((col, scals))
             }
             OwnedColumn::TimestampTZ(tu, tz, col) => Column::TimestampTZ(*tu, *tz, col),
-        }
+        })
     }
 }
 impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
@@ -112,6 +129,14 @@ impl<CP: CommitmentEvaluationProof> MetadataAccessor for OwnedTableTestAccessor<
     fn get_offset(&self, table_ref: TableRef) -> usize {
         self.tables.get(&table_ref).unwrap().1
     }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_table(table_ref)?.0.num_rows())
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_table(table_ref)?.1)
+    }
 }
 impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<'_, CP> {
     fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
@@ -156,4 +181,31 @@ impl<'a, CP: CommitmentEvaluationProof> OwnedTableTestAccessor<'a, CP> {
         res.add_table(table_ref, owned_table, offset);
         res
     }
+
+    fn try_get_table(
+        &self,
+        table_ref: TableRef,
+    ) -> AccessorResult<&(OwnedTable<CP::Scalar>, usize)> {
+        self.tables
+            .get(&table_ref)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+
+    /// Returns the tables and offsets of this accessor.
+    pub fn state(&self) -> OwnedTableTestAccessorState<CP::Scalar> {
+        OwnedTableTestAccessorState {
+            tables: self.tables.clone(),
+        }
+    }
+
+    /// Create a new test accessor from the tables and offsets of another one, using the given
+    /// setup.
+    pub fn from_state(
+        state: OwnedTableTestAccessorState<CP::Scalar>,
+        setup: CP::ProverPublicSetup<'a>,
+    ) -> Self {
+        let mut res = Self::new_empty_with_setup(setup);
+        res.tables = state.tables;
+        res
+    }
 }
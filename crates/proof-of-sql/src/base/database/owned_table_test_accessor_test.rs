@@ -1,6 +1,7 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
-    OwnedTableTestAccessor, SchemaAccessor, TestAccessor,
+    AccessorError, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedTableTestAccessor, OwnedTableTestAccessorState, SchemaAccessor,
+    TestAccessor,
 };
 use crate::base::{
     database::owned_table_utility::*,
@@ -118,6 +119,45 @@ fn we_can_access_the_columns_of_a_table() {
     };
 }
 
+#[test]
+fn we_get_errors_when_accessing_missing_tables_and_columns() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_ref = "sxt.test".parse().unwrap();
+    let missing_table_ref = "sxt.missing".parse().unwrap();
+    accessor.add_table(table_ref, owned_table([bigint("a", [1, 2, 3])]), 2_usize);
+
+    assert_eq!(accessor.try_get_length(table_ref), Ok(3));
+    assert_eq!(accessor.try_get_offset(table_ref), Ok(2));
+    let column = ColumnRef::new(table_ref, "a".parse().unwrap(), ColumnType::BigInt);
+    assert_eq!(
+        accessor.try_get_column(column),
+        Ok(Column::BigInt(&[1, 2, 3]))
+    );
+
+    let missing_table = AccessorError::TableNotFound {
+        table_ref: missing_table_ref,
+    };
+    assert_eq!(
+        accessor.try_get_length(missing_table_ref),
+        Err(missing_table.clone())
+    );
+    assert_eq!(
+        accessor.try_get_offset(missing_table_ref),
+        Err(missing_table.clone())
+    );
+    let column = ColumnRef::new(missing_table_ref, "a".parse().unwrap(), ColumnType::BigInt);
+    assert_eq!(accessor.try_get_column(column), Err(missing_table));
+
+    let column = ColumnRef::new(table_ref, "b".parse().unwrap(), ColumnType::BigInt);
+    assert_eq!(
+        accessor.try_get_column(column),
+        Err(AccessorError::ColumnNotFound {
+            table_ref,
+            column_id: "b".parse().unwrap(),
+        })
+    );
+}
+
 #[test]
 fn we_can_access_the_commitments_of_table_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
@@ -250,3 +290,44 @@ fn we_can_correctly_update_offsets() {
     assert_eq!(accessor1.get_offset(table_ref), offset);
     assert_eq!(accessor2.get_offset(table_ref), offset);
 }
+
+#[test]
+fn we_can_replay_a_serialized_accessor_state() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let table_ref_1 = "sxt.test".parse().unwrap();
+    let table_ref_2 = "sxt.test2".parse().unwrap();
+    accessor.add_table(
+        table_ref_1,
+        owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "z"])]),
+        0_usize,
+    );
+    accessor.add_table(
+        table_ref_2,
+        owned_table([scalar("c", [4, 5]), boolean("d", [true, false])]),
+        3_usize,
+    );
+
+    let serialized = serde_json::to_string(&accessor.state()).unwrap();
+    let state: OwnedTableTestAccessorState<Curve25519Scalar> =
+        serde_json::from_str(&serialized).unwrap();
+    assert_eq!(state, accessor.state());
+    let replayed = OwnedTableTestAccessor::<InnerProductProof>::from_state(state, ());
+
+    for table_ref in [table_ref_1, table_ref_2] {
+        assert_eq!(
+            replayed.get_length(table_ref),
+            accessor.get_length(table_ref)
+        );
+        assert_eq!(
+            replayed.get_offset(table_ref),
+            accessor.get_offset(table_ref)
+        );
+        for (column_id, column_type) in accessor.lookup_schema(table_ref) {
+            let column = ColumnRef::new(table_ref, column_id, column_type);
+            assert_eq!(
+                replayed.get_commitment(column),
+                accessor.get_commitment(column)
+            );
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use super::{ColumnRef, ColumnType, OwnedColumn, OwnedTable, SchemaAccessor, TableRef};
+use crate::base::scalar::Scalar;
+use proof_of_sql_parser::Identifier;
+use thiserror::Error;
+
+/// The name of the column that makes a table retractable.
+///
+/// The rows of a retractable table are never changed or removed, so its commitments only ever have
+/// rows appended to them. Instead, a record is deleted by appending a retraction row, which repeats
+/// the values of the record with a multiplicity of `-1`, while the rows that insert records have a
+/// multiplicity of `1`. Queries are proven over all rows of the table, including retractions, and
+/// the retractions are netted out of the verified result before it is transformed.
+///
+/// The column is hidden from queries, so it cannot be selected or filtered on.
+pub const MULTIPLICITY_COLUMN_NAME: &str = "__multiplicity__";
+
+/// Returns the identifier of the [`MULTIPLICITY_COLUMN_NAME`] column.
+pub fn multiplicity_column_id() -> Identifier {
+    Identifier::try_new(MULTIPLICITY_COLUMN_NAME)
+        .expect("the multiplicity column name is a valid identifier")
+}
+
+/// Returns the multiplicity column of a table if the table is retractable.
+///
+/// A table is retractable if it has a `BigInt` column named [`MULTIPLICITY_COLUMN_NAME`].
+pub fn lookup_multiplicity_column(
+    accessor: &dyn SchemaAccessor,
+    table_ref: TableRef,
+) -> Option<ColumnRef> {
+    let column_id = multiplicity_column_id();
+    match accessor.lookup_column(table_ref, column_id) {
+        Some(ColumnType::BigInt) => Some(ColumnRef::new(table_ref, column_id, ColumnType::BigInt)),
+        _ => None,
+    }
+}
+
+/// Errors that can occur when preparing rows for a retractable table.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RetractionError {
+    /// The rows already have a multiplicity column.
+    #[error("the rows already have a column named {MULTIPLICITY_COLUMN_NAME}")]
+    ReservedColumnName,
+}
+
+fn with_multiplicity<S: Scalar>(
+    records: OwnedTable<S>,
+    multiplicity: i64,
+) -> Result<OwnedTable<S>, RetractionError> {
+    let num_rows = records.num_rows();
+    let mut columns = records.into_inner();
+    let multiplicities = OwnedColumn::BigInt(vec![multiplicity; num_rows]);
+    if columns
+        .insert(multiplicity_column_id(), multiplicities)
+        .is_some()
+    {
+        return Err(RetractionError::ReservedColumnName);
+    }
+    Ok(
+        OwnedTable::try_new(columns)
+            .expect("the multiplicity column has as many rows as the table"),
+    )
+}
+
+/// Returns the rows to append to a retractable table in order to insert the given records.
+pub fn insertion_rows<S: Scalar>(records: OwnedTable<S>) -> Result<OwnedTable<S>, RetractionError> {
+    with_multiplicity(records, 1)
+}
+
+/// Returns the rows to append to a retractable table in order to delete the given records.
+///
+/// A record appears in query results as many times as it was inserted minus the number of times it
+/// was retracted, or not at all if it was retracted at least as often as it was inserted.
+pub fn retraction_rows<S: Scalar>(
+    records: OwnedTable<S>,
+) -> Result<OwnedTable<S>, RetractionError> {
+    with_multiplicity(records, -1)
+}
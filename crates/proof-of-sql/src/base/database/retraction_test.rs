@@ -0,0 +1,66 @@
+use super::{
+    insertion_rows, lookup_multiplicity_column, multiplicity_column_id, owned_table_utility::*,
+    retraction_rows, ColumnRef, ColumnType, OwnedTable, RetractionError, TableRef,
+    TestSchemaAccessor,
+};
+use crate::base::scalar::Curve25519Scalar;
+use indexmap::indexmap;
+use proof_of_sql_parser::utility::ident;
+
+#[test]
+fn we_can_mark_rows_as_insertions_or_retractions() {
+    let records: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])]);
+    assert_eq!(
+        insertion_rows(records.clone()).unwrap(),
+        owned_table([
+            bigint("a", [1, 2]),
+            varchar("b", ["x", "y"]),
+            bigint("__multiplicity__", [1, 1]),
+        ])
+    );
+    assert_eq!(
+        retraction_rows(records).unwrap(),
+        owned_table([
+            bigint("a", [1, 2]),
+            varchar("b", ["x", "y"]),
+            bigint("__multiplicity__", [-1, -1]),
+        ])
+    );
+}
+
+#[test]
+fn we_cannot_mark_rows_that_already_have_a_multiplicity() {
+    let rows: OwnedTable<Curve25519Scalar> =
+        insertion_rows(owned_table([bigint("a", [1, 2])])).unwrap();
+    assert_eq!(
+        retraction_rows(rows),
+        Err(RetractionError::ReservedColumnName)
+    );
+}
+
+#[test]
+fn we_can_look_up_the_multiplicity_column_of_a_retractable_table() {
+    let retractable: TableRef = "sxt.retractable".parse().unwrap();
+    let wrong_type: TableRef = "sxt.wrong_type".parse().unwrap();
+    let plain: TableRef = "sxt.plain".parse().unwrap();
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        retractable => indexmap! {
+            ident("a") => ColumnType::BigInt,
+            multiplicity_column_id() => ColumnType::BigInt,
+        },
+        wrong_type => indexmap! {
+            multiplicity_column_id() => ColumnType::Int,
+        },
+        plain => indexmap! {
+            ident("a") => ColumnType::BigInt,
+        },
+    });
+    let multiplicity = ColumnRef::new(retractable, multiplicity_column_id(), ColumnType::BigInt);
+    assert_eq!(
+        lookup_multiplicity_column(&accessor, retractable),
+        Some(multiplicity)
+    );
+    assert_eq!(lookup_multiplicity_column(&accessor, wrong_type), None);
+    assert_eq!(lookup_multiplicity_column(&accessor, plain), None);
+}
@@ -0,0 +1,279 @@
+use super::{
+    append_only_table::extend_table, AccessorError, AccessorResult, Column, ColumnRef, ColumnType,
+    CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor,
+    TableRef,
+};
+use crate::base::commitment::{
+    ColumnCommitmentsMismatch, Commitment, QueryCommitments, TableCommitment,
+};
+use bumpalo::Bump;
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+use std::sync::{Arc, Mutex, MutexGuard};
+use thiserror::Error;
+
+/// Errors that can occur when changing the tables of a [`SnapshotStore`].
+#[derive(Debug, Error)]
+pub enum SnapshotStoreError {
+    /// The table does not exist in the store
+    #[error("table {table_ref} does not exist")]
+    TableNotFound {
+        /// The table that was looked up
+        table_ref: TableRef,
+    },
+    /// The table already exists in the store
+    #[error("table {table_ref} already exists")]
+    TableAlreadyExists {
+        /// The table that was added
+        table_ref: TableRef,
+    },
+    /// The appended rows do not have the columns of the table
+    #[error(transparent)]
+    ColumnCommitmentsMismatch(#[from] ColumnCommitmentsMismatch),
+}
+
+/// A table of a snapshot along with the commitment to its rows.
+struct SnapshotTable<C: Commitment> {
+    table: OwnedTable<C::Scalar>,
+    commitment: TableCommitment<C>,
+}
+
+/// The tables of a [`SnapshotStore`] at one version. This is never changed once it is created.
+struct Snapshot<C: Commitment> {
+    version: u64,
+    tables: IndexMap<TableRef, Arc<SnapshotTable<C>>>,
+}
+
+/// A store of append-only tables that can be queried while it is appended to.
+///
+/// Every change to the store creates a new snapshot of its tables, rather than mutating the
+/// current one. A [`SnapshotAccessor`] taken with [`SnapshotStore::snapshot`] keeps reading the
+/// snapshot that was current when it was taken. So a proof that is in progress never sees rows
+/// that were appended after it started, and the data it proves against always matches the
+/// commitments returned by [`SnapshotAccessor::query_commitments`], which the result has to be
+/// verified with.
+///
+/// Appends copy the rows of the table that they append to, while the other tables are shared
+/// between snapshots. Appends are serialized, so that concurrent appends to a table are never
+/// lost.
+pub struct SnapshotStore<C: Commitment> {
+    current: Mutex<Arc<Snapshot<C>>>,
+    writer: Mutex<()>,
+}
+
+impl<C: Commitment> Default for SnapshotStore<C> {
+    fn default() -> Self {
+        Self {
+            current: Mutex::new(Arc::new(Snapshot {
+                version: 0,
+                tables: IndexMap::new(),
+            })),
+            writer: Mutex::new(()),
+        }
+    }
+}
+
+impl<C: Commitment> SnapshotStore<C> {
+    /// Create a new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn current(&self) -> MutexGuard<'_, Arc<Snapshot<C>>> {
+        self.current
+            .lock()
+            .expect("the current snapshot should not be poisoned")
+    }
+
+    /// Returns the version of the current snapshot, which is bumped by every change to the store.
+    pub fn version(&self) -> u64 {
+        self.current().version
+    }
+
+    /// Returns an accessor for the current snapshot of the tables.
+    ///
+    /// The accessor is not affected by changes to the store after it is taken.
+    pub fn snapshot(&self) -> SnapshotAccessor<C> {
+        SnapshotAccessor {
+            snapshot: Arc::clone(&self.current()),
+            alloc: Bump::new(),
+        }
+    }
+
+    /// Add a table from its first rows, which start at the given row offset.
+    ///
+    /// Returns the version of the snapshot that has the table.
+    pub fn try_add_table(
+        &self,
+        table_ref: TableRef,
+        table: OwnedTable<C::Scalar>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<u64, SnapshotStoreError> {
+        self.try_update(table_ref, |existing| match existing {
+            Some(_) => Err(SnapshotStoreError::TableAlreadyExists { table_ref }),
+            None => {
+                let commitment =
+                    TableCommitment::from_owned_table_with_offset(&table, offset, setup);
+                Ok(SnapshotTable { table, commitment })
+            }
+        })
+    }
+
+    /// Append rows to a table and to its commitment.
+    ///
+    /// The rows have to have the same columns, in the same order, as the table. Otherwise, the
+    /// store is not changed.
+    ///
+    /// Returns the version of the snapshot that has the rows.
+    pub fn try_append(
+        &self,
+        table_ref: TableRef,
+        rows: &OwnedTable<C::Scalar>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<u64, SnapshotStoreError> {
+        self.try_update(table_ref, |existing| {
+            let existing = existing.ok_or(SnapshotStoreError::TableNotFound { table_ref })?;
+            let mut commitment = existing.commitment.clone();
+            commitment.append_owned_table(rows, setup)?;
+            let mut table = existing.table.clone();
+            extend_table(&mut table, rows);
+            Ok(SnapshotTable { table, commitment })
+        })
+    }
+
+    /// Replace a table with the result of `update`, creating a new snapshot.
+    ///
+    /// The new table is computed without holding the lock on the current snapshot, so that new
+    /// snapshots can be taken in the meantime.
+    fn try_update(
+        &self,
+        table_ref: TableRef,
+        update: impl FnOnce(Option<&SnapshotTable<C>>) -> Result<SnapshotTable<C>, SnapshotStoreError>,
+    ) -> Result<u64, SnapshotStoreError> {
+        let _writer = self
+            .writer
+            .lock()
+            .expect("the snapshot writer should not be poisoned");
+        let previous = Arc::clone(&self.current());
+        let table = update(previous.tables.get(&table_ref).map(Arc::as_ref))?;
+        let mut tables = previous.tables.clone();
+        tables.insert(table_ref, Arc::new(table));
+        let version = previous.version + 1;
+        *self.current() = Arc::new(Snapshot { version, tables });
+        Ok(version)
+    }
+}
+
+/// An accessor for one snapshot of the tables of a [`SnapshotStore`].
+///
+/// This can be sent to the thread that creates the proof.
+pub struct SnapshotAccessor<C: Commitment> {
+    snapshot: Arc<Snapshot<C>>,
+    alloc: Bump,
+}
+
+impl<C: Commitment> SnapshotAccessor<C> {
+    /// Returns the version of the snapshot.
+    pub fn version(&self) -> u64 {
+        self.snapshot.version
+    }
+
+    /// Returns the rows of a table of the snapshot.
+    pub fn table(&self, table_ref: TableRef) -> Option<&OwnedTable<C::Scalar>> {
+        Some(&self.snapshot.tables.get(&table_ref)?.table)
+    }
+
+    /// Returns the commitment to the rows of a table of the snapshot.
+    pub fn table_commitment(&self, table_ref: TableRef) -> Option<&TableCommitment<C>> {
+        Some(&self.snapshot.tables.get(&table_ref)?.commitment)
+    }
+
+    /// Returns the commitments to the tables of the snapshot, which a query against the snapshot
+    /// is verified with.
+    pub fn query_commitments(&self) -> QueryCommitments<C> {
+        self.snapshot
+            .tables
+            .iter()
+            .map(|(&table_ref, table)| (table_ref, table.commitment.clone()))
+            .collect()
+    }
+
+    fn try_get_table(&self, table_ref: TableRef) -> AccessorResult<&SnapshotTable<C>> {
+        self.snapshot
+            .tables
+            .get(&table_ref)
+            .map(Arc::as_ref)
+            .ok_or(AccessorError::TableNotFound { table_ref })
+    }
+
+    fn try_get_column_data(&self, column: ColumnRef) -> AccessorResult<&OwnedColumn<C::Scalar>> {
+        self.try_get_table(column.table_ref())?
+            .table
+            .inner_table()
+            .get(&column.column_id())
+            .ok_or_else(|| AccessorError::column_not_found(column))
+    }
+}
+
+impl<C: Commitment> MetadataAccessor for SnapshotAccessor<C> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.try_get_length(table_ref).unwrap()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.try_get_offset(table_ref).unwrap()
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_table(table_ref)?.commitment.num_rows())
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        Ok(self.try_get_table(table_ref)?.commitment.range().start)
+    }
+}
+
+impl<C: Commitment> CommitmentAccessor<C> for SnapshotAccessor<C> {
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        self.try_get_column_data(column).unwrap();
+        self.try_get_table(column.table_ref())
+            .unwrap()
+            .commitment
+            .column_commitments()
+            .get_commitment(&column.column_id())
+            .expect("the commitment has a column for every column of the table")
+    }
+}
+
+impl<C: Commitment> DataAccessor<C::Scalar> for SnapshotAccessor<C> {
+    fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
+        self.try_get_column(column).unwrap()
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<C::Scalar>> {
+        Ok(Column::from_owned_column(
+            self.try_get_column_data(column)?,
+            &self.alloc,
+        ))
+    }
+}
+
+impl<C: Commitment> SchemaAccessor for SnapshotAccessor<C> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        let table = self.try_get_table(table_ref).ok()?;
+        Some(table.table.inner_table().get(&column_id)?.column_type())
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        let Ok(table) = self.try_get_table(table_ref) else {
+            return Vec::new();
+        };
+        table
+            .table
+            .inner_table()
+            .iter()
+            .map(|(&identifier, column)| (identifier, column.column_type()))
+            .collect()
+    }
+}
@@ -0,0 +1,236 @@
+use super::{
+    owned_table_utility::*, AccessorError, Column, ColumnRef, ColumnType, CommitmentAccessor,
+    DataAccessor, MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, SnapshotStore,
+    SnapshotStoreError, TableRef,
+};
+use crate::{
+    base::{
+        commitment::{ColumnCommitmentsMismatch, InnerProductProof, TableCommitment},
+        scalar::Curve25519Scalar,
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn table_ref() -> TableRef {
+    "sxt.t".parse().unwrap()
+}
+
+fn rows(a: &[i64], b: &[&str]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([bigint("a", a.to_vec()), varchar("b", b.to_vec())])
+}
+
+#[test]
+fn a_snapshot_is_not_affected_by_later_appends() {
+    let store = SnapshotStore::<RistrettoPoint>::new();
+    assert_eq!(store.version(), 0);
+    assert_eq!(
+        store
+            .try_add_table(table_ref(), rows(&[1, 2], &["x", "y"]), 3, &())
+            .unwrap(),
+        1
+    );
+    let snapshot = store.snapshot();
+    assert_eq!(
+        store
+            .try_append(table_ref(), &rows(&[3], &["z"]), &())
+            .unwrap(),
+        2
+    );
+    let new_snapshot = store.snapshot();
+
+    assert_eq!(snapshot.version(), 1);
+    assert_eq!(
+        snapshot.table(table_ref()),
+        Some(&rows(&[1, 2], &["x", "y"]))
+    );
+    assert_eq!(snapshot.get_length(table_ref()), 2);
+    assert_eq!(
+        snapshot.query_commitments()[&table_ref()],
+        TableCommitment::from_owned_table_with_offset(&rows(&[1, 2], &["x", "y"]), 3, &())
+    );
+
+    let expected_rows = rows(&[1, 2, 3], &["x", "y", "z"]);
+    assert_eq!(new_snapshot.version(), 2);
+    assert_eq!(new_snapshot.table(table_ref()), Some(&expected_rows));
+    assert_eq!(new_snapshot.get_offset(table_ref()), 3);
+    assert_eq!(new_snapshot.get_length(table_ref()), 3);
+    assert_eq!(
+        new_snapshot.table_commitment(table_ref()),
+        Some(&TableCommitment::from_owned_table_with_offset(
+            &expected_rows,
+            3,
+            &()
+        ))
+    );
+}
+
+#[test]
+fn we_cannot_change_the_store_with_invalid_tables_or_rows() {
+    let store = SnapshotStore::<RistrettoPoint>::new();
+    let other_table_ref: TableRef = "sxt.u".parse().unwrap();
+    assert!(matches!(
+        store.try_append(other_table_ref, &rows(&[3], &["z"]), &()),
+        Err(SnapshotStoreError::TableNotFound { table_ref }) if table_ref == other_table_ref
+    ));
+    store
+        .try_add_table(table_ref(), rows(&[1, 2], &["x", "y"]), 0, &())
+        .unwrap();
+    assert!(matches!(
+        store.try_add_table(table_ref(), rows(&[3], &["z"]), 0, &()),
+        Err(SnapshotStoreError::TableAlreadyExists { .. })
+    ));
+    assert!(matches!(
+        store.try_append(table_ref(), &owned_table([bigint("a", [3])]), &()),
+        Err(SnapshotStoreError::ColumnCommitmentsMismatch(
+            ColumnCommitmentsMismatch::NumColumns
+        ))
+    ));
+    assert_eq!(store.version(), 1);
+    assert_eq!(
+        store.snapshot().table(table_ref()),
+        Some(&rows(&[1, 2], &["x", "y"]))
+    );
+}
+
+#[test]
+fn we_can_access_the_columns_of_a_snapshot() {
+    let store = SnapshotStore::<RistrettoPoint>::new();
+    store
+        .try_add_table(table_ref(), rows(&[1, 2], &["x", "y"]), 0, &())
+        .unwrap();
+    let snapshot = store.snapshot();
+    let a = ColumnRef::new(table_ref(), ident("a"), ColumnType::BigInt);
+    match snapshot.get_column(a) {
+        Column::BigInt(col) => assert_eq!(col.to_vec(), vec![1, 2]),
+        _ => panic!("Invalid column type"),
+    };
+    assert_eq!(
+        snapshot.get_commitment(a),
+        snapshot
+            .table_commitment(table_ref())
+            .unwrap()
+            .column_commitments()
+            .get_commitment(&ident("a"))
+            .unwrap()
+    );
+    assert_eq!(
+        snapshot.lookup_schema(table_ref()),
+        vec![
+            (ident("a"), ColumnType::BigInt),
+            (ident("b"), ColumnType::VarChar)
+        ]
+    );
+
+    let other_table_ref: TableRef = "sxt.u".parse().unwrap();
+    let c = ColumnRef::new(table_ref(), ident("c"), ColumnType::BigInt);
+    assert_eq!(
+        snapshot.try_get_column(c).unwrap_err(),
+        AccessorError::column_not_found(c)
+    );
+    assert_eq!(
+        snapshot.try_get_length(other_table_ref),
+        Err(AccessorError::TableNotFound {
+            table_ref: other_table_ref
+        })
+    );
+    assert!(snapshot.lookup_schema(other_table_ref).is_empty());
+    assert_eq!(snapshot.lookup_column(other_table_ref, ident("a")), None);
+}
+
+#[test]
+fn we_can_prove_against_a_snapshot_while_the_table_is_appended_to() {
+    let store = SnapshotStore::<RistrettoPoint>::new();
+    store
+        .try_add_table(table_ref(), rows(&[1, 2], &["x", "y"]), 0, &())
+        .unwrap();
+    store
+        .try_append(table_ref(), &rows(&[3, 4], &["z", "y"]), &())
+        .unwrap();
+    let snapshot = store.snapshot();
+    let query = QueryExpr::try_new(
+        "SELECT a FROM t WHERE b = 'y'".parse().unwrap(),
+        ident("sxt"),
+        &snapshot,
+    )
+    .unwrap();
+
+    let proof = std::thread::scope(|scope| {
+        let appender = scope.spawn(|| {
+            for a in 5..15 {
+                store
+                    .try_append(table_ref(), &rows(&[a], &["y"]), &())
+                    .unwrap();
+            }
+        });
+        let proof =
+            VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &snapshot, &());
+        appender.join().unwrap();
+        proof
+    });
+    assert_eq!(store.version(), 12);
+    assert_eq!(store.snapshot().get_length(table_ref()), 14);
+
+    let data = proof
+        .verify(query.proof_expr(), &snapshot.query_commitments(), &())
+        .unwrap();
+    let result: OwnedTable<Curve25519Scalar> = query
+        .result()
+        .transform_results(data.table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    assert_eq!(result, owned_table([bigint("a", [2, 4])]));
+
+    // The commitments of the current snapshot are for other rows.
+    assert!(proof
+        .verify(
+            query.proof_expr(),
+            &store.snapshot().query_commitments(),
+            &()
+        )
+        .is_err());
+}
+
+#[test]
+fn concurrent_appends_are_not_lost() {
+    let store = SnapshotStore::<RistrettoPoint>::new();
+    store
+        .try_add_table(table_ref(), rows(&[0], &["x"]), 0, &())
+        .unwrap();
+    std::thread::scope(|scope| {
+        for thread in 0..4 {
+            let store = &store;
+            scope.spawn(move || {
+                for i in 0..5 {
+                    store
+                        .try_append(table_ref(), &rows(&[thread * 10 + i], &["y"]), &())
+                        .unwrap();
+                }
+            });
+        }
+    });
+
+    let snapshot = store.snapshot();
+    assert_eq!(snapshot.version(), 21);
+    assert_eq!(snapshot.get_length(table_ref()), 21);
+    let table = snapshot.table(table_ref()).unwrap();
+    assert_eq!(
+        snapshot.table_commitment(table_ref()),
+        Some(&TableCommitment::from_owned_table_with_offset(
+            table,
+            0,
+            &()
+        ))
+    );
+    let OwnedColumn::BigInt(a) = &table.inner_table()[&ident("a")] else {
+        panic!("Invalid column type");
+    };
+    let mut a = a.clone();
+    a.sort_unstable();
+    let mut expected: Vec<i64> = vec![0];
+    expected.extend((0..4).flat_map(|thread| (0..5).map(move |i| thread * 10 + i)));
+    expected.sort_unstable();
+    assert_eq!(a, expected);
+}
@@ -0,0 +1,140 @@
+use super::OwnedColumn;
+use crate::base::scalar::Scalar;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+#[cfg(feature = "varchar-normalization")]
+use unicode_normalization::{is_nfc, is_nfkc, UnicodeNormalization};
+
+/// The Unicode normalization applied to the strings of a VarChar column before they are committed.
+///
+/// Strings are committed as hashes of their bytes, so two canonically equivalent strings
+/// (e.g. a precomposed `é` and an `e` followed by a combining accent) are different values unless
+/// they are normalized. The normalization of a column is recorded in its
+/// [`ColumnCommitmentMetadata`](crate::base::commitment::ColumnCommitmentMetadata), and string
+/// literals compared against the column are normalized the same way when a query is planned.
+///
+/// Normalizing strings requires the `varchar-normalization` feature. Without it, the metadata of
+/// commitments still records the normalization of each column, see
+/// [`VarCharNormalization::is_supported`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VarCharNormalization {
+    /// Strings are committed as they are.
+    #[default]
+    None,
+    /// Strings are committed in Normalization Form C (canonical composition).
+    Nfc,
+    /// Strings are committed in Normalization Form KC (compatibility composition).
+    ///
+    /// This additionally identifies compatibility variants, such as `ﬁ` and `fi`.
+    Nfkc,
+}
+
+impl VarCharNormalization {
+    /// Returns whether strings can be normalized this way in this build.
+    ///
+    /// [`VarCharNormalization::Nfc`] and [`VarCharNormalization::Nfkc`] require the
+    /// `varchar-normalization` feature. Without it, columns committed with them can still be
+    /// verified, but not compared against string literals or appended to.
+    pub fn is_supported(&self) -> bool {
+        *self == VarCharNormalization::None || cfg!(feature = "varchar-normalization")
+    }
+
+    /// Normalize a string, borrowing it if it is already normalized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the normalization is not supported, see [`VarCharNormalization::is_supported`].
+    pub fn normalize<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self {
+            VarCharNormalization::None => Cow::Borrowed(s),
+            #[cfg(feature = "varchar-normalization")]
+            VarCharNormalization::Nfc if !is_nfc(s) => Cow::Owned(s.nfc().collect()),
+            #[cfg(feature = "varchar-normalization")]
+            VarCharNormalization::Nfkc if !is_nfkc(s) => Cow::Owned(s.nfkc().collect()),
+            #[cfg(feature = "varchar-normalization")]
+            VarCharNormalization::Nfc | VarCharNormalization::Nfkc => Cow::Borrowed(s),
+            #[cfg(not(feature = "varchar-normalization"))]
+            VarCharNormalization::Nfc | VarCharNormalization::Nfkc => {
+                panic!("{self:?} normalization requires the `varchar-normalization` feature")
+            }
+        }
+    }
+
+    /// Normalize the strings of a VarChar column. Columns of other types are returned as they are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column is a VarChar column and the normalization is not supported, see
+    /// [`VarCharNormalization::is_supported`].
+    pub fn normalize_column<'a, S: Scalar>(
+        &self,
+        column: &'a OwnedColumn<S>,
+    ) -> Cow<'a, OwnedColumn<S>> {
+        match (self, column) {
+            (VarCharNormalization::Nfc | VarCharNormalization::Nfkc, OwnedColumn::VarChar(col)) => {
+                Cow::Owned(OwnedColumn::VarChar(
+                    col.iter().map(|s| self.normalize(s).into_owned()).collect(),
+                ))
+            }
+            _ => Cow::Borrowed(column),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "varchar-normalization"))]
+mod tests {
+    use super::*;
+    use crate::base::scalar::Curve25519Scalar;
+
+    const PRECOMPOSED: &str = "caf\u{e9}";
+    const DECOMPOSED: &str = "cafe\u{301}";
+
+    #[test]
+    fn we_can_normalize_canonically_equivalent_strings_to_the_same_string() {
+        assert_ne!(PRECOMPOSED, DECOMPOSED);
+        for normalization in [VarCharNormalization::Nfc, VarCharNormalization::Nfkc] {
+            assert_eq!(normalization.normalize(DECOMPOSED), PRECOMPOSED);
+            assert!(matches!(
+                normalization.normalize(PRECOMPOSED),
+                Cow::Borrowed(PRECOMPOSED)
+            ));
+        }
+        assert_eq!(VarCharNormalization::None.normalize(DECOMPOSED), DECOMPOSED);
+        assert!(VarCharNormalization::Nfc.is_supported());
+        assert!(VarCharNormalization::Nfkc.is_supported());
+    }
+
+    #[test]
+    fn only_nfkc_identifies_compatibility_variants() {
+        assert_eq!(
+            VarCharNormalization::Nfc.normalize("\u{fb01}le"),
+            "\u{fb01}le"
+        );
+        assert_eq!(VarCharNormalization::Nfkc.normalize("\u{fb01}le"), "file");
+    }
+
+    #[test]
+    fn we_can_normalize_only_varchar_columns() {
+        let column = OwnedColumn::<Curve25519Scalar>::VarChar(vec![
+            DECOMPOSED.to_string(),
+            "plain".to_string(),
+        ]);
+        assert_eq!(
+            VarCharNormalization::Nfc
+                .normalize_column(&column)
+                .into_owned(),
+            OwnedColumn::VarChar(vec![PRECOMPOSED.to_string(), "plain".to_string()])
+        );
+        assert_eq!(
+            VarCharNormalization::None
+                .normalize_column(&column)
+                .into_owned(),
+            column
+        );
+        let column = OwnedColumn::<Curve25519Scalar>::BigInt(vec![1, 2]);
+        assert!(matches!(
+            VarCharNormalization::Nfkc.normalize_column(&column),
+            Cow::Borrowed(_)
+        ));
+    }
+}
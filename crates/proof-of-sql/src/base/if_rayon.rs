@@ -0,0 +1,18 @@
+/// Evaluates to the first expression if the `rayon` feature is enabled and to the second one
+/// otherwise.
+///
+/// This is used to fall back to serial iterators in builds without rayon, such as verifiers that
+/// are compiled to WebAssembly.
+macro_rules! if_rayon {
+    ($rayon_value:expr, $else_value:expr) => {{
+        #[cfg(feature = "rayon")]
+        {
+            $rayon_value
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            $else_value
+        }
+    }};
+}
+pub(crate) use if_rayon;
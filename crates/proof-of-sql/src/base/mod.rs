@@ -1,13 +1,29 @@
 //! This module contains basic shared functionalities of the library.
 pub(crate) mod bit;
 pub mod commitment;
+#[cfg(feature = "rayon")]
+mod compute_config;
+#[cfg(feature = "rayon")]
+pub use compute_config::ComputeConfig;
+#[cfg(all(test, feature = "rayon"))]
+mod compute_config_test;
 pub mod database;
 pub(crate) mod encode;
+mod if_rayon;
+pub(crate) use if_rayon::if_rayon;
 pub mod math;
 pub(crate) mod polynomial;
 pub(crate) mod proof;
 pub(crate) mod ref_into;
 pub mod scalar;
 mod serialize;
-pub(crate) use serialize::{impl_serde_for_ark_serde_checked, impl_serde_for_ark_serde_unchecked};
+pub(crate) use serialize::{
+    deserialize_exact, impl_serde_for_ark_serde_checked, impl_serde_for_ark_serde_unchecked,
+};
 pub(crate) mod slice_ops;
+#[cfg(feature = "trace-export")]
+mod trace_export;
+#[cfg(feature = "trace-export")]
+pub use trace_export::{export_traces, TraceExportError, TraceExportGuard, TraceFormat};
+#[cfg(all(test, feature = "trace-export", feature = "blitzar"))]
+mod trace_export_test;
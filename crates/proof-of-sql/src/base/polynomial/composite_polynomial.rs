@@ -108,6 +108,7 @@ impl<S: Scalar> CompositePolynomial<S> {
             .sum();
         result
     }
+    #[cfg(feature = "tracing")]
     #[tracing::instrument(
         name = "CompositePolynomial::annotate_trace",
         level = "debug",
@@ -1,3 +1,4 @@
+use crate::base::scalar::Scalar;
 use core::ops::{Add, Mul, Sub};
 use num_traits::{One, Zero};
 
@@ -110,3 +111,62 @@ where
         }
     }
 }
+
+/// Given the point `point` (or `a`) with length nu, we can evaluate the lagrange basis of length 2^nu at that point.
+/// This is what [super::compute_evaluation_vector] does.
+/// Call the resulting evaluation vector A. This function computes `sum i * A[i] for i in 0..length`. That is:
+/// ```text
+/// 0 * (1-a[0])(1-a[1])...(1-a[nu-1]) +
+/// 1 * (a[0])(1-a[1])...(1-a[nu-1]) +
+/// 2 * (1-a[0])(a[1])...(1-a[nu-1]) +
+/// 3 * (a[0])(a[1])...(1-a[nu-1]) + ...
+/// ```
+/// In other words, this is the evaluation of the MLE of the row indexes `0, 1, ..., length - 1`.
+pub fn compute_truncated_lagrange_basis_index_sum<S: Scalar>(length: usize, point: &[S]) -> S {
+    compute_truncated_lagrange_basis_index_sum_impl(length, point).0
+}
+
+// The returned value from this function is (part, full, part_sum).
+// The full value is what the result would be if it were not truncated. (In other words, if length==2^nu.)
+// The part_sum value is the result of `compute_truncated_lagrange_basis_sum`, which is needed for the iteration.
+fn compute_truncated_lagrange_basis_index_sum_impl<S: Scalar>(
+    part_length: usize,
+    point: &[S],
+) -> (S, S, S) {
+    let nu = point.len();
+    if nu == 0 {
+        assert!(part_length <= 1);
+        // The only index is 0, so the index sums are always 0.
+        if part_length == 1 {
+            (S::zero(), S::zero(), S::one())
+        } else {
+            (S::zero(), S::zero(), S::zero())
+        }
+    } else {
+        let first_half_term = S::one() - point[nu - 1];
+        let second_half_term = point[nu - 1];
+        let half_full_length = 1 << (nu - 1);
+        let sub_part_length = if part_length >= half_full_length {
+            part_length - half_full_length
+        } else {
+            part_length
+        };
+        let (sub_part, sub_full, sub_part_sum) =
+            compute_truncated_lagrange_basis_index_sum_impl(sub_part_length, &point[..nu - 1]);
+
+        // Every index in the second half is offset by `half_full_length`.
+        let half_full_length_scalar = S::from(half_full_length as i128);
+        let (part, part_sum) = if part_length >= half_full_length {
+            (
+                sub_full * first_half_term
+                    + (sub_part + half_full_length_scalar * sub_part_sum) * second_half_term,
+                first_half_term + sub_part_sum * second_half_term,
+            )
+        } else {
+            (sub_part * first_half_term, sub_part_sum * first_half_term)
+        };
+        // The full lagrange basis sums to 1, so the full second half contributes `half_full_length`.
+        let full = sub_full + half_full_length_scalar * second_half_term;
+        (part, full, part_sum)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::base::{
     polynomial::{
-        compute_evaluation_vector, compute_truncated_lagrange_basis_inner_product,
-        compute_truncated_lagrange_basis_sum,
+        compute_evaluation_vector, compute_truncated_lagrange_basis_index_sum,
+        compute_truncated_lagrange_basis_inner_product, compute_truncated_lagrange_basis_sum,
     },
     scalar::Curve25519Scalar,
 };
@@ -401,3 +401,53 @@ fn compute_truncated_lagrange_basis_inner_product_matches_inner_product_of_resul
         // -----------------------------------------------------------
     }
 }
+
+#[test]
+fn compute_truncated_lagrange_basis_index_sum_gives_correct_values_with_2_variables() {
+    let point = vec![Curve25519Scalar::from(2u8), Curve25519Scalar::from(5u8)];
+    assert_eq!(
+        compute_truncated_lagrange_basis_index_sum(4, &point),
+        Curve25519Scalar::from(12u8) // This is 0*(1-2)(1-5)+1*(2)(1-5)+2*(1-2)(5)+3*(2)(5)
+    );
+    assert_eq!(
+        compute_truncated_lagrange_basis_index_sum(3, &point),
+        -Curve25519Scalar::from(18u8) // This is 0*(1-2)(1-5)+1*(2)(1-5)+2*(1-2)(5)
+    );
+    assert_eq!(
+        compute_truncated_lagrange_basis_index_sum(2, &point),
+        -Curve25519Scalar::from(8u8) // This is 0*(1-2)(1-5)+1*(2)(1-5)
+    );
+    assert_eq!(
+        compute_truncated_lagrange_basis_index_sum(1, &point),
+        Curve25519Scalar::from(0u8)
+    );
+    assert_eq!(
+        compute_truncated_lagrange_basis_index_sum(0, &point),
+        Curve25519Scalar::from(0u8)
+    );
+}
+
+#[test]
+fn compute_truncated_lagrange_basis_index_sum_matches_index_weighted_sum_of_compute_evaluation_vector(
+) {
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for variables in 0..6 {
+        let point: Vec<_> = iter::repeat_with(|| Curve25519Scalar::rand(&mut rng))
+            .take(variables)
+            .collect();
+        for length in 0..=(1 << variables) {
+            let mut eval_vec = vec![Curve25519Scalar::zero(); length];
+            compute_evaluation_vector(&mut eval_vec, &point);
+            assert_eq!(
+                compute_truncated_lagrange_basis_index_sum(length, &point),
+                eval_vec
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, x)| Curve25519Scalar::from(i as u64) * x)
+                    .sum()
+            );
+        }
+    }
+}
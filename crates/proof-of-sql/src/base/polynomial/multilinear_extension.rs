@@ -1,5 +1,6 @@
-use crate::base::{database::Column, scalar::Scalar, slice_ops};
+use crate::base::{database::Column, if_rayon, scalar::Scalar, slice_ops};
 use num_traits::Zero;
+#[cfg(feature = "rayon")]
 use rayon::iter::*;
 use std::{ffi::c_void, rc::Rc};
 
@@ -44,11 +45,18 @@ where
         let values = self;
         let n = 1 << num_vars;
         assert!(n >= values.len());
-        let scalars = values
-            .par_iter()
-            .map(|val| val.into())
-            .chain(rayon::iter::repeatn(Zero::zero(), n - values.len()))
-            .collect();
+        let scalars = if_rayon!(
+            values
+                .par_iter()
+                .map(|val| val.into())
+                .chain(rayon::iter::repeatn(Zero::zero(), n - values.len()))
+                .collect(),
+            values
+                .iter()
+                .map(|val| val.into())
+                .chain(std::iter::repeat(Zero::zero()).take(n - values.len()))
+                .collect()
+        );
         Rc::new(scalars)
     }
 
@@ -0,0 +1,63 @@
+use super::ProverError;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// A deadline and a step budget for proving a query.
+///
+/// The prover checks the deadline periodically, e.g. once per sumcheck round and between chunks of
+/// multi-scalar multiplications, and gives up with a [`ProverError`] once the deadline has passed
+/// or the budget of checks has been used up. A single step can still take a long time on huge
+/// inputs, so the prover may overrun the deadline by the duration of one step.
+///
+/// The default `ProverDeadline` never expires.
+#[derive(Debug, Default)]
+pub struct ProverDeadline {
+    deadline: Option<Instant>,
+    step_budget: Option<u64>,
+    steps_taken: AtomicU64,
+}
+
+impl ProverDeadline {
+    /// Create a `ProverDeadline` that never expires.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expire at the given instant.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Expire once the given amount of time has passed from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Expire once the prover has taken the given number of steps.
+    pub fn with_step_budget(mut self, step_budget: u64) -> Self {
+        self.step_budget = Some(step_budget);
+        self
+    }
+
+    /// Returns the number of steps the prover has taken so far.
+    pub fn steps_taken(&self) -> u64 {
+        self.steps_taken.load(Ordering::Relaxed)
+    }
+
+    /// Take a step, failing if the deadline has passed or the step budget is used up.
+    pub fn check(&self) -> Result<(), ProverError> {
+        let steps_taken = self.steps_taken.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(step_budget) = self.step_budget {
+            if steps_taken > step_budget {
+                return Err(ProverError::StepBudgetExhausted { step_budget });
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(ProverError::Timeout);
+            }
+        }
+        Ok(())
+    }
+}
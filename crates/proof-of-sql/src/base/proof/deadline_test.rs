@@ -0,0 +1,30 @@
+use super::{ProverDeadline, ProverError};
+use std::time::{Duration, Instant};
+
+#[test]
+fn the_default_deadline_never_expires() {
+    let deadline = ProverDeadline::new();
+    for _ in 0..1000 {
+        assert!(deadline.check().is_ok());
+    }
+    assert_eq!(deadline.steps_taken(), 1000);
+}
+
+#[test]
+fn we_cannot_take_more_steps_than_the_budget() {
+    let deadline = ProverDeadline::new().with_step_budget(2);
+    assert!(deadline.check().is_ok());
+    assert!(deadline.check().is_ok());
+    assert!(matches!(
+        deadline.check(),
+        Err(ProverError::StepBudgetExhausted { step_budget: 2 })
+    ));
+}
+
+#[test]
+fn we_cannot_take_steps_after_the_deadline() {
+    let deadline = ProverDeadline::new().with_deadline(Instant::now());
+    assert!(matches!(deadline.check(), Err(ProverError::Timeout)));
+    let deadline = ProverDeadline::new().with_timeout(Duration::from_secs(3600));
+    assert!(deadline.check().is_ok());
+}
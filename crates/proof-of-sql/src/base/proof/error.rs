@@ -1,3 +1,4 @@
+use crate::base::database::AccessorError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -7,3 +8,38 @@ pub enum ProofError {
     /// This error occurs when a proof failed to verify.
     VerificationError(&'static str),
 }
+
+#[derive(Error, Debug, PartialEq, Eq)]
+/// These errors occur when the prover gives up on a proof before finishing it.
+///
+/// See [`ProverDeadline`](super::ProverDeadline).
+pub enum ProverError {
+    #[error("The prover did not finish before its deadline")]
+    /// This error occurs when the prover's deadline has passed.
+    Timeout,
+    #[error("The prover used up its budget of {step_budget} steps")]
+    /// This error occurs when the prover has taken more steps than its budget allows.
+    StepBudgetExhausted {
+        /// The configured step budget
+        step_budget: u64,
+    },
+    #[error("The receiver of the streamed result hung up")]
+    /// This error occurs when the result is streamed to a receiver that was dropped.
+    ///
+    /// See [`ResultStreamEncoder`](crate::sql::proof::ResultStreamEncoder).
+    ResultStreamClosed,
+    #[error(transparent)]
+    /// This error occurs when the query references a table or column that the accessor doesn't
+    /// have, so that the query cannot be proven.
+    Accessor(#[from] AccessorError),
+    #[error("{gadget} overflowed in row {row}")]
+    /// This error occurs when an arithmetic gadget computes a value that does not fit in the type
+    /// of its result, and the query was planned with
+    /// [`OverflowPolicy::Error`](crate::sql::ast::OverflowPolicy::Error).
+    Overflow {
+        /// The gadget that overflowed
+        gadget: &'static str,
+        /// The first row that overflowed
+        row: usize,
+    },
+}
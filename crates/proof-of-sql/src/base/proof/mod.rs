@@ -1,10 +1,25 @@
 //! Contains the transcript protocol used to construct a proof,
-//! as well as an error type which can occur when verification fails.
+//! as well as error types which can occur when proving is interrupted or verification fails.
 mod error;
-pub use error::ProofError;
+pub use error::{ProofError, ProverError};
+
+mod deadline;
+pub use deadline::ProverDeadline;
+#[cfg(test)]
+mod deadline_test;
 
 /// Contains an extension trait for `merlin::Transcript`, which is used to construct a proof.
 mod transcript_protocol;
 #[cfg(test)]
 mod transcript_protocol_test;
 pub use transcript_protocol::{MessageLabel, TranscriptProtocol};
+
+#[cfg(feature = "transcript-replay")]
+mod transcript_replay;
+#[cfg(feature = "transcript-replay")]
+pub use transcript_replay::{
+    record_transcript, TranscriptChallenge, TranscriptDivergence, TranscriptLog, TranscriptStep,
+    TranscriptStepKind,
+};
+#[cfg(all(test, feature = "blitzar", feature = "transcript-replay"))]
+mod transcript_replay_test;
@@ -46,14 +46,20 @@ pub trait TranscriptProtocol {
     );
 
     /// Compute multiple challenge values of a type that extends `ark_std::UniformRand` (which requires a label). This generalizes `challenge_curve25519_scalars`.
-    fn challenge_scalars<'a, U: ark_std::UniformRand + 'a>(
+    fn challenge_scalars<'a, U: ark_std::UniformRand + CanonicalSerialize + 'a>(
         &mut self,
         buf: impl IntoIterator<Item = &'a mut U>,
         label: MessageLabel,
     );
 
+    /// Fill `dest` with challenge bytes (which requires a label).
+    ///
+    /// Unlike [`Transcript::challenge_bytes`], the challenge shows up in the logs of
+    /// `record_transcript` with the `transcript-replay` feature.
+    fn challenge_label_bytes(&mut self, label: MessageLabel, dest: &mut [u8]);
+
     /// Compute a challenge variable (which requires a label).
-    fn challenge_scalar_single<U: ark_std::UniformRand + Default>(
+    fn challenge_scalar_single<U: ark_std::UniformRand + CanonicalSerialize + Default>(
         &mut self,
         label: MessageLabel,
     ) -> U {
@@ -65,7 +71,15 @@ pub trait TranscriptProtocol {
 
 impl TranscriptProtocol for Transcript {
     fn append_auto(&mut self, label: MessageLabel, message: &(impl serde::Serialize + ?Sized)) {
-        self.append_message(label.as_bytes(), &postcard::to_allocvec(message).unwrap());
+        let buf = postcard::to_allocvec(message).unwrap();
+        #[cfg(feature = "transcript-replay")]
+        super::transcript_replay::record_step(
+            label.as_bytes(),
+            super::TranscriptStepKind::Message,
+            &buf,
+            Vec::new(),
+        );
+        self.append_message(label.as_bytes(), &buf);
     }
 
     fn append_canonical_serialize(
@@ -75,16 +89,23 @@ impl TranscriptProtocol for Transcript {
     ) {
         let mut buf = vec![Default::default(); message.compressed_size()];
         message.serialize_compressed(&mut buf).unwrap();
+        #[cfg(feature = "transcript-replay")]
+        super::transcript_replay::record_step(
+            label.as_bytes(),
+            super::TranscriptStepKind::Message,
+            &buf,
+            Vec::new(),
+        );
         self.append_message(label.as_bytes(), &buf);
     }
 
-    fn challenge_scalars<'a, U: ark_std::UniformRand + 'a>(
+    fn challenge_scalars<'a, U: ark_std::UniformRand + CanonicalSerialize + 'a>(
         &mut self,
         buf: impl IntoIterator<Item = &'a mut U>,
         label: MessageLabel,
     ) {
         self.append_message(label.as_bytes(), &[]);
-        struct TranscriptProtocolRng<'a>(&'a mut Transcript);
+        struct TranscriptProtocolRng<'a>(&'a mut Transcript, Vec<u8>);
         impl<'a> ark_std::rand::RngCore for TranscriptProtocolRng<'a> {
             fn next_u32(&mut self) -> u32 {
                 let mut buf = [0u8; 4];
@@ -98,16 +119,43 @@ impl TranscriptProtocol for Transcript {
             }
             fn fill_bytes(&mut self, dest: &mut [u8]) {
                 self.0.challenge_bytes(&[], dest);
+                if cfg!(feature = "transcript-replay") {
+                    self.1.extend_from_slice(dest);
+                }
             }
             fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), ark_std::rand::Error> {
                 self.fill_bytes(dest);
                 Ok(())
             }
         }
-        let rng = &mut TranscriptProtocolRng(self);
+        let rng = &mut TranscriptProtocolRng(self, Vec::new());
+        let mut values = Vec::new();
         for val in buf {
             *val = ark_ff::UniformRand::rand(rng);
+            if cfg!(feature = "transcript-replay") {
+                let mut value = vec![0; val.compressed_size()];
+                val.serialize_compressed(&mut value).unwrap();
+                values.push(value);
+            }
         }
+        #[cfg(feature = "transcript-replay")]
+        super::transcript_replay::record_step(
+            label.as_bytes(),
+            super::TranscriptStepKind::Challenge,
+            &rng.1,
+            values,
+        );
+    }
+
+    fn challenge_label_bytes(&mut self, label: MessageLabel, dest: &mut [u8]) {
+        self.challenge_bytes(label.as_bytes(), dest);
+        #[cfg(feature = "transcript-replay")]
+        super::transcript_replay::record_step(
+            label.as_bytes(),
+            super::TranscriptStepKind::Challenge,
+            dest,
+            vec![dest.to_vec()],
+        );
     }
 }
 
@@ -146,6 +194,8 @@ pub enum MessageLabel {
     DoryMessage,
     /// Represents a challenge in the context of the Dory protocol.
     DoryChallenge,
+    /// Represents a message in the context of the Pedersen evaluation proof.
+    PedersenMessage,
     /// Represents challenges posted after result computation.
     PostResultChallenges,
     /// Represents a SQL query
@@ -154,6 +204,23 @@ pub enum MessageLabel {
     TableLength,
     /// Represents an offset for a generator.
     GeneratorOffset,
+    /// Represents the values bound from the evaluation context.
+    EvaluationContext,
+    /// Represents the fingerprint of a prover's transcript that is stored in a checkpoint.
+    ProverCheckpoint,
+    /// Represents the sampling of rows of a committed result.
+    ResultSample,
+    /// Represents the commitment to the rows of a result.
+    ResultCommitment,
+    /// Represents a challenge that chooses a row of a committed result.
+    ResultSampleChallenge,
+    /// Represents the check that the data of an accessor matches its commitments.
+    AccessorConsistency,
+    /// Represents a challenge that weighs the columns in an accessor consistency check.
+    AccessorConsistencyChallenge,
+    /// Represents a challenge that is drawn after the proof of a test vector.
+    #[cfg(any(test, feature = "test"))]
+    TestVectorChallenge,
 }
 
 impl MessageLabel {
@@ -178,10 +245,20 @@ impl MessageLabel {
             MessageLabel::VerificationHash => b"verificationhash v1",
             MessageLabel::DoryMessage => b"dorymessage v1",
             MessageLabel::DoryChallenge => b"dorychallenge v1",
+            MessageLabel::PedersenMessage => b"pedersenmessage v1",
             MessageLabel::PostResultChallenges => b"postresultchallenges v1",
             MessageLabel::ProofExpr => b"proofexpr v1",
             MessageLabel::TableLength => b"tablelength v1",
             MessageLabel::GeneratorOffset => b"generatoroffset v1",
+            MessageLabel::EvaluationContext => b"evaluationcontext v1",
+            MessageLabel::ProverCheckpoint => b"provercheckpoint v1",
+            MessageLabel::ResultSample => b"resultsample v1",
+            MessageLabel::ResultCommitment => b"resultcommitment v1",
+            MessageLabel::ResultSampleChallenge => b"resultsamplechallenge v1",
+            MessageLabel::AccessorConsistency => b"accessorconsistency v1",
+            MessageLabel::AccessorConsistencyChallenge => b"accessorconsistencychallenge v1",
+            #[cfg(any(test, feature = "test"))]
+            MessageLabel::TestVectorChallenge => b"testvectorchallenge v1",
         }
     }
 }
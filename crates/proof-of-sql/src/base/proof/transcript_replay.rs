@@ -0,0 +1,189 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, collections::HashMap};
+
+thread_local! {
+    /// The steps recorded by the innermost [`record_transcript`] on this thread, if any.
+    static RECORDER: RefCell<Option<Vec<TranscriptStep>>> = RefCell::new(None);
+}
+
+/// Whether a step of a transcript absorbed a message or squeezed a challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TranscriptStepKind {
+    /// A message that was appended to the transcript.
+    Message,
+    /// A challenge that was drawn from the transcript.
+    Challenge,
+}
+
+/// A message or challenge of a transcript, see [`record_transcript`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptStep {
+    /// The label of the step, i.e. [`MessageLabel::as_bytes`](super::MessageLabel::as_bytes).
+    pub label: String,
+    /// Whether the step is a message or a challenge.
+    pub kind: TranscriptStepKind,
+    /// The serialized message, or the bytes that the challenge was drawn from.
+    pub data: Vec<u8>,
+    /// The canonically serialized values of the challenge, or nothing for a message.
+    #[serde(default)]
+    pub values: Vec<Vec<u8>>,
+}
+
+/// The values of a Fiat–Shamir challenge of a transcript, see [`TranscriptLog::challenges`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptChallenge {
+    /// The label of the challenge, i.e. [`MessageLabel::as_bytes`](super::MessageLabel::as_bytes).
+    pub label: String,
+    /// How many earlier challenges have the same label, e.g. the round of a sumcheck.
+    pub round: usize,
+    /// The canonically serialized values that were drawn, e.g. one per sumcheck variable.
+    ///
+    /// Challenges that are used as bytes, such as the row indexes of a result sample, have a single
+    /// value with those bytes.
+    pub values: Vec<Vec<u8>>,
+}
+
+/// The steps of the transcripts of a prover or a verifier, in order.
+///
+/// A prover can record its log with [`record_transcript`] and send it along with the proof, so
+/// that a verifier that rejects the proof can find where its own transcript diverged with
+/// [`TranscriptLog::first_divergence`]. Since the challenges depend on everything before them,
+/// every step after the first divergence differs as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptLog {
+    steps: Vec<TranscriptStep>,
+}
+
+impl TranscriptLog {
+    /// Returns the steps of the log.
+    pub fn steps(&self) -> &[TranscriptStep] {
+        &self.steps
+    }
+
+    /// Returns the Fiat–Shamir challenges of the log, in the order they were drawn.
+    ///
+    /// This lets a reviewer or a secondary verifier audit every challenge that a proof consumed,
+    /// e.g. by exporting them with `serde`, and check them against an independent derivation.
+    pub fn challenges(&self) -> Vec<TranscriptChallenge> {
+        let mut rounds = HashMap::<&str, usize>::new();
+        self.steps
+            .iter()
+            .filter(|step| step.kind == TranscriptStepKind::Challenge)
+            .map(|step| {
+                let round = rounds.entry(&step.label).or_default();
+                *round += 1;
+                TranscriptChallenge {
+                    label: step.label.clone(),
+                    round: *round - 1,
+                    values: step.values.clone(),
+                }
+            })
+            .collect()
+    }
+
+    /// Replays the log of a prover next to the log of a verifier and returns the first step where
+    /// they differ, or `None` if the logs are the same.
+    pub fn first_divergence(&self, verifier: &TranscriptLog) -> Option<TranscriptDivergence> {
+        let len = self.steps.len().max(verifier.steps.len());
+        let step = (0..len).find(|&i| self.steps.get(i) != verifier.steps.get(i))?;
+        let prover = self.steps.get(step).cloned();
+        let verifier = verifier.steps.get(step).cloned();
+        let label = prover
+            .as_ref()
+            .or(verifier.as_ref())
+            .expect("one of the logs has the step")
+            .label
+            .clone();
+        let round = self.steps[..step.min(self.steps.len())]
+            .iter()
+            .filter(|earlier| earlier.label == label)
+            .count();
+        Some(TranscriptDivergence {
+            step,
+            label,
+            round,
+            prover,
+            verifier,
+        })
+    }
+}
+
+/// The first step where the transcripts of a prover and a verifier differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptDivergence {
+    /// The index of the step in the logs.
+    pub step: usize,
+    /// The label of the step of the prover, or of the verifier if the prover's log ended.
+    pub label: String,
+    /// How many earlier steps of the prover have the same label, e.g. the round of a sumcheck.
+    pub round: usize,
+    /// The step of the prover, or `None` if its log ended before this step.
+    pub prover: Option<TranscriptStep>,
+    /// The step of the verifier, or `None` if its log ended before this step.
+    pub verifier: Option<TranscriptStep>,
+}
+
+impl fmt::Display for TranscriptDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "transcripts diverge at step {} ('{}', round {}): prover ",
+            self.step, self.label, self.round
+        )?;
+        write_step(f, self.prover.as_ref())?;
+        write!(f, ", verifier ")?;
+        write_step(f, self.verifier.as_ref())
+    }
+}
+
+fn write_step(f: &mut fmt::Formatter<'_>, step: Option<&TranscriptStep>) -> fmt::Result {
+    let Some(step) = step else {
+        return write!(f, "ended");
+    };
+    write!(f, "{:?} '{}' ", step.kind, step.label)?;
+    step.data
+        .iter()
+        .try_for_each(|byte| write!(f, "{byte:02x}"))
+}
+
+/// Records every step of the transcripts that `f` appends to or draws challenges from on the
+/// current thread, and returns the result of `f` along with the log.
+///
+/// This is meant for debugging proofs that fail to verify, e.g. wrapping both
+/// [`VerifiableQueryResult::new`](crate::sql::proof::VerifiableQueryResult::new) and
+/// [`VerifiableQueryResult::verify`](crate::sql::proof::VerifiableQueryResult::verify), and is
+/// only available with the `transcript-replay` feature.
+pub fn record_transcript<T>(f: impl FnOnce() -> T) -> (T, TranscriptLog) {
+    /// Restores the recorder of an enclosing `record_transcript`, even if `f` panics.
+    struct Restore(Option<Vec<TranscriptStep>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            RECORDER.with(|recorder| *recorder.borrow_mut() = self.0.take());
+        }
+    }
+
+    let _restore = Restore(RECORDER.with(|recorder| recorder.replace(Some(Vec::new()))));
+    let res = f();
+    let steps = RECORDER.with(|recorder| recorder.borrow_mut().take().unwrap_or_default());
+    (res, TranscriptLog { steps })
+}
+
+/// Record a step of a transcript if [`record_transcript`] is running on this thread.
+pub(super) fn record_step(
+    label: &[u8],
+    kind: TranscriptStepKind,
+    data: &[u8],
+    values: Vec<Vec<u8>>,
+) {
+    RECORDER.with(|recorder| {
+        if let Some(steps) = recorder.borrow_mut().as_mut() {
+            steps.push(TranscriptStep {
+                label: String::from_utf8_lossy(label).into_owned(),
+                kind,
+                data: data.to_vec(),
+                values,
+            });
+        }
+    });
+}
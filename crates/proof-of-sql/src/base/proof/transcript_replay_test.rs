@@ -0,0 +1,154 @@
+use super::{record_transcript, MessageLabel, TranscriptProtocol, TranscriptStepKind};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::{test_utility::*, ProofPlan},
+        proof::VerifiableQueryResult,
+    },
+};
+use ark_serialize::CanonicalSerialize;
+use curve25519_dalek::RistrettoPoint;
+use merlin::Transcript;
+
+#[test]
+fn the_transcripts_of_an_honest_prover_and_verifier_do_not_diverge() {
+    let t = "sxt.t".parse().unwrap();
+    let data = owned_table([bigint("a", [1_i64, 2, 3, 2]), bigint("b", [5_i64, 6, 7, 8])]);
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: ProofPlan<RistrettoPoint> = dense_filter(
+        cols_expr_plan(t, &["b"], &accessor),
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(2)),
+    );
+    let (verifiable_res, prover_log) =
+        record_transcript(|| VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &()));
+    let (res, verifier_log) = record_transcript(|| verifiable_res.verify(&ast, &accessor, &()));
+    assert!(res.is_ok());
+    assert!(!prover_log.steps().is_empty());
+    assert_eq!(prover_log.first_divergence(&verifier_log), None);
+    assert_eq!(prover_log.challenges(), verifier_log.challenges());
+    assert!(verifier_log
+        .challenges()
+        .iter()
+        .any(|challenge| challenge.label == "querysumcheckchallenge v1"));
+}
+
+#[test]
+fn we_can_find_where_a_verifier_with_a_different_plan_diverges() {
+    let t = "sxt.t".parse().unwrap();
+    let data = owned_table([bigint("a", [1_i64, 2, 3, 2]), bigint("b", [5_i64, 6, 7, 8])]);
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = |threshold| -> ProofPlan<RistrettoPoint> {
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(threshold)),
+        )
+    };
+    let (verifiable_res, prover_log) = record_transcript(|| {
+        VerifiableQueryResult::<InnerProductProof>::new(&ast(2), &accessor, &())
+    });
+    let (res, verifier_log) = record_transcript(|| verifiable_res.verify(&ast(3), &accessor, &()));
+    assert!(res.is_err());
+    let divergence = prover_log.first_divergence(&verifier_log).unwrap();
+    // The result is the same for both, but the plans differ
+    assert_eq!(divergence.step, 1);
+    assert_eq!(divergence.label, "proofexpr v1");
+    assert_eq!(divergence.round, 0);
+    assert_eq!(
+        divergence.prover.unwrap().kind,
+        divergence.verifier.unwrap().kind
+    );
+}
+
+#[test]
+fn we_can_find_the_round_where_transcripts_diverge() {
+    let transcript_with = |messages: &[u64], num_challenges: usize| {
+        record_transcript(|| {
+            let mut transcript = Transcript::new(b"test");
+            for message in messages {
+                transcript.append_auto(MessageLabel::SumcheckRoundEvaluation, message);
+                let _: Curve25519Scalar =
+                    transcript.challenge_scalar_single(MessageLabel::SumcheckChallenge);
+            }
+            for _ in 0..num_challenges {
+                let _: Curve25519Scalar =
+                    transcript.challenge_scalar_single(MessageLabel::VerificationHash);
+            }
+        })
+        .1
+    };
+    let prover_log = transcript_with(&[1, 2, 3], 1);
+    assert_eq!(prover_log.steps().len(), 7);
+    assert_eq!(prover_log.steps()[1].kind, TranscriptStepKind::Challenge);
+    assert_eq!(
+        prover_log.first_divergence(&transcript_with(&[1, 2, 3], 1)),
+        None
+    );
+
+    let divergence = prover_log
+        .first_divergence(&transcript_with(&[1, 2, 4], 1))
+        .unwrap();
+    assert_eq!(divergence.step, 4);
+    assert_eq!(divergence.label, "sumcheckroundevaluationscalars v1");
+    assert_eq!(divergence.round, 2);
+    assert!(divergence.to_string().starts_with(
+        "transcripts diverge at step 4 ('sumcheckroundevaluationscalars v1', round 2)"
+    ));
+
+    let divergence = prover_log
+        .first_divergence(&transcript_with(&[1, 2, 3], 0))
+        .unwrap();
+    assert_eq!(divergence.step, 6);
+    assert_eq!(divergence.verifier, None);
+    assert!(divergence.to_string().ends_with("verifier ended"));
+}
+
+#[test]
+fn recording_a_transcript_does_not_change_it() {
+    let challenge = || {
+        let mut transcript = Transcript::new(b"test");
+        transcript.append_auto(MessageLabel::QueryResultData, &[1_u8, 2, 3]);
+        transcript.challenge_scalar_single::<Curve25519Scalar>(MessageLabel::SumcheckChallenge)
+    };
+    let (recorded, log) = record_transcript(challenge);
+    assert_eq!(recorded, challenge());
+    let (_, nested_log) = record_transcript(|| record_transcript(challenge));
+    assert!(nested_log.steps().is_empty());
+    assert_eq!(log.steps().len(), 2);
+}
+
+#[test]
+fn we_can_export_the_challenges_of_a_transcript() {
+    let ((scalars, sample), log) = record_transcript(|| {
+        let mut transcript = Transcript::new(b"test");
+        let mut scalars = Vec::new();
+        for message in [1_u64, 2] {
+            transcript.append_auto(MessageLabel::SumcheckRoundEvaluation, &message);
+            scalars.push(
+                transcript
+                    .challenge_scalar_single::<Curve25519Scalar>(MessageLabel::SumcheckChallenge),
+            );
+        }
+        let mut sample = [0; 8];
+        transcript.challenge_label_bytes(MessageLabel::ResultSampleChallenge, &mut sample);
+        (scalars, sample)
+    });
+    let challenges = log.challenges();
+    assert_eq!(challenges.len(), 3);
+    for (round, (challenge, scalar)) in challenges.iter().zip(scalars).enumerate() {
+        let mut value = Vec::new();
+        scalar.serialize_compressed(&mut value).unwrap();
+        assert_eq!(challenge.label, "sumcheckchallenge v1");
+        assert_eq!(challenge.round, round);
+        assert_eq!(challenge.values, [value]);
+    }
+    assert_eq!(challenges[2].label, "resultsamplechallenge v1");
+    assert_eq!(challenges[2].round, 0);
+    assert_eq!(challenges[2].values, [sample.to_vec()]);
+    assert!(log.steps()[0].values.is_empty());
+}
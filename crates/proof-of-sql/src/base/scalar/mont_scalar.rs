@@ -1,4 +1,4 @@
-use super::{scalar_conversion_to_int, Scalar, ScalarConversionError};
+use super::{Scalar, ScalarConversionError};
 use crate::base::math::decimal::MAX_SUPPORTED_PRECISION;
 use ark_ff::{BigInteger, Field, Fp, Fp256, MontBackend, MontConfig, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -18,6 +18,9 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// A wrapper struct around a `Fp256<MontBackend<T, 4>>` that can easily implement the `Scalar` trait.
 ///
 /// Using the `Scalar` trait rather than this type is encouraged to allow for easier switching of the underlying field.
+///
+/// Any 256-bit prime field that arkworks implements with a `MontConfig<4>` is a `Scalar`, so the proof system can
+/// be instantiated over the scalar field of another curve by choosing `T`, e.g. `Bn254Scalar` with the `bn254` feature.
 #[repr(transparent)]
 pub struct MontScalar<T: MontConfig<4>>(pub Fp256<MontBackend<T, 4>>);
 
@@ -119,6 +122,9 @@ impl<T: MontConfig<4>> Ord for MontScalar<T> {
 ///
 /// Using the `Scalar` trait rather than this type is encouraged to allow for easier switching of the underlying field.
 pub type Curve25519Scalar = MontScalar<ark_curve25519::FrConfig>;
+/// The scalar field of the BN254 curve. (alias for `MontScalar<ark_bn254::FrConfig>`)
+#[cfg(feature = "bn254")]
+pub type Bn254Scalar = MontScalar<ark_bn254::FrConfig>;
 
 impl<T: MontConfig<4>> MontScalar<T> {
     /// Convenience function for creating a new `MontScalar<T>` from the underlying `Fp256<MontBackend<T, 4>>`. Should only be used in tests.
@@ -254,9 +260,10 @@ impl<T: MontConfig<4>> Serialize for MontScalar<T> {
 }
 impl<'de, T: MontConfig<4>> Deserialize<'de> for MontScalar<T> {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        CanonicalDeserialize::deserialize_compressed(Vec::deserialize(deserializer)?.as_slice())
-            .map_err(serde::de::Error::custom)
-            .map(Self)
+        crate::base::deserialize_exact(deserializer, |bytes| {
+            CanonicalDeserialize::deserialize_compressed(bytes)
+        })
+        .map(Self)
     }
 }
 
@@ -356,13 +363,164 @@ impl<T: MontConfig<4>> Display for MontScalar<T> {
     }
 }
 
-impl super::Scalar for Curve25519Scalar {
-    const MAX_SIGNED: Self = Self(ark_ff::MontFp!(
-        "3618502788666131106986593281521497120428558179689953803000975469142727125494"
+/// The constants are computed from the modulus of the field, so that this holds for any field.
+impl<T: MontConfig<4>> Scalar for MontScalar<T> {
+    const MAX_SIGNED: Self = Self(Fp::new(
+        <Fp256<MontBackend<T, 4>> as PrimeField>::MODULUS_MINUS_ONE_DIV_TWO,
     ));
-    const ZERO: Self = Self(ark_ff::MontFp!("0"));
-    const ONE: Self = Self(ark_ff::MontFp!("1"));
-    const TWO: Self = Self(ark_ff::MontFp!("2"));
+    const ZERO: Self = Self(Fp::new(ark_ff::BigInt([0, 0, 0, 0])));
+    const ONE: Self = Self(Fp::new(ark_ff::BigInt([1, 0, 0, 0])));
+    const TWO: Self = Self(Fp::new(ark_ff::BigInt([2, 0, 0, 0])));
+}
+
+impl<T: MontConfig<4>> TryFrom<MontScalar<T>> for bool {
+    type Error = ScalarConversionError;
+    fn try_from(value: MontScalar<T>) -> Result<Self, Self::Error> {
+        let (sign, abs): (i128, [u64; 4]) = if value > MontScalar::<T>::MAX_SIGNED {
+            (-1, (-value).into())
+        } else {
+            (1, value.into())
+        };
+        if abs[1] != 0 || abs[2] != 0 || abs[3] != 0 {
+            return Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i8",
+                value
+            )));
+        }
+        let val: i128 = sign * abs[0] as i128;
+        match val {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in a bool",
+                value
+            ))),
+        }
+    }
+}
+
+impl<T: MontConfig<4>> TryFrom<MontScalar<T>> for i8 {
+    type Error = ScalarConversionError;
+    fn try_from(value: MontScalar<T>) -> Result<Self, Self::Error> {
+        let (sign, abs): (i128, [u64; 4]) = if value > MontScalar::<T>::MAX_SIGNED {
+            (-1, (-value).into())
+        } else {
+            (1, value.into())
+        };
+        if abs[1] != 0 || abs[2] != 0 || abs[3] != 0 {
+            return Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i8",
+                value
+            )));
+        }
+        let val: i128 = sign * abs[0] as i128;
+        val.try_into().map_err(|_| {
+            ScalarConversionError::Overflow(format!("{} is too large to fit in an i8", value))
+        })
+    }
+}
+
+impl<T: MontConfig<4>> TryFrom<MontScalar<T>> for i16 {
+    type Error = ScalarConversionError;
+    fn try_from(value: MontScalar<T>) -> Result<Self, Self::Error> {
+        let (sign, abs): (i128, [u64; 4]) = if value > MontScalar::<T>::MAX_SIGNED {
+            (-1, (-value).into())
+        } else {
+            (1, value.into())
+        };
+        if abs[1] != 0 || abs[2] != 0 || abs[3] != 0 {
+            return Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i16",
+                value
+            )));
+        }
+        let val: i128 = sign * abs[0] as i128;
+        val.try_into().map_err(|_| {
+            ScalarConversionError::Overflow(format!("{} is too large to fit in an i16", value))
+        })
+    }
 }
 
-scalar_conversion_to_int!(Curve25519Scalar);
+impl<T: MontConfig<4>> TryFrom<MontScalar<T>> for i32 {
+    type Error = ScalarConversionError;
+    fn try_from(value: MontScalar<T>) -> Result<Self, Self::Error> {
+        let (sign, abs): (i128, [u64; 4]) = if value > MontScalar::<T>::MAX_SIGNED {
+            (-1, (-value).into())
+        } else {
+            (1, value.into())
+        };
+        if abs[1] != 0 || abs[2] != 0 || abs[3] != 0 {
+            return Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i32",
+                value
+            )));
+        }
+        let val: i128 = sign * abs[0] as i128;
+        val.try_into().map_err(|_| {
+            ScalarConversionError::Overflow(format!("{} is too large to fit in an i32", value))
+        })
+    }
+}
+
+impl<T: MontConfig<4>> TryFrom<MontScalar<T>> for i64 {
+    type Error = ScalarConversionError;
+    fn try_from(value: MontScalar<T>) -> Result<Self, Self::Error> {
+        let (sign, abs): (i128, [u64; 4]) = if value > MontScalar::<T>::MAX_SIGNED {
+            (-1, (-value).into())
+        } else {
+            (1, value.into())
+        };
+        if abs[1] != 0 || abs[2] != 0 || abs[3] != 0 {
+            return Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i64",
+                value
+            )));
+        }
+        let val: i128 = sign * abs[0] as i128;
+        val.try_into().map_err(|_| {
+            ScalarConversionError::Overflow(format!("{} is too large to fit in an i64", value))
+        })
+    }
+}
+
+impl<T: MontConfig<4>> TryFrom<MontScalar<T>> for i128 {
+    type Error = ScalarConversionError;
+    fn try_from(value: MontScalar<T>) -> Result<Self, Self::Error> {
+        let (sign, abs): (i128, [u64; 4]) = if value > MontScalar::<T>::MAX_SIGNED {
+            (-1, (-value).into())
+        } else {
+            (1, value.into())
+        };
+        if abs[2] != 0 || abs[3] != 0 {
+            return Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i128",
+                value
+            )));
+        }
+        let val: u128 = (abs[1] as u128) << 64 | (abs[0] as u128);
+        match (sign, val) {
+            (1, v) if v <= i128::MAX as u128 => Ok(v as i128),
+            (-1, v) if v <= i128::MAX as u128 => Ok(-(v as i128)),
+            (-1, v) if v == i128::MAX as u128 + 1 => Ok(i128::MIN),
+            _ => Err(ScalarConversionError::Overflow(format!(
+                "{} is too large to fit in an i128",
+                value
+            ))),
+        }
+    }
+}
+
+impl<T: MontConfig<4>> From<MontScalar<T>> for BigInt {
+    fn from(value: MontScalar<T>) -> Self {
+        // Since we wrap around in finite fields anything greater than the max signed value is negative
+        let is_negative = value > MontScalar::<T>::MAX_SIGNED;
+        let sign = if is_negative {
+            num_bigint::Sign::Minus
+        } else {
+            num_bigint::Sign::Plus
+        };
+        let value_abs: [u64; 4] = (if is_negative { -value } else { value }).into();
+        let bits: &[u8] = bytemuck::cast_slice(&value_abs);
+        BigInt::from_bytes_le(sign, &bits)
+    }
+}
@@ -1,6 +1,8 @@
-use crate::base::scalar::{Curve25519Scalar, Scalar, ScalarConversionError};
+#[cfg(feature = "bn254")]
+use crate::base::scalar::Bn254Scalar;
+use crate::base::scalar::{Curve25519Scalar, MontScalar, Scalar, ScalarConversionError};
 use num_bigint::BigInt;
-use num_traits::{Inv, One};
+use num_traits::{Inv, One, Zero};
 
 #[test]
 fn test_dalek_interop_1() {
@@ -59,6 +61,23 @@ fn test_curve25519_scalar_serialization() {
     assert_eq!(s, deserialized);
 }
 
+#[test]
+fn we_cannot_deserialize_non_canonical_curve25519_scalars() {
+    let bytes = postcard::to_allocvec(&Curve25519Scalar::from(123)).unwrap();
+    assert_eq!(
+        postcard::from_bytes::<Curve25519Scalar>(&bytes).unwrap(),
+        Curve25519Scalar::from(123)
+    );
+
+    // Trailing bytes, missing bytes, and a value that is not reduced by the modulus
+    let mut trailing = vec![1_u8; 32];
+    trailing.push(0);
+    for encoding in [trailing, vec![0_u8; 31], vec![0xff_u8; 32]] {
+        let bytes = postcard::to_allocvec(&encoding).unwrap();
+        assert!(postcard::from_bytes::<Curve25519Scalar>(&bytes).is_err());
+    }
+}
+
 #[test]
 fn test_curve25519_scalar_display() {
     assert_eq!(
@@ -290,3 +309,63 @@ fn test_curve25519_scalar_from_bigint() {
         -Curve25519Scalar::ONE
     );
 }
+
+#[test]
+fn the_scalar_constants_match_the_constants_of_the_fields() {
+    assert_eq!(
+        Curve25519Scalar::MAX_SIGNED,
+        Curve25519Scalar::new(ark_ff::MontFp!(
+            "3618502788666131106986593281521497120428558179689953803000975469142727125494"
+        ))
+    );
+    assert_eq!(
+        MontScalar::<ark_bls12_381::FrConfig>::MAX_SIGNED,
+        MontScalar::<ark_bls12_381::FrConfig>::new(ark_ff::MontFp!(
+            "26217937587563095239723870254092982918845276250263818911301829349969290592256"
+        ))
+    );
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn the_scalar_constants_match_the_constants_of_the_bn254_field() {
+    assert_eq!(
+        Bn254Scalar::MAX_SIGNED,
+        Bn254Scalar::new(ark_ff::MontFp!(
+            "10944121435919637611123202872628637544274182200208017171849102093287904247808"
+        ))
+    );
+    assert_eq!(Bn254Scalar::ZERO, Bn254Scalar::zero());
+    assert_eq!(Bn254Scalar::ONE, Bn254Scalar::one());
+    assert_eq!(Bn254Scalar::TWO, Bn254Scalar::one() + Bn254Scalar::one());
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_convert_bn254_scalars_to_and_from_signed_integers() {
+    assert_eq!(
+        Bn254Scalar::MAX_SIGNED + Bn254Scalar::MAX_SIGNED,
+        -Bn254Scalar::ONE
+    );
+    assert_eq!(
+        i64::try_from(Bn254Scalar::from(i64::MIN)).unwrap(),
+        i64::MIN
+    );
+    assert_eq!(i128::try_from(Bn254Scalar::from(-123_i128)).unwrap(), -123);
+    assert!(matches!(
+        i8::try_from(Bn254Scalar::from(i16::MAX)),
+        Err(ScalarConversionError::Overflow(_))
+    ));
+    assert_eq!(
+        BigInt::from(-Bn254Scalar::MAX_SIGNED),
+        -BigInt::from(Bn254Scalar::MAX_SIGNED)
+    );
+    assert_eq!(
+        Bn254Scalar::try_from(BigInt::from(i128::MIN)).unwrap(),
+        Bn254Scalar::from(i128::MIN)
+    );
+    assert_eq!(
+        (-Bn254Scalar::ONE).signed_cmp(&Bn254Scalar::ONE),
+        core::cmp::Ordering::Less
+    );
+}
@@ -11,10 +11,9 @@ macro_rules! impl_serde_for_ark_serde_checked {
         }
         impl<'de> serde::Deserialize<'de> for $t {
             fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-                ark_serialize::CanonicalDeserialize::deserialize_compressed(
-                    Vec::deserialize(deserializer)?.as_slice(),
-                )
-                .map_err(serde::de::Error::custom)
+                $crate::base::deserialize_exact(deserializer, |bytes| {
+                    ark_serialize::CanonicalDeserialize::deserialize_compressed(bytes)
+                })
             }
         }
     };
@@ -33,14 +32,33 @@ macro_rules! impl_serde_for_ark_serde_unchecked {
         }
         impl<'de> serde::Deserialize<'de> for $t {
             fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-                ark_serialize::CanonicalDeserialize::deserialize_compressed_unchecked(
-                    Vec::deserialize(deserializer)?.as_slice(),
-                )
-                .map_err(serde::de::Error::custom)
+                $crate::base::deserialize_exact(deserializer, |bytes| {
+                    ark_serialize::CanonicalDeserialize::deserialize_compressed_unchecked(bytes)
+                })
             }
         }
     };
 }
 
+/// Deserializes a value from a byte vector with an `ark_serialize` method.
+///
+/// Bytes that are left over once the value has been read are rejected, so that every value has
+/// exactly one encoding and a proof cannot be altered by appending bytes to its elements.
+pub(crate) fn deserialize_exact<'de, T, D: serde::Deserializer<'de>>(
+    deserializer: D,
+    deserialize: impl FnOnce(&mut &[u8]) -> Result<T, ark_serialize::SerializationError>,
+) -> Result<T, D::Error> {
+    let bytes = <Vec<u8> as serde::Deserialize>::deserialize(deserializer)?;
+    let mut remaining = bytes.as_slice();
+    let value = deserialize(&mut remaining).map_err(serde::de::Error::custom)?;
+    if !remaining.is_empty() {
+        return Err(serde::de::Error::invalid_length(
+            bytes.len(),
+            &"the bytes of exactly one value",
+        ));
+    }
+    Ok(value)
+}
+
 pub(crate) use impl_serde_for_ark_serde_checked;
 pub(crate) use impl_serde_for_ark_serde_unchecked;
@@ -1,3 +1,5 @@
+#[cfg(feature = "rayon")]
+use crate::base::ComputeConfig;
 use crate::base::{scalar::Curve25519Scalar, slice_ops};
 use num_traits::{Inv, Zero};
 
@@ -57,6 +59,7 @@ fn we_can_pseudo_invert_arrays_of_length_bigger_than_1_with_zeros_and_non_zeros(
 }
 
 #[test]
+#[cfg(feature = "rayon")]
 fn we_can_pseudo_invert_arrays_with_nonzero_count_bigger_than_min_chunking_size_with_zeros_and_non_zeros(
 ) {
     let input: Vec<_> = vec![
@@ -70,7 +73,7 @@ fn we_can_pseudo_invert_arrays_with_nonzero_count_bigger_than_min_chunking_size_
     ]
     .into_iter()
     .cycle()
-    .take(slice_ops::MIN_RAYON_LEN * 10)
+    .take(ComputeConfig::new().min_chunk_len() * 10)
     .collect();
 
     let mut res = vec![Curve25519Scalar::from(0_u32); input.len()];
@@ -88,6 +91,7 @@ fn we_can_pseudo_invert_arrays_with_nonzero_count_bigger_than_min_chunking_size_
 }
 
 #[test]
+#[cfg(feature = "rayon")]
 fn we_can_pseudo_invert_arrays_with_nonzero_count_smaller_than_min_chunking_size_with_zeros_and_non_zeros(
 ) {
     let input: Vec<_> = vec![
@@ -101,7 +105,7 @@ fn we_can_pseudo_invert_arrays_with_nonzero_count_smaller_than_min_chunking_size
     ]
     .into_iter()
     .cycle()
-    .take(slice_ops::MIN_RAYON_LEN - 1)
+    .take(ComputeConfig::new().min_chunk_len() - 1)
     .collect();
 
     let mut res = vec![Curve25519Scalar::from(0_u32); input.len()];
@@ -1,4 +1,8 @@
+use crate::base::if_rayon;
+#[cfg(feature = "rayon")]
+use crate::base::ComputeConfig;
 use core::ops::{AddAssign, Mul};
+#[cfg(feature = "rayon")]
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
 
 /// This operation does `result[i] += multiplier * to_mul_add[i]` for `i` in `0..to_mul_add.len()`.
@@ -10,11 +14,14 @@ where
     S: Into<T> + Sync + Copy,
 {
     assert!(result.len() >= to_mul_add.len());
-    result
-        .par_iter_mut()
-        .with_min_len(super::MIN_RAYON_LEN)
-        .zip(to_mul_add)
-        .for_each(|(res_i, &data_i)| {
-            *res_i += multiplier * data_i.into();
-        })
+    if_rayon!(
+        result
+            .par_iter_mut()
+            .with_min_len(ComputeConfig::current().min_len(result.len()))
+            .zip(to_mul_add),
+        result.iter_mut().zip(to_mul_add)
+    )
+    .for_each(|(res_i, &data_i)| {
+        *res_i += multiplier * data_i.into();
+    })
 }
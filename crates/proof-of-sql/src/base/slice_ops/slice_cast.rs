@@ -1,3 +1,7 @@
+use crate::base::if_rayon;
+#[cfg(feature = "rayon")]
+use crate::base::ComputeConfig;
+#[cfg(feature = "rayon")]
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
 };
@@ -9,11 +13,14 @@ where
     F: Sync,
     T: Send,
 {
-    value
-        .par_iter()
-        .with_min_len(super::MIN_RAYON_LEN)
-        .map(cast)
-        .collect()
+    if_rayon!(
+        value
+            .par_iter()
+            .with_min_len(ComputeConfig::current().min_len(value.len())),
+        value.iter()
+    )
+    .map(cast)
+    .collect()
 }
 
 /// This operation takes a slice and casts it to a mutable slice of a different type using the provided function.
@@ -23,20 +30,26 @@ where
     F: Sync,
     T: Send + Sync,
 {
-    value
-        .par_iter()
-        .with_min_len(super::MIN_RAYON_LEN)
-        .zip(result.par_iter_mut())
-        .for_each(|(a, b)| *b = cast(a));
+    if_rayon!(
+        value
+            .par_iter()
+            .with_min_len(ComputeConfig::current().min_len(value.len()))
+            .zip(result.par_iter_mut()),
+        value.iter().zip(result.iter_mut())
+    )
+    .for_each(|(a, b)| *b = cast(a));
 }
 
 /// This operation takes an `IndexedParallelIterator` and casts it to an `IndexedParallelIterator` of a different type using the provided function.
+#[cfg(feature = "rayon")]
 pub fn iter_cast_to_iter<F: Sync + Into<T>, T: Send>(
     value: impl IndexedParallelIterator<Item = F>,
 ) -> impl IndexedParallelIterator<Item = T> {
-    value.with_min_len(super::MIN_RAYON_LEN).map(Into::into)
+    let min_len = ComputeConfig::current().min_len(value.len());
+    value.with_min_len(min_len).map(Into::into)
 }
 /// This operation takes an `IndexedParallelIterator` and casts it to a vector of a different type using the provided function.
+#[cfg(feature = "rayon")]
 pub fn iter_cast<F: Sync + Into<T>, T: Send>(
     value: impl IndexedParallelIterator<Item = F>,
 ) -> Vec<T> {
@@ -58,7 +71,10 @@ pub fn slice_cast<'a, F: Sync, T: Send>(value: &'a [F]) -> Vec<T>
 where
     &'a F: Into<T>,
 {
-    iter_cast(value.par_iter())
+    if_rayon!(
+        iter_cast(value.par_iter()),
+        value.iter().map(Into::into).collect()
+    )
 }
 
 /// This operation takes a slice and casts it to a mutable slice of a different type using the provided function.
@@ -68,9 +84,12 @@ where
     T: Send + Sync,
     &'a F: Into<T>,
 {
-    value
-        .par_iter()
-        .with_min_len(super::MIN_RAYON_LEN)
-        .zip(result.par_iter_mut())
-        .for_each(|(a, b)| *b = a.into());
+    if_rayon!(
+        value
+            .par_iter()
+            .with_min_len(ComputeConfig::current().min_len(value.len()))
+            .zip(result.par_iter_mut()),
+        value.iter().zip(result.iter_mut())
+    )
+    .for_each(|(a, b)| *b = a.into());
 }
@@ -0,0 +1,89 @@
+use std::{fs::File, io::BufWriter, path::Path};
+use thiserror::Error;
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_flame::FlameLayer;
+use tracing_subscriber::{
+    layer::SubscriberExt,
+    util::{SubscriberInitExt, TryInitError},
+};
+
+/// The format of the traces that [`export_traces`] writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// Folded stacks, which `inferno-flamegraph` turns into a flamegraph.
+    ///
+    /// The stacks of all threads are merged, so that the work that rayon spreads over threads is
+    /// attributed to the phase of the prover that it belongs to.
+    Flamegraph,
+    /// The Chrome trace event format, which `chrome://tracing` and Perfetto open. The fields of
+    /// the spans, e.g. the number of rows, are recorded as arguments of the events.
+    Chrome,
+}
+
+/// Errors that can occur when setting up the export of traces.
+#[derive(Debug, Error)]
+pub enum TraceExportError {
+    /// The trace file could not be created.
+    #[error("could not create the trace file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The flamegraph layer could not be created.
+    #[error(transparent)]
+    Flame(#[from] tracing_flame::Error),
+    /// Another subscriber is already the global default.
+    #[error(transparent)]
+    Init(#[from] TryInitError),
+}
+
+enum FlushGuard {
+    Flamegraph(tracing_flame::FlushGuard<BufWriter<File>>),
+    Chrome(tracing_chrome::FlushGuard),
+}
+
+/// Writes the remaining traces to the trace file when it is dropped.
+#[must_use = "the trace file is only complete once the guard is dropped"]
+pub struct TraceExportGuard(FlushGuard);
+
+impl TraceExportGuard {
+    /// Write the traces that have been recorded so far to the trace file.
+    pub fn flush(&self) -> Result<(), TraceExportError> {
+        match &self.0 {
+            FlushGuard::Flamegraph(guard) => guard.flush()?,
+            FlushGuard::Chrome(guard) => guard.flush(),
+        }
+        Ok(())
+    }
+}
+
+/// Install a global subscriber that writes the spans of the library to a trace file.
+///
+/// Every phase of proving and verifying a query has a span named after it, e.g.
+/// `QueryProof::sumcheck`, which records the number of `rows` of the queried table span, the
+/// number of `columns` that the query references and the commitment `scheme`. So the traces of
+/// different queries, table sizes and commitment schemes are comparable. Dory evaluation proofs
+/// additionally record `nu` and `sigma`.
+///
+/// The traces are only written completely once the returned guard is dropped, so it should be
+/// held until the program is done proving.
+pub fn export_traces(
+    path: impl AsRef<Path>,
+    format: TraceFormat,
+) -> Result<TraceExportGuard, TraceExportError> {
+    let guard = match format {
+        TraceFormat::Flamegraph => {
+            let (layer, guard) = FlameLayer::with_file(path)?;
+            tracing_subscriber::registry()
+                .with(layer.with_threads_collapsed(true))
+                .try_init()?;
+            FlushGuard::Flamegraph(guard)
+        }
+        TraceFormat::Chrome => {
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .writer(File::create(path)?)
+                .include_args(true)
+                .build();
+            tracing_subscriber::registry().with(layer).try_init()?;
+            FlushGuard::Chrome(guard)
+        }
+    };
+    Ok(TraceExportGuard(guard))
+}
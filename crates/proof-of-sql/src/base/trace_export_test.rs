@@ -0,0 +1,54 @@
+use super::{export_traces, TraceExportError, TraceFormat};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor},
+    },
+    sql::{ast::test_utility::*, proof::VerifiableQueryResult},
+};
+use curve25519_dalek::RistrettoPoint;
+
+#[test]
+fn we_can_export_the_phases_of_a_proof_as_a_chrome_trace() {
+    let path = std::env::temp_dir().join(format!("posql-trace-{}.json", std::process::id()));
+    let guard = export_traces(&path, TraceFormat::Chrome).unwrap();
+    let other_path = path.with_extension("folded");
+    assert!(matches!(
+        export_traces(&other_path, TraceFormat::Flamegraph),
+        Err(TraceExportError::Init(_))
+    ));
+    std::fs::remove_file(&other_path).unwrap();
+
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 0, 1])]),
+        0,
+        (),
+    );
+    let expr = dense_filter::<RistrettoPoint>(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let proof = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    proof.verify(&expr, &accessor, &()).unwrap();
+    drop(guard);
+
+    let trace = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    for phase in [
+        "QueryProof::result_evaluate",
+        "QueryProof::prover_evaluate",
+        "QueryProof::commit_intermediate_mles",
+        "QueryProof::sumcheck",
+        "QueryProof::evaluate_pcs_proof_mles",
+        "QueryProof::evaluation_proof",
+        "QueryProof::verify_sumcheck",
+        "QueryProof::verifier_evaluate",
+        "QueryProof::verify_evaluation_proof",
+    ] {
+        assert!(trace.contains(phase), "missing span {phase}");
+    }
+    assert!(trace.contains("\"scheme\":\"RistrettoPoint\""));
+}
@@ -26,27 +26,16 @@ use super::{DoryProverPublicSetup, GT};
 use crate::base::{
     commitment::{Commitment, CommittableColumn},
     impl_serde_for_ark_serde_checked,
-    scalar::{scalar_conversion_to_int, MontScalar, Scalar, ScalarConversionError},
+    scalar::MontScalar,
 };
 use ark_ec::pairing::PairingOutput;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use core::ops::Mul;
 use derive_more::{AddAssign, Neg, Sub, SubAssign};
-use num_bigint::BigInt;
 use num_traits::One;
 
 /// The Dory scalar type. (alias for `MontScalar<ark_bls12_381::FrConfig>`)
 pub type DoryScalar = MontScalar<ark_bls12_381::FrConfig>;
-scalar_conversion_to_int!(DoryScalar);
-
-impl Scalar for DoryScalar {
-    const MAX_SIGNED: Self = Self(ark_ff::MontFp!(
-        "26217937587563095239723870254092982918845276250263818911301829349969290592256"
-    ));
-    const ZERO: Self = Self(ark_ff::MontFp!("0"));
-    const ONE: Self = Self(ark_ff::MontFp!("1"));
-    const TWO: Self = Self(ark_ff::MontFp!("2"));
-}
 
 #[derive(
     Debug,
@@ -99,6 +88,17 @@ impl Commitment for DoryCommitment {
         let c = super::compute_dory_commitments(committable_columns, offset, setup);
         commitments.copy_from_slice(&c);
     }
+
+    fn compute_commitments_on_cpu(
+        commitments: &mut [Self],
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) {
+        assert_eq!(commitments.len(), committable_columns.len());
+        let c = super::compute_dory_commitments_on_cpu(committable_columns, offset, setup);
+        commitments.copy_from_slice(&c);
+    }
 }
 
 #[cfg(test)]
@@ -24,6 +24,22 @@ pub enum DoryError {
     /// This error occurs when the setup is too small.
     #[error("setup is too small: the setup is {0}, but the proof requires a setup of size {1}")]
     SmallSetup(usize, usize),
+    /// This error occurs when the precomputed tables were computed for another setup.
+    #[error("the verifier precomputation does not match the verifier setup")]
+    PrecomputationMismatch,
+}
+
+/// Whether the proof holds exactly the messages that the prover sends for `nu`.
+///
+/// Eval-VMV-RE sends 2 GT messages and 1 G1 message, each of the `nu` rounds of Dory-Reduce sends
+/// 6 GT, 3 G1 and 3 G2 messages, and Scalar-Product sends 1 G1 and 1 G2 message.
+/// The verifier checks this up front, so that a proof cannot carry extra messages that are never
+/// read and the size of a proof is bounded by the setup.
+fn has_message_counts_for(messages: &DoryMessages, nu: usize) -> bool {
+    messages.F_messages.is_empty()
+        && messages.G1_messages.len() == 3 * nu + 2
+        && messages.G2_messages.len() == 3 * nu + 1
+        && messages.GT_messages.len() == 6 * nu + 2
 }
 
 impl CommitmentEvaluationProof for DoryEvaluationProof {
@@ -33,7 +49,15 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
     type ProverPublicSetup<'a> = DoryProverPublicSetup<'a>;
     type VerifierPublicSetup<'a> = DoryVerifierPublicSetup<'a>;
 
-    #[tracing::instrument(name = "DoryEvaluationProof::new", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "DoryEvaluationProof::new",
+            level = "debug",
+            skip_all,
+            fields(nu, sigma = setup.sigma())
+        )
+    )]
     fn new(
         transcript: &mut Transcript,
         a: &[Self::Scalar],
@@ -51,6 +75,8 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
         let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(b_point);
         let prover_setup = setup.prover_setup();
         let nu = compute_nu(b_point.len(), setup.sigma());
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("nu", nu);
         if nu > prover_setup.max_nu {
             return Default::default(); // Note: this will always result in a verification error.
         }
@@ -58,15 +84,27 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
         let state = build_vmv_prover_state(a, b_point, T_vec_prime, setup.sigma(), nu);
 
         let mut messages = Default::default();
-        let extended_state = eval_vmv_re_prove(&mut messages, transcript, state, prover_setup);
-        extended_dory_inner_product_prove(&mut messages, transcript, extended_state, prover_setup);
+        let cache = setup.generator_cache();
+        let extended_state =
+            eval_vmv_re_prove(&mut messages, transcript, state, prover_setup, cache);
+        extended_dory_inner_product_prove(
+            &mut messages,
+            transcript,
+            extended_state,
+            prover_setup,
+            cache,
+        );
         messages
     }
 
-    #[tracing::instrument(
-        name = "DoryEvaluationProof::verify_batched_proof",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "DoryEvaluationProof::verify_batched_proof",
+            level = "debug",
+            skip_all,
+            fields(nu, sigma = setup.sigma())
+        )
     )]
     fn verify_batched_proof(
         &self,
@@ -89,11 +127,20 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
         }
         let b_point: &[F] = bytemuck::TransparentWrapper::peel_slice(b_point);
         let verifier_setup = setup.verifier_setup();
-        let mut messages = self.clone();
         let nu = compute_nu(b_point.len(), setup.sigma());
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("nu", nu);
         if nu > verifier_setup.max_nu {
             return Err(DoryError::SmallSetup(verifier_setup.max_nu, nu));
         }
+        if !has_message_counts_for(self, nu) {
+            return Err(DoryError::VerificationError);
+        }
+        let mut messages = self.clone();
+        let precomputation = setup.precomputation();
+        if precomputation.is_some_and(|precomputation| !precomputation.is_for(verifier_setup)) {
+            return Err(DoryError::PrecomputationMismatch);
+        }
         let state = build_vmv_verifier_state(product.0, b_point, a_commit, setup.sigma(), nu);
         let extended_state = eval_vmv_re_verify(&mut messages, transcript, state, verifier_setup)
             .ok_or(DoryError::VerificationError)?;
@@ -102,6 +149,7 @@ impl CommitmentEvaluationProof for DoryEvaluationProof {
             transcript,
             extended_state,
             verifier_setup,
+            precomputation,
             extended_dory_reduce_verify_fold_s_vecs,
         ) {
             Err(DoryError::VerificationError)?;
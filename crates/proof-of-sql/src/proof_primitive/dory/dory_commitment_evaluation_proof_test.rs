@@ -1,8 +1,15 @@
 use super::{
-    test_rng, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup,
-    ProverSetup, PublicParameters, VerifierSetup,
+    dory_commitment_evaluation_proof::DoryError, test_rng, DoryEvaluationProof,
+    DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup, G1Affine, ProverSetup,
+    PublicParameters, VerifierSetup, F, GT,
+};
+use crate::base::{
+    commitment::{
+        commitment_evaluation_proof_test::*, CommitmentEvaluationProof, VecCommitmentExt,
+    },
+    database::Column,
+    polynomial::compute_evaluation_vector,
 };
-use crate::base::commitment::{commitment_evaluation_proof_test::*, CommitmentEvaluationProof};
 use ark_std::UniformRand;
 use merlin::Transcript;
 
@@ -92,3 +99,80 @@ fn we_can_serialize_and_deserialize_dory_evaluation_proofs() {
     let decoded: DoryEvaluationProof = postcard::from_bytes(&encoded).unwrap();
     assert_eq!(decoded, proof);
 }
+
+#[test]
+fn we_cannot_verify_dory_evaluation_proofs_with_missing_or_extra_messages() {
+    let mut rng = test_rng();
+    let public_parameters = PublicParameters::rand(4, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+    let a = core::iter::repeat_with(|| DoryScalar::rand(&mut rng))
+        .take(30)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| DoryScalar::rand(&mut rng))
+        .take(5)
+        .collect::<Vec<_>>();
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = DoryEvaluationProof::new(&mut transcript, &a, &b_point, 0, &prover_setup);
+    let commits = Vec::from_columns_with_offset(&[Column::Scalar(&a)], 0, &prover_setup);
+    let mut b = vec![DoryScalar::default(); a.len()];
+    compute_evaluation_vector(&mut b, &b_point);
+    let product: DoryScalar = a.iter().zip(&b).map(|(a, b)| *a * *b).sum();
+    let verify = |proof: &DoryEvaluationProof| {
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        proof.verify_proof(
+            &mut transcript,
+            &commits[0],
+            &product,
+            &b_point,
+            0,
+            a.len(),
+            &verifier_setup,
+        )
+    };
+    assert!(verify(&proof).is_ok());
+
+    // Messages that the verifier would never read
+    let mut tampered = proof.clone();
+    tampered.F_messages.push(F::rand(&mut rng));
+    assert!(matches!(
+        verify(&tampered),
+        Err(DoryError::VerificationError)
+    ));
+    let mut tampered = proof.clone();
+    tampered.G1_messages.insert(0, G1Affine::rand(&mut rng));
+    assert!(matches!(
+        verify(&tampered),
+        Err(DoryError::VerificationError)
+    ));
+    let mut tampered = proof.clone();
+    tampered.GT_messages.insert(0, GT::rand(&mut rng));
+    assert!(matches!(
+        verify(&tampered),
+        Err(DoryError::VerificationError)
+    ));
+
+    // Messages that are missing, or a round of messages of a smaller proof
+    for drop_message in [
+        |proof: &mut DoryEvaluationProof| proof.G1_messages.truncate(1),
+        |proof: &mut DoryEvaluationProof| proof.G2_messages.clear(),
+        |proof: &mut DoryEvaluationProof| proof.GT_messages.truncate(2),
+    ] {
+        let mut tampered = proof.clone();
+        drop_message(&mut tampered);
+        assert!(matches!(
+            verify(&tampered),
+            Err(DoryError::VerificationError)
+        ));
+    }
+    let mut tampered = proof.clone();
+    tampered.G1_messages.truncate(proof.G1_messages.len() - 3);
+    tampered.G2_messages.truncate(proof.G2_messages.len() - 3);
+    tampered.GT_messages.truncate(proof.GT_messages.len() - 6);
+    assert!(matches!(
+        verify(&tampered),
+        Err(DoryError::VerificationError)
+    ));
+}
@@ -3,7 +3,10 @@ use crate::base::commitment::CommittableColumn;
 use ark_ec::VariableBaseMSM;
 use core::iter::once;
 
-#[tracing::instrument(name = "compute_dory_commitment_impl (cpu)", level = "debug", skip_all)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "compute_dory_commitment_impl (cpu)", level = "debug", skip_all)
+)]
 fn compute_dory_commitment_impl<'a, T>(
     column: &'a [T],
     offset: usize,
@@ -13,6 +16,11 @@ where
     &'a T: Into<DoryScalar>,
     T: Sync,
 {
+    // The commitment to an empty column is the identity, no matter the offset.
+    if column.is_empty() {
+        return DoryCommitment::default();
+    }
+
     // Compute offsets for the matrix.
     let num_columns = 1 << setup.sigma();
     let first_row_offset = offset % num_columns;
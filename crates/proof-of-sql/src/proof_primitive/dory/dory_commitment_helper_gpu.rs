@@ -8,7 +8,10 @@ use ark_std::ops::Mul;
 use blitzar::{compute::ElementP2, sequence::Sequence};
 use rayon::prelude::*;
 
-#[tracing::instrument(name = "get_offset_commits (gpu)", level = "debug", skip_all)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "get_offset_commits (gpu)", level = "debug", skip_all)
+)]
 fn get_offset_commits(
     column_len: usize,
     offset: usize,
@@ -86,7 +89,10 @@ fn get_offset_commits(
         .collect()
 }
 
-#[tracing::instrument(name = "compute_dory_commitment_impl (gpu)", level = "debug", skip_all)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "compute_dory_commitment_impl (gpu)", level = "debug", skip_all)
+)]
 fn compute_dory_commitment_impl<'a, T>(
     column: &'a [T],
     offset: usize,
@@ -97,6 +103,11 @@ where
     &'a [T]: Into<Sequence<'a>>,
     T: OffsetToBytes,
 {
+    // The commitment to an empty column is the identity, no matter the offset.
+    if column.is_empty() {
+        return DoryCommitment::default();
+    }
+
     let num_columns = 1 << setup.sigma();
     let data_size = std::mem::size_of::<T>();
 
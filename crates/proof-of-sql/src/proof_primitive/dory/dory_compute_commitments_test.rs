@@ -1,7 +1,11 @@
 use crate::{
-    base::{commitment::CommittableColumn, math::decimal::Precision},
+    base::{
+        commitment::{Commitment, CommittableColumn},
+        math::decimal::Precision,
+    },
     proof_primitive::dory::{
-        compute_dory_commitments, DoryProverPublicSetup, ProverSetup, PublicParameters, F, GT,
+        compute_dory_commitments, DoryCommitment, DoryProverPublicSetup, ProverSetup,
+        PublicParameters, F, GT,
     },
 };
 use ark_ec::pairing::Pairing;
@@ -330,6 +334,32 @@ fn we_can_compute_an_empty_dory_commitment() {
     assert_eq!(res[0].0, GT::zero());
 }
 
+#[test]
+fn the_commitment_to_an_empty_column_is_the_identity_at_any_offset() {
+    let public_parameters = PublicParameters::rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let setup = DoryProverPublicSetup::new(&prover_setup, 2);
+    let empty_columns = [
+        CommittableColumn::Boolean(&[]),
+        CommittableColumn::SmallInt(&[]),
+        CommittableColumn::Int(&[]),
+        CommittableColumn::BigInt(&[]),
+        CommittableColumn::Int128(&[]),
+        CommittableColumn::Decimal75(Precision::new(1).unwrap(), 0, vec![]),
+        CommittableColumn::Scalar(vec![]),
+        CommittableColumn::VarChar(vec![]),
+        CommittableColumn::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &[]),
+    ];
+    // The last offset is beyond the generators of the setup
+    for offset in [0, 5, 1 << 20] {
+        let res = compute_dory_commitments(&empty_columns, offset, &setup);
+        assert!(res.iter().all(|commitment| commitment.0 == GT::zero()));
+        let mut res = vec![DoryCommitment::default(); empty_columns.len()];
+        DoryCommitment::compute_commitments_on_cpu(&mut res, &empty_columns, offset, &setup);
+        assert!(res.iter().all(|commitment| commitment.0 == GT::zero()));
+    }
+}
+
 #[test]
 fn test_compute_dory_commitment_when_sigma_is_zero() {
     let public_parameters = PublicParameters::rand(5, &mut test_rng());
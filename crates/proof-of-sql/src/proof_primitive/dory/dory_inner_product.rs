@@ -37,5 +37,5 @@ pub fn dory_inner_product_verify(
             return false;
         }
     }
-    scalar_product_verify(messages, transcript, state, setup)
+    scalar_product_verify(messages, transcript, state, setup, None)
 }
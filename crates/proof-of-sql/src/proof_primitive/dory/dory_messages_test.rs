@@ -1,4 +1,5 @@
 use super::{test_rng, DoryMessages, G1Affine, G2Affine, F, GT};
+use ark_serialize::CanonicalSerialize;
 use ark_std::UniformRand;
 use merlin::Transcript;
 
@@ -289,3 +290,39 @@ fn verifier_messages_fail_when_prover_messages_are_out_of_order() {
         Pmessage9
     );
 }
+
+#[test]
+fn we_cannot_deserialize_messages_with_trailing_bytes() {
+    let mut rng = test_rng();
+    let messages = DoryMessages {
+        F_messages: vec![F::rand(&mut rng)],
+        G1_messages: vec![G1Affine::rand(&mut rng)],
+        G2_messages: vec![G2Affine::rand(&mut rng)],
+        GT_messages: vec![GT::rand(&mut rng)],
+    };
+    let mut bytes = Vec::new();
+    messages.serialize_compressed(&mut bytes).unwrap();
+    let encoded = postcard::to_allocvec(&bytes).unwrap();
+    assert_eq!(
+        postcard::from_bytes::<DoryMessages>(&encoded).unwrap(),
+        messages
+    );
+
+    bytes.push(0);
+    let encoded = postcard::to_allocvec(&bytes).unwrap();
+    assert!(postcard::from_bytes::<DoryMessages>(&encoded).is_err());
+}
+
+#[test]
+fn we_cannot_deserialize_messages_with_points_outside_the_prime_order_subgroup() {
+    let point = (0_u64..)
+        .filter_map(|x| G1Affine::get_point_from_x_unchecked(x.into(), false))
+        .find(|point| !point.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap();
+    let messages = DoryMessages {
+        G1_messages: vec![point],
+        ..Default::default()
+    };
+    let encoded = postcard::to_allocvec(&messages).unwrap();
+    assert!(postcard::from_bytes::<DoryMessages>(&encoded).is_err());
+}
@@ -1,10 +1,11 @@
-use super::{ProverSetup, VerifierSetup};
+use super::{ProverGeneratorCache, ProverSetup, VerifierPrecomputation, VerifierSetup};
 
 /// The public setup required for the Dory PCS by the prover and the commitment computation.
 #[derive(Clone, Copy)]
 pub struct DoryProverPublicSetup<'a> {
     prover_setup: &'a ProverSetup<'a>,
     sigma: usize,
+    generator_cache: Option<&'a ProverGeneratorCache>,
 }
 impl<'a> DoryProverPublicSetup<'a> {
     /// Create a new public setup for the Dory PCS.
@@ -14,6 +15,21 @@ impl<'a> DoryProverPublicSetup<'a> {
         Self {
             prover_setup,
             sigma,
+            generator_cache: None,
+        }
+    }
+    /// Prove with generators that were prepared for the prover setup, which speeds up proving.
+    ///
+    /// # Panics
+    /// Panics if the generators were prepared for another prover setup.
+    pub fn with_generator_cache(self, generator_cache: &'a ProverGeneratorCache) -> Self {
+        assert!(
+            generator_cache.is_for(self.prover_setup),
+            "the generator cache was prepared for another prover setup"
+        );
+        Self {
+            generator_cache: Some(generator_cache),
+            ..self
         }
     }
     /// Returns sigma. A commitment with this setup is a matrix commitment with `1 << sigma` columns.
@@ -24,6 +40,10 @@ impl<'a> DoryProverPublicSetup<'a> {
     pub fn prover_setup(&self) -> &ProverSetup {
         self.prover_setup
     }
+    /// The prepared generators for the prover setup, if there are any.
+    pub fn generator_cache(&self) -> Option<&ProverGeneratorCache> {
+        self.generator_cache
+    }
 }
 
 /// The verifier's public setup for the Dory PCS.
@@ -31,6 +51,7 @@ impl<'a> DoryProverPublicSetup<'a> {
 pub struct DoryVerifierPublicSetup<'a> {
     verifier_setup: &'a VerifierSetup,
     sigma: usize,
+    precomputation: Option<&'a VerifierPrecomputation>,
 }
 impl<'a> DoryVerifierPublicSetup<'a> {
     /// Create a new public setup for the Dory PCS.
@@ -40,6 +61,15 @@ impl<'a> DoryVerifierPublicSetup<'a> {
         Self {
             verifier_setup,
             sigma,
+            precomputation: None,
+        }
+    }
+    /// Verify with precomputed tables for the verifier setup, which speeds up verification.
+    /// The tables must have been computed for the same verifier setup.
+    pub fn with_precomputation(self, precomputation: &'a VerifierPrecomputation) -> Self {
+        Self {
+            precomputation: Some(precomputation),
+            ..self
         }
     }
     /// Returns sigma. A commitment with this setup is a matrix commitment with `1<<sigma` columns.
@@ -50,4 +80,8 @@ impl<'a> DoryVerifierPublicSetup<'a> {
     pub fn verifier_setup(&self) -> &VerifierSetup {
         self.verifier_setup
     }
+    /// The precomputed tables for the verifier setup, if there are any.
+    pub fn precomputation(&self) -> Option<&VerifierPrecomputation> {
+        self.precomputation
+    }
 }
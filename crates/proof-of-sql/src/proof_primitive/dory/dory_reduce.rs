@@ -13,7 +13,7 @@ pub fn dory_reduce_prove(
 ) {
     assert!(state.nu > 0);
     let half_n = 1usize << (state.nu - 1);
-    let (D_1L, D_1R, D_2L, D_2R) = dory_reduce_prove_compute_Ds(state, setup, half_n);
+    let (D_1L, D_1R, D_2L, D_2R) = dory_reduce_prove_compute_Ds(state, setup, None, half_n);
     messages.prover_send_GT_message(transcript, D_1L);
     messages.prover_send_GT_message(transcript, D_1R);
     messages.prover_send_GT_message(transcript, D_2L);
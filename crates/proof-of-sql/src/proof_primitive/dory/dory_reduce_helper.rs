@@ -1,7 +1,10 @@
 use super::{
     pairings::{multi_pairing_2, multi_pairing_4},
-    DeferredGT, ProverSetup, ProverState, VerifierSetup, VerifierState, F, GT,
+    DeferredGT, ProverGeneratorCache, ProverSetup, ProverState, VerifierSetup, VerifierState, F,
+    GT,
 };
+use crate::base::if_rayon;
+#[cfg(feature = "rayon")]
 use rayon::{
     iter::IndexedParallelIterator,
     prelude::{IntoParallelRefMutIterator, ParallelIterator},
@@ -16,20 +19,33 @@ use rayon::{
 /// * D_2R = <Gamma_1', v_2R>
 ///
 /// Returns (D_1L, D_1R, D_2L, D_2R).
-#[tracing::instrument(level = "debug", skip_all)]
+///
+/// Gamma_2' is taken from the cache if it holds the generators of this round.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn dory_reduce_prove_compute_Ds(
     state: &ProverState,
     setup: &ProverSetup,
+    cache: Option<&ProverGeneratorCache>,
     half_n: usize,
 ) -> (GT, GT, GT, GT) {
     let (v_1L, v_1R) = state.v1.split_at(half_n);
     let (v_2L, v_2R) = state.v2.split_at(half_n);
-    let (D_1L, D_1R, D_2L, D_2R) = multi_pairing_4(
-        (v_1L, setup.Gamma_2[state.nu - 1]),
-        (v_1R, setup.Gamma_2[state.nu - 1]),
-        (setup.Gamma_1[state.nu - 1], v_2L),
-        (setup.Gamma_1[state.nu - 1], v_2R),
-    );
+    let Gamma_1 = setup.Gamma_1[state.nu - 1];
+    let (D_1L, D_1R, D_2L, D_2R) =
+        match cache.and_then(|cache| cache.Gamma_2_prepared(state.nu - 1)) {
+            Some(Gamma_2) => multi_pairing_4(
+                (v_1L, Gamma_2.iter().cloned()),
+                (v_1R, Gamma_2.iter().cloned()),
+                (Gamma_1, v_2L),
+                (Gamma_1, v_2R),
+            ),
+            None => multi_pairing_4(
+                (v_1L, setup.Gamma_2[state.nu - 1]),
+                (v_1R, setup.Gamma_2[state.nu - 1]),
+                (Gamma_1, v_2L),
+                (Gamma_1, v_2R),
+            ),
+        };
     (D_1L, D_1R, D_2L, D_2R)
 }
 /// From the Dory-Reduce algorithm in section 3.2 of https://eprint.iacr.org/2020/1274.pdf.
@@ -37,20 +53,16 @@ pub fn dory_reduce_prove_compute_Ds(
 /// Mutates v_1 and v_2.
 /// * v_1 <- v_1 + beta * Gamma_1
 /// * v_2 <- v_2 + beta_inv * Gamma_2
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn dory_reduce_prove_mutate_v_vecs(
     state: &mut ProverState,
     setup: &ProverSetup,
     (beta, beta_inv): (F, F),
 ) {
-    state
-        .v1
-        .par_iter_mut()
+    if_rayon!(state.v1.par_iter_mut(), state.v1.iter_mut())
         .zip(setup.Gamma_1[state.nu])
         .for_each(|(v, &g)| *v = (*v + g * beta).into());
-    state
-        .v2
-        .par_iter_mut()
+    if_rayon!(state.v2.par_iter_mut(), state.v2.iter_mut())
         .zip(setup.Gamma_2[state.nu])
         .for_each(|(v, &g)| *v = (*v + g * beta_inv).into());
 }
@@ -59,7 +71,7 @@ pub fn dory_reduce_prove_mutate_v_vecs(
 /// Computes
 /// * C_plus = <v_1L, v_2R>
 /// * C_minus = <v_1R, v_2L>
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn dory_reduce_prove_compute_Cs(state: &ProverState, half_n: usize) -> (GT, GT) {
     let (v_1L, v_1R) = state.v1.split_at(half_n);
     let (v_2L, v_2R) = state.v2.split_at(half_n);
@@ -72,7 +84,7 @@ pub fn dory_reduce_prove_compute_Cs(state: &ProverState, half_n: usize) -> (GT,
 /// Folds v_1 and v_2.
 /// * v_1' <- alpha * v_1L + v_1R
 /// * v_2' <- alpha_inv * v_2L + v_2R
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn dory_reduce_prove_fold_v_vecs(
     state: &mut ProverState,
     (alpha, alpha_inv): (F, F),
@@ -80,10 +92,10 @@ pub fn dory_reduce_prove_fold_v_vecs(
 ) {
     let (v_1L, v_1R) = state.v1.split_at_mut(half_n);
     let (v_2L, v_2R) = state.v2.split_at_mut(half_n);
-    v_1L.par_iter_mut()
+    if_rayon!(v_1L.par_iter_mut(), v_1L.iter_mut())
         .zip(v_1R)
         .for_each(|(v_L, v_R)| *v_L = (*v_L * alpha + v_R).into());
-    v_2L.par_iter_mut()
+    if_rayon!(v_2L.par_iter_mut(), v_2L.iter_mut())
         .zip(v_2R)
         .for_each(|(v_L, v_R)| *v_L = (*v_L * alpha_inv + v_R).into());
     state.v1.truncate(half_n);
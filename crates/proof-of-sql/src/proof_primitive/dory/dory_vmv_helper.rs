@@ -33,7 +33,7 @@ fn convert_scalar_to_array(
 }
 
 /// Compute the commitments to the rows of the matrix M that is derived from `a`.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 #[cfg(feature = "blitzar")]
 pub(super) fn compute_T_vec_prime(
     a: &[F],
@@ -60,7 +60,7 @@ pub(super) fn compute_T_vec_prime(
     blitzar_commits.par_iter().map(Into::into).collect()
 }
 
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 #[cfg(not(feature = "blitzar"))]
 pub(super) fn compute_T_vec_prime(
     a: &[F],
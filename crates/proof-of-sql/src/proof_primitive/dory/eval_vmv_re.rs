@@ -1,6 +1,6 @@
 use super::{
     pairings, DeferredG2, DoryMessages, ExtendedProverState, ExtendedVerifierState, G1Projective,
-    ProverSetup, VMVProverState, VMVVerifierState, VerifierSetup,
+    ProverGeneratorCache, ProverSetup, VMVProverState, VMVVerifierState, VerifierSetup,
 };
 use ark_ec::VariableBaseMSM;
 use merlin::Transcript;
@@ -13,20 +13,25 @@ use merlin::Transcript;
 ///     We should have E_1 = s2 * v1 and E_2 = s1 * v2, which is the case if we use s1 = R and s2 = L.
 ///
 /// Note: the paper has the prover send E_2 to the verifier. We opt to simply have the verifier compute E_2 from y, which is known.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn eval_vmv_re_prove(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
     state: VMVProverState,
     setup: &ProverSetup,
+    cache: Option<&ProverGeneratorCache>,
 ) -> ExtendedProverState {
+    let Gamma_2_fin = match cache {
+        Some(cache) => cache.Gamma_2_fin_prepared().clone(),
+        None => setup.Gamma_2_fin.into(),
+    };
     let C = pairings::pairing(
         G1Projective::msm_unchecked(&state.T_vec_prime, &state.v_vec),
-        setup.Gamma_2_fin,
+        Gamma_2_fin.clone(),
     );
     let D_2 = pairings::pairing(
         G1Projective::msm_unchecked(setup.Gamma_1[state.nu], &state.v_vec),
-        setup.Gamma_2_fin,
+        Gamma_2_fin,
     );
     let E_1 = G1Projective::msm_unchecked(&state.T_vec_prime, &state.L_vec);
     messages.prover_send_GT_message(transcript, C);
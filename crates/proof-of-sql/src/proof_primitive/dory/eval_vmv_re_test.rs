@@ -17,8 +17,13 @@ fn we_can_prove_and_verify_an_eval_vmv_re() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let extended_prover_state =
-        eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let extended_prover_state = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     assert_eq!(
@@ -47,8 +52,13 @@ fn we_can_prove_and_verify_an_eval_vmv_re_for_multiple_nu_values() {
 
         let mut transcript = Transcript::new(b"eval_vmv_re_test");
         let mut messages = DoryMessages::default();
-        let extended_prover_state =
-            eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+        let extended_prover_state = eval_vmv_re_prove(
+            &mut messages,
+            &mut transcript,
+            prover_state,
+            &prover_setup,
+            None,
+        );
 
         let mut transcript = Transcript::new(b"eval_vmv_re_test");
         assert_eq!(
@@ -76,8 +86,13 @@ fn we_fail_to_verify_an_eval_vmv_re_when_a_message_is_modified() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let extended_prover_state =
-        eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let extended_prover_state = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages[0] = GT::rand(&mut rng);
 
@@ -107,7 +122,13 @@ fn we_fail_to_verify_an_eval_vmv_re_when_there_are_too_few_GT_messages() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let _ = eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let _ = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages.pop();
 
@@ -136,7 +157,13 @@ fn we_fail_to_verify_an_eval_vmv_re_when_there_are_too_few_G1_messages() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let _ = eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let _ = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.G1_messages.pop();
 
@@ -166,8 +193,13 @@ fn we_fail_to_verify_an_eval_vmv_re_when_the_setups_differ() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let extended_prover_state =
-        eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let extended_prover_state = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages[0] = GT::rand(&mut rng);
 
@@ -200,8 +232,13 @@ fn we_fail_to_verify_an_eval_vmv_re_when_the_commitment_is_wrong() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let extended_prover_state =
-        eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let extended_prover_state = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
 
@@ -232,8 +269,13 @@ fn we_fail_to_verify_an_eval_vmv_re_when_the_evaluation_value_is_wrong() {
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
     let mut messages = DoryMessages::default();
-    let extended_prover_state =
-        eval_vmv_re_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    let extended_prover_state = eval_vmv_re_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"eval_vmv_re_test");
 
@@ -1,6 +1,7 @@
 use super::{
     scalar_product_prove, scalar_product_verify, DoryMessages, ExtendedProverState,
-    ExtendedVerifierState, ProverSetup, VerifierSetup, F,
+    ExtendedVerifierState, ProverGeneratorCache, ProverSetup, VerifierPrecomputation,
+    VerifierSetup, F,
 };
 use crate::proof_primitive::dory::{
     extended_dory_reduce_prove, extended_dory_reduce_verify, fold_scalars_0_prove,
@@ -10,17 +11,18 @@ use merlin::Transcript;
 
 /// This is the prover side of the extended Dory-Innerproduct algorithm in section 4.3 of https://eprint.iacr.org/2020/1274.pdf.
 /// This function builds/enqueues `messages`, appends to `transcript`, and consumes `state`.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_inner_product_prove(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
     mut state: ExtendedProverState,
     setup: &ProverSetup,
+    cache: Option<&ProverGeneratorCache>,
 ) {
     let nu = state.base_state.nu;
     assert!(setup.max_nu >= nu);
     for _ in 0..nu {
-        extended_dory_reduce_prove(messages, transcript, &mut state, setup);
+        extended_dory_reduce_prove(messages, transcript, &mut state, setup, cache);
     }
     let base_state = fold_scalars_0_prove(messages, transcript, state, setup);
     scalar_product_prove(messages, transcript, base_state)
@@ -28,12 +30,13 @@ pub fn extended_dory_inner_product_prove(
 
 /// This is the verifier side of the extended Dory-Innerproduct algorithm in section 4.3 of https://eprint.iacr.org/2020/1274.pdf.
 /// This function consumes/dequeues from `messages`, appends to `transcript`, and consumes `state`.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_inner_product_verify(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
     mut state: ExtendedVerifierState,
     setup: &VerifierSetup,
+    precomputation: Option<&VerifierPrecomputation>,
     fold_s_tensors_verify: impl Fn(&ExtendedVerifierState) -> (F, F),
 ) -> bool {
     let nu = state.base_state.nu;
@@ -43,7 +46,13 @@ pub fn extended_dory_inner_product_verify(
             return false;
         }
     }
-    let base_state =
-        fold_scalars_0_verify(messages, transcript, state, setup, fold_s_tensors_verify);
-    scalar_product_verify(messages, transcript, base_state, setup)
+    let base_state = fold_scalars_0_verify(
+        messages,
+        transcript,
+        state,
+        setup,
+        precomputation,
+        fold_s_tensors_verify,
+    );
+    scalar_product_verify(messages, transcript, base_state, setup, precomputation)
 }
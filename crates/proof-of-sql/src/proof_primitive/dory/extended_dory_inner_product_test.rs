@@ -20,7 +20,13 @@ fn we_can_prove_and_verify_an_extended_dory_inner_product() {
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     assert!(extended_dory_inner_product_verify(
@@ -28,6 +34,7 @@ fn we_can_prove_and_verify_an_extended_dory_inner_product() {
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -53,6 +60,7 @@ fn we_can_prove_and_verify_an_extended_dory_inner_product_for_multiple_nu_values
             &mut transcript,
             prover_state,
             &prover_setup,
+            None,
         );
 
         let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
@@ -61,6 +69,7 @@ fn we_can_prove_and_verify_an_extended_dory_inner_product_for_multiple_nu_values
             &mut transcript,
             verifier_state,
             &verifier_setup,
+            None,
             extended_dory_reduce_verify_fold_s_vecs
         ));
     }
@@ -80,7 +89,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_a_message_is_modified()
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages[0] = GT::rand(&mut rng);
 
@@ -90,6 +105,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_a_message_is_modified()
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -108,7 +124,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_few_GT_me
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages.pop();
 
@@ -118,6 +140,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_few_GT_me
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -136,7 +159,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_many_GT_m
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages.push(GT::rand(&mut rng));
 
@@ -146,6 +175,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_many_GT_m
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -164,7 +194,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_few_G1_me
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.G1_messages.pop();
 
@@ -174,6 +210,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_few_G1_me
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -192,7 +229,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_many_G1_m
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.G1_messages.push(G1Affine::rand(&mut rng));
 
@@ -202,6 +245,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_there_are_too_many_G1_m
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -220,7 +264,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_the_transcripts_differ(
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test_wrong");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     assert!(!extended_dory_inner_product_verify(
@@ -228,6 +278,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_the_transcripts_differ(
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -247,7 +298,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_the_setups_differ() {
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     messages.GT_messages[0] = GT::rand(&mut rng);
 
@@ -257,6 +314,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_the_setups_differ() {
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -277,7 +335,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_the_base_commitment_is_
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     assert!(!extended_dory_inner_product_verify(
@@ -285,6 +349,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_the_base_commitment_is_
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
@@ -305,7 +370,13 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_a_scalar_commitment_is_
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     let mut messages = DoryMessages::default();
-    extended_dory_inner_product_prove(&mut messages, &mut transcript, prover_state, &prover_setup);
+    extended_dory_inner_product_prove(
+        &mut messages,
+        &mut transcript,
+        prover_state,
+        &prover_setup,
+        None,
+    );
 
     let mut transcript = Transcript::new(b"extended_dory_inner_product_test");
     assert!(!extended_dory_inner_product_verify(
@@ -313,6 +384,7 @@ fn we_fail_to_verify_an_extended_dory_inner_product_when_a_scalar_commitment_is_
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs
     ));
 }
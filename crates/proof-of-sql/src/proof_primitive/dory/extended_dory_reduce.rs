@@ -2,21 +2,23 @@ use super::{
     dory_reduce_helper::*,
     extended_dory_reduce_helper::*,
     extended_state::{ExtendedProverState, ExtendedVerifierState},
-    DoryMessages, ProverSetup, VerifierSetup,
+    DoryMessages, ProverGeneratorCache, ProverSetup, VerifierSetup,
 };
 use merlin::Transcript;
 
 /// This is the prover side of the extended Dory-Reduce algorithm in section 3.2 & 4.2 of https://eprint.iacr.org/2020/1274.pdf.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_reduce_prove(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
     state: &mut ExtendedProverState,
     setup: &ProverSetup,
+    cache: Option<&ProverGeneratorCache>,
 ) {
     assert!(state.base_state.nu > 0);
     let half_n = 1usize << (state.base_state.nu - 1);
-    let (D_1L, D_1R, D_2L, D_2R) = dory_reduce_prove_compute_Ds(&state.base_state, setup, half_n);
+    let (D_1L, D_1R, D_2L, D_2R) =
+        dory_reduce_prove_compute_Ds(&state.base_state, setup, cache, half_n);
     let (E_1beta, E_2beta) = extended_dory_reduce_prove_compute_E_betas(state, setup);
     messages.prover_send_GT_message(transcript, D_1L);
     messages.prover_send_GT_message(transcript, D_1R);
@@ -42,7 +44,7 @@ pub fn extended_dory_reduce_prove(
 }
 
 /// This is the verifier side of the extended Dory-Reduce algorithm in section 3.2 & 4.2 of https://eprint.iacr.org/2020/1274.pdf.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_reduce_verify(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
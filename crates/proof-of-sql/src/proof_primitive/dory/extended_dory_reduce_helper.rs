@@ -10,7 +10,7 @@ use ark_ff::Field;
 /// Computes
 /// * E_1beta = <Gamma_1, s_2>
 /// * E_2beta = <s_1, Gamma_2>
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_reduce_prove_compute_E_betas(
     state: &ExtendedProverState,
     setup: &ProverSetup,
@@ -28,7 +28,7 @@ pub fn extended_dory_reduce_prove_compute_E_betas(
 /// * E_1minus = <v_1R, s_2L>
 /// * E_2plus = <s_1L, v_2R>
 /// * E_2minus = <s_1R, v_2L>
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_reduce_prove_compute_signed_Es(
     state: &ExtendedProverState,
     half_n: usize,
@@ -48,7 +48,7 @@ pub fn extended_dory_reduce_prove_compute_signed_Es(
 /// Folds s1 and s2.
 /// * s_1' <- alpha * s_1L + s_1R
 /// * s_2' <- alpha_inv * s_2L + s_2R
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn extended_dory_reduce_prove_fold_s_vecs(
     state: &mut ExtendedProverState,
     (alpha, alpha_inv): (F, F),
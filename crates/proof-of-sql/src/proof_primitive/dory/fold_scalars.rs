@@ -1,7 +1,8 @@
 use super::{
     extended_state::{ExtendedProverState, ExtendedVerifierState},
-    pairings, DeferredGT, DoryMessages, G1Projective, G2Projective, ProverSetup, ProverState,
-    VerifierSetup, VerifierState, F,
+    pairings, DeferredGT, DoryMessages, G1Projective, G2Projective, Gamma_1_0_mul, Gamma_2_0_mul,
+    H_2_prepared, ProverSetup, ProverState, VerifierPrecomputation, VerifierSetup, VerifierState,
+    F,
 };
 use merlin::Transcript;
 
@@ -27,17 +28,19 @@ pub fn fold_scalars_0_prove(
 ///
 /// See [extended_dory_reduce_verify_fold_s_vecs](super::extended_dory_reduce_helper::extended_dory_reduce_verify_fold_s_vecs)
 /// for an explaination of the `s_folded` values
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn fold_scalars_0_verify(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
     mut state: ExtendedVerifierState,
     setup: &VerifierSetup,
+    precomputation: Option<&VerifierPrecomputation>,
     fold_s_tensors_verify: impl Fn(&ExtendedVerifierState) -> (F, F),
 ) -> VerifierState {
     assert_eq!(state.base_state.nu, 0);
     let (gamma, gamma_inv) = messages.verifier_F_message(transcript);
     let (s1_folded, s2_folded) = fold_s_tensors_verify(&state);
+    let H_2 = H_2_prepared(setup, precomputation);
     state.base_state.C += DeferredGT::from(setup.H_T) * s1_folded * s2_folded
         + DeferredGT::from(pairings::pairing(
             setup.H_1,
@@ -45,9 +48,15 @@ pub fn fold_scalars_0_verify(
         )) * gamma
         + DeferredGT::from(pairings::pairing(
             state.E_1.compute::<G1Projective>(),
-            setup.H_2,
+            H_2.clone(),
         )) * gamma_inv;
-    state.base_state.D_1 += pairings::pairing(setup.H_1, setup.Gamma_2_0 * s1_folded * gamma);
-    state.base_state.D_2 += pairings::pairing(setup.Gamma_1_0 * s2_folded * gamma_inv, setup.H_2);
+    state.base_state.D_1 += pairings::pairing(
+        setup.H_1,
+        Gamma_2_0_mul(setup, precomputation, s1_folded * gamma),
+    );
+    state.base_state.D_2 += pairings::pairing(
+        Gamma_1_0_mul(setup, precomputation, s2_folded * gamma_inv),
+        H_2,
+    );
     state.base_state
 }
@@ -28,6 +28,7 @@ fn we_can_fold_scalars() {
         &mut transcript,
         verifier_state,
         &verifier_setup,
+        None,
         extended_dory_reduce_verify_fold_s_vecs,
     );
     assert_eq!(
@@ -105,14 +105,30 @@ mod vmv_state_test;
 mod dory_public_setup;
 pub use dory_public_setup::{DoryProverPublicSetup, DoryVerifierPublicSetup};
 
+mod verifier_precomputation;
+pub use verifier_precomputation::VerifierPrecomputation;
+use verifier_precomputation::{Gamma_1_0_mul, Gamma_2_0_mul, H_2_prepared};
+#[cfg(test)]
+mod verifier_precomputation_test;
+
+mod setup_registry;
+pub use setup_registry::{SetupFingerprint, SetupHandle, SetupRegistry, SetupRegistryError};
+#[cfg(test)]
+mod setup_registry_test;
+
+mod prover_generator_cache;
+pub use prover_generator_cache::ProverGeneratorCache;
+#[cfg(test)]
+mod prover_generator_cache_test;
+
 mod dory_commitment;
 #[cfg(test)]
 mod dory_commitment_test;
 
-#[cfg(not(feature = "blitzar"))]
 mod dory_commitment_helper_cpu;
 #[cfg(not(feature = "blitzar"))]
 use dory_commitment_helper_cpu::compute_dory_commitments;
+use dory_commitment_helper_cpu::compute_dory_commitments as compute_dory_commitments_on_cpu;
 #[cfg(feature = "blitzar")]
 mod dory_commitment_helper_gpu;
 pub use dory_commitment::{DoryCommitment, DoryScalar};
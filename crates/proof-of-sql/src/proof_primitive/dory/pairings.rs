@@ -1,5 +1,6 @@
+use crate::base::if_rayon;
 use ark_ec::pairing::{Pairing, PairingOutput};
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 // This is a wrapper around multi_pairing_impl simply because tracing doesn't work well with threading.
 pub fn pairing<P: Pairing>(
     p: impl Into<P::G1Prepared>,
@@ -7,7 +8,7 @@ pub fn pairing<P: Pairing>(
 ) -> PairingOutput<P> {
     Pairing::pairing(p, q)
 }
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 // This is a wrapper around multi_pairing_impl simply because tracing doesn't work well with threading.
 pub fn multi_pairing<P: Pairing>(
     a: impl IntoIterator<Item = impl Into<P::G1Prepared>> + Send,
@@ -15,7 +16,7 @@ pub fn multi_pairing<P: Pairing>(
 ) -> PairingOutput<P> {
     multi_pairing_impl(a, b)
 }
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 // This is a wrapper around multi_pairing_2_impl simply because tracing doesn't work well with threading.
 pub fn multi_pairing_2<P: Pairing>(
     (a0, b0): (
@@ -29,7 +30,7 @@ pub fn multi_pairing_2<P: Pairing>(
 ) -> (PairingOutput<P>, PairingOutput<P>) {
     multi_pairing_2_impl((a0, b0), (a1, b1))
 }
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 // This is a wrapper around multi_pairing_4_impl simply because tracing doesn't work well with threading.
 pub fn multi_pairing_4<P: Pairing>(
     (a0, b0): (
@@ -72,7 +73,10 @@ fn multi_pairing_2_impl<P: Pairing>(
         impl IntoIterator<Item = impl Into<P::G2Prepared>> + Send,
     ),
 ) -> (PairingOutput<P>, PairingOutput<P>) {
-    rayon::join(|| multi_pairing_impl(a0, b0), || multi_pairing_impl(a1, b1))
+    if_rayon!(
+        rayon::join(|| multi_pairing_impl(a0, b0), || multi_pairing_impl(a1, b1)),
+        (multi_pairing_impl(a0, b0), multi_pairing_impl(a1, b1))
+    )
 }
 fn multi_pairing_4_impl<P: Pairing>(
     (a0, b0): (
@@ -97,9 +101,15 @@ fn multi_pairing_4_impl<P: Pairing>(
     PairingOutput<P>,
     PairingOutput<P>,
 ) {
-    let ((c0, c1), (c2, c3)) = rayon::join(
-        || multi_pairing_2_impl((a0, b0), (a1, b1)),
-        || multi_pairing_2_impl((a2, b2), (a3, b3)),
+    let ((c0, c1), (c2, c3)) = if_rayon!(
+        rayon::join(
+            || multi_pairing_2_impl((a0, b0), (a1, b1)),
+            || multi_pairing_2_impl((a2, b2), (a3, b3)),
+        ),
+        (
+            multi_pairing_2_impl((a0, b0), (a1, b1)),
+            multi_pairing_2_impl((a2, b2), (a3, b3)),
+        )
     );
     (c0, c1, c2, c3)
 }
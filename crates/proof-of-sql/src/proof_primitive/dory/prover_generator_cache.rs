@@ -0,0 +1,67 @@
+use super::{G2Affine, ProverSetup};
+use ark_bls12_381::Bls12_381;
+use ark_ec::pairing::Pairing;
+
+/// The `G2Prepared` of the BLS12-381 pairing, which holds the Miller loop coefficients of a point.
+type G2Prepared = <Bls12_381 as Pairing>::G2Prepared;
+
+/// Generators of a [`ProverSetup`] that are prepared for pairing once, rather than in every proof.
+///
+/// Each round of Dory-Reduce pairs the prover's `v_1` with the generators `Gamma_2` of the
+/// round, which are a prefix of the generators of the round before it. So the Miller loop
+/// coefficients of a single prefix of `Gamma_2` serve every round of every proof under the setup.
+/// This holds those coefficients, along with the ones of `Gamma_2_fin`, which Eval-VMV-RE pairs
+/// with.
+///
+/// The coefficients of a generator take about 20 KiB, so the cache is built on demand for the
+/// largest `nu` that the prover expects, rather than being part of the `ProverSetup`. Rounds of
+/// proofs with a larger `nu` fall back on preparing the generators.
+pub struct ProverGeneratorCache {
+    /// The generators of `Gamma_2` that are cached.
+    Gamma_2: Vec<G2Affine>,
+    /// `Gamma_2_fin` = Gamma_2,fin in the Dory paper.
+    Gamma_2_fin: G2Affine,
+    /// The Miller loop coefficients of `Gamma_2`.
+    Gamma_2_prepared: Vec<G2Prepared>,
+    /// The Miller loop coefficients of `Gamma_2_fin`.
+    Gamma_2_fin_prepared: G2Prepared,
+}
+
+impl ProverGeneratorCache {
+    /// Prepare the generators that the rounds of proofs with a `nu` of up to `max_nu` pair with.
+    ///
+    /// # Panics
+    /// Panics if `max_nu` is larger than the `max_nu` of the setup.
+    pub fn new(setup: &ProverSetup, max_nu: usize) -> Self {
+        assert!(max_nu <= setup.max_nu);
+        // The rounds of a proof with `nu` pair with `Gamma_2[nu - 1]` down to `Gamma_2[0]`.
+        let Gamma_2 = match max_nu {
+            0 => Vec::new(),
+            _ => setup.Gamma_2[max_nu - 1].to_vec(),
+        };
+        Self {
+            Gamma_2_prepared: Gamma_2.iter().map(Into::into).collect(),
+            Gamma_2,
+            Gamma_2_fin: setup.Gamma_2_fin,
+            Gamma_2_fin_prepared: setup.Gamma_2_fin.into(),
+        }
+    }
+
+    /// Returns whether the generators were prepared for the setup.
+    pub fn is_for(&self, setup: &ProverSetup) -> bool {
+        self.Gamma_2_fin == setup.Gamma_2_fin
+            && setup.Gamma_2.last().is_some_and(|Gamma_2| {
+                Gamma_2.len() >= self.Gamma_2.len() && Gamma_2[..self.Gamma_2.len()] == self.Gamma_2
+            })
+    }
+
+    /// Returns the first `2^k` generators of `Gamma_2` prepared for pairing, if they are cached.
+    pub(super) fn Gamma_2_prepared(&self, k: usize) -> Option<&[G2Prepared]> {
+        self.Gamma_2_prepared.get(..1 << k)
+    }
+
+    /// Returns `Gamma_2_fin` prepared for pairing.
+    pub(super) fn Gamma_2_fin_prepared(&self) -> &G2Prepared {
+        &self.Gamma_2_fin_prepared
+    }
+}
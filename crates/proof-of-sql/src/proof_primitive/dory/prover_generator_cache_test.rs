@@ -0,0 +1,97 @@
+use super::{
+    test_rng, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup,
+    ProverGeneratorCache, ProverSetup, PublicParameters, VerifierSetup,
+};
+use crate::base::commitment::{commitment_evaluation_proof_test::*, CommitmentEvaluationProof};
+use ark_std::UniformRand;
+use merlin::Transcript;
+
+#[test]
+fn the_cached_generators_are_prepared_from_the_setup() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let cache = ProverGeneratorCache::new(&prover_setup, 3);
+    assert!(cache.is_for(&prover_setup));
+    for k in 0..3 {
+        let prepared = cache.Gamma_2_prepared(k).unwrap();
+        assert_eq!(prepared.len(), 1 << k);
+        for (prepared, generator) in prepared.iter().zip(prover_setup.Gamma_2[k]) {
+            assert_eq!(*prepared, (*generator).into());
+        }
+    }
+    assert!(cache.Gamma_2_prepared(3).is_none());
+    assert_eq!(
+        *cache.Gamma_2_fin_prepared(),
+        prover_setup.Gamma_2_fin.into()
+    );
+}
+
+#[test]
+fn we_can_verify_proofs_made_with_cached_generators() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let cache = ProverGeneratorCache::new(&prover_setup, 4);
+    test_simple_commitment_evaluation_proof::<DoryEvaluationProof>(
+        &DoryProverPublicSetup::new(&prover_setup, 4).with_generator_cache(&cache),
+        &DoryVerifierPublicSetup::new(&verifier_setup, 4),
+    );
+    test_commitment_evaluation_proof_with_length_1::<DoryEvaluationProof>(
+        &DoryProverPublicSetup::new(&prover_setup, 3).with_generator_cache(&cache),
+        &DoryVerifierPublicSetup::new(&verifier_setup, 3),
+    );
+    for length in [64, 50, 10, 3] {
+        test_random_commitment_evaluation_proof::<DoryEvaluationProof>(
+            length,
+            0,
+            &DoryProverPublicSetup::new(&prover_setup, 3).with_generator_cache(&cache),
+            &DoryVerifierPublicSetup::new(&verifier_setup, 3),
+        );
+    }
+}
+
+#[test]
+fn proofs_made_with_cached_generators_are_the_same_as_without() {
+    let mut rng = test_rng();
+    let public_parameters = PublicParameters::rand(4, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    // A cache for a smaller nu than the one of the proof only serves the later rounds.
+    for cache_nu in [0, 2, 4] {
+        let cache = ProverGeneratorCache::new(&prover_setup, cache_nu);
+        let a: Vec<_> = core::iter::repeat_with(|| DoryScalar::rand(&mut rng))
+            .take(60)
+            .collect();
+        let b_point: Vec<_> = core::iter::repeat_with(|| DoryScalar::rand(&mut rng))
+            .take(6)
+            .collect();
+        let prove = |setup: &DoryProverPublicSetup| {
+            let mut transcript = Transcript::new(b"evaluation_proof");
+            DoryEvaluationProof::new(&mut transcript, &a, &b_point, 0, setup)
+        };
+        let setup = DoryProverPublicSetup::new(&prover_setup, 3);
+        assert_eq!(prove(&setup.with_generator_cache(&cache)), prove(&setup));
+    }
+}
+
+#[test]
+fn the_cached_generators_are_not_for_another_setup() {
+    let mut rng = test_rng();
+    let public_parameters = PublicParameters::rand(2, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let other_public_parameters = PublicParameters::rand(2, &mut rng);
+    let other_prover_setup = ProverSetup::from(&other_public_parameters);
+    let cache = ProverGeneratorCache::new(&other_prover_setup, 2);
+    assert!(!cache.is_for(&prover_setup));
+}
+
+#[test]
+#[should_panic(expected = "the generator cache was prepared for another prover setup")]
+fn we_cannot_prove_with_cached_generators_for_another_setup() {
+    let mut rng = test_rng();
+    let public_parameters = PublicParameters::rand(2, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let other_public_parameters = PublicParameters::rand(2, &mut rng);
+    let other_prover_setup = ProverSetup::from(&other_public_parameters);
+    let cache = ProverGeneratorCache::new(&other_prover_setup, 2);
+    let _ = DoryProverPublicSetup::new(&prover_setup, 1).with_generator_cache(&cache);
+}
@@ -1,4 +1,5 @@
-use super::{G1Affine, G2Affine};
+use super::{G1Affine, G2Affine, SetupFingerprint};
+use ark_serialize::CanonicalSerialize;
 /// The public parameters for the Dory protocol. See section 5 of https://eprint.iacr.org/2020/1274.pdf for details.
 ///
 /// Note: even though H_1 and H_2 are marked as blue, they are still needed.
@@ -40,4 +41,16 @@ impl PublicParameters {
             Gamma_2_fin,
         }
     }
+
+    /// Returns the fingerprint that identifies these public parameters.
+    pub fn fingerprint(&self) -> SetupFingerprint {
+        let mut bytes = Vec::new();
+        self.Gamma_1.serialize_compressed(&mut bytes).unwrap();
+        self.Gamma_2.serialize_compressed(&mut bytes).unwrap();
+        self.H_1.serialize_compressed(&mut bytes).unwrap();
+        self.H_2.serialize_compressed(&mut bytes).unwrap();
+        self.Gamma_2_fin.serialize_compressed(&mut bytes).unwrap();
+        bytes.extend_from_slice(&(self.max_nu as u64).to_le_bytes());
+        SetupFingerprint(*blake3::hash(&bytes).as_bytes())
+    }
 }
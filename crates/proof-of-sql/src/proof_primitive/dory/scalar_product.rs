@@ -1,5 +1,8 @@
 #![allow(unused_variables)]
-use super::{pairings, DoryMessages, ProverState, VerifierSetup, VerifierState};
+use super::{
+    pairings, DoryMessages, Gamma_1_0_mul, Gamma_2_0_mul, ProverState, VerifierPrecomputation,
+    VerifierSetup, VerifierState,
+};
 use merlin::Transcript;
 
 /// This is the prover side of the Scalar-Product algorithm in section 3.1 of https://eprint.iacr.org/2020/1274.pdf.
@@ -36,12 +39,13 @@ pub fn scalar_product_prove(
 }
 
 /// This is the verifier side of the Scalar-Product algorithm in section 3.1 of https://eprint.iacr.org/2020/1274.pdf.
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn scalar_product_verify(
     messages: &mut DoryMessages,
     transcript: &mut Transcript,
     state: VerifierState,
     setup: &VerifierSetup,
+    precomputation: Option<&VerifierPrecomputation>,
 ) -> bool {
     // See section 3.1 of https://eprint.iacr.org/2020/1274.pdf.
     //
@@ -70,6 +74,8 @@ pub fn scalar_product_verify(
     let E_1 = messages.prover_recieve_G1_message(transcript);
     let E_2 = messages.prover_recieve_G2_message(transcript);
     let (d, d_inv) = messages.verifier_F_message(transcript);
-    pairings::pairing(E_1 + setup.Gamma_1_0 * d, E_2 + setup.Gamma_2_0 * d_inv)
-        == (state.C + setup.chi[0] + state.D_2 * d + state.D_1 * d_inv).compute()
+    pairings::pairing(
+        E_1 + Gamma_1_0_mul(setup, precomputation, d),
+        E_2 + Gamma_2_0_mul(setup, precomputation, d_inv),
+    ) == (state.C + setup.chi[0] + state.D_2 * d + state.D_1 * d_inv).compute()
 }
@@ -64,7 +64,10 @@ impl<'a> ProverSetup<'a> {
     }
 
     #[cfg(feature = "blitzar")]
-    #[tracing::instrument(name = "ProverSetup::blitzar_msm", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProverSetup::blitzar_msm", level = "debug", skip_all)
+    )]
     pub(super) fn blitzar_msm(
         &self,
         res: &mut [blitzar::compute::ElementP2<ark_bls12_381::g1::Config>],
@@ -0,0 +1,229 @@
+use super::{DoryProverPublicSetup, G1Affine, G2Affine, ProverSetup, PublicParameters};
+use indexmap::IndexMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use thiserror::Error;
+
+/// The Blake3 hash of the compressed [`PublicParameters`] of a setup, which identifies it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SetupFingerprint(pub [u8; 32]);
+
+/// Errors from loading a setup into a [`SetupRegistry`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SetupRegistryError {
+    /// The loaded public parameters are not the ones that were asked for.
+    #[error("the loaded public parameters have fingerprint {actual:?} rather than {expected:?}")]
+    FingerprintMismatch {
+        /// The fingerprint that was asked for
+        expected: SetupFingerprint,
+        /// The fingerprint of the loaded public parameters
+        actual: SetupFingerprint,
+    },
+}
+
+/// A [`ProverSetup`] along with the [`PublicParameters`] that it borrows.
+///
+/// The prover setup borrows the generators of the public parameters as `&'static` slices, which
+/// is sound because of two invariants:
+/// - fields are dropped in the order they are declared, so `prover_setup` has to be declared
+///   before `public_parameters` to be dropped before the generators it borrows, and
+/// - the public parameters are never modified or moved out of their box, so the buffers of the
+///   generators stay where they are for as long as the setup is loaded.
+///
+/// The fields are private and only borrowed from, so nothing outside of this type can break them.
+struct LoadedSetup {
+    fingerprint: SetupFingerprint,
+    // This has to be declared before the public parameters, since it borrows them.
+    prover_setup: ProverSetup<'static>,
+    #[allow(dead_code)]
+    public_parameters: Box<PublicParameters>,
+}
+
+impl LoadedSetup {
+    fn new(public_parameters: PublicParameters, fingerprint: SetupFingerprint) -> Self {
+        let public_parameters = Box::new(public_parameters);
+        // SAFETY: the generators live in the heap buffers of the public parameters, which are
+        // never modified and are only dropped after the prover setup, see the invariants of
+        // `LoadedSetup`.
+        let (Gamma_1, Gamma_2): (&'static [G1Affine], &'static [G2Affine]) = unsafe {
+            (
+                core::slice::from_raw_parts(
+                    public_parameters.Gamma_1.as_ptr(),
+                    public_parameters.Gamma_1.len(),
+                ),
+                core::slice::from_raw_parts(
+                    public_parameters.Gamma_2.as_ptr(),
+                    public_parameters.Gamma_2.len(),
+                ),
+            )
+        };
+        let prover_setup = ProverSetup::new(
+            Gamma_1,
+            Gamma_2,
+            public_parameters.H_1,
+            public_parameters.H_2,
+            public_parameters.Gamma_2_fin,
+            public_parameters.max_nu,
+        );
+        Self {
+            fingerprint,
+            prover_setup,
+            public_parameters,
+        }
+    }
+}
+
+/// A cheap, reference counted handle to a setup that was loaded into a [`SetupRegistry`].
+///
+/// The setup stays loaded for as long as there is a handle to it, even after it is evicted from
+/// the registry.
+#[derive(Clone)]
+pub struct SetupHandle(Arc<LoadedSetup>);
+
+impl SetupHandle {
+    /// Returns the fingerprint of the public parameters of the setup.
+    pub fn fingerprint(&self) -> SetupFingerprint {
+        self.0.fingerprint
+    }
+
+    /// Returns the maximum nu that the setup works for.
+    pub fn max_nu(&self) -> usize {
+        self.0.prover_setup.max_nu
+    }
+
+    /// Returns the prover setup.
+    pub fn prover_setup(&self) -> &ProverSetup {
+        &self.0.prover_setup
+    }
+
+    /// Returns the public setup of the prover for matrix commitments with `1 << sigma` columns.
+    pub fn prover_public_setup(&self, sigma: usize) -> DoryProverPublicSetup {
+        DoryProverPublicSetup::new(self.prover_setup(), sigma)
+    }
+}
+
+/// The entry of a setup in a [`SetupRegistry`], which is empty until the setup is loaded.
+#[derive(Default)]
+struct SetupSlot {
+    handle: OnceLock<SetupHandle>,
+    // Held while the setup is loaded, so that concurrent requests for it wait for it.
+    loading: Mutex<()>,
+}
+
+/// A registry of the setups that a server proves with, which loads each setup only once.
+///
+/// Creating a [`ProverSetup`] is expensive, so a server that hosts several setup sizes should not
+/// do it for every request. Instead, requests get a [`SetupHandle`] from the registry by the
+/// [`SetupFingerprint`] of the setup, and the setup is only loaded by the first of them.
+/// Setups can also be loaded ahead of requests with [`SetupRegistry::prewarm`] and unloaded with
+/// [`SetupRegistry::evict`].
+#[derive(Default)]
+pub struct SetupRegistry {
+    setups: Mutex<IndexMap<SetupFingerprint, Arc<SetupSlot>>>,
+}
+
+impl SetupRegistry {
+    /// Create a registry without any setups.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn setups(&self) -> MutexGuard<'_, IndexMap<SetupFingerprint, Arc<SetupSlot>>> {
+        // The map is never left in an inconsistent state, so it is fine to use after a panic.
+        self.setups.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the slot of the setup, adding an empty one if there is none.
+    ///
+    /// The registry is only locked while the slot is looked up, so that loading a setup does not
+    /// block requests for other setups.
+    fn slot(&self, fingerprint: SetupFingerprint) -> Arc<SetupSlot> {
+        self.setups().entry(fingerprint).or_default().clone()
+    }
+
+    /// Returns a handle to the setup, if it is loaded.
+    pub fn get(&self, fingerprint: SetupFingerprint) -> Option<SetupHandle> {
+        self.setups().get(&fingerprint)?.handle.get().cloned()
+    }
+
+    /// Returns a handle to the setup, loading its public parameters with `load` if it is not
+    /// loaded yet.
+    ///
+    /// Concurrent requests for a setup that is being loaded wait for it rather than loading it
+    /// again, while requests for other setups are not blocked by it.
+    pub fn get_or_load(
+        &self,
+        fingerprint: SetupFingerprint,
+        load: impl FnOnce() -> PublicParameters,
+    ) -> Result<SetupHandle, SetupRegistryError> {
+        let slot = self.slot(fingerprint);
+        if let Some(handle) = slot.handle.get() {
+            return Ok(handle.clone());
+        }
+        let _loading = slot.loading.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(handle) = slot.handle.get() {
+            return Ok(handle.clone());
+        }
+        let public_parameters = load();
+        let actual = public_parameters.fingerprint();
+        if actual != fingerprint {
+            self.remove_empty_slot(fingerprint, &slot);
+            return Err(SetupRegistryError::FingerprintMismatch {
+                expected: fingerprint,
+                actual,
+            });
+        }
+        Ok(slot
+            .handle
+            .get_or_init(|| SetupHandle(Arc::new(LoadedSetup::new(public_parameters, fingerprint))))
+            .clone())
+    }
+
+    /// Remove the slot of a setup that failed to load, unless it was replaced or loaded since.
+    fn remove_empty_slot(&self, fingerprint: SetupFingerprint, slot: &Arc<SetupSlot>) {
+        let mut setups = self.setups();
+        if setups
+            .get(&fingerprint)
+            .is_some_and(|current| Arc::ptr_eq(current, slot) && current.handle.get().is_none())
+        {
+            setups.shift_remove(&fingerprint);
+        }
+    }
+
+    /// Load the setups of the public parameters ahead of the requests for them, returning their
+    /// fingerprints.
+    ///
+    /// Setups that are already loaded are kept as they are.
+    pub fn prewarm(
+        &self,
+        public_parameters: impl IntoIterator<Item = PublicParameters>,
+    ) -> Vec<SetupFingerprint> {
+        public_parameters
+            .into_iter()
+            .map(|public_parameters| {
+                let fingerprint = public_parameters.fingerprint();
+                self.slot(fingerprint).handle.get_or_init(|| {
+                    SetupHandle(Arc::new(LoadedSetup::new(public_parameters, fingerprint)))
+                });
+                fingerprint
+            })
+            .collect()
+    }
+
+    /// Remove the setup from the registry, returning whether it was loaded.
+    ///
+    /// The setup is unloaded once the handles that are still held are dropped.
+    pub fn evict(&self, fingerprint: SetupFingerprint) -> bool {
+        self.setups()
+            .shift_remove(&fingerprint)
+            .is_some_and(|slot| slot.handle.get().is_some())
+    }
+
+    /// Returns the fingerprints of the loaded setups, in the order they were first requested.
+    pub fn fingerprints(&self) -> Vec<SetupFingerprint> {
+        self.setups()
+            .iter()
+            .filter(|(_, slot)| slot.handle.get().is_some())
+            .map(|(fingerprint, _)| *fingerprint)
+            .collect()
+    }
+}
@@ -0,0 +1,117 @@
+use super::{
+    rand_util::test_seed_rng, DoryEvaluationProof, DoryVerifierPublicSetup, PublicParameters,
+    SetupRegistry, SetupRegistryError, VerifierSetup,
+};
+use crate::base::commitment::commitment_evaluation_proof_test::test_simple_commitment_evaluation_proof;
+
+fn public_parameters(seed: u8, max_nu: usize) -> PublicParameters {
+    PublicParameters::rand(max_nu, &mut test_seed_rng([seed; 32]))
+}
+
+#[test]
+fn the_fingerprint_identifies_the_public_parameters() {
+    assert_eq!(
+        public_parameters(1, 2).fingerprint(),
+        public_parameters(1, 2).fingerprint()
+    );
+    assert_ne!(
+        public_parameters(1, 2).fingerprint(),
+        public_parameters(2, 2).fingerprint()
+    );
+    assert_ne!(
+        public_parameters(1, 2).fingerprint(),
+        public_parameters(1, 3).fingerprint()
+    );
+}
+
+#[test]
+fn we_only_load_each_setup_once() {
+    let registry = SetupRegistry::new();
+    let fingerprint = public_parameters(1, 2).fingerprint();
+    assert!(registry.get(fingerprint).is_none());
+    let handle = registry
+        .get_or_load(fingerprint, || public_parameters(1, 2))
+        .unwrap();
+    assert_eq!(handle.fingerprint(), fingerprint);
+    assert_eq!(handle.max_nu(), 2);
+    let other_handle = registry
+        .get_or_load(fingerprint, || panic!("the setup is already loaded"))
+        .unwrap();
+    assert!(core::ptr::eq(
+        handle.prover_setup(),
+        other_handle.prover_setup()
+    ));
+    assert!(core::ptr::eq(
+        handle.prover_setup(),
+        registry.get(fingerprint).unwrap().prover_setup()
+    ));
+    assert_eq!(registry.fingerprints(), vec![fingerprint]);
+}
+
+#[test]
+fn we_cannot_load_public_parameters_with_another_fingerprint() {
+    let registry = SetupRegistry::new();
+    let fingerprint = public_parameters(1, 2).fingerprint();
+    assert_eq!(
+        registry
+            .get_or_load(fingerprint, || public_parameters(2, 2))
+            .err(),
+        Some(SetupRegistryError::FingerprintMismatch {
+            expected: fingerprint,
+            actual: public_parameters(2, 2).fingerprint(),
+        })
+    );
+    assert!(registry.get(fingerprint).is_none());
+    assert!(registry.fingerprints().is_empty());
+}
+
+#[test]
+fn we_can_use_the_registry_while_a_setup_is_loading() {
+    let registry = SetupRegistry::new();
+    let fingerprint = public_parameters(1, 2).fingerprint();
+    let other_fingerprint = public_parameters(2, 3).fingerprint();
+    let handle = registry
+        .get_or_load(fingerprint, || {
+            assert!(registry.get(fingerprint).is_none());
+            let other_handle = registry
+                .get_or_load(other_fingerprint, || public_parameters(2, 3))
+                .unwrap();
+            assert_eq!(other_handle.max_nu(), 3);
+            assert_eq!(registry.fingerprints(), vec![other_fingerprint]);
+            public_parameters(1, 2)
+        })
+        .unwrap();
+    assert_eq!(handle.max_nu(), 2);
+    assert_eq!(
+        registry.fingerprints(),
+        vec![fingerprint, other_fingerprint]
+    );
+}
+
+#[test]
+fn we_can_prewarm_and_evict_setups() {
+    let registry = SetupRegistry::new();
+    let fingerprints = registry.prewarm([public_parameters(1, 2), public_parameters(2, 3)]);
+    assert_eq!(
+        fingerprints,
+        vec![
+            public_parameters(1, 2).fingerprint(),
+            public_parameters(2, 3).fingerprint()
+        ]
+    );
+    assert_eq!(registry.fingerprints(), fingerprints);
+    let handle = registry.get(fingerprints[1]).unwrap();
+    assert_eq!(handle.max_nu(), 3);
+
+    assert!(registry.evict(fingerprints[1]));
+    assert!(!registry.evict(fingerprints[1]));
+    assert!(registry.get(fingerprints[1]).is_none());
+    assert_eq!(registry.fingerprints(), vec![fingerprints[0]]);
+
+    // The setup stays usable for as long as a handle to it is held.
+    let verifier_setup = VerifierSetup::from(&public_parameters(2, 3));
+    test_simple_commitment_evaluation_proof::<DoryEvaluationProof>(
+        &handle.prover_public_setup(3),
+        &DoryVerifierPublicSetup::new(&verifier_setup, 3),
+    );
+}
@@ -1,6 +1,9 @@
 use crate::proof_primitive::dory::offset_to_bytes::OffsetToBytes;
 
-#[tracing::instrument(name = "transpose_for_fixed_msm (gpu)", level = "debug", skip_all)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "transpose_for_fixed_msm (gpu)", level = "debug", skip_all)
+)]
 pub fn transpose_for_fixed_msm<T: OffsetToBytes>(
     column: &[T],
     offset: usize,
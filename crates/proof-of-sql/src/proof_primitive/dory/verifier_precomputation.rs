@@ -0,0 +1,138 @@
+use super::{G1Affine, G1Projective, G2Affine, G2Projective, VerifierSetup, F};
+use crate::base::impl_serde_for_ark_serde_unchecked;
+use ark_bls12_381::Bls12_381;
+use ark_ec::{pairing::Pairing, scalar_mul::fixed_base::FixedBase};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    path::Path,
+};
+
+/// The window size of the fixed-base tables.
+///
+/// Each table holds `ceil(255 / WINDOW) * 2^WINDOW` points.
+const WINDOW: usize = 8;
+
+/// The number of bits of the scalars that the tables multiply by.
+const SCALAR_SIZE: usize = F::MODULUS_BIT_SIZE as usize;
+
+/// The `G2Prepared` of the BLS12-381 pairing, which holds the Miller loop coefficients of a point.
+type G2Prepared = <Bls12_381 as Pairing>::G2Prepared;
+
+/// Precomputed tables that speed up verification against a [`VerifierSetup`].
+///
+/// The verifier multiplies `Gamma_1_0` and `Gamma_2_0` by fresh scalars and pairs with `H_2` in
+/// every proof. This holds fixed-base tables for the former and the Miller loop coefficients of
+/// the latter, so that high-throughput verifiers compute them once rather than for every proof.
+/// The tables are a few megabytes, so they are computed on demand rather than being part of the
+/// `VerifierSetup`, and can be persisted with [`VerifierPrecomputation::save_to_file`].
+///
+/// Note: The tables are trusted just like the setup itself. Only the base points are checked
+/// against the setup before the tables are used.
+#[derive(CanonicalSerialize, CanonicalDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct VerifierPrecomputation {
+    /// `Gamma_1_0` is the Γ_1 used in Scalar-Product algorithm in the Dory paper.
+    Gamma_1_0: G1Affine,
+    /// `Gamma_2_0` is the Γ_2 used in Scalar-Product algorithm in the Dory paper.
+    Gamma_2_0: G2Affine,
+    /// `H_2` = H_2 in the Dory paper.
+    H_2: G2Affine,
+    /// The fixed-base table of `Gamma_1_0`.
+    Gamma_1_0_table: Vec<Vec<G1Affine>>,
+    /// The fixed-base table of `Gamma_2_0`.
+    Gamma_2_0_table: Vec<Vec<G2Affine>>,
+    /// The Miller loop coefficients of `H_2`.
+    H_2_prepared: G2Prepared,
+}
+
+impl_serde_for_ark_serde_unchecked!(VerifierPrecomputation);
+
+impl VerifierPrecomputation {
+    /// Compute the tables for the setup.
+    pub fn new(setup: &VerifierSetup) -> Self {
+        Self {
+            Gamma_1_0: setup.Gamma_1_0,
+            Gamma_2_0: setup.Gamma_2_0,
+            H_2: setup.H_2,
+            Gamma_1_0_table: FixedBase::get_window_table(
+                SCALAR_SIZE,
+                WINDOW,
+                G1Projective::from(setup.Gamma_1_0),
+            ),
+            Gamma_2_0_table: FixedBase::get_window_table(
+                SCALAR_SIZE,
+                WINDOW,
+                G2Projective::from(setup.Gamma_2_0),
+            ),
+            H_2_prepared: setup.H_2.into(),
+        }
+    }
+
+    /// Returns whether the tables were computed for the setup.
+    pub fn is_for(&self, setup: &VerifierSetup) -> bool {
+        self.Gamma_1_0 == setup.Gamma_1_0
+            && self.Gamma_2_0 == setup.Gamma_2_0
+            && self.H_2 == setup.H_2
+    }
+
+    /// Write the tables to a file, uncompressed so that they load quickly.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.serialize_uncompressed(&mut writer)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Read the tables from a file that was written by [`VerifierPrecomputation::save_to_file`].
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Self::deserialize_uncompressed(&mut reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Returns `Gamma_1_0 * scalar`, using the fixed-base table if there is one.
+pub(super) fn Gamma_1_0_mul(
+    setup: &VerifierSetup,
+    precomputation: Option<&VerifierPrecomputation>,
+    scalar: F,
+) -> G1Projective {
+    match precomputation {
+        Some(precomputation) => FixedBase::windowed_mul(
+            SCALAR_SIZE.div_ceil(WINDOW),
+            WINDOW,
+            &precomputation.Gamma_1_0_table,
+            &scalar,
+        ),
+        None => setup.Gamma_1_0 * scalar,
+    }
+}
+
+/// Returns `Gamma_2_0 * scalar`, using the fixed-base table if there is one.
+pub(super) fn Gamma_2_0_mul(
+    setup: &VerifierSetup,
+    precomputation: Option<&VerifierPrecomputation>,
+    scalar: F,
+) -> G2Projective {
+    match precomputation {
+        Some(precomputation) => FixedBase::windowed_mul(
+            SCALAR_SIZE.div_ceil(WINDOW),
+            WINDOW,
+            &precomputation.Gamma_2_0_table,
+            &scalar,
+        ),
+        None => setup.Gamma_2_0 * scalar,
+    }
+}
+
+/// Returns `H_2` prepared for pairing, using the precomputed coefficients if there are any.
+pub(super) fn H_2_prepared(
+    setup: &VerifierSetup,
+    precomputation: Option<&VerifierPrecomputation>,
+) -> G2Prepared {
+    match precomputation {
+        Some(precomputation) => precomputation.H_2_prepared.clone(),
+        None => setup.H_2.into(),
+    }
+}
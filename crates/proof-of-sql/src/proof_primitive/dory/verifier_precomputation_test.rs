@@ -0,0 +1,111 @@
+use super::{
+    dory_commitment_evaluation_proof::DoryError, test_rng, DoryEvaluationProof,
+    DoryProverPublicSetup, DoryScalar, DoryVerifierPublicSetup, Gamma_1_0_mul, Gamma_2_0_mul,
+    H_2_prepared, ProverSetup, PublicParameters, VerifierPrecomputation, VerifierSetup, F,
+};
+use crate::base::{
+    commitment::{
+        commitment_evaluation_proof_test::*, CommitmentEvaluationProof, VecCommitmentExt,
+    },
+    database::Column,
+};
+use ark_std::UniformRand;
+use merlin::Transcript;
+
+#[test]
+fn the_precomputed_tables_compute_the_same_values_as_the_setup() {
+    let mut rng = test_rng();
+    let public_parameters = PublicParameters::rand(2, &mut rng);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let precomputation = VerifierPrecomputation::new(&verifier_setup);
+    assert!(precomputation.is_for(&verifier_setup));
+    for _ in 0..10 {
+        let scalar = F::rand(&mut rng);
+        assert_eq!(
+            Gamma_1_0_mul(&verifier_setup, Some(&precomputation), scalar),
+            Gamma_1_0_mul(&verifier_setup, None, scalar)
+        );
+        assert_eq!(
+            Gamma_2_0_mul(&verifier_setup, Some(&precomputation), scalar),
+            Gamma_2_0_mul(&verifier_setup, None, scalar)
+        );
+    }
+    assert_eq!(
+        H_2_prepared(&verifier_setup, Some(&precomputation)),
+        H_2_prepared(&verifier_setup, None)
+    );
+}
+
+#[test]
+fn we_can_verify_proofs_with_precomputed_tables() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let precomputation = VerifierPrecomputation::new(&verifier_setup);
+    test_simple_commitment_evaluation_proof::<DoryEvaluationProof>(
+        &DoryProverPublicSetup::new(&prover_setup, 4),
+        &DoryVerifierPublicSetup::new(&verifier_setup, 4).with_precomputation(&precomputation),
+    );
+    test_commitment_evaluation_proof_with_length_1::<DoryEvaluationProof>(
+        &DoryProverPublicSetup::new(&prover_setup, 3),
+        &DoryVerifierPublicSetup::new(&verifier_setup, 3).with_precomputation(&precomputation),
+    );
+    for length in [64, 50, 10, 3] {
+        test_random_commitment_evaluation_proof::<DoryEvaluationProof>(
+            length,
+            0,
+            &DoryProverPublicSetup::new(&prover_setup, 3),
+            &DoryVerifierPublicSetup::new(&verifier_setup, 3).with_precomputation(&precomputation),
+        );
+    }
+}
+
+#[test]
+fn we_cannot_verify_with_tables_for_another_setup() {
+    let mut rng = test_rng();
+    let public_parameters = PublicParameters::rand(2, &mut rng);
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let other_verifier_setup = VerifierSetup::from(&PublicParameters::rand(2, &mut rng));
+    let precomputation = VerifierPrecomputation::new(&other_verifier_setup);
+    assert!(!precomputation.is_for(&verifier_setup));
+
+    let a = [DoryScalar::from(1_i64), DoryScalar::from(2_i64)];
+    let b_point = [DoryScalar::from(3_i64)];
+    let product = a[0] * (DoryScalar::from(1_i64) - b_point[0]) + a[1] * b_point[0];
+    let prover_public_setup = DoryProverPublicSetup::new(&prover_setup, 1);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = DoryEvaluationProof::new(&mut transcript, &a, &b_point, 0, &prover_public_setup);
+    let commits = Vec::from_columns_with_offset(&[Column::Scalar(&a)], 0, &prover_public_setup);
+    let verify = |verifier_public_setup: DoryVerifierPublicSetup| {
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        proof.verify_proof(
+            &mut transcript,
+            &commits[0],
+            &product,
+            &b_point,
+            0,
+            a.len(),
+            &verifier_public_setup,
+        )
+    };
+    let verifier_public_setup = DoryVerifierPublicSetup::new(&verifier_setup, 1);
+    assert!(verify(verifier_public_setup).is_ok());
+    assert!(matches!(
+        verify(verifier_public_setup.with_precomputation(&precomputation)),
+        Err(DoryError::PrecomputationMismatch)
+    ));
+}
+
+#[test]
+fn we_can_save_and_load_precomputed_tables() {
+    let public_parameters = PublicParameters::rand(2, &mut test_rng());
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let precomputation = VerifierPrecomputation::new(&verifier_setup);
+    let path = std::env::temp_dir().join("we_can_save_and_load_precomputed_tables.bin");
+    precomputation.save_to_file(&path).unwrap();
+    let loaded = VerifierPrecomputation::load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, precomputation);
+    assert!(VerifierPrecomputation::load_from_file(&path).is_err());
+}
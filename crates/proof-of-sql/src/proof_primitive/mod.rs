@@ -1,3 +1,9 @@
 //! TODO: add docs
 pub mod dory;
+#[cfg(feature = "bn254")]
+pub mod pedersen;
 pub(crate) mod sumcheck;
+#[cfg(any(test, feature = "test"))]
+pub mod test_vectors;
+#[cfg(all(test, feature = "blitzar"))]
+mod test_vectors_test;
@@ -0,0 +1,23 @@
+//! A Pedersen vector commitment scheme over the G1 group of the BN254 curve.
+//!
+//! This instantiates Proof of SQL over the scalar field of BN254, [`Bn254Scalar`](crate::base::scalar::Bn254Scalar).
+//! The commitment to a column is `sum_i a_i * G_{offset + i}`, where the generators `G_i` are hashed to the curve,
+//! so that nobody knows a discrete log relation between them.
+//!
+//! Note: the evaluation proof is neither succinct nor hiding. The prover sends the whole folded column, which the
+//! verifier commits to and evaluates itself. Verification is linear in the length of the table and reveals a random
+//! linear combination of the committed columns.
+use ark_bn254::{Fq, G1Affine, G1Projective};
+
+mod pedersen_setup;
+pub use pedersen_setup::PedersenSetup;
+
+mod pedersen_commitment;
+pub use pedersen_commitment::PedersenCommitment;
+#[cfg(test)]
+mod pedersen_commitment_test;
+
+mod pedersen_evaluation_proof;
+pub use pedersen_evaluation_proof::{PedersenError, PedersenEvaluationProof};
+#[cfg(test)]
+mod pedersen_evaluation_proof_test;
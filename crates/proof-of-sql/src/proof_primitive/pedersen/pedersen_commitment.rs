@@ -0,0 +1,109 @@
+use super::{G1Projective, PedersenSetup};
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    impl_serde_for_ark_serde_checked,
+    scalar::Bn254Scalar,
+};
+use ark_ec::VariableBaseMSM;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use core::ops::Mul;
+use derive_more::{AddAssign, Neg, Sub, SubAssign};
+
+#[derive(
+    Debug,
+    Default,
+    Sub,
+    Eq,
+    PartialEq,
+    Neg,
+    Copy,
+    Clone,
+    AddAssign,
+    SubAssign,
+    CanonicalSerialize,
+    CanonicalDeserialize,
+)]
+/// The Pedersen commitment type.
+///
+/// The default is the identity, which is the commitment to an empty column.
+pub struct PedersenCommitment(pub(super) G1Projective);
+
+// Traits required for `PedersenCommitment` to impl `Commitment`.
+impl_serde_for_ark_serde_checked!(PedersenCommitment);
+impl Mul<PedersenCommitment> for Bn254Scalar {
+    type Output = PedersenCommitment;
+    fn mul(self, rhs: PedersenCommitment) -> Self::Output {
+        PedersenCommitment(rhs.0 * self.0)
+    }
+}
+impl<'a> Mul<&'a PedersenCommitment> for Bn254Scalar {
+    type Output = PedersenCommitment;
+    fn mul(self, rhs: &'a PedersenCommitment) -> Self::Output {
+        PedersenCommitment(rhs.0 * self.0)
+    }
+}
+
+impl PedersenCommitment {
+    /// Commit to the values, where the first value is committed to with the generator at `offset`.
+    ///
+    /// # Panics
+    /// Panics if the setup has fewer than `offset + values.len()` generators.
+    pub(super) fn compute<'a, T>(values: &'a [T], offset: usize, setup: &PedersenSetup) -> Self
+    where
+        &'a T: Into<Bn254Scalar>,
+    {
+        let generators = setup.generators();
+        assert!(
+            offset + values.len() <= generators.len(),
+            "the setup has {} generators, but committing requires {}",
+            generators.len(),
+            offset + values.len()
+        );
+        Self(G1Projective::msm_unchecked(
+            &generators[offset..offset + values.len()],
+            &Vec::from_iter(values.iter().map(|v| v.into().0)),
+        ))
+    }
+}
+
+fn compute_pedersen_commitment(
+    committable_column: &CommittableColumn,
+    offset: usize,
+    setup: &PedersenSetup,
+) -> PedersenCommitment {
+    match committable_column {
+        CommittableColumn::Scalar(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::SmallInt(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::Int(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::BigInt(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::Int128(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::Decimal75(_, _, column) => {
+            PedersenCommitment::compute(column, offset, setup)
+        }
+        CommittableColumn::VarChar(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::Boolean(column) => PedersenCommitment::compute(column, offset, setup),
+        CommittableColumn::TimestampTZ(_, _, column) => {
+            PedersenCommitment::compute(column, offset, setup)
+        }
+    }
+}
+
+impl Commitment for PedersenCommitment {
+    type Scalar = Bn254Scalar;
+    type PublicSetup<'a> = &'a PedersenSetup;
+
+    fn compute_commitments(
+        commitments: &mut [Self],
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) {
+        assert_eq!(commitments.len(), committable_columns.len());
+        commitments
+            .iter_mut()
+            .zip(committable_columns)
+            .for_each(|(commitment, column)| {
+                *commitment = compute_pedersen_commitment(column, offset, setup);
+            });
+    }
+}
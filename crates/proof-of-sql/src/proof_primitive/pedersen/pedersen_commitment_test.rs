@@ -0,0 +1,135 @@
+use super::{G1Projective, PedersenCommitment, PedersenSetup};
+use crate::base::{
+    commitment::{NumColumnsMismatch, VecCommitmentExt},
+    database::{Column, OwnedColumn},
+    scalar::Bn254Scalar,
+};
+use ark_ec::VariableBaseMSM;
+
+#[test]
+fn the_generators_are_deterministic_and_distinct() {
+    let setup = PedersenSetup::new(8);
+    assert_eq!(setup, PedersenSetup::new(8));
+    assert_eq!(
+        setup.generators(),
+        &PedersenSetup::new(16).generators()[..8]
+    );
+    for (i, g) in setup.generators().iter().enumerate() {
+        assert!(g.is_on_curve());
+        assert!(g.is_in_correct_subgroup_assuming_on_curve());
+        assert!(!setup.generators()[..i].contains(g));
+    }
+}
+
+#[test]
+fn we_can_convert_from_columns() {
+    let setup = PedersenSetup::new(8);
+    let generators = setup.generators();
+
+    // empty case
+    let commitments = Vec::<PedersenCommitment>::from_columns_with_offset(
+        &Vec::<Column<Bn254Scalar>>::new(),
+        0,
+        &&setup,
+    );
+    assert!(commitments.is_empty());
+
+    // nonempty case
+    let column_a = [12i64, 34, 56];
+    let column_b = ["Lorem", "ipsum", "dolor"].map(String::from);
+    let columns = vec![
+        OwnedColumn::<Bn254Scalar>::BigInt(column_a.to_vec()),
+        OwnedColumn::VarChar(column_b.to_vec()),
+    ];
+
+    let commitments = Vec::<PedersenCommitment>::from_columns_with_offset(&columns, 2, &&setup);
+
+    let expected_commitments = vec![
+        PedersenCommitment(G1Projective::msm_unchecked(
+            &generators[2..5],
+            &column_a.map(|v| Bn254Scalar::from(v).0),
+        )),
+        PedersenCommitment(G1Projective::msm_unchecked(
+            &generators[2..5],
+            &column_b.map(|v| Bn254Scalar::from(v).0),
+        )),
+    ];
+    assert_eq!(commitments, expected_commitments);
+}
+
+#[test]
+fn the_commitment_to_an_empty_column_is_the_identity() {
+    let setup = PedersenSetup::new(4);
+    let commitments = Vec::<PedersenCommitment>::from_columns_with_offset(
+        &[OwnedColumn::<Bn254Scalar>::BigInt(vec![])],
+        10,
+        &&setup,
+    );
+    assert_eq!(commitments, vec![PedersenCommitment::default()]);
+}
+
+#[test]
+fn we_can_append_rows() {
+    let setup = PedersenSetup::new(8);
+
+    let column_a = [12i64, 34, 56, 78, 90];
+    let column_b = ["Lorem", "ipsum", "dolor", "sit", "amet"].map(String::from);
+
+    let columns = vec![
+        OwnedColumn::<Bn254Scalar>::BigInt(column_a[..3].to_vec()),
+        OwnedColumn::VarChar(column_b[..3].to_vec()),
+    ];
+    let mut commitments = Vec::<PedersenCommitment>::from_columns_with_offset(&columns, 0, &&setup);
+
+    let new_columns = vec![
+        OwnedColumn::<Bn254Scalar>::BigInt(column_a[3..].to_vec()),
+        OwnedColumn::VarChar(column_b[3..].to_vec()),
+    ];
+    commitments
+        .try_append_rows_with_offset(&new_columns, 3, &&setup)
+        .unwrap();
+
+    let all_columns = vec![
+        OwnedColumn::<Bn254Scalar>::BigInt(column_a.to_vec()),
+        OwnedColumn::VarChar(column_b.to_vec()),
+    ];
+    assert_eq!(
+        commitments,
+        Vec::<PedersenCommitment>::from_columns_with_offset(&all_columns, 0, &&setup)
+    );
+}
+
+#[test]
+fn we_cannot_append_rows_with_different_column_count() {
+    let setup = PedersenSetup::new(8);
+    let columns = vec![OwnedColumn::<Bn254Scalar>::BigInt(vec![1, 2, 3])];
+    let mut commitments = Vec::<PedersenCommitment>::from_columns_with_offset(&columns, 0, &&setup);
+
+    let new_columns = Vec::<Column<Bn254Scalar>>::new();
+    assert!(matches!(
+        commitments.try_append_rows_with_offset(&new_columns, 3, &&setup),
+        Err(NumColumnsMismatch)
+    ));
+}
+
+#[test]
+fn we_can_scale_commitments() {
+    let setup = PedersenSetup::new(4);
+    let columns = vec![OwnedColumn::<Bn254Scalar>::BigInt(vec![1, 2, 3])];
+    let commitment = Vec::<PedersenCommitment>::from_columns_with_offset(&columns, 0, &&setup)[0];
+    let scaled_columns = vec![OwnedColumn::<Bn254Scalar>::BigInt(vec![5, 10, 15])];
+    let scaled_commitment =
+        Vec::<PedersenCommitment>::from_columns_with_offset(&scaled_columns, 0, &&setup)[0];
+    assert_eq!(Bn254Scalar::from(5u64) * commitment, scaled_commitment);
+}
+
+#[test]
+#[should_panic(expected = "the setup has 4 generators, but committing requires 5")]
+fn we_cannot_commit_beyond_the_generators_of_the_setup() {
+    let setup = PedersenSetup::new(4);
+    Vec::<PedersenCommitment>::from_columns_with_offset(
+        &[OwnedColumn::<Bn254Scalar>::BigInt(vec![1, 2, 3])],
+        2,
+        &&setup,
+    );
+}
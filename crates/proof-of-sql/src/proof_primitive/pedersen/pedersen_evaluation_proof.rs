@@ -0,0 +1,93 @@
+use super::{PedersenCommitment, PedersenSetup};
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    polynomial::compute_evaluation_vector,
+    proof::{MessageLabel, TranscriptProtocol},
+    scalar::Bn254Scalar,
+};
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The errors that can occur when verifying a [`PedersenEvaluationProof`].
+#[derive(Error, Debug)]
+pub enum PedersenError {
+    /// This error occurs when the proof does not hold one value for every row of the table.
+    #[error("the proof holds {0} values, but the table has {1} rows")]
+    InvalidLength(usize, usize),
+    /// This error occurs when the setup is too small.
+    #[error("setup is too small: the setup has {0} generators, but the proof requires {1}")]
+    SmallSetup(usize, usize),
+    /// This error occurs when the proof fails to verify.
+    #[error("verification error")]
+    VerificationError,
+}
+
+/// The evaluation proof of the Pedersen commitment scheme, which is the folded column itself.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PedersenEvaluationProof {
+    a: Vec<Bn254Scalar>,
+}
+
+impl CommitmentEvaluationProof for PedersenEvaluationProof {
+    type Scalar = Bn254Scalar;
+    type Commitment = PedersenCommitment;
+    type Error = PedersenError;
+    type ProverPublicSetup<'a> = &'a PedersenSetup;
+    type VerifierPublicSetup<'a> = &'a PedersenSetup;
+
+    fn new(
+        transcript: &mut Transcript,
+        a: &[Self::Scalar],
+        _b_point: &[Self::Scalar],
+        _generators_offset: u64,
+        _setup: &Self::ProverPublicSetup<'_>,
+    ) -> Self {
+        transcript.append_auto(MessageLabel::PedersenMessage, a);
+        Self { a: a.to_vec() }
+    }
+
+    fn verify_batched_proof(
+        &self,
+        transcript: &mut Transcript,
+        commit_batch: &[Self::Commitment],
+        batching_factors: &[Self::Scalar],
+        product: &Self::Scalar,
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        table_length: usize,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Result<(), Self::Error> {
+        if self.a.len() != table_length || table_length > 1 << b_point.len() {
+            return Err(PedersenError::InvalidLength(self.a.len(), table_length));
+        }
+        let num_generators = generators_offset as usize + table_length;
+        if num_generators > setup.generators().len() {
+            return Err(PedersenError::SmallSetup(
+                setup.generators().len(),
+                num_generators,
+            ));
+        }
+        transcript.append_auto(MessageLabel::PedersenMessage, &self.a);
+
+        let a_commit = commit_batch
+            .iter()
+            .zip(batching_factors)
+            .map(|(c, f)| *f * c)
+            .fold(PedersenCommitment::default(), |mut acc, c| {
+                acc += c;
+                acc
+            });
+        if PedersenCommitment::compute(&self.a, generators_offset as usize, setup) != a_commit {
+            return Err(PedersenError::VerificationError);
+        }
+
+        let mut b = vec![Default::default(); table_length];
+        compute_evaluation_vector(&mut b, b_point);
+        let a_dot_b: Bn254Scalar = self.a.iter().zip(&b).map(|(a, b)| *a * *b).sum();
+        if a_dot_b != *product {
+            return Err(PedersenError::VerificationError);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use super::{PedersenCommitment, PedersenError, PedersenEvaluationProof, PedersenSetup};
+use crate::base::{
+    commitment::{
+        commitment_evaluation_proof_test::*, CommitmentEvaluationProof, VecCommitmentExt,
+    },
+    database::Column,
+    polynomial::compute_evaluation_vector,
+    scalar::Bn254Scalar,
+};
+use ark_std::UniformRand;
+use merlin::Transcript;
+
+#[test]
+fn test_simple_pedersen_evaluation_proof() {
+    let setup = PedersenSetup::new(4);
+    test_simple_commitment_evaluation_proof::<PedersenEvaluationProof>(&&setup, &&setup);
+}
+
+#[test]
+fn test_pedersen_evaluation_proof_with_length_1() {
+    let setup = PedersenSetup::new(4);
+    test_commitment_evaluation_proof_with_length_1::<PedersenEvaluationProof>(&&setup, &&setup);
+}
+
+/// Creates a proof of a random column of length `table_length` at `offset`, and returns the proof with the
+/// commitment, the product and the point that it is verified with.
+fn random_proof(
+    table_length: usize,
+    offset: usize,
+    setup: &PedersenSetup,
+) -> (
+    PedersenEvaluationProof,
+    Vec<PedersenCommitment>,
+    Bn254Scalar,
+    Vec<Bn254Scalar>,
+) {
+    let nu = table_length.next_power_of_two().trailing_zeros() as usize;
+    let mut rng = ark_std::test_rng();
+    let a = core::iter::repeat_with(|| Bn254Scalar::rand(&mut rng))
+        .take(table_length)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| Bn254Scalar::rand(&mut rng))
+        .take(nu)
+        .collect::<Vec<_>>();
+
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = PedersenEvaluationProof::new(&mut transcript, &a, &b_point, offset as u64, &setup);
+    let commits = Vec::from_columns_with_offset(&[Column::Scalar(&a)], offset, &setup);
+
+    let mut b = vec![Bn254Scalar::default(); a.len()];
+    compute_evaluation_vector(&mut b, &b_point);
+    let product = a.iter().zip(b.iter()).map(|(a, b)| *a * *b).sum();
+    (proof, commits, product, b_point)
+}
+
+#[test]
+fn we_can_verify_random_pedersen_evaluation_proofs() {
+    let setup = PedersenSetup::new(64);
+    for (table_length, offset) in [(2, 0), (3, 5), (10, 0), (10, 20), (32, 32)] {
+        let (proof, commits, product, b_point) = random_proof(table_length, offset, &setup);
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        let r = proof.verify_proof(
+            &mut transcript,
+            &commits[0],
+            &product,
+            &b_point,
+            offset as u64,
+            table_length,
+            &&setup,
+        );
+        assert!(r.is_ok());
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_pedersen_evaluation_proof_with_the_wrong_product() {
+    let setup = PedersenSetup::new(16);
+    let (proof, commits, product, b_point) = random_proof(10, 3, &setup);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let r = proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &(product + Bn254Scalar::from(1u64)),
+        &b_point,
+        3,
+        10,
+        &&setup,
+    );
+    assert!(matches!(r, Err(PedersenError::VerificationError)));
+}
+
+#[test]
+fn we_cannot_verify_a_pedersen_evaluation_proof_with_the_wrong_offset() {
+    let setup = PedersenSetup::new(16);
+    let (proof, commits, product, b_point) = random_proof(10, 3, &setup);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let r = proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &product,
+        &b_point,
+        2,
+        10,
+        &&setup,
+    );
+    assert!(matches!(r, Err(PedersenError::VerificationError)));
+}
+
+#[test]
+fn we_cannot_verify_a_pedersen_evaluation_proof_with_the_wrong_commitment() {
+    let setup = PedersenSetup::new(16);
+    let (proof, commits, product, b_point) = random_proof(10, 3, &setup);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let r = proof.verify_proof(
+        &mut transcript,
+        &-commits[0],
+        &product,
+        &b_point,
+        3,
+        10,
+        &&setup,
+    );
+    assert!(matches!(r, Err(PedersenError::VerificationError)));
+}
+
+#[test]
+fn we_cannot_verify_a_pedersen_evaluation_proof_with_the_wrong_table_length() {
+    let setup = PedersenSetup::new(16);
+    let (proof, commits, product, b_point) = random_proof(10, 3, &setup);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let r = proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &product,
+        &b_point,
+        3,
+        9,
+        &&setup,
+    );
+    assert!(matches!(r, Err(PedersenError::InvalidLength(10, 9))));
+}
+
+#[test]
+fn we_cannot_verify_a_pedersen_evaluation_proof_with_a_small_setup() {
+    let setup = PedersenSetup::new(16);
+    let (proof, commits, product, b_point) = random_proof(10, 3, &setup);
+    let small_setup = PedersenSetup::new(12);
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let r = proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &product,
+        &b_point,
+        3,
+        10,
+        &&small_setup,
+    );
+    assert!(matches!(r, Err(PedersenError::SmallSetup(12, 13))));
+}
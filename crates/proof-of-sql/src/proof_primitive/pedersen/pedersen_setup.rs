@@ -0,0 +1,55 @@
+use super::{Fq, G1Affine};
+use crate::base::if_rayon;
+use ark_ff::PrimeField;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The domain separator of the hash that generators are derived from.
+const GENERATOR_DOMAIN: &[u8] = b"proof-of-sql pedersen generator v1";
+
+/// The public setup of the Pedersen commitment scheme, which is shared by the prover and the verifier.
+///
+/// This is the list of generators. Every generator is derived deterministically from its index, so there is no
+/// trusted setup and any two setups agree on the generators that they both have.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PedersenSetup {
+    generators: Vec<G1Affine>,
+}
+
+impl PedersenSetup {
+    /// Derive the first `num_generators` generators.
+    ///
+    /// A column with offset `offset` and length `len` can be committed to with this setup only if
+    /// `offset + len <= num_generators`.
+    pub fn new(num_generators: usize) -> Self {
+        Self {
+            generators: if_rayon!(
+                (0..num_generators as u64).into_par_iter(),
+                0..num_generators as u64
+            )
+            .map(hash_to_generator)
+            .collect(),
+        }
+    }
+
+    /// The generators of the setup.
+    pub fn generators(&self) -> &[G1Affine] {
+        &self.generators
+    }
+}
+
+/// Hash the index of a generator to a point on the curve with try-and-increment.
+///
+/// The G1 group of BN254 has cofactor 1, so every point on the curve is in the group.
+fn hash_to_generator(index: u64) -> G1Affine {
+    (0u64..)
+        .find_map(|counter| {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(GENERATOR_DOMAIN);
+            hasher.update(&index.to_le_bytes());
+            hasher.update(&counter.to_le_bytes());
+            let x = Fq::from_le_bytes_mod_order(hasher.finalize().as_bytes());
+            G1Affine::get_point_from_x_unchecked(x, false)
+        })
+        .expect("the counter does not overflow before an x coordinate on the curve is found")
+}
@@ -1,7 +1,7 @@
 use crate::{
     base::{
         polynomial::{CompositePolynomial, CompositePolynomialInfo},
-        proof::{MessageLabel, ProofError, TranscriptProtocol},
+        proof::{MessageLabel, ProofError, ProverDeadline, ProverError, TranscriptProtocol},
         scalar::Scalar,
     },
     proof_primitive::sumcheck::{prove_round, ProverState, Subclaim},
@@ -21,12 +21,31 @@ pub struct SumcheckProof<S: Scalar> {
 }
 
 impl<S: Scalar> SumcheckProof<S> {
-    #[tracing::instrument(name = "SumcheckProof::create", level = "debug", skip_all)]
     pub fn create(
         transcript: &mut Transcript,
         evaluation_point: &mut [S],
         polynomial: &CompositePolynomial<S>,
     ) -> Self {
+        Self::create_with_deadline(
+            transcript,
+            evaluation_point,
+            polynomial,
+            &ProverDeadline::default(),
+        )
+        .expect("the default deadline never expires")
+    }
+
+    /// Create a sumcheck proof, checking the deadline before every round.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "SumcheckProof::create", level = "debug", skip_all)
+    )]
+    pub fn create_with_deadline(
+        transcript: &mut Transcript,
+        evaluation_point: &mut [S],
+        polynomial: &CompositePolynomial<S>,
+        deadline: &ProverDeadline,
+    ) -> Result<Self, ProverError> {
         assert_eq!(evaluation_point.len(), polynomial.num_variables);
         transcript.append_auto(
             MessageLabel::Sumcheck,
@@ -36,6 +55,7 @@ impl<S: Scalar> SumcheckProof<S> {
         let mut state = ProverState::create(polynomial);
         let mut evaluations = Vec::with_capacity(polynomial.num_variables);
         for scalar in evaluation_point.iter_mut().take(polynomial.num_variables) {
+            deadline.check()?;
             let round_evaluations = prove_round(&mut state, &r);
             transcript.append_canonical_serialize(
                 MessageLabel::SumcheckRoundEvaluation,
@@ -46,13 +66,16 @@ impl<S: Scalar> SumcheckProof<S> {
             r = Some(*scalar);
         }
 
-        SumcheckProof { evaluations }
+        Ok(SumcheckProof { evaluations })
     }
 
-    #[tracing::instrument(
-        name = "SumcheckProof::verify_without_evaluation",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "SumcheckProof::verify_without_evaluation",
+            level = "debug",
+            skip_all
+        )
     )]
     pub fn verify_without_evaluation(
         &self,
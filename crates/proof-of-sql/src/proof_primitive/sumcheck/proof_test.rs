@@ -1,6 +1,6 @@
 use crate::base::{
     polynomial::CompositePolynomial,
-    proof::{MessageLabel, TranscriptProtocol},
+    proof::{MessageLabel, ProverDeadline, ProverError, TranscriptProtocol},
     scalar::Curve25519Scalar,
 };
 /**
@@ -160,3 +160,34 @@ fn test_normal_polynomial() {
 
     test_polynomial(nv, num_multiplicands_range, num_products);
 }
+
+#[test]
+fn we_cannot_create_a_sumcheck_proof_with_fewer_steps_than_rounds() {
+    let mut rng = <ark_std::rand::rngs::StdRng as ark_std::rand::SeedableRng>::from_seed([0u8; 32]);
+    let (poly, _) = random_polynomial(7, (4, 9), 5, &mut rng);
+    let mut evaluation_point = vec![Curve25519Scalar::zero(); 7];
+
+    let deadline = ProverDeadline::new().with_step_budget(6);
+    let mut transcript = Transcript::new(b"sumchecktest");
+    assert_eq!(
+        SumcheckProof::create_with_deadline(
+            &mut transcript,
+            &mut evaluation_point,
+            &poly,
+            &deadline
+        )
+        .unwrap_err(),
+        ProverError::StepBudgetExhausted { step_budget: 6 }
+    );
+
+    let deadline = ProverDeadline::new().with_step_budget(7);
+    let mut transcript = Transcript::new(b"sumchecktest");
+    assert!(SumcheckProof::create_with_deadline(
+        &mut transcript,
+        &mut evaluation_point,
+        &poly,
+        &deadline
+    )
+    .is_ok());
+    assert_eq!(deadline.steps_taken(), 7);
+}
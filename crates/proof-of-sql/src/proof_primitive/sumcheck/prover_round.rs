@@ -3,11 +3,13 @@
  *
  * See third_party/license/arkworks.LICENSE
  */
+use crate::base::if_rayon;
 use crate::base::scalar::Scalar;
 use crate::proof_primitive::sumcheck::ProverState;
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
-#[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn prove_round<S: Scalar>(prover_state: &mut ProverState<S>, r_maybe: &Option<S>) -> Vec<S> {
     if let Some(r) = r_maybe {
         if prover_state.round == 0 {
@@ -17,16 +19,17 @@ pub fn prove_round<S: Scalar>(prover_state: &mut ProverState<S>, r_maybe: &Optio
 
         // fix argument
         let r_as_field = prover_state.randomness[prover_state.round - 1];
-        prover_state
-            .flattened_ml_extensions
-            .par_iter_mut()
-            .for_each(|multiplicand| {
-                in_place_fix_variable(
-                    multiplicand,
-                    r_as_field,
-                    prover_state.num_vars - prover_state.round,
-                );
-            });
+        if_rayon!(
+            prover_state.flattened_ml_extensions.par_iter_mut(),
+            prover_state.flattened_ml_extensions.iter_mut()
+        )
+        .for_each(|multiplicand| {
+            in_place_fix_variable(
+                multiplicand,
+                r_as_field,
+                prover_state.num_vars - prover_state.round,
+            );
+        });
     } else if prover_state.round > 0 {
         panic!("verifier message is empty");
     }
@@ -53,43 +56,51 @@ pub fn prove_round<S: Scalar>(prover_state: &mut ProverState<S>, r_maybe: &Optio
     // The order of these loops is changed for the purpose of efficiency.
 
     // The outer loop is the loop over all products in the list_of_products
-    let result = prover_state
-        .list_of_products
-        .par_iter()
-        .map(|(coefficient, multiplicand_indices)| {
-            // The second loop is the loop over the row (b) in 0..round_length
-            (0..round_length)
-                .into_par_iter()
-                .map(|b| {
-                    // We add a vector of products, which takes a bit of extra memory. The reason for this is for the efficient modification described below
-                    let mut products = vec![*coefficient; degree + 1];
-
-                    // The third loop is the loop over the factors/multiplicand in the product term.
-                    for &multiplicand_index in multiplicand_indices {
-                        let table = &prover_state.flattened_ml_extensions[multiplicand_index];
+    let product_sums = if_rayon!(
+        prover_state.list_of_products.par_iter(),
+        prover_state.list_of_products.iter()
+    )
+    .map(|(coefficient, multiplicand_indices)| {
+        // The second loop is the loop over the row (b) in 0..round_length
+        let row_products = |b: usize| {
+            // We add a vector of products, which takes a bit of extra memory. The reason for this is for the efficient modification described below
+            let mut products = vec![*coefficient; degree + 1];
 
-                        // This third+final loop give an efficient way of computing
-                        // products[t] *= table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field;
-                        // It requires only 1 addition (plus the cumulative multiplication) to accomplish the same task.
-                        // It relies on the fact that
-                        // table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field == table[b << 1] + t * diff
-                        let mut start = table[b << 1];
-                        let step = table[(b << 1) + 1] - start;
+            // The third loop is the loop over the factors/multiplicand in the product term.
+            for &multiplicand_index in multiplicand_indices {
+                let table = &prover_state.flattened_ml_extensions[multiplicand_index];
 
-                        // The innermost loop loops over the values (t) that we are evaluating at.
-                        products.iter_mut().take(degree).for_each(|product| {
-                            *product *= start;
-                            start += step;
-                        });
-                        products[degree] *= start;
-                    }
-                    products
-                })
-                .reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add)
-        })
-        .reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add);
+                // This third+final loop give an efficient way of computing
+                // products[t] *= table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field;
+                // It requires only 1 addition (plus the cumulative multiplication) to accomplish the same task.
+                // It relies on the fact that
+                // table[b << 1] * (S::one() - t_as_field) + table[(b << 1) + 1] * t_as_field == table[b << 1] + t * diff
+                let mut start = table[b << 1];
+                let step = table[(b << 1) + 1] - start;
 
-    result
+                // The innermost loop loops over the values (t) that we are evaluating at.
+                products.iter_mut().take(degree).for_each(|product| {
+                    *product *= start;
+                    start += step;
+                });
+                products[degree] *= start;
+            }
+            products
+        };
+        if_rayon!(
+            (0..round_length)
+                .into_par_iter()
+                .map(row_products)
+                .reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add),
+            (0..round_length)
+                .map(row_products)
+                .fold(vec![S::zero(); degree + 1], vec_elementwise_add)
+        )
+    });
+    if_rayon!(
+        product_sums.reduce(|| vec![S::zero(); degree + 1], vec_elementwise_add),
+        product_sums.fold(vec![S::zero(); degree + 1], vec_elementwise_add)
+    )
 }
 
 /// This is equivalent to
@@ -20,7 +20,10 @@ pub struct ProverState<S: Scalar> {
 }
 
 impl<S: Scalar> ProverState<S> {
-    #[tracing::instrument(name = "ProverState::create", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProverState::create", level = "debug", skip_all)
+    )]
     pub fn create(polynomial: &CompositePolynomial<S>) -> Self {
         if polynomial.num_variables == 0 {
             panic!("Attempt to prove a constant.")
@@ -0,0 +1,315 @@
+//! Language-agnostic test vectors for the proof primitives.
+//!
+//! Verifiers that are written in other languages, e.g. Solidity, Go or JavaScript, can check
+//! against these vectors that they agree with this crate byte for byte: given the setup and the
+//! inputs, a verifier has to accept the proof, and its transcript has to produce the same
+//! challenges.
+//!
+//! Every value is a hex string. Integers are encoded as 8 little-endian bytes, and scalars as
+//! their canonical 32 little-endian bytes. Proofs, commitments and setups are encoded with
+//! postcard, exactly as this crate serializes them.
+use super::{
+    dory::{
+        test_rng, DoryEvaluationProof, DoryProverPublicSetup, ProverSetup, PublicParameters,
+        VerifierSetup,
+    },
+    sumcheck::SumcheckProof,
+};
+#[cfg(feature = "blitzar")]
+use crate::base::commitment::InnerProductProof;
+use crate::base::{
+    commitment::{CommitmentEvaluationProof, VecCommitmentExt},
+    database::Column,
+    math::log2_up,
+    polynomial::{compute_evaluation_vector, CompositePolynomial},
+    proof::{MessageLabel, TranscriptProtocol},
+    scalar::{Curve25519Scalar, Scalar},
+};
+use indexmap::IndexMap;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use std::rc::Rc;
+
+/// The label that the transcripts of the test vectors are created with.
+pub const TEST_VECTOR_TRANSCRIPT_LABEL: &[u8] = b"proof-of-sql test vector";
+
+/// A test vector of a proof primitive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// The proof primitive, e.g. `sumcheck`
+    pub primitive: String,
+    /// What the test vector covers
+    pub description: String,
+    /// The public setup, by name
+    pub setup: IndexMap<String, Vec<String>>,
+    /// The inputs of the prover and the verifier, by name
+    pub inputs: IndexMap<String, Vec<String>>,
+    /// The proof
+    pub proof: String,
+    /// The values that the verifier derives from the proof, by name. These always include the
+    /// `final_challenge`, which is drawn from the transcript with the `testvectorchallenge v1`
+    /// label once the proof is verified.
+    pub outputs: IndexMap<String, Vec<String>>,
+}
+
+fn encode_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn encode_integer(value: u64) -> Vec<String> {
+    vec![encode_bytes(&value.to_le_bytes())]
+}
+
+fn encode_scalar<S: Scalar>(scalar: &S) -> String {
+    let limbs: [u64; 4] = (*scalar).into();
+    encode_bytes(&limbs.map(u64::to_le_bytes).concat())
+}
+
+fn encode_scalars<S: Scalar>(scalars: &[S]) -> Vec<String> {
+    scalars.iter().map(encode_scalar).collect()
+}
+
+fn encode_serialized(value: &impl Serialize) -> String {
+    encode_bytes(&postcard::to_allocvec(value).expect("test vector values are always serializable"))
+}
+
+fn final_challenge<S: Scalar>(transcript: &mut Transcript) -> Vec<String> {
+    vec![encode_scalar(&transcript.challenge_scalar_single::<S>(
+        MessageLabel::TestVectorChallenge,
+    ))]
+}
+
+/// Deterministic scalars that are not all small or non-negative.
+fn sample_scalars<S: Scalar>(len: usize, seed: i64) -> Vec<S> {
+    (0..len as i64)
+        .map(|i| S::from(&((i * i + seed) * if i % 3 == 0 { -7919 } else { 104_729 })))
+        .collect()
+}
+
+fn sumcheck_test_vector(
+    description: &str,
+    num_variables: usize,
+    mles: &[Vec<Curve25519Scalar>],
+    products: &[(Curve25519Scalar, Vec<usize>)],
+) -> TestVector {
+    let mut polynomial = CompositePolynomial::new(num_variables);
+    for (coefficient, indexes) in products {
+        let product = indexes.iter().map(|&index| Rc::new(mles[index].clone()));
+        polynomial.add_product(product, *coefficient);
+    }
+    let claimed_sum: Curve25519Scalar = (0..1 << num_variables)
+        .map(|row| {
+            products
+                .iter()
+                .map(|(coefficient, indexes)| {
+                    indexes
+                        .iter()
+                        .fold(*coefficient, |product, &index| product * mles[index][row])
+                })
+                .sum::<Curve25519Scalar>()
+        })
+        .sum();
+
+    let mut transcript = Transcript::new(TEST_VECTOR_TRANSCRIPT_LABEL);
+    let mut evaluation_point = vec![Curve25519Scalar::default(); num_variables];
+    let proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, &polynomial);
+
+    let mut inputs = IndexMap::new();
+    inputs.insert(
+        "num_variables".to_string(),
+        encode_integer(num_variables as u64),
+    );
+    inputs.insert(
+        "max_multiplicands".to_string(),
+        encode_integer(polynomial.max_multiplicands as u64),
+    );
+    inputs.insert("claimed_sum".to_string(), vec![encode_scalar(&claimed_sum)]);
+    for (index, mle) in mles.iter().enumerate() {
+        inputs.insert(format!("mle_{index}"), encode_scalars(mle));
+    }
+    for (index, (coefficient, indexes)) in products.iter().enumerate() {
+        let mut product = vec![encode_scalar(coefficient)];
+        product.extend(
+            indexes
+                .iter()
+                .flat_map(|&index| encode_integer(index as u64)),
+        );
+        inputs.insert(format!("product_{index}"), product);
+    }
+
+    let mut outputs = IndexMap::new();
+    outputs.insert(
+        "evaluation_point".to_string(),
+        encode_scalars(&evaluation_point),
+    );
+    outputs.insert(
+        "expected_evaluation".to_string(),
+        vec![encode_scalar(&polynomial.evaluate(&evaluation_point))],
+    );
+    outputs.insert(
+        "final_challenge".to_string(),
+        final_challenge::<Curve25519Scalar>(&mut transcript),
+    );
+    TestVector {
+        primitive: "sumcheck".to_string(),
+        description: description.to_string(),
+        setup: IndexMap::new(),
+        inputs,
+        proof: encode_serialized(&proof),
+        outputs,
+    }
+}
+
+/// Test vectors of the sumcheck protocol over the Curve25519 scalar field.
+///
+/// The `product_<i>` inputs are the terms of the polynomial: a coefficient followed by the
+/// indexes of the `mle_<j>` inputs that it multiplies. The verifier only needs
+/// `num_variables`, `max_multiplicands` and `claimed_sum`.
+pub fn sumcheck_test_vectors() -> Vec<TestVector> {
+    let a = sample_scalars(8, 3);
+    let b = sample_scalars(8, -5);
+    let c = sample_scalars(8, 11);
+    vec![
+        sumcheck_test_vector(
+            "a single linear term in one variable",
+            1,
+            &[vec![
+                Curve25519Scalar::from(123),
+                Curve25519Scalar::from(456),
+            ]],
+            &[(Curve25519Scalar::from(1), vec![0])],
+        ),
+        sumcheck_test_vector(
+            "terms of degree 1 to 3 in three variables",
+            3,
+            &[a, b, c],
+            &[
+                (Curve25519Scalar::from(2), vec![0, 1]),
+                (-Curve25519Scalar::from(3), vec![2]),
+                (Curve25519Scalar::from(5), vec![0, 1, 2]),
+            ],
+        ),
+    ]
+}
+
+fn evaluation_proof_test_vector<CP: CommitmentEvaluationProof + Serialize>(
+    primitive: &str,
+    description: &str,
+    a: &[CP::Scalar],
+    b_point: &[CP::Scalar],
+    generators_offset: u64,
+    prover_setup: &CP::ProverPublicSetup<'_>,
+    setup: IndexMap<String, Vec<String>>,
+) -> TestVector {
+    let commitments = Vec::<CP::Commitment>::from_columns_with_offset(
+        &[Column::Scalar(a)],
+        generators_offset as usize,
+        prover_setup,
+    );
+    let mut b = vec![CP::Scalar::default(); a.len()];
+    compute_evaluation_vector(&mut b, b_point);
+    let product: CP::Scalar = a.iter().zip(&b).map(|(a, b)| *a * *b).sum();
+
+    let mut transcript = Transcript::new(TEST_VECTOR_TRANSCRIPT_LABEL);
+    let proof = CP::new(&mut transcript, a, b_point, generators_offset, prover_setup);
+
+    let mut inputs = IndexMap::new();
+    inputs.insert("a".to_string(), encode_scalars(a));
+    inputs.insert("b_point".to_string(), encode_scalars(b_point));
+    inputs.insert(
+        "generators_offset".to_string(),
+        encode_integer(generators_offset),
+    );
+    inputs.insert("table_length".to_string(), encode_integer(a.len() as u64));
+    inputs.insert(
+        "commitment".to_string(),
+        vec![encode_serialized(&commitments[0])],
+    );
+    inputs.insert("product".to_string(), vec![encode_scalar(&product)]);
+
+    let mut outputs = IndexMap::new();
+    outputs.insert(
+        "final_challenge".to_string(),
+        final_challenge::<CP::Scalar>(&mut transcript),
+    );
+    TestVector {
+        primitive: primitive.to_string(),
+        description: description.to_string(),
+        setup,
+        inputs,
+        proof: encode_serialized(&proof),
+        outputs,
+    }
+}
+
+/// Test vectors of the inner product proof over Curve25519, whose generators are fixed, so that
+/// it has no setup.
+///
+/// The proof shows that `product` is the evaluation of the MLE of `a`, which `commitment`
+/// commits to, at `b_point`. `a` is only needed by the prover.
+#[cfg(feature = "blitzar")]
+pub fn inner_product_proof_test_vectors() -> Vec<TestVector> {
+    [(1, 0), (3, 0), (8, 0), (8, 5), (13, 100)]
+        .into_iter()
+        .map(|(len, generators_offset)| {
+            let num_variables = log2_up(len);
+            evaluation_proof_test_vector::<InnerProductProof>(
+                "inner_product_proof",
+                &format!("{len} scalars at generators offset {generators_offset}"),
+                &sample_scalars(len, len as i64),
+                &sample_scalars(num_variables, 17),
+                generators_offset,
+                &(),
+                IndexMap::new(),
+            )
+        })
+        .collect()
+}
+
+/// Test vectors of the Dory evaluation proof over BLS12-381.
+///
+/// The setup is `PublicParameters::rand(3, &mut test_rng())` with a `sigma` of 2. The
+/// `verifier_setup` is the only part of it that a verifier needs. The proof shows that `product`
+/// is the evaluation of the MLE of `a`, which `commitment` commits to, at `b_point`. `a` is only
+/// needed by the prover.
+pub fn dory_evaluation_proof_test_vectors() -> Vec<TestVector> {
+    let sigma = 2;
+    let public_parameters = PublicParameters::rand(3, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let mut setup = IndexMap::new();
+    setup.insert("sigma".to_string(), encode_integer(sigma as u64));
+    setup.insert(
+        "verifier_setup".to_string(),
+        vec![encode_serialized(&verifier_setup)],
+    );
+    [1, 4, 10, 16]
+        .into_iter()
+        .map(|len| {
+            let num_variables = log2_up(len);
+            evaluation_proof_test_vector::<DoryEvaluationProof>(
+                "dory_evaluation_proof",
+                &format!("{len} scalars"),
+                &sample_scalars(len, len as i64),
+                &sample_scalars(num_variables, 17),
+                0,
+                &DoryProverPublicSetup::new(&prover_setup, sigma),
+                setup.clone(),
+            )
+        })
+        .collect()
+}
+
+/// The test vectors of every proof primitive.
+pub fn test_vectors() -> Vec<TestVector> {
+    let mut test_vectors = sumcheck_test_vectors();
+    #[cfg(feature = "blitzar")]
+    test_vectors.extend(inner_product_proof_test_vectors());
+    test_vectors.extend(dory_evaluation_proof_test_vectors());
+    test_vectors
+}
+
+/// Encode the test vectors as JSON.
+pub fn test_vectors_to_json(test_vectors: &[TestVector]) -> String {
+    serde_json::to_string_pretty(test_vectors).expect("test vectors are always serializable")
+}
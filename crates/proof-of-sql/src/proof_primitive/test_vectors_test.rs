@@ -0,0 +1,151 @@
+use super::{
+    dory::{
+        test_rng, DoryEvaluationProof, DoryVerifierPublicSetup, PublicParameters, VerifierSetup,
+    },
+    sumcheck::SumcheckProof,
+    test_vectors::*,
+};
+use crate::base::{
+    commitment::{CommitmentEvaluationProof, InnerProductProof},
+    polynomial::CompositePolynomialInfo,
+    proof::{MessageLabel, TranscriptProtocol},
+    scalar::{Curve25519Scalar, Scalar},
+};
+use merlin::Transcript;
+use serde::de::DeserializeOwned;
+
+fn decode_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn decode_integer(values: &[String]) -> u64 {
+    u64::from_le_bytes(decode_bytes(&values[0]).try_into().unwrap())
+}
+
+fn decode_scalar<S: Scalar>(hex: &str) -> S {
+    let bytes = decode_bytes(hex);
+    let limbs: [u64; 4] =
+        core::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap()));
+    S::from(limbs)
+}
+
+fn decode_scalars<S: Scalar>(values: &[String]) -> Vec<S> {
+    values.iter().map(|value| decode_scalar(value)).collect()
+}
+
+fn decode_deserialized<T: DeserializeOwned>(hex: &str) -> T {
+    postcard::from_bytes(&decode_bytes(hex)).unwrap()
+}
+
+fn final_challenge<S: Scalar>(transcript: &mut Transcript) -> S {
+    transcript.challenge_scalar_single(MessageLabel::TestVectorChallenge)
+}
+
+fn verify_sumcheck_test_vector(test_vector: &TestVector) {
+    let inputs = &test_vector.inputs;
+    let outputs = &test_vector.outputs;
+    let proof: SumcheckProof<Curve25519Scalar> = decode_deserialized(&test_vector.proof);
+    let mut transcript = Transcript::new(TEST_VECTOR_TRANSCRIPT_LABEL);
+    let subclaim = proof
+        .verify_without_evaluation(
+            &mut transcript,
+            CompositePolynomialInfo {
+                max_multiplicands: decode_integer(&inputs["max_multiplicands"]) as usize,
+                num_variables: decode_integer(&inputs["num_variables"]) as usize,
+            },
+            &decode_scalar(&inputs["claimed_sum"][0]),
+        )
+        .unwrap();
+    assert_eq!(
+        subclaim.evaluation_point,
+        decode_scalars::<Curve25519Scalar>(&outputs["evaluation_point"])
+    );
+    assert_eq!(
+        subclaim.expected_evaluation,
+        decode_scalar(&outputs["expected_evaluation"][0])
+    );
+    assert_eq!(
+        final_challenge::<Curve25519Scalar>(&mut transcript),
+        decode_scalar(&outputs["final_challenge"][0])
+    );
+}
+
+fn verify_evaluation_proof_test_vector<CP: CommitmentEvaluationProof + DeserializeOwned>(
+    test_vector: &TestVector,
+    setup: &CP::VerifierPublicSetup<'_>,
+) {
+    let inputs = &test_vector.inputs;
+    let proof: CP = decode_deserialized(&test_vector.proof);
+    let mut transcript = Transcript::new(TEST_VECTOR_TRANSCRIPT_LABEL);
+    assert!(proof
+        .verify_proof(
+            &mut transcript,
+            &decode_deserialized(&inputs["commitment"][0]),
+            &decode_scalar(&inputs["product"][0]),
+            &decode_scalars(&inputs["b_point"]),
+            decode_integer(&inputs["generators_offset"]),
+            decode_integer(&inputs["table_length"]) as usize,
+            setup,
+        )
+        .is_ok());
+    assert_eq!(
+        final_challenge::<CP::Scalar>(&mut transcript),
+        decode_scalar(&test_vector.outputs["final_challenge"][0])
+    );
+}
+
+#[test]
+fn we_can_verify_the_sumcheck_test_vectors() {
+    let test_vectors = sumcheck_test_vectors();
+    assert_eq!(test_vectors.len(), 2);
+    // 579 = 123 + 456 as 32 little-endian bytes
+    assert_eq!(
+        test_vectors[0].inputs["claimed_sum"],
+        [format!("4302{}", "0".repeat(60))]
+    );
+    test_vectors.iter().for_each(verify_sumcheck_test_vector);
+}
+
+#[test]
+fn we_can_verify_the_inner_product_proof_test_vectors() {
+    for test_vector in inner_product_proof_test_vectors() {
+        assert!(test_vector.setup.is_empty());
+        verify_evaluation_proof_test_vector::<InnerProductProof>(&test_vector, &());
+    }
+}
+
+#[test]
+fn we_can_verify_the_dory_evaluation_proof_test_vectors() {
+    let public_parameters = PublicParameters::rand(3, &mut test_rng());
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    for test_vector in dory_evaluation_proof_test_vectors() {
+        let sigma = decode_integer(&test_vector.setup["sigma"]) as usize;
+        let decoded_setup: VerifierSetup =
+            decode_deserialized(&test_vector.setup["verifier_setup"][0]);
+        assert_eq!(decoded_setup, verifier_setup);
+        verify_evaluation_proof_test_vector::<DoryEvaluationProof>(
+            &test_vector,
+            &DoryVerifierPublicSetup::new(&decoded_setup, sigma),
+        );
+    }
+}
+
+#[test]
+fn the_test_vectors_are_deterministic_and_round_trip_through_json() {
+    let test_vectors = test_vectors();
+    assert_eq!(test_vectors, super::test_vectors::test_vectors());
+    let primitives: Vec<_> = test_vectors
+        .iter()
+        .map(|test_vector| test_vector.primitive.as_str())
+        .collect();
+    assert!(primitives.contains(&"sumcheck"));
+    assert!(primitives.contains(&"inner_product_proof"));
+    assert!(primitives.contains(&"dory_evaluation_proof"));
+
+    let json = test_vectors_to_json(&test_vectors);
+    let decoded: Vec<TestVector> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, test_vectors);
+}
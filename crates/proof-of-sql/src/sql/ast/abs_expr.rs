@@ -0,0 +1,172 @@
+use super::{
+    audit_column_type_range, count_sign, prover_evaluate_sign, result_evaluate_sign,
+    verifier_evaluate_sign, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use indexmap::IndexSet;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+
+/// Provable `abs(expr)` expression over an integer expression.
+///
+/// The prover commits to the result `a` and proves that `a = x - 2 * x * s`, where `s` is the sign
+/// bit of `x` from the sign decomposition. The sign bit is arbitrary for zeros, which is harmless
+/// since both signs give `a = 0`.
+///
+/// As with the other arithmetic expressions, the absolute value of the minimum value of the type is
+/// out of the range of the type.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AbsExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> AbsExpr<C> {
+    /// Create a new `abs` expression
+    pub fn new(expr: Box<ProvableExprPlan<C>>) -> Self {
+        Self { expr }
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for AbsExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        count_sign(builder)?;
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(1);
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "AbsExpr::result_evaluate", level = "debug", skip_all)
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let values: &[_] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let sign = result_evaluate_sign(table_length, alloc, values);
+        Column::Scalar(compute_abs(alloc, values, sign))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "AbsExpr::prover_evaluate", level = "debug", skip_all)
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let values: &[_] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+
+        // sign(x) == -1
+        let sign = prover_evaluate_sign(
+            builder,
+            alloc,
+            values,
+            None,
+            #[cfg(test)]
+            false,
+        );
+
+        // a
+        let abs = compute_abs(alloc, values, sign);
+        audit_column_type_range("abs_expr", self.data_type(), abs);
+        builder.produce_intermediate_mle(abs);
+
+        // subpolynomial: a - x + 2 * x * s
+        prove_abs(builder, abs, values, sign);
+        Column::Scalar(abs)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let eval = self.expr.verifier_evaluate(builder, accessor)?;
+
+        // sign(x) == -1
+        let sign_eval = verifier_evaluate_sign(builder, eval, one_eval)?;
+
+        // a
+        let abs_eval = builder.consume_intermediate_mle();
+
+        // subpolynomial: a - x + 2 * x * s
+        verify_abs(builder, abs_eval, eval, sign_eval);
+        Ok(abs_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+/// Negates the entries of `values` whose sign bit is set.
+fn compute_abs<'a, S: Scalar>(alloc: &'a Bump, values: &[S], sign: &[bool]) -> &'a [S] {
+    alloc.alloc_slice_fill_with(
+        values.len(),
+        |i| {
+            if sign[i] {
+                -values[i]
+            } else {
+                values[i]
+            }
+        },
+    )
+}
+
+/// Prove that `abs` is the absolute value of `values`, given the sign bits of `values`.
+fn prove_abs<'a, S: Scalar>(
+    builder: &mut ProofBuilder<'a, S>,
+    abs: &'a [S],
+    values: &'a [S],
+    sign: &'a [bool],
+) {
+    // subpolynomial: abs - values + 2 * values * sign
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(abs)]),
+            (-S::one(), vec![Box::new(values)]),
+            (S::TWO, vec![Box::new(values), Box::new(sign)]),
+        ],
+    );
+}
+
+/// Verify that `abs_eval` is the evaluation of the absolute value of the expression with evaluation
+/// `eval`, given the evaluation of its sign bits.
+///
+/// See `prove_abs`.
+fn verify_abs<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    abs_eval: C::Scalar,
+    eval: C::Scalar,
+    sign_eval: C::Scalar,
+) {
+    // subpolynomial: abs - values + 2 * values * sign
+    let eval = builder.mle_evaluations.random_evaluation
+        * (abs_eval - eval + C::Scalar::TWO * eval * sign_eval);
+    builder.produce_sumcheck_subpolynomial_evaluation(&eval);
+}
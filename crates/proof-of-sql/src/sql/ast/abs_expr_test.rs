@@ -0,0 +1,102 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor},
+    },
+    sql::{
+        ast::{test_utility::*, ProofPlan, ProvableExprPlan},
+        parse::ConversionError,
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+
+// select a, abs(a) as b from sxt.t where abs(a - 1) >= 2
+#[test]
+fn we_can_prove_abs_in_a_filter_result_and_a_where_clause() {
+    let data = owned_table([bigint("a", [-3_i64, 0, 2, -1, 5, 1, i64::MIN + 1])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = dense_filter(
+        vec![
+            col_expr_plan(t, "a", &accessor),
+            aliased_plan(abs(column(t, "a", &accessor)), "b"),
+        ],
+        tab(t),
+        gte(
+            abs(subtract(column(t, "a", &accessor), const_bigint(1))),
+            const_bigint(2),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [-3_i64, -1, 5, i64::MIN + 1]),
+        bigint("b", [3_i64, 1, 5, i64::MAX]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select abs(a) as b, abs(c) as d from sxt.t
+#[test]
+fn we_can_prove_abs_of_columns_with_a_constant_sign() {
+    let data = owned_table([
+        smallint("a", [1_i16, 2, 3]),
+        int128("c", [-7_i128, -1, i128::MIN + 1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = dense_filter(
+        vec![
+            aliased_plan(abs(column(t, "a", &accessor)), "b"),
+            aliased_plan(abs(column(t, "c", &accessor)), "d"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        smallint("b", [1_i16, 2, 3]),
+        int128("d", [7_i128, 1, i128::MAX]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select abs(a) as b from sxt.t
+#[test]
+fn the_abs_of_the_minimum_value_of_a_type_overflows() {
+    let data = owned_table([smallint("a", [i16::MIN, 1])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: ProofPlan<RistrettoPoint> = dense_filter(
+        vec![aliased_plan(abs(column(t, "a", &accessor)), "b")],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::Overflow)
+    ));
+}
+
+#[test]
+fn we_cannot_take_the_abs_of_non_integer_expressions() {
+    let data = owned_table([
+        varchar("a", ["x"]),
+        decimal75("b", 10, 2, [-150_i64]),
+        boolean("c", [true]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for name in ["a", "b", "c"] {
+        assert!(matches!(
+            ProvableExprPlan::<RistrettoPoint>::try_new_abs(column(t, name, &accessor)),
+            Err(ConversionError::InvalidExpression(_))
+        ));
+    }
+}
@@ -1,10 +1,13 @@
-use super::{add_subtract_columns, scale_and_add_subtract_eval, ProvableExpr, ProvableExprPlan};
+use super::{
+    add_subtract_columns, audit_column_type_range, check_overflow, scale_and_add_subtract_eval,
+    OverflowPolicy, ProvableExpr, ProvableExprPlan,
+};
 use crate::{
     base::{
         commitment::Commitment,
         database::{
-            try_add_subtract_column_types, Column, ColumnRef, ColumnType, CommitmentAccessor,
-            DataAccessor,
+            try_add_subtract_column_types, Column, ColumnOperationResult, ColumnRef, ColumnType,
+            CommitmentAccessor, DataAccessor,
         },
         proof::ProofError,
     },
@@ -21,6 +24,7 @@ pub struct AddSubtractExpr<C: Commitment> {
     lhs: Box<ProvableExprPlan<C>>,
     rhs: Box<ProvableExprPlan<C>>,
     is_subtract: bool,
+    overflow_policy: OverflowPolicy,
 }
 
 impl<C: Commitment> AddSubtractExpr<C> {
@@ -29,13 +33,29 @@ impl<C: Commitment> AddSubtractExpr<C> {
         lhs: Box<ProvableExprPlan<C>>,
         rhs: Box<ProvableExprPlan<C>>,
         is_subtract: bool,
+        overflow_policy: OverflowPolicy,
     ) -> Self {
         Self {
             lhs,
             rhs,
             is_subtract,
+            overflow_policy,
         }
     }
+
+    /// Returns the type of the result, or an error if the policy widens it beyond a `DECIMAL75`.
+    pub(crate) fn try_data_type(&self) -> ColumnOperationResult<ColumnType> {
+        let operator = if self.is_subtract {
+            BinaryOperator::Subtract
+        } else {
+            BinaryOperator::Add
+        };
+        try_add_subtract_column_types(
+            self.overflow_policy.operand_type(self.lhs.data_type()),
+            self.overflow_policy.operand_type(self.rhs.data_type()),
+            operator,
+        )
+    }
 }
 
 impl<C: Commitment> ProvableExpr<C> for AddSubtractExpr<C> {
@@ -46,12 +66,7 @@ impl<C: Commitment> ProvableExpr<C> for AddSubtractExpr<C> {
     }
 
     fn data_type(&self) -> ColumnType {
-        let operator = if self.is_subtract {
-            BinaryOperator::Subtract
-        } else {
-            BinaryOperator::Add
-        };
-        try_add_subtract_column_types(self.lhs.data_type(), self.rhs.data_type(), operator)
+        self.try_data_type()
             .expect("Failed to add/subtract column types")
     }
 
@@ -75,10 +90,13 @@ impl<C: Commitment> ProvableExpr<C> for AddSubtractExpr<C> {
         ))
     }
 
-    #[tracing::instrument(
-        name = "proofs.sql.ast.add_subtract_expr.prover_evaluate",
-        level = "info",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "proofs.sql.ast.add_subtract_expr.prover_evaluate",
+            level = "info",
+            skip_all
+        )
     )]
     fn prover_evaluate<'a>(
         &self,
@@ -88,14 +106,23 @@ impl<C: Commitment> ProvableExpr<C> for AddSubtractExpr<C> {
     ) -> Column<'a, C::Scalar> {
         let lhs_column: Column<'a, C::Scalar> = self.lhs.prover_evaluate(builder, alloc, accessor);
         let rhs_column: Column<'a, C::Scalar> = self.rhs.prover_evaluate(builder, alloc, accessor);
-        Column::Scalar(add_subtract_columns(
+        let res = add_subtract_columns(
             lhs_column,
             rhs_column,
             self.lhs.data_type().scale().unwrap_or(0),
             self.rhs.data_type().scale().unwrap_or(0),
             alloc,
             self.is_subtract,
-        ))
+        );
+        audit_column_type_range("add_subtract_expr", self.data_type(), res);
+        check_overflow(
+            builder,
+            self.overflow_policy,
+            "add_subtract_expr",
+            self.data_type(),
+            res,
+        );
+        Column::Scalar(res)
     }
 
     fn verifier_evaluate(
@@ -2,12 +2,13 @@ use crate::{
     base::{
         commitment::InnerProductProof,
         database::{owned_table_utility::*, Column, OwnedTableTestAccessor},
+        proof::{ProverDeadline, ProverError},
         scalar::Curve25519Scalar,
     },
     sql::{
-        ast::{test_utility::*, ProofPlan, ProvableExpr, ProvableExprPlan},
+        ast::{test_utility::*, OverflowPolicy, ProofPlan, ProvableExpr, ProvableExprPlan},
         parse::ConversionError,
-        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof::{exercise_verification, EvaluationContext, QueryError, VerifiableQueryResult},
     },
 };
 use bumpalo::Bump;
@@ -214,6 +215,91 @@ fn result_expr_can_overflow_more() {
     ));
 }
 
+// select a + b as c, a - b as d from sxt.t
+#[test]
+fn results_are_widened_to_decimals_with_the_widening_overflow_policy() {
+    let data = owned_table([
+        bigint("a", [i64::MAX, i64::MIN, 1]),
+        smallint("b", [i16::MAX, i16::MAX, 2]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: ProofPlan<RistrettoPoint> = dense_filter(
+        vec![
+            aliased_plan(
+                ProvableExprPlan::try_new_add_with_overflow_policy(
+                    column(t, "a", &accessor),
+                    column(t, "b", &accessor),
+                    OverflowPolicy::WidenToDecimal,
+                )
+                .unwrap(),
+                "c",
+            ),
+            aliased_plan(
+                ProvableExprPlan::try_new_subtract_with_overflow_policy(
+                    column(t, "a", &accessor),
+                    column(t, "b", &accessor),
+                    OverflowPolicy::WidenToDecimal,
+                )
+                .unwrap(),
+                "d",
+            ),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let max = i128::from(i64::MAX);
+    let min = i128::from(i64::MIN);
+    let b = i128::from(i16::MAX);
+    let expected_res = owned_table([
+        decimal75("c", 20, 0, [max + b, min + b, 3]),
+        decimal75("d", 20, 0, [max - b, min - b, -1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a + b as c from sxt.t where b = 0
+#[test]
+fn the_prover_refuses_to_prove_an_overflow_with_the_error_overflow_policy() {
+    let data = owned_table([
+        smallint("a", [i16::MIN + 1, i16::MAX]),
+        smallint("b", [0_i16, 1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: ProofPlan<RistrettoPoint> = dense_filter(
+        vec![aliased_plan(
+            ProvableExprPlan::try_new_add_with_overflow_policy(
+                column(t, "a", &accessor),
+                column(t, "b", &accessor),
+                OverflowPolicy::Error,
+            )
+            .unwrap(),
+            "c",
+        )],
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(0)),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_deadline(
+        &ast,
+        &accessor,
+        &(),
+        EvaluationContext::default(),
+        &ProverDeadline::default(),
+    );
+    assert_eq!(
+        res.err(),
+        Some(ProverError::Overflow {
+            gadget: "add_subtract_expr",
+            row: 1
+        })
+    );
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
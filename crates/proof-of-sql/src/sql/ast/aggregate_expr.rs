@@ -41,7 +41,10 @@ impl<C: Commitment> ProvableExpr<C> for AggregateExpr<C> {
         }
     }
 
-    #[tracing::instrument(name = "AggregateExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "AggregateExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -51,7 +54,10 @@ impl<C: Commitment> ProvableExpr<C> for AggregateExpr<C> {
         self.expr.result_evaluate(table_length, alloc, accessor)
     }
 
-    #[tracing::instrument(name = "AggregateExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "AggregateExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
@@ -15,8 +15,8 @@ use serde::{Deserialize, Serialize};
 /// Provable logical AND expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AndExpr<C: Commitment> {
-    lhs: Box<ProvableExprPlan<C>>,
-    rhs: Box<ProvableExprPlan<C>>,
+    pub(super) lhs: Box<ProvableExprPlan<C>>,
+    pub(super) rhs: Box<ProvableExprPlan<C>>,
 }
 
 impl<C: Commitment> AndExpr<C> {
@@ -40,7 +40,10 @@ impl<C: Commitment> ProvableExpr<C> for AndExpr<C> {
         ColumnType::Boolean
     }
 
-    #[tracing::instrument(name = "AndExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "AndExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -56,7 +59,10 @@ impl<C: Commitment> ProvableExpr<C> for AndExpr<C> {
         Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| lhs[i] && rhs[i]))
     }
 
-    #[tracing::instrument(name = "AndExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "AndExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
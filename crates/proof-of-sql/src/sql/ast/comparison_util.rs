@@ -1,6 +1,9 @@
+use super::ProvableExprPlan;
 use crate::{
     base::{
+        commitment::Commitment,
         database::Column,
+        if_rayon,
         math::decimal::{DecimalError, Precision},
         scalar::Scalar,
     },
@@ -10,10 +13,12 @@ use crate::{
             type_check_binary_operation, ConversionError, ConversionError::DecimalConversionError,
             ConversionResult,
         },
+        proof::ColumnLiteralDifference,
     },
 };
 use bumpalo::Bump;
 use proof_of_sql_parser::intermediate_ast::BinaryOperator;
+#[cfg(feature = "rayon")]
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
 };
@@ -25,11 +30,13 @@ fn unchecked_subtract_impl<'a, S: Scalar>(
     table_length: usize,
 ) -> ConversionResult<&'a [S]> {
     let res = alloc.alloc_slice_fill_default(table_length);
-    res.par_iter_mut()
-        .zip(lhs.par_iter().zip(rhs.par_iter()))
-        .for_each(|(a, (l, r))| {
-            *a = *l - *r;
-        });
+    if_rayon!(
+        res.par_iter_mut().zip(lhs.par_iter().zip(rhs.par_iter())),
+        res.iter_mut().zip(lhs.iter().zip(rhs.iter()))
+    )
+    .for_each(|(a, (l, r))| {
+        *a = *l - *r;
+    });
     Ok(res)
 }
 
@@ -88,3 +95,26 @@ pub(crate) fn scale_and_subtract<'a, S: Scalar>(
         lhs_len,
     )
 }
+
+/// If one side of a comparison is a column and the other is a literal, describe the difference
+/// `lhs - rhs` so that the prover can cache the intermediate MLEs derived from it.
+pub(crate) fn column_literal_difference<C: Commitment>(
+    lhs: &ProvableExprPlan<C>,
+    rhs: &ProvableExprPlan<C>,
+) -> Option<ColumnLiteralDifference> {
+    let (column, literal, literal_first) = match (lhs, rhs) {
+        (ProvableExprPlan::Column(column), ProvableExprPlan::Literal(literal)) => {
+            (column, literal, false)
+        }
+        (ProvableExprPlan::Literal(literal), ProvableExprPlan::Column(column)) => {
+            (column, literal, true)
+        }
+        _ => return None,
+    };
+    Some(ColumnLiteralDifference {
+        column: column.get_column_reference(),
+        literal_type: literal.value().column_type(),
+        literal: literal.value().to_scalar().into(),
+        literal_first,
+    })
+}
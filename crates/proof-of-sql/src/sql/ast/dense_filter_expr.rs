@@ -6,7 +6,7 @@ use crate::{
         commitment::Commitment,
         database::{
             filter_util::filter_columns, Column, ColumnField, ColumnRef, CommitmentAccessor,
-            DataAccessor, MetadataAccessor, OwnedTable,
+            DataAccessor, LiteralValue, MetadataAccessor, OwnedTable,
         },
         proof::ProofError,
         scalar::Scalar,
@@ -21,6 +21,7 @@ use bumpalo::Bump;
 use core::{iter::repeat_with, marker::PhantomData};
 use indexmap::IndexSet;
 use num_traits::{One, Zero};
+use proof_of_sql_parser::Identifier;
 use serde::{Deserialize, Serialize};
 
 /// Provable expressions for queries of the form
@@ -144,8 +145,54 @@ where
 /// Alias for a dense filter expression with a honest prover.
 pub type DenseFilterExpr<C> = OstensibleDenseFilterExpr<C, HonestProver>;
 
+impl<C: Commitment> DenseFilterExpr<C> {
+    /// Returns the row indexes of the rows that satisfy the where clause, in table order.
+    pub(crate) fn selected_row_indexes(
+        &self,
+        accessor: &dyn DataAccessor<C::Scalar>,
+    ) -> Vec<usize> {
+        let alloc = Bump::new();
+        let offset = accessor.get_offset(self.table.table_ref);
+        let length = accessor.get_length(self.table.table_ref);
+        let selection_column = self.where_clause.result_evaluate(length, &alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+        (0..length)
+            .filter(|&i| selection[i])
+            .map(|i| offset + i)
+            .collect()
+    }
+
+    /// Returns the same filter restricted to the rows before the row index `end`, whose result has
+    /// the row index of every selected row as an additional column named `row_index_alias`.
+    pub(crate) fn with_row_index_bound(&self, end: usize, row_index_alias: Identifier) -> Self {
+        let table_ref = self.table.table_ref;
+        let is_before_end = ProvableExprPlan::try_new_inequality(
+            ProvableExprPlan::new_row_index(table_ref),
+            ProvableExprPlan::new_literal(LiteralValue::BigInt(end as i64 - 1)),
+            true,
+        )
+        .expect("row indexes are comparable to bigints");
+        let mut aliased_results = self.aliased_results.clone();
+        aliased_results.push(AliasedProvableExprPlan {
+            expr: ProvableExprPlan::new_row_index(table_ref),
+            alias: row_index_alias,
+        });
+        Self::new(
+            aliased_results,
+            TableExpr { table_ref },
+            ProvableExprPlan::try_new_and(self.where_clause.clone(), is_before_end)
+                .expect("both operands are boolean"),
+        )
+    }
+}
+
 impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
-    #[tracing::instrument(name = "DenseFilterExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "DenseFilterExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         builder: &mut ResultBuilder<'a>,
@@ -177,7 +224,10 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DenseFilterExpr<C> {
         builder.request_post_result_challenges(2);
     }
 
-    #[tracing::instrument(name = "DenseFilterExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "DenseFilterExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     #[allow(unused_variables)]
     fn prover_evaluate<'a>(
         &self,
@@ -12,7 +12,8 @@ use crate::{
             test_utility::*, ColumnExpr, DenseFilterExpr, LiteralExpr, ProvableExprPlan, TableExpr,
         },
         proof::{
-            exercise_verification, ProofExpr, ProverEvaluate, ResultBuilder, VerifiableQueryResult,
+            exercise_verification, ProofExpr, ProverEvaluate, QueryError, ResultBuilder,
+            VerifiableQueryResult,
         },
     },
 };
@@ -180,6 +181,75 @@ fn we_can_prove_and_get_the_correct_result_from_a_basic_dense_filter() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_prove_a_dense_filter_with_boolean_result_expressions() {
+    let data = owned_table([
+        bigint("a", [1_i64, 4, 5, 2, 5]),
+        bigint("b", [1_i64, 2, 6, 4, 5]),
+        boolean("c", [true, false, true, true, false]),
+        decimal75("d", 3, 1, [10, 45, -5, 20, 51]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    // SELECT a, a > b AS gt, a = b OR c AS eq_or_c, d <= a AS le FROM t WHERE b <> 4
+    let ast = dense_filter(
+        vec![
+            col_expr_plan(t, "a", &accessor),
+            aliased_plan(
+                not(lte(column(t, "a", &accessor), column(t, "b", &accessor))),
+                "gt",
+            ),
+            aliased_plan(
+                or(
+                    equal(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    column(t, "c", &accessor),
+                ),
+                "eq_or_c",
+            ),
+            aliased_plan(
+                lte(column(t, "d", &accessor), column(t, "a", &accessor)),
+                "le",
+            ),
+        ],
+        tab(t),
+        not(equal(column(t, "b", &accessor), const_bigint(4))),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 4, 5, 5]),
+        boolean("gt", [false, true, false, false]),
+        boolean("eq_or_c", [true, false, true, true]),
+        boolean("le", [true, false, true, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_verify_the_number_of_rows_selected_by_a_dense_filter() {
+    let data = owned_table([
+        bigint("a", [1_i64, 4_i64, 5_i64, 2_i64, 5_i64]),
+        bigint("b", [1_i64, 2, 3, 4, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let where_clause = equal(column(t, "a", &accessor), const_int128(5_i128));
+    let ast = dense_filter(cols_expr_plan(t, &["b"], &accessor), tab(t), where_clause);
+    let data = VerifiableQueryResult::new(&ast, &accessor, &())
+        .verify(&ast, &accessor, &())
+        .unwrap();
+    assert_eq!(data.selected_rows, 2);
+    assert!(data.check_min_selected_rows(2).is_ok());
+    assert!(matches!(
+        data.check_min_selected_rows(3),
+        Err(QueryError::TooFewSelectedRows {
+            selected_rows: 2,
+            min_selected_rows: 3,
+        })
+    ));
+}
+
 #[test]
 fn we_can_get_an_empty_result_from_a_basic_dense_filter_on_an_empty_table_using_result_evaluate() {
     let data = owned_table([
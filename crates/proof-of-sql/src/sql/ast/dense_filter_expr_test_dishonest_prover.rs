@@ -27,10 +27,13 @@ impl ProverHonestyMarker for Dishonest {}
 type DishonestDenseFilterExpr<C> = OstensibleDenseFilterExpr<C, Dishonest>;
 
 impl ProverEvaluate<Curve25519Scalar> for DishonestDenseFilterExpr<RistrettoPoint> {
-    #[tracing::instrument(
-        name = "DishonestDenseFilterExpr::result_evaluate",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "DishonestDenseFilterExpr::result_evaluate",
+            level = "debug",
+            skip_all
+        )
     )]
     fn result_evaluate<'a>(
         &self,
@@ -63,10 +66,13 @@ impl ProverEvaluate<Curve25519Scalar> for DishonestDenseFilterExpr<RistrettoPoin
         builder.request_post_result_challenges(2);
     }
 
-    #[tracing::instrument(
-        name = "DishonestDenseFilterExpr::prover_evaluate",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "DishonestDenseFilterExpr::prover_evaluate",
+            level = "debug",
+            skip_all
+        )
     )]
     #[allow(unused_variables)]
     fn prover_evaluate<'a>(
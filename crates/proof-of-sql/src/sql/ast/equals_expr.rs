@@ -1,4 +1,7 @@
-use super::{scale_and_add_subtract_eval, scale_and_subtract, ProvableExpr, ProvableExprPlan};
+use super::{
+    column_literal_difference, scale_and_add_subtract_eval, scale_and_subtract, ProvableExpr,
+    ProvableExprPlan,
+};
 use crate::{
     base::{
         commitment::Commitment,
@@ -7,7 +10,10 @@ use crate::{
         scalar::Scalar,
         slice_ops,
     },
-    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{
+        ColumnLiteralDifference, CountBuilder, DerivedMleKey, DerivedMleKind, DerivedMles,
+        ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder,
+    },
 };
 use bumpalo::Bump;
 use indexmap::IndexSet;
@@ -16,8 +22,8 @@ use serde::{Deserialize, Serialize};
 /// Provable AST expression for an equals expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct EqualsExpr<C: Commitment> {
-    lhs: Box<ProvableExprPlan<C>>,
-    rhs: Box<ProvableExprPlan<C>>,
+    pub(super) lhs: Box<ProvableExprPlan<C>>,
+    pub(super) rhs: Box<ProvableExprPlan<C>>,
 }
 
 impl<C: Commitment> EqualsExpr<C> {
@@ -39,7 +45,10 @@ impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
         ColumnType::Boolean
     }
 
-    #[tracing::instrument(name = "EqualsExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "EqualsExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -55,7 +64,10 @@ impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
         Column::Boolean(result_evaluate_equals_zero(table_length, alloc, res))
     }
 
-    #[tracing::instrument(name = "EqualsExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "EqualsExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
@@ -68,7 +80,8 @@ impl<C: Commitment> ProvableExpr<C> for EqualsExpr<C> {
         let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
         let res = scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, true)
             .expect("Failed to scale and subtract");
-        Column::Boolean(prover_evaluate_equals_zero(builder, alloc, res))
+        let difference = column_literal_difference(&self.lhs, &self.rhs);
+        Column::Boolean(prover_evaluate_equals_zero(builder, alloc, res, difference))
     }
 
     fn verifier_evaluate(
@@ -99,21 +112,51 @@ pub fn result_evaluate_equals_zero<'a, S: Scalar>(
     alloc.alloc_slice_fill_with(table_length, |i| lhs[i] == S::zero())
 }
 
+/// Prove which entries of `lhs` are zero.
+///
+/// If `lhs` is the difference between a column and a literal, the witness is reused from, or
+/// stored in, the prover cache.
 pub fn prover_evaluate_equals_zero<'a, S: Scalar>(
     builder: &mut ProofBuilder<'a, S>,
     alloc: &'a Bump,
     lhs: &'a [S],
+    difference: Option<ColumnLiteralDifference>,
 ) -> &'a [bool] {
     let table_length = builder.table_length();
-
-    // lhs_pseudo_inv
-    let lhs_pseudo_inv = alloc.alloc_slice_copy(lhs);
-    slice_ops::batch_inversion(lhs_pseudo_inv);
-
-    builder.produce_intermediate_mle(lhs_pseudo_inv as &[_]);
-
-    // selection_not
-    let selection_not: &[_] = alloc.alloc_slice_fill_with(table_length, |i| lhs[i] != S::zero());
+    let key = difference.map(|difference| DerivedMleKey {
+        difference,
+        kind: DerivedMleKind::EqualsZero,
+    });
+    let cached = key.and_then(|key| builder.cached_derived_mles(&key));
+
+    let (lhs_pseudo_inv, selection_not): (&[_], &[_]) = match cached.as_deref() {
+        Some(DerivedMles::EqualsZero {
+            lhs_pseudo_inv,
+            selection_not,
+        }) => (
+            alloc.alloc_slice_copy(lhs_pseudo_inv),
+            alloc.alloc_slice_copy(selection_not),
+        ),
+        _ => {
+            // lhs_pseudo_inv
+            let lhs_pseudo_inv = alloc.alloc_slice_copy(lhs);
+            slice_ops::batch_inversion(lhs_pseudo_inv);
+            let lhs_pseudo_inv: &[_] = lhs_pseudo_inv;
+
+            // selection_not
+            let selection_not: &[_] =
+                alloc.alloc_slice_fill_with(table_length, |i| lhs[i] != S::zero());
+
+            if let Some(key) = key {
+                builder.cache_derived_mles(key, || DerivedMles::EqualsZero {
+                    lhs_pseudo_inv: lhs_pseudo_inv.to_vec(),
+                    selection_not: selection_not.to_vec(),
+                });
+            }
+            (lhs_pseudo_inv, selection_not)
+        }
+    };
+    builder.produce_intermediate_mle(lhs_pseudo_inv);
     builder.produce_intermediate_mle(selection_not);
 
     // selection
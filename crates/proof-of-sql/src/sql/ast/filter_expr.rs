@@ -111,7 +111,10 @@ where
 
 pub type FilterExpr<C> = OstensibleFilterExpr<C, HonestProver>;
 impl<C: Commitment> ProverEvaluate<C::Scalar> for FilterExpr<C> {
-    #[tracing::instrument(name = "FilterExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "FilterExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         builder: &mut ResultBuilder<'a>,
@@ -141,7 +144,10 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for FilterExpr<C> {
         }
     }
 
-    #[tracing::instrument(name = "FilterExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "FilterExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
@@ -23,10 +23,13 @@ impl ProverHonestyMarker for Dishonest {}
 type DishonestFilterExpr = OstensibleFilterExpr<RistrettoPoint, Dishonest>;
 
 impl ProverEvaluate<Curve25519Scalar> for DishonestFilterExpr {
-    #[tracing::instrument(
-        name = "DishonestFilterExpr::result_evaluate",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "DishonestFilterExpr::result_evaluate",
+            level = "debug",
+            skip_all
+        )
     )]
     fn result_evaluate<'a>(
         &self,
@@ -53,10 +56,13 @@ impl ProverEvaluate<Curve25519Scalar> for DishonestFilterExpr {
         builder.set_result_indexes(Indexes::Sparse(indexes));
     }
 
-    #[tracing::instrument(
-        name = "DishonestFilterExpr::prover_evaluate",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "DishonestFilterExpr::prover_evaluate",
+            level = "debug",
+            skip_all
+        )
     )]
     fn prover_evaluate<'a>(
         &self,
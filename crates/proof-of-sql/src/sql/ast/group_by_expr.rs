@@ -1,6 +1,5 @@
 use super::{
-    fold_columns, fold_vals, AliasedProvableExprPlan, ColumnExpr, ProvableExpr, ProvableExprPlan,
-    TableExpr,
+    fold_columns, fold_vals, AliasedProvableExprPlan, ProvableExpr, ProvableExprPlan, TableExpr,
 };
 use crate::{
     base::{
@@ -10,7 +9,7 @@ use crate::{
                 aggregate_columns, compare_indexes_by_owned_columns, AggregatedColumns,
             },
             Column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
-            MetadataAccessor, OwnedTable,
+            MetadataAccessor, OwnedColumn, OwnedTable,
         },
         proof::ProofError,
         scalar::Scalar,
@@ -39,9 +38,16 @@ use serde::{Deserialize, Serialize};
 /// ```
 ///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+///
+/// Group by expressions are usually columns, but any provable expression, e.g. `time_bucket`, may be used.
+///
+/// Inside the proof, the key of a row is encoded as `alpha + sum beta^j * g[j]` over the group by
+/// expressions `g`, where `alpha` and `beta` are post-result challenges. Two different key tuples,
+/// e.g. `(1, 2)` and `(2, 1)`, only get the same encoding if `beta` is a root of their difference,
+/// which happens with negligible probability, so composite keys are as safe as single columns.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct GroupByExpr<C: Commitment> {
-    pub(super) group_by_exprs: Vec<ColumnExpr<C>>,
+    pub(super) group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
     pub(super) sum_expr: Vec<AliasedProvableExprPlan<C>>,
     pub(super) count_alias: Identifier,
     pub(super) table: TableExpr,
@@ -51,7 +57,7 @@ pub struct GroupByExpr<C: Commitment> {
 impl<C: Commitment> GroupByExpr<C> {
     /// Creates a new group_by expression.
     pub fn new(
-        group_by_exprs: Vec<ColumnExpr<C>>,
+        group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
         sum_expr: Vec<AliasedProvableExprPlan<C>>,
         count_alias: Identifier,
         table: TableExpr,
@@ -74,8 +80,8 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         _accessor: &dyn MetadataAccessor,
     ) -> Result<(), ProofError> {
         self.where_clause.count(builder)?;
-        for expr in self.group_by_exprs.iter() {
-            expr.count(builder)?;
+        for aliased_expr in self.group_by_exprs.iter() {
+            aliased_expr.expr.count(builder)?;
             builder.count_result_columns(1);
         }
         for aliased_expr in self.sum_expr.iter() {
@@ -111,7 +117,7 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, accessor))
+            .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
             .sum_expr
@@ -151,13 +157,13 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
                 let cols = self
                     .group_by_exprs
                     .iter()
-                    .map(|col| table.inner_table().get(&col.column_id()))
+                    .map(|aliased_expr| table.inner_table().get(&aliased_expr.alias))
                     .collect::<Option<Vec<_>>>()
                     .ok_or(ProofError::VerificationError(
                         "Result does not all correct group by columns.",
                     ))?;
-                if (0..table.num_rows() - 1)
-                    .any(|i| compare_indexes_by_owned_columns(&cols, i, i + 1).is_ge())
+                if (1..table.num_rows())
+                    .any(|i| compare_indexes_by_owned_columns(&cols, i - 1, i).is_ge())
                 {
                     Err(ProofError::VerificationError(
                         "Result of group by not ordered as expected.",
@@ -172,10 +178,8 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.group_by_exprs
             .iter()
-            .map(|col| col.get_column_field())
-            .chain(self.sum_expr.iter().map(|aliased_expr| {
-                ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type())
-            }))
+            .chain(self.sum_expr.iter())
+            .map(|aliased_expr| ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type()))
             .chain(std::iter::once(ColumnField::new(
                 self.count_alias,
                 ColumnType::BigInt,
@@ -186,8 +190,8 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
         let mut columns = IndexSet::new();
 
-        for col in self.group_by_exprs.iter() {
-            columns.insert(col.get_column_reference());
+        for aliased_expr in self.group_by_exprs.iter() {
+            aliased_expr.expr.get_column_references(&mut columns);
         }
         for aliased_expr in self.sum_expr.iter() {
             aliased_expr.expr.get_column_references(&mut columns);
@@ -197,10 +201,21 @@ impl<C: Commitment> ProofExpr<C> for GroupByExpr<C> {
 
         columns
     }
+
+    fn get_selected_row_count(&self, result: &OwnedTable<C::Scalar>) -> usize {
+        // Every selected row is counted in exactly one group.
+        match result.inner_table().get(&self.count_alias) {
+            Some(OwnedColumn::BigInt(counts)) => counts.iter().sum::<i64>() as usize,
+            _ => 0,
+        }
+    }
 }
 
 impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
-    #[tracing::instrument(name = "GroupByExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "GroupByExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         builder: &mut ResultBuilder<'a>,
@@ -217,11 +232,11 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
             .expect("selection is not boolean");
 
         // 2. columns
-        let group_by_columns = Vec::from_iter(
-            self.group_by_exprs
-                .iter()
-                .map(|expr| expr.result_evaluate(builder.table_length(), alloc, accessor)),
-        );
+        let group_by_columns = Vec::from_iter(self.group_by_exprs.iter().map(|aliased_expr| {
+            aliased_expr
+                .expr
+                .result_evaluate(builder.table_length(), alloc, accessor)
+        }));
         let sum_columns = Vec::from_iter(self.sum_expr.iter().map(|aliased_expr| {
             aliased_expr
                 .expr
@@ -248,7 +263,10 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
         builder.request_post_result_challenges(2);
     }
 
-    #[tracing::instrument(name = "GroupByExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "GroupByExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     #[allow(unused_variables)]
     fn prover_evaluate<'a>(
         &self,
@@ -267,7 +285,7 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExpr<C> {
         let group_by_columns = Vec::from_iter(
             self.group_by_exprs
                 .iter()
-                .map(|expr| expr.prover_evaluate(builder, alloc, accessor)),
+                .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor)),
         );
         let sum_columns = Vec::from_iter(
             self.sum_expr
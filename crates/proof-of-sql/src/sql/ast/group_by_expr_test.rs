@@ -5,7 +5,10 @@ use crate::{
         database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
         scalar::Curve25519Scalar,
     },
-    sql::proof::{exercise_verification, VerifiableQueryResult},
+    sql::proof::{
+        exercise_verification, Indexes, ProvableQueryResult, ProvableResultColumn,
+        VerifiableQueryResult,
+    },
 };
 
 /// select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a
@@ -37,6 +40,75 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+/// select sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99
+#[test]
+fn we_can_verify_the_number_of_rows_selected_by_a_group_by_that_only_returns_aggregates() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        vec![],
+        vec![sum_expr(column(t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let data = VerifiableQueryResult::new(&expr, &accessor, &())
+        .verify(&expr, &accessor, &())
+        .unwrap();
+    assert_eq!(data.table.num_rows(), 1);
+    assert_eq!(data.selected_rows, 4);
+    assert!(data.check_min_selected_rows(4).is_ok());
+    assert!(data.check_min_selected_rows(5).is_err());
+
+    // grouping does not change the number of selected rows
+    let expr = group_by(
+        cols_expr(t, &["a"], &accessor),
+        vec![],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let data = VerifiableQueryResult::new(&expr, &accessor, &())
+        .verify(&expr, &accessor, &())
+        .unwrap();
+    assert_eq!(data.selected_rows, 4);
+}
+
+/// select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 1 group by a
+#[test]
+fn we_can_prove_a_group_by_that_returns_no_groups_from_a_non_empty_table() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("b", [99, 99, 99, 99, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr(t, &["a"], &accessor),
+        vec![sum_expr(column(t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(1)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [0; 0]),
+        bigint("sum_c", [0; 0]),
+        bigint("__count__", [0; 0]),
+    ]);
+    assert_eq!(res, expected);
+}
+
 /// select a, sum(c * 2 + 1) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a
 #[test]
 fn we_can_prove_a_group_by_with_bigint_columns() {
@@ -226,3 +298,72 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     ]);
     assert_eq!(res, expected);
 }
+
+/// select a, b, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a, b
+#[test]
+fn we_can_prove_a_group_by_with_composite_keys_whose_columns_have_the_same_sum() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3, 1, 0, 2]),
+        bigint("b", [2, 1, 0, 2, 3, 1]),
+        bigint("c", [10, 20, 30, 40, 50, 60]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr(t, &["a", "b"], &accessor),
+        vec![sum_expr(column(t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let expected = owned_table([
+        bigint("a", [0, 1, 2, 3]),
+        bigint("b", [3, 2, 1, 0]),
+        bigint("sum_c", [50, 10 + 40, 20 + 60, 30]),
+        bigint("__count__", [1, 2, 2, 1]),
+    ]);
+    assert_eq!(res.verify(&expr, &accessor, &()).unwrap().table, expected);
+
+    // Swapping the aggregates of (1, 2) and (2, 1) is rejected, even though the keys have the
+    // same sum
+    let mut res_p = res.clone();
+    let cols: [Box<dyn ProvableResultColumn>; 4] = [
+        Box::new([0_i64, 1, 2, 3]),
+        Box::new([3_i64, 2, 1, 0]),
+        Box::new([50_i64, 20 + 60, 10 + 40, 30]),
+        Box::new([1_i64, 2, 2, 1]),
+    ];
+    res_p.provable_result = Some(ProvableQueryResult::new(&Indexes::Dense(0..4), &cols));
+    assert!(res_p.verify(&expr, &accessor, &()).is_err());
+}
+
+/// select x, y, count(*) as __count__ from sxt.t group by x, y
+#[test]
+fn we_can_prove_a_group_by_with_composite_varchar_keys_whose_concatenations_are_equal() {
+    let data = owned_table([
+        varchar("x", ["ab", "a", "ab", "", "abc"]),
+        varchar("y", ["c", "bc", "c", "abc", ""]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr(t, &["x", "y"], &accessor),
+        vec![],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        varchar("x", ["", "a", "ab", "abc"]),
+        varchar("y", ["abc", "bc", "c", ""]),
+        bigint("__count__", [1, 1, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
@@ -1,6 +1,7 @@
 use super::{
-    count_equals_zero, count_or, count_sign, prover_evaluate_equals_zero, prover_evaluate_or,
-    prover_evaluate_sign, result_evaluate_equals_zero, result_evaluate_or, result_evaluate_sign,
+    column_literal_difference, count_equals_zero, count_or, count_sign,
+    prover_evaluate_equals_zero, prover_evaluate_or, prover_evaluate_sign,
+    result_evaluate_equals_zero, result_evaluate_or, result_evaluate_sign,
     scale_and_add_subtract_eval, scale_and_subtract, verifier_evaluate_equals_zero,
     verifier_evaluate_or, verifier_evaluate_sign, ProvableExpr, ProvableExprPlan,
 };
@@ -9,19 +10,21 @@ use crate::{
         commitment::Commitment,
         database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
         proof::ProofError,
+        scalar::Scalar,
     },
-    sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
 };
 use bumpalo::Bump;
 use indexmap::IndexSet;
+use num_traits::One;
 use serde::{Deserialize, Serialize};
 
 /// Provable AST expression for an inequality expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct InequalityExpr<C: Commitment> {
-    lhs: Box<ProvableExprPlan<C>>,
-    rhs: Box<ProvableExprPlan<C>>,
-    is_lte: bool,
+    pub(super) lhs: Box<ProvableExprPlan<C>>,
+    pub(super) rhs: Box<ProvableExprPlan<C>>,
+    pub(super) is_lte: bool,
     #[cfg(test)]
     pub(crate) treat_column_of_zeros_as_negative: bool,
 }
@@ -53,7 +56,10 @@ impl<C: Commitment> ProvableExpr<C> for InequalityExpr<C> {
         ColumnType::Boolean
     }
 
-    #[tracing::instrument(name = "InequalityExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "InequalityExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -82,7 +88,10 @@ impl<C: Commitment> ProvableExpr<C> for InequalityExpr<C> {
         Column::Boolean(result_evaluate_or(table_length, alloc, equals_zero, sign))
     }
 
-    #[tracing::instrument(name = "InequalityExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "InequalityExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
@@ -93,22 +102,29 @@ impl<C: Commitment> ProvableExpr<C> for InequalityExpr<C> {
         let rhs_column = self.rhs.prover_evaluate(builder, alloc, accessor);
         let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
         let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
-        let diff = if self.is_lte {
-            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
-                .expect("Failed to scale and subtract")
+        let (diff, difference) = if self.is_lte {
+            (
+                scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
+                    .expect("Failed to scale and subtract"),
+                column_literal_difference(&self.lhs, &self.rhs),
+            )
         } else {
-            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
-                .expect("Failed to scale and subtract")
+            (
+                scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
+                    .expect("Failed to scale and subtract"),
+                column_literal_difference(&self.rhs, &self.lhs),
+            )
         };
 
         // diff == 0
-        let equals_zero = prover_evaluate_equals_zero(builder, alloc, diff);
+        let equals_zero = prover_evaluate_equals_zero(builder, alloc, diff, difference);
 
         // sign(diff) == -1
         let sign = prover_evaluate_sign(
             builder,
             alloc,
             diff,
+            difference,
             #[cfg(test)]
             self.treat_column_of_zeros_as_negative,
         );
@@ -148,3 +164,68 @@ impl<C: Commitment> ProvableExpr<C> for InequalityExpr<C> {
         self.rhs.get_column_references(columns);
     }
 }
+
+/// Count the components needed to prove that every entry of an expression is less than or equal
+/// to zero.
+pub fn count_non_positive(builder: &mut CountBuilder) -> Result<(), ProofError> {
+    count_equals_zero(builder);
+    count_sign(builder)?;
+    count_or(builder);
+    builder.count_subpolynomials(1);
+    Ok(())
+}
+
+/// Prove that every entry of `expr` is less than or equal to zero.
+pub fn prove_non_positive<'a, S: Scalar>(
+    builder: &mut ProofBuilder<'a, S>,
+    alloc: &'a Bump,
+    expr: &'a [S],
+) {
+    // expr == 0
+    let equals_zero = prover_evaluate_equals_zero(builder, alloc, expr, None);
+
+    // sign(expr) == -1
+    let sign = prover_evaluate_sign(
+        builder,
+        alloc,
+        expr,
+        None,
+        #[cfg(test)]
+        false,
+    );
+
+    // (expr == 0) || (sign(expr) == -1)
+    let non_positive = prover_evaluate_or(builder, alloc, equals_zero, sign);
+
+    // subpolynomial: 1 - non_positive
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![]),
+            (-S::one(), vec![Box::new(non_positive)]),
+        ],
+    );
+}
+
+/// Verify that every entry of the expression with evaluation `eval` is less than or equal to zero.
+///
+/// See `prove_non_positive`.
+pub fn verify_non_positive<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    eval: C::Scalar,
+    one_eval: C::Scalar,
+) -> Result<(), ProofError> {
+    // expr == 0
+    let equals_zero = verifier_evaluate_equals_zero(builder, eval);
+
+    // sign(expr) == -1
+    let sign = verifier_evaluate_sign(builder, eval, one_eval)?;
+
+    // (expr == 0) || (sign(expr) == -1)
+    let non_positive = verifier_evaluate_or(builder, &equals_zero, &sign);
+
+    // subpolynomial: 1 - non_positive
+    let eval = builder.mle_evaluations.random_evaluation * (one_eval - non_positive);
+    builder.produce_sumcheck_subpolynomial_evaluation(&eval);
+    Ok(())
+}
@@ -33,6 +33,11 @@ impl<S: Scalar> LiteralExpr<S> {
     pub fn new(value: LiteralValue<S>) -> Self {
         Self { value }
     }
+
+    /// Returns the value of the literal
+    pub(crate) fn value(&self) -> &LiteralValue<S> {
+        &self.value
+    }
 }
 
 impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
@@ -44,7 +49,10 @@ impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
         self.value.column_type()
     }
 
-    #[tracing::instrument(name = "LiteralExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "LiteralExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -54,7 +62,10 @@ impl<C: Commitment> ProvableExpr<C> for LiteralExpr<C::Scalar> {
         Column::from_literal_with_length(&self.value, table_length, alloc)
     }
 
-    #[tracing::instrument(name = "LiteralExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "LiteralExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
@@ -5,6 +5,9 @@ pub(crate) use aliased_provable_expr_plan::AliasedProvableExprPlan;
 mod filter_result_expr;
 pub(crate) use filter_result_expr::FilterResultExpr;
 
+mod overflow_policy;
+pub use overflow_policy::OverflowPolicy;
+
 mod add_subtract_expr;
 pub(crate) use add_subtract_expr::AddSubtractExpr;
 #[cfg(all(test, feature = "blitzar"))]
@@ -32,8 +35,13 @@ use bitwise_verification::*;
 #[cfg(test)]
 mod bitwise_verification_test;
 
+mod range_audit;
+use range_audit::*;
+#[cfg(test)]
+mod range_audit_test;
+
 mod provable_expr_plan;
-pub(crate) use provable_expr_plan::ProvableExprPlan;
+pub use provable_expr_plan::ProvableExprPlan;
 
 mod provable_expr;
 pub(crate) use provable_expr::ProvableExpr;
@@ -71,7 +79,7 @@ use not_expr::NotExpr;
 mod not_expr_test;
 
 mod comparison_util;
-pub(crate) use comparison_util::scale_and_subtract;
+pub(crate) use comparison_util::{column_literal_difference, scale_and_subtract};
 
 mod numerical_util;
 pub(crate) use numerical_util::{
@@ -88,6 +96,31 @@ use sign_expr::*;
 #[cfg(all(test, feature = "blitzar"))]
 mod sign_expr_test;
 
+mod time_bucket_expr;
+pub(crate) use time_bucket_expr::TimeBucketExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod time_bucket_expr_test;
+
+mod abs_expr;
+pub(crate) use abs_expr::AbsExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod abs_expr_test;
+
+mod signum_expr;
+pub(crate) use signum_expr::SignumExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod signum_expr_test;
+
+mod modulo_expr;
+pub(crate) use modulo_expr::ModuloExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod modulo_expr_test;
+
+mod row_index_expr;
+pub(crate) use row_index_expr::RowIndexExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod row_index_expr_test;
+
 mod table_expr;
 pub(crate) use table_expr::TableExpr;
 
@@ -121,3 +154,28 @@ mod group_by_expr_test;
 
 mod proof_plan;
 pub use proof_plan::ProofPlan;
+
+mod proof_plan_builder;
+pub use proof_plan_builder::ProofPlanBuilder;
+#[cfg(all(test, feature = "blitzar"))]
+mod proof_plan_builder_test;
+
+mod proof_plan_encoding;
+pub use proof_plan_encoding::{PlanEncodingError, PROOF_PLAN_ENCODING_VERSION};
+#[cfg(all(test, feature = "blitzar"))]
+mod proof_plan_encoding_test;
+
+mod uniqueness_proof;
+pub use uniqueness_proof::{UniquenessProof, UniquenessProofError};
+#[cfg(all(test, feature = "blitzar"))]
+mod uniqueness_proof_test;
+
+mod table_length_proof;
+pub use table_length_proof::{TableLengthProof, TableLengthProofError};
+#[cfg(all(test, feature = "blitzar"))]
+mod table_length_proof_test;
+
+mod monotonic_append_proof;
+pub use monotonic_append_proof::{MonotonicAppendProof, MonotonicAppendProofError};
+#[cfg(all(test, feature = "blitzar"))]
+mod monotonic_append_proof_test;
@@ -0,0 +1,226 @@
+use super::{
+    count_non_positive, count_sign, prove_non_positive, prover_evaluate_sign,
+    verifier_evaluate_sign, verify_non_positive, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use indexmap::IndexSet;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+
+/// Provable `expr % modulus` expression over an integer expression and a positive constant.
+///
+/// The remainder has the sign of `expr`, i.e. `expr == modulus * trunc(expr / modulus) + expr % modulus`.
+///
+/// The prover commits to the quotient `q = |x| / modulus` and the remainder `r = |x| - modulus * q`
+/// of the absolute value of `x`, where `|x| = x - 2 * x * s` and `s` is the sign bit of `x`. It
+/// proves that `0 <= q`, which bounds `q` well below the order of the field, and that
+/// `0 <= r <= modulus - 1`, which pins down `q` and `r`. The result is then `m = r - 2 * r * s`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModuloExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+    modulus: i64,
+}
+
+impl<C: Commitment> ModuloExpr<C> {
+    /// Create a new `%` expression
+    pub fn new(expr: Box<ProvableExprPlan<C>>, modulus: i64) -> Self {
+        Self { expr, modulus }
+    }
+
+    /// Returns the constant that the input expression is divided by
+    pub fn modulus(&self) -> i64 {
+        self.modulus
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for ModuloExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        count_sign(builder)?;
+        builder.count_intermediate_mles(3);
+        builder.count_subpolynomials(2);
+        builder.count_degree(3);
+        // 0 <= q, 0 <= r and r <= modulus - 1
+        for _ in 0..3 {
+            count_non_positive(builder)?;
+        }
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ModuloExpr::result_evaluate", level = "debug", skip_all)
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let values = integer_values(&column.to_scalar_with_scaling(0));
+        let modulus = i128::from(self.modulus);
+        Column::Scalar(
+            alloc.alloc_slice_fill_with(values.len(), |i| C::Scalar::from(values[i] % modulus)),
+        )
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ModuloExpr::prover_evaluate", level = "debug", skip_all)
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let values: &[_] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+
+        // sign(x) == -1
+        let sign = prover_evaluate_sign(
+            builder,
+            alloc,
+            values,
+            None,
+            #[cfg(test)]
+            false,
+        );
+
+        // q, r and m
+        let (quotients, remainders, results) =
+            compute_quotients_and_remainders(alloc, &integer_values(values), self.modulus);
+        builder.produce_intermediate_mle(quotients);
+        builder.produce_intermediate_mle(remainders);
+        builder.produce_intermediate_mle(results);
+
+        let one = C::Scalar::one();
+        let modulus = C::Scalar::from(self.modulus);
+
+        // subpolynomial: r - x + 2 * x * s + modulus * q
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (one, vec![Box::new(remainders)]),
+                (-one, vec![Box::new(values)]),
+                (C::Scalar::TWO, vec![Box::new(values), Box::new(sign)]),
+                (modulus, vec![Box::new(quotients)]),
+            ],
+        );
+
+        // subpolynomial: m - r + 2 * r * s
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (one, vec![Box::new(results)]),
+                (-one, vec![Box::new(remainders)]),
+                (C::Scalar::TWO, vec![Box::new(remainders), Box::new(sign)]),
+            ],
+        );
+
+        // -q <= 0, -r <= 0 and r - (modulus - 1) <= 0
+        let n = values.len();
+        let upper_bound = modulus - one;
+        for bound in [
+            alloc.alloc_slice_fill_with(n, |i| -quotients[i]),
+            alloc.alloc_slice_fill_with(n, |i| -remainders[i]),
+            alloc.alloc_slice_fill_with(n, |i| remainders[i] - upper_bound),
+        ] {
+            prove_non_positive(builder, alloc, bound);
+        }
+
+        Column::Scalar(results)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let eval = self.expr.verifier_evaluate(builder, accessor)?;
+
+        // sign(x) == -1
+        let sign_eval = verifier_evaluate_sign(builder, eval, one_eval)?;
+
+        // q, r and m
+        let quotient_eval = builder.consume_intermediate_mle();
+        let remainder_eval = builder.consume_intermediate_mle();
+        let result_eval = builder.consume_intermediate_mle();
+
+        let modulus = C::Scalar::from(self.modulus);
+
+        // subpolynomial: r - x + 2 * x * s + modulus * q
+        let subpolynomial_eval = builder.mle_evaluations.random_evaluation
+            * (remainder_eval - eval + C::Scalar::TWO * eval * sign_eval + modulus * quotient_eval);
+        builder.produce_sumcheck_subpolynomial_evaluation(&subpolynomial_eval);
+
+        // subpolynomial: m - r + 2 * r * s
+        let subpolynomial_eval = builder.mle_evaluations.random_evaluation
+            * (result_eval - remainder_eval + C::Scalar::TWO * remainder_eval * sign_eval);
+        builder.produce_sumcheck_subpolynomial_evaluation(&subpolynomial_eval);
+
+        // -q <= 0, -r <= 0 and r - (modulus - 1) <= 0
+        verify_non_positive(builder, -quotient_eval, one_eval)?;
+        verify_non_positive(builder, -remainder_eval, one_eval)?;
+        verify_non_positive(
+            builder,
+            remainder_eval - (modulus - C::Scalar::one()) * one_eval,
+            one_eval,
+        )?;
+
+        Ok(result_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+/// Returns the values of an integer expression from their scalars.
+fn integer_values<S: Scalar>(values: &[S]) -> Vec<i128> {
+    values
+        .iter()
+        .map(|&value| {
+            TryInto::<i128>::try_into(value).expect("integer expressions should fit in an i128")
+        })
+        .collect()
+}
+
+/// Computes the quotients `q` and remainders `r` of the absolute values of `values`, along with
+/// the results `m`, whose sign is that of the values.
+fn compute_quotients_and_remainders<'a, S: Scalar>(
+    alloc: &'a Bump,
+    values: &[i128],
+    modulus: i64,
+) -> (&'a [S], &'a [S], &'a [S]) {
+    let n = values.len();
+    let modulus = i128::from(modulus);
+    let results: Vec<_> = values.iter().map(|value| value % modulus).collect();
+    // The quotient of `i128::MIN` may not fit in an `i128` once it is made positive
+    let quotients = alloc.alloc_slice_fill_with(n, |i| {
+        let quotient = S::from((values[i] - results[i]) / modulus);
+        if values[i] < 0 {
+            -quotient
+        } else {
+            quotient
+        }
+    });
+    let remainders = alloc.alloc_slice_fill_with(n, |i| S::from(results[i].abs()));
+    let results = alloc.alloc_slice_fill_with(n, |i| S::from(results[i]));
+    (quotients, remainders, results)
+}
@@ -0,0 +1,83 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor},
+    },
+    sql::{
+        ast::{test_utility::*, ProvableExprPlan},
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+
+// select a, a % 3 as r from sxt.t where a % 2 = 0
+#[test]
+fn we_can_prove_remainders_in_a_filter_result_and_a_where_clause() {
+    let data = owned_table([bigint(
+        "a",
+        [-7_i64, -6, -4, -1, 0, 1, 4, 5, 8, i64::MIN, i64::MAX],
+    )]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = dense_filter(
+        vec![
+            col_expr_plan(t, "a", &accessor),
+            aliased_plan(modulo(column(t, "a", &accessor), 3), "r"),
+        ],
+        tab(t),
+        equal(modulo(column(t, "a", &accessor), 2), const_bigint(0)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [-6_i64, -4, 0, 4, 8, i64::MIN]),
+        bigint("r", [0_i64, -1, 0, 1, 2, i64::MIN % 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a % 1000 as r, abs(b) % 1 as s from sxt.t
+#[test]
+fn we_can_prove_remainders_of_columns_with_a_constant_sign_and_of_expressions() {
+    let data = owned_table([
+        int128("a", [1_i128, 999, 1000, 123_456_789, i128::MAX]),
+        int128("b", [-1_i128, -2, -3, -4, i128::MIN + 1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = dense_filter(
+        vec![
+            aliased_plan(modulo(column(t, "a", &accessor), 1000), "r"),
+            aliased_plan(modulo(abs(column(t, "b", &accessor)), 1), "s"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int128("r", [1_i128, 999, 0, 789, i128::MAX % 1000]),
+        int128("s", [0_i128; 5]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_take_remainders_of_non_integers_or_by_non_positive_constants() {
+    let data = owned_table([bigint("a", [1_i64]), decimal75("b", 10, 2, [-150_i64])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_modulo(column(t, "b", &accessor), 3),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    for modulus in [0, -3] {
+        assert!(matches!(
+            ProvableExprPlan::<RistrettoPoint>::try_new_modulo(column(t, "a", &accessor), modulus),
+            Err(ConversionError::InvalidExpression(_))
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+use super::{AliasedProvableExprPlan, DenseFilterExpr, ProofPlan, ProvableExprPlan, TableExpr};
+use crate::{
+    base::{
+        commitment::{
+            Commitment, CommitmentEvaluationProof, QueryCommitments, TableCommitment,
+            TableCommitmentArithmeticError,
+        },
+        database::{
+            AccessorResult, Column, ColumnRef, ColumnType, DataAccessor, LiteralValue,
+            MetadataAccessor, OwnedColumn, TableRef,
+        },
+        scalar::Scalar,
+    },
+    sql::proof::{QueryError, VerifiableQueryResult},
+};
+use core::ops::Range;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The alias of the row index in the underlying filter proof.
+const ROW_INDEX_ALIAS: &str = "__monotonic_row_index__";
+/// The alias of the timestamp in the underlying filter proof.
+const TIMESTAMP_ALIAS: &str = "__monotonic_timestamp__";
+
+/// Errors that can occur when creating or verifying a [`MonotonicAppendProof`].
+#[derive(Error, Debug)]
+pub enum MonotonicAppendProofError {
+    /// Only timestamp columns can be checked for monotonic ingestion.
+    #[error("column '{0}' is not a timestamp column")]
+    NotATimestamp(Identifier),
+    /// The appended batch has no rows.
+    #[error("the appended batch has no rows")]
+    EmptyBatch,
+    /// The commitment of the appended batch could not be derived from the table commitments.
+    #[error(transparent)]
+    CommitmentArithmetic(#[from] TableCommitmentArithmeticError),
+    /// The proof was created for a different column, batch or boundary than the one being
+    /// verified.
+    #[error("the proof is for another column, batch or boundary")]
+    ProofMismatch,
+    /// The underlying proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+    /// The proof verified, but a row of the batch is older than the last committed row.
+    #[error("row {row} has timestamp {timestamp}, which is older than the last committed row")]
+    OutOfOrder {
+        /// The index of the row in the table
+        row: usize,
+        /// The timestamp of the row
+        timestamp: i64,
+    },
+    /// The proof verified, but its result is not that of a monotonic append proof.
+    #[error("the verified result is malformed")]
+    MalformedResult,
+}
+
+/// A proof that a batch of rows appended to a committed table does not go back in time, i.e. that
+/// every timestamp of the batch is at least the timestamp of the last row that was committed
+/// before it.
+///
+/// The verifier only needs the table commitments from before and after the append. It returns the
+/// timestamp of the last row of the batch, which is the boundary of the next batch, so verifying
+/// the proof of every batch in turn shows that the timestamps are non-decreasing across batches.
+/// Rows within a single batch are not compared to each other.
+///
+/// Internally this is a proof of
+/// ```ignore
+///     SELECT <row_index>, <timestamp> FROM <batch>
+///     WHERE NOT (<timestamp> >= <boundary>) OR <row_index> = <last_row_index>
+/// ```
+/// where the verifier additionally checks that the only row of the result is the last one.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MonotonicAppendProof<CP: CommitmentEvaluationProof> {
+    column: ColumnRef,
+    range: Range<usize>,
+    boundary: Option<i64>,
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> MonotonicAppendProof<CP> {
+    /// Prove that the rows of the table after the first `previous_length` ones do not go back in
+    /// time.
+    ///
+    /// The boundary is the timestamp of the last of the `previous_length` rows, if any.
+    /// This succeeds even if the batch is out of order, in which case the proof will fail to
+    /// verify.
+    pub fn new(
+        column: ColumnRef,
+        previous_length: usize,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, MonotonicAppendProofError> {
+        let table_ref = column.table_ref();
+        let length = accessor.get_length(table_ref);
+        if length <= previous_length {
+            return Err(MonotonicAppendProofError::EmptyBatch);
+        }
+        let boundary = match accessor.get_column(column) {
+            Column::TimestampTZ(_, _, timestamps) => {
+                previous_length.checked_sub(1).map(|row| timestamps[row])
+            }
+            _ => return Err(MonotonicAppendProofError::NotATimestamp(column.column_id())),
+        };
+        let offset = accessor.get_offset(table_ref);
+        let range = offset + previous_length..offset + length;
+        let plan = monotonic_plan::<CP::Commitment>(column, &range, boundary)?;
+        let batch_accessor = BatchDataAccessor {
+            accessor,
+            previous_length,
+        };
+        let result = VerifiableQueryResult::new(&plan, &batch_accessor, setup);
+        Ok(Self {
+            column,
+            range,
+            boundary,
+            result,
+        })
+    }
+
+    /// Returns the timestamp column that this proof is for.
+    pub fn column(&self) -> ColumnRef {
+        self.column
+    }
+
+    /// Returns the range of the rows of the appended batch.
+    pub fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    /// Verify that the rows of `appended` that are not in `previous` do not go back in time,
+    /// returning the timestamp of the last of them.
+    ///
+    /// `boundary` has to be the timestamp of the last row of `previous`, e.g. as returned by the
+    /// verification of the previous batch. It is only `None` if there is no earlier row to compare
+    /// against, in which case `previous` is typically `None` as well.
+    pub fn verify(
+        &self,
+        column: ColumnRef,
+        previous: Option<&TableCommitment<CP::Commitment>>,
+        appended: &TableCommitment<CP::Commitment>,
+        boundary: Option<i64>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<i64, MonotonicAppendProofError> {
+        let batch = batch_commitment(previous, appended)?;
+        if batch.num_rows() == 0 {
+            return Err(MonotonicAppendProofError::EmptyBatch);
+        }
+        if self.column != column || &self.range != batch.range() || self.boundary != boundary {
+            return Err(MonotonicAppendProofError::ProofMismatch);
+        }
+        let plan = monotonic_plan::<CP::Commitment>(column, &self.range, boundary)?;
+        let commitments = QueryCommitments::from_iter([(column.table_ref(), batch)]);
+        let table = self.result.verify(&plan, &commitments, setup)?.table;
+        let (rows, timestamps) = match (
+            table.inner_table().get(&alias(ROW_INDEX_ALIAS)),
+            table.inner_table().get(&alias(TIMESTAMP_ALIAS)),
+        ) {
+            (Some(OwnedColumn::BigInt(rows)), Some(OwnedColumn::TimestampTZ(_, _, timestamps))) => {
+                (rows, timestamps)
+            }
+            _ => return Err(MonotonicAppendProofError::MalformedResult),
+        };
+        let last_row = self.range.end - 1;
+        for (&row, &timestamp) in rows.iter().zip(timestamps) {
+            if boundary.is_some_and(|boundary| timestamp < boundary) {
+                return Err(MonotonicAppendProofError::OutOfOrder {
+                    row: row as usize,
+                    timestamp,
+                });
+            }
+        }
+        match (rows.as_slice(), timestamps.as_slice()) {
+            ([row], [timestamp]) if *row as usize == last_row => Ok(*timestamp),
+            _ => Err(MonotonicAppendProofError::MalformedResult),
+        }
+    }
+}
+
+/// Returns the commitment of the rows of `appended` that are not in `previous`.
+fn batch_commitment<C: Commitment>(
+    previous: Option<&TableCommitment<C>>,
+    appended: &TableCommitment<C>,
+) -> Result<TableCommitment<C>, TableCommitmentArithmeticError> {
+    let Some(previous) = previous else {
+        return Ok(appended.clone());
+    };
+    let batch = appended.clone().try_sub(previous.clone())?;
+    // `try_sub` also accepts a `previous` that ends with `appended` rather than starting with it.
+    if batch.range().start != previous.range().end {
+        return Err(TableCommitmentArithmeticError::NonContiguous);
+    }
+    Ok(batch)
+}
+
+fn alias(name: &str) -> Identifier {
+    name.parse()
+        .expect("the monotonic append aliases should be valid identifiers")
+}
+
+/// Build the filter plan whose result has the out of order rows and the last row of the batch.
+fn monotonic_plan<C: Commitment>(
+    column: ColumnRef,
+    range: &Range<usize>,
+    boundary: Option<i64>,
+) -> Result<ProofPlan<C>, MonotonicAppendProofError> {
+    let ColumnType::TimestampTZ(time_unit, time_zone) = column.column_type() else {
+        return Err(MonotonicAppendProofError::NotATimestamp(column.column_id()));
+    };
+    let table_ref = column.table_ref();
+    let is_last_row = ProvableExprPlan::try_new_equals(
+        ProvableExprPlan::new_row_index(table_ref),
+        ProvableExprPlan::new_literal(LiteralValue::BigInt(range.end as i64 - 1)),
+    )
+    .expect("row indexes are comparable to bigints");
+    let where_clause = match boundary {
+        Some(boundary) => {
+            let is_in_order = ProvableExprPlan::try_new_inequality(
+                ProvableExprPlan::new_column(column),
+                ProvableExprPlan::new_literal(LiteralValue::TimeStampTZ(
+                    *time_unit, *time_zone, boundary,
+                )),
+                false,
+            )
+            .expect("timestamps of the same column type are comparable");
+            ProvableExprPlan::try_new_or(
+                ProvableExprPlan::try_new_not(is_in_order).expect("comparisons are boolean"),
+                is_last_row,
+            )
+            .expect("both operands are boolean")
+        }
+        None => is_last_row,
+    };
+    Ok(ProofPlan::DenseFilter(DenseFilterExpr::new(
+        vec![
+            AliasedProvableExprPlan {
+                expr: ProvableExprPlan::new_row_index(table_ref),
+                alias: alias(ROW_INDEX_ALIAS),
+            },
+            AliasedProvableExprPlan {
+                expr: ProvableExprPlan::new_column(column),
+                alias: alias(TIMESTAMP_ALIAS),
+            },
+        ],
+        TableExpr { table_ref },
+        where_clause,
+    )))
+}
+
+/// A [`DataAccessor`] that only exposes the rows of each table after the first `previous_length`.
+struct BatchDataAccessor<'a, A> {
+    accessor: &'a A,
+    previous_length: usize,
+}
+
+impl<A: MetadataAccessor> MetadataAccessor for BatchDataAccessor<'_, A> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.accessor.get_length(table_ref) - self.previous_length
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.accessor.get_offset(table_ref) + self.previous_length
+    }
+}
+
+impl<S: Scalar, A: DataAccessor<S>> DataAccessor<S> for BatchDataAccessor<'_, A> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        let length = self.accessor.get_length(column.table_ref());
+        self.accessor
+            .get_column(column)
+            .slice(self.previous_length, length)
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<S>> {
+        let length = self.accessor.try_get_length(column.table_ref())?;
+        Ok(self
+            .accessor
+            .try_get_column(column)?
+            .slice(self.previous_length, length))
+    }
+}
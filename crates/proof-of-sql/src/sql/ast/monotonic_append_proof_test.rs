@@ -0,0 +1,221 @@
+use super::{MonotonicAppendProof, MonotonicAppendProofError};
+use crate::base::{
+    commitment::{InnerProductProof, TableCommitment},
+    database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTable, OwnedTableTestAccessor,
+        TableRef, TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    utility::ident,
+};
+
+fn table_ref() -> TableRef {
+    "sxt.t".parse().unwrap()
+}
+
+fn ts_column() -> ColumnRef {
+    ColumnRef::new(
+        table_ref(),
+        ident("ts"),
+        ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
+    )
+}
+
+fn events(timestamps: &[i64]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("id", 0..timestamps.len() as i64),
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            timestamps.to_vec(),
+        ),
+    ])
+}
+
+fn accessor(
+    timestamps: &[i64],
+    offset: usize,
+) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(table_ref(), events(timestamps), offset);
+    accessor
+}
+
+fn commitment(timestamps: &[i64], offset: usize) -> TableCommitment<RistrettoPoint> {
+    TableCommitment::from_owned_table_with_offset(&events(timestamps), offset, &())
+}
+
+#[test]
+fn we_can_prove_and_verify_a_chain_of_monotonic_appends() {
+    let timestamps = [10, 20, 20, 30, 45, 45];
+    let accessor = accessor(&timestamps, 0);
+    let mut boundary = None;
+    let mut previous: Option<TableCommitment<RistrettoPoint>> = None;
+    for end in [2, 5, 6] {
+        let start = previous.as_ref().map_or(0, TableCommitment::num_rows);
+        let appended = commitment(&timestamps[..end], 0);
+        let proof =
+            MonotonicAppendProof::<InnerProductProof>::new(ts_column(), start, &accessor, &())
+                .unwrap();
+        assert_eq!(proof.column(), ts_column());
+        assert_eq!(proof.range(), &(start..end));
+        let last = proof
+            .verify(ts_column(), previous.as_ref(), &appended, boundary, &())
+            .unwrap();
+        assert_eq!(last, timestamps[end - 1]);
+        boundary = Some(last);
+        previous = Some(appended);
+    }
+}
+
+#[test]
+fn we_can_prove_and_verify_a_monotonic_append_to_a_table_with_an_offset() {
+    let timestamps = [1, 5, 5, 8];
+    let accessor = accessor(&timestamps, 3);
+    let proof =
+        MonotonicAppendProof::<InnerProductProof>::new(ts_column(), 1, &accessor, &()).unwrap();
+    assert_eq!(proof.range(), &(4..7));
+    assert_eq!(
+        proof
+            .verify(
+                ts_column(),
+                Some(&commitment(&timestamps[..1], 3)),
+                &commitment(&timestamps, 3),
+                Some(1),
+                &()
+            )
+            .unwrap(),
+        8
+    );
+}
+
+#[test]
+fn we_can_verify_a_deserialized_monotonic_append_proof() {
+    let timestamps = [1, 2, 3];
+    let accessor = accessor(&timestamps, 0);
+    let proof =
+        MonotonicAppendProof::<InnerProductProof>::new(ts_column(), 2, &accessor, &()).unwrap();
+    let bytes = postcard::to_allocvec(&proof).unwrap();
+    let proof: MonotonicAppendProof<InnerProductProof> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        proof
+            .verify(
+                ts_column(),
+                Some(&commitment(&timestamps[..2], 0)),
+                &commitment(&timestamps, 0),
+                Some(2),
+                &()
+            )
+            .unwrap(),
+        3
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_batch_that_goes_back_in_time() {
+    let timestamps = [10, 20, 30, 15, 40];
+    let accessor = accessor(&timestamps, 0);
+    let proof =
+        MonotonicAppendProof::<InnerProductProof>::new(ts_column(), 3, &accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(
+            ts_column(),
+            Some(&commitment(&timestamps[..3], 0)),
+            &commitment(&timestamps, 0),
+            Some(30),
+            &()
+        ),
+        Err(MonotonicAppendProofError::OutOfOrder {
+            row: 3,
+            timestamp: 15
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_batch_whose_last_row_goes_back_in_time() {
+    let timestamps = [10, 20, 5];
+    let accessor = accessor(&timestamps, 0);
+    let proof =
+        MonotonicAppendProof::<InnerProductProof>::new(ts_column(), 2, &accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(
+            ts_column(),
+            Some(&commitment(&timestamps[..2], 0)),
+            &commitment(&timestamps, 0),
+            Some(20),
+            &()
+        ),
+        Err(MonotonicAppendProofError::OutOfOrder {
+            row: 2,
+            timestamp: 5
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_monotonic_append_proof_for_another_boundary_or_batch() {
+    let timestamps = [10, 20, 30, 40];
+    let accessor = accessor(&timestamps, 0);
+    let proof =
+        MonotonicAppendProof::<InnerProductProof>::new(ts_column(), 2, &accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(
+            ts_column(),
+            Some(&commitment(&timestamps[..2], 0)),
+            &commitment(&timestamps, 0),
+            Some(10),
+            &()
+        ),
+        Err(MonotonicAppendProofError::ProofMismatch)
+    ));
+    assert!(matches!(
+        proof.verify(
+            ts_column(),
+            Some(&commitment(&timestamps[..1], 0)),
+            &commitment(&timestamps, 0),
+            Some(20),
+            &()
+        ),
+        Err(MonotonicAppendProofError::ProofMismatch)
+    ));
+    assert!(matches!(
+        proof.verify(
+            ts_column(),
+            Some(&commitment(&timestamps, 0)),
+            &commitment(&timestamps, 0),
+            Some(20),
+            &()
+        ),
+        Err(MonotonicAppendProofError::EmptyBatch)
+    ));
+    assert!(matches!(
+        proof.verify(
+            ts_column(),
+            Some(&commitment(&timestamps[..2], 1)),
+            &commitment(&timestamps, 0),
+            Some(20),
+            &()
+        ),
+        Err(MonotonicAppendProofError::CommitmentArithmetic(..))
+    ));
+}
+
+#[test]
+fn we_cannot_prove_a_monotonic_append_of_no_rows_or_of_a_non_timestamp_column() {
+    let accessor = accessor(&[10, 20], 0);
+    assert!(matches!(
+        MonotonicAppendProof::<InnerProductProof>::new(ts_column(), 2, &accessor, &()),
+        Err(MonotonicAppendProofError::EmptyBatch)
+    ));
+    let id = ColumnRef::new(table_ref(), ident("id"), ColumnType::BigInt);
+    assert!(matches!(
+        MonotonicAppendProof::<InnerProductProof>::new(id, 1, &accessor, &()),
+        Err(MonotonicAppendProofError::NotATimestamp(..))
+    ));
+}
@@ -1,15 +1,15 @@
-use super::{ProvableExpr, ProvableExprPlan};
+use super::{OverflowPolicy, ProvableExpr, ProvableExprPlan};
 use crate::{
     base::{
         commitment::Commitment,
         database::{
-            try_multiply_column_types, Column, ColumnRef, ColumnType, CommitmentAccessor,
-            DataAccessor,
+            try_multiply_column_types, Column, ColumnOperationResult, ColumnRef, ColumnType,
+            CommitmentAccessor, DataAccessor,
         },
         proof::ProofError,
     },
     sql::{
-        ast::multiply_columns,
+        ast::{audit_column_type_range, check_overflow, multiply_columns},
         proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
     },
 };
@@ -23,12 +23,29 @@ use serde::{Deserialize, Serialize};
 pub struct MultiplyExpr<C: Commitment> {
     lhs: Box<ProvableExprPlan<C>>,
     rhs: Box<ProvableExprPlan<C>>,
+    overflow_policy: OverflowPolicy,
 }
 
 impl<C: Commitment> MultiplyExpr<C> {
     /// Create numerical `*` expression
-    pub fn new(lhs: Box<ProvableExprPlan<C>>, rhs: Box<ProvableExprPlan<C>>) -> Self {
-        Self { lhs, rhs }
+    pub fn new(
+        lhs: Box<ProvableExprPlan<C>>,
+        rhs: Box<ProvableExprPlan<C>>,
+        overflow_policy: OverflowPolicy,
+    ) -> Self {
+        Self {
+            lhs,
+            rhs,
+            overflow_policy,
+        }
+    }
+
+    /// Returns the type of the product, or an error if the policy widens it beyond a `DECIMAL75`.
+    pub(crate) fn try_data_type(&self) -> ColumnOperationResult<ColumnType> {
+        try_multiply_column_types(
+            self.overflow_policy.operand_type(self.lhs.data_type()),
+            self.overflow_policy.operand_type(self.rhs.data_type()),
+        )
     }
 }
 
@@ -43,7 +60,7 @@ impl<C: Commitment> ProvableExpr<C> for MultiplyExpr<C> {
     }
 
     fn data_type(&self) -> ColumnType {
-        try_multiply_column_types(self.lhs.data_type(), self.rhs.data_type())
+        self.try_data_type()
             .expect("Failed to multiply column types")
     }
 
@@ -61,10 +78,13 @@ impl<C: Commitment> ProvableExpr<C> for MultiplyExpr<C> {
         Column::Scalar(scalars)
     }
 
-    #[tracing::instrument(
-        name = "proofs.sql.ast.multiply_expr.prover_evaluate",
-        level = "info",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "proofs.sql.ast.multiply_expr.prover_evaluate",
+            level = "info",
+            skip_all
+        )
     )]
     fn prover_evaluate<'a>(
         &self,
@@ -77,6 +97,14 @@ impl<C: Commitment> ProvableExpr<C> for MultiplyExpr<C> {
 
         // lhs_times_rhs
         let lhs_times_rhs: &'a [C::Scalar] = multiply_columns(&lhs_column, &rhs_column, alloc);
+        audit_column_type_range("multiply_expr", self.data_type(), lhs_times_rhs);
+        check_overflow(
+            builder,
+            self.overflow_policy,
+            "multiply_expr",
+            self.data_type(),
+            lhs_times_rhs,
+        );
         builder.produce_intermediate_mle(lhs_times_rhs);
 
         // subpolynomial: lhs_times_rhs - lhs * rhs
@@ -2,12 +2,13 @@ use crate::{
     base::{
         commitment::InnerProductProof,
         database::{owned_table_utility::*, Column, OwnedTableTestAccessor},
+        proof::{ProverDeadline, ProverError},
         scalar::Curve25519Scalar,
     },
     sql::{
-        ast::{test_utility::*, ProofPlan, ProvableExpr, ProvableExprPlan},
+        ast::{test_utility::*, OverflowPolicy, ProofPlan, ProvableExpr, ProvableExprPlan},
         parse::ConversionError,
-        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof::{exercise_verification, EvaluationContext, QueryError, VerifiableQueryResult},
     },
 };
 use bumpalo::Bump;
@@ -231,6 +232,103 @@ fn where_clause_can_wrap_around() {
     assert_eq!(res, expected_res);
 }
 
+// select * from sxt.t where a * b * c * d * e = res
+#[test]
+fn the_prover_refuses_to_let_the_where_clause_wrap_around_with_the_error_overflow_policy() {
+    let data = owned_table([
+        bigint("a", [884_i64, 2357878470324616199]),
+        bigint("b", [884_i64, 31194601778911687]),
+        bigint("c", [884_i64, 500213946116239]),
+        bigint("d", [884_i64, 211980999383887]),
+        bigint("e", [884_i64, 927908842441]),
+        bigint("res", [539835356263424_i64, -20]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let multiply = |lhs, rhs| {
+        ProvableExprPlan::try_new_multiply_with_overflow_policy(lhs, rhs, OverflowPolicy::Error)
+            .unwrap()
+    };
+    let ast: ProofPlan<RistrettoPoint> = dense_filter(
+        cols_expr_plan(t, &["a", "b", "c", "d", "e", "res"], &accessor),
+        tab(t),
+        equal(
+            multiply(
+                multiply(
+                    multiply(
+                        multiply(column(t, "a", &accessor), column(t, "b", &accessor)),
+                        column(t, "c", &accessor),
+                    ),
+                    column(t, "d", &accessor),
+                ),
+                column(t, "e", &accessor),
+            ),
+            column(t, "res", &accessor),
+        ),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_deadline(
+        &ast,
+        &accessor,
+        &(),
+        EvaluationContext::default(),
+        &ProverDeadline::default(),
+    );
+    assert_eq!(
+        res.err(),
+        Some(ProverError::Overflow {
+            gadget: "multiply_expr",
+            row: 1
+        })
+    );
+}
+
+// select a * b as c from sxt.t
+#[test]
+fn products_are_widened_to_decimals_with_the_widening_overflow_policy() {
+    let data = owned_table([
+        bigint("a", [i64::MAX, i64::MIN, 3]),
+        bigint("b", [i64::MAX, i64::MAX, -4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast: ProofPlan<RistrettoPoint> = dense_filter(
+        vec![aliased_plan(
+            ProvableExprPlan::try_new_multiply_with_overflow_policy(
+                column(t, "a", &accessor),
+                column(t, "b", &accessor),
+                OverflowPolicy::WidenToDecimal,
+            )
+            .unwrap(),
+            "c",
+        )],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let max = i128::from(i64::MAX);
+    let min = i128::from(i64::MIN);
+    let expected_res = owned_table([decimal75("c", 39, 0, [max * max, min * max, -12])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_widen_products_beyond_the_precision_of_decimals() {
+    let data = owned_table([int128("a", [1_i128]), int128("b", [2_i128])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    assert!(matches!(
+        ProvableExprPlan::try_new_multiply_with_overflow_policy(
+            column(t, "a", &accessor),
+            column::<RistrettoPoint>(t, "b", &accessor),
+            OverflowPolicy::WidenToDecimal,
+        ),
+        Err(ConversionError::ColumnOperationError(..))
+    ));
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 /// Provable logical NOT expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NotExpr<C: Commitment> {
-    expr: Box<ProvableExprPlan<C>>,
+    pub(super) expr: Box<ProvableExprPlan<C>>,
 }
 
 impl<C: Commitment> NotExpr<C> {
@@ -33,7 +33,10 @@ impl<C: Commitment> ProvableExpr<C> for NotExpr<C> {
         ColumnType::Boolean
     }
 
-    #[tracing::instrument(name = "NotExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "NotExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -46,7 +49,10 @@ impl<C: Commitment> ProvableExpr<C> for NotExpr<C> {
         Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| !expr[i]))
     }
 
-    #[tracing::instrument(name = "NotExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "NotExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
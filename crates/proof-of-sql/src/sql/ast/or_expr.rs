@@ -15,8 +15,8 @@ use serde::{Deserialize, Serialize};
 /// Provable logical OR expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct OrExpr<C: Commitment> {
-    lhs: Box<ProvableExprPlan<C>>,
-    rhs: Box<ProvableExprPlan<C>>,
+    pub(super) lhs: Box<ProvableExprPlan<C>>,
+    pub(super) rhs: Box<ProvableExprPlan<C>>,
 }
 
 impl<C: Commitment> OrExpr<C> {
@@ -38,7 +38,10 @@ impl<C: Commitment> ProvableExpr<C> for OrExpr<C> {
         ColumnType::Boolean
     }
 
-    #[tracing::instrument(name = "OrExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "OrExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         table_length: usize,
@@ -54,7 +57,10 @@ impl<C: Commitment> ProvableExpr<C> for OrExpr<C> {
         Column::Boolean(result_evaluate_or(table_length, alloc, lhs, rhs))
     }
 
-    #[tracing::instrument(name = "OrExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "OrExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut ProofBuilder<'a, C::Scalar>,
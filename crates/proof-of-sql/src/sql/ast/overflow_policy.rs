@@ -0,0 +1,47 @@
+use crate::base::{database::ColumnType, math::decimal::Precision};
+use serde::{Deserialize, Serialize};
+
+/// How the arithmetic gadgets (`+`, `-` and `*`) handle results that do not fit in the integer
+/// type of the expression.
+///
+/// Gadgets compute in the scalar field, so a result that does not fit in its type is still exact
+/// within the proof. The policy decides what the query reports for such a result. It is part of
+/// the plan, so the prover and the verifier have to plan a query with the same policy.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// The proof is built, and the verifier rejects a result column with a value that does not
+    /// fit in its type with [`QueryError::Overflow`](crate::sql::proof::QueryError::Overflow).
+    ///
+    /// Values that only appear in a `WHERE` clause are compared exactly.
+    #[default]
+    Report,
+    /// The prover refuses to build a proof with
+    /// [`ProverError::Overflow`](crate::base::proof::ProverError::Overflow) as soon as a gadget
+    /// computes a value that does not fit in its type.
+    ///
+    /// Gadgets are evaluated over every row of the table, so this includes rows that the `WHERE`
+    /// clause filters out.
+    Error,
+    /// Integer operands are treated as decimals with as many digits as their type, so that the
+    /// result is a `DECIMAL75` with enough precision to never overflow.
+    ///
+    /// Planning fails if the result needs more than 75 digits, e.g. for the product of two
+    /// `INT128`s.
+    WidenToDecimal,
+}
+
+impl OverflowPolicy {
+    /// Returns the type that an arithmetic gadget with this policy treats an operand of type
+    /// `column_type` as.
+    pub(crate) fn operand_type(&self, column_type: ColumnType) -> ColumnType {
+        match (self, column_type.precision_value()) {
+            (Self::WidenToDecimal, Some(precision)) if column_type.is_integer() => {
+                ColumnType::Decimal75(
+                    Precision::new(precision).expect("integer types have a valid precision"),
+                    0,
+                )
+            }
+            _ => column_type,
+        }
+    }
+}
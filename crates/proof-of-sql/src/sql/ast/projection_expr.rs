@@ -92,7 +92,10 @@ impl<C: Commitment> ProofExpr<C> for ProjectionExpr<C> {
 }
 
 impl<C: Commitment> ProverEvaluate<C::Scalar> for ProjectionExpr<C> {
-    #[tracing::instrument(name = "ProjectionExpr::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProjectionExpr::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         builder: &mut ResultBuilder<'a>,
@@ -110,7 +113,10 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProjectionExpr<C> {
         }
     }
 
-    #[tracing::instrument(name = "ProjectionExpr::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProjectionExpr::prover_evaluate", level = "debug", skip_all)
+    )]
     #[allow(unused_variables)]
     fn prover_evaluate<'a>(
         &self,
@@ -1,9 +1,15 @@
 use super::{DenseFilterExpr, FilterExpr, GroupByExpr, ProjectionExpr};
 use crate::{
-    base::commitment::Commitment,
+    base::{
+        commitment::{
+            Commitment, PartitionPruningError, PartitionedTableCommitment, TableCommitment,
+        },
+        database::{ColumnRef, TableRef},
+    },
     sql::proof::{ProofExpr, ProverEvaluate},
 };
 use serde::{Deserialize, Serialize};
+use std::ops::{Range, RangeInclusive};
 
 /// The query plan for proving a query
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -35,6 +41,72 @@ pub enum ProofPlan<C: Commitment> {
     DenseFilter(DenseFilterExpr<C>),
 }
 
+impl<C: Commitment> ProofPlan<C> {
+    /// Returns the table that the query reads from.
+    pub fn table_ref(&self) -> TableRef {
+        match self {
+            ProofPlan::Projection(expr) => expr.table.table_ref,
+            ProofPlan::Filter(expr) => expr.table.table_ref,
+            ProofPlan::GroupBy(expr) => expr.table.table_ref,
+            ProofPlan::DenseFilter(expr) => expr.table.table_ref,
+        }
+    }
+
+    /// Returns a range that contains the value of `column` in every row that the query uses, or
+    /// `None` if the query uses no row.
+    ///
+    /// Rows outside of this range cannot affect the result, so they do not need to be proven over.
+    /// The range is only narrowed by comparisons of `column` with integer or timestamp literals in
+    /// the `WHERE` clause.
+    pub fn column_value_range(&self, column: ColumnRef) -> Option<RangeInclusive<i128>> {
+        match self {
+            ProofPlan::Projection(_) => Some(i128::MIN..=i128::MAX),
+            ProofPlan::Filter(expr) => expr.where_clause.column_value_range(column),
+            ProofPlan::GroupBy(expr) => expr.where_clause.column_value_range(column),
+            ProofPlan::DenseFilter(expr) => expr.where_clause.column_value_range(column),
+        }
+    }
+
+    fn partition_key_range(
+        &self,
+        partitioned_table: &PartitionedTableCommitment<C>,
+    ) -> Option<RangeInclusive<i128>> {
+        self.column_value_range(ColumnRef::new(
+            self.table_ref(),
+            partitioned_table.partition_key(),
+            partitioned_table.partition_key_type(),
+        ))
+    }
+
+    /// Returns the partitions of the queried table that may contain rows that the query uses.
+    ///
+    /// The prover only needs to prove the query over the rows of these partitions, which is done by
+    /// giving the prover an accessor that only holds these rows, at their original offset.
+    pub fn prune_partitions(
+        &self,
+        partitioned_table: &PartitionedTableCommitment<C>,
+    ) -> Range<usize> {
+        partitioned_table.prune(&self.partition_key_range(partitioned_table))
+    }
+
+    /// Checks that the partitions that the prover dropped cannot contain rows that the query uses,
+    /// and returns the commitment to the partitions that the query was proven over.
+    ///
+    /// This trusts the bounds of the partition key in the metadata of the partitions, in the same
+    /// way that the commitments themselves are trusted.
+    pub fn verify_partition_pruning(
+        &self,
+        partitioned_table: &PartitionedTableCommitment<C>,
+        kept_partitions: Range<usize>,
+    ) -> Result<TableCommitment<C>, PartitionPruningError> {
+        partitioned_table.verify_pruning(
+            &self.partition_key_range(partitioned_table),
+            kept_partitions.clone(),
+        )?;
+        Ok(partitioned_table.table_commitment(kept_partitions))
+    }
+}
+
 impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
     fn count(
         &self,
@@ -67,7 +139,10 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
         }
     }
 
-    #[tracing::instrument(name = "ProofPlan::verifier_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProofPlan::verifier_evaluate", level = "debug", skip_all)
+    )]
     fn verifier_evaluate(
         &self,
         builder: &mut crate::sql::proof::VerificationBuilder<C>,
@@ -99,10 +174,29 @@ impl<C: Commitment> ProofExpr<C> for ProofPlan<C> {
             ProofPlan::DenseFilter(expr) => expr.get_column_references(),
         }
     }
+
+    fn get_selected_row_count(
+        &self,
+        result: &crate::base::database::OwnedTable<C::Scalar>,
+    ) -> usize {
+        match self {
+            ProofPlan::Projection(expr) => expr.get_selected_row_count(result),
+            ProofPlan::Filter(expr) => expr.get_selected_row_count(result),
+            ProofPlan::GroupBy(expr) => expr.get_selected_row_count(result),
+            ProofPlan::DenseFilter(expr) => expr.get_selected_row_count(result),
+        }
+    }
+    fn requires_result_table(&self) -> bool {
+        // Only the verifier of a `GROUP BY` reads its result, e.g. to count the selected rows.
+        matches!(self, ProofPlan::GroupBy(_))
+    }
 }
 
 impl<C: Commitment> ProverEvaluate<C::Scalar> for ProofPlan<C> {
-    #[tracing::instrument(name = "ProofPlan::result_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProofPlan::result_evaluate", level = "debug", skip_all)
+    )]
     fn result_evaluate<'a>(
         &self,
         builder: &mut crate::sql::proof::ResultBuilder<'a>,
@@ -117,18 +211,28 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProofPlan<C> {
         }
     }
 
-    #[tracing::instrument(name = "ProofPlan::prover_evaluate", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProofPlan::prover_evaluate", level = "debug", skip_all)
+    )]
     fn prover_evaluate<'a>(
         &self,
         builder: &mut crate::sql::proof::ProofBuilder<'a, C::Scalar>,
         alloc: &'a bumpalo::Bump,
         accessor: &'a dyn crate::base::database::DataAccessor<C::Scalar>,
     ) {
+        builder.enter_plan_node(match self {
+            ProofPlan::Projection(_) => "Projection",
+            ProofPlan::Filter(_) => "Filter",
+            ProofPlan::GroupBy(_) => "GroupBy",
+            ProofPlan::DenseFilter(_) => "DenseFilter",
+        });
         match self {
             ProofPlan::Projection(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::Filter(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::GroupBy(expr) => expr.prover_evaluate(builder, alloc, accessor),
             ProofPlan::DenseFilter(expr) => expr.prover_evaluate(builder, alloc, accessor),
         }
+        builder.exit_plan_node();
     }
 }
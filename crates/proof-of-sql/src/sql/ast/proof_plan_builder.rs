@@ -0,0 +1,186 @@
+use super::{
+    AliasedProvableExprPlan, DenseFilterExpr, GroupByExpr, ProofPlan, ProvableExpr,
+    ProvableExprPlan, TableExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{ColumnRef, ColumnType, LiteralValue, TableRef},
+    },
+    sql::parse::{ConversionError, ConversionResult},
+};
+use indexmap::IndexSet;
+use proof_of_sql_parser::{intermediate_ast::AggregationOperator, Identifier};
+
+/// A builder of [`ProofPlan`]s out of provable expressions, for callers that generate queries
+/// themselves rather than writing them as SQL.
+///
+/// The expressions are created with the constructors of [`ProvableExprPlan`], which check their
+/// types just like the SQL planner does. The resulting plan can be proven and verified like the
+/// plan of any [`crate::sql::parse::QueryExpr`]. For example,
+/// ```ignore
+///     SELECT a, b FROM sxt.t WHERE a = 2
+/// ```
+/// can be built as
+/// ```ignore
+/// let (a, b) = (ColumnRef::new(t, ident("a"), BigInt), ColumnRef::new(t, ident("b"), VarChar));
+/// let plan = ProofPlanBuilder::new(t)
+///     .add_result_column(a)?
+///     .add_result_column(b)?
+///     .add_where_expr(ProvableExprPlan::try_new_equals(
+///         ProvableExprPlan::new_column(a),
+///         ProvableExprPlan::new_literal(LiteralValue::BigInt(2)),
+///     )?)?
+///     .build_filter()?;
+/// ```
+#[derive(Debug)]
+pub struct ProofPlanBuilder<C: Commitment> {
+    table_ref: TableRef,
+    result_exprs: Vec<AliasedProvableExprPlan<C>>,
+    sum_exprs: Vec<AliasedProvableExprPlan<C>>,
+    where_clause: Option<ProvableExprPlan<C>>,
+    aliases: IndexSet<Identifier>,
+}
+
+impl<C: Commitment> ProofPlanBuilder<C> {
+    /// Start building a plan that reads from `table_ref`.
+    pub fn new(table_ref: TableRef) -> Self {
+        Self {
+            table_ref,
+            result_exprs: Vec::new(),
+            sum_exprs: Vec::new(),
+            where_clause: None,
+            aliases: IndexSet::new(),
+        }
+    }
+
+    /// Add a result column computed by `expr`.
+    ///
+    /// In a group by plan, these are the expressions that the rows are grouped by.
+    pub fn add_result_expr(
+        mut self,
+        expr: ProvableExprPlan<C>,
+        alias: Identifier,
+    ) -> ConversionResult<Self> {
+        self.check_columns(&expr)?;
+        self.check_alias(alias)?;
+        self.result_exprs
+            .push(AliasedProvableExprPlan { expr, alias });
+        Ok(self)
+    }
+
+    /// Add a column of the table to the results, under its own name.
+    pub fn add_result_column(self, column_ref: ColumnRef) -> ConversionResult<Self> {
+        self.add_result_expr(
+            ProvableExprPlan::new_column(column_ref),
+            column_ref.column_id(),
+        )
+    }
+
+    /// Add a result column with the sum of `expr` over each group. This requires a group by plan.
+    pub fn add_sum_expr(
+        mut self,
+        expr: ProvableExprPlan<C>,
+        alias: Identifier,
+    ) -> ConversionResult<Self> {
+        self.check_columns(&expr)?;
+        if !expr.data_type().is_numeric() {
+            return Err(ConversionError::InvalidExpression(format!(
+                "cannot sum an expression of type {}",
+                expr.data_type()
+            )));
+        }
+        self.check_alias(alias)?;
+        self.sum_exprs.push(AliasedProvableExprPlan {
+            expr: ProvableExprPlan::new_aggregate(AggregationOperator::Sum, expr),
+            alias,
+        });
+        Ok(self)
+    }
+
+    /// Only keep the rows for which the boolean `expr` holds.
+    ///
+    /// Calling this more than once keeps the rows for which all of the expressions hold.
+    pub fn add_where_expr(mut self, expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        self.check_columns(&expr)?;
+        if expr.data_type() != ColumnType::Boolean {
+            return Err(ConversionError::NonbooleanWhereClause(expr.data_type()));
+        }
+        self.where_clause = Some(match self.where_clause.take() {
+            Some(where_clause) => ProvableExprPlan::try_new_and(where_clause, expr)?,
+            None => expr,
+        });
+        Ok(self)
+    }
+
+    /// Build a plan of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+    /// ```
+    pub fn build_filter(self) -> ConversionResult<ProofPlan<C>> {
+        if !self.sum_exprs.is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "sums can only be part of a group by plan".to_owned(),
+            ));
+        }
+        if self.result_exprs.is_empty() {
+            return Err(ConversionError::InvalidExpression(
+                "a plan must have at least one result column".to_owned(),
+            ));
+        }
+        Ok(ProofPlan::DenseFilter(DenseFilterExpr::new(
+            self.result_exprs,
+            TableExpr {
+                table_ref: self.table_ref,
+            },
+            self.where_clause
+                .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true))),
+        )))
+    }
+
+    /// Build a plan of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprM>,
+    ///         SUM(<sum_expr1>), ..., SUM(<sum_exprN>), COUNT(*) AS <count_alias>
+    ///     FROM <table> WHERE <where_clause>
+    ///     GROUP BY <result_expr1>, ..., <result_exprM>
+    /// ```
+    pub fn build_group_by(mut self, count_alias: Identifier) -> ConversionResult<ProofPlan<C>> {
+        self.check_alias(count_alias)?;
+        Ok(ProofPlan::GroupBy(GroupByExpr::new(
+            self.result_exprs,
+            self.sum_exprs,
+            count_alias,
+            TableExpr {
+                table_ref: self.table_ref,
+            },
+            self.where_clause
+                .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true))),
+        )))
+    }
+
+    /// Check that `expr` only refers to columns of the table of the plan.
+    fn check_columns(&self, expr: &ProvableExprPlan<C>) -> ConversionResult<()> {
+        let mut columns = IndexSet::new();
+        expr.get_column_references(&mut columns);
+        match columns
+            .into_iter()
+            .find(|column| column.table_ref() != self.table_ref)
+        {
+            Some(column) => Err(ConversionError::MissingColumn(
+                Box::new(column.column_id()),
+                Box::new(self.table_ref.resource_id()),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that no other result column has the same alias.
+    fn check_alias(&mut self, alias: Identifier) -> ConversionResult<()> {
+        if self.aliases.insert(alias) {
+            Ok(())
+        } else {
+            Err(ConversionError::DuplicateResultAlias(alias.to_string()))
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use super::{test_utility::*, ProofPlan, ProofPlanBuilder};
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::{ConversionError, QueryExpr},
+        proof::VerifiableQueryResult,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 2, 3]),
+            bigint("b", [10, 20, 30, 40]),
+            varchar("name", ["x", "y", "z", "w"]),
+        ]),
+        0,
+    );
+    accessor.add_table("sxt.u".parse().unwrap(), owned_table([bigint("a", [1])]), 0);
+    accessor
+}
+
+fn sql_query(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> QueryExpr<RistrettoPoint> {
+    QueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor).unwrap()
+}
+
+fn prove_and_verify(
+    plan: &ProofPlan<RistrettoPoint>,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> OwnedTable<Curve25519Scalar> {
+    VerifiableQueryResult::<InnerProductProof>::new(plan, accessor, &())
+        .verify(plan, accessor, &())
+        .unwrap()
+        .table
+}
+
+#[test]
+fn we_can_build_the_same_filter_plan_as_the_sql_planner() {
+    let accessor = accessor();
+    let t = "sxt.t".parse().unwrap();
+    let plan = ProofPlanBuilder::new(t)
+        .add_result_column(col_ref(t, "a", &accessor))
+        .unwrap()
+        .add_result_column(col_ref(t, "name", &accessor))
+        .unwrap()
+        .add_where_expr(equal(column(t, "a", &accessor), const_bigint(2)))
+        .unwrap()
+        .build_filter()
+        .unwrap();
+    let query = sql_query("SELECT a, name FROM t WHERE a = 2", &accessor);
+    assert_eq!(&plan, query.proof_expr());
+    assert_eq!(
+        prove_and_verify(&plan, &accessor),
+        owned_table([bigint("a", [2, 2]), varchar("name", ["y", "z"])])
+    );
+}
+
+#[test]
+fn we_can_build_the_same_group_by_plan_as_the_sql_planner() {
+    let accessor = accessor();
+    let t = "sxt.t".parse().unwrap();
+    let plan = ProofPlanBuilder::new(t)
+        .add_result_column(col_ref(t, "a", &accessor))
+        .unwrap()
+        .add_sum_expr(column(t, "b", &accessor), ident("s"))
+        .unwrap()
+        .build_group_by(ident("n"))
+        .unwrap();
+    let query = sql_query(
+        "SELECT a, sum(b) as s, count(*) as n FROM t GROUP BY a",
+        &accessor,
+    );
+    assert_eq!(&plan, query.proof_expr());
+    assert_eq!(
+        prove_and_verify(&plan, &accessor),
+        owned_table([
+            bigint("a", [1, 2, 3]),
+            bigint("s", [10, 50, 40]),
+            bigint("n", [1, 2, 1]),
+        ])
+    );
+}
+
+#[test]
+fn we_can_build_a_plan_whose_where_clause_is_the_conjunction_of_several_expressions() {
+    let accessor = accessor();
+    let t = "sxt.t".parse().unwrap();
+    let plan = ProofPlanBuilder::new(t)
+        .add_result_expr(
+            add(column(t, "a", &accessor), column(t, "b", &accessor)),
+            ident("total"),
+        )
+        .unwrap()
+        .add_where_expr(gte(column(t, "b", &accessor), const_bigint(20)))
+        .unwrap()
+        .add_where_expr(lte(column(t, "a", &accessor), const_bigint(2)))
+        .unwrap()
+        .build_filter()
+        .unwrap();
+    let query = sql_query(
+        "SELECT a + b as total FROM t WHERE b >= 20 and a <= 2",
+        &accessor,
+    );
+    assert_eq!(&plan, query.proof_expr());
+    assert_eq!(
+        prove_and_verify(&plan, &accessor),
+        owned_table([bigint("total", [22, 32])])
+    );
+}
+
+#[test]
+fn we_cannot_build_invalid_plans() {
+    let accessor = accessor();
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let builder = || ProofPlanBuilder::<RistrettoPoint>::new(t);
+    assert!(matches!(
+        builder().add_result_column(col_ref(u, "a", &accessor)),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        builder().add_where_expr(equal(column(u, "a", &accessor), const_bigint(1))),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        builder()
+            .add_result_column(col_ref(t, "a", &accessor))
+            .unwrap()
+            .add_result_expr(column(t, "b", &accessor), ident("a")),
+        Err(ConversionError::DuplicateResultAlias(_))
+    ));
+    assert!(matches!(
+        builder()
+            .add_result_column(col_ref(t, "a", &accessor))
+            .unwrap()
+            .build_group_by(ident("a")),
+        Err(ConversionError::DuplicateResultAlias(_))
+    ));
+    assert!(matches!(
+        builder().add_where_expr(column(t, "a", &accessor)),
+        Err(ConversionError::NonbooleanWhereClause(_))
+    ));
+    assert!(matches!(
+        builder().add_sum_expr(column(t, "name", &accessor), ident("s")),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        builder()
+            .add_sum_expr(column(t, "b", &accessor), ident("s"))
+            .unwrap()
+            .build_filter(),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        builder().build_filter(),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+}
@@ -0,0 +1,99 @@
+use super::ProofPlan;
+use crate::base::commitment::Commitment;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+/// The version of the encodings of a [`ProofPlan`].
+///
+/// It is bumped whenever a change to the plans stops older encodings from decoding to the same
+/// plan, so that a verifier rejects a plan from an incompatible planner instead of misreading it.
+pub const PROOF_PLAN_ENCODING_VERSION: u8 = 2;
+
+/// Errors that can occur when decoding a [`ProofPlan`] or comparing it to the plan of a query.
+#[derive(Error, Debug)]
+pub enum PlanEncodingError {
+    /// The encoding has no version.
+    #[error("the encoding of the plan is empty")]
+    MissingVersion,
+    /// The encoding is of a different version than [`PROOF_PLAN_ENCODING_VERSION`].
+    #[error("the encoding of the plan has the unsupported version {0}")]
+    UnsupportedVersion(u8),
+    /// The binary encoding could not be decoded.
+    #[error("the binary encoding of the plan could not be decoded: {0}")]
+    BinaryDecodingError(#[from] postcard::Error),
+    /// The JSON encoding could not be decoded.
+    #[error("the JSON encoding of the plan could not be decoded: {0}")]
+    JsonDecodingError(#[from] serde_json::Error),
+    /// The plan differs from the plan of the query.
+    #[error("the plan differs from the plan of the query")]
+    PlanMismatch,
+}
+
+/// The JSON encoding of a plan, which records the version of the encoding next to the plan.
+#[derive(Serialize, Deserialize)]
+struct JsonPlan<P> {
+    version: u8,
+    plan: P,
+}
+
+/// The version of a JSON encoding of a plan, which is read before the plan itself.
+#[derive(Deserialize)]
+struct JsonVersion {
+    version: u8,
+}
+
+/// Encodings of plans, so that a query can be planned in a different process than the one that
+/// proves or verifies it.
+///
+/// The encodings are canonical, i.e. equal plans always have equal encodings. A verifier should
+/// not trust a plan that it receives, but rather plan the query itself and check that the plans
+/// match with [`ProofPlan::check_matches`].
+impl<C: Commitment + Serialize + DeserializeOwned> ProofPlan<C> {
+    /// Encode the plan as the version of the encoding followed by the plan in postcard format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let bytes = vec![PROOF_PLAN_ENCODING_VERSION];
+        postcard::to_extend(self, bytes).expect("plans are always serializable")
+    }
+
+    /// Decode a plan that was encoded with [`ProofPlan::to_bytes`].
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, PlanEncodingError> {
+        let (&version, plan) = bytes
+            .split_first()
+            .ok_or(PlanEncodingError::MissingVersion)?;
+        check_version(version)?;
+        Ok(postcard::from_bytes(plan)?)
+    }
+
+    /// Encode the plan as a JSON object with the version of the encoding and the plan.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&JsonPlan {
+            version: PROOF_PLAN_ENCODING_VERSION,
+            plan: self,
+        })
+        .expect("plans are always serializable")
+    }
+
+    /// Decode a plan that was encoded with [`ProofPlan::to_json`].
+    pub fn try_from_json(json: &str) -> Result<Self, PlanEncodingError> {
+        let JsonVersion { version } = serde_json::from_str(json)?;
+        check_version(version)?;
+        let JsonPlan { plan, .. } = serde_json::from_str(json)?;
+        Ok(plan)
+    }
+
+    /// Check that the plan is the same as `expected`, e.g. the plan that the verifier made for the
+    /// query itself.
+    pub fn check_matches(&self, expected: &Self) -> Result<(), PlanEncodingError> {
+        if self != expected {
+            return Err(PlanEncodingError::PlanMismatch);
+        }
+        Ok(())
+    }
+}
+
+fn check_version(version: u8) -> Result<(), PlanEncodingError> {
+    if version != PROOF_PLAN_ENCODING_VERSION {
+        return Err(PlanEncodingError::UnsupportedVersion(version));
+    }
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use super::{PlanEncodingError, ProofPlan, PROOF_PLAN_ENCODING_VERSION};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::{parse::QueryExpr, proof::VerifiableQueryResult},
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 2, 3]),
+            decimal75("d", 12, 2, [100, 250, -3, 0]),
+            varchar("name", ["x", "y", "z", "w"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn plan(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> QueryExpr<RistrettoPoint> {
+    QueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor).unwrap()
+}
+
+const QUERIES: [&str; 4] = [
+    "SELECT a, name FROM t",
+    "SELECT name, a * 2 AS b FROM t WHERE d >= 1.5 AND name <> 'x'",
+    "SELECT a, sum(a) AS s, count(*) AS n FROM t WHERE a > 1 GROUP BY a",
+    "SELECT * FROM t WHERE NOT (a = 2 OR name = 'w')",
+];
+
+#[test]
+fn we_can_encode_and_decode_plans_in_binary_and_json() {
+    let accessor = accessor();
+    for sql in QUERIES {
+        let query = plan(sql, &accessor);
+        let bytes = query.proof_expr().to_bytes();
+        assert_eq!(bytes[0], PROOF_PLAN_ENCODING_VERSION);
+        let decoded = ProofPlan::<RistrettoPoint>::try_from_bytes(&bytes).unwrap();
+        assert_eq!(&decoded, query.proof_expr());
+        decoded.check_matches(query.proof_expr()).unwrap();
+        // Encodings are canonical
+        assert_eq!(decoded.to_bytes(), bytes);
+
+        let json = query.proof_expr().to_json();
+        let decoded = ProofPlan::<RistrettoPoint>::try_from_json(&json).unwrap();
+        assert_eq!(&decoded, query.proof_expr());
+        assert_eq!(decoded.to_json(), json);
+    }
+}
+
+#[test]
+fn we_can_verify_a_proof_of_a_plan_from_another_process_against_our_own_plan() {
+    let accessor = accessor();
+    let sql = "SELECT a, name FROM t WHERE d >= 1";
+    // The planner sends the plan to the prover
+    let bytes = plan(sql, &accessor).proof_expr().to_bytes();
+    let prover_plan = ProofPlan::<RistrettoPoint>::try_from_bytes(&bytes).unwrap();
+    let verifiable_result =
+        VerifiableQueryResult::<InnerProductProof>::new(&prover_plan, &accessor, &());
+
+    // The verifier plans the query itself
+    let verifier_query = plan(sql, &accessor);
+    prover_plan
+        .check_matches(verifier_query.proof_expr())
+        .unwrap();
+    let table = verifiable_result
+        .verify(verifier_query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    assert_eq!(
+        table,
+        owned_table([bigint("a", [1, 2]), varchar("name", ["x", "y"])])
+    );
+}
+
+#[test]
+fn we_cannot_match_a_plan_of_another_query() {
+    let accessor = accessor();
+    let bytes = plan("SELECT a FROM t WHERE a = 1", &accessor)
+        .proof_expr()
+        .to_bytes();
+    let decoded = ProofPlan::<RistrettoPoint>::try_from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        decoded.check_matches(plan("SELECT a FROM t WHERE a = 2", &accessor).proof_expr()),
+        Err(PlanEncodingError::PlanMismatch)
+    ));
+}
+
+#[test]
+fn we_cannot_decode_invalid_encodings() {
+    let accessor = accessor();
+    let query = plan("SELECT a FROM t WHERE a = 1", &accessor);
+
+    assert!(matches!(
+        ProofPlan::<RistrettoPoint>::try_from_bytes(&[]),
+        Err(PlanEncodingError::MissingVersion)
+    ));
+    let mut bytes = query.proof_expr().to_bytes();
+    bytes[0] = PROOF_PLAN_ENCODING_VERSION + 1;
+    assert!(matches!(
+        ProofPlan::<RistrettoPoint>::try_from_bytes(&bytes),
+        Err(PlanEncodingError::UnsupportedVersion(version))
+            if version == PROOF_PLAN_ENCODING_VERSION + 1
+    ));
+    let bytes = query.proof_expr().to_bytes();
+    assert!(matches!(
+        ProofPlan::<RistrettoPoint>::try_from_bytes(&bytes[..bytes.len() / 2]),
+        Err(PlanEncodingError::BinaryDecodingError(_))
+    ));
+
+    let json = query.proof_expr().to_json().replacen(
+        &format!("\"version\":{PROOF_PLAN_ENCODING_VERSION}"),
+        "\"version\":0",
+        1,
+    );
+    assert!(matches!(
+        ProofPlan::<RistrettoPoint>::try_from_json(&json),
+        Err(PlanEncodingError::UnsupportedVersion(0))
+    ));
+    assert!(matches!(
+        ProofPlan::<RistrettoPoint>::try_from_json(r#"{"version":1,"plan":"Projection"}"#),
+        Err(PlanEncodingError::JsonDecodingError(_))
+    ));
+}
@@ -1,11 +1,14 @@
 use super::{
-    AddSubtractExpr, AggregateExpr, AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr,
-    MultiplyExpr, NotExpr, OrExpr, ProvableExpr,
+    AbsExpr, AddSubtractExpr, AggregateExpr, AndExpr, ColumnExpr, EqualsExpr, InequalityExpr,
+    LiteralExpr, ModuloExpr, MultiplyExpr, NotExpr, OrExpr, OverflowPolicy, ProvableExpr,
+    RowIndexExpr, SignumExpr, TimeBucketExpr,
 };
 use crate::{
     base::{
         commitment::Commitment,
-        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LiteralValue},
+        database::{
+            Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LiteralValue, TableRef,
+        },
         proof::ProofError,
     },
     sql::{
@@ -17,7 +20,7 @@ use bumpalo::Bump;
 use indexmap::IndexSet;
 use proof_of_sql_parser::intermediate_ast::{AggregationOperator, BinaryOperator};
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 
 /// Enum of AST column expression types that implement `ProvableExpr`. Is itself a `ProvableExpr`.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -42,12 +45,26 @@ pub enum ProvableExprPlan<C: Commitment> {
     Multiply(MultiplyExpr<C>),
     /// Provable aggregate expression
     Aggregate(AggregateExpr<C>),
+    /// Provable `time_bucket` expression
+    TimeBucket(TimeBucketExpr<C>),
+    /// Provable original row index of each row
+    RowIndex(RowIndexExpr<C>),
+    /// Provable `abs` expression
+    Abs(AbsExpr<C>),
+    /// Provable `sign` expression
+    Signum(SignumExpr<C>),
+    /// Provable `%` expression with a constant modulus
+    Modulo(ModuloExpr<C>),
 }
 impl<C: Commitment> ProvableExprPlan<C> {
     /// Create column expression
     pub fn new_column(column_ref: ColumnRef) -> Self {
         Self::Column(ColumnExpr::new(column_ref))
     }
+    /// Create row index expression
+    pub fn new_row_index(table_ref: TableRef) -> Self {
+        Self::RowIndex(RowIndexExpr::new(table_ref))
+    }
     /// Create logical AND expression
     pub fn try_new_and(
         lhs: ProvableExprPlan<C>,
@@ -122,20 +139,16 @@ impl<C: Commitment> ProvableExprPlan<C> {
         lhs: ProvableExprPlan<C>,
         rhs: ProvableExprPlan<C>,
     ) -> ConversionResult<Self> {
-        let lhs_datatype = lhs.data_type();
-        let rhs_datatype = rhs.data_type();
-        if !type_check_binary_operation(&lhs_datatype, &rhs_datatype, BinaryOperator::Add) {
-            Err(ConversionError::DataTypeMismatch(
-                lhs_datatype.to_string(),
-                rhs_datatype.to_string(),
-            ))
-        } else {
-            Ok(Self::AddSubtract(AddSubtractExpr::new(
-                Box::new(lhs),
-                Box::new(rhs),
-                false,
-            )))
-        }
+        Self::try_new_add_with_overflow_policy(lhs, rhs, OverflowPolicy::default())
+    }
+
+    /// Create a new add expression that handles overflows according to `overflow_policy`
+    pub fn try_new_add_with_overflow_policy(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+        overflow_policy: OverflowPolicy,
+    ) -> ConversionResult<Self> {
+        Self::try_new_add_subtract(lhs, rhs, false, overflow_policy)
     }
 
     /// Create a new subtract expression
@@ -143,19 +156,41 @@ impl<C: Commitment> ProvableExprPlan<C> {
         lhs: ProvableExprPlan<C>,
         rhs: ProvableExprPlan<C>,
     ) -> ConversionResult<Self> {
+        Self::try_new_subtract_with_overflow_policy(lhs, rhs, OverflowPolicy::default())
+    }
+
+    /// Create a new subtract expression that handles overflows according to `overflow_policy`
+    pub fn try_new_subtract_with_overflow_policy(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+        overflow_policy: OverflowPolicy,
+    ) -> ConversionResult<Self> {
+        Self::try_new_add_subtract(lhs, rhs, true, overflow_policy)
+    }
+
+    fn try_new_add_subtract(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+        is_subtract: bool,
+        overflow_policy: OverflowPolicy,
+    ) -> ConversionResult<Self> {
+        let operator = if is_subtract {
+            BinaryOperator::Subtract
+        } else {
+            BinaryOperator::Add
+        };
         let lhs_datatype = lhs.data_type();
         let rhs_datatype = rhs.data_type();
-        if !type_check_binary_operation(&lhs_datatype, &rhs_datatype, BinaryOperator::Subtract) {
+        if !type_check_binary_operation(&lhs_datatype, &rhs_datatype, operator) {
             Err(ConversionError::DataTypeMismatch(
                 lhs_datatype.to_string(),
                 rhs_datatype.to_string(),
             ))
         } else {
-            Ok(Self::AddSubtract(AddSubtractExpr::new(
-                Box::new(lhs),
-                Box::new(rhs),
-                true,
-            )))
+            let expr =
+                AddSubtractExpr::new(Box::new(lhs), Box::new(rhs), is_subtract, overflow_policy);
+            expr.try_data_type()?;
+            Ok(Self::AddSubtract(expr))
         }
     }
 
@@ -163,6 +198,15 @@ impl<C: Commitment> ProvableExprPlan<C> {
     pub fn try_new_multiply(
         lhs: ProvableExprPlan<C>,
         rhs: ProvableExprPlan<C>,
+    ) -> ConversionResult<Self> {
+        Self::try_new_multiply_with_overflow_policy(lhs, rhs, OverflowPolicy::default())
+    }
+
+    /// Create a new multiply expression that handles overflows according to `overflow_policy`
+    pub fn try_new_multiply_with_overflow_policy(
+        lhs: ProvableExprPlan<C>,
+        rhs: ProvableExprPlan<C>,
+        overflow_policy: OverflowPolicy,
     ) -> ConversionResult<Self> {
         let lhs_datatype = lhs.data_type();
         let rhs_datatype = rhs.data_type();
@@ -172,10 +216,9 @@ impl<C: Commitment> ProvableExprPlan<C> {
                 rhs_datatype.to_string(),
             ))
         } else {
-            Ok(Self::Multiply(MultiplyExpr::new(
-                Box::new(lhs),
-                Box::new(rhs),
-            )))
+            let expr = MultiplyExpr::new(Box::new(lhs), Box::new(rhs), overflow_policy);
+            expr.try_data_type()?;
+            Ok(Self::Multiply(expr))
         }
     }
 
@@ -184,6 +227,80 @@ impl<C: Commitment> ProvableExprPlan<C> {
         Self::Aggregate(AggregateExpr::new(op, Box::new(expr)))
     }
 
+    /// Create a new `time_bucket` expression
+    ///
+    /// `bucket_width` is measured in the time unit of `expr`, which must be a timestamp.
+    pub fn try_new_time_bucket(
+        expr: ProvableExprPlan<C>,
+        bucket_width: i64,
+    ) -> ConversionResult<Self> {
+        if !matches!(expr.data_type(), ColumnType::TimestampTZ(_, _)) {
+            Err(ConversionError::InvalidExpression(format!(
+                "time_bucket requires a timestamp but found '{}'",
+                expr.data_type()
+            )))
+        } else if bucket_width <= 0 {
+            Err(ConversionError::InvalidExpression(format!(
+                "time_bucket requires a positive bucket width but found {}",
+                bucket_width
+            )))
+        } else {
+            Ok(Self::TimeBucket(TimeBucketExpr::new(
+                Box::new(expr),
+                bucket_width,
+            )))
+        }
+    }
+
+    /// Create a new `abs` expression
+    pub fn try_new_abs(expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        check_integer(&expr, "abs")?;
+        Ok(Self::Abs(AbsExpr::new(Box::new(expr))))
+    }
+
+    /// Create a new `sign` expression, which is -1, 0 or 1
+    pub fn try_new_signum(expr: ProvableExprPlan<C>) -> ConversionResult<Self> {
+        check_integer(&expr, "sign")?;
+        Ok(Self::Signum(SignumExpr::new(Box::new(expr))))
+    }
+
+    /// Create a new `%` expression
+    ///
+    /// The remainder has the sign of `expr`, which must be an integer.
+    pub fn try_new_modulo(expr: ProvableExprPlan<C>, modulus: i64) -> ConversionResult<Self> {
+        check_integer(&expr, "%")?;
+        if modulus <= 0 {
+            Err(ConversionError::InvalidExpression(format!(
+                "% requires a positive modulus but found {}",
+                modulus
+            )))
+        } else {
+            Ok(Self::Modulo(ModuloExpr::new(Box::new(expr), modulus)))
+        }
+    }
+
+    /// The name of the plan node, which constraints are attributed to in a
+    /// [`crate::sql::proof::ConstraintSystem`].
+    fn plan_node_name(&self) -> &'static str {
+        match self {
+            ProvableExprPlan::Column(_) => "Column",
+            ProvableExprPlan::And(_) => "And",
+            ProvableExprPlan::Or(_) => "Or",
+            ProvableExprPlan::Not(_) => "Not",
+            ProvableExprPlan::Literal(_) => "Literal",
+            ProvableExprPlan::Equals(_) => "Equals",
+            ProvableExprPlan::Inequality(_) => "Inequality",
+            ProvableExprPlan::AddSubtract(_) => "AddSubtract",
+            ProvableExprPlan::Multiply(_) => "Multiply",
+            ProvableExprPlan::Aggregate(_) => "Aggregate",
+            ProvableExprPlan::TimeBucket(_) => "TimeBucket",
+            ProvableExprPlan::RowIndex(_) => "RowIndex",
+            ProvableExprPlan::Abs(_) => "Abs",
+            ProvableExprPlan::Signum(_) => "Signum",
+            ProvableExprPlan::Modulo(_) => "Modulo",
+        }
+    }
+
     /// Check that the plan has the correct data type
     fn check_data_type(&self, data_type: ColumnType) -> ConversionResult<()> {
         if self.data_type() == data_type {
@@ -195,6 +312,128 @@ impl<C: Commitment> ProvableExprPlan<C> {
             })
         }
     }
+
+    /// Returns a range that contains the value of `column` in every row that satisfies this
+    /// boolean expression, or `None` if no row satisfies it.
+    ///
+    /// The range is only narrowed by comparisons of an integer or timestamp column with literals
+    /// of the same kind, combined with `AND`, `OR` and `NOT`. Any other expression may be
+    /// satisfied by any value, so the range is all of `i128` for it.
+    pub fn column_value_range(&self, column: ColumnRef) -> Option<RangeInclusive<i128>> {
+        self.column_value_range_with_exactness(column).0
+    }
+
+    /// Returns the range of [`ProvableExprPlan::column_value_range`], along with whether the rows
+    /// that satisfy this expression are exactly the rows whose value of `column` is in the range.
+    fn column_value_range_with_exactness(
+        &self,
+        column: ColumnRef,
+    ) -> (Option<RangeInclusive<i128>>, bool) {
+        let unknown_range = (Some(i128::MIN..=i128::MAX), false);
+        match self {
+            Self::Literal(literal) => match literal.value() {
+                LiteralValue::Boolean(true) => (Some(i128::MIN..=i128::MAX), true),
+                LiteralValue::Boolean(false) => (None, true),
+                _ => unknown_range,
+            },
+            Self::And(and_expr) => {
+                let (lhs, lhs_is_exact) = and_expr.lhs.column_value_range_with_exactness(column);
+                let (rhs, rhs_is_exact) = and_expr.rhs.column_value_range_with_exactness(column);
+                let intersection = lhs.zip(rhs).and_then(|(lhs, rhs)| {
+                    let range = *lhs.start().max(rhs.start())..=*lhs.end().min(rhs.end());
+                    (!range.is_empty()).then_some(range)
+                });
+                (intersection, lhs_is_exact && rhs_is_exact)
+            }
+            Self::Or(or_expr) => {
+                let (lhs, _) = or_expr.lhs.column_value_range_with_exactness(column);
+                let (rhs, _) = or_expr.rhs.column_value_range_with_exactness(column);
+                let hull = match (lhs, rhs) {
+                    (Some(lhs), Some(rhs)) => {
+                        Some(*lhs.start().min(rhs.start())..=*lhs.end().max(rhs.end()))
+                    }
+                    (range, None) | (None, range) => range,
+                };
+                (hull, false)
+            }
+            // The complement of a range is only a range if the range is unbounded on one side
+            Self::Not(not_expr) => match not_expr.expr.column_value_range_with_exactness(column) {
+                (None, true) => (Some(i128::MIN..=i128::MAX), true),
+                (Some(range), true) => match (*range.start(), *range.end()) {
+                    (i128::MIN, i128::MAX) => (None, true),
+                    (i128::MIN, end) => (Some(end + 1..=i128::MAX), true),
+                    (start, i128::MAX) => (Some(i128::MIN..=start - 1), true),
+                    _ => unknown_range,
+                },
+                _ => unknown_range,
+            },
+            Self::Equals(equals_expr) => {
+                match compared_literal(&equals_expr.lhs, &equals_expr.rhs, column) {
+                    Some((_, value)) => (Some(value..=value), true),
+                    None => unknown_range,
+                }
+            }
+            Self::Inequality(inequality_expr) => {
+                match compared_literal(&inequality_expr.lhs, &inequality_expr.rhs, column) {
+                    // `column <= value` or `value >= column`
+                    Some((column_is_lhs, value)) if column_is_lhs == inequality_expr.is_lte => {
+                        (Some(i128::MIN..=value), true)
+                    }
+                    Some((_, value)) => (Some(value..=i128::MAX), true),
+                    None => unknown_range,
+                }
+            }
+            _ => unknown_range,
+        }
+    }
+}
+
+/// Check that the expression is an integer, which the function or operator `name` requires.
+fn check_integer<C: Commitment>(expr: &ProvableExprPlan<C>, name: &str) -> ConversionResult<()> {
+    if expr.data_type().is_integer() {
+        Ok(())
+    } else {
+        Err(ConversionError::InvalidExpression(format!(
+            "{} requires an integer but found '{}'",
+            name,
+            expr.data_type()
+        )))
+    }
+}
+
+/// If one side of a comparison is `column` and the other is a literal that can be compared with it
+/// as an integer, returns whether the column is the left hand side, along with the literal.
+fn compared_literal<C: Commitment>(
+    lhs: &ProvableExprPlan<C>,
+    rhs: &ProvableExprPlan<C>,
+    column: ColumnRef,
+) -> Option<(bool, i128)> {
+    let (column_is_lhs, column_expr, literal_expr) = match (lhs, rhs) {
+        (ProvableExprPlan::Column(column_expr), ProvableExprPlan::Literal(literal_expr)) => {
+            (true, column_expr, literal_expr)
+        }
+        (ProvableExprPlan::Literal(literal_expr), ProvableExprPlan::Column(column_expr)) => {
+            (false, column_expr, literal_expr)
+        }
+        _ => return None,
+    };
+    if column_expr.get_column_reference() != column {
+        return None;
+    }
+    let is_integer = column.column_type().is_integer();
+    let value = match (literal_expr.value(), column.column_type()) {
+        (LiteralValue::SmallInt(value), _) if is_integer => (*value).into(),
+        (LiteralValue::Int(value), _) if is_integer => (*value).into(),
+        (LiteralValue::BigInt(value), _) if is_integer => (*value).into(),
+        (LiteralValue::Int128(value), _) if is_integer => *value,
+        (LiteralValue::TimeStampTZ(unit, _, value), ColumnType::TimestampTZ(column_unit, _))
+            if unit == column_unit =>
+        {
+            (*value).into()
+        }
+        _ => return None,
+    };
+    Some((column_is_lhs, value))
 }
 
 impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
@@ -210,6 +449,11 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::AddSubtract(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::Multiply(expr) => ProvableExpr::<C>::count(expr, builder),
             ProvableExprPlan::Aggregate(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::TimeBucket(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::RowIndex(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Abs(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Signum(expr) => ProvableExpr::<C>::count(expr, builder),
+            ProvableExprPlan::Modulo(expr) => ProvableExpr::<C>::count(expr, builder),
         }
     }
 
@@ -219,6 +463,11 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::AddSubtract(expr) => expr.data_type(),
             ProvableExprPlan::Multiply(expr) => expr.data_type(),
             ProvableExprPlan::Aggregate(expr) => expr.data_type(),
+            ProvableExprPlan::TimeBucket(expr) => expr.data_type(),
+            ProvableExprPlan::RowIndex(expr) => expr.data_type(),
+            ProvableExprPlan::Abs(expr) => expr.data_type(),
+            ProvableExprPlan::Signum(expr) => expr.data_type(),
+            ProvableExprPlan::Modulo(expr) => expr.data_type(),
             ProvableExprPlan::Literal(expr) => ProvableExpr::<C>::data_type(expr),
             ProvableExprPlan::And(_)
             | ProvableExprPlan::Or(_)
@@ -265,6 +514,21 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Aggregate(expr) => {
                 ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
+            ProvableExprPlan::TimeBucket(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::RowIndex(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Abs(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Signum(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            ProvableExprPlan::Modulo(expr) => {
+                ProvableExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
         }
     }
 
@@ -274,7 +538,8 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
         alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        match self {
+        builder.enter_plan_node(self.plan_node_name());
+        let column = match self {
             ProvableExprPlan::Column(expr) => {
                 ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
@@ -305,7 +570,24 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Aggregate(expr) => {
                 ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
-        }
+            ProvableExprPlan::TimeBucket(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::RowIndex(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Abs(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Signum(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            ProvableExprPlan::Modulo(expr) => {
+                ProvableExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+        };
+        builder.exit_plan_node();
+        column
     }
 
     fn verifier_evaluate(
@@ -326,6 +608,11 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::AddSubtract(expr) => expr.verifier_evaluate(builder, accessor),
             ProvableExprPlan::Multiply(expr) => expr.verifier_evaluate(builder, accessor),
             ProvableExprPlan::Aggregate(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::TimeBucket(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::RowIndex(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Abs(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Signum(expr) => expr.verifier_evaluate(builder, accessor),
+            ProvableExprPlan::Modulo(expr) => expr.verifier_evaluate(builder, accessor),
         }
     }
 
@@ -355,6 +642,19 @@ impl<C: Commitment> ProvableExpr<C> for ProvableExprPlan<C> {
             ProvableExprPlan::Aggregate(expr) => {
                 ProvableExpr::<C>::get_column_references(expr, columns)
             }
+            ProvableExprPlan::TimeBucket(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::RowIndex(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Abs(expr) => ProvableExpr::<C>::get_column_references(expr, columns),
+            ProvableExprPlan::Signum(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
+            ProvableExprPlan::Modulo(expr) => {
+                ProvableExpr::<C>::get_column_references(expr, columns)
+            }
         }
     }
 }
@@ -43,3 +43,69 @@ fn we_can_compute_the_correct_result_of_a_complex_bool_expr_using_result_evaluat
     ]);
     assert_eq!(res, expected_res);
 }
+
+#[test]
+fn we_can_get_the_range_of_column_values_that_satisfy_a_bool_expr() {
+    let data = owned_table([
+        bigint("a", [1, 2, 3]),
+        int128("c", [1, 2, 3]),
+        varchar("b", ["x", "y", "z"]),
+    ]);
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(t, data, 0);
+    let a = col_ref(t, "a", &accessor);
+    let a_lte = |value| lte::<RistrettoPoint>(column(t, "a", &accessor), const_bigint(value));
+    let a_gte = |value| gte::<RistrettoPoint>(column(t, "a", &accessor), const_bigint(value));
+
+    assert_eq!(a_lte(5).column_value_range(a), Some(i128::MIN..=5));
+    assert_eq!(a_gte(5).column_value_range(a), Some(5..=i128::MAX));
+    assert_eq!(
+        lte::<RistrettoPoint>(const_int128(5), column(t, "a", &accessor)).column_value_range(a),
+        Some(5..=i128::MAX)
+    );
+    assert_eq!(
+        equal::<RistrettoPoint>(column(t, "a", &accessor), const_int(7)).column_value_range(a),
+        Some(7..=7)
+    );
+    assert_eq!(and(a_gte(2), a_lte(5)).column_value_range(a), Some(2..=5));
+    assert_eq!(and(a_gte(5), a_lte(2)).column_value_range(a), None);
+    assert_eq!(
+        or(a_lte(2), a_gte(5)).column_value_range(a),
+        Some(i128::MIN..=i128::MAX)
+    );
+    let two_ranges = or(and(a_gte(2), a_lte(3)), and(a_gte(7), a_lte(9)));
+    assert_eq!(two_ranges.column_value_range(a), Some(2..=9));
+    assert_eq!(not(a_lte(5)).column_value_range(a), Some(6..=i128::MAX));
+    assert_eq!(
+        not(and(a_gte(2), a_lte(5))).column_value_range(a),
+        Some(i128::MIN..=i128::MAX)
+    );
+    assert_eq!(
+        not(and(a_lte(2), a_gte(5))).column_value_range(a),
+        Some(i128::MIN..=i128::MAX)
+    );
+    assert_eq!(
+        const_bool::<RistrettoPoint>(false).column_value_range(a),
+        None
+    );
+
+    // Comparisons that do not involve the column with a literal do not narrow the range
+    let unknown_range = Some(i128::MIN..=i128::MAX);
+    let c_lte = lte::<RistrettoPoint>(column(t, "c", &accessor), const_bigint(5));
+    assert_eq!(c_lte.column_value_range(a), unknown_range);
+    assert_eq!(
+        equal::<RistrettoPoint>(column(t, "b", &accessor), const_varchar("x"))
+            .column_value_range(a),
+        unknown_range
+    );
+    assert_eq!(
+        lte::<RistrettoPoint>(column(t, "a", &accessor), column(t, "c", &accessor))
+            .column_value_range(a),
+        unknown_range
+    );
+    assert_eq!(
+        and(c_lte, a_lte(5)).column_value_range(a),
+        Some(i128::MIN..=5)
+    );
+}
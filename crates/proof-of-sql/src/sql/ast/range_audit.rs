@@ -0,0 +1,122 @@
+use super::{is_within_acceptable_range, OverflowPolicy};
+use crate::{
+    base::{
+        bit::{make_abs_bit_mask, BitDistribution},
+        database::ColumnType,
+        scalar::Scalar,
+    },
+    sql::proof::ProofBuilder,
+};
+use num_bigint::{BigInt, BigUint};
+
+/// Whether the prover audits the ranges of the intermediate values it computes.
+///
+/// Gadgets compute intermediate values as scalars, so a value that leaves the range implied by its
+/// type or its bit distribution silently wraps around in the field rather than failing. Such bugs
+/// only show up as soundness issues later, so the `range-audit` feature makes the prover panic as
+/// soon as it computes such a value. This is meant for testing new gadgets and is too slow for
+/// production.
+pub(crate) const RANGE_AUDIT: bool = cfg!(feature = "range-audit");
+
+/// Returns whether the value is in the range of values of the column type.
+///
+/// Column types without a range, i.e. `Scalar` and `VarChar`, accept every value.
+pub(crate) fn is_in_column_type_range<S: Scalar>(column_type: ColumnType, value: S) -> bool {
+    match column_type {
+        ColumnType::Boolean => TryInto::<bool>::try_into(value).is_ok(),
+        ColumnType::SmallInt => TryInto::<i16>::try_into(value).is_ok(),
+        ColumnType::Int => TryInto::<i32>::try_into(value).is_ok(),
+        ColumnType::BigInt | ColumnType::TimestampTZ(_, _) => {
+            TryInto::<i64>::try_into(value).is_ok()
+        }
+        ColumnType::Int128 => TryInto::<i128>::try_into(value).is_ok(),
+        ColumnType::Decimal75(precision, _) => {
+            let value: BigInt = value.into();
+            *value.magnitude() < BigUint::from(10u8).pow(precision.value().into())
+        }
+        ColumnType::Scalar | ColumnType::VarChar => true,
+    }
+}
+
+/// With [`RANGE_AUDIT`], assert that every value that a gadget computed is in the range of the
+/// column type of the gadget's result.
+pub(crate) fn audit_column_type_range<S: Scalar>(
+    gadget: &str,
+    column_type: ColumnType,
+    values: &[S],
+) {
+    if !RANGE_AUDIT {
+        return;
+    }
+    if let Some(row) = values
+        .iter()
+        .position(|&value| !is_in_column_type_range(column_type, value))
+    {
+        panic!(
+            "range audit failed in {gadget}: {} in row {row} is out of range for {column_type}",
+            values[row]
+        );
+    }
+}
+
+/// With [`OverflowPolicy::Error`], report the first value that a gadget computed out of the range
+/// of the column type of the gadget's result, so that the prover refuses to build the proof.
+pub(crate) fn check_overflow<S: Scalar>(
+    builder: &mut ProofBuilder<'_, S>,
+    overflow_policy: OverflowPolicy,
+    gadget: &'static str,
+    column_type: ColumnType,
+    values: &[S],
+) {
+    if overflow_policy != OverflowPolicy::Error {
+        return;
+    }
+    if let Some(row) = values
+        .iter()
+        .position(|&value| !is_in_column_type_range(column_type, value))
+    {
+        builder.report_overflow(gadget, row);
+    }
+}
+
+/// With [`RANGE_AUDIT`], assert that the bit distribution is in the range that the verifier
+/// accepts and that the bits of every row recompose to the value of the row.
+pub(crate) fn audit_bit_decomposition<S: Scalar>(
+    gadget: &str,
+    values: &[S],
+    dist: &BitDistribution,
+    bits: &[&[bool]],
+) {
+    if !RANGE_AUDIT {
+        return;
+    }
+    assert!(
+        is_within_acceptable_range(dist),
+        "range audit failed in {gadget}: the bit distribution is out of the acceptable range"
+    );
+    assert_eq!(
+        bits.len(),
+        dist.num_varying_bits(),
+        "range audit failed in {gadget}: expected a column for every varying bit"
+    );
+    for (row, &value) in values.iter().enumerate() {
+        let mut mask = [0; 4];
+        for (i, limb) in mask.iter_mut().enumerate() {
+            *limb = dist.or_all[i] & !dist.vary_mask[i];
+        }
+        let mut varying_bits = bits.iter();
+        dist.for_each_varying_bit(|i, pos| {
+            if varying_bits
+                .next()
+                .expect("there is a column for every bit")[row]
+            {
+                mask[i] |= 1 << pos;
+            }
+        });
+        assert_eq!(
+            mask,
+            make_abs_bit_mask(value),
+            "range audit failed in {gadget}: the bits of row {row} do not recompose to {value}"
+        );
+    }
+}
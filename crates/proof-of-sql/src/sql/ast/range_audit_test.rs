@@ -0,0 +1,101 @@
+use super::{audit_bit_decomposition, audit_column_type_range, is_in_column_type_range};
+use crate::base::{
+    bit::{compute_varying_bit_matrix_from_abs_bit_masks, make_abs_bit_masks, BitDistribution},
+    database::ColumnType,
+    math::decimal::Precision,
+    scalar::{Curve25519Scalar, Scalar},
+};
+use bumpalo::Bump;
+
+#[test]
+fn we_can_check_that_values_are_in_the_range_of_integer_types() {
+    let s = Curve25519Scalar::from;
+    assert!(is_in_column_type_range(
+        ColumnType::SmallInt,
+        s(i16::MIN as i64)
+    ));
+    assert!(!is_in_column_type_range(
+        ColumnType::SmallInt,
+        s(i16::MAX as i64 + 1)
+    ));
+    assert!(is_in_column_type_range(ColumnType::Int, s(i32::MAX as i64)));
+    assert!(!is_in_column_type_range(
+        ColumnType::Int,
+        s(i32::MIN as i64 - 1)
+    ));
+    assert!(is_in_column_type_range(ColumnType::BigInt, s(i64::MIN)));
+    assert!(!is_in_column_type_range(
+        ColumnType::BigInt,
+        Curve25519Scalar::from(i64::MAX as i128 + 1)
+    ));
+    assert!(is_in_column_type_range(
+        ColumnType::Int128,
+        Curve25519Scalar::from(i128::MIN)
+    ));
+    assert!(!is_in_column_type_range(
+        ColumnType::Int128,
+        Curve25519Scalar::from(i128::MAX) + Curve25519Scalar::ONE
+    ));
+    assert!(is_in_column_type_range(ColumnType::Boolean, s(1)));
+    assert!(!is_in_column_type_range(ColumnType::Boolean, s(2)));
+}
+
+#[test]
+fn we_can_check_that_values_are_in_the_range_of_a_decimal_precision() {
+    let decimal = ColumnType::Decimal75(Precision::new(3).unwrap(), 1);
+    assert!(is_in_column_type_range(
+        decimal,
+        Curve25519Scalar::from(999_i64)
+    ));
+    assert!(is_in_column_type_range(
+        decimal,
+        Curve25519Scalar::from(-999_i64)
+    ));
+    assert!(!is_in_column_type_range(
+        decimal,
+        Curve25519Scalar::from(1000_i64)
+    ));
+    assert!(!is_in_column_type_range(
+        decimal,
+        Curve25519Scalar::from(-1000_i64)
+    ));
+}
+
+#[test]
+fn scalars_and_varchars_are_always_in_range() {
+    let value = -Curve25519Scalar::ONE;
+    assert!(is_in_column_type_range(ColumnType::Scalar, value));
+    assert!(is_in_column_type_range(ColumnType::VarChar, value));
+}
+
+#[test]
+fn the_audit_accepts_values_that_are_in_range_and_matching_bits() {
+    let alloc = Bump::new();
+    let values: Vec<_> = [-3_i64, 5, 0, 7].map(Curve25519Scalar::from).to_vec();
+    audit_column_type_range("test", ColumnType::SmallInt, &values);
+    let masks = make_abs_bit_masks(&values);
+    let dist = BitDistribution::from_abs_bit_masks(&masks);
+    let bits = compute_varying_bit_matrix_from_abs_bit_masks(&alloc, &masks, &dist);
+    audit_bit_decomposition("test", &values, &dist, &bits);
+}
+
+#[cfg(feature = "range-audit")]
+#[test]
+#[should_panic(expected = "range audit failed in test")]
+fn the_audit_panics_on_values_that_overflow_their_column_type() {
+    let values = [1, i32::MAX as i64 + 1].map(Curve25519Scalar::from);
+    audit_column_type_range("test", ColumnType::Int, &values);
+}
+
+#[cfg(feature = "range-audit")]
+#[test]
+#[should_panic(expected = "range audit failed in test")]
+fn the_audit_panics_on_bits_that_do_not_recompose_to_the_values() {
+    let alloc = Bump::new();
+    let values: Vec<_> = [-3_i64, 5, 0, 7].map(Curve25519Scalar::from).to_vec();
+    let masks = make_abs_bit_masks(&values);
+    let dist = BitDistribution::from_abs_bit_masks(&masks);
+    let bits = compute_varying_bit_matrix_from_abs_bit_masks(&alloc, &masks, &dist);
+    let stale_values: Vec<_> = [-3_i64, 5, 1, 7].map(Curve25519Scalar::from).to_vec();
+    audit_bit_decomposition("test", &stale_values, &dist, &bits);
+}
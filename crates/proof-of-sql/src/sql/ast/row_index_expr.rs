@@ -0,0 +1,85 @@
+use super::ProvableExpr;
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, TableRef},
+        proof::ProofError,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
+};
+use bumpalo::Bump;
+use core::marker::PhantomData;
+use indexmap::IndexSet;
+use serde::{Deserialize, Serialize};
+
+/// Provable expression for the original row index of each row of a table.
+///
+/// The index of a row is its position in the full table, i.e. the table offset plus its
+/// position in the queried data span. No commitment is needed because the verifier
+/// evaluates the MLE of the row indexes directly.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct RowIndexExpr<C: Commitment> {
+    table_ref: TableRef,
+    _phantom_data: PhantomData<C>,
+}
+
+impl<C: Commitment> RowIndexExpr<C> {
+    /// Create a new row index expression for the given table
+    pub fn new(table_ref: TableRef) -> Self {
+        Self {
+            table_ref,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Return the table whose row indexes this expression produces
+    pub fn table_ref(&self) -> TableRef {
+        self.table_ref
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for RowIndexExpr<C> {
+    fn count(&self, _builder: &mut CountBuilder) -> Result<(), ProofError> {
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::BigInt
+    }
+
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let offset = accessor.get_offset(self.table_ref);
+        Column::BigInt(alloc.alloc_slice_fill_with(table_length, |i| (offset + i) as i64))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "RowIndexExpr::prover_evaluate", level = "debug", skip_all)
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let offset = accessor.get_offset(self.table_ref);
+        Column::BigInt(alloc.alloc_slice_fill_with(builder.table_length(), |i| (offset + i) as i64))
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let offset = C::Scalar::from(accessor.get_offset(self.table_ref) as i64);
+        Ok(builder.mle_evaluations.row_index_evaluation
+            + offset * builder.mle_evaluations.one_evaluation)
+    }
+
+    fn get_column_references(&self, _columns: &mut IndexSet<ColumnRef>) {}
+}
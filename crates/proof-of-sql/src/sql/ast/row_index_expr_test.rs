@@ -0,0 +1,63 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Curve25519Scalar,
+    },
+    sql::proof::{exercise_verification, VerifiableQueryResult},
+};
+
+fn we_can_prove_row_indexes_of_selected_rows_with_offset(offset: usize) {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 1, 5]),
+        varchar("b", ["t", "u", "v", "w", "x"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, offset);
+    let expr = dense_filter(
+        vec![
+            col_expr_plan(t, "b", &accessor),
+            aliased_plan(row_index(t), "__row_index__"),
+        ],
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(2)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let offset = offset as i64;
+    let expected = owned_table([
+        varchar("b", ["u", "v", "x"]),
+        bigint("__row_index__", [offset + 1, offset + 2, offset + 4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_row_indexes_of_selected_rows() {
+    we_can_prove_row_indexes_of_selected_rows_with_offset(0);
+}
+
+#[test]
+fn we_can_prove_row_indexes_of_selected_rows_of_a_table_with_an_offset() {
+    we_can_prove_row_indexes_of_selected_rows_with_offset(3);
+}
+
+#[test]
+fn we_can_prove_row_indexes_of_an_empty_table() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [0; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = dense_filter(
+        vec![aliased_plan(row_index(t), "__row_index__")],
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("__row_index__", [0; 0])]);
+    assert_eq!(res, expected);
+}
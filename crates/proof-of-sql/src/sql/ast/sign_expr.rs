@@ -1,17 +1,17 @@
 use super::{
-    is_within_acceptable_range, verify_constant_abs_decomposition,
+    audit_bit_decomposition, is_within_acceptable_range, verify_constant_abs_decomposition,
     verify_constant_sign_decomposition,
 };
 use crate::{
     base::{
-        bit::{compute_varying_bit_matrix, BitDistribution},
+        bit::{compute_varying_bit_matrix_from_abs_bit_masks, make_abs_bit_masks, BitDistribution},
         commitment::Commitment,
         proof::ProofError,
         scalar::Scalar,
     },
     sql::proof::{
-        CountBuilder, ProofBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
-        VerificationBuilder,
+        ColumnLiteralDifference, CountBuilder, DerivedMleKey, DerivedMleKind, DerivedMles,
+        ProofBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType, VerificationBuilder,
     },
 };
 use bumpalo::Bump;
@@ -46,7 +46,8 @@ pub fn result_evaluate_sign<'a, S: Scalar>(
 ) -> &'a [bool] {
     assert_eq!(table_length, expr.len());
     // bit_distribution
-    let dist = BitDistribution::new::<S, _>(expr);
+    let masks = make_abs_bit_masks(expr);
+    let dist = BitDistribution::from_abs_bit_masks(&masks);
 
     // handle the constant case
     if dist.num_varying_bits() == 0 {
@@ -54,7 +55,7 @@ pub fn result_evaluate_sign<'a, S: Scalar>(
     }
 
     // prove that the bits are binary
-    let bits = compute_varying_bit_matrix(alloc, expr, &dist);
+    let bits = compute_varying_bit_matrix_from_abs_bit_masks(alloc, &masks, &dist);
     if !dist.has_varying_sign_bit() {
         return alloc.alloc_slice_fill_copy(table_length, dist.sign_bit());
     }
@@ -72,22 +73,52 @@ pub fn result_evaluate_sign<'a, S: Scalar>(
 ///
 /// Note: We can only prove the sign bit for non-zero scalars, and we restict
 /// the range of non-zero scalar so that there is a unique sign representation.
+///
+/// If `expr` is the difference between a column and a literal, the bit decomposition is reused
+/// from, or stored in, the prover cache.
 pub fn prover_evaluate_sign<'a, S: Scalar>(
     builder: &mut ProofBuilder<'a, S>,
     alloc: &'a Bump,
     expr: &'a [S],
+    difference: Option<ColumnLiteralDifference>,
     #[cfg(test)] treat_column_of_zeros_as_negative: bool,
 ) -> &'a [bool] {
     let table_length = expr.len();
-    // bit_distribution
-    let dist = BitDistribution::new::<S, _>(expr);
-    #[cfg(test)]
-    let dist = {
-        let mut dist = dist;
-        if treat_column_of_zeros_as_negative && dist.vary_mask == [0; 4] {
-            dist.or_all[3] = 1 << 63;
+    let key = difference.map(|difference| DerivedMleKey {
+        difference,
+        kind: DerivedMleKind::Sign,
+    });
+    let cached = key.and_then(|key| builder.cached_derived_mles(&key));
+
+    let (dist, bits) = match cached.as_deref() {
+        Some(DerivedMles::Sign { dist, bits }) => {
+            let data: &[_] = alloc.alloc_slice_copy(bits);
+            let bits: Vec<_> = (0..dist.num_varying_bits())
+                .map(|bit_index| &data[table_length * bit_index..table_length * (bit_index + 1)])
+                .collect();
+            (dist.clone(), bits)
+        }
+        _ => {
+            // bit_distribution
+            let masks = make_abs_bit_masks(expr);
+            let dist = BitDistribution::from_abs_bit_masks(&masks);
+            #[cfg(test)]
+            let dist = {
+                let mut dist = dist;
+                if treat_column_of_zeros_as_negative && dist.vary_mask == [0; 4] {
+                    dist.or_all[3] = 1 << 63;
+                }
+                dist
+            };
+            let bits = compute_varying_bit_matrix_from_abs_bit_masks(alloc, &masks, &dist);
+            if let Some(key) = key {
+                builder.cache_derived_mles(key, || DerivedMles::Sign {
+                    dist: dist.clone(),
+                    bits: bits.concat(),
+                });
+            }
+            (dist, bits)
         }
-        dist
     };
     builder.produce_bit_distribution(dist.clone());
 
@@ -95,9 +126,9 @@ pub fn prover_evaluate_sign<'a, S: Scalar>(
     if dist.num_varying_bits() == 0 {
         return alloc.alloc_slice_fill_copy(table_length, dist.sign_bit());
     }
+    audit_bit_decomposition("sign_expr", expr, &dist, &bits);
 
     // prove that the bits are binary
-    let bits = compute_varying_bit_matrix(alloc, expr, &dist);
     prove_bits_are_binary(builder, &bits);
     if !dist.has_varying_sign_bit() {
         return alloc.alloc_slice_fill_copy(table_length, dist.sign_bit());
@@ -20,7 +20,7 @@ fn prover_evaluation_generates_the_bit_distribution_of_a_constant_column() {
     let alloc = Bump::new();
     let data: Vec<Curve25519Scalar> = data.into_iter().map(Curve25519Scalar::from).collect();
     let mut builder = ProofBuilder::new(3, 2, Vec::new());
-    let sign = prover_evaluate_sign(&mut builder, &alloc, &data, false);
+    let sign = prover_evaluate_sign(&mut builder, &alloc, &data, None, false);
     assert_eq!(sign, [false; 3]);
     assert_eq!(builder.bit_distributions(), [dist]);
 }
@@ -32,7 +32,7 @@ fn prover_evaluation_generates_the_bit_distribution_of_a_negative_constant_colum
     let alloc = Bump::new();
     let data: Vec<Curve25519Scalar> = data.into_iter().map(Curve25519Scalar::from).collect();
     let mut builder = ProofBuilder::new(3, 2, Vec::new());
-    let sign = prover_evaluate_sign(&mut builder, &alloc, &data, false);
+    let sign = prover_evaluate_sign(&mut builder, &alloc, &data, None, false);
     assert_eq!(sign, [true; 3]);
     assert_eq!(builder.bit_distributions(), [dist]);
 }
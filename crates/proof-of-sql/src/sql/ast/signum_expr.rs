@@ -0,0 +1,151 @@
+use super::{
+    count_equals_zero, count_sign, prover_evaluate_equals_zero, prover_evaluate_sign,
+    result_evaluate_equals_zero, result_evaluate_sign, verifier_evaluate_equals_zero,
+    verifier_evaluate_sign, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+};
+use bumpalo::Bump;
+use indexmap::IndexSet;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+
+/// Provable `sign(expr)` expression over an integer expression, which is -1, 0 or 1.
+///
+/// The prover commits to the result `r` and proves that `r = (1 - z) * (1 - 2 * s)`, where `z`
+/// is whether `x == 0` and `s` is the sign bit of `x` from the sign decomposition. The sign bit is
+/// arbitrary for zeros, which is harmless since `z` is then `1`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignumExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+}
+
+impl<C: Commitment> SignumExpr<C> {
+    /// Create a new `sign` expression
+    pub fn new(expr: Box<ProvableExprPlan<C>>) -> Self {
+        Self { expr }
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for SignumExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        count_equals_zero(builder);
+        count_sign(builder)?;
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(1);
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "SignumExpr::result_evaluate", level = "debug", skip_all)
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let values: &[_] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let equals_zero = result_evaluate_equals_zero(table_length, alloc, values);
+        let sign = result_evaluate_sign(table_length, alloc, values);
+        Column::Scalar(compute_signum(alloc, equals_zero, sign))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "SignumExpr::prover_evaluate", level = "debug", skip_all)
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let values: &[_] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+
+        // x == 0
+        let equals_zero = prover_evaluate_equals_zero(builder, alloc, values, None);
+
+        // sign(x) == -1
+        let sign = prover_evaluate_sign(
+            builder,
+            alloc,
+            values,
+            None,
+            #[cfg(test)]
+            false,
+        );
+
+        // r
+        let signum = compute_signum(alloc, equals_zero, sign);
+        builder.produce_intermediate_mle(signum);
+
+        // subpolynomial: r - 1 + z + 2 * s - 2 * z * s
+        let one = C::Scalar::one();
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (one, vec![Box::new(signum)]),
+                (-one, vec![]),
+                (one, vec![Box::new(equals_zero)]),
+                (C::Scalar::TWO, vec![Box::new(sign)]),
+                (-C::Scalar::TWO, vec![Box::new(equals_zero), Box::new(sign)]),
+            ],
+        );
+        Column::Scalar(signum)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let eval = self.expr.verifier_evaluate(builder, accessor)?;
+
+        // x == 0
+        let equals_zero_eval = verifier_evaluate_equals_zero(builder, eval);
+
+        // sign(x) == -1
+        let sign_eval = verifier_evaluate_sign(builder, eval, one_eval)?;
+
+        // r
+        let signum_eval = builder.consume_intermediate_mle();
+
+        // subpolynomial: r - 1 + z + 2 * s - 2 * z * s
+        let eval = builder.mle_evaluations.random_evaluation
+            * (signum_eval - one_eval + equals_zero_eval + C::Scalar::TWO * sign_eval
+                - C::Scalar::TWO * equals_zero_eval * sign_eval);
+        builder.produce_sumcheck_subpolynomial_evaluation(&eval);
+        Ok(signum_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+/// Computes -1, 0 or 1 from whether each entry is zero and its sign bit.
+fn compute_signum<'a, S: Scalar>(alloc: &'a Bump, equals_zero: &[bool], sign: &[bool]) -> &'a [S] {
+    alloc.alloc_slice_fill_with(equals_zero.len(), |i| match (equals_zero[i], sign[i]) {
+        (true, _) => S::ZERO,
+        (false, true) => -S::ONE,
+        (false, false) => S::ONE,
+    })
+}
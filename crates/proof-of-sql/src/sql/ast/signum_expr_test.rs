@@ -0,0 +1,82 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor},
+    },
+    sql::{
+        ast::{test_utility::*, ProvableExprPlan},
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+
+// select a, sign(a) as s from sxt.t where sign(a - 2) = -1
+#[test]
+fn we_can_prove_sign_in_a_filter_result_and_a_where_clause() {
+    let data = owned_table([int("a", [-3_i32, 0, 2, 1, 5, i32::MIN, i32::MAX])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = dense_filter(
+        vec![
+            col_expr_plan(t, "a", &accessor),
+            aliased_plan(signum(column(t, "a", &accessor)), "s"),
+        ],
+        tab(t),
+        equal(
+            signum(subtract(column(t, "a", &accessor), const_int(2))),
+            const_int(-1),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("a", [-3_i32, 0, 1, i32::MIN]),
+        int("s", [-1_i32, 0, 1, -1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select sign(a) as z, sign(b) as p, sign(c) as n from sxt.t
+#[test]
+fn we_can_prove_sign_of_columns_with_a_constant_sign() {
+    let data = owned_table([
+        bigint("a", [0_i64, 0, 0]),
+        bigint("b", [4_i64, 1, 9]),
+        bigint("c", [-4_i64, -1, i64::MIN]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = dense_filter(
+        vec![
+            aliased_plan(signum(column(t, "a", &accessor)), "z"),
+            aliased_plan(signum(column(t, "b", &accessor)), "p"),
+            aliased_plan(signum(column(t, "c", &accessor)), "n"),
+        ],
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("z", [0_i64, 0, 0]),
+        bigint("p", [1_i64, 1, 1]),
+        bigint("n", [-1_i64, -1, -1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_take_the_sign_of_non_integer_expressions() {
+    let data = owned_table([varchar("a", ["x"]), decimal75("b", 10, 2, [-150_i64])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    for name in ["a", "b"] {
+        assert!(matches!(
+            ProvableExprPlan::<RistrettoPoint>::try_new_signum(column(t, name, &accessor)),
+            Err(ConversionError::InvalidExpression(_))
+        ));
+    }
+}
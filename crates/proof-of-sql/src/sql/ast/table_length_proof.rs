@@ -0,0 +1,131 @@
+use super::{GroupByExpr, ProofPlan, ProvableExprPlan, TableExpr};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{CommitmentAccessor, DataAccessor, LiteralValue, OwnedColumn, TableRef},
+    },
+    sql::proof::{QueryError, VerifiableQueryResult},
+};
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The alias of the row count in the underlying group by proof.
+const COUNT_ALIAS: &str = "__length_count__";
+
+/// Errors that can occur when verifying a [`TableLengthProof`].
+#[derive(Error, Debug)]
+pub enum TableLengthProofError {
+    /// The proof was created for a different table than the one being verified.
+    #[error("the proof is for the table {proven} rather than {expected}")]
+    TableMismatch {
+        /// The table the proof was created for
+        proven: TableRef,
+        /// The table that was expected
+        expected: TableRef,
+    },
+    /// The underlying proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+    /// The proof verified, but the proven row count is not the claimed one.
+    #[error("the table has {proven} rows rather than the claimed {claimed}")]
+    LengthMismatch {
+        /// The row count that was proven
+        proven: usize,
+        /// The row count claimed by the proof
+        claimed: usize,
+    },
+}
+
+/// A standalone proof of the number of rows of a committed table at a snapshot.
+///
+/// This is much cheaper than proving a full query, so it is suited to monitoring the growth of a
+/// table: each proof records the row count of the table when it was created, and verifying a
+/// sequence of them shows how the committed table changed over time.
+///
+/// Internally this is a proof of
+/// ```ignore
+///     SELECT COUNT(*) FROM <table>
+/// ```
+/// where the verifier additionally checks that the count is the claimed one.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TableLengthProof<CP: CommitmentEvaluationProof> {
+    table_ref: TableRef,
+    length: usize,
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> TableLengthProof<CP> {
+    /// Prove the number of rows of the table.
+    pub fn new(
+        table_ref: TableRef,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        let length = accessor.get_length(table_ref);
+        let result = VerifiableQueryResult::new(&length_plan(table_ref), accessor, setup);
+        Self {
+            table_ref,
+            length,
+            result,
+        }
+    }
+
+    /// Returns the table that this proof is for.
+    pub fn table_ref(&self) -> TableRef {
+        self.table_ref
+    }
+
+    /// Returns the row count claimed by this proof. This is only trustworthy after [`Self::verify`]
+    /// succeeds.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Verify the number of rows of the table, returning it.
+    pub fn verify(
+        &self,
+        table_ref: TableRef,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<usize, TableLengthProofError> {
+        if self.table_ref != table_ref {
+            return Err(TableLengthProofError::TableMismatch {
+                proven: self.table_ref,
+                expected: table_ref,
+            });
+        }
+        let plan = length_plan::<CP::Commitment>(table_ref);
+        let table = self.result.verify(&plan, accessor, setup)?.table;
+        // An empty table has no groups at all, and every other table has a single group.
+        let proven = match table.inner_table().get(&count_alias()) {
+            Some(OwnedColumn::BigInt(counts)) => counts.iter().sum::<i64>() as usize,
+            _ => 0,
+        };
+        if proven == self.length {
+            Ok(proven)
+        } else {
+            Err(TableLengthProofError::LengthMismatch {
+                proven,
+                claimed: self.length,
+            })
+        }
+    }
+}
+
+fn count_alias() -> Identifier {
+    COUNT_ALIAS
+        .parse()
+        .expect("the count alias should be a valid identifier")
+}
+
+/// Build the group by plan whose result is the row count of the table.
+fn length_plan<C: Commitment>(table_ref: TableRef) -> ProofPlan<C> {
+    ProofPlan::GroupBy(GroupByExpr::new(
+        vec![],
+        vec![],
+        count_alias(),
+        TableExpr { table_ref },
+        ProvableExprPlan::new_literal(LiteralValue::Boolean(true)),
+    ))
+}
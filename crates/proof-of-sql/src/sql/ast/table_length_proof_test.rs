@@ -0,0 +1,83 @@
+use super::{TableLengthProof, TableLengthProofError};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{
+        owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+
+fn accessor_with_table(
+    table_ref: TableRef,
+    table: OwnedTable<Curve25519Scalar>,
+) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(table_ref, table, 0);
+    accessor
+}
+
+fn sample_table(length: usize) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("id", 0..length as i64),
+        varchar("name", (0..length).map(|i| format!("row{i}"))),
+    ])
+}
+
+#[test]
+fn we_can_prove_and_verify_the_length_of_a_table() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table(5));
+    let proof = TableLengthProof::<InnerProductProof>::new(t, &accessor, &());
+    assert_eq!(proof.table_ref(), t);
+    assert_eq!(proof.length(), 5);
+    assert_eq!(proof.verify(t, &accessor, &()).unwrap(), 5);
+}
+
+#[test]
+fn we_can_prove_and_verify_the_length_of_an_empty_table() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table(0));
+    let proof = TableLengthProof::<InnerProductProof>::new(t, &accessor, &());
+    assert_eq!(proof.verify(t, &accessor, &()).unwrap(), 0);
+}
+
+#[test]
+fn we_can_verify_a_deserialized_table_length_proof() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table(3));
+    let proof = TableLengthProof::<InnerProductProof>::new(t, &accessor, &());
+    let bytes = postcard::to_allocvec(&proof).unwrap();
+    let proof: TableLengthProof<InnerProductProof> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(proof.verify(t, &accessor, &()).unwrap(), 3);
+}
+
+#[test]
+fn we_can_track_the_growth_of_a_table_with_table_length_proofs() {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = accessor_with_table(t, sample_table(2));
+    let first = TableLengthProof::<InnerProductProof>::new(t, &accessor, &());
+    let first_length = first.verify(t, &accessor, &()).unwrap();
+    accessor.add_table(t, sample_table(7), 0);
+    let second = TableLengthProof::<InnerProductProof>::new(t, &accessor, &());
+    let second_length = second.verify(t, &accessor, &()).unwrap();
+    assert_eq!((first_length, second_length), (2, 7));
+
+    // The earlier snapshot no longer matches the committed table.
+    assert!(matches!(
+        first.verify(t, &accessor, &()),
+        Err(TableLengthProofError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_table_length_proof_for_a_different_table() {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let mut accessor = accessor_with_table(t, sample_table(4));
+    accessor.add_table(u, sample_table(4), 0);
+    let proof = TableLengthProof::<InnerProductProof>::new(t, &accessor, &());
+    assert!(matches!(
+        proof.verify(u, &accessor, &()),
+        Err(TableLengthProofError::TableMismatch { .. })
+    ));
+}
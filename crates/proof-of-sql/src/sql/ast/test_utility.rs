@@ -256,6 +256,45 @@ pub fn sum_expr<C: Commitment>(
     }
 }
 
+pub fn row_index<C: Commitment>(tab: TableRef) -> ProvableExprPlan<C> {
+    ProvableExprPlan::new_row_index(tab)
+}
+
+pub fn time_bucket<C: Commitment>(
+    expr: ProvableExprPlan<C>,
+    bucket_width: i64,
+) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_time_bucket(expr, bucket_width).unwrap()
+}
+
+pub fn abs<C: Commitment>(expr: ProvableExprPlan<C>) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_abs(expr).unwrap()
+}
+
+pub fn signum<C: Commitment>(expr: ProvableExprPlan<C>) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_signum(expr).unwrap()
+}
+
+pub fn modulo<C: Commitment>(expr: ProvableExprPlan<C>, modulus: i64) -> ProvableExprPlan<C> {
+    ProvableExprPlan::try_new_modulo(expr, modulus).unwrap()
+}
+
+pub fn group_by_exprs<C: Commitment>(
+    group_by_exprs: Vec<AliasedProvableExprPlan<C>>,
+    sum_expr: Vec<AliasedProvableExprPlan<C>>,
+    count_alias: &str,
+    table: TableExpr,
+    where_clause: ProvableExprPlan<C>,
+) -> ProofPlan<C> {
+    ProofPlan::GroupBy(GroupByExpr::new(
+        group_by_exprs,
+        sum_expr,
+        count_alias.parse().unwrap(),
+        table,
+        where_clause,
+    ))
+}
+
 pub fn group_by<C: Commitment>(
     group_by_exprs: Vec<ColumnExpr<C>>,
     sum_expr: Vec<AliasedProvableExprPlan<C>>,
@@ -264,7 +303,13 @@ pub fn group_by<C: Commitment>(
     where_clause: ProvableExprPlan<C>,
 ) -> ProofPlan<C> {
     ProofPlan::GroupBy(GroupByExpr::new(
-        group_by_exprs,
+        group_by_exprs
+            .into_iter()
+            .map(|expr| AliasedProvableExprPlan {
+                alias: expr.column_id(),
+                expr: ProvableExprPlan::Column(expr),
+            })
+            .collect(),
         sum_expr,
         count_alias.parse().unwrap(),
         table,
@@ -0,0 +1,178 @@
+use super::{
+    count_non_positive, prove_non_positive, verify_non_positive, ProvableExpr, ProvableExprPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, ProofBuilder, VerificationBuilder},
+};
+use bumpalo::Bump;
+use indexmap::IndexSet;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+
+/// Provable `time_bucket(bucket_width, expr)` expression over a `TimestampTZ` expression.
+///
+/// Each timestamp `ts` is mapped to the start of its bucket, `floor(ts / bucket_width) * bucket_width`,
+/// where `bucket_width` is measured in the time unit of `expr`.
+///
+/// The prover commits to the bucket index `b = floor(ts / bucket_width)` and proves that the
+/// remainder `r = ts - bucket_width * b` satisfies `0 <= r <= bucket_width - 1`, which pins down `b`.
+///
+/// The start of the bucket of a timestamp within `bucket_width` of `i64::MIN` does not fit in an
+/// `i64`, so the prover refuses to prove such timestamps with [`ProverError::Overflow`].
+///
+/// [`ProverError::Overflow`]: crate::base::proof::ProverError::Overflow
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeBucketExpr<C: Commitment> {
+    expr: Box<ProvableExprPlan<C>>,
+    bucket_width: i64,
+}
+
+impl<C: Commitment> TimeBucketExpr<C> {
+    /// Create a new `time_bucket` expression
+    pub fn new(expr: Box<ProvableExprPlan<C>>, bucket_width: i64) -> Self {
+        Self { expr, bucket_width }
+    }
+
+    /// Returns the width of each bucket in the time unit of the input expression
+    pub fn bucket_width(&self) -> i64 {
+        self.bucket_width
+    }
+}
+
+impl<C: Commitment> ProvableExpr<C> for TimeBucketExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        builder.count_intermediate_mles(1);
+        // 0 <= r and r <= bucket_width - 1
+        for _ in 0..2 {
+            count_non_positive(builder)?;
+        }
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "TimeBucketExpr::result_evaluate", level = "debug", skip_all)
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let Column::TimestampTZ(time_unit, timezone, timestamps) =
+            self.expr.result_evaluate(table_length, alloc, accessor)
+        else {
+            panic!("time_bucket expression is not a timestamp");
+        };
+        // The prover reports timestamps whose bucket start overflows in `prover_evaluate`
+        let bucket_starts = alloc.alloc_slice_fill_with(timestamps.len(), |i| {
+            bucket_start(
+                timestamps[i].div_euclid(self.bucket_width),
+                self.bucket_width,
+            )
+            .unwrap_or(i64::MIN)
+        });
+        Column::TimestampTZ(time_unit, timezone, bucket_starts)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "TimeBucketExpr::prover_evaluate", level = "debug", skip_all)
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let Column::TimestampTZ(time_unit, timezone, timestamps) =
+            self.expr.prover_evaluate(builder, alloc, accessor)
+        else {
+            panic!("time_bucket expression is not a timestamp");
+        };
+        let (buckets, lower, upper) =
+            compute_buckets_and_remainder_bounds(alloc, timestamps, self.bucket_width);
+
+        // b
+        builder.produce_intermediate_mle(buckets);
+
+        // -r <= 0 and r - (bucket_width - 1) <= 0
+        for bound in [lower, upper] {
+            prove_non_positive(builder, alloc, bound);
+        }
+
+        if let Some(row) = buckets
+            .iter()
+            .position(|&bucket| bucket_start(bucket, self.bucket_width).is_none())
+        {
+            builder.report_overflow("time_bucket_expr", row);
+        }
+        let bucket_starts = alloc.alloc_slice_fill_with(buckets.len(), |i| {
+            bucket_start(buckets[i], self.bucket_width).unwrap_or(i64::MIN)
+        });
+        Column::TimestampTZ(time_unit, timezone, bucket_starts)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.one_evaluation;
+        let timestamp_eval = self.expr.verifier_evaluate(builder, accessor)?;
+
+        // b
+        let bucket_eval = builder.consume_intermediate_mle();
+
+        // r = ts - bucket_width * b
+        let width = C::Scalar::from(self.bucket_width);
+        let remainder_eval = timestamp_eval - width * bucket_eval;
+
+        // -r <= 0 and r - (bucket_width - 1) <= 0
+        verify_non_positive(builder, -remainder_eval, one_eval)?;
+        verify_non_positive(
+            builder,
+            remainder_eval - (width - C::Scalar::one()) * one_eval,
+            one_eval,
+        )?;
+
+        Ok(width * bucket_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
+
+/// Returns the start of bucket `bucket`, or `None` if it does not fit in an `i64`.
+fn bucket_start(bucket: i64, bucket_width: i64) -> Option<i64> {
+    bucket.checked_mul(bucket_width)
+}
+
+/// Computes the bucket indexes `b` along with `-r` and `r - (bucket_width - 1)`,
+/// where `r` is the remainder of each timestamp within its bucket.
+fn compute_buckets_and_remainder_bounds<'a, S: Scalar>(
+    alloc: &'a Bump,
+    timestamps: &[i64],
+    bucket_width: i64,
+) -> (&'a [i64], &'a [S], &'a [S]) {
+    let n = timestamps.len();
+    let buckets = alloc.alloc_slice_fill_with(n, |i| timestamps[i].div_euclid(bucket_width));
+    let lower =
+        alloc.alloc_slice_fill_with(n, |i| -S::from(timestamps[i].rem_euclid(bucket_width)));
+    let upper = alloc.alloc_slice_fill_with(n, |i| {
+        S::from(timestamps[i].rem_euclid(bucket_width)) - S::from(bucket_width - 1)
+    });
+    (buckets, lower, upper)
+}
@@ -0,0 +1,202 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        proof::{ProverDeadline, ProverError},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        ast::ProvableExprPlan,
+        parse::ConversionError,
+        proof::{exercise_verification, EvaluationContext, VerifiableQueryResult},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+/// select time_bucket(60, ts) as bucket, sum(volume) as volume, count(*) as __count__ from sxt.t group by bucket
+#[test]
+fn we_can_prove_a_group_by_over_time_buckets() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            vec![0, 30, 59, 60, 125, -1],
+        ),
+        bigint("volume", [1, 2, 3, 4, 5, 6]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_exprs(
+        vec![aliased_plan(
+            time_bucket(column(t, "ts", &accessor), 60),
+            "bucket",
+        )],
+        vec![sum_expr(column(t, "volume", &accessor), "volume")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        timestamptz(
+            "bucket",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            vec![-60, 0, 60, 120],
+        ),
+        bigint("volume", [6, 1 + 2 + 3, 4, 5]),
+        bigint("__count__", [1, 3, 1, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// select ts, time_bucket(1000, ts) as bucket from sxt.t where volume >= 2
+#[test]
+fn we_can_prove_time_buckets_in_a_filter_result() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            vec![999, 1000, 2999, -1001],
+        ),
+        bigint("volume", [1, 2, 3, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = dense_filter(
+        vec![
+            col_expr_plan(t, "ts", &accessor),
+            aliased_plan(time_bucket(column(t, "ts", &accessor), 1000), "bucket"),
+        ],
+        tab(t),
+        gte(column(t, "volume", &accessor), const_bigint(2)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            vec![1000, 2999, -1001],
+        ),
+        timestamptz(
+            "bucket",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            vec![1000, 2000, -2000],
+        ),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// select ts, time_bucket(60, ts) as bucket from sxt.t where volume >= 2
+#[test]
+fn the_prover_refuses_to_prove_time_buckets_that_start_before_the_minimum_timestamp() {
+    // i64::MIN = -8 (mod 60), so the bucket of i64::MIN + 52 starts at i64::MIN + 52, but the
+    // bucket of i64::MIN + 51 starts before i64::MIN
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let t = "sxt.t".parse().unwrap();
+    accessor.add_table(
+        t,
+        owned_table([
+            timestamptz(
+                "ts",
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::Utc,
+                vec![i64::MIN + 52, i64::MIN + 100],
+            ),
+            bigint("volume", [2, 3]),
+        ]),
+        0,
+    );
+    let u = "sxt.u".parse().unwrap();
+    accessor.add_table(
+        u,
+        owned_table([
+            timestamptz(
+                "ts",
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::Utc,
+                vec![i64::MIN + 52, i64::MIN + 51],
+            ),
+            bigint("volume", [2, 3]),
+        ]),
+        0,
+    );
+    let expr = |t| {
+        dense_filter(
+            vec![
+                col_expr_plan(t, "ts", &accessor),
+                aliased_plan(time_bucket(column(t, "ts", &accessor), 60), "bucket"),
+            ],
+            tab(t),
+            gte(column(t, "volume", &accessor), const_bigint(2)),
+        )
+    };
+    let res = VerifiableQueryResult::new(&expr(t), &accessor, &());
+    exercise_verification(&res, &expr(t), &accessor, t);
+    let res = res.verify(&expr(t), &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        timestamptz(
+            "ts",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            vec![i64::MIN + 52, i64::MIN + 100],
+        ),
+        timestamptz(
+            "bucket",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            vec![i64::MIN + 52, i64::MIN + 52],
+        ),
+    ]);
+    assert_eq!(res, expected);
+
+    let res = VerifiableQueryResult::<InnerProductProof>::new_with_deadline(
+        &expr(u),
+        &accessor,
+        &(),
+        EvaluationContext::default(),
+        &ProverDeadline::default(),
+    );
+    assert_eq!(
+        res.err(),
+        Some(ProverError::Overflow {
+            gadget: "time_bucket_expr",
+            row: 1
+        })
+    );
+}
+
+#[test]
+fn we_cannot_bucket_non_timestamp_expressions_or_use_non_positive_widths() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        timestamptz("ts", PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, vec![0]),
+        bigint("volume", [1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_time_bucket(column(t, "volume", &accessor), 60),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_time_bucket(column(t, "ts", &accessor), 0),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        ProvableExprPlan::<RistrettoPoint>::try_new_time_bucket(column(t, "ts", &accessor), -60),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+}
@@ -0,0 +1,147 @@
+use super::{AliasedProvableExprPlan, GroupByExpr, ProofPlan, ProvableExprPlan, TableExpr};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{ColumnRef, CommitmentAccessor, DataAccessor, LiteralValue, OwnedColumn},
+    },
+    sql::proof::{QueryError, VerifiableQueryResult},
+};
+use indexmap::IndexSet;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The alias of the per-key row count in the underlying group by proof.
+const COUNT_ALIAS: &str = "__uniqueness_count__";
+
+/// Errors that can occur when creating or verifying a [`UniquenessProof`].
+#[derive(Error, Debug)]
+pub enum UniquenessProofError {
+    /// A key must have at least one column.
+    #[error("a key must have at least one column")]
+    EmptyKey,
+    /// All columns of a key must belong to the same table.
+    #[error("all columns of a key must belong to the same table")]
+    MixedTables,
+    /// A key cannot contain the same column twice.
+    #[error("column '{0}' appears in the key more than once")]
+    DuplicateKeyColumn(Identifier),
+    /// The name of a key column collides with the name used for the row counts of the proof.
+    #[error("column '{0}' has a name reserved by uniqueness proofs")]
+    ReservedColumnName(Identifier),
+    /// The proof was created for a different key than the one being verified.
+    #[error("the proof is for the key {proven:?} rather than {expected:?}")]
+    KeyMismatch {
+        /// The key the proof was created for
+        proven: Vec<ColumnRef>,
+        /// The key that was expected
+        expected: Vec<ColumnRef>,
+    },
+    /// The underlying proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+    /// The proof verified, but some key value occurs in more than one row.
+    #[error("the key columns contain duplicate values")]
+    DuplicateKey,
+}
+
+/// A standalone proof that a set of columns forms a unique key of a committed table,
+/// i.e. that no two rows of the table agree on all of the key columns.
+///
+/// Since the proof only depends on the table, it can be verified once and cached,
+/// e.g. to justify the semantics of joins or point lookups on the key.
+/// It has to be recreated whenever the table changes.
+///
+/// Internally this is a proof of
+/// ```ignore
+///     SELECT <key_column1>, ..., <key_columnN>, COUNT(*) FROM <table>
+///     GROUP BY <key_column1>, ..., <key_columnN>
+/// ```
+/// where the verifier additionally checks that every count is one.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UniquenessProof<CP: CommitmentEvaluationProof> {
+    key: Vec<ColumnRef>,
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> UniquenessProof<CP> {
+    /// Prove that the `key` columns form a unique key of their table.
+    ///
+    /// This succeeds even if the key has duplicates, in which case the proof will fail to verify.
+    pub fn new(
+        key: Vec<ColumnRef>,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, UniquenessProofError> {
+        let plan = uniqueness_plan::<CP::Commitment>(&key)?;
+        let result = VerifiableQueryResult::new(&plan, accessor, setup);
+        Ok(Self { key, result })
+    }
+
+    /// Returns the key columns that this proof is for.
+    pub fn key(&self) -> &[ColumnRef] {
+        &self.key
+    }
+
+    /// Verify that the `key` columns form a unique key of their table.
+    pub fn verify(
+        &self,
+        key: &[ColumnRef],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<(), UniquenessProofError> {
+        if self.key != key {
+            return Err(UniquenessProofError::KeyMismatch {
+                proven: self.key.clone(),
+                expected: key.to_vec(),
+            });
+        }
+        let plan = uniqueness_plan::<CP::Commitment>(key)?;
+        let table = self.result.verify(&plan, accessor, setup)?.table;
+        match table.inner_table().get(&count_alias()) {
+            Some(OwnedColumn::BigInt(counts)) if counts.iter().all(|&count| count == 1) => Ok(()),
+            _ => Err(UniquenessProofError::DuplicateKey),
+        }
+    }
+}
+
+fn count_alias() -> Identifier {
+    COUNT_ALIAS
+        .parse()
+        .expect("the count alias should be a valid identifier")
+}
+
+/// Build the group by plan whose result has one row per distinct key value.
+fn uniqueness_plan<C: Commitment>(key: &[ColumnRef]) -> Result<ProofPlan<C>, UniquenessProofError> {
+    let table_ref = key
+        .first()
+        .ok_or(UniquenessProofError::EmptyKey)?
+        .table_ref();
+    let count_alias = count_alias();
+    let mut column_ids = IndexSet::new();
+    for column in key {
+        if column.table_ref() != table_ref {
+            return Err(UniquenessProofError::MixedTables);
+        }
+        if column.column_id() == count_alias {
+            return Err(UniquenessProofError::ReservedColumnName(column.column_id()));
+        }
+        if !column_ids.insert(column.column_id()) {
+            return Err(UniquenessProofError::DuplicateKeyColumn(column.column_id()));
+        }
+    }
+    let group_by_exprs = key
+        .iter()
+        .map(|column| AliasedProvableExprPlan {
+            expr: ProvableExprPlan::new_column(*column),
+            alias: column.column_id(),
+        })
+        .collect();
+    Ok(ProofPlan::GroupBy(GroupByExpr::new(
+        group_by_exprs,
+        vec![],
+        count_alias,
+        TableExpr { table_ref },
+        ProvableExprPlan::new_literal(LiteralValue::Boolean(true)),
+    )))
+}
@@ -0,0 +1,158 @@
+use super::{UniquenessProof, UniquenessProofError};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTable, OwnedTableTestAccessor,
+        SchemaAccessor, TableRef, TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+
+fn accessor_with_table(
+    table_ref: TableRef,
+    table: OwnedTable<Curve25519Scalar>,
+) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(table_ref, table, 0);
+    accessor
+}
+
+fn key(
+    table_ref: TableRef,
+    columns: &[&str],
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Vec<ColumnRef> {
+    columns
+        .iter()
+        .map(|name| {
+            let name = name.parse().unwrap();
+            let column_type = accessor.lookup_column(table_ref, name).unwrap();
+            ColumnRef::new(table_ref, name, column_type)
+        })
+        .collect()
+}
+
+fn sample_table() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("id", [5, 3, 1, 4, 2]),
+        varchar("region", ["eu", "us", "eu", "us", "eu"]),
+        int("shard", [1, 1, 2, 2, 1]),
+        bigint("version", [1, 1, 1, 2, 2]),
+    ])
+}
+
+#[test]
+fn we_can_prove_and_verify_a_single_column_key() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table());
+    let key = key(t, &["id"], &accessor);
+    let proof = UniquenessProof::<InnerProductProof>::new(key.clone(), &accessor, &()).unwrap();
+    assert_eq!(proof.key(), key);
+    proof.verify(&key, &accessor, &()).unwrap();
+}
+
+#[test]
+fn we_can_prove_and_verify_a_composite_key_whose_columns_are_not_unique_on_their_own() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table());
+    let key = key(t, &["region", "shard", "version"], &accessor);
+    let proof = UniquenessProof::<InnerProductProof>::new(key.clone(), &accessor, &()).unwrap();
+    proof.verify(&key, &accessor, &()).unwrap();
+}
+
+#[test]
+fn we_cannot_verify_a_key_with_duplicate_values() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table());
+    for columns in [&["region"][..], &["region", "shard"], &["version"]] {
+        let key = key(t, columns, &accessor);
+        let proof = UniquenessProof::<InnerProductProof>::new(key.clone(), &accessor, &()).unwrap();
+        assert!(matches!(
+            proof.verify(&key, &accessor, &()),
+            Err(UniquenessProofError::DuplicateKey)
+        ));
+    }
+}
+
+#[test]
+fn we_can_verify_a_key_of_an_empty_table() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, owned_table([bigint("id", [0; 0])]));
+    let key = key(t, &["id"], &accessor);
+    let proof = UniquenessProof::<InnerProductProof>::new(key.clone(), &accessor, &()).unwrap();
+    proof.verify(&key, &accessor, &()).unwrap();
+}
+
+#[test]
+fn we_can_verify_a_deserialized_uniqueness_proof() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table());
+    let key = key(t, &["region", "shard", "version"], &accessor);
+    let proof = UniquenessProof::<InnerProductProof>::new(key.clone(), &accessor, &()).unwrap();
+    let bytes = postcard::to_allocvec(&proof).unwrap();
+    let proof: UniquenessProof<InnerProductProof> = postcard::from_bytes(&bytes).unwrap();
+    proof.verify(&key, &accessor, &()).unwrap();
+}
+
+#[test]
+fn we_cannot_verify_a_uniqueness_proof_for_a_different_key_or_table() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor_with_table(t, sample_table());
+    let id_key = key(t, &["id"], &accessor);
+    let proof = UniquenessProof::<InnerProductProof>::new(id_key.clone(), &accessor, &()).unwrap();
+
+    let composite_key = key(t, &["region", "shard", "version"], &accessor);
+    assert!(matches!(
+        proof.verify(&composite_key, &accessor, &()),
+        Err(UniquenessProofError::KeyMismatch { .. })
+    ));
+
+    // The same key over a table with different data
+    let other_accessor = accessor_with_table(
+        t,
+        owned_table([
+            bigint("id", [5, 3, 1, 4, 6]),
+            varchar("region", ["eu", "us", "eu", "us", "eu"]),
+            int("shard", [1, 1, 2, 2, 1]),
+            bigint("version", [1, 1, 1, 2, 2]),
+        ]),
+    );
+    assert!(matches!(
+        proof.verify(&id_key, &other_accessor, &()),
+        Err(UniquenessProofError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_create_a_uniqueness_proof_for_an_invalid_key() {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let mut accessor = accessor_with_table(t, sample_table());
+    accessor.add_table(u, owned_table([bigint("id", [1, 2, 3])]), 0);
+
+    assert!(matches!(
+        UniquenessProof::<InnerProductProof>::new(vec![], &accessor, &()),
+        Err(UniquenessProofError::EmptyKey)
+    ));
+    assert!(matches!(
+        UniquenessProof::<InnerProductProof>::new(
+            [key(t, &["id"], &accessor), key(u, &["id"], &accessor)].concat(),
+            &accessor,
+            &()
+        ),
+        Err(UniquenessProofError::MixedTables)
+    ));
+    assert!(matches!(
+        UniquenessProof::<InnerProductProof>::new(key(t, &["id", "id"], &accessor), &accessor, &()),
+        Err(UniquenessProofError::DuplicateKeyColumn(_))
+    ));
+    let reserved = ColumnRef::new(
+        t,
+        "__uniqueness_count__".parse().unwrap(),
+        ColumnType::BigInt,
+    );
+    assert!(matches!(
+        UniquenessProof::<InnerProductProof>::new(vec![reserved], &accessor, &()),
+        Err(UniquenessProofError::ReservedColumnName(_))
+    ));
+}
@@ -0,0 +1,309 @@
+use crate::{
+    base::{
+        database::{group_by_util::is_aggregation_supported, ColumnRef, ColumnType, TableRef},
+        math::decimal::{Precision, MAX_SUPPORTED_PRECISION},
+    },
+    sql::parse::{
+        type_check_aggregation, type_check_binary_operation, ProvableExprPlanBuilder,
+        MAX_EXISTS_KEYS, MAX_JOIN_KEYS,
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+use indexmap::IndexMap;
+use proof_of_sql_parser::{
+    intermediate_ast::{AggregationOperator, BinaryOperator, Expression, UnaryOperator},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    Identifier,
+};
+use serde::{Deserialize, Serialize};
+
+/// A kind of [`ColumnType`], ignoring parameters such as the precision of a decimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColumnTypeKind {
+    /// See [`ColumnType::Boolean`]
+    Boolean,
+    /// See [`ColumnType::SmallInt`]
+    SmallInt,
+    /// See [`ColumnType::Int`]
+    Int,
+    /// See [`ColumnType::BigInt`]
+    BigInt,
+    /// See [`ColumnType::Int128`]
+    Int128,
+    /// See [`ColumnType::VarChar`]
+    VarChar,
+    /// See [`ColumnType::Decimal75`]
+    Decimal75,
+    /// See [`ColumnType::TimestampTZ`]
+    TimestampTZ,
+}
+
+impl ColumnTypeKind {
+    /// All kinds of column types that a table can contain
+    pub const ALL: [ColumnTypeKind; 8] = [
+        ColumnTypeKind::Boolean,
+        ColumnTypeKind::SmallInt,
+        ColumnTypeKind::Int,
+        ColumnTypeKind::BigInt,
+        ColumnTypeKind::Int128,
+        ColumnTypeKind::VarChar,
+        ColumnTypeKind::Decimal75,
+        ColumnTypeKind::TimestampTZ,
+    ];
+
+    /// Returns the kind of a column type, or `None` if tables cannot contain the type.
+    pub fn of(column_type: ColumnType) -> Option<Self> {
+        match column_type {
+            ColumnType::Boolean => Some(ColumnTypeKind::Boolean),
+            ColumnType::SmallInt => Some(ColumnTypeKind::SmallInt),
+            ColumnType::Int => Some(ColumnTypeKind::Int),
+            ColumnType::BigInt => Some(ColumnTypeKind::BigInt),
+            ColumnType::Int128 => Some(ColumnTypeKind::Int128),
+            ColumnType::VarChar => Some(ColumnTypeKind::VarChar),
+            ColumnType::Decimal75(_, _) => Some(ColumnTypeKind::Decimal75),
+            ColumnType::TimestampTZ(_, _) => Some(ColumnTypeKind::TimestampTZ),
+            ColumnType::Scalar => None,
+        }
+    }
+
+    /// The column type that support for this kind is checked with.
+    ///
+    /// Decimals are checked with a small precision. See [`SqlLimits`] for the precisions at which
+    /// decimals stop being supported.
+    fn representative(self) -> ColumnType {
+        match self {
+            ColumnTypeKind::Boolean => ColumnType::Boolean,
+            ColumnTypeKind::SmallInt => ColumnType::SmallInt,
+            ColumnTypeKind::Int => ColumnType::Int,
+            ColumnTypeKind::BigInt => ColumnType::BigInt,
+            ColumnTypeKind::Int128 => ColumnType::Int128,
+            ColumnTypeKind::VarChar => ColumnType::VarChar,
+            ColumnTypeKind::Decimal75 => {
+                ColumnType::Decimal75(Precision::new(10).expect("10 is a valid precision"), 2)
+            }
+            ColumnTypeKind::TimestampTZ => {
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc)
+            }
+        }
+    }
+}
+
+/// The support for a binary operator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinaryOperatorCapability {
+    /// The operator
+    pub operator: BinaryOperator,
+    /// The kinds of the `(left, right)` operands that queries may apply the operator to
+    pub operand_types: Vec<(ColumnTypeKind, ColumnTypeKind)>,
+    /// The kinds of the `(left, right)` operands for which the operator is proven rather than
+    /// evaluated after verification. Only provable comparisons can be used in a `WHERE` clause.
+    pub provable_operand_types: Vec<(ColumnTypeKind, ColumnTypeKind)>,
+}
+
+/// The support for a unary operator. Unary operators are always proven.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnaryOperatorCapability {
+    /// The operator
+    pub operator: UnaryOperator,
+    /// The kinds of the operand that queries may apply the operator to
+    pub operand_types: Vec<ColumnTypeKind>,
+}
+
+/// The support for an aggregation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregationCapability {
+    /// The aggregation operator
+    pub operator: AggregationOperator,
+    /// The kinds of the argument that queries may aggregate
+    pub argument_types: Vec<ColumnTypeKind>,
+    /// The kinds of the argument for which the aggregation is proven in a `GROUP BY` query
+    /// rather than computed after verification
+    pub provable_argument_types: Vec<ColumnTypeKind>,
+}
+
+/// Limits on the values that queries may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlLimits {
+    /// The largest precision of a decimal
+    pub max_decimal_precision: u8,
+    /// The largest precision of a decimal that can be compared with `<=` or `>=`
+    pub max_comparable_decimal_precision: u8,
+    /// The largest number of distinct keys that the table of an `EXISTS` subquery may have. See
+    /// [`crate::sql::parse::ExistsQueryExpr`].
+    pub max_exists_keys: usize,
+    /// The largest number of distinct keys that the right table of a join may match. See
+    /// [`crate::sql::parse::JoinAggregateExpr`].
+    pub max_join_keys: usize,
+}
+
+/// A description of the SQL that Proof of SQL supports. See [`capabilities`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlCapabilities {
+    /// The kinds of column types that tables may contain
+    pub column_types: Vec<ColumnTypeKind>,
+    /// The support for each binary operator, including comparisons
+    pub binary_operators: Vec<BinaryOperatorCapability>,
+    /// The support for each unary operator
+    pub unary_operators: Vec<UnaryOperatorCapability>,
+    /// The support for each aggregation
+    pub aggregations: Vec<AggregationCapability>,
+    /// Limits on the values that queries may use
+    pub limits: SqlLimits,
+}
+
+/// The binary operators of the parser. This has to be extended when the parser adds one.
+const BINARY_OPERATORS: [BinaryOperator; 10] = [
+    BinaryOperator::Add,
+    BinaryOperator::Subtract,
+    BinaryOperator::Multiply,
+    BinaryOperator::Division,
+    BinaryOperator::Modulo,
+    BinaryOperator::And,
+    BinaryOperator::Or,
+    BinaryOperator::Equal,
+    BinaryOperator::GreaterThanOrEqual,
+    BinaryOperator::LessThanOrEqual,
+];
+
+/// The unary operators of the parser. This has to be extended when the parser adds one.
+const UNARY_OPERATORS: [UnaryOperator; 1] = [UnaryOperator::Not];
+
+/// The aggregation operators of the parser. This has to be extended when the parser adds one.
+const AGGREGATION_OPERATORS: [AggregationOperator; 5] = [
+    AggregationOperator::Max,
+    AggregationOperator::Min,
+    AggregationOperator::Sum,
+    AggregationOperator::Count,
+    AggregationOperator::First,
+];
+
+/// Returns a description of the SQL that Proof of SQL supports.
+///
+/// The description is derived from the same type checks that the query planner and the provable
+/// AST apply, so clients and gateways can validate queries against it up front instead of finding
+/// out that a query is unsupported when it is planned or proven.
+pub fn capabilities() -> SqlCapabilities {
+    SqlCapabilities {
+        column_types: ColumnTypeKind::ALL.to_vec(),
+        binary_operators: BINARY_OPERATORS
+            .into_iter()
+            .map(binary_operator_capability)
+            .collect(),
+        unary_operators: UNARY_OPERATORS
+            .into_iter()
+            .map(unary_operator_capability)
+            .collect(),
+        aggregations: AGGREGATION_OPERATORS
+            .into_iter()
+            .map(aggregation_capability)
+            .collect(),
+        limits: SqlLimits {
+            max_decimal_precision: MAX_SUPPORTED_PRECISION,
+            max_comparable_decimal_precision: max_comparable_decimal_precision(),
+            max_exists_keys: MAX_EXISTS_KEYS,
+            max_join_keys: MAX_JOIN_KEYS,
+        },
+    }
+}
+
+/// Whether the provable AST accepts the expression, where each identifier refers to a column of
+/// the representative type of its kind.
+fn is_provable(expr: &Expression, columns: &[(Identifier, ColumnTypeKind)]) -> bool {
+    let table_ref: TableRef = "capabilities.t".parse().expect("the table name is valid");
+    let column_mapping: IndexMap<Identifier, ColumnRef> = columns
+        .iter()
+        .map(|&(id, kind)| (id, ColumnRef::new(table_ref, id, kind.representative())))
+        .collect();
+    ProvableExprPlanBuilder::new(&column_mapping)
+        .build::<RistrettoPoint>(expr)
+        .is_ok()
+}
+
+fn identifier(name: &str) -> Identifier {
+    Identifier::try_new(name).expect("the identifier is valid")
+}
+
+fn binary_operator_capability(operator: BinaryOperator) -> BinaryOperatorCapability {
+    let (lhs, rhs) = (identifier("lhs"), identifier("rhs"));
+    let expr = Expression::Binary {
+        op: operator,
+        left: Box::new(Expression::Column(lhs)),
+        right: Box::new(Expression::Column(rhs)),
+    };
+    let operand_types: Vec<_> = ColumnTypeKind::ALL
+        .into_iter()
+        .flat_map(|left| {
+            ColumnTypeKind::ALL
+                .into_iter()
+                .map(move |right| (left, right))
+        })
+        .filter(|(left, right)| {
+            type_check_binary_operation(&left.representative(), &right.representative(), operator)
+        })
+        .collect();
+    let provable_operand_types = operand_types
+        .iter()
+        .copied()
+        .filter(|&(left, right)| is_provable(&expr, &[(lhs, left), (rhs, right)]))
+        .collect();
+    BinaryOperatorCapability {
+        operator,
+        operand_types,
+        provable_operand_types,
+    }
+}
+
+fn unary_operator_capability(operator: UnaryOperator) -> UnaryOperatorCapability {
+    let operand = identifier("operand");
+    let expr = Expression::Unary {
+        op: operator,
+        expr: Box::new(Expression::Column(operand)),
+    };
+    UnaryOperatorCapability {
+        operator,
+        operand_types: ColumnTypeKind::ALL
+            .into_iter()
+            .filter(|&kind| is_provable(&expr, &[(operand, kind)]))
+            .collect(),
+    }
+}
+
+fn aggregation_capability(operator: AggregationOperator) -> AggregationCapability {
+    let argument = identifier("argument");
+    let expr = Expression::Aggregation {
+        op: operator,
+        expr: Box::new(Expression::Column(argument)),
+    };
+    let provable_argument_types: Vec<_> = ColumnTypeKind::ALL
+        .into_iter()
+        .filter(|&kind| type_check_aggregation(operator, kind.representative()))
+        .filter(|&kind| is_provable(&expr, &[(argument, kind)]))
+        .collect();
+    // Aggregations that cannot be proven are computed after verification
+    let argument_types = ColumnTypeKind::ALL
+        .into_iter()
+        .filter(|&kind| type_check_aggregation(operator, kind.representative()))
+        .filter(|&kind| {
+            provable_argument_types.contains(&kind)
+                || is_aggregation_supported(operator, kind.representative())
+        })
+        .collect();
+    AggregationCapability {
+        operator,
+        argument_types,
+        provable_argument_types,
+    }
+}
+
+fn max_comparable_decimal_precision() -> u8 {
+    (1..=MAX_SUPPORTED_PRECISION)
+        .rev()
+        .find(|&precision| {
+            let decimal = ColumnType::Decimal75(
+                Precision::new(precision).expect("the precision is supported"),
+                0,
+            );
+            type_check_binary_operation(&decimal, &decimal, BinaryOperator::LessThanOrEqual)
+        })
+        .unwrap_or(0)
+}
@@ -0,0 +1,159 @@
+use super::{capabilities, ColumnTypeKind, SqlCapabilities, SqlLimits};
+use crate::base::{database::ColumnType, math::decimal::Precision};
+use proof_of_sql_parser::{
+    intermediate_ast::{AggregationOperator, BinaryOperator, UnaryOperator},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+const NUMERIC: [ColumnTypeKind; 5] = [
+    ColumnTypeKind::SmallInt,
+    ColumnTypeKind::Int,
+    ColumnTypeKind::BigInt,
+    ColumnTypeKind::Int128,
+    ColumnTypeKind::Decimal75,
+];
+
+#[test]
+fn we_can_get_the_kind_of_column_types() {
+    assert_eq!(
+        ColumnTypeKind::of(ColumnType::Decimal75(Precision::new(40).unwrap(), -3)),
+        Some(ColumnTypeKind::Decimal75)
+    );
+    assert_eq!(
+        ColumnTypeKind::of(ColumnType::TimestampTZ(
+            PoSQLTimeUnit::Nanosecond,
+            PoSQLTimeZone::FixedOffset(3600)
+        )),
+        Some(ColumnTypeKind::TimestampTZ)
+    );
+    assert_eq!(
+        ColumnTypeKind::of(ColumnType::VarChar),
+        Some(ColumnTypeKind::VarChar)
+    );
+    assert_eq!(ColumnTypeKind::of(ColumnType::Scalar), None);
+    assert_eq!(capabilities().column_types, ColumnTypeKind::ALL);
+}
+
+#[test]
+fn we_can_list_the_supported_binary_operators() {
+    let capabilities = capabilities();
+    assert_eq!(capabilities.binary_operators.len(), 10);
+    let operator = |op| {
+        capabilities
+            .binary_operators
+            .iter()
+            .find(|capability| capability.operator == op)
+            .unwrap()
+    };
+
+    let and = operator(BinaryOperator::And);
+    let boolean_operands = vec![(ColumnTypeKind::Boolean, ColumnTypeKind::Boolean)];
+    assert_eq!(and.operand_types, boolean_operands);
+    assert_eq!(and.provable_operand_types, boolean_operands);
+
+    let add = operator(BinaryOperator::Add);
+    assert_eq!(add.operand_types.len(), NUMERIC.len() * NUMERIC.len());
+    assert_eq!(add.provable_operand_types, add.operand_types);
+    assert!(add
+        .operand_types
+        .contains(&(ColumnTypeKind::BigInt, ColumnTypeKind::Decimal75)));
+    assert!(!add
+        .operand_types
+        .contains(&(ColumnTypeKind::VarChar, ColumnTypeKind::BigInt)));
+
+    let equal = operator(BinaryOperator::Equal);
+    assert!(equal
+        .provable_operand_types
+        .contains(&(ColumnTypeKind::VarChar, ColumnTypeKind::VarChar)));
+    assert!(!equal
+        .operand_types
+        .contains(&(ColumnTypeKind::VarChar, ColumnTypeKind::BigInt)));
+
+    let greater_than_or_equal = operator(BinaryOperator::GreaterThanOrEqual);
+    assert!(greater_than_or_equal
+        .provable_operand_types
+        .contains(&(ColumnTypeKind::TimestampTZ, ColumnTypeKind::TimestampTZ)));
+    assert!(!greater_than_or_equal
+        .operand_types
+        .contains(&(ColumnTypeKind::VarChar, ColumnTypeKind::VarChar)));
+
+    // Division is only evaluated after verification
+    let division = operator(BinaryOperator::Division);
+    assert!(division
+        .operand_types
+        .contains(&(ColumnTypeKind::Decimal75, ColumnTypeKind::Int)));
+    assert!(division.provable_operand_types.is_empty());
+
+    // Only the remainders of integers by a constant are provable
+    let modulo = operator(BinaryOperator::Modulo);
+    assert!(modulo
+        .operand_types
+        .contains(&(ColumnTypeKind::Int128, ColumnTypeKind::SmallInt)));
+    assert!(!modulo
+        .operand_types
+        .contains(&(ColumnTypeKind::Decimal75, ColumnTypeKind::BigInt)));
+    assert!(modulo.provable_operand_types.is_empty());
+}
+
+#[test]
+fn we_can_list_the_supported_unary_operators() {
+    let unary_operators = capabilities().unary_operators;
+    assert_eq!(unary_operators.len(), 1);
+    assert_eq!(unary_operators[0].operator, UnaryOperator::Not);
+    assert_eq!(
+        unary_operators[0].operand_types,
+        vec![ColumnTypeKind::Boolean]
+    );
+}
+
+#[test]
+fn we_can_list_the_supported_aggregations() {
+    let capabilities = capabilities();
+    let aggregation = |op| {
+        capabilities
+            .aggregations
+            .iter()
+            .find(|capability| capability.operator == op)
+            .unwrap()
+    };
+
+    let sum = aggregation(AggregationOperator::Sum);
+    assert_eq!(sum.argument_types, NUMERIC);
+    assert_eq!(sum.provable_argument_types, NUMERIC);
+
+    let count = aggregation(AggregationOperator::Count);
+    assert_eq!(count.argument_types, ColumnTypeKind::ALL);
+    assert_eq!(count.provable_argument_types, ColumnTypeKind::ALL);
+
+    // MAX and MIN are computed after verification, and not for strings
+    for op in [AggregationOperator::Max, AggregationOperator::Min] {
+        let capability = aggregation(op);
+        assert_eq!(
+            capability.argument_types.len(),
+            ColumnTypeKind::ALL.len() - 1
+        );
+        assert!(!capability.argument_types.contains(&ColumnTypeKind::VarChar));
+        assert!(capability.provable_argument_types.is_empty());
+    }
+
+    let first = aggregation(AggregationOperator::First);
+    assert!(first.argument_types.is_empty());
+    assert!(first.provable_argument_types.is_empty());
+}
+
+#[test]
+fn we_can_list_the_limits_and_serialize_the_capabilities() {
+    let capabilities = capabilities();
+    assert_eq!(
+        capabilities.limits,
+        SqlLimits {
+            max_decimal_precision: 75,
+            max_comparable_decimal_precision: 38,
+            max_exists_keys: 64,
+            max_join_keys: 64,
+        }
+    );
+    let serialized = serde_json::to_string(&capabilities).unwrap();
+    let deserialized: SqlCapabilities = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, capabilities);
+}
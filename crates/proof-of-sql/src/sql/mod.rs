@@ -1,5 +1,13 @@
 //! This module contains the main logic for Proof of SQL.
 pub mod ast;
+mod capabilities;
+pub use capabilities::{
+    capabilities, AggregationCapability, BinaryOperatorCapability, ColumnTypeKind, SqlCapabilities,
+    SqlLimits, UnaryOperatorCapability,
+};
+#[cfg(test)]
+mod capabilities_test;
+pub mod oracle;
 pub mod parse;
 pub mod postprocessing;
 pub mod proof;
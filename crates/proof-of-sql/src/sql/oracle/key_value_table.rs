@@ -0,0 +1,155 @@
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{
+            ColumnRef, CommitmentAccessor, DataAccessor, LiteralValue, OwnedColumn, SchemaAccessor,
+            TableRef,
+        },
+        scalar::Scalar,
+    },
+    sql::{
+        ast::{AliasedProvableExprPlan, DenseFilterExpr, ProofPlan, ProvableExprPlan, TableExpr},
+        parse::{ConversionError, ConversionResult},
+        proof::{QueryError, VerifiableQueryResult},
+    },
+};
+use proof_of_sql_parser::Identifier;
+use thiserror::Error;
+
+/// Errors that can occur when verifying a value of a [`KeyValueTable`].
+#[derive(Error, Debug)]
+pub enum KeyValueError {
+    /// The key does not have the type of the key column.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    /// More than one row of the table has the key.
+    #[error("the key is not unique in the table")]
+    DuplicateKey,
+    /// The proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+}
+
+/// A table that is used as a mapping from the values of one column to the values of another.
+///
+/// This packages the usual "verified lookup" of an oracle: the prover proves
+/// ```ignore
+///     SELECT <value> FROM <table> WHERE <key> = <literal>
+/// ```
+/// with [`KeyValueTable::prove_value`], and the verifier checks the proof and gets the value with
+/// [`KeyValueTable::verify_value`]. Since the query only depends on the table and the key, the
+/// verifier does not have to plan any SQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyValueTable {
+    key: ColumnRef,
+    value: ColumnRef,
+}
+
+impl KeyValueTable {
+    /// Register the columns of a table as a mapping from `key` to `value`.
+    pub fn try_new(
+        table_ref: TableRef,
+        key: Identifier,
+        value: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let column_ref = |column_id: Identifier| {
+            let column_type = schema_accessor
+                .lookup_column(table_ref, column_id)
+                .ok_or_else(|| {
+                    ConversionError::MissingColumn(
+                        Box::new(column_id),
+                        Box::new(table_ref.resource_id()),
+                    )
+                })?;
+            Ok(ColumnRef::new(table_ref, column_id, column_type))
+        };
+        Ok(Self {
+            key: column_ref(key)?,
+            value: column_ref(value)?,
+        })
+    }
+
+    /// Returns the key column.
+    pub fn key_column(&self) -> ColumnRef {
+        self.key
+    }
+
+    /// Returns the value column.
+    pub fn value_column(&self) -> ColumnRef {
+        self.value
+    }
+
+    /// Prove the value of the key.
+    ///
+    /// The proof also proves the absence of the key, in which case there is no value.
+    pub fn prove_value<CP: CommitmentEvaluationProof>(
+        &self,
+        key: &LiteralValue<CP::Scalar>,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> ConversionResult<VerifiableQueryResult<CP>> {
+        let plan = self.lookup_plan::<CP::Commitment>(key)?;
+        Ok(VerifiableQueryResult::new(&plan, accessor, setup))
+    }
+
+    /// Verify the proof of the value of the key.
+    ///
+    /// Returns the value, or `None` if the table does not have the key.
+    pub fn verify_value<CP: CommitmentEvaluationProof>(
+        &self,
+        key: &LiteralValue<CP::Scalar>,
+        proof: &VerifiableQueryResult<CP>,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Option<LiteralValue<CP::Scalar>>, KeyValueError> {
+        let plan = self.lookup_plan::<CP::Commitment>(key)?;
+        let table = proof.verify(&plan, accessor, setup)?.table;
+        let values = &table.inner_table()[&self.value.column_id()];
+        match values.len() {
+            0 => Ok(None),
+            1 => Ok(Some(literal_at(values, 0))),
+            _ => Err(KeyValueError::DuplicateKey),
+        }
+    }
+
+    /// Plan `SELECT <value> FROM <table> WHERE <key> = <literal>`.
+    fn lookup_plan<C: Commitment>(
+        &self,
+        key: &LiteralValue<C::Scalar>,
+    ) -> ConversionResult<ProofPlan<C>> {
+        let where_clause = ProvableExprPlan::try_new_equals(
+            ProvableExprPlan::new_column(self.key),
+            ProvableExprPlan::new_literal(key.clone()),
+        )?;
+        Ok(ProofPlan::DenseFilter(DenseFilterExpr::new(
+            vec![AliasedProvableExprPlan {
+                expr: ProvableExprPlan::new_column(self.value),
+                alias: self.value.column_id(),
+            }],
+            TableExpr {
+                table_ref: self.key.table_ref(),
+            },
+            where_clause,
+        )))
+    }
+}
+
+/// Returns the element of the column at `index` as a literal.
+fn literal_at<S: Scalar>(column: &OwnedColumn<S>, index: usize) -> LiteralValue<S> {
+    match column {
+        OwnedColumn::Boolean(col) => LiteralValue::Boolean(col[index]),
+        OwnedColumn::SmallInt(col) => LiteralValue::SmallInt(col[index]),
+        OwnedColumn::Int(col) => LiteralValue::Int(col[index]),
+        OwnedColumn::BigInt(col) => LiteralValue::BigInt(col[index]),
+        OwnedColumn::Int128(col) => LiteralValue::Int128(col[index]),
+        OwnedColumn::VarChar(col) => {
+            LiteralValue::VarChar((col[index].clone(), col[index].as_str().into()))
+        }
+        OwnedColumn::Decimal75(precision, scale, col) => {
+            LiteralValue::Decimal75(*precision, *scale, col[index])
+        }
+        OwnedColumn::Scalar(col) => LiteralValue::Scalar(col[index]),
+        OwnedColumn::TimestampTZ(tu, tz, col) => LiteralValue::TimeStampTZ(*tu, *tz, col[index]),
+    }
+}
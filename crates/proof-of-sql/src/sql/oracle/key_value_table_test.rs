@@ -0,0 +1,144 @@
+use super::{KeyValueError, KeyValueTable};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, ColumnType, LiteralValue, OwnedTableTestAccessor, TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::parse::ConversionError,
+};
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        "sxt.prices".parse().unwrap(),
+        owned_table([
+            varchar("symbol", ["BTC", "ETH", "SOL", "ETH"]),
+            bigint("id", [1, 2, 3, 4]),
+            bigint("price", [60_000, 3_000, 150, 3_100]),
+        ]),
+        0,
+        (),
+    )
+}
+
+fn varchar_key(key: &str) -> LiteralValue<Curve25519Scalar> {
+    LiteralValue::VarChar((key.to_string(), key.into()))
+}
+
+#[test]
+fn we_can_prove_and_verify_the_value_of_a_key() {
+    let accessor = accessor();
+    let table = KeyValueTable::try_new(
+        "sxt.prices".parse().unwrap(),
+        ident("id"),
+        ident("price"),
+        &accessor,
+    )
+    .unwrap();
+    assert_eq!(table.key_column().column_type(), &ColumnType::BigInt);
+
+    let key = LiteralValue::BigInt(3);
+    let proof = table
+        .prove_value::<InnerProductProof>(&key, &accessor, &())
+        .unwrap();
+    assert_eq!(
+        table.verify_value(&key, &proof, &accessor, &()).unwrap(),
+        Some(LiteralValue::BigInt(150))
+    );
+
+    let key = LiteralValue::BigInt(5);
+    let proof = table
+        .prove_value::<InnerProductProof>(&key, &accessor, &())
+        .unwrap();
+    assert_eq!(
+        table.verify_value(&key, &proof, &accessor, &()).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn we_can_look_up_varchar_values() {
+    let accessor = accessor();
+    let table = KeyValueTable::try_new(
+        "sxt.prices".parse().unwrap(),
+        ident("id"),
+        ident("symbol"),
+        &accessor,
+    )
+    .unwrap();
+    let key = LiteralValue::BigInt(1);
+    let proof = table
+        .prove_value::<InnerProductProof>(&key, &accessor, &())
+        .unwrap();
+    assert_eq!(
+        table.verify_value(&key, &proof, &accessor, &()).unwrap(),
+        Some(varchar_key("BTC"))
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_another_key() {
+    let accessor = accessor();
+    let table = KeyValueTable::try_new(
+        "sxt.prices".parse().unwrap(),
+        ident("id"),
+        ident("price"),
+        &accessor,
+    )
+    .unwrap();
+    let proof = table
+        .prove_value::<InnerProductProof>(&LiteralValue::BigInt(1), &accessor, &())
+        .unwrap();
+    assert!(matches!(
+        table.verify_value(&LiteralValue::BigInt(2), &proof, &accessor, &()),
+        Err(KeyValueError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_look_up_a_key_that_is_not_unique() {
+    let accessor = accessor();
+    let table = KeyValueTable::try_new(
+        "sxt.prices".parse().unwrap(),
+        ident("symbol"),
+        ident("price"),
+        &accessor,
+    )
+    .unwrap();
+    let key = varchar_key("ETH");
+    let proof = table
+        .prove_value::<InnerProductProof>(&key, &accessor, &())
+        .unwrap();
+    assert!(matches!(
+        table.verify_value(&key, &proof, &accessor, &()),
+        Err(KeyValueError::DuplicateKey)
+    ));
+}
+
+#[test]
+fn we_cannot_look_up_missing_columns_or_keys_of_the_wrong_type() {
+    let accessor = accessor();
+    assert!(matches!(
+        KeyValueTable::try_new(
+            "sxt.prices".parse().unwrap(),
+            ident("id"),
+            ident("volume"),
+            &accessor,
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    let table = KeyValueTable::try_new(
+        "sxt.prices".parse().unwrap(),
+        ident("id"),
+        ident("price"),
+        &accessor,
+    )
+    .unwrap();
+    assert!(matches!(
+        table.prove_value::<InnerProductProof>(&varchar_key("BTC"), &accessor, &()),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+}
@@ -0,0 +1,5 @@
+//! Thin APIs that package the SQL machinery for common oracle use cases.
+mod key_value_table;
+pub use key_value_table::{KeyValueError, KeyValueTable};
+#[cfg(all(test, feature = "blitzar"))]
+mod key_value_table_test;
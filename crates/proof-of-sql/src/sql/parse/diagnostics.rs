@@ -0,0 +1,171 @@
+use super::ConversionError;
+use crate::base::database::{SchemaAccessor, TableRef};
+use proof_of_sql_parser::{Identifier, SelectStatement, SyntaxError};
+use std::{fmt, ops::Range};
+
+/// A detailed report of why a query could not be parsed or planned, pointing at where in the
+/// query the problem is and, when possible, how to fix it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// What went wrong
+    pub message: String,
+    /// The byte range of the query the problem is in, if it could be located
+    pub span: Option<Range<usize>>,
+    /// The tokens that would have been accepted at `span`, if the query failed to parse
+    pub expected: Vec<String>,
+    /// A likely replacement for the text at `span`, e.g. the name of a similar column
+    pub suggestion: Option<String>,
+    /// The underlying error, if the query parsed but could not be planned
+    pub error: Option<ConversionError>,
+}
+
+impl Diagnostics {
+    /// Diagnose a query that failed to parse.
+    pub(super) fn from_syntax_error(query: &str, error: SyntaxError) -> Self {
+        let found = query[error.span.clone()].to_lowercase();
+        let suggestion =
+            closest(&found, error.expected.iter().map(String::as_str)).map(str::to_string);
+        Self {
+            message: error.message,
+            span: Some(error.span),
+            expected: error.expected,
+            suggestion,
+            error: None,
+        }
+    }
+
+    /// Diagnose a query that parsed but could not be converted into a `QueryExpr`.
+    pub(super) fn from_conversion_error(
+        query: &str,
+        ast: &SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        error: ConversionError,
+    ) -> Self {
+        let (span, suggestion) = match &error {
+            ConversionError::MissingColumn(column, _)
+            | ConversionError::MissingColumnWithoutTable(column) => {
+                let columns = ast
+                    .get_table_references(default_schema)
+                    .into_iter()
+                    .flat_map(|table| schema_accessor.lookup_schema(TableRef::new(table)))
+                    .map(|(column, _)| column);
+                (
+                    locate(query, column),
+                    closest(column.as_str(), columns).map(|column| column.to_string()),
+                )
+            }
+            ConversionError::InvalidOrderBy(alias) => (
+                Identifier::try_new(alias)
+                    .ok()
+                    .and_then(|alias| locate(query, &alias)),
+                None,
+            ),
+            _ => (None, None),
+        };
+        Self {
+            message: error.to_string(),
+            span,
+            expected: Vec::new(),
+            suggestion,
+            error: Some(error),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(span) = &self.span {
+            write!(f, " at byte {}", span.start)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean `{suggestion}`?")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostics {}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The candidate closest to `name`, provided it is close enough to be a likely typo.
+fn closest<T: AsRef<str>>(name: &str, candidates: impl IntoIterator<Item = T>) -> Option<T> {
+    let threshold = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate.as_ref()), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The byte range of the first whole-word occurrence of the identifier in the query.
+///
+/// Identifiers that are not quoted are matched without regard to case.
+fn locate(query: &str, identifier: &Identifier) -> Option<Range<usize>> {
+    let needle = identifier.to_string();
+    let haystack = if identifier.needs_quotes() {
+        query.to_string()
+    } else {
+        query.to_ascii_lowercase()
+    };
+    let is_word_byte = |byte: &u8| byte.is_ascii_alphanumeric() || *byte == b'_';
+    let bytes = haystack.as_bytes();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, _)| start..start + needle.len())
+        .find(|span| {
+            !(span.start > 0 && is_word_byte(&bytes[span.start - 1]))
+                && !bytes.get(span.end).is_some_and(is_word_byte)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_compute_edit_distances() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("value", "value"), 0);
+        assert_eq!(edit_distance("valu", "value"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn we_only_suggest_close_candidates() {
+        assert_eq!(closest("valu", ["value", "values", "a"]), Some("value"));
+        assert_eq!(closest("frm", ["where", "from"]), Some("from"));
+        assert_eq!(closest("x", ["value", "where"]), None);
+    }
+
+    #[test]
+    fn we_can_locate_identifiers_in_a_query() {
+        let query = "SELECT avalue, VALUE FROM tab";
+        let value = Identifier::try_new("value").unwrap();
+        assert_eq!(locate(query, &value), Some(15..20));
+        let quoted = Identifier::try_new_case_sensitive("Value").unwrap();
+        assert_eq!(
+            locate(r#"SELECT value, "Value" FROM tab"#, &quoted),
+            Some(14..21)
+        );
+        assert_eq!(locate(query, &quoted), None);
+    }
+}
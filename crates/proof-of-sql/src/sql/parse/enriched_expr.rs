@@ -1,7 +1,7 @@
 use super::ProvableExprPlanBuilder;
 use crate::{
     base::{commitment::Commitment, database::ColumnRef},
-    sql::ast::ProvableExprPlan,
+    sql::ast::{OverflowPolicy, ProvableExprPlan},
 };
 use indexmap::IndexMap;
 use proof_of_sql_parser::{
@@ -26,14 +26,17 @@ impl<C: Commitment> EnrichedExpr<C> {
     /// If the expression is not provable, the `provable_expr_plan` will be `None`.
     /// Otherwise the `provable_expr_plan` will contain the provable expression plan
     /// and the `residue_expression` will contain the remaining expression.
+    /// Arithmetic in the provable expression handles overflows according to `overflow_policy`.
     pub fn new(
         expression: AliasedResultExpr,
         column_mapping: IndexMap<Identifier, ColumnRef>,
+        overflow_policy: OverflowPolicy,
     ) -> Self {
         // TODO: Using new_agg (ironically) disables aggregations in `QueryExpr` for now.
         // Re-enable aggregations when we add `GroupByExpr` generalizations.
-        let res_provable_expr_plan =
-            ProvableExprPlanBuilder::new_agg(&column_mapping).build(&expression.expr);
+        let res_provable_expr_plan = ProvableExprPlanBuilder::new_agg(&column_mapping)
+            .with_overflow_policy(overflow_policy)
+            .build(&expression.expr);
         match res_provable_expr_plan {
             Ok(provable_expr_plan) => {
                 let alias = expression.alias;
@@ -45,10 +48,15 @@ impl<C: Commitment> EnrichedExpr<C> {
                     provable_expr_plan: Some(provable_expr_plan),
                 }
             }
-            Err(_) => Self {
-                residue_expression: expression,
-                provable_expr_plan: None,
-            },
+            Err(_) => Self::new_unprovable(expression),
+        }
+    }
+
+    /// Create a new `EnrichedExpr` that is evaluated entirely outside of the proof.
+    pub fn new_unprovable(expression: AliasedResultExpr) -> Self {
+        Self {
+            residue_expression: expression,
+            provable_expr_plan: None,
         }
     }
 
@@ -61,7 +69,6 @@ impl<C: Commitment> EnrichedExpr<C> {
     }
 
     /// Is the `EnrichedExpr` provable
-    #[allow(dead_code)]
     pub fn is_provable(&self) -> bool {
         self.provable_expr_plan.is_some()
     }
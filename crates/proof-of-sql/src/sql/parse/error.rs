@@ -3,8 +3,8 @@ use crate::base::{
     math::decimal::DecimalError,
 };
 use proof_of_sql_parser::{
-    intermediate_decimal::IntermediateDecimalError, posql_time::PoSQLTimestampError, Identifier,
-    ResourceId,
+    intermediate_ast::ContextVariable, intermediate_decimal::IntermediateDecimalError,
+    posql_time::PoSQLTimestampError, Identifier, ResourceId,
 };
 use thiserror::Error;
 
@@ -52,6 +52,43 @@ pub enum ConversionError {
     /// GROUP BY clause references a non-existent column
     InvalidGroupByColumnRef(String),
 
+    #[error("Literal {literal} is out of range for column type '{column_type}'")]
+    /// A literal compared against a column cannot be represented by the column's type
+    LiteralOutOfRange {
+        /// The literal as written in the query
+        literal: String,
+        /// The type of the column the literal is compared against
+        column_type: ColumnType,
+    },
+
+    #[error("Query uses {0} but it is not bound in the evaluation context")]
+    /// The query uses a context variable that the evaluation context has no value for
+    UnboundContextVariable(ContextVariable),
+
+    #[error("EXISTS subqueries are only supported in the WHERE clause of an ExistsQueryExpr")]
+    /// The query uses an `EXISTS` subquery that was not bound to the keys of its table
+    UnboundExists,
+
+    #[error("AS OF queries are only supported through a SnapshotQueryExpr")]
+    /// The query reads a snapshot of its table, which has to be resolved against a registry
+    UnboundSnapshot,
+
+    #[error("Function '{0}' is not registered")]
+    /// The query calls a function that is not in the
+    /// [`FunctionRegistry`](crate::sql::parse::FunctionRegistry) it is planned with
+    UnregisteredFunction(Box<Identifier>),
+
+    #[error("Function '{name}' takes {expected} arguments but was called with {actual}")]
+    /// A function was called with the wrong number of arguments
+    FunctionArgumentCount {
+        /// The name of the function
+        name: Box<Identifier>,
+        /// The number of arguments that the function takes
+        expected: usize,
+        /// The number of arguments that the function was called with
+        actual: usize,
+    },
+
     #[error("Invalid expression: {0}")]
     /// General error for invalid expressions
     InvalidExpression(String),
@@ -72,6 +109,13 @@ pub enum ConversionError {
     #[error(transparent)]
     ColumnOperationError(#[from] ColumnOperationError),
 
+    /// Errors in lowering a statement parsed by `sqlparser` into the intermediate AST
+    #[cfg(feature = "sqlparser")]
+    #[error(transparent)]
+    SqlParserConversionError(
+        #[from] proof_of_sql_parser::sqlparser_adapter::SqlParserConversionError,
+    ),
+
     #[error("Query not provable because: {0}")]
     /// Query requires unprovable feature
     Unprovable(String),
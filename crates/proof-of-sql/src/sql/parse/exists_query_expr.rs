@@ -0,0 +1,412 @@
+use super::{ConversionError, ConversionResult, QueryExpr};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{
+            lookup_multiplicity_column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+            LiteralValue, OwnedColumn, SchemaAccessor, TableRef,
+        },
+        scalar::Scalar,
+    },
+    sql::{
+        ast::{AliasedProvableExprPlan, GroupByExpr, ProofPlan, ProvableExprPlan, TableExpr},
+        proof::{ProvableQueryResultLimits, QueryData, QueryError, VerifiableQueryResult},
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression, Literal, SetExpression, TableExpression},
+    Identifier, ResourceId, SelectStatement,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// The largest number of distinct keys that the table of an `EXISTS` subquery may have.
+pub const MAX_EXISTS_KEYS: usize = 64;
+
+/// The alias of the per-key row count in the proofs of the subquery keys.
+const COUNT_ALIAS: &str = "__exists_count__";
+
+/// Errors that can occur when proving or verifying an [`ExistsQueryProof`].
+#[derive(Error, Debug)]
+pub enum ExistsQueryError {
+    /// The query could not be planned once its subqueries were bound to their keys.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    /// The key column of a subquery has more distinct values than [`MAX_EXISTS_KEYS`].
+    #[error(
+        "column '{0}' of an EXISTS subquery has more than {} distinct values",
+        MAX_EXISTS_KEYS
+    )]
+    TooManyKeys(Identifier),
+    /// The proof does not prove the keys of every subquery of the query.
+    #[error("the proof has keys for {proven} subqueries but the query has {expected}")]
+    SubqueryCountMismatch {
+        /// The number of subqueries the proof has keys for
+        proven: usize,
+        /// The number of subqueries of the query
+        expected: usize,
+    },
+    /// One of the underlying proofs failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+}
+
+/// A query whose `WHERE` clause uses `EXISTS` subqueries of the bounded form
+/// ```ignore
+///     EXISTS (SELECT 1 FROM <other> WHERE <other>.<key> = <table>.<column>)
+/// ```
+///
+/// A proof only covers a single table, so a subquery is not proven together with the query.
+/// Instead, the keys of the subquery's table are proven first with
+/// ```ignore
+///     SELECT <key>, COUNT(*) FROM <other> GROUP BY <key>
+/// ```
+/// and the verifier plans the query with every subquery replaced by
+/// `<column> = <key1> OR ... OR <column> = <keyN>`, using the keys it verified. `NOT EXISTS`
+/// is the negation of that, so anti-joins are proven in the same way.
+///
+/// This requires the subquery's table to have at most [`MAX_EXISTS_KEYS`] distinct keys. The key
+/// and the column must have the same integer or `VARCHAR` type, and the subquery's table cannot
+/// be retractable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExistsQueryExpr {
+    ast: SelectStatement,
+    default_schema: Identifier,
+    key_columns: Vec<ColumnRef>,
+}
+
+impl ExistsQueryExpr {
+    /// Resolve the `EXISTS` subqueries of an intermediate AST `SelectStatement`.
+    pub fn try_new(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let SetExpression::Query {
+            from, where_expr, ..
+        } = ast.expr.as_ref();
+        let Some(TableExpression::Named { table, schema }) = from.first().map(Box::as_ref) else {
+            return Err(ConversionError::InvalidExpression(
+                "a query must have a table".to_string(),
+            ));
+        };
+        let outer_table_ref = table_ref(*table, *schema, default_schema);
+        let mut subqueries = Vec::new();
+        if let Some(where_expr) = where_expr {
+            collect_exists(where_expr, &mut subqueries);
+        }
+        let key_columns = subqueries
+            .into_iter()
+            .map(|subquery| {
+                resolve_subquery(
+                    subquery,
+                    *table,
+                    outer_table_ref,
+                    default_schema,
+                    schema_accessor,
+                )
+            })
+            .collect::<ConversionResult<_>>()?;
+        Ok(Self {
+            ast,
+            default_schema,
+            key_columns,
+        })
+    }
+
+    /// Returns the key columns of the subqueries, in the order they appear in the query.
+    pub fn key_columns(&self) -> &[ColumnRef] {
+        &self.key_columns
+    }
+
+    /// Plan the query with every subquery replaced by a comparison against its keys.
+    fn bind_keys<C: Commitment>(
+        &self,
+        keys: &[Vec<ExistsKey>],
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<QueryExpr<C>> {
+        let mut ast = self.ast.clone();
+        let SetExpression::Query { where_expr, .. } = ast.expr.as_mut();
+        if let Some(where_expr) = where_expr {
+            bind_exists(where_expr, &mut keys.iter());
+        }
+        QueryExpr::try_new(ast, self.default_schema, schema_accessor)
+    }
+}
+
+/// A proof of an [`ExistsQueryExpr`], which consists of a proof of the keys of each subquery and
+/// a proof of the query with the subqueries bound to those keys.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExistsQueryProof<CP: CommitmentEvaluationProof> {
+    keys: Vec<VerifiableQueryResult<CP>>,
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> ExistsQueryProof<CP> {
+    /// Prove the query.
+    pub fn new(
+        expr: &ExistsQueryExpr,
+        accessor: &(impl DataAccessor<CP::Scalar> + SchemaAccessor),
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, ExistsQueryError> {
+        let keys = expr
+            .key_columns
+            .iter()
+            .map(|&key_column| {
+                let column = OwnedColumn::from(&accessor.get_column(key_column));
+                canonical_keys(&column, key_column)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let key_results = expr
+            .key_columns
+            .iter()
+            .map(|&key_column| {
+                VerifiableQueryResult::new(&key_plan::<CP::Commitment>(key_column), accessor, setup)
+            })
+            .collect();
+        let query = expr.bind_keys::<CP::Commitment>(&keys, accessor)?;
+        Ok(Self {
+            keys: key_results,
+            result: VerifiableQueryResult::new(query.proof_expr(), accessor, setup),
+        })
+    }
+
+    /// Verify the query.
+    ///
+    /// Returns the query with its subqueries bound to the verified keys, along with its verified
+    /// result. As with a [`QueryExpr`], the result still has to be transformed with
+    /// [`QueryExpr::result`].
+    pub fn verify(
+        &self,
+        expr: &ExistsQueryExpr,
+        accessor: &(impl CommitmentAccessor<CP::Commitment> + SchemaAccessor),
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<(QueryExpr<CP::Commitment>, QueryData<CP::Scalar>), ExistsQueryError> {
+        if self.keys.len() != expr.key_columns.len() {
+            return Err(ExistsQueryError::SubqueryCountMismatch {
+                proven: self.keys.len(),
+                expected: expr.key_columns.len(),
+            });
+        }
+        let limits = ProvableQueryResultLimits::new().with_max_rows(MAX_EXISTS_KEYS);
+        let keys = self
+            .keys
+            .iter()
+            .zip(&expr.key_columns)
+            .map(|(key_result, &key_column)| {
+                let plan = key_plan::<CP::Commitment>(key_column);
+                let table = key_result
+                    .verify_with_limits(&plan, accessor, setup, &limits)?
+                    .table;
+                canonical_keys(&table.inner_table()[&key_column.column_id()], key_column)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let query = expr.bind_keys::<CP::Commitment>(&keys, accessor)?;
+        let data = self.result.verify(query.proof_expr(), accessor, setup)?;
+        Ok((query, data))
+    }
+}
+
+/// A key of a subquery, ordered so that the prover and the verifier bind the same keys in the
+/// same order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum ExistsKey {
+    Integer(i128),
+    VarChar(String),
+}
+
+impl ExistsKey {
+    fn to_literal(&self) -> Literal {
+        match self {
+            ExistsKey::Integer(value) => {
+                i64::try_from(*value).map_or(Literal::Int128(*value), Literal::BigInt)
+            }
+            ExistsKey::VarChar(value) => Literal::VarChar(value.clone()),
+        }
+    }
+}
+
+/// Returns the distinct values of a key column in ascending order.
+fn canonical_keys<S: Scalar>(
+    column: &OwnedColumn<S>,
+    key_column: ColumnRef,
+) -> Result<Vec<ExistsKey>, ExistsQueryError> {
+    let keys: BTreeSet<_> = match column {
+        OwnedColumn::SmallInt(values) => values
+            .iter()
+            .map(|&value| ExistsKey::Integer(value.into()))
+            .collect(),
+        OwnedColumn::Int(values) => values
+            .iter()
+            .map(|&value| ExistsKey::Integer(value.into()))
+            .collect(),
+        OwnedColumn::BigInt(values) => values
+            .iter()
+            .map(|&value| ExistsKey::Integer(value.into()))
+            .collect(),
+        OwnedColumn::Int128(values) => values.iter().copied().map(ExistsKey::Integer).collect(),
+        OwnedColumn::VarChar(values) => values.iter().cloned().map(ExistsKey::VarChar).collect(),
+        _ => unreachable!("key columns are checked when the query is resolved"),
+    };
+    if keys.len() > MAX_EXISTS_KEYS {
+        return Err(ExistsQueryError::TooManyKeys(key_column.column_id()));
+    }
+    Ok(keys.into_iter().collect())
+}
+
+fn count_alias() -> Identifier {
+    COUNT_ALIAS
+        .parse()
+        .expect("the count alias should be a valid identifier")
+}
+
+/// Build the group by plan whose result has one row per distinct key.
+fn key_plan<C: Commitment>(key_column: ColumnRef) -> ProofPlan<C> {
+    ProofPlan::GroupBy(GroupByExpr::new(
+        vec![AliasedProvableExprPlan {
+            expr: ProvableExprPlan::new_column(key_column),
+            alias: key_column.column_id(),
+        }],
+        vec![],
+        count_alias(),
+        TableExpr {
+            table_ref: key_column.table_ref(),
+        },
+        ProvableExprPlan::new_literal(LiteralValue::Boolean(true)),
+    ))
+}
+
+fn table_ref(
+    table: Identifier,
+    schema: Option<Identifier>,
+    default_schema: Identifier,
+) -> TableRef {
+    TableRef::new(ResourceId::new(schema.unwrap_or(default_schema), table))
+}
+
+/// Collect the `EXISTS` subqueries of an expression, from left to right.
+fn collect_exists<'a>(expr: &'a Expression, subqueries: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::Exists { .. } => subqueries.push(expr),
+        Expression::Binary { left, right, .. } => {
+            collect_exists(left, subqueries);
+            collect_exists(right, subqueries);
+        }
+        Expression::Unary { expr, .. } | Expression::Aggregation { expr, .. } => {
+            collect_exists(expr, subqueries);
+        }
+        Expression::Function { args, .. } => {
+            for arg in args {
+                collect_exists(arg, subqueries);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace the `EXISTS` subqueries of an expression, from left to right, with comparisons of
+/// their outer column against the given keys.
+fn bind_exists<'a>(expr: &mut Expression, keys: &mut impl Iterator<Item = &'a Vec<ExistsKey>>) {
+    match expr {
+        Expression::Exists { outer_column, .. } => {
+            let outer_column = *outer_column;
+            let keys = keys.next().expect("every subquery should have keys");
+            *expr = keys
+                .iter()
+                .map(|key| Expression::Binary {
+                    op: BinaryOperator::Equal,
+                    left: Box::new(Expression::Column(outer_column)),
+                    right: Box::new(Expression::Literal(key.to_literal())),
+                })
+                .reduce(|left, right| Expression::Binary {
+                    op: BinaryOperator::Or,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+                .unwrap_or(Expression::Literal(Literal::Boolean(false)));
+        }
+        Expression::Binary { left, right, .. } => {
+            bind_exists(left, keys);
+            bind_exists(right, keys);
+        }
+        Expression::Unary { expr, .. } | Expression::Aggregation { expr, .. } => {
+            bind_exists(expr, keys);
+        }
+        Expression::Function { args, .. } => {
+            for arg in args {
+                bind_exists(arg, keys);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check that a subquery has the bounded form and return its key column.
+fn resolve_subquery(
+    subquery: &Expression,
+    outer_table: Identifier,
+    outer_table_ref: TableRef,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<ColumnRef> {
+    let Expression::Exists {
+        table,
+        column,
+        outer_table: subquery_outer_table,
+        outer_column,
+    } = subquery
+    else {
+        unreachable!("only EXISTS subqueries are collected")
+    };
+    if *subquery_outer_table != outer_table {
+        return Err(ConversionError::InvalidExpression(format!(
+            "EXISTS subquery refers to table '{subquery_outer_table}' rather than '{outer_table}'"
+        )));
+    }
+    let key_table_ref = match table.as_ref() {
+        TableExpression::Named { table, schema } => table_ref(*table, *schema, default_schema),
+    };
+    if lookup_multiplicity_column(schema_accessor, key_table_ref).is_some() {
+        return Err(ConversionError::Unprovable(
+            "EXISTS subqueries on retractable tables are not supported".to_string(),
+        ));
+    }
+    if *column == count_alias() {
+        return Err(ConversionError::InvalidExpression(format!(
+            "column '{column}' has a name reserved by EXISTS subqueries"
+        )));
+    }
+    let key_type = schema_accessor
+        .lookup_column(key_table_ref, *column)
+        .ok_or_else(|| {
+            ConversionError::MissingColumn(Box::new(*column), Box::new(key_table_ref.resource_id()))
+        })?;
+    let outer_type = schema_accessor
+        .lookup_column(outer_table_ref, *outer_column)
+        .ok_or_else(|| {
+            ConversionError::MissingColumn(
+                Box::new(*outer_column),
+                Box::new(outer_table_ref.resource_id()),
+            )
+        })?;
+    if !matches!(
+        key_type,
+        ColumnType::SmallInt
+            | ColumnType::Int
+            | ColumnType::BigInt
+            | ColumnType::Int128
+            | ColumnType::VarChar
+    ) {
+        return Err(ConversionError::Unprovable(format!(
+            "EXISTS subqueries cannot match columns of type '{key_type}'"
+        )));
+    }
+    if key_type != outer_type {
+        return Err(ConversionError::DataTypeMismatch(
+            outer_type.to_string(),
+            key_type.to_string(),
+        ));
+    }
+    Ok(ColumnRef::new(key_table_ref, *column, key_type))
+}
@@ -0,0 +1,229 @@
+use super::{
+    ConversionError, ExistsQueryError, ExistsQueryExpr, ExistsQueryProof, QueryExpr,
+    MAX_EXISTS_KEYS,
+};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTable, OwnedTableTestAccessor,
+        TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5]),
+            varchar("b", ["x", "y", "z", "y", "x"]),
+            int("c", [1, 2, 3, 4, 5]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.other".parse().unwrap(),
+        owned_table([
+            bigint("k", [4, 2, 9, 4]),
+            varchar("name", ["y", "q", "q", "y"]),
+        ]),
+        3,
+    );
+    accessor.add_table(
+        "sxt.empty".parse().unwrap(),
+        owned_table([bigint("k", [0_i64; 0])]),
+        0,
+    );
+    accessor
+}
+
+fn exists_query(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Result<ExistsQueryExpr, ConversionError> {
+    ExistsQueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor)
+}
+
+fn prove_and_verify(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> OwnedTable<Curve25519Scalar> {
+    let expr = exists_query(sql, accessor).unwrap();
+    let proof = ExistsQueryProof::<InnerProductProof>::new(&expr, accessor, &()).unwrap();
+    let (query, data) = proof.verify(&expr, accessor, &()).unwrap();
+    query
+        .result()
+        .transform_results(data.table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn we_can_prove_and_verify_an_exists_subquery() {
+    let accessor = accessor();
+    let expr = exists_query(
+        "select a from t where exists (select 1 from other where other.k = t.a)",
+        &accessor,
+    )
+    .unwrap();
+    assert_eq!(
+        expr.key_columns(),
+        [ColumnRef::new(
+            "sxt.other".parse().unwrap(),
+            ident("k"),
+            ColumnType::BigInt
+        )]
+    );
+    assert_eq!(
+        prove_and_verify(
+            "select a from t where exists (select 1 from other where other.k = t.a)",
+            &accessor
+        ),
+        owned_table([bigint("a", [2, 4])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_a_not_exists_subquery_on_varchars() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select a, b from t where not exists (select 1 from other where t.b = other.name)",
+            &accessor
+        ),
+        owned_table([bigint("a", [1, 3, 5]), varchar("b", ["x", "z", "x"])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_several_subqueries_combined_with_other_predicates() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select a from t where (exists (select 1 from other where other.k = t.a) or a = 5) \
+             and not exists (select 1 from other where other.name = t.b)",
+            &accessor
+        ),
+        owned_table([bigint("a", [5])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_subqueries_on_an_empty_table() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select a from t where exists (select 1 from empty where empty.k = t.a)",
+            &accessor
+        ),
+        owned_table([bigint("a", [0_i64; 0])])
+    );
+    assert_eq!(
+        prove_and_verify(
+            "select a from t where not exists (select 1 from empty where empty.k = t.a)",
+            &accessor
+        ),
+        owned_table([bigint("a", [1, 2, 3, 4, 5])])
+    );
+}
+
+#[test]
+fn we_cannot_prove_a_subquery_whose_table_has_too_many_keys() {
+    let mut accessor = accessor();
+    accessor.add_table(
+        "sxt.big".parse().unwrap(),
+        owned_table([bigint("k", 0..=MAX_EXISTS_KEYS as i64)]),
+        0,
+    );
+    let expr = exists_query(
+        "select a from t where exists (select 1 from big where big.k = t.a)",
+        &accessor,
+    )
+    .unwrap();
+    assert!(matches!(
+        ExistsQueryProof::<InnerProductProof>::new(&expr, &accessor, &()),
+        Err(ExistsQueryError::TooManyKeys(column)) if column == ident("k")
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_different_keys() {
+    let accessor = accessor();
+    let mut prover_accessor = accessor.clone();
+    prover_accessor.add_table(
+        "sxt.other".parse().unwrap(),
+        owned_table([bigint("k", [1, 2, 3]), varchar("name", ["x", "y", "z"])]),
+        3,
+    );
+    let expr = exists_query(
+        "select a from t where exists (select 1 from other where other.k = t.a)",
+        &accessor,
+    )
+    .unwrap();
+    let proof = ExistsQueryProof::<InnerProductProof>::new(&expr, &prover_accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(&expr, &accessor, &()),
+        Err(ExistsQueryError::VerificationError(_))
+    ));
+
+    let other_expr = exists_query("select a from t where a = 1", &accessor).unwrap();
+    assert!(matches!(
+        proof.verify(&other_expr, &accessor, &()),
+        Err(ExistsQueryError::SubqueryCountMismatch {
+            proven: 1,
+            expected: 0
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_resolve_subqueries_that_do_not_have_the_bounded_form() {
+    let accessor = accessor();
+    assert!(matches!(
+        exists_query(
+            "select a from t where exists (select 1 from other where other.k = u.a)",
+            &accessor
+        ),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        exists_query(
+            "select a from t where exists (select 1 from other where other.k = t.c)",
+            &accessor
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        exists_query(
+            "select a from t where exists (select 1 from other where other.j = t.a)",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        exists_query(
+            "select a from t where exists (select 1 from missing where missing.k = t.a)",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+}
+
+#[test]
+fn we_cannot_plan_an_exists_subquery_without_binding_its_keys() {
+    let accessor = accessor();
+    assert_eq!(
+        QueryExpr::<RistrettoPoint>::try_new(
+            "select a from t where exists (select 1 from other where other.k = t.a)"
+                .parse()
+                .unwrap(),
+            ident("sxt"),
+            &accessor,
+        ),
+        Err(ConversionError::UnboundExists)
+    );
+}
@@ -4,7 +4,9 @@ use crate::{
         commitment::Commitment,
         database::{ColumnRef, LiteralValue, TableRef},
     },
-    sql::ast::{AliasedProvableExprPlan, DenseFilterExpr, ProvableExprPlan, TableExpr},
+    sql::ast::{
+        AliasedProvableExprPlan, DenseFilterExpr, OverflowPolicy, ProvableExprPlan, TableExpr,
+    },
 };
 use indexmap::IndexMap;
 use itertools::Itertools;
@@ -15,6 +17,7 @@ pub struct FilterExprBuilder<C: Commitment> {
     where_expr: Option<ProvableExprPlan<C>>,
     filter_result_expr_list: Vec<AliasedProvableExprPlan<C>>,
     column_mapping: IndexMap<Identifier, ColumnRef>,
+    overflow_policy: OverflowPolicy,
 }
 
 // Public interface
@@ -25,9 +28,15 @@ impl<C: Commitment> FilterExprBuilder<C> {
             where_expr: None,
             filter_result_expr_list: vec![],
             column_mapping,
+            overflow_policy: OverflowPolicy::default(),
         }
     }
 
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
     pub fn add_table_expr(mut self, table_ref: TableRef) -> Self {
         self.table_expr = Some(TableExpr { table_ref });
         self
@@ -37,7 +46,9 @@ impl<C: Commitment> FilterExprBuilder<C> {
         mut self,
         where_expr: Option<Box<Expression>>,
     ) -> Result<Self, ConversionError> {
-        self.where_expr = WhereExprBuilder::new(&self.column_mapping).build(where_expr)?;
+        self.where_expr = WhereExprBuilder::new(&self.column_mapping)
+            .with_overflow_policy(self.overflow_policy)
+            .build(where_expr)?;
         Ok(self)
     }
 
@@ -58,6 +69,14 @@ impl<C: Commitment> FilterExprBuilder<C> {
         if has_nonprovable_column {
             // Has to keep them sorted to have deterministic order for tests
             for alias in self.column_mapping.keys().sorted() {
+                // A provable result aliased to the column's own name is already the raw column.
+                if self
+                    .filter_result_expr_list
+                    .iter()
+                    .any(|aliased_expr| aliased_expr.alias == *alias)
+                {
+                    continue;
+                }
                 let column_ref = self.column_mapping.get(alias).unwrap();
                 self.filter_result_expr_list.push(AliasedProvableExprPlan {
                     expr: ProvableExprPlan::new_column(*column_ref),
@@ -68,6 +87,18 @@ impl<C: Commitment> FilterExprBuilder<C> {
         self
     }
 
+    /// Add the multiplicity column of a retractable table to the results, so that the retractions
+    /// can be netted out of them after they are verified.
+    pub fn add_multiplicity_column(mut self, multiplicity_column: Option<ColumnRef>) -> Self {
+        if let Some(column_ref) = multiplicity_column {
+            self.filter_result_expr_list.push(AliasedProvableExprPlan {
+                expr: ProvableExprPlan::new_column(column_ref),
+                alias: column_ref.column_id(),
+            });
+        }
+        self
+    }
+
     pub fn build(self) -> DenseFilterExpr<C> {
         DenseFilterExpr::new(
             self.filter_result_expr_list,
@@ -0,0 +1,222 @@
+use super::{ConversionError, ConversionResult};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{ColumnType, LiteralValue},
+    },
+    sql::ast::ProvableExprPlan,
+};
+use indexmap::IndexMap;
+use proof_of_sql_parser::{intermediate_ast::ContextVariable, Identifier};
+
+/// A provable gadget that calls of SQL functions can be resolved to.
+///
+/// Queries reach a gadget through the name that it is registered under in a
+/// [`FunctionRegistry`], so adding a gadget does not require any changes to the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProvableFunction {
+    /// `time_bucket(bucket_width, expr)`, which maps each timestamp to the start of its bucket.
+    ///
+    /// The bucket width has to be a positive integer literal, and is measured in the time unit of
+    /// `expr`. See [`ProvableExprPlan::try_new_time_bucket`].
+    TimeBucket,
+    /// `abs(expr)`, the absolute value of an integer expression. See [`ProvableExprPlan::try_new_abs`].
+    Abs,
+    /// `sign(expr)`, which is -1, 0 or 1 depending on the sign of an integer expression. See
+    /// [`ProvableExprPlan::try_new_signum`].
+    Sign,
+    /// `now()` or `block_height()`, which is bound to a literal from the
+    /// [`EvaluationContext`](crate::sql::proof::EvaluationContext) when the query is planned.
+    ContextVariable(ContextVariable),
+}
+
+impl ProvableFunction {
+    /// Every gadget, which the default [`FunctionRegistry`] has under its own name.
+    pub const ALL: [Self; 5] = [
+        Self::TimeBucket,
+        Self::Abs,
+        Self::Sign,
+        Self::ContextVariable(ContextVariable::Now),
+        Self::ContextVariable(ContextVariable::BlockHeight),
+    ];
+
+    /// Returns the name of the gadget.
+    ///
+    /// Once a call is resolved, it refers to the gadget by this name rather than the name it was
+    /// registered under.
+    pub fn name(&self) -> Identifier {
+        match self {
+            Self::TimeBucket => "time_bucket",
+            Self::Abs => "abs",
+            Self::Sign => "sign",
+            Self::ContextVariable(ContextVariable::Now) => "now",
+            Self::ContextVariable(ContextVariable::BlockHeight) => "block_height",
+        }
+        .parse()
+        .expect("the names of gadgets should be valid identifiers")
+    }
+
+    /// Returns the number of arguments of the gadget.
+    pub fn argument_count(&self) -> usize {
+        match self {
+            Self::TimeBucket => 2,
+            Self::Abs | Self::Sign => 1,
+            Self::ContextVariable(_) => 0,
+        }
+    }
+
+    /// Returns the context variable that calls of the gadget are bound to, if any.
+    pub(crate) fn context_variable(&self) -> Option<ContextVariable> {
+        match self {
+            Self::ContextVariable(variable) => Some(*variable),
+            _ => None,
+        }
+    }
+
+    /// Returns the gadget with the given name, see [`ProvableFunction::name`].
+    pub(crate) fn from_name(name: Identifier) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|function| function.name() == name)
+    }
+
+    /// Checks the types of the arguments of a call and returns the type of its result.
+    pub(crate) fn return_type(&self, arg_types: &[ColumnType]) -> ConversionResult<ColumnType> {
+        self.check_argument_count(arg_types.len())?;
+        match (self, arg_types) {
+            (Self::TimeBucket, [ColumnType::BigInt, timestamp @ ColumnType::TimestampTZ(..)]) => {
+                Ok(*timestamp)
+            }
+            (Self::TimeBucket, [ColumnType::BigInt, actual]) => {
+                Err(ConversionError::InvalidExpression(format!(
+                    "time_bucket requires a timestamp but found '{}'",
+                    actual
+                )))
+            }
+            (Self::TimeBucket, [actual, _]) => Err(ConversionError::InvalidDataType {
+                expected: ColumnType::BigInt,
+                actual: *actual,
+            }),
+            (Self::Abs | Self::Sign, [integer]) if integer.is_integer() => Ok(*integer),
+            (Self::Abs | Self::Sign, [actual]) => Err(ConversionError::InvalidExpression(format!(
+                "{} requires an integer but found '{}'",
+                self.name(),
+                actual
+            ))),
+            (Self::ContextVariable(_), _) => {
+                unreachable!("context variables are bound to literals before they are typed")
+            }
+            _ => unreachable!("the number of arguments is checked above"),
+        }
+    }
+
+    /// Builds the provable expression of a call with the given arguments.
+    pub(crate) fn try_build<C: Commitment>(
+        &self,
+        args: Vec<ProvableExprPlan<C>>,
+    ) -> ConversionResult<ProvableExprPlan<C>> {
+        self.check_argument_count(args.len())?;
+        match self {
+            Self::TimeBucket => match <[_; 2]>::try_from(args) {
+                Ok([ProvableExprPlan::Literal(bucket_width), expr]) => match bucket_width.value() {
+                    LiteralValue::BigInt(bucket_width) => {
+                        ProvableExprPlan::try_new_time_bucket(expr, *bucket_width)
+                    }
+                    _ => Err(ConversionError::InvalidDataType {
+                        expected: ColumnType::BigInt,
+                        actual: bucket_width.value().column_type(),
+                    }),
+                },
+                _ => Err(ConversionError::Unprovable(
+                    "the bucket width of time_bucket must be a literal".to_string(),
+                )),
+            },
+            Self::Abs => ProvableExprPlan::try_new_abs(single_argument(args)),
+            Self::Sign => ProvableExprPlan::try_new_signum(single_argument(args)),
+            Self::ContextVariable(_) => {
+                unreachable!("context variables are bound to literals before they are planned")
+            }
+        }
+    }
+
+    pub(crate) fn check_argument_count(&self, actual: usize) -> ConversionResult<()> {
+        if actual != self.argument_count() {
+            return Err(ConversionError::FunctionArgumentCount {
+                name: Box::new(self.name()),
+                expected: self.argument_count(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Returns the argument of a call with exactly one argument.
+fn single_argument<C: Commitment>(args: Vec<ProvableExprPlan<C>>) -> ProvableExprPlan<C> {
+    match <[_; 1]>::try_from(args) {
+        Ok([arg]) => arg,
+        Err(_) => unreachable!("the number of arguments is checked above"),
+    }
+}
+
+/// The SQL functions that queries can call, by the name that they are called with.
+///
+/// Only calls of registered functions are planned, so deployments can restrict queries to a
+/// whitelist of functions. A gadget may also be registered under other names, e.g. to match the
+/// dialect of another database.
+///
+/// The default registry has every [`ProvableFunction`] under its own name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionRegistry {
+    functions: IndexMap<Identifier, ProvableFunction>,
+}
+
+impl Default for FunctionRegistry {
+    fn default() -> Self {
+        ProvableFunction::ALL
+            .into_iter()
+            .fold(Self::empty(), |registry, function| {
+                registry.with_function(function.name(), function)
+            })
+    }
+}
+
+impl FunctionRegistry {
+    /// Create a registry without any functions.
+    pub fn empty() -> Self {
+        Self {
+            functions: IndexMap::new(),
+        }
+    }
+
+    /// Register a gadget under the given name, replacing any function that was registered under
+    /// it before.
+    pub fn with_function(mut self, name: Identifier, function: ProvableFunction) -> Self {
+        self.functions.insert(name, function);
+        self
+    }
+
+    /// Remove the function that is registered under the given name, if any.
+    pub fn without_function(mut self, name: Identifier) -> Self {
+        self.functions.shift_remove(&name);
+        self
+    }
+
+    /// Returns the gadget that is registered under the given name, if any.
+    pub fn get(&self, name: Identifier) -> Option<ProvableFunction> {
+        self.functions.get(&name).copied()
+    }
+
+    /// Returns the registered names along with their gadgets, in the order they were registered.
+    pub fn iter(&self) -> impl Iterator<Item = (Identifier, ProvableFunction)> + '_ {
+        self.functions
+            .iter()
+            .map(|(name, function)| (*name, *function))
+    }
+
+    /// Returns the gadget that is registered under the given name.
+    pub(crate) fn resolve(&self, name: Identifier) -> ConversionResult<ProvableFunction> {
+        self.get(name)
+            .ok_or_else(|| ConversionError::UnregisteredFunction(Box::new(name)))
+    }
+}
@@ -0,0 +1,277 @@
+use super::{ConversionError, FunctionRegistry, ProvableFunction, QueryExpr};
+use crate::{
+    base::database::{ColumnType, TableRef, TestSchemaAccessor},
+    sql::{ast::test_utility::*, proof::EvaluationContext},
+};
+use curve25519_dalek::RistrettoPoint;
+use indexmap::indexmap;
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    utility::ident,
+    SelectStatement,
+};
+
+fn accessor(t: TableRef) -> TestSchemaAccessor {
+    TestSchemaAccessor::new(indexmap! {
+        t => indexmap! {
+            ident("a") => ColumnType::BigInt,
+            ident("ts") => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
+        },
+    })
+}
+
+fn plan(
+    sql: &str,
+    accessor: &TestSchemaAccessor,
+    registry: &FunctionRegistry,
+) -> Result<QueryExpr<RistrettoPoint>, ConversionError> {
+    QueryExpr::try_new_with_functions(
+        sql.parse::<SelectStatement>().unwrap(),
+        ident("sxt"),
+        accessor,
+        EvaluationContext::default(),
+        registry,
+    )
+}
+
+#[test]
+fn the_default_registry_has_every_gadget_under_its_own_name() {
+    let registry = FunctionRegistry::default();
+    assert_eq!(
+        registry.iter().collect::<Vec<_>>(),
+        ProvableFunction::ALL
+            .into_iter()
+            .map(|function| (function.name(), function))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        registry.get(ident("time_bucket")),
+        Some(ProvableFunction::TimeBucket)
+    );
+    assert_eq!(FunctionRegistry::empty().get(ident("time_bucket")), None);
+}
+
+#[test]
+fn we_can_register_gadgets_under_other_names() {
+    let registry = FunctionRegistry::default()
+        .with_function(ident("date_bin"), ProvableFunction::TimeBucket)
+        .without_function(ident("time_bucket"));
+    assert_eq!(
+        registry.iter().collect::<Vec<_>>(),
+        [(ident("date_bin"), ProvableFunction::TimeBucket)]
+    );
+}
+
+#[test]
+fn we_can_plan_calls_of_registered_functions() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let expected = dense_filter(
+        vec![aliased_plan(
+            time_bucket(column(t, "ts", &accessor), 60),
+            "b",
+        )],
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(1)),
+    );
+
+    let query = plan(
+        "select time_bucket(60, ts) as b from t where a >= 1",
+        &accessor,
+        &FunctionRegistry::default(),
+    )
+    .unwrap();
+    assert_eq!(query.proof_expr(), &expected);
+
+    let registry =
+        FunctionRegistry::empty().with_function(ident("DATE_BIN"), ProvableFunction::TimeBucket);
+    let query = plan(
+        "select date_bin(60, ts) as b from t where a >= 1",
+        &accessor,
+        &registry,
+    )
+    .unwrap();
+    assert_eq!(query.proof_expr(), &expected);
+}
+
+#[test]
+fn we_can_call_functions_in_the_where_clause() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let query = plan(
+        "select a from t where time_bucket(3600, ts) = ts",
+        &accessor,
+        &FunctionRegistry::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        query.proof_expr(),
+        &dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(
+                time_bucket(column(t, "ts", &accessor), 3600),
+                column(t, "ts", &accessor)
+            ),
+        )
+    );
+}
+
+#[test]
+fn we_can_plan_calls_of_abs_and_sign() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let query = plan(
+        "select abs(a) as b from t where sign(a - 5) = -1",
+        &accessor,
+        &FunctionRegistry::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        query.proof_expr(),
+        &dense_filter(
+            vec![aliased_plan(abs(column(t, "a", &accessor)), "b")],
+            tab(t),
+            equal(
+                signum(subtract(column(t, "a", &accessor), const_bigint(5))),
+                const_bigint(-1)
+            ),
+        )
+    );
+    assert!(matches!(
+        plan(
+            "select abs(ts) as b from t",
+            &accessor,
+            &FunctionRegistry::default()
+        ),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        plan(
+            "select sign(a, a) as b from t",
+            &accessor,
+            &FunctionRegistry::default()
+        ),
+        Err(ConversionError::FunctionArgumentCount {
+            expected: 1,
+            actual: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_call_functions_that_are_not_registered() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    for (sql, registry) in [
+        (
+            "select time_bucket(60, ts) as b from t",
+            FunctionRegistry::empty(),
+        ),
+        (
+            "select time_bucket(60, ts) as b from t",
+            FunctionRegistry::default().without_function(ident("time_bucket")),
+        ),
+        ("select ceil(a) as b from t", FunctionRegistry::default()),
+        (
+            "select a from t where clamp(a, 0, 10) = a",
+            FunctionRegistry::default(),
+        ),
+    ] {
+        assert!(
+            matches!(
+                plan(sql, &accessor, &registry),
+                Err(ConversionError::UnregisteredFunction(_))
+            ),
+            "{sql}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_call_functions_with_invalid_arguments() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let registry = FunctionRegistry::default();
+    assert!(matches!(
+        plan("select time_bucket(ts) as b from t", &accessor, &registry),
+        Err(ConversionError::FunctionArgumentCount {
+            expected: 2,
+            actual: 1,
+            ..
+        })
+    ));
+    assert!(matches!(
+        plan(
+            "select time_bucket(60, a) as b from t",
+            &accessor,
+            &registry
+        ),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        plan(
+            "select time_bucket(ts, ts) as b from t",
+            &accessor,
+            &registry
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    assert!(matches!(
+        plan(
+            "select time_bucket(a, ts) as b from t",
+            &accessor,
+            &registry
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+    assert!(matches!(
+        plan(
+            "select time_bucket(0, ts) as b from t",
+            &accessor,
+            &registry
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
+
+#[test]
+fn we_can_bind_context_variables_through_the_registry() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let sql = "select a from t where a <= block_height()";
+    let bind = |registry: &FunctionRegistry| {
+        QueryExpr::<RistrettoPoint>::try_new_with_functions(
+            sql.parse::<SelectStatement>().unwrap(),
+            ident("sxt"),
+            &accessor,
+            EvaluationContext::new().with_block_height(7),
+            registry,
+        )
+    };
+    assert_eq!(
+        bind(&FunctionRegistry::default()).unwrap().proof_expr(),
+        &dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            lte(column(t, "a", &accessor), const_bigint(7)),
+        )
+    );
+    assert!(matches!(
+        bind(&FunctionRegistry::default().without_function(ident("block_height"))),
+        Err(ConversionError::UnregisteredFunction(_))
+    ));
+    assert!(matches!(
+        plan(
+            "select a from t where a <= block_height(1)",
+            &accessor,
+            &FunctionRegistry::default()
+        ),
+        Err(ConversionError::FunctionArgumentCount {
+            expected: 0,
+            actual: 1,
+            ..
+        })
+    ));
+}
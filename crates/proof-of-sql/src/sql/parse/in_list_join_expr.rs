@@ -0,0 +1,367 @@
+use super::{
+    join_aggregate_expr::key_literals,
+    lookup_join_expr::{column_keys, LookupKey},
+    ConversionError, ConversionResult, WhereExprBuilder,
+};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{
+            lookup_multiplicity_column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+            LiteralValue, OwnedTable, SchemaAccessor, TableRef,
+        },
+        scalar::Scalar,
+    },
+    sql::{
+        ast::{AliasedProvableExprPlan, DenseFilterExpr, ProofPlan, ProvableExprPlan, TableExpr},
+        proof::{ProofExpr, ProvableQueryResultLimits, QueryError, VerifiableQueryResult},
+    },
+};
+use indexmap::{IndexMap, IndexSet};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression, Literal, TableExpression},
+    Identifier, InListJoinStatement, ResourceId,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// The largest number of rows that the build side of an IN-list join may have after its predicates.
+pub const MAX_BUILD_ROWS: usize = 64;
+
+/// Errors that can occur when proving or verifying a [`InListJoinProof`].
+#[derive(Error, Debug)]
+pub enum InListJoinError {
+    /// One of the sides of the join could not be planned.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    /// The rows of the build side that satisfy its predicates are more than [`MAX_BUILD_ROWS`].
+    #[error("the build side of the join has more than {} rows", MAX_BUILD_ROWS)]
+    TooManyBuildRows,
+    /// One of the underlying proofs failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+}
+
+/// The side of an IN-list join that a column belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InListJoinSide {
+    Probe,
+    Build,
+}
+
+/// One of the tables of an IN-list join, along with the columns that are proven about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InListJoinTable {
+    columns: IndexMap<Identifier, ColumnRef>,
+    key: ColumnRef,
+    selected: IndexSet<ColumnRef>,
+    where_expr: Option<Box<Expression>>,
+}
+
+impl InListJoinTable {
+    /// Build the filter plan whose result has the selected columns of the rows of the table that
+    /// satisfy its predicates.
+    ///
+    /// If `keys` is given, only rows with one of those keys are included.
+    fn plan<C: Commitment>(&self, keys: Option<&[Literal]>) -> ConversionResult<ProofPlan<C>> {
+        let key_filter = keys.map(|keys| {
+            keys.iter()
+                .map(|key| Expression::Binary {
+                    op: BinaryOperator::Equal,
+                    left: Box::new(Expression::Column(self.key.column_id())),
+                    right: Box::new(Expression::Literal(key.clone())),
+                })
+                .reduce(|left, right| Expression::Binary {
+                    op: BinaryOperator::Or,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+                .unwrap_or(Expression::Literal(Literal::Boolean(false)))
+        });
+        let where_expr = match (self.where_expr.clone(), key_filter) {
+            (Some(where_expr), Some(key_filter)) => Some(Box::new(Expression::Binary {
+                op: BinaryOperator::And,
+                left: where_expr,
+                right: Box::new(key_filter),
+            })),
+            (where_expr, key_filter) => where_expr.or(key_filter.map(Box::new)),
+        };
+        let where_clause = WhereExprBuilder::new(&self.columns)
+            .build::<C>(where_expr)?
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        Ok(ProofPlan::DenseFilter(DenseFilterExpr::new(
+            self.selected
+                .iter()
+                .map(|&column| AliasedProvableExprPlan {
+                    expr: ProvableExprPlan::new_column(column),
+                    alias: column.column_id(),
+                })
+                .collect(),
+            TableExpr {
+                table_ref: self.key.table_ref(),
+            },
+            where_clause,
+        )))
+    }
+}
+
+/// The inner join of a table with a small table, such as a dimension table, e.g.
+/// ```ignore
+///     SELECT sales.amount, dim.label FROM sales JOIN dim ON sales.k = dim.k
+///     WHERE dim.active = true
+/// ```
+/// See [`InListJoinStatement`] for the supported form.
+///
+/// A proof only covers a single table, so the join is proven in two steps, much like a
+/// [`super::JoinAggregateExpr`]. First the rows of the build side that satisfy its predicates are
+/// proven with
+/// ```ignore
+///     SELECT <columns>, <key> FROM <build> WHERE <predicates>
+/// ```
+/// Then the rows of the probe side are proven with a semi-join against the keys of these rows,
+/// written out as an `IN` list
+/// ```ignore
+///     SELECT <columns>, <key> FROM <probe>
+///     WHERE <predicates> AND (<key> = <key1> OR ... OR <key> = <keyN>)
+/// ```
+/// so that every row of the probe side is proven to either match the build side or to be left out
+/// of the join. The verifier joins every verified row of the probe side with the rows of the build
+/// side with the same key. Only the rows of the probe side that are joined are part of the proof,
+/// so it stays small even if the probe side is large.
+///
+/// Nothing about the build side is hidden from the verifier. Its rows that satisfy its predicates
+/// are part of the proof in the clear, since the verifier needs them to build the `IN` list, and
+/// the proof grows with the number of distinct keys. This is why the build side may have at most
+/// [`MAX_BUILD_ROWS`] rows that satisfy its predicates. The keys must have the same integer or
+/// `VARCHAR` type, and neither table can be retractable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InListJoinExpr {
+    probe: InListJoinTable,
+    build: InListJoinTable,
+    result_columns: Vec<(Identifier, InListJoinSide, ColumnRef)>,
+}
+
+impl InListJoinExpr {
+    /// Resolve the tables and columns of an IN-list join against the schema.
+    pub fn try_new(
+        ast: InListJoinStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let mut probe = resolve_table(&ast.probe, ast.probe_key, default_schema, schema_accessor)?;
+        let mut build = resolve_table(&ast.build, ast.build_key, default_schema, schema_accessor)?;
+        if probe.key.column_type() != build.key.column_type() {
+            return Err(ConversionError::DataTypeMismatch(
+                probe.key.column_type().to_string(),
+                build.key.column_type().to_string(),
+            ));
+        }
+        let probe_table = ast.probe_table();
+        let mut result_columns: Vec<(Identifier, InListJoinSide, ColumnRef)> =
+            Vec::with_capacity(ast.columns.len());
+        for column in ast.columns {
+            if result_columns
+                .iter()
+                .any(|(alias, _, _)| *alias == column.alias)
+            {
+                return Err(ConversionError::DuplicateResultAlias(
+                    column.alias.to_string(),
+                ));
+            }
+            let (side, join_table) = if column.column.table == probe_table {
+                (InListJoinSide::Probe, &mut probe)
+            } else {
+                (InListJoinSide::Build, &mut build)
+            };
+            let column_ref = *join_table
+                .columns
+                .get(&column.column.column)
+                .ok_or_else(|| {
+                    ConversionError::MissingColumn(
+                        Box::new(column.column.column),
+                        Box::new(join_table.key.table_ref().resource_id()),
+                    )
+                })?;
+            join_table.selected.insert(column_ref);
+            result_columns.push((column.alias, side, column_ref));
+        }
+        probe.selected.insert(probe.key);
+        build.selected.insert(build.key);
+        for predicate in ast.predicates {
+            let join_table = if predicate.table == probe_table {
+                &mut probe
+            } else {
+                &mut build
+            };
+            join_table.where_expr = Some(match join_table.where_expr.take() {
+                Some(where_expr) => Box::new(Expression::Binary {
+                    op: BinaryOperator::And,
+                    left: where_expr,
+                    right: predicate.expr,
+                }),
+                None => predicate.expr,
+            });
+        }
+        Ok(Self {
+            probe,
+            build,
+            result_columns,
+        })
+    }
+
+    /// Returns the key columns of the probe and the build side.
+    pub fn key_columns(&self) -> (ColumnRef, ColumnRef) {
+        (self.probe.key, self.build.key)
+    }
+
+    /// Join the verified rows of the probe side with the verified rows of the build side.
+    fn combine<S: Scalar>(&self, probe: &OwnedTable<S>, build: &OwnedTable<S>) -> OwnedTable<S> {
+        let mut build_rows: HashMap<LookupKey, Vec<usize>> = HashMap::new();
+        for (row, key) in column_keys(&build.inner_table()[&self.build.key.column_id()])
+            .into_iter()
+            .enumerate()
+        {
+            build_rows.entry(key).or_default().push(row);
+        }
+        let (probe_rows, build_rows): (Vec<usize>, Vec<usize>) =
+            column_keys(&probe.inner_table()[&self.probe.key.column_id()])
+                .iter()
+                .enumerate()
+                .flat_map(|(row, key)| {
+                    build_rows
+                        .get(key)
+                        .into_iter()
+                        .flatten()
+                        .map(move |&build_row| (row, build_row))
+                })
+                .unzip();
+        let columns = self
+            .result_columns
+            .iter()
+            .map(|&(alias, side, column)| {
+                let (table, rows) = match side {
+                    InListJoinSide::Probe => (probe, &probe_rows),
+                    InListJoinSide::Build => (build, &build_rows),
+                };
+                (
+                    alias,
+                    table.inner_table()[&column.column_id()].select_rows(rows),
+                )
+            })
+            .collect();
+        OwnedTable::try_new(columns).expect("every column has a row for each joined pair of rows")
+    }
+}
+
+/// A proof of an [`InListJoinExpr`], which consists of a proof that reveals the rows of its build
+/// side and a proof of the rows of its probe side that match them.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InListJoinProof<CP: CommitmentEvaluationProof> {
+    build: VerifiableQueryResult<CP>,
+    probe: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> InListJoinProof<CP> {
+    /// Prove the join.
+    pub fn new(
+        expr: &InListJoinExpr,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, InListJoinError> {
+        let build_plan = expr.build.plan::<CP::Commitment>(None)?;
+        let build = VerifiableQueryResult::new(&build_plan, accessor, setup);
+        let keys = match &build.provable_result {
+            Some(result) => {
+                let table =
+                    result.to_owned_table::<CP::Scalar>(&build_plan.get_column_result_fields())?;
+                if table.num_rows() > MAX_BUILD_ROWS {
+                    return Err(InListJoinError::TooManyBuildRows);
+                }
+                distinct_keys(&table, expr.build.key)
+            }
+            None => Vec::new(),
+        };
+        let probe_plan = expr.probe.plan::<CP::Commitment>(Some(&keys))?;
+        let probe = VerifiableQueryResult::new(&probe_plan, accessor, setup);
+        Ok(Self { build, probe })
+    }
+
+    /// Verify the join, returning a table with a column for each column of the query and a row for
+    /// each pair of joined rows.
+    pub fn verify(
+        &self,
+        expr: &InListJoinExpr,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<OwnedTable<CP::Scalar>, InListJoinError> {
+        let limits = ProvableQueryResultLimits::new().with_max_rows(MAX_BUILD_ROWS);
+        let build_plan = expr.build.plan::<CP::Commitment>(None)?;
+        let build = self
+            .build
+            .verify_with_limits(&build_plan, accessor, setup, &limits)?
+            .table;
+        let keys = distinct_keys(&build, expr.build.key);
+        let probe_plan = expr.probe.plan::<CP::Commitment>(Some(&keys))?;
+        let probe = self.probe.verify(&probe_plan, accessor, setup)?.table;
+        Ok(expr.combine(&probe, &build))
+    }
+}
+
+/// Returns the distinct values of the key column of the build side as literals, in the order of
+/// the rows.
+fn distinct_keys<S: Scalar>(build: &OwnedTable<S>, key: ColumnRef) -> Vec<Literal> {
+    let mut keys = Vec::new();
+    for literal in key_literals(&build.inner_table()[&key.column_id()]) {
+        if !keys.contains(&literal) {
+            keys.push(literal);
+        }
+    }
+    keys
+}
+
+/// Resolve a table of an IN-list join and its key column.
+fn resolve_table(
+    table: &TableExpression,
+    key: Identifier,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<InListJoinTable> {
+    let table_ref = match table {
+        TableExpression::Named { table, schema } => {
+            TableRef::new(ResourceId::new(schema.unwrap_or(default_schema), *table))
+        }
+    };
+    if lookup_multiplicity_column(schema_accessor, table_ref).is_some() {
+        return Err(ConversionError::Unprovable(
+            "joins on retractable tables are not supported".to_string(),
+        ));
+    }
+    let columns: IndexMap<_, _> = schema_accessor
+        .lookup_schema(table_ref)
+        .into_iter()
+        .map(|(id, column_type)| (id, ColumnRef::new(table_ref, id, column_type)))
+        .collect();
+    let key = *columns.get(&key).ok_or_else(|| {
+        ConversionError::MissingColumn(Box::new(key), Box::new(table_ref.resource_id()))
+    })?;
+    if !matches!(
+        key.column_type(),
+        ColumnType::SmallInt
+            | ColumnType::Int
+            | ColumnType::BigInt
+            | ColumnType::Int128
+            | ColumnType::VarChar
+    ) {
+        return Err(ConversionError::Unprovable(format!(
+            "joins cannot match columns of type '{}'",
+            key.column_type()
+        )));
+    }
+    Ok(InListJoinTable {
+        columns,
+        key,
+        selected: IndexSet::new(),
+        where_expr: None,
+    })
+}
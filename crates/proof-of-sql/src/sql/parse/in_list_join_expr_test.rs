@@ -0,0 +1,205 @@
+use super::{ConversionError, InListJoinError, InListJoinExpr, InListJoinProof, MAX_BUILD_ROWS};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTable, OwnedTableTestAccessor,
+        TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.sales".parse().unwrap(),
+        owned_table([
+            bigint("k", [1, 2, 3, 1, 4, 2]),
+            bigint("amount", [10, 20, 30, 40, 50, 60]),
+            varchar("owner", ["x", "y", "x", "z", "x", "y"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.dim".parse().unwrap(),
+        owned_table([
+            bigint("k", [1, 2, 4, 4]),
+            varchar("label", ["one", "two", "four", "vier"]),
+            boolean("active", [true, false, true, true]),
+        ]),
+        2,
+    );
+    accessor.add_table(
+        "sxt.owners".parse().unwrap(),
+        owned_table([varchar("name", ["x", "z"]), bigint("rank", [1, 2])]),
+        0,
+    );
+    accessor
+}
+
+fn join_query(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Result<InListJoinExpr, ConversionError> {
+    InListJoinExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor)
+}
+
+fn prove_and_verify(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> OwnedTable<Curve25519Scalar> {
+    let expr = join_query(sql, accessor).unwrap();
+    let proof = InListJoinProof::<InnerProductProof>::new(&expr, accessor, &()).unwrap();
+    proof.verify(&expr, accessor, &()).unwrap()
+}
+
+#[test]
+fn we_can_join_a_table_with_a_dimension_table() {
+    let accessor = accessor();
+    let sql = "select sales.amount, dim.label from sales join dim on sales.k = dim.k \
+               where dim.active = true";
+    assert_eq!(
+        join_query(sql, &accessor).unwrap().key_columns(),
+        (
+            ColumnRef::new("sxt.sales".parse().unwrap(), ident("k"), ColumnType::BigInt),
+            ColumnRef::new("sxt.dim".parse().unwrap(), ident("k"), ColumnType::BigInt)
+        )
+    );
+    assert_eq!(
+        prove_and_verify(sql, &accessor),
+        owned_table([
+            bigint("amount", [10, 40, 50, 50]),
+            varchar("label", ["one", "one", "four", "vier"]),
+        ])
+    );
+    assert_eq!(
+        prove_and_verify(
+            "select dim.k as key, sales.owner from sales join dim on dim.k = sales.k \
+             where sales.amount >= 20 and dim.label != 'vier'",
+            &accessor
+        ),
+        owned_table([
+            bigint("key", [2, 1, 4, 2]),
+            varchar("owner", ["y", "z", "x", "y"])
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_an_in_list_join_on_varchar_keys() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select owners.rank, sales.amount from sales join owners on owners.name = sales.owner",
+            &accessor
+        ),
+        owned_table([
+            bigint("rank", [1, 1, 2, 1]),
+            bigint("amount", [10, 30, 40, 50])
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_an_in_list_join_without_any_rows() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select sales.amount, dim.label from sales join dim on sales.k = dim.k \
+             where dim.k >= 100",
+            &accessor
+        ),
+        owned_table([bigint("amount", [0; 0]), varchar("label", [""; 0])])
+    );
+}
+
+#[test]
+fn we_cannot_prove_an_in_list_join_with_too_many_build_rows() {
+    let mut accessor = accessor();
+    accessor.add_table(
+        "sxt.big".parse().unwrap(),
+        owned_table([bigint("k", (0..=MAX_BUILD_ROWS as i64).collect::<Vec<_>>())]),
+        0,
+    );
+    let expr = join_query(
+        "select sales.amount from sales join big on sales.k = big.k",
+        &accessor,
+    )
+    .unwrap();
+    assert!(matches!(
+        InListJoinProof::<InnerProductProof>::new(&expr, &accessor, &()),
+        Err(InListJoinError::TooManyBuildRows)
+    ));
+    let expr = join_query(
+        "select sales.amount from sales join big on sales.k = big.k where big.k <= 3",
+        &accessor,
+    )
+    .unwrap();
+    assert!(InListJoinProof::<InnerProductProof>::new(&expr, &accessor, &()).is_ok());
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_a_different_build_side() {
+    let accessor = accessor();
+    let mut prover_accessor = accessor.clone();
+    prover_accessor.add_table(
+        "sxt.dim".parse().unwrap(),
+        owned_table([
+            bigint("k", [1, 2, 4, 3]),
+            varchar("label", ["one", "two", "four", "three"]),
+            boolean("active", [true, false, true, true]),
+        ]),
+        2,
+    );
+    let expr = join_query(
+        "select sales.amount, dim.label from sales join dim on sales.k = dim.k \
+         where dim.active = true",
+        &accessor,
+    )
+    .unwrap();
+    let proof = InListJoinProof::<InnerProductProof>::new(&expr, &prover_accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(&expr, &accessor, &()),
+        Err(InListJoinError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_resolve_in_list_joins_with_invalid_columns() {
+    let accessor = accessor();
+    assert!(matches!(
+        join_query(
+            "select sales.amount from sales join owners on sales.k = owners.name",
+            &accessor
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        join_query(
+            "select sales.missing from sales join dim on sales.k = dim.k",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        join_query(
+            "select sales.amount from sales join dim on sales.k = dim.missing",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        join_query(
+            "select sales.amount as x, dim.label as x from sales join dim on sales.k = dim.k",
+            &accessor
+        ),
+        Err(ConversionError::DuplicateResultAlias(_))
+    ));
+    assert!(matches!(
+        join_query(
+            "select dim.label from dim join owners on dim.active = owners.name",
+            &accessor
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
@@ -0,0 +1,472 @@
+use super::{ConversionError, ConversionResult, WhereExprBuilder};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{
+            lookup_multiplicity_column, Column, ColumnRef, ColumnType, CommitmentAccessor,
+            DataAccessor, LiteralValue, OwnedColumn, OwnedColumnError, OwnedTable, SchemaAccessor,
+            TableRef,
+        },
+        scalar::Scalar,
+    },
+    sql::{
+        ast::{AliasedProvableExprPlan, GroupByExpr, ProofPlan, ProvableExprPlan, TableExpr},
+        proof::{ProofExpr, ProvableQueryResultLimits, QueryError, VerifiableQueryResult},
+    },
+};
+use bumpalo::Bump;
+use indexmap::IndexMap;
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AggregationOperator, BinaryOperator, Expression, Literal, QualifiedColumn, TableExpression,
+    },
+    Identifier, JoinAggregateStatement, ResourceId,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// The largest number of distinct keys that the right table of a join may match.
+pub const MAX_JOIN_KEYS: usize = 64;
+
+/// The alias of the per-key row count in the proofs of the sides of a join.
+const COUNT_ALIAS: &str = "__join_count__";
+
+/// The prefix of the aliases of the per-key sums in the proofs of the sides of a join.
+const SUM_ALIAS_PREFIX: &str = "__join_sum_";
+
+/// Errors that can occur when proving or verifying a [`JoinAggregateProof`].
+#[derive(Error, Debug)]
+pub enum JoinAggregateError {
+    /// One of the sides of the join could not be planned.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    /// The rows of the right table that satisfy its predicates have more than [`MAX_JOIN_KEYS`]
+    /// distinct keys.
+    #[error(
+        "the right table of the join matches more than {} distinct keys",
+        MAX_JOIN_KEYS
+    )]
+    TooManyKeys,
+    /// An aggregation does not fit in the type of its column.
+    #[error(transparent)]
+    AggregateOverflow(#[from] OwnedColumnError),
+    /// One of the underlying proofs failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+}
+
+/// The table of a join that a column belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JoinSide {
+    Left,
+    Right,
+}
+
+/// An aggregation of a join, resolved against the sides of the join.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ResolvedAggregation {
+    /// The sum of the column at the index of the sums of a side
+    Sum(JoinSide, usize),
+    /// The number of pairs of joined rows
+    Count,
+}
+
+/// One of the tables of a join, along with what has to be proven about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct JoinTable {
+    columns: IndexMap<Identifier, ColumnRef>,
+    key: ColumnRef,
+    sums: Vec<ColumnRef>,
+    where_expr: Option<Box<Expression>>,
+}
+
+impl JoinTable {
+    /// Build the group by plan that sums and counts the rows of the table per key.
+    ///
+    /// If `keys` is given, only rows with one of those keys are included.
+    fn plan<C: Commitment>(&self, keys: Option<&[Literal]>) -> ConversionResult<ProofPlan<C>> {
+        let key_filter = keys.map(|keys| {
+            keys.iter()
+                .map(|key| Expression::Binary {
+                    op: BinaryOperator::Equal,
+                    left: Box::new(Expression::Column(self.key.column_id())),
+                    right: Box::new(Expression::Literal(key.clone())),
+                })
+                .reduce(|left, right| Expression::Binary {
+                    op: BinaryOperator::Or,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+                .unwrap_or(Expression::Literal(Literal::Boolean(false)))
+        });
+        let where_expr = match (self.where_expr.clone(), key_filter) {
+            (Some(where_expr), Some(key_filter)) => Some(Box::new(Expression::Binary {
+                op: BinaryOperator::And,
+                left: where_expr,
+                right: Box::new(key_filter),
+            })),
+            (where_expr, key_filter) => where_expr.or(key_filter.map(Box::new)),
+        };
+        let where_clause = WhereExprBuilder::new(&self.columns)
+            .build::<C>(where_expr)?
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        Ok(ProofPlan::GroupBy(GroupByExpr::new(
+            vec![AliasedProvableExprPlan {
+                expr: ProvableExprPlan::new_column(self.key),
+                alias: self.key.column_id(),
+            }],
+            self.sums
+                .iter()
+                .enumerate()
+                .map(|(index, &sum)| AliasedProvableExprPlan {
+                    expr: ProvableExprPlan::new_column(sum),
+                    alias: sum_alias(index),
+                })
+                .collect(),
+            count_alias(),
+            TableExpr {
+                table_ref: self.key.table_ref(),
+            },
+            where_clause,
+        )))
+    }
+}
+
+/// An aggregation over the inner join of two tables, such as
+/// ```ignore
+///     SELECT SUM(a.amount) FROM a JOIN b ON a.k = b.k WHERE b.flag = true
+/// ```
+/// See [`JoinAggregateStatement`] for the supported form.
+///
+/// A proof only covers a single table, so the join is proven in two steps, much like an
+/// [`super::ExistsQueryExpr`]. First the rows of the right table that satisfy its predicates are
+/// summed and counted per key with
+/// ```ignore
+///     SELECT <key>, SUM(...), COUNT(*) FROM <right> WHERE <predicates> GROUP BY <key>
+/// ```
+/// Then the rows of the left table are summed and counted in the same way, restricted to the keys
+/// that the verifier read from the first result. The verifier combines the two verified results:
+/// every row of one table is joined with every row of the other table with the same key, so a sum
+/// over the join is the sum over the keys of the sum of one side times the count of the other.
+///
+/// This requires the right table to match at most [`MAX_JOIN_KEYS`] distinct keys. The keys must
+/// have the same integer or `VARCHAR` type, and neither table can be retractable. An aggregation
+/// over a join without any rows is `0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinAggregateExpr {
+    left: JoinTable,
+    right: JoinTable,
+    aggregations: Vec<(Identifier, ResolvedAggregation)>,
+}
+
+impl JoinAggregateExpr {
+    /// Resolve the tables and columns of a join against the schema.
+    pub fn try_new(
+        ast: JoinAggregateStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let mut left = resolve_table(&ast.left, ast.left_key, default_schema, schema_accessor)?;
+        let mut right = resolve_table(&ast.right, ast.right_key, default_schema, schema_accessor)?;
+        if left.key.column_type() != right.key.column_type() {
+            return Err(ConversionError::DataTypeMismatch(
+                left.key.column_type().to_string(),
+                right.key.column_type().to_string(),
+            ));
+        }
+        let (left_table, right_table) = (ast.left_table(), ast.right_table());
+        let mut aggregations: Vec<(Identifier, ResolvedAggregation)> =
+            Vec::with_capacity(ast.aggregations.len());
+        for aggregation in ast.aggregations {
+            if aggregations
+                .iter()
+                .any(|(alias, _)| *alias == aggregation.alias)
+            {
+                return Err(ConversionError::DuplicateResultAlias(
+                    aggregation.alias.to_string(),
+                ));
+            }
+            let resolved = match (aggregation.op, aggregation.column) {
+                (AggregationOperator::Count, None) => ResolvedAggregation::Count,
+                (AggregationOperator::Sum, Some(QualifiedColumn { table, column })) => {
+                    let (side, join_table) = if table == left_table {
+                        (JoinSide::Left, &mut left)
+                    } else {
+                        (JoinSide::Right, &mut right)
+                    };
+                    ResolvedAggregation::Sum(side, add_sum(join_table, column)?)
+                }
+                (op, _) => {
+                    return Err(ConversionError::InvalidExpression(format!(
+                        "joins do not support the aggregation {op}"
+                    )))
+                }
+            };
+            aggregations.push((aggregation.alias, resolved));
+        }
+        for predicate in ast.predicates {
+            let join_table = if predicate.table == right_table {
+                &mut right
+            } else {
+                &mut left
+            };
+            join_table.where_expr = Some(match join_table.where_expr.take() {
+                Some(where_expr) => Box::new(Expression::Binary {
+                    op: BinaryOperator::And,
+                    left: where_expr,
+                    right: predicate.expr,
+                }),
+                None => predicate.expr,
+            });
+        }
+        Ok(Self {
+            left,
+            right,
+            aggregations,
+        })
+    }
+
+    /// Returns the key columns of the left and the right table.
+    pub fn key_columns(&self) -> (ColumnRef, ColumnRef) {
+        (self.left.key, self.right.key)
+    }
+
+    /// Returns the types of the result columns, which are the types of the summed columns and
+    /// `BIGINT` for counts.
+    fn result_type(&self, aggregation: &ResolvedAggregation) -> ColumnType {
+        match aggregation {
+            ResolvedAggregation::Sum(side, index) => *self.table(*side).sums[*index].column_type(),
+            ResolvedAggregation::Count => ColumnType::BigInt,
+        }
+    }
+
+    fn table(&self, side: JoinSide) -> &JoinTable {
+        match side {
+            JoinSide::Left => &self.left,
+            JoinSide::Right => &self.right,
+        }
+    }
+
+    /// Combine the verified per-key results of the two tables into the result of the join.
+    fn combine<S: Scalar>(
+        &self,
+        left: &OwnedTable<S>,
+        right: &OwnedTable<S>,
+    ) -> Result<OwnedTable<S>, JoinAggregateError> {
+        let left_keys = scalars(left, self.left.key.column_id());
+        let right_keys = scalars(right, self.right.key.column_id());
+        let left_counts = scalars(left, count_alias());
+        let right_counts = scalars(right, count_alias());
+        let left_sums: Vec<_> = (0..self.left.sums.len())
+            .map(|index| scalars(left, sum_alias(index)))
+            .collect();
+        let right_sums: Vec<_> = (0..self.right.sums.len())
+            .map(|index| scalars(right, sum_alias(index)))
+            .collect();
+        let left_rows: HashMap<[u64; 4], usize> = left_keys
+            .iter()
+            .enumerate()
+            .map(|(row, &key)| (key.into(), row))
+            .collect();
+        let mut totals = vec![S::zero(); self.aggregations.len()];
+        for (right_row, &key) in right_keys.iter().enumerate() {
+            let key: [u64; 4] = key.into();
+            let Some(&left_row) = left_rows.get(&key) else {
+                continue;
+            };
+            let (left_count, right_count) = (left_counts[left_row], right_counts[right_row]);
+            for (total, (_, aggregation)) in totals.iter_mut().zip(&self.aggregations) {
+                *total += match aggregation {
+                    ResolvedAggregation::Sum(JoinSide::Left, index) => {
+                        left_sums[*index][left_row] * right_count
+                    }
+                    ResolvedAggregation::Sum(JoinSide::Right, index) => {
+                        right_sums[*index][right_row] * left_count
+                    }
+                    ResolvedAggregation::Count => left_count * right_count,
+                };
+            }
+        }
+        let columns = self
+            .aggregations
+            .iter()
+            .zip(totals)
+            .map(|((alias, aggregation), total)| {
+                Ok((
+                    *alias,
+                    OwnedColumn::try_from_scalars(&[total], self.result_type(aggregation))?,
+                ))
+            })
+            .collect::<Result<IndexMap<_, _>, JoinAggregateError>>()?;
+        Ok(OwnedTable::try_new(columns).expect("every aggregation has a single row"))
+    }
+}
+
+/// A proof of a [`JoinAggregateExpr`], which consists of a proof of the per-key aggregations of
+/// each of its tables.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JoinAggregateProof<CP: CommitmentEvaluationProof> {
+    right: VerifiableQueryResult<CP>,
+    left: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> JoinAggregateProof<CP> {
+    /// Prove the aggregation.
+    pub fn new(
+        expr: &JoinAggregateExpr,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, JoinAggregateError> {
+        let right_plan = expr.right.plan::<CP::Commitment>(None)?;
+        let right = VerifiableQueryResult::new(&right_plan, accessor, setup);
+        let keys = match &right.provable_result {
+            Some(result) => {
+                let table =
+                    result.to_owned_table::<CP::Scalar>(&right_plan.get_column_result_fields())?;
+                key_literals(&table.inner_table()[&expr.right.key.column_id()])
+            }
+            None => Vec::new(),
+        };
+        if keys.len() > MAX_JOIN_KEYS {
+            return Err(JoinAggregateError::TooManyKeys);
+        }
+        let left_plan = expr.left.plan::<CP::Commitment>(Some(&keys))?;
+        let left = VerifiableQueryResult::new(&left_plan, accessor, setup);
+        Ok(Self { right, left })
+    }
+
+    /// Verify the aggregation, returning a table with a single row that has a column for each
+    /// aggregation.
+    pub fn verify(
+        &self,
+        expr: &JoinAggregateExpr,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<OwnedTable<CP::Scalar>, JoinAggregateError> {
+        let limits = ProvableQueryResultLimits::new().with_max_rows(MAX_JOIN_KEYS);
+        let right_plan = expr.right.plan::<CP::Commitment>(None)?;
+        let right = self
+            .right
+            .verify_with_limits(&right_plan, accessor, setup, &limits)?
+            .table;
+        let keys = key_literals(&right.inner_table()[&expr.right.key.column_id()]);
+        let left_plan = expr.left.plan::<CP::Commitment>(Some(&keys))?;
+        let left = self
+            .left
+            .verify_with_limits(&left_plan, accessor, setup, &limits)?
+            .table;
+        expr.combine(&left, &right)
+    }
+}
+
+/// Returns the values of a column of a verified result as scalars.
+fn scalars<S: Scalar>(table: &OwnedTable<S>, id: Identifier) -> Vec<S> {
+    let alloc = Bump::new();
+    let column = Column::from_owned_column(&table.inner_table()[&id], &alloc);
+    (0..column.len())
+        .map(|row| {
+            column
+                .scalar_at(row)
+                .expect("the row should be in the column")
+        })
+        .collect()
+}
+
+/// Returns the values of a key column as literals, in the order of the column.
+pub(super) fn key_literals<S: Scalar>(column: &OwnedColumn<S>) -> Vec<Literal> {
+    match column {
+        OwnedColumn::SmallInt(values) => values.iter().copied().map(Literal::from).collect(),
+        OwnedColumn::Int(values) => values.iter().copied().map(Literal::from).collect(),
+        OwnedColumn::BigInt(values) => values.iter().copied().map(Literal::from).collect(),
+        OwnedColumn::Int128(values) => values.iter().copied().map(Literal::from).collect(),
+        OwnedColumn::VarChar(values) => values.iter().cloned().map(Literal::from).collect(),
+        _ => unreachable!("key columns are checked when the join is resolved"),
+    }
+}
+
+fn count_alias() -> Identifier {
+    COUNT_ALIAS
+        .parse()
+        .expect("the count alias should be a valid identifier")
+}
+
+fn sum_alias(index: usize) -> Identifier {
+    format!("{SUM_ALIAS_PREFIX}{index}__")
+        .parse()
+        .expect("the sum alias should be a valid identifier")
+}
+
+/// Resolve a table of a join and its key column.
+fn resolve_table(
+    table: &TableExpression,
+    key: Identifier,
+    default_schema: Identifier,
+    schema_accessor: &dyn SchemaAccessor,
+) -> ConversionResult<JoinTable> {
+    let table_ref = match table {
+        TableExpression::Named { table, schema } => {
+            TableRef::new(ResourceId::new(schema.unwrap_or(default_schema), *table))
+        }
+    };
+    if lookup_multiplicity_column(schema_accessor, table_ref).is_some() {
+        return Err(ConversionError::Unprovable(
+            "joins on retractable tables are not supported".to_string(),
+        ));
+    }
+    if key == count_alias() || key.as_str().starts_with(SUM_ALIAS_PREFIX) {
+        return Err(ConversionError::InvalidExpression(format!(
+            "column '{key}' has a name reserved by joins"
+        )));
+    }
+    let columns: IndexMap<_, _> = schema_accessor
+        .lookup_schema(table_ref)
+        .into_iter()
+        .map(|(id, column_type)| (id, ColumnRef::new(table_ref, id, column_type)))
+        .collect();
+    let key = *columns.get(&key).ok_or_else(|| {
+        ConversionError::MissingColumn(Box::new(key), Box::new(table_ref.resource_id()))
+    })?;
+    if !matches!(
+        key.column_type(),
+        ColumnType::SmallInt
+            | ColumnType::Int
+            | ColumnType::BigInt
+            | ColumnType::Int128
+            | ColumnType::VarChar
+    ) {
+        return Err(ConversionError::Unprovable(format!(
+            "joins cannot match columns of type '{}'",
+            key.column_type()
+        )));
+    }
+    Ok(JoinTable {
+        columns,
+        key,
+        sums: Vec::new(),
+        where_expr: None,
+    })
+}
+
+/// Add a column to the sums of a table, returning the index of its sum.
+fn add_sum(table: &mut JoinTable, column: Identifier) -> ConversionResult<usize> {
+    let column_ref = *table.columns.get(&column).ok_or_else(|| {
+        ConversionError::MissingColumn(
+            Box::new(column),
+            Box::new(table.key.table_ref().resource_id()),
+        )
+    })?;
+    if !column_ref.column_type().is_numeric() {
+        return Err(ConversionError::non_numeric_expr_in_agg(
+            column_ref.column_type().to_string(),
+            "SUM".to_string(),
+        ));
+    }
+    Ok(match table.sums.iter().position(|&sum| sum == column_ref) {
+        Some(index) => index,
+        None => {
+            table.sums.push(column_ref);
+            table.sums.len() - 1
+        }
+    })
+}
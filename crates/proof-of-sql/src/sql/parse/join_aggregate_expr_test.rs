@@ -0,0 +1,215 @@
+use super::{
+    ConversionError, JoinAggregateError, JoinAggregateExpr, JoinAggregateProof, MAX_JOIN_KEYS,
+};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTable, OwnedTableTestAccessor,
+        TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.a".parse().unwrap(),
+        owned_table([
+            bigint("k", [1, 2, 3, 1, 4]),
+            bigint("amount", [10, 20, 30, 40, 50]),
+            varchar("owner", ["x", "y", "x", "z", "x"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.b".parse().unwrap(),
+        owned_table([
+            bigint("k", [1, 1, 2, 4, 5]),
+            boolean("flag", [true, true, false, true, true]),
+            bigint("fee", [1, 2, 3, 4, 5]),
+        ]),
+        2,
+    );
+    accessor.add_table(
+        "sxt.accounts".parse().unwrap(),
+        owned_table([varchar("name", ["x", "y"]), boolean("flag", [true, false])]),
+        0,
+    );
+    accessor
+}
+
+fn join_query(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Result<JoinAggregateExpr, ConversionError> {
+    JoinAggregateExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor)
+}
+
+fn prove_and_verify(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> OwnedTable<Curve25519Scalar> {
+    let expr = join_query(sql, accessor).unwrap();
+    let proof = JoinAggregateProof::<InnerProductProof>::new(&expr, accessor, &()).unwrap();
+    proof.verify(&expr, accessor, &()).unwrap()
+}
+
+#[test]
+fn we_can_prove_and_verify_a_sum_over_a_filtered_join() {
+    let accessor = accessor();
+    let expr = join_query(
+        "select sum(a.amount) from a join b on a.k = b.k where b.flag = true",
+        &accessor,
+    )
+    .unwrap();
+    assert_eq!(
+        expr.key_columns(),
+        (
+            ColumnRef::new("sxt.a".parse().unwrap(), ident("k"), ColumnType::BigInt),
+            ColumnRef::new("sxt.b".parse().unwrap(), ident("k"), ColumnType::BigInt)
+        )
+    );
+    assert_eq!(
+        prove_and_verify(
+            "select sum(a.amount) from a join b on a.k = b.k where b.flag = true",
+            &accessor
+        ),
+        owned_table([bigint("__sum__", [150])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_sums_and_counts_of_both_tables_with_predicates_on_both() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select sum(a.amount) as total, sum(b.fee) as fees, count(*) as n \
+             from a inner join b on b.k = a.k",
+            &accessor
+        ),
+        owned_table([
+            bigint("total", [170]),
+            bigint("fees", [13]),
+            bigint("n", [6]),
+        ])
+    );
+    assert_eq!(
+        prove_and_verify(
+            "select sum(a.amount) as total, count(*) as n from a join b on a.k = b.k \
+             where b.flag = true and a.amount > 15",
+            &accessor
+        ),
+        owned_table([bigint("total", [130]), bigint("n", [3])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_a_join_on_varchar_keys() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select sum(a.amount), count(*) from a join accounts on accounts.name = a.owner \
+             where accounts.flag = true",
+            &accessor
+        ),
+        owned_table([bigint("__sum__", [90]), bigint("__count__", [3])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_a_join_without_any_rows() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select sum(a.amount), count(*) from a join b on a.k = b.k where b.k = 9",
+            &accessor
+        ),
+        owned_table([bigint("__sum__", [0]), bigint("__count__", [0])])
+    );
+}
+
+#[test]
+fn we_cannot_prove_a_join_whose_right_table_has_too_many_keys() {
+    let mut accessor = accessor();
+    accessor.add_table(
+        "sxt.big".parse().unwrap(),
+        owned_table([bigint("k", 0..=MAX_JOIN_KEYS as i64)]),
+        0,
+    );
+    let expr = join_query("select count(*) from a join big on a.k = big.k", &accessor).unwrap();
+    assert!(matches!(
+        JoinAggregateProof::<InnerProductProof>::new(&expr, &accessor, &()),
+        Err(JoinAggregateError::TooManyKeys)
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_different_data() {
+    let accessor = accessor();
+    let mut prover_accessor = accessor.clone();
+    prover_accessor.add_table(
+        "sxt.b".parse().unwrap(),
+        owned_table([
+            bigint("k", [1, 2, 3, 4, 5]),
+            boolean("flag", [true, true, true, true, true]),
+            bigint("fee", [1, 2, 3, 4, 5]),
+        ]),
+        2,
+    );
+    let expr = join_query(
+        "select sum(a.amount) from a join b on a.k = b.k where b.flag = true",
+        &accessor,
+    )
+    .unwrap();
+    let proof = JoinAggregateProof::<InnerProductProof>::new(&expr, &prover_accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(&expr, &accessor, &()),
+        Err(JoinAggregateError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_resolve_joins_with_invalid_columns() {
+    let accessor = accessor();
+    assert!(matches!(
+        join_query("select count(*) from a join b on a.owner = b.k", &accessor),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        join_query("select count(*) from a join b on a.k = b.j", &accessor),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        join_query(
+            "select sum(a.missing) from a join b on a.k = b.k",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        join_query("select sum(b.flag) from a join b on a.k = b.k", &accessor),
+        Err(ConversionError::InvalidExpression(_))
+    ));
+    assert!(matches!(
+        join_query(
+            "select sum(a.amount) as x, count(*) as x from a join b on a.k = b.k",
+            &accessor
+        ),
+        Err(ConversionError::DuplicateResultAlias(_))
+    ));
+    assert!(matches!(
+        join_query(
+            "select count(*) from a join b on a.flag = b.flag",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        join_query(
+            "select count(*) from accounts join b on accounts.flag = b.flag",
+            &accessor
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
@@ -0,0 +1,266 @@
+use super::{ConversionError, ConversionResult, QueryExpr};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{
+            lookup_multiplicity_column, CommitmentAccessor, DataAccessor, OwnedColumn, OwnedTable,
+            SchemaAccessor, TableRef,
+        },
+    },
+    sql::{
+        ast::{DenseFilterExpr, ProofPlan},
+        proof::{ProofExpr, QueryData, QueryError, VerifiableQueryResult},
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{Expression, SelectResultExpr, SetExpression, Slice},
+    Identifier, SelectStatement,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The alias of the row index in the underlying filter proof.
+const ROW_INDEX_ALIAS: &str = "__limit_row_index__";
+
+/// Errors that can occur when proving or verifying a [`LimitQueryProof`].
+#[derive(Error, Debug)]
+pub enum LimitQueryError {
+    /// The underlying proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+    /// The proof verified, but it has more rows than the limit of the query.
+    #[error("the result has {rows} rows, but the query is limited to {limit}")]
+    TooManyRows {
+        /// The number of rows of the result
+        rows: usize,
+        /// The limit of the query
+        limit: usize,
+    },
+    /// The proof verified, but its rows are not in the order of the table.
+    #[error("the rows of the result are not in the order of the table")]
+    OutOfOrder,
+    /// The proof verified, but it has fewer rows than the limit without covering every row of the
+    /// table, so there may be matches that it leaves out.
+    #[error("the result has fewer rows than the limit, but only covers the rows before {end}")]
+    IncompleteResult {
+        /// The row index before which the proof covers the table
+        end: usize,
+    },
+    /// The proof verified, but its result is not that of a limit query proof.
+    #[error("the verified result is malformed")]
+    MalformedResult,
+}
+
+/// A query of the form
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause> LIMIT <k>
+/// ```
+/// that returns the first `k` matches of the where clause in the order of the table.
+///
+/// A [`QueryExpr`] proves every match of the where clause and only applies the limit in
+/// postprocessing, which is wasteful for a selective filter over a large table. A
+/// [`LimitQueryProof`] instead only proves the matches up to the `k`-th one, see there.
+///
+/// The query cannot have an `OFFSET`, an `ORDER BY`, a `GROUP BY` or aggregations, and its table
+/// cannot be retractable.
+#[derive(Debug)]
+pub struct LimitQueryExpr<C: Commitment> {
+    query: QueryExpr<C>,
+    limit: usize,
+}
+
+impl<C: Commitment> LimitQueryExpr<C> {
+    /// Parse an intermediate AST `SelectStatement` with a `LIMIT` into a `LimitQueryExpr`.
+    pub fn try_new(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let limit = match &ast.slice {
+            Some(Slice {
+                number_rows,
+                offset_value: 0,
+            }) => usize::try_from(*number_rows).unwrap_or(usize::MAX),
+            Some(_) => {
+                return Err(ConversionError::Unprovable(
+                    "limit queries with an OFFSET are not supported".to_string(),
+                ))
+            }
+            None => {
+                return Err(ConversionError::InvalidExpression(
+                    "a limit query must have a LIMIT".to_string(),
+                ))
+            }
+        };
+        if !ast.order_by.is_empty() {
+            return Err(ConversionError::Unprovable(
+                "limit queries with an ORDER BY are not supported".to_string(),
+            ));
+        }
+        let SetExpression::Query {
+            result_exprs,
+            group_by,
+            ..
+        } = ast.expr.as_ref();
+        let has_aggregation = result_exprs.iter().any(|result_expr| match result_expr {
+            SelectResultExpr::ALL => false,
+            SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                has_aggregation(&aliased_expr.expr)
+            }
+        });
+        if !group_by.is_empty() || has_aggregation {
+            return Err(ConversionError::Unprovable(
+                "limit queries with a GROUP BY or aggregations are not supported".to_string(),
+            ));
+        }
+        if let Some(table_ref) = ast
+            .get_table_references(default_schema)
+            .into_iter()
+            .next()
+            .map(TableRef::new)
+        {
+            if lookup_multiplicity_column(schema_accessor, table_ref).is_some() {
+                return Err(ConversionError::Unprovable(
+                    "limit queries on retractable tables are not supported".to_string(),
+                ));
+            }
+        }
+        let query = QueryExpr::try_new(ast, default_schema, schema_accessor)?;
+        let ProofPlan::DenseFilter(filter) = query.proof_expr() else {
+            return Err(ConversionError::Unprovable(
+                "only filter queries can be limited in the proof".to_string(),
+            ));
+        };
+        if filter
+            .get_column_result_fields()
+            .iter()
+            .any(|field| field.name() == row_index_alias())
+        {
+            return Err(ConversionError::InvalidExpression(format!(
+                "column '{ROW_INDEX_ALIAS}' has a name reserved by limit queries"
+            )));
+        }
+        Ok(Self { query, limit })
+    }
+
+    /// Returns the planned query. Its verified results still have to be transformed with
+    /// [`QueryExpr::result`].
+    pub fn query(&self) -> &QueryExpr<C> {
+        &self.query
+    }
+
+    /// Returns the number of rows that the query is limited to.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    fn filter(&self) -> &DenseFilterExpr<C> {
+        match self.query.proof_expr() {
+            ProofPlan::DenseFilter(filter) => filter,
+            _ => unreachable!("limit queries are checked to be filters when they are planned"),
+        }
+    }
+
+    /// Build the filter plan whose result has the matches before the row index `end`, along with
+    /// their row indexes.
+    fn plan(&self, end: usize) -> ProofPlan<C> {
+        ProofPlan::DenseFilter(self.filter().with_row_index_bound(end, row_index_alias()))
+    }
+}
+
+/// A proof of a [`LimitQueryExpr`], which proves the first `k` matches of the where clause
+/// without proving the matches after them.
+///
+/// Internally this is a proof of
+/// ```ignore
+///     SELECT <result_expr1>, ..., <result_exprN>, <row_index> FROM <table>
+///     WHERE <where_clause> AND <row_index> < <end>
+/// ```
+/// where `end` is the row index after the `k`-th match, or the end of the table if there are
+/// fewer matches. Since the filter proof shows that the result has every match before `end`, the
+/// verifier only has to check that the result has at most `k` rows in the order of the table,
+/// and that it covers the whole table if it has fewer than `k` rows.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LimitQueryProof<CP: CommitmentEvaluationProof> {
+    end: usize,
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> LimitQueryProof<CP> {
+    /// Prove the first matches of the query.
+    pub fn new(
+        expr: &LimitQueryExpr<CP::Commitment>,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        let table_ref = expr.query.proof_expr().table_ref();
+        let offset = accessor.get_offset(table_ref);
+        let end = match expr.limit.checked_sub(1) {
+            Some(last) => expr
+                .filter()
+                .selected_row_indexes(accessor)
+                .get(last)
+                .map_or(offset + accessor.get_length(table_ref), |row| row + 1),
+            None => offset,
+        };
+        Self {
+            end,
+            result: VerifiableQueryResult::new(&expr.plan(end), accessor, setup),
+        }
+    }
+
+    /// Verify that the result has the first matches of the query in the order of the table.
+    ///
+    /// The verified result does not have the row indexes of the matches. As with a [`QueryExpr`],
+    /// it still has to be transformed with [`QueryExpr::result`].
+    pub fn verify(
+        &self,
+        expr: &LimitQueryExpr<CP::Commitment>,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<QueryData<CP::Scalar>, LimitQueryError> {
+        let table_ref = expr.query.proof_expr().table_ref();
+        let table_end = accessor.get_offset(table_ref) + accessor.get_length(table_ref);
+        if self.end > table_end {
+            return Err(LimitQueryError::MalformedResult);
+        }
+        let mut data = self.result.verify(&expr.plan(self.end), accessor, setup)?;
+        let mut table = data.table.into_inner();
+        let Some(OwnedColumn::BigInt(rows)) = table.shift_remove(&row_index_alias()) else {
+            return Err(LimitQueryError::MalformedResult);
+        };
+        if rows.len() > expr.limit {
+            return Err(LimitQueryError::TooManyRows {
+                rows: rows.len(),
+                limit: expr.limit,
+            });
+        }
+        if rows.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(LimitQueryError::OutOfOrder);
+        }
+        if rows.len() < expr.limit && self.end < table_end {
+            return Err(LimitQueryError::IncompleteResult { end: self.end });
+        }
+        data.table = OwnedTable::try_new(table).map_err(|_| LimitQueryError::MalformedResult)?;
+        if let Some(column_checksums) = &mut data.column_checksums {
+            column_checksums.shift_remove(&row_index_alias());
+        }
+        Ok(data)
+    }
+}
+
+fn row_index_alias() -> Identifier {
+    ROW_INDEX_ALIAS
+        .parse()
+        .expect("the row index alias should be a valid identifier")
+}
+
+/// Returns whether an expression has an aggregation.
+fn has_aggregation(expr: &Expression) -> bool {
+    match expr {
+        Expression::Aggregation { .. } => true,
+        Expression::Binary { left, right, .. } => has_aggregation(left) || has_aggregation(right),
+        Expression::Unary { expr, .. } => has_aggregation(expr),
+        _ => false,
+    }
+}
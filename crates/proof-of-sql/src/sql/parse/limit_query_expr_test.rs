@@ -0,0 +1,140 @@
+use super::{ConversionError, LimitQueryError, LimitQueryExpr, LimitQueryProof};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    scalar::Curve25519Scalar,
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn accessor(offset: usize) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7, 8]),
+            varchar("b", ["x", "y", "x", "z", "y", "x", "y", "x"]),
+        ]),
+        offset,
+    );
+    accessor.add_table(
+        "sxt.r".parse().unwrap(),
+        owned_table([bigint("a", [1, 2]), bigint("__multiplicity__", [1, 1])]),
+        0,
+    );
+    accessor
+}
+
+fn limit_query(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Result<LimitQueryExpr<RistrettoPoint>, ConversionError> {
+    LimitQueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor)
+}
+
+fn prove_and_verify(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> OwnedTable<Curve25519Scalar> {
+    let expr = limit_query(sql, accessor).unwrap();
+    let proof = LimitQueryProof::<InnerProductProof>::new(&expr, accessor, &());
+    let data = proof.verify(&expr, accessor, &()).unwrap();
+    expr.query()
+        .result()
+        .transform_results(data.table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn we_can_prove_and_verify_the_first_matches_of_a_query() {
+    for offset in [0, 3] {
+        let accessor = accessor(offset);
+        assert_eq!(
+            prove_and_verify("SELECT a FROM t WHERE b = 'x' LIMIT 2", &accessor),
+            owned_table([bigint("a", [1, 3])])
+        );
+        assert_eq!(
+            prove_and_verify("SELECT a, b FROM t WHERE a >= 4 LIMIT 3", &accessor),
+            owned_table([bigint("a", [4, 5, 6]), varchar("b", ["z", "y", "x"])])
+        );
+        assert_eq!(
+            prove_and_verify("SELECT a + 1 AS c FROM t WHERE b = 'y' LIMIT 1", &accessor),
+            owned_table([bigint("c", [3])])
+        );
+    }
+}
+
+#[test]
+fn we_can_prove_and_verify_a_limit_beyond_the_number_of_matches() {
+    let accessor = accessor(2);
+    assert_eq!(
+        prove_and_verify("SELECT a FROM t WHERE b = 'y' LIMIT 5", &accessor),
+        owned_table([bigint("a", [2, 5, 7])])
+    );
+    assert_eq!(
+        prove_and_verify("SELECT a FROM t WHERE b = 'w' LIMIT 5", &accessor),
+        owned_table([bigint("a", [0; 0])])
+    );
+    assert_eq!(
+        prove_and_verify("SELECT a FROM t WHERE b = 'x' LIMIT 0", &accessor),
+        owned_table([bigint("a", [0; 0])])
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_a_different_limit() {
+    let accessor = accessor(0);
+    let sql = |limit: usize| format!("SELECT a FROM t WHERE b = 'x' LIMIT {limit}");
+    let one = limit_query(&sql(1), &accessor).unwrap();
+    let three = limit_query(&sql(3), &accessor).unwrap();
+
+    let proof = LimitQueryProof::<InnerProductProof>::new(&one, &accessor, &());
+    assert!(matches!(
+        proof.verify(&three, &accessor, &()),
+        Err(LimitQueryError::IncompleteResult { end: 1 })
+    ));
+
+    let proof = LimitQueryProof::<InnerProductProof>::new(&three, &accessor, &());
+    assert!(matches!(
+        proof.verify(&one, &accessor, &()),
+        Err(LimitQueryError::TooManyRows { rows: 3, limit: 1 })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_against_a_table_with_other_matches() {
+    let accessor = accessor(0);
+    let expr = limit_query("SELECT a FROM t WHERE b = 'z' LIMIT 2", &accessor).unwrap();
+    let proof = LimitQueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+
+    let mut other_accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    other_accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7, 8]),
+            varchar("b", ["x", "y", "x", "z", "y", "x", "z", "x"]),
+        ]),
+        0,
+    );
+    assert!(matches!(
+        proof.verify(&expr, &other_accessor, &()),
+        Err(LimitQueryError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_limit_queries_that_are_not_plain_filters() {
+    let accessor = accessor(0);
+    for sql in [
+        "SELECT a FROM t WHERE b = 'x'",
+        "SELECT a FROM t WHERE b = 'x' LIMIT 2 OFFSET 1",
+        "SELECT a FROM t WHERE b = 'x' ORDER BY a LIMIT 2",
+        "SELECT b, count(*) AS n FROM t GROUP BY b LIMIT 2",
+        "SELECT sum(a) + 1 AS s FROM t LIMIT 2",
+        "SELECT a FROM r LIMIT 2",
+    ] {
+        assert!(limit_query(sql, &accessor).is_err(), "{sql}");
+    }
+}
@@ -0,0 +1,537 @@
+use super::{ConversionError, ConversionResult, WhereExprBuilder};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{
+            lookup_multiplicity_column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor,
+            DataAccessor, LiteralValue, MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor,
+            TableRef,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        ast::{AliasedProvableExprPlan, DenseFilterExpr, ProofPlan, ProvableExprPlan, TableExpr},
+        proof::{
+            CountBuilder, ProofBuilder, ProofExpr, ProverEvaluate, QueryError, ResultBuilder,
+            VerifiableQueryResult, VerificationBuilder,
+        },
+    },
+};
+use bumpalo::Bump;
+use indexmap::{IndexMap, IndexSet};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression, InlineTable, Literal, TableExpression},
+    Identifier, LookupJoinStatement, ResourceId,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// The largest number of rows that the `VALUES` table of a lookup join may have.
+pub const MAX_LOOKUP_ROWS: usize = 64;
+
+/// Errors that can occur when proving or verifying a [`LookupJoinProof`].
+#[derive(Error, Debug)]
+pub enum LookupJoinError {
+    /// The table of the join could not be planned.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    /// The underlying proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+}
+
+/// The source of a column of the result of a lookup join.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LookupSource {
+    /// A column of the table
+    Table(ColumnRef),
+    /// The column at the index of the `VALUES` table, with its type
+    Values(usize, ColumnType),
+}
+
+/// A key of a lookup join, which compares integers of different types by value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum LookupKey {
+    Integer(i128),
+    VarChar(String),
+}
+
+/// The inner join of a table with a `VALUES` table, such as
+/// ```ignore
+///     SELECT t.amount, v.label FROM t JOIN (VALUES (1, 'a'), (2, 'b')) AS v(k, label)
+///     ON t.k = v.k
+/// ```
+/// See [`LookupJoinStatement`] for the supported form.
+///
+/// The rows of the `VALUES` table are given by the client, so only the rows of the table have to
+/// be proven. The rows that satisfy the predicates and whose key is one of the keys of the `VALUES`
+/// table are proven with
+/// ```ignore
+///     SELECT <columns>, <key> FROM <table>
+///     WHERE <predicates> AND (<key> = <key1> OR ... OR <key> = <keyN>)
+/// ```
+/// and the verifier joins every verified row with the rows of the `VALUES` table with the same key.
+/// The whole `VALUES` table is absorbed into the transcript along with the proof plan, so a proof
+/// is only valid for the mapping that it was created with.
+///
+/// This requires the `VALUES` table to have at most [`MAX_LOOKUP_ROWS`] rows of boolean, integer
+/// or `VARCHAR` literals. The keys must both be integers or both be `VARCHAR`s, and the table
+/// cannot be retractable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupJoinExpr {
+    columns: IndexMap<Identifier, ColumnRef>,
+    key: ColumnRef,
+    values: InlineTable,
+    values_key: usize,
+    selected: IndexSet<ColumnRef>,
+    result_columns: Vec<(Identifier, LookupSource)>,
+    where_expr: Option<Box<Expression>>,
+}
+
+impl LookupJoinExpr {
+    /// Resolve the table and the columns of a lookup join against the schema.
+    pub fn try_new(
+        ast: LookupJoinStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        let table_ref = match ast.table.as_ref() {
+            TableExpression::Named { table, schema } => {
+                TableRef::new(ResourceId::new(schema.unwrap_or(default_schema), *table))
+            }
+        };
+        if lookup_multiplicity_column(schema_accessor, table_ref).is_some() {
+            return Err(ConversionError::Unprovable(
+                "joins on retractable tables are not supported".to_string(),
+            ));
+        }
+        if ast.values.rows.len() > MAX_LOOKUP_ROWS {
+            return Err(ConversionError::Unprovable(format!(
+                "VALUES tables can have at most {MAX_LOOKUP_ROWS} rows"
+            )));
+        }
+        let columns: IndexMap<_, _> = schema_accessor
+            .lookup_schema(table_ref)
+            .into_iter()
+            .map(|(id, column_type)| (id, ColumnRef::new(table_ref, id, column_type)))
+            .collect();
+        let resolve_column = |column: Identifier| {
+            columns.get(&column).copied().ok_or_else(|| {
+                ConversionError::MissingColumn(Box::new(column), Box::new(table_ref.resource_id()))
+            })
+        };
+        let key = resolve_column(ast.table_key)?;
+        let values_key = ast
+            .values
+            .column_index(ast.values_key)
+            .expect("the parser checks that the key is a column of the VALUES table");
+        let values_key_type = values_column_type(&ast.values, values_key)?;
+        match (key.column_type(), values_key_type) {
+            (
+                ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt | ColumnType::Int128,
+                ColumnType::BigInt | ColumnType::Int128,
+            )
+            | (ColumnType::VarChar, ColumnType::VarChar) => {}
+            (
+                ColumnType::SmallInt
+                | ColumnType::Int
+                | ColumnType::BigInt
+                | ColumnType::Int128
+                | ColumnType::VarChar,
+                _,
+            ) => {
+                return Err(ConversionError::DataTypeMismatch(
+                    key.column_type().to_string(),
+                    values_key_type.to_string(),
+                ))
+            }
+            (column_type, _) => {
+                return Err(ConversionError::Unprovable(format!(
+                    "joins cannot match columns of type '{column_type}'"
+                )))
+            }
+        }
+        let table_name = ast.table_name();
+        let mut selected = IndexSet::new();
+        let mut result_columns: Vec<(Identifier, LookupSource)> =
+            Vec::with_capacity(ast.columns.len());
+        for column in ast.columns {
+            if result_columns
+                .iter()
+                .any(|(alias, _)| *alias == column.alias)
+            {
+                return Err(ConversionError::DuplicateResultAlias(
+                    column.alias.to_string(),
+                ));
+            }
+            let source = if column.column.table == table_name {
+                let column_ref = resolve_column(column.column.column)?;
+                selected.insert(column_ref);
+                LookupSource::Table(column_ref)
+            } else {
+                let index = ast
+                    .values
+                    .column_index(column.column.column)
+                    .expect("the parser checks that the columns belong to one of the tables");
+                LookupSource::Values(index, values_column_type(&ast.values, index)?)
+            };
+            result_columns.push((column.alias, source));
+        }
+        selected.insert(key);
+        let where_expr = ast
+            .predicates
+            .into_iter()
+            .map(|predicate| predicate.expr)
+            .reduce(|left, right| {
+                Box::new(Expression::Binary {
+                    op: BinaryOperator::And,
+                    left,
+                    right,
+                })
+            });
+        Ok(Self {
+            columns,
+            key,
+            values: ast.values,
+            values_key,
+            selected,
+            result_columns,
+            where_expr,
+        })
+    }
+
+    /// Returns the key column of the table.
+    pub fn key_column(&self) -> ColumnRef {
+        self.key
+    }
+
+    /// Returns the distinct keys of the `VALUES` table that a row of the table can have, in the
+    /// order of the rows.
+    fn keys(&self) -> Vec<&Literal> {
+        let mut keys = IndexMap::new();
+        for row in &self.values.rows {
+            let literal = &row[self.values_key];
+            if key_fits(literal, *self.key.column_type()) {
+                keys.entry(literal_key(literal)).or_insert(literal);
+            }
+        }
+        keys.into_values().collect()
+    }
+
+    /// Build the filter plan whose result has the selected columns of the rows of the table that
+    /// satisfy the predicates and match a row of the `VALUES` table.
+    fn plan<C: Commitment>(&self) -> ConversionResult<LookupJoinPlan<C>> {
+        let key_filter = self
+            .keys()
+            .into_iter()
+            .map(|key| Expression::Binary {
+                op: BinaryOperator::Equal,
+                left: Box::new(Expression::Column(self.key.column_id())),
+                right: Box::new(Expression::Literal(key.clone())),
+            })
+            .reduce(|left, right| Expression::Binary {
+                op: BinaryOperator::Or,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+            .unwrap_or(Expression::Literal(Literal::Boolean(false)));
+        let where_expr = match self.where_expr.clone() {
+            Some(where_expr) => Expression::Binary {
+                op: BinaryOperator::And,
+                left: where_expr,
+                right: Box::new(key_filter),
+            },
+            None => key_filter,
+        };
+        let where_clause = WhereExprBuilder::new(&self.columns)
+            .build::<C>(Some(Box::new(where_expr)))?
+            .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
+        let plan = ProofPlan::DenseFilter(DenseFilterExpr::new(
+            self.selected
+                .iter()
+                .map(|&column| AliasedProvableExprPlan {
+                    expr: ProvableExprPlan::new_column(column),
+                    alias: column.column_id(),
+                })
+                .collect(),
+            TableExpr {
+                table_ref: self.key.table_ref(),
+            },
+            where_clause,
+        ));
+        Ok(LookupJoinPlan {
+            plan,
+            values: self.values.clone(),
+        })
+    }
+
+    /// Join the verified rows of the table with the rows of the `VALUES` table.
+    fn combine<S: Scalar>(&self, table: &OwnedTable<S>) -> OwnedTable<S> {
+        let mut values_rows: HashMap<LookupKey, Vec<usize>> = HashMap::new();
+        for (row, values) in self.values.rows.iter().enumerate() {
+            values_rows
+                .entry(literal_key(&values[self.values_key]))
+                .or_default()
+                .push(row);
+        }
+        let (table_rows, values_rows): (Vec<usize>, Vec<usize>) =
+            column_keys(&table.inner_table()[&self.key.column_id()])
+                .iter()
+                .enumerate()
+                .flat_map(|(row, key)| {
+                    values_rows
+                        .get(key)
+                        .into_iter()
+                        .flatten()
+                        .map(move |&values_row| (row, values_row))
+                })
+                .unzip();
+        let columns = self
+            .result_columns
+            .iter()
+            .map(|&(alias, source)| {
+                let column = match source {
+                    LookupSource::Table(column) => {
+                        table.inner_table()[&column.column_id()].select_rows(&table_rows)
+                    }
+                    LookupSource::Values(index, column_type) => {
+                        values_column(&self.values, index, column_type, &values_rows)
+                    }
+                };
+                (alias, column)
+            })
+            .collect();
+        OwnedTable::try_new(columns).expect("every column has a row for each joined pair of rows")
+    }
+}
+
+/// A proof of a [`LookupJoinExpr`], which is a proof of the rows of its table that are joined.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LookupJoinProof<CP: CommitmentEvaluationProof> {
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> LookupJoinProof<CP> {
+    /// Prove the join.
+    pub fn new(
+        expr: &LookupJoinExpr,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, LookupJoinError> {
+        let plan = expr.plan::<CP::Commitment>()?;
+        Ok(Self {
+            result: VerifiableQueryResult::new(&plan, accessor, setup),
+        })
+    }
+
+    /// Verify the join, returning a table with a column for each column of the query and a row for
+    /// each pair of joined rows.
+    pub fn verify(
+        &self,
+        expr: &LookupJoinExpr,
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<OwnedTable<CP::Scalar>, LookupJoinError> {
+        let plan = expr.plan::<CP::Commitment>()?;
+        let table = self.result.verify(&plan, accessor, setup)?.table;
+        Ok(expr.combine(&table))
+    }
+}
+
+/// The filter plan of a [`LookupJoinExpr`] along with its `VALUES` table.
+///
+/// The plan is proven as is, but the `VALUES` table is serialized with it, so that it is absorbed
+/// into the transcript as a public input of the proof.
+#[derive(Debug, Serialize)]
+struct LookupJoinPlan<C: Commitment> {
+    plan: ProofPlan<C>,
+    values: InlineTable,
+}
+
+impl<C: Commitment> ProofExpr<C> for LookupJoinPlan<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.plan.count(builder, accessor)
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.plan.get_length(accessor)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.plan.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<(), ProofError> {
+        self.plan.verifier_evaluate(builder, accessor, result)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.plan.get_column_references()
+    }
+
+    fn get_selected_row_count(&self, result: &OwnedTable<C::Scalar>) -> usize {
+        self.plan.get_selected_row_count(result)
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for LookupJoinPlan<C> {
+    fn result_evaluate<'a>(
+        &self,
+        builder: &mut ResultBuilder<'a>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.plan.result_evaluate(builder, alloc, accessor);
+    }
+
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut ProofBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.plan.prover_evaluate(builder, alloc, accessor);
+    }
+}
+
+/// Returns the type of a column of a `VALUES` table, which is the type of all of its literals.
+///
+/// Integer columns are `BIGINT` unless one of their literals only fits into an `INT128`.
+fn values_column_type(values: &InlineTable, index: usize) -> ConversionResult<ColumnType> {
+    values
+        .rows
+        .iter()
+        .map(|row| match &row[index] {
+            Literal::Boolean(_) => Ok(ColumnType::Boolean),
+            Literal::BigInt(_) => Ok(ColumnType::BigInt),
+            Literal::Int128(_) => Ok(ColumnType::Int128),
+            Literal::VarChar(_) => Ok(ColumnType::VarChar),
+            Literal::Decimal(_) | Literal::Timestamp(_) => {
+                Err(ConversionError::Unprovable(format!(
+                    "column '{}' of a VALUES table can only hold boolean, integer or varchar \
+                     literals",
+                    values.columns[index]
+                )))
+            }
+        })
+        .reduce(|left, right| match (left?, right?) {
+            (left, right) if left == right => Ok(left),
+            (ColumnType::BigInt | ColumnType::Int128, ColumnType::BigInt | ColumnType::Int128) => {
+                Ok(ColumnType::Int128)
+            }
+            (left, right) => Err(ConversionError::DataTypeMismatch(
+                left.to_string(),
+                right.to_string(),
+            )),
+        })
+        .expect("a VALUES table has at least one row")
+}
+
+/// Returns a column of a `VALUES` table with the entries at the given rows.
+fn values_column<S: Scalar>(
+    values: &InlineTable,
+    index: usize,
+    column_type: ColumnType,
+    rows: &[usize],
+) -> OwnedColumn<S> {
+    let literals = rows.iter().map(|&row| &values.rows[row][index]);
+    let unexpected = "the types of VALUES columns are checked when the join is resolved";
+    match column_type {
+        ColumnType::Boolean => OwnedColumn::Boolean(
+            literals
+                .map(|literal| match literal {
+                    Literal::Boolean(value) => *value,
+                    _ => unreachable!("{unexpected}"),
+                })
+                .collect(),
+        ),
+        ColumnType::BigInt => OwnedColumn::BigInt(
+            literals
+                .map(|literal| match literal {
+                    Literal::BigInt(value) => *value,
+                    _ => unreachable!("{unexpected}"),
+                })
+                .collect(),
+        ),
+        ColumnType::Int128 => OwnedColumn::Int128(
+            literals
+                .map(|literal| match literal {
+                    Literal::BigInt(value) => (*value).into(),
+                    Literal::Int128(value) => *value,
+                    _ => unreachable!("{unexpected}"),
+                })
+                .collect(),
+        ),
+        ColumnType::VarChar => OwnedColumn::VarChar(
+            literals
+                .map(|literal| match literal {
+                    Literal::VarChar(value) => value.clone(),
+                    _ => unreachable!("{unexpected}"),
+                })
+                .collect(),
+        ),
+        _ => unreachable!("{unexpected}"),
+    }
+}
+
+/// Returns the key of a literal of the key column of a `VALUES` table.
+fn literal_key(literal: &Literal) -> LookupKey {
+    match literal {
+        Literal::BigInt(value) => LookupKey::Integer((*value).into()),
+        Literal::Int128(value) => LookupKey::Integer(*value),
+        Literal::VarChar(value) => LookupKey::VarChar(value.clone()),
+        _ => unreachable!("key columns are checked when the join is resolved"),
+    }
+}
+
+/// Returns the keys of a verified key column.
+pub(super) fn column_keys<S: Scalar>(column: &OwnedColumn<S>) -> Vec<LookupKey> {
+    match column {
+        OwnedColumn::SmallInt(values) => values
+            .iter()
+            .map(|&value| LookupKey::Integer(value.into()))
+            .collect(),
+        OwnedColumn::Int(values) => values
+            .iter()
+            .map(|&value| LookupKey::Integer(value.into()))
+            .collect(),
+        OwnedColumn::BigInt(values) => values
+            .iter()
+            .map(|&value| LookupKey::Integer(value.into()))
+            .collect(),
+        OwnedColumn::Int128(values) => values.iter().copied().map(LookupKey::Integer).collect(),
+        OwnedColumn::VarChar(values) => values.iter().cloned().map(LookupKey::VarChar).collect(),
+        _ => unreachable!("key columns are checked when the join is resolved"),
+    }
+}
+
+/// Returns whether a key of a `VALUES` table is in the range of the key column of the table.
+///
+/// Keys outside of the range cannot match any row, and comparing the column with them would not
+/// type check.
+fn key_fits(literal: &Literal, column_type: ColumnType) -> bool {
+    let LookupKey::Integer(value) = literal_key(literal) else {
+        return true;
+    };
+    match column_type {
+        ColumnType::SmallInt => i16::try_from(value).is_ok(),
+        ColumnType::Int => i32::try_from(value).is_ok(),
+        ColumnType::BigInt => i64::try_from(value).is_ok(),
+        _ => true,
+    }
+}
@@ -0,0 +1,213 @@
+use super::{ConversionError, LookupJoinError, LookupJoinExpr, LookupJoinProof, MAX_LOOKUP_ROWS};
+use crate::base::{
+    commitment::InnerProductProof,
+    database::{
+        owned_table_utility::*, ColumnRef, ColumnType, OwnedTable, OwnedTableTestAccessor,
+        TestAccessor,
+    },
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::utility::ident;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            smallint("k", [1_i16, 2, 3, 1, 4]),
+            bigint("amount", [10, 20, 30, 40, 50]),
+            varchar("owner", ["x", "y", "x", "z", "x"]),
+            boolean("flag", [true, false, true, true, false]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn lookup_query(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Result<LookupJoinExpr, ConversionError> {
+    LookupJoinExpr::try_new(sql.parse().unwrap(), ident("sxt"), accessor)
+}
+
+fn prove_and_verify(
+    sql: &str,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> OwnedTable<Curve25519Scalar> {
+    let expr = lookup_query(sql, accessor).unwrap();
+    let proof = LookupJoinProof::<InnerProductProof>::new(&expr, accessor, &()).unwrap();
+    proof.verify(&expr, accessor, &()).unwrap()
+}
+
+#[test]
+fn we_can_enrich_the_rows_of_a_table_with_a_values_table() {
+    let accessor = accessor();
+    let sql = "select t.amount, v.label from t \
+               join (values (1, 'one'), (4, 'four'), (1, 'uno')) as v(k, label) on t.k = v.k \
+               where t.amount >= 20";
+    assert_eq!(
+        lookup_query(sql, &accessor).unwrap().key_column(),
+        ColumnRef::new("sxt.t".parse().unwrap(), ident("k"), ColumnType::SmallInt)
+    );
+    assert_eq!(
+        prove_and_verify(sql, &accessor),
+        owned_table([
+            bigint("amount", [40, 40, 50]),
+            varchar("label", ["one", "uno", "four"]),
+        ])
+    );
+    assert_eq!(
+        prove_and_verify(
+            "select v.k as key, t.owner from t join (values (2), (3)) as v(k) on t.k = v.k",
+            &accessor
+        ),
+        owned_table([bigint("key", [2, 3]), varchar("owner", ["y", "x"])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_a_lookup_join_on_varchar_keys() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select v.rank, t.amount from t \
+             join (values ('x', 1), ('z', 2)) as v(name, rank) on v.name = t.owner",
+            &accessor
+        ),
+        owned_table([
+            bigint("rank", [1, 1, 2, 1]),
+            bigint("amount", [10, 30, 40, 50])
+        ])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_a_lookup_join_without_any_rows() {
+    let accessor = accessor();
+    assert_eq!(
+        prove_and_verify(
+            "select t.amount, v.label from t \
+             join (values (7, 'seven'), (100000, 'big')) as v(k, label) on t.k = v.k",
+            &accessor
+        ),
+        owned_table([bigint("amount", [0; 0]), varchar("label", [""; 0])])
+    );
+}
+
+#[test]
+fn we_cannot_resolve_a_lookup_join_with_too_many_values_rows() {
+    let accessor = accessor();
+    let rows = (0..=MAX_LOOKUP_ROWS)
+        .map(|row| format!("({row}, 'a')"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    assert!(matches!(
+        lookup_query(
+            &format!("select v.label from t join (values {rows}) as v(k, label) on t.k = v.k"),
+            &accessor
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_different_data() {
+    let accessor = accessor();
+    let mut prover_accessor = accessor.clone();
+    prover_accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            smallint("k", [1_i16, 2, 3, 1, 4]),
+            bigint("amount", [10, 20, 30, 41, 50]),
+            varchar("owner", ["x", "y", "x", "z", "x"]),
+            boolean("flag", [true, false, true, true, false]),
+        ]),
+        0,
+    );
+    let expr = lookup_query(
+        "select t.amount, v.label from t join (values (1, 'one')) as v(k, label) on t.k = v.k",
+        &accessor,
+    )
+    .unwrap();
+    let proof = LookupJoinProof::<InnerProductProof>::new(&expr, &prover_accessor, &()).unwrap();
+    assert!(matches!(
+        proof.verify(&expr, &accessor, &()),
+        Err(LookupJoinError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_against_a_different_values_table() {
+    let accessor = accessor();
+    let prover_expr = lookup_query(
+        "select t.amount, v.label from t join (values (1, 'one')) as v(k, label) on t.k = v.k",
+        &accessor,
+    )
+    .unwrap();
+    let verifier_expr = lookup_query(
+        "select t.amount, v.label from t join (values (1, 'uno')) as v(k, label) on t.k = v.k",
+        &accessor,
+    )
+    .unwrap();
+    let proof = LookupJoinProof::<InnerProductProof>::new(&prover_expr, &accessor, &()).unwrap();
+    assert!(proof.verify(&prover_expr, &accessor, &()).is_ok());
+    assert!(matches!(
+        proof.verify(&verifier_expr, &accessor, &()),
+        Err(LookupJoinError::VerificationError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_resolve_lookup_joins_with_invalid_columns() {
+    let accessor = accessor();
+    assert!(matches!(
+        lookup_query(
+            "select t.amount from t join (values ('a')) as v(k) on t.k = v.k",
+            &accessor
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        lookup_query(
+            "select v.label from t join (values (1, 'a'), (2, true)) as v(k, label) on t.k = v.k",
+            &accessor
+        ),
+        Err(ConversionError::DataTypeMismatch(_, _))
+    ));
+    assert!(matches!(
+        lookup_query(
+            "select t.missing from t join (values (1)) as v(k) on t.k = v.k",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        lookup_query(
+            "select t.amount from t join (values (1)) as v(k) on t.missing = v.k",
+            &accessor
+        ),
+        Err(ConversionError::MissingColumn(_, _))
+    ));
+    assert!(matches!(
+        lookup_query(
+            "select t.amount as x, v.k as x from t join (values (1)) as v(k) on t.k = v.k",
+            &accessor
+        ),
+        Err(ConversionError::DuplicateResultAlias(_))
+    ));
+    assert!(matches!(
+        lookup_query(
+            "select v.price from t join (values (1, 1.5)) as v(k, price) on t.k = v.k",
+            &accessor
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+    assert!(matches!(
+        lookup_query(
+            "select t.amount from t join (values (true)) as v(flag) on t.flag = v.flag",
+            &accessor
+        ),
+        Err(ConversionError::Unprovable(_))
+    ));
+}
@@ -4,6 +4,14 @@ mod error;
 pub use error::ConversionError;
 pub(crate) use error::ConversionResult;
 
+mod diagnostics;
+pub use diagnostics::Diagnostics;
+
+mod function_registry;
+pub use function_registry::{FunctionRegistry, ProvableFunction};
+#[cfg(test)]
+mod function_registry_test;
+
 mod enriched_expr;
 pub(crate) use enriched_expr::EnrichedExpr;
 
@@ -13,6 +21,43 @@ mod query_expr_tests;
 mod query_expr;
 pub use query_expr::QueryExpr;
 
+mod exists_query_expr;
+pub use exists_query_expr::{ExistsQueryError, ExistsQueryExpr, ExistsQueryProof, MAX_EXISTS_KEYS};
+#[cfg(all(test, feature = "blitzar"))]
+mod exists_query_expr_test;
+
+mod join_aggregate_expr;
+pub use join_aggregate_expr::{
+    JoinAggregateError, JoinAggregateExpr, JoinAggregateProof, MAX_JOIN_KEYS,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod join_aggregate_expr_test;
+
+mod lookup_join_expr;
+pub use lookup_join_expr::{LookupJoinError, LookupJoinExpr, LookupJoinProof, MAX_LOOKUP_ROWS};
+#[cfg(all(test, feature = "blitzar"))]
+mod lookup_join_expr_test;
+
+mod in_list_join_expr;
+pub use in_list_join_expr::{InListJoinError, InListJoinExpr, InListJoinProof, MAX_BUILD_ROWS};
+#[cfg(all(test, feature = "blitzar"))]
+mod in_list_join_expr_test;
+
+mod limit_query_expr;
+pub use limit_query_expr::{LimitQueryError, LimitQueryExpr, LimitQueryProof};
+#[cfg(all(test, feature = "blitzar"))]
+mod limit_query_expr_test;
+
+mod snapshot_query_expr;
+pub use snapshot_query_expr::{SnapshotQueryError, SnapshotQueryExpr, SnapshotQueryProof};
+#[cfg(all(test, feature = "blitzar"))]
+mod snapshot_query_expr_test;
+
+mod snapshot_diff;
+pub use snapshot_diff::{SnapshotDiff, SnapshotDiffProof};
+#[cfg(all(test, feature = "blitzar"))]
+mod snapshot_diff_test;
+
 mod result_expr_builder;
 pub(crate) use result_expr_builder::ResultExprBuilder;
 
@@ -23,7 +68,9 @@ pub(crate) mod query_context;
 pub(crate) use query_context::QueryContext;
 
 mod query_context_builder;
-pub(crate) use query_context_builder::{type_check_binary_operation, QueryContextBuilder};
+pub(crate) use query_context_builder::{
+    type_check_aggregation, type_check_binary_operation, QueryContextBuilder,
+};
 
 mod provable_expr_plan_builder;
 pub(crate) use provable_expr_plan_builder::ProvableExprPlanBuilder;
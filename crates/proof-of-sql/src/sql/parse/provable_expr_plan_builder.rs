@@ -1,12 +1,12 @@
-use super::ConversionError;
+use super::{ConversionError, ProvableFunction};
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, LiteralValue},
+        database::{ColumnRef, ColumnType, LiteralValue},
         math::decimal::{try_into_to_scalar, DecimalError::InvalidPrecision, Precision},
     },
     sql::{
-        ast::{ColumnExpr, ProvableExpr, ProvableExprPlan},
+        ast::{ColumnExpr, OverflowPolicy, ProvableExpr, ProvableExprPlan},
         parse::ConversionError::DecimalConversionError,
     },
 };
@@ -22,6 +22,7 @@ use proof_of_sql_parser::{
 pub struct ProvableExprPlanBuilder<'a> {
     column_mapping: &'a IndexMap<Identifier, ColumnRef>,
     in_agg_scope: bool,
+    overflow_policy: OverflowPolicy,
 }
 
 impl<'a> ProvableExprPlanBuilder<'a> {
@@ -30,6 +31,7 @@ impl<'a> ProvableExprPlanBuilder<'a> {
         Self {
             column_mapping,
             in_agg_scope: false,
+            overflow_policy: OverflowPolicy::default(),
         }
     }
     /// Creates a new `ProvableExprPlanBuilder` with the given column mapping and within aggregation scope.
@@ -37,8 +39,14 @@ impl<'a> ProvableExprPlanBuilder<'a> {
         Self {
             column_mapping,
             in_agg_scope: true,
+            overflow_policy: OverflowPolicy::default(),
         }
     }
+    /// Sets how the arithmetic expressions that are built handle overflows.
+    pub(crate) fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
     /// Builds a `proofs::sql::ast::ProvableExprPlan` from a `proof_of_sql_parser::intermediate_ast::Expression`
     pub fn build<C: Commitment>(
         &self,
@@ -60,6 +68,7 @@ impl ProvableExprPlanBuilder<'_> {
             Expression::Binary { op, left, right } => self.visit_binary_expr(*op, left, right),
             Expression::Unary { op, expr } => self.visit_unary_expr(*op, expr),
             Expression::Aggregation { op, expr } => self.visit_aggregate_expr(*op, expr),
+            Expression::Function { name, args } => self.visit_function(*name, args),
             _ => Err(ConversionError::Unprovable(format!(
                 "Expression {:?} is not supported yet",
                 expr
@@ -154,16 +163,19 @@ impl ProvableExprPlanBuilder<'_> {
                 ProvableExprPlan::try_new_or(left?, right?)
             }
             BinaryOperator::Equal => {
+                self.check_literal_range(left, right)?;
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
                 ProvableExprPlan::try_new_equals(left?, right?)
             }
             BinaryOperator::GreaterThanOrEqual => {
+                self.check_literal_range(left, right)?;
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
                 ProvableExprPlan::try_new_inequality(left?, right?, false)
             }
             BinaryOperator::LessThanOrEqual => {
+                self.check_literal_range(left, right)?;
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
                 ProvableExprPlan::try_new_inequality(left?, right?, true)
@@ -171,25 +183,105 @@ impl ProvableExprPlanBuilder<'_> {
             BinaryOperator::Add => {
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
-                ProvableExprPlan::try_new_add(left?, right?)
+                ProvableExprPlan::try_new_add_with_overflow_policy(
+                    left?,
+                    right?,
+                    self.overflow_policy,
+                )
             }
             BinaryOperator::Subtract => {
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
-                ProvableExprPlan::try_new_subtract(left?, right?)
+                ProvableExprPlan::try_new_subtract_with_overflow_policy(
+                    left?,
+                    right?,
+                    self.overflow_policy,
+                )
             }
             BinaryOperator::Multiply => {
                 let left = self.visit_expr(left);
                 let right = self.visit_expr(right);
-                ProvableExprPlan::try_new_multiply(left?, right?)
+                ProvableExprPlan::try_new_multiply_with_overflow_policy(
+                    left?,
+                    right?,
+                    self.overflow_policy,
+                )
             }
             BinaryOperator::Division => Err(ConversionError::Unprovable(format!(
                 "Binary operator {:?} is not supported at this location",
                 op
             ))),
+            BinaryOperator::Modulo => match right {
+                Expression::Literal(Literal::BigInt(modulus)) => {
+                    ProvableExprPlan::try_new_modulo(self.visit_expr(left)?, *modulus)
+                }
+                _ => Err(ConversionError::Unprovable(
+                    "the modulus of % must be an integer literal".to_string(),
+                )),
+            },
+        }
+    }
+
+    /// Checks that an integer or decimal literal compared against a decimal column fits in the column's type.
+    ///
+    /// Without this check, a literal such as `123456.7` compared against a `DECIMAL(5, 2)` column
+    /// would silently produce a comparison that can never hold.
+    fn check_literal_range(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<(), ConversionError> {
+        let (identifier, lit) = match (left, right) {
+            (Expression::Column(identifier), Expression::Literal(lit))
+            | (Expression::Literal(lit), Expression::Column(identifier)) => (identifier, lit),
+            _ => return Ok(()),
+        };
+        let Some(column_ref) = self.column_mapping.get(identifier) else {
+            return Ok(());
+        };
+        let column_type = *column_ref.column_type();
+        let out_of_range_literal = match (lit, column_type) {
+            (Literal::BigInt(i), _) if !integer_literal_fits(*i as i128, column_type) => {
+                Some(i.to_string())
+            }
+            (Literal::Int128(i), _) if !integer_literal_fits(*i, column_type) => {
+                Some(i.to_string())
+            }
+            (Literal::Decimal(d), ColumnType::Decimal75(precision, scale))
+                if d.precision() as i16 - d.scale() as i16
+                    > precision.value() as i16 - scale as i16 =>
+            {
+                Some(d.to_string())
+            }
+            _ => None,
+        };
+        match out_of_range_literal {
+            Some(literal) => Err(ConversionError::LiteralOutOfRange {
+                literal,
+                column_type,
+            }),
+            None => Ok(()),
         }
     }
 
+    /// Builds a call of a gadget.
+    ///
+    /// Calls are resolved against the [`FunctionRegistry`](super::FunctionRegistry) when the
+    /// query context is built, which leaves them with the name of their gadget.
+    fn visit_function<C: Commitment>(
+        &self,
+        name: Identifier,
+        args: &[Expression],
+    ) -> Result<ProvableExprPlan<C>, ConversionError> {
+        let function = ProvableFunction::from_name(name)
+            .ok_or_else(|| ConversionError::UnregisteredFunction(Box::new(name)))?;
+        let args = args
+            .iter()
+            .map(|arg| self.visit_expr(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        function.try_build(args)
+    }
+
     fn visit_aggregate_expr<C: Commitment>(
         &self,
         op: AggregationOperator,
@@ -200,7 +292,9 @@ impl ProvableExprPlanBuilder<'_> {
                 "nested aggregations are invalid".to_string(),
             ));
         }
-        let expr = ProvableExprPlanBuilder::new_agg(self.column_mapping).visit_expr(expr)?;
+        let expr = ProvableExprPlanBuilder::new_agg(self.column_mapping)
+            .with_overflow_policy(self.overflow_policy)
+            .visit_expr(expr)?;
         match (op, expr.data_type().is_numeric()) {
             (AggregationOperator::Count, _) | (AggregationOperator::Sum, true) => {
                 Ok(ProvableExprPlan::new_aggregate(op, expr))
@@ -216,3 +310,18 @@ impl ProvableExprPlanBuilder<'_> {
         }
     }
 }
+
+/// Whether the integer literal `value` can be represented by a column of type `column_type`.
+///
+/// Integer columns are compared against integer literals in the scalar field, so any literal is
+/// accepted there. Decimal columns can only hold `precision - scale` integer digits, and 0 has
+/// none, so it fits any decimal column.
+fn integer_literal_fits(value: i128, column_type: ColumnType) -> bool {
+    match column_type {
+        ColumnType::Decimal75(precision, scale) => {
+            let digits = value.unsigned_abs().checked_ilog10().map_or(0, |d| d + 1) as i16;
+            digits <= precision.value() as i16 - scale as i16
+        }
+        _ => true,
+    }
+}
@@ -4,7 +4,10 @@ use crate::{
         database::{ColumnRef, ColumnType, LiteralValue, TableRef},
     },
     sql::{
-        ast::{AliasedProvableExprPlan, ColumnExpr, GroupByExpr, ProvableExprPlan, TableExpr},
+        ast::{
+            AliasedProvableExprPlan, ColumnExpr, GroupByExpr, OverflowPolicy, ProvableExprPlan,
+            TableExpr,
+        },
         parse::{ConversionError, ConversionResult, ProvableExprPlanBuilder, WhereExprBuilder},
     },
 };
@@ -30,6 +33,7 @@ pub struct QueryContext {
     res_aliased_exprs: Vec<AliasedResultExpr>,
     column_mapping: IndexMap<Identifier, ColumnRef>,
     first_result_col_out_agg_scope: Option<Identifier>,
+    overflow_policy: OverflowPolicy,
 }
 
 impl QueryContext {
@@ -214,6 +218,14 @@ impl QueryContext {
     pub fn get_column_mapping(&self) -> IndexMap<Identifier, ColumnRef> {
         self.column_mapping.clone()
     }
+
+    pub fn set_overflow_policy(&mut self, overflow_policy: OverflowPolicy) {
+        self.overflow_policy = overflow_policy;
+    }
+
+    pub fn get_overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
 }
 
 /// Converts a `QueryContext` into a `Option<GroupByExpr>`.
@@ -225,6 +237,7 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
 
     fn try_from(value: &QueryContext) -> Result<Option<GroupByExpr<C>>, Self::Error> {
         let where_clause = WhereExprBuilder::new(&value.column_mapping)
+            .with_overflow_policy(value.overflow_policy)
             .build(value.where_expr.clone())?
             .unwrap_or_else(|| ProvableExprPlan::new_literal(LiteralValue::Boolean(true)));
         let table = value.table.map(|table_ref| TableExpr { table_ref }).ok_or(
@@ -234,17 +247,22 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
         let group_by_exprs = value
             .group_by_exprs
             .iter()
-            .map(|expr| -> Result<ColumnExpr<C>, ConversionError> {
-                value
-                    .column_mapping
-                    .get(expr)
-                    .ok_or(ConversionError::MissingColumn(
-                        Box::new(*expr),
-                        Box::new(resource_id),
-                    ))
-                    .map(|column_ref| ColumnExpr::<C>::new(*column_ref))
-            })
-            .collect::<Result<Vec<ColumnExpr<C>>, ConversionError>>()?;
+            .map(
+                |expr| -> Result<AliasedProvableExprPlan<C>, ConversionError> {
+                    value
+                        .column_mapping
+                        .get(expr)
+                        .ok_or(ConversionError::MissingColumn(
+                            Box::new(*expr),
+                            Box::new(resource_id),
+                        ))
+                        .map(|column_ref| AliasedProvableExprPlan {
+                            alias: *expr,
+                            expr: ProvableExprPlan::Column(ColumnExpr::<C>::new(*column_ref)),
+                        })
+                },
+            )
+            .collect::<Result<Vec<AliasedProvableExprPlan<C>>, ConversionError>>()?;
         // For a query to be provable the result columns must be of one of three kinds below:
         // 1. Group by columns (it is mandatory to have all of them in the correct order)
         // 2. Sum(expr) expressions (it is optional to have any)
@@ -290,7 +308,9 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExpr<C>> {
                 } = (*res.expr).clone()
                 {
                     let res_provable_expr_plan =
-                        ProvableExprPlanBuilder::new(&value.column_mapping).build(&res.expr);
+                        ProvableExprPlanBuilder::new(&value.column_mapping)
+                            .with_overflow_policy(value.overflow_policy)
+                            .build(&res.expr);
                     res_provable_expr_plan
                         .ok()
                         .map(|provable_expr_plan| AliasedProvableExprPlan {
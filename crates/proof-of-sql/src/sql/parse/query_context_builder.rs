@@ -1,16 +1,20 @@
-use super::{ConversionError, ConversionResult, QueryContext};
-use crate::base::{
-    database::{
-        try_add_subtract_column_types, try_multiply_column_types, ColumnRef, ColumnType,
-        SchemaAccessor, TableRef,
+use super::{ConversionError, ConversionResult, FunctionRegistry, ProvableFunction, QueryContext};
+use crate::{
+    base::{
+        database::{
+            multiplicity_column_id, try_add_subtract_column_types, try_multiply_column_types,
+            ColumnRef, ColumnType, SchemaAccessor, TableRef,
+        },
+        math::decimal::Precision,
     },
-    math::decimal::Precision,
+    sql::{ast::OverflowPolicy, proof::EvaluationContext},
 };
 use proof_of_sql_parser::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        SelectResultExpr, Slice, TableExpression, UnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator, ContextVariable, Expression,
+        Literal, OrderBy, SelectResultExpr, Slice, TableExpression, UnaryOperator,
     },
+    posql_time::PoSQLTimestamp,
     Identifier, ResourceId,
 };
 use std::ops::Deref;
@@ -18,14 +22,25 @@ use std::ops::Deref;
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
     schema_accessor: &'a dyn SchemaAccessor,
+    evaluation_context: EvaluationContext,
+    function_registry: &'a FunctionRegistry,
 }
 
 // Public interface
 impl<'a> QueryContextBuilder<'a> {
-    pub fn new(schema_accessor: &'a dyn SchemaAccessor) -> Self {
+    pub fn new(
+        schema_accessor: &'a dyn SchemaAccessor,
+        evaluation_context: EvaluationContext,
+        function_registry: &'a FunctionRegistry,
+        overflow_policy: OverflowPolicy,
+    ) -> Self {
+        let mut context = QueryContext::default();
+        context.set_overflow_policy(overflow_policy);
         Self {
-            context: QueryContext::default(),
+            context,
             schema_accessor,
+            evaluation_context,
+            function_registry,
         }
     }
 
@@ -102,9 +117,16 @@ impl<'a> QueryContextBuilder<'a> {
 
 // Private interface
 impl<'a> QueryContextBuilder<'a> {
+    /// Returns the columns of the table that queries can reference, which excludes the
+    /// multiplicity column of retractable tables.
     fn lookup_schema(&self) -> Vec<(Identifier, ColumnType)> {
         let table_ref = self.context.get_table_ref();
-        let columns = self.schema_accessor.lookup_schema(*table_ref);
+        let columns: Vec<_> = self
+            .schema_accessor
+            .lookup_schema(*table_ref)
+            .into_iter()
+            .filter(|(column_name, _)| *column_name != multiplicity_column_id())
+            .collect();
         assert!(!columns.is_empty(), "At least one column must exist");
         columns
     }
@@ -126,7 +148,9 @@ impl<'a> QueryContextBuilder<'a> {
     /// Visits the expression and returns its data type.
     ///
     /// This function accepts the expression as a mutable reference because certain expressions
-    /// require replacement, such as `count(*)` being replaced with `count(some_column)`.
+    /// require replacement, such as `count(*)` being replaced with `count(some_column)`
+    /// and context variables being replaced with the literals they are bound to. Calls of functions
+    /// are resolved against the registry and renamed to the gadget they are resolved to.
     fn visit_expr(&mut self, expr: &mut Expression) -> ConversionResult<ColumnType> {
         match expr {
             Expression::Wildcard => self.visit_wildcard_expr(expr),
@@ -135,6 +159,47 @@ impl<'a> QueryContextBuilder<'a> {
             Expression::Unary { op, expr } => self.visit_unary_expr(op, expr),
             Expression::Binary { op, left, right } => self.visit_binary_expr(op, left, right),
             Expression::Aggregation { op, expr } => self.visit_agg_expr(op, expr),
+            Expression::Exists { .. } => Err(ConversionError::UnboundExists),
+            Expression::Function { name, args } => {
+                let function = self.function_registry.resolve(*name)?;
+                match function.context_variable() {
+                    Some(variable) => {
+                        function.check_argument_count(args.len())?;
+                        *expr = Expression::Literal(self.bind_context_variable(variable)?);
+                        self.visit_expr(expr)
+                    }
+                    None => self.visit_function_expr(function, name, args),
+                }
+            }
+        }
+    }
+
+    fn visit_function_expr(
+        &mut self,
+        function: ProvableFunction,
+        name: &mut Identifier,
+        args: &mut [Expression],
+    ) -> ConversionResult<ColumnType> {
+        *name = function.name();
+        let arg_types = args
+            .iter_mut()
+            .map(|arg| self.visit_expr(arg))
+            .collect::<ConversionResult<Vec<_>>>()?;
+        function.return_type(&arg_types)
+    }
+
+    fn bind_context_variable(&self, variable: ContextVariable) -> ConversionResult<Literal> {
+        let unbound = ConversionError::UnboundContextVariable(variable);
+        match variable {
+            ContextVariable::Now => {
+                let current_time = self.evaluation_context.current_time().ok_or(unbound)?;
+                Ok(Literal::Timestamp(PoSQLTimestamp::to_timestamp(
+                    current_time,
+                )?))
+            }
+            ContextVariable::BlockHeight => Ok(Literal::BigInt(
+                self.evaluation_context.block_height().ok_or(unbound)?,
+            )),
         }
     }
 
@@ -180,6 +245,10 @@ impl<'a> QueryContextBuilder<'a> {
         let left_dtype = self.visit_expr(left)?;
         let right_dtype = self.visit_expr(right)?;
         check_dtypes(left_dtype, right_dtype, *op)?;
+        if op == &BinaryOperator::Equal {
+            self.normalize_varchar_literal(left, right)?;
+            self.normalize_varchar_literal(right, left)?;
+        }
         match op {
             BinaryOperator::And
             | BinaryOperator::Or
@@ -188,11 +257,36 @@ impl<'a> QueryContextBuilder<'a> {
             | BinaryOperator::LessThanOrEqual => Ok(ColumnType::Boolean),
             BinaryOperator::Multiply
             | BinaryOperator::Division
+            | BinaryOperator::Modulo
             | BinaryOperator::Subtract
             | BinaryOperator::Add => Ok(left_dtype),
         }
     }
 
+    /// Normalizes a string literal compared against a VarChar column in the same way that the
+    /// strings of the column were normalized when they were committed.
+    fn normalize_varchar_literal(
+        &self,
+        column: &Expression,
+        literal: &mut Expression,
+    ) -> ConversionResult<()> {
+        if let (Expression::Column(column_id), Expression::Literal(Literal::VarChar(value))) =
+            (column, literal)
+        {
+            let normalization = self
+                .schema_accessor
+                .lookup_varchar_normalization(*self.context.get_table_ref(), *column_id);
+            if !normalization.is_supported() {
+                return Err(ConversionError::Unprovable(format!(
+                    "column {column_id} was committed with {normalization:?} normalization, which \
+                     requires the `varchar-normalization` feature"
+                )));
+            }
+            *value = normalization.normalize(value).into_owned();
+        }
+        Ok(())
+    }
+
     fn visit_unary_expr(
         &mut self,
         op: &UnaryOperator,
@@ -221,8 +315,7 @@ impl<'a> QueryContextBuilder<'a> {
 
         let expr_dtype = self.visit_expr(expr)?;
 
-        // We only support sum/max/min aggregations on numeric columns.
-        if op != &AggregationOperator::Count && expr_dtype == ColumnType::VarChar {
+        if !type_check_aggregation(*op, expr_dtype) {
             return Err(ConversionError::non_numeric_expr_in_agg(
                 expr_dtype.to_string(),
                 op.to_string(),
@@ -255,7 +348,12 @@ impl<'a> QueryContextBuilder<'a> {
 
     fn visit_column_identifier(&mut self, column_name: Identifier) -> ConversionResult<ColumnType> {
         let table_ref = self.context.get_table_ref();
-        let column_type = self.schema_accessor.lookup_column(*table_ref, column_name);
+        // The multiplicity column of a retractable table is hidden from queries
+        let column_type = if column_name == multiplicity_column_id() {
+            None
+        } else {
+            self.schema_accessor.lookup_column(*table_ref, column_name)
+        };
 
         let column_type = column_type.ok_or_else(|| {
             ConversionError::MissingColumn(Box::new(column_name), Box::new(table_ref.resource_id()))
@@ -322,9 +420,16 @@ pub(crate) fn type_check_binary_operation(
         }
         BinaryOperator::Multiply => try_multiply_column_types(*left_dtype, *right_dtype).is_ok(),
         BinaryOperator::Division => left_dtype.is_numeric() && right_dtype.is_numeric(),
+        BinaryOperator::Modulo => left_dtype.is_integer() && right_dtype.is_integer(),
     }
 }
 
+/// Checks whether an aggregation can be applied to an expression of the given type.
+pub(crate) fn type_check_aggregation(op: AggregationOperator, dtype: ColumnType) -> bool {
+    // We only support sum/max/min aggregations on numeric columns.
+    op == AggregationOperator::Count || dtype != ColumnType::VarChar
+}
+
 fn check_dtypes(
     left_dtype: ColumnType,
     right_dtype: ColumnType,
@@ -1,14 +1,21 @@
-use super::{EnrichedExpr, FilterExprBuilder, QueryContextBuilder, ResultExprBuilder};
+use super::{
+    Diagnostics, EnrichedExpr, FilterExprBuilder, FunctionRegistry, QueryContextBuilder,
+    ResultExprBuilder,
+};
 use crate::{
-    base::{commitment::Commitment, database::SchemaAccessor},
+    base::{
+        commitment::Commitment,
+        database::{lookup_multiplicity_column, MetadataAccessor, SchemaAccessor},
+    },
     sql::{
-        ast::{GroupByExpr, ProofPlan},
-        parse::ConversionResult,
+        ast::{GroupByExpr, OverflowPolicy, ProofPlan},
+        parse::{ConversionError, ConversionResult},
+        proof::{EvaluationContext, ProverCostEstimate},
         transform::ResultExpr,
     },
 };
 use proof_of_sql_parser::{
-    intermediate_ast::{AliasedResultExpr, Expression, SetExpression},
+    intermediate_ast::{AliasedResultExpr, BinaryOperator, Expression, SetExpression},
     Identifier, SelectStatement,
 };
 use serde::{Deserialize, Serialize};
@@ -46,24 +53,133 @@ impl<C: Commitment> QueryExpr<C> {
         default_schema: Identifier,
         schema_accessor: &dyn SchemaAccessor,
     ) -> ConversionResult<Self> {
+        Self::try_new_with_context(
+            ast,
+            default_schema,
+            schema_accessor,
+            EvaluationContext::default(),
+        )
+    }
+
+    /// Parse a query string into a `QueryExpr`, reporting failures as [`Diagnostics`].
+    ///
+    /// Unlike [`QueryExpr::try_new`], a failure points at where in the query the problem is and
+    /// suggests a fix when one is likely, e.g. a column with a similar name.
+    pub fn try_new_with_diagnostics(
+        query: &str,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> Result<Self, Diagnostics> {
+        let ast = SelectStatement::try_parse(query)
+            .map_err(|error| Diagnostics::from_syntax_error(query, error))?;
+        Self::try_new(ast.clone(), default_schema, schema_accessor).map_err(|error| {
+            Diagnostics::from_conversion_error(query, &ast, default_schema, schema_accessor, error)
+        })
+    }
+
+    /// Parse a statement from the `sqlparser` crate into a `QueryExpr`.
+    ///
+    /// The statement is lowered into the intermediate AST first, so it is subject to the same
+    /// restrictions as a query parsed by `proof_of_sql_parser`.
+    #[cfg(feature = "sqlparser")]
+    pub fn try_new_from_sqlparser(
+        statement: &proof_of_sql_parser::sqlparser::ast::Statement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        Self::try_new(
+            SelectStatement::try_from(statement)?,
+            default_schema,
+            schema_accessor,
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, binding context variables
+    /// such as `NOW()` to their values in `evaluation_context`.
+    ///
+    /// The prover and the verifier must plan the query with the same context, which the verifier
+    /// can read from the `VerifiableQueryResult`.
+    pub fn try_new_with_context(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        evaluation_context: EvaluationContext,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_functions(
+            ast,
+            default_schema,
+            schema_accessor,
+            evaluation_context,
+            &FunctionRegistry::default(),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, resolving calls of
+    /// functions against `function_registry`.
+    ///
+    /// See [`QueryExpr::try_new_with_context`]. Calls of functions that are not registered are
+    /// rejected with [`ConversionError::UnregisteredFunction`].
+    pub fn try_new_with_functions(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        evaluation_context: EvaluationContext,
+        function_registry: &FunctionRegistry,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_overflow_policy(
+            ast,
+            default_schema,
+            schema_accessor,
+            evaluation_context,
+            function_registry,
+            OverflowPolicy::default(),
+        )
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr` whose `+`, `-` and `*`
+    /// handle overflows according to `overflow_policy`.
+    ///
+    /// See [`QueryExpr::try_new_with_functions`] and [`OverflowPolicy`]. Postprocessing cannot
+    /// enforce a policy other than [`OverflowPolicy::Report`], so with such a policy arithmetic
+    /// that is not provable is rejected with [`ConversionError::Unprovable`].
+    pub fn try_new_with_overflow_policy(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        evaluation_context: EvaluationContext,
+        function_registry: &FunctionRegistry,
+        overflow_policy: OverflowPolicy,
+    ) -> ConversionResult<Self> {
+        if ast.as_of.is_some() {
+            return Err(ConversionError::UnboundSnapshot);
+        }
         let context = match *ast.expr {
             SetExpression::Query {
                 result_exprs,
                 from,
                 where_expr,
                 group_by,
-            } => QueryContextBuilder::new(schema_accessor)
-                .visit_table_expr(from, default_schema)
-                .visit_group_by_exprs(group_by)?
-                .visit_result_exprs(result_exprs)?
-                .visit_where_expr(where_expr)?
-                .visit_order_by_exprs(ast.order_by)
-                .visit_slice_expr(ast.slice)
-                .build()?,
+            } => QueryContextBuilder::new(
+                schema_accessor,
+                evaluation_context,
+                function_registry,
+                overflow_policy,
+            )
+            .visit_table_expr(from, default_schema)
+            .visit_group_by_exprs(group_by)?
+            .visit_result_exprs(result_exprs)?
+            .visit_where_expr(where_expr)?
+            .visit_order_by_exprs(ast.order_by)
+            .visit_slice_expr(ast.slice)
+            .build()?,
         };
         let result_aliased_exprs = context.get_aliased_result_exprs()?;
+        let multiplicity_column =
+            lookup_multiplicity_column(schema_accessor, *context.get_table_ref());
         let group_by = context.get_group_by_exprs();
-        if !group_by.is_empty() {
+        // Retractions have to be netted out before the records are grouped, so a query against a
+        // retractable table is always grouped in postprocessing.
+        if !group_by.is_empty() && multiplicity_column.is_none() {
             if let Some(group_by_expr) = Option::<GroupByExpr<C>>::try_from(&context)? {
                 // If the group by expression is provable the projection step is just identity.
                 let new_result_aliased_exprs = result_aliased_exprs
@@ -86,20 +202,69 @@ impl<C: Commitment> QueryExpr<C> {
             }
         }
         let column_mapping = context.get_column_mapping();
-        let enriched_exprs = result_aliased_exprs
+        let mut enriched_exprs = result_aliased_exprs
             .iter()
-            .map(|aliased_expr| EnrichedExpr::new(aliased_expr.clone(), column_mapping.clone()))
+            .map(|aliased_expr| {
+                EnrichedExpr::new(
+                    aliased_expr.clone(),
+                    column_mapping.clone(),
+                    overflow_policy,
+                )
+            })
             .collect::<Vec<_>>();
+        // If any result expression is not provable, the referenced columns are sent in the proof
+        // result under their own names. A provable expression whose alias shadows one of those
+        // columns would collide with it, so it has to be evaluated in postprocessing instead.
+        if enriched_exprs
+            .iter()
+            .any(|enriched_expr| !enriched_expr.is_provable())
+        {
+            enriched_exprs = result_aliased_exprs
+                .iter()
+                .zip(enriched_exprs)
+                .map(|(aliased_expr, enriched_expr)| {
+                    let shadows_column = column_mapping.contains_key(&aliased_expr.alias)
+                        && *aliased_expr.expr != Expression::Column(aliased_expr.alias);
+                    if enriched_expr.is_provable() && shadows_column {
+                        EnrichedExpr::new_unprovable(aliased_expr.clone())
+                    } else {
+                        enriched_expr
+                    }
+                })
+                .collect();
+        }
+        // Function calls and `%` are only planned as gadgets, so they cannot be evaluated in
+        // postprocessing.
+        if enriched_exprs.iter().any(|enriched_expr| {
+            !enriched_expr.is_provable() && has_gadget(&enriched_expr.residue_expression.expr)
+        }) {
+            return Err(ConversionError::Unprovable(
+                "function calls and % are only supported in provable expressions".to_string(),
+            ));
+        }
+        if overflow_policy != OverflowPolicy::Report
+            && enriched_exprs.iter().any(|enriched_expr| {
+                !enriched_expr.is_provable()
+                    && has_arithmetic(&enriched_expr.residue_expression.expr)
+            })
+        {
+            return Err(ConversionError::Unprovable(format!(
+                "the {overflow_policy:?} overflow policy is only supported for provable arithmetic"
+            )));
+        }
         let select_exprs = enriched_exprs
             .iter()
             .map(|enriched_expr| enriched_expr.residue_expression.clone())
             .collect::<Vec<_>>();
         let filter = FilterExprBuilder::new(context.get_column_mapping())
+            .with_overflow_policy(overflow_policy)
             .add_table_expr(*context.get_table_ref())
             .add_where_expr(context.get_where_expr().clone())?
             .add_result_columns(&enriched_exprs)
+            .add_multiplicity_column(multiplicity_column)
             .build();
         let result = ResultExprBuilder::default()
+            .add_net_retractions_expr(multiplicity_column.is_some())
             .add_group_by_exprs(context.get_group_by_exprs(), &select_exprs)
             .add_select_exprs(&select_exprs)
             .add_order_by_exprs(context.get_order_by_exprs()?)
@@ -121,4 +286,41 @@ impl<C: Commitment> QueryExpr<C> {
     pub fn result(&self) -> &ResultExpr {
         &self.result
     }
+
+    /// Estimate the cost of proving this query from the table metadata alone.
+    ///
+    /// See [`ProverCostEstimate`] and [`crate::sql::proof::ProverAdmissionPolicy`].
+    pub fn estimate_prover_cost(&self, accessor: &dyn MetadataAccessor) -> ProverCostEstimate {
+        ProverCostEstimate::new(&self.proof_expr, accessor)
+    }
+}
+
+/// Returns whether an expression calls a function or takes a remainder.
+fn has_gadget(expr: &Expression) -> bool {
+    match expr {
+        Expression::Function { .. }
+        | Expression::Binary {
+            op: BinaryOperator::Modulo,
+            ..
+        } => true,
+        Expression::Binary { left, right, .. } => has_gadget(left) || has_gadget(right),
+        Expression::Unary { expr, .. } | Expression::Aggregation { expr, .. } => has_gadget(expr),
+        _ => false,
+    }
+}
+
+/// Returns whether an expression adds, subtracts or multiplies.
+fn has_arithmetic(expr: &Expression) -> bool {
+    match expr {
+        Expression::Binary {
+            op: BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply,
+            ..
+        } => true,
+        Expression::Binary { left, right, .. } => has_arithmetic(left) || has_arithmetic(right),
+        Expression::Unary { expr, .. } | Expression::Aggregation { expr, .. } => {
+            has_arithmetic(expr)
+        }
+        Expression::Function { args, .. } => args.iter().any(has_arithmetic),
+        _ => false,
+    }
 }
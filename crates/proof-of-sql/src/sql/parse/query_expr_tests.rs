@@ -1,9 +1,13 @@
-use super::ConversionError;
+use super::{ConversionError, ConversionResult};
 use crate::{
-    base::database::{ColumnType, TableRef, TestSchemaAccessor},
+    base::{
+        database::{ColumnType, TableRef, TestSchemaAccessor},
+        math::decimal::Precision,
+    },
     sql::{
-        ast::{test_utility::*, ProofPlan},
-        parse::QueryExpr,
+        ast::{test_utility::*, OverflowPolicy, ProofPlan, ProvableExprPlan},
+        parse::{FunctionRegistry, QueryExpr},
+        proof::EvaluationContext,
         transform::test_utility::{col as pc, *},
     },
 };
@@ -153,6 +157,69 @@ fn we_can_convert_an_ast_with_one_column() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_convert_an_ast_with_quoted_case_sensitive_columns() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            Identifier::try_new_case_sensitive("A").unwrap() => ColumnType::BigInt,
+            "a".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        r#"select "A", a as "B" from sxt_tab where "A" = a"#,
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            aliased_cols_expr_plan(t, &[(r#""A""#, r#""A""#), ("a", r#""B""#)], &accessor),
+            tab(t),
+            equal(column(t, r#""A""#, &accessor), column(t, "a", &accessor)),
+        ),
+        result(&[(r#""A""#, r#""A""#), (r#""B""#, r#""B""#)]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_get_diagnostics_for_invalid_queries() {
+    let t: TableRef = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "value".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let try_new = |query| {
+        QueryExpr::<RistrettoPoint>::try_new_with_diagnostics(query, t.schema_id(), &accessor)
+    };
+    assert!(try_new("select value from sxt_tab").is_ok());
+
+    let diagnostics = try_new("select b, valu from sxt_tab").unwrap_err();
+    assert_eq!(diagnostics.span, Some(10..14));
+    assert_eq!(diagnostics.suggestion.as_deref(), Some("value"));
+    assert!(matches!(
+        diagnostics.error,
+        Some(ConversionError::MissingColumn(..))
+    ));
+    assert!(diagnostics
+        .to_string()
+        .ends_with("at byte 10, did you mean `value`?"));
+
+    let diagnostics = try_new("selct b from sxt_tab").unwrap_err();
+    assert_eq!(diagnostics.span, Some(0..5));
+    assert!(diagnostics.expected.contains(&"select".to_string()));
+    assert_eq!(diagnostics.suggestion.as_deref(), Some("select"));
+    assert!(diagnostics.error.is_none());
+
+    let diagnostics = try_new("select b from sxt_tab where c = 1").unwrap_err();
+    assert_eq!(diagnostics.span, Some(28..29));
+    assert_eq!(diagnostics.suggestion.as_deref(), Some("b"));
+}
+
 #[test]
 fn we_can_convert_an_ast_with_one_column_and_i128_data() {
     let t = "sxt.sxt_tab".parse().unwrap();
@@ -612,6 +679,108 @@ fn we_can_convert_an_ast_using_an_aliased_column() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_convert_an_ast_with_comparisons_in_the_result_columns() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+            "c".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select a > b as gt, a < 3 as small, c <> 'x' as not_x, a >= b and c = 'y' as both from sxt_tab",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                aliased_plan(
+                    not(lte(column(t, "a", &accessor), column(t, "b", &accessor))),
+                    "gt",
+                ),
+                aliased_plan(
+                    not(gte(column(t, "a", &accessor), const_bigint(3))),
+                    "small",
+                ),
+                aliased_plan(
+                    not(equal(column(t, "c", &accessor), const_varchar("x"))),
+                    "not_x",
+                ),
+                aliased_plan(
+                    and(
+                        gte(column(t, "a", &accessor), column(t, "b", &accessor)),
+                        equal(column(t, "c", &accessor), const_varchar("y")),
+                    ),
+                    "both",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        result(&[
+            ("gt", "gt"),
+            ("small", "small"),
+            ("not_x", "not_x"),
+            ("both", "both"),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_alias_shadowing_a_column_used_by_an_unprovable_expression() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(t, "select a as b, b / 2 as c from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![select(&[
+            pc("a").alias("b"),
+            (pc("b") / lit_i64(2)).alias("c"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_dont_duplicate_columns_aliased_to_their_own_name_alongside_unprovable_expressions() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(t, "select a, b / 2 as c from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        composite_result(vec![select(&[
+            pc("a").alias("a"),
+            (pc("b") / lit_i64(2)).alias("c"),
+        ])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_cannot_convert_an_ast_with_a_nonexistent_column() {
     let t = "sxt.sxt_tab".parse().unwrap();
@@ -636,6 +805,68 @@ fn we_cannot_convert_an_ast_with_a_column_type_different_than_equal_literal() {
     invalid_query_to_provable_ast(t, "select * from sxt_tab where b = 123", &accessor);
 }
 
+#[test]
+fn we_cannot_convert_an_ast_with_an_integer_literal_out_of_range_for_a_decimal_column() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "b".parse().unwrap() => ColumnType::Decimal75(Precision::new(5).unwrap(), 2),
+        },
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select * from sxt_tab where b >= 123456")
+        .unwrap();
+    assert_eq!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::LiteralOutOfRange {
+            literal: "123456".to_string(),
+            column_type: ColumnType::Decimal75(Precision::new(5).unwrap(), 2),
+        })
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select * from sxt_tab where 1234.5 = b")
+        .unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::LiteralOutOfRange { .. })
+    ));
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select * from sxt_tab where b <= -999.99")
+        .unwrap();
+    assert!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor).is_ok()
+    );
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_zero_literal_for_a_decimal_column_without_integer_digits() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let column_type = ColumnType::Decimal75(Precision::new(2).unwrap(), 2);
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "b".parse().unwrap() => column_type,
+        },
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select * from sxt_tab where b = 0")
+        .unwrap();
+    assert!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor).is_ok()
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select * from sxt_tab where b = 1")
+        .unwrap();
+    assert_eq!(
+        QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+        Err(ConversionError::LiteralOutOfRange {
+            literal: "1".to_string(),
+            column_type,
+        })
+    );
+}
+
 #[test]
 fn we_can_convert_an_ast_with_a_schema() {
     let t = "eth.sxt_tab".parse().unwrap();
@@ -1568,6 +1799,60 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_convert_remainders_by_constants_in_the_result_expr_and_the_where_clause() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::SmallInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select a % 7 as r, b from employees where (a + b) % 2 = 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            vec![
+                aliased_plan(modulo(column(t, "a", &accessor), 7), "r"),
+                col_expr_plan(t, "b", &accessor),
+            ],
+            tab(t),
+            equal(
+                modulo(add(column(t, "a", &accessor), column(t, "b", &accessor)), 2),
+                const_bigint(1),
+            ),
+        ),
+        composite_result(vec![select(&[pc("r").alias("r"), pc("b").alias("b")])]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_remainders_by_columns_or_non_positive_constants() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+            "d".parse().unwrap() => ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+        },
+    );
+    for query in [
+        "select a % b as r from employees",
+        "select a from employees where a % b = 0",
+        "select a % 0 as r from employees",
+        "select a from employees where a % -2 = 0",
+        "select d % 2 as r from employees",
+    ] {
+        invalid_query_to_provable_ast(t, query, &accessor);
+    }
+}
+
 #[test]
 fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
 ) {
@@ -1959,3 +2244,174 @@ fn we_can_serialize_list_of_filters_from_query_expr() {
     assert_eq!(filter_exprs.len(), deserialized_as_ref.len());
     assert_eq!(filter_exprs[0], deserialized_as_ref[0]);
 }
+
+fn get_retractable_test_accessor() -> (TableRef, TestSchemaAccessor) {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::VarChar,
+            "__multiplicity__".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    (t, accessor)
+}
+
+#[test]
+fn we_can_parse_a_query_against_a_retractable_table() {
+    let (t, accessor) = get_retractable_test_accessor();
+    let ast = query_to_provable_ast(t, "select * from sxt_tab where a = 3 limit 2", &accessor);
+    let expected_ast = QueryExpr::new(
+        dense_filter(
+            cols_expr_plan(t, &["a", "b", "__multiplicity__"], &accessor),
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(3)),
+        ),
+        composite_result(vec![
+            net_retractions(),
+            select(&[pc("a").alias("a"), pc("b").alias("b")]),
+            slice(2, 0),
+        ]),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_group_the_records_of_a_retractable_table_after_netting_out_retractions() {
+    let (t, accessor) = get_retractable_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select b, sum(a) as total, count(*) as num from sxt_tab group by b",
+        &accessor,
+    );
+    // The group by is not provable, since the retractions have to be netted out first
+    assert_eq!(
+        ast.proof_expr(),
+        &dense_filter(
+            cols_expr_plan(t, &["a", "b", "__multiplicity__"], &accessor),
+            tab(t),
+            const_bool(true),
+        )
+    );
+}
+
+#[test]
+fn we_cannot_reference_the_multiplicity_column_of_a_retractable_table() {
+    let (t, accessor) = get_retractable_test_accessor();
+    invalid_query_to_provable_ast(t, "select __multiplicity__ from sxt_tab", &accessor);
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where __multiplicity__ = 1",
+        &accessor,
+    );
+}
+
+#[cfg(feature = "sqlparser")]
+#[test]
+fn we_can_convert_a_statement_parsed_by_sqlparser() {
+    use proof_of_sql_parser::sqlparser::{dialect::GenericDialect, parser::Parser};
+
+    let (t, accessor) = get_test_accessor();
+    let sql = "select s, i from sxt.t where d >= 3 and s0 = 'abc' order by i desc limit 2";
+    let statements = Parser::parse_sql(&GenericDialect {}, sql).unwrap();
+    let ast: QueryExpr<RistrettoPoint> =
+        QueryExpr::try_new_from_sqlparser(&statements[0], t.schema_id(), &accessor).unwrap();
+    assert_eq!(ast, query_to_provable_ast(t, sql, &accessor));
+
+    let statements = Parser::parse_sql(&GenericDialect {}, "select avg(i) from sxt.t").unwrap();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new_from_sqlparser(
+            &statements[0],
+            t.schema_id(),
+            &accessor
+        ),
+        Err(ConversionError::SqlParserConversionError(_))
+    ));
+}
+
+fn query_to_provable_ast_with_overflow_policy(
+    table: TableRef,
+    query: &str,
+    accessor: &TestSchemaAccessor,
+    overflow_policy: OverflowPolicy,
+) -> ConversionResult<QueryExpr<RistrettoPoint>> {
+    QueryExpr::try_new_with_overflow_policy(
+        SelectStatementParser::new().parse(query).unwrap(),
+        table.schema_id(),
+        accessor,
+        EvaluationContext::default(),
+        &FunctionRegistry::default(),
+        overflow_policy,
+    )
+}
+
+#[test]
+fn we_can_plan_arithmetic_with_an_overflow_policy() {
+    let (t, accessor) = get_test_accessor();
+    let query = "select i + i as r from sxt.t where i * i >= 4";
+    let ast = query_to_provable_ast_with_overflow_policy(
+        t,
+        query,
+        &accessor,
+        OverflowPolicy::WidenToDecimal,
+    )
+    .unwrap();
+    let expected_plan = dense_filter(
+        vec![aliased_plan(
+            ProvableExprPlan::try_new_add_with_overflow_policy(
+                column(t, "i", &accessor),
+                column(t, "i", &accessor),
+                OverflowPolicy::WidenToDecimal,
+            )
+            .unwrap(),
+            "r",
+        )],
+        tab(t),
+        gte(
+            ProvableExprPlan::try_new_multiply_with_overflow_policy(
+                column(t, "i", &accessor),
+                column(t, "i", &accessor),
+                OverflowPolicy::WidenToDecimal,
+            )
+            .unwrap(),
+            const_bigint(4),
+        ),
+    );
+    assert_eq!(ast.proof_expr(), &expected_plan);
+    // The policy is part of the plan, so the verifier has to plan with the same policy
+    assert_ne!(ast, query_to_provable_ast(t, query, &accessor));
+}
+
+#[test]
+fn we_cannot_evaluate_arithmetic_in_postprocessing_with_an_enforcing_overflow_policy() {
+    let (t, accessor) = get_test_accessor();
+    let query = "select s, sum(i * 2) as total from sxt.t group by s";
+    for overflow_policy in [OverflowPolicy::Error, OverflowPolicy::WidenToDecimal] {
+        assert!(matches!(
+            query_to_provable_ast_with_overflow_policy(t, query, &accessor, overflow_policy),
+            Err(ConversionError::Unprovable(_))
+        ));
+    }
+    assert!(query_to_provable_ast_with_overflow_policy(
+        t,
+        query,
+        &accessor,
+        OverflowPolicy::Report
+    )
+    .is_ok());
+}
+
+#[test]
+fn we_cannot_widen_arithmetic_beyond_the_precision_of_decimals() {
+    let (t, accessor) = get_test_accessor();
+    assert!(matches!(
+        query_to_provable_ast_with_overflow_policy(
+            t,
+            "select s from sxt.t where d * d >= 0",
+            &accessor,
+            OverflowPolicy::WidenToDecimal,
+        ),
+        Err(ConversionError::ColumnOperationError(_))
+    ));
+}
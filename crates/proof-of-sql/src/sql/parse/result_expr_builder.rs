@@ -1,4 +1,6 @@
-use crate::sql::transform::{CompositionExpr, GroupByExpr, OrderByExprs, SelectExpr, SliceExpr};
+use crate::sql::transform::{
+    CompositionExpr, GroupByExpr, NetRetractionsExpr, OrderByExprs, SelectExpr, SliceExpr,
+};
 use proof_of_sql_parser::{
     intermediate_ast::{AliasedResultExpr, Expression, OrderBy, Slice},
     Identifier,
@@ -8,9 +10,21 @@ use proof_of_sql_parser::{
 #[derive(Default)]
 pub struct ResultExprBuilder {
     composition: CompositionExpr,
+    has_group_by: bool,
 }
 
 impl ResultExprBuilder {
+    /// Chain a new `NetRetractionsExpr` to the current `ResultExpr` if the queried table is
+    /// retractable.
+    ///
+    /// This has to be the first transformation, since every other one works with netted records.
+    pub fn add_net_retractions_expr(mut self, is_retractable: bool) -> Self {
+        if is_retractable {
+            self.composition.add(Box::new(NetRetractionsExpr::new()));
+        }
+        self
+    }
+
     /// Chain a new `GroupByExpr` to the current `ResultExpr`.
     pub fn add_group_by_exprs(
         mut self,
@@ -22,14 +36,15 @@ impl ResultExprBuilder {
         }
         self.composition
             .add(Box::new(GroupByExpr::new(by_exprs, aliased_exprs)));
+        self.has_group_by = true;
         self
     }
 
     /// Chain a new `SelectExpr` to the current `ResultExpr`.
     pub fn add_select_exprs(mut self, aliased_exprs: &[AliasedResultExpr]) -> Self {
         assert!(!aliased_exprs.is_empty());
-        if !self.composition.is_empty() {
-            // The only transformation before a select is a group by.
+        if self.has_group_by {
+            // The only transformation before a select that changes the schema is a group by.
             // GROUP BY modifies the schema, so we need to
             // update the code to reflect the changes.
             let exprs: Vec<_> = aliased_exprs
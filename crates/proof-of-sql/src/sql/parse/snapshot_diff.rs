@@ -0,0 +1,127 @@
+use super::{SnapshotQueryError, SnapshotQueryExpr, SnapshotQueryProof};
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    database::{filter_util::filter_column_by_index, Column, DataAccessor, OwnedTable},
+    scalar::Scalar,
+};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The rows that the result of a query gained and lost between two snapshots of its table.
+///
+/// The rows are those of the verified results, so they still have to be transformed with
+/// [`QueryExpr::result`](super::QueryExpr::result) to apply e.g. `ORDER BY`. Rows are compared
+/// as a multiset, so a row that appears twice at the first snapshot and once at the second is
+/// removed once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotDiff<S: Scalar> {
+    /// The rows of the result at the second snapshot that are not in the result at the first one
+    pub added: OwnedTable<S>,
+    /// The rows of the result at the first snapshot that are not in the result at the second one
+    pub removed: OwnedTable<S>,
+}
+
+/// A proof of the results of a query at two snapshots of its table, e.g. the latest version and
+/// the version that a consumer last saw.
+///
+/// The verifier checks both results against the commitments that it retained for the snapshots
+/// and returns the [`SnapshotDiff`] between them, so a consumer that tracks the changes to a
+/// query gets a verified delta.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnapshotDiffProof<CP: CommitmentEvaluationProof> {
+    from: SnapshotQueryProof<CP>,
+    to: SnapshotQueryProof<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> SnapshotDiffProof<CP> {
+    /// Prove the query at both snapshots.
+    ///
+    /// # Panics
+    /// Panics if the snapshot queries are not the same query.
+    pub fn new(
+        from: &SnapshotQueryExpr<CP::Commitment>,
+        to: &SnapshotQueryExpr<CP::Commitment>,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        assert!(
+            from.query() == to.query(),
+            "the snapshots have to be of the same query"
+        );
+        Self {
+            from: SnapshotQueryProof::new(from, accessor, setup),
+            to: SnapshotQueryProof::new(to, accessor, setup),
+        }
+    }
+
+    /// Verify the query at both snapshots and return the difference between the results.
+    pub fn verify(
+        &self,
+        from: &SnapshotQueryExpr<CP::Commitment>,
+        to: &SnapshotQueryExpr<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<SnapshotDiff<CP::Scalar>, SnapshotQueryError> {
+        if from.query() != to.query() {
+            return Err(SnapshotQueryError::QueryMismatch);
+        }
+        let from_table = self.from.verify(from, setup)?.table;
+        let to_table = self.to.verify(to, setup)?.table;
+        Ok(SnapshotDiff {
+            added: rows_not_in(&to_table, &from_table),
+            removed: rows_not_in(&from_table, &to_table),
+        })
+    }
+}
+
+/// Returns the columns of the table.
+fn columns<'a, S: Scalar>(table: &'a OwnedTable<S>, alloc: &'a Bump) -> Vec<Column<'a, S>> {
+    table
+        .inner_table()
+        .values()
+        .map(|column| Column::from_owned_column(column, alloc))
+        .collect()
+}
+
+/// Returns the rows of each column as scalars.
+fn rows<S: Scalar>(columns: &[Column<S>]) -> Vec<Vec<S>> {
+    let num_rows = columns.first().map_or(0, Column::len);
+    (0..num_rows)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|column| column.scalar_at(row).expect("the row is in the column"))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the rows of `table` that are not in `other`, counting duplicate rows.
+fn rows_not_in<S: Scalar>(table: &OwnedTable<S>, other: &OwnedTable<S>) -> OwnedTable<S> {
+    let alloc = Bump::new();
+    let table_columns = columns(table, &alloc);
+    let mut other_rows = BTreeMap::<_, usize>::new();
+    for row in rows(&columns(other, &alloc)) {
+        *other_rows.entry(row).or_default() += 1;
+    }
+    let indexes: Vec<_> = rows(&table_columns)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, row)| match other_rows.get_mut(&row) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                None
+            }
+            _ => Some(index),
+        })
+        .collect();
+    OwnedTable::try_from_iter(table.column_names().zip(&table_columns).map(
+        |(&identifier, column)| {
+            (
+                identifier,
+                (&filter_column_by_index(&alloc, column, &indexes)).into(),
+            )
+        },
+    ))
+    .expect("every column has the selected rows")
+}
@@ -0,0 +1,124 @@
+use super::{SnapshotDiff, SnapshotDiffProof, SnapshotQueryError, SnapshotQueryExpr};
+use crate::base::{
+    commitment::{CommitmentRegistry, InnerProductProof, TableCommitment},
+    database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    scalar::Curve25519Scalar,
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn rows(length: usize) -> OwnedTable<Curve25519Scalar> {
+    let a = [1, 2, 3, 4, 5];
+    let b = ["x", "y", "z", "y", "x"];
+    owned_table([
+        bigint("a", a[..length].to_vec()),
+        varchar("b", b[..length].to_vec()),
+    ])
+}
+
+/// The latest version of the table, with 5 rows.
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table("sxt.t".parse().unwrap(), rows(5), 0);
+    accessor
+}
+
+/// A registry with versions 0, 1 and 2 of the table, with 2, 4 and 5 rows.
+fn registry() -> CommitmentRegistry<RistrettoPoint> {
+    let mut registry = CommitmentRegistry::new();
+    for length in [2, 4, 5] {
+        registry
+            .register(
+                "sxt.t".parse().unwrap(),
+                TableCommitment::from_owned_table_with_offset(&rows(length), 0, &()),
+            )
+            .unwrap();
+    }
+    registry
+}
+
+fn snapshot_query(
+    sql: &str,
+    registry: &CommitmentRegistry<RistrettoPoint>,
+) -> SnapshotQueryExpr<RistrettoPoint> {
+    SnapshotQueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), registry).unwrap()
+}
+
+fn prove_and_verify_diff(from_sql: &str, to_sql: &str) -> SnapshotDiff<Curve25519Scalar> {
+    let registry = registry();
+    let from = snapshot_query(from_sql, &registry);
+    let to = snapshot_query(to_sql, &registry);
+    let proof = SnapshotDiffProof::<InnerProductProof>::new(&from, &to, &accessor(), &());
+    proof.verify(&from, &to, &()).unwrap()
+}
+
+#[test]
+fn we_can_prove_and_verify_the_rows_added_to_a_filter_query() {
+    assert_eq!(
+        prove_and_verify_diff(
+            "SELECT a FROM t AS OF 0 WHERE b = 'y'",
+            "SELECT a FROM t WHERE b = 'y'"
+        ),
+        SnapshotDiff {
+            added: owned_table([bigint("a", [4])]),
+            removed: owned_table([bigint("a", [0; 0])]),
+        }
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_the_rows_added_and_removed_in_an_aggregation() {
+    let diff = prove_and_verify_diff(
+        "SELECT b, count(*) AS n FROM t AS OF 0 GROUP BY b",
+        "SELECT b, count(*) AS n FROM t AS OF 2 GROUP BY b",
+    );
+    // Every group of version 0 has a different count in version 2, and `z` is a new group.
+    assert_eq!(diff.removed.num_rows(), 2);
+    assert_eq!(diff.added.num_rows(), 3);
+}
+
+#[test]
+fn we_compare_the_rows_of_the_results_as_a_multiset() {
+    assert_eq!(
+        prove_and_verify_diff("SELECT b FROM t AS OF 1", "SELECT b FROM t AS OF 2"),
+        SnapshotDiff {
+            added: owned_table([varchar("b", ["x"])]),
+            removed: owned_table([varchar("b", [""; 0])]),
+        }
+    );
+    assert_eq!(
+        prove_and_verify_diff("SELECT b FROM t AS OF 2", "SELECT b FROM t AS OF 0"),
+        SnapshotDiff {
+            added: owned_table([varchar("b", [""; 0])]),
+            removed: owned_table([varchar("b", ["z", "y", "x"])]),
+        }
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_diff_of_different_queries() {
+    let registry = registry();
+    let from = snapshot_query("SELECT a FROM t AS OF 0", &registry);
+    let to = snapshot_query("SELECT a FROM t", &registry);
+    let proof = SnapshotDiffProof::<InnerProductProof>::new(&from, &to, &accessor(), &());
+    let other_to = snapshot_query("SELECT b FROM t", &registry);
+    assert!(matches!(
+        proof.verify(&from, &other_to, &()),
+        Err(SnapshotQueryError::QueryMismatch)
+    ));
+    // The verifier checks each result against its own snapshot.
+    let other_from = snapshot_query("SELECT a FROM t AS OF 1", &registry);
+    assert!(matches!(
+        proof.verify(&other_from, &to, &()),
+        Err(SnapshotQueryError::SnapshotMismatch { .. })
+    ));
+}
+
+#[test]
+#[should_panic(expected = "the snapshots have to be of the same query")]
+fn we_cannot_prove_a_diff_of_different_queries() {
+    let registry = registry();
+    let from = snapshot_query("SELECT a FROM t AS OF 0", &registry);
+    let to = snapshot_query("SELECT b FROM t", &registry);
+    SnapshotDiffProof::<InnerProductProof>::new(&from, &to, &accessor(), &());
+}
@@ -0,0 +1,241 @@
+use super::{ConversionError, QueryExpr};
+use crate::{
+    base::{
+        commitment::{Commitment, CommitmentEvaluationProof, CommitmentRegistry, QueryCommitments},
+        database::{AccessorResult, Column, ColumnRef, DataAccessor, MetadataAccessor, TableRef},
+    },
+    sql::proof::{QueryData, QueryError, VerifiableQueryResult},
+};
+use proof_of_sql_parser::{Identifier, SelectStatement};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use thiserror::Error;
+
+/// Errors that can occur when planning, proving, or verifying a [`SnapshotQueryExpr`].
+#[derive(Error, Debug)]
+pub enum SnapshotQueryError {
+    /// The query could not be planned against the schema of the snapshot.
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    /// The table of the query has not been registered.
+    #[error("table {0} has not been registered")]
+    TableNotFound(TableRef),
+    /// The table of the query has no such version.
+    #[error("table {table_ref} has no version {version}")]
+    VersionNotFound {
+        /// The table of the query
+        table_ref: TableRef,
+        /// The version that the query reads
+        version: u64,
+    },
+    /// The proof is for a different snapshot than the one the query reads.
+    #[error(
+        "the proof is for version {proven_version} of {proven_table} rather than version \
+         {expected_version} of {expected_table}"
+    )]
+    SnapshotMismatch {
+        /// The table the proof was created for
+        proven_table: TableRef,
+        /// The version the proof was created for
+        proven_version: u64,
+        /// The table the query reads
+        expected_table: TableRef,
+        /// The version the query reads
+        expected_version: u64,
+    },
+    /// The proof is for different rows than the ones the snapshot commits to.
+    #[error("the proof is for rows {proven:?} rather than the rows {expected:?} of the snapshot")]
+    RangeMismatch {
+        /// The rows the proof was created for
+        proven: Range<usize>,
+        /// The rows the snapshot commits to
+        expected: Range<usize>,
+    },
+    /// The snapshots that are compared are of different queries.
+    #[error("the snapshots that are compared are of different queries")]
+    QueryMismatch,
+    /// The underlying proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+}
+
+/// A query that reads a snapshot of its table, e.g.
+/// ```ignore
+///     SELECT a, b FROM sxt.t AS OF 3 WHERE a = 2
+/// ```
+///
+/// The snapshot is resolved against a [`CommitmentRegistry`], and a query without `AS OF` reads
+/// the latest version of its table. The prover and the verifier each resolve the query against
+/// their own registry, so the verifier checks the proof against the commitment that it retained
+/// for the snapshot rather than against one chosen by the prover.
+#[derive(Debug)]
+pub struct SnapshotQueryExpr<C: Commitment> {
+    query: QueryExpr<C>,
+    table_ref: TableRef,
+    version: u64,
+    commitments: QueryCommitments<C>,
+}
+
+impl<C: Commitment> SnapshotQueryExpr<C> {
+    /// Resolve the snapshot that an intermediate AST `SelectStatement` reads and plan the query
+    /// against it.
+    pub fn try_new(
+        mut ast: SelectStatement,
+        default_schema: Identifier,
+        registry: &CommitmentRegistry<C>,
+    ) -> Result<Self, SnapshotQueryError> {
+        let table_ref = ast
+            .get_table_references(default_schema)
+            .into_iter()
+            .next()
+            .map(TableRef::new)
+            .ok_or_else(|| {
+                ConversionError::InvalidExpression("a query must have a table".to_string())
+            })?;
+        let version = match ast.as_of.take() {
+            Some(version) => version,
+            None => registry
+                .latest_version(table_ref)
+                .ok_or(SnapshotQueryError::TableNotFound(table_ref))?,
+        };
+        let commitment = registry
+            .get(table_ref, version)
+            .ok_or(SnapshotQueryError::VersionNotFound { table_ref, version })?;
+        let commitments = QueryCommitments::from_iter([(table_ref, commitment.clone())]);
+        let query = QueryExpr::try_new(ast, default_schema, &commitments)?;
+        Ok(Self {
+            query,
+            table_ref,
+            version,
+            commitments,
+        })
+    }
+
+    /// Returns the planned query. Its verified results still have to be transformed with
+    /// [`QueryExpr::result`].
+    pub fn query(&self) -> &QueryExpr<C> {
+        &self.query
+    }
+
+    /// Returns the table that the query reads.
+    pub fn table_ref(&self) -> TableRef {
+        self.table_ref
+    }
+
+    /// Returns the version of the table that the query reads.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Returns the commitments of the snapshot.
+    pub fn commitments(&self) -> &QueryCommitments<C> {
+        &self.commitments
+    }
+
+    /// Returns the rows that the snapshot commits to.
+    fn range(&self) -> &Range<usize> {
+        self.commitments[&self.table_ref].range()
+    }
+}
+
+/// A proof of a [`SnapshotQueryExpr`], which records the snapshot that it was created against.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnapshotQueryProof<CP: CommitmentEvaluationProof> {
+    table_ref: TableRef,
+    version: u64,
+    range: Range<usize>,
+    result: VerifiableQueryResult<CP>,
+}
+
+impl<CP: CommitmentEvaluationProof> SnapshotQueryProof<CP> {
+    /// Prove the query against the snapshot.
+    ///
+    /// `accessor` may hold later versions of the table, since the rows of the snapshot are a
+    /// prefix of the rows of every later version.
+    pub fn new(
+        expr: &SnapshotQueryExpr<CP::Commitment>,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        let accessor = SnapshotDataAccessor {
+            accessor,
+            commitments: &expr.commitments,
+        };
+        Self {
+            table_ref: expr.table_ref,
+            version: expr.version,
+            range: expr.range().clone(),
+            result: VerifiableQueryResult::new(expr.query.proof_expr(), &accessor, setup),
+        }
+    }
+
+    /// Returns the version of the table that the proof was created against.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Verify the query against the snapshot, checking that the proof was created against
+    /// exactly that snapshot.
+    pub fn verify(
+        &self,
+        expr: &SnapshotQueryExpr<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<QueryData<CP::Scalar>, SnapshotQueryError> {
+        if (self.table_ref, self.version) != (expr.table_ref, expr.version) {
+            return Err(SnapshotQueryError::SnapshotMismatch {
+                proven_table: self.table_ref,
+                proven_version: self.version,
+                expected_table: expr.table_ref,
+                expected_version: expr.version,
+            });
+        }
+        if &self.range != expr.range() {
+            return Err(SnapshotQueryError::RangeMismatch {
+                proven: self.range.clone(),
+                expected: expr.range().clone(),
+            });
+        }
+        Ok(self
+            .result
+            .verify(expr.query.proof_expr(), &expr.commitments, setup)?)
+    }
+}
+
+/// A [`DataAccessor`] that only exposes the rows of a snapshot of each table.
+struct SnapshotDataAccessor<'a, C: Commitment, A> {
+    accessor: &'a A,
+    commitments: &'a QueryCommitments<C>,
+}
+
+impl<C: Commitment, A> MetadataAccessor for SnapshotDataAccessor<'_, C, A> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.commitments.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.commitments.get_offset(table_ref)
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.commitments.try_get_length(table_ref)
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.commitments.try_get_offset(table_ref)
+    }
+}
+
+impl<C: Commitment, A: DataAccessor<C::Scalar>> DataAccessor<C::Scalar>
+    for SnapshotDataAccessor<'_, C, A>
+{
+    fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
+        self.accessor
+            .get_column(column)
+            .slice(0, self.get_length(column.table_ref()))
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<C::Scalar>> {
+        let length = self.try_get_length(column.table_ref())?;
+        Ok(self.accessor.try_get_column(column)?.slice(0, length))
+    }
+}
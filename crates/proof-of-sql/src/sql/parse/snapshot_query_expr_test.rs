@@ -0,0 +1,147 @@
+use super::{
+    ConversionError, QueryExpr, SnapshotQueryError, SnapshotQueryExpr, SnapshotQueryProof,
+};
+use crate::base::{
+    commitment::{CommitmentRegistry, InnerProductProof, TableCommitment},
+    database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+    scalar::Curve25519Scalar,
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::utility::ident;
+
+fn rows(length: usize) -> OwnedTable<Curve25519Scalar> {
+    let a = [1, 2, 3, 4, 5];
+    let b = ["x", "y", "z", "y", "x"];
+    owned_table([
+        bigint("a", a[..length].to_vec()),
+        varchar("b", b[..length].to_vec()),
+    ])
+}
+
+/// The latest version of the table, with 5 rows.
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table("sxt.t".parse().unwrap(), rows(5), 0);
+    accessor
+}
+
+/// A registry that retains a version of the table for each of the lengths.
+fn registry(lengths: &[usize]) -> CommitmentRegistry<RistrettoPoint> {
+    let mut registry = CommitmentRegistry::new();
+    for &length in lengths {
+        registry
+            .register(
+                "sxt.t".parse().unwrap(),
+                TableCommitment::from_owned_table_with_offset(&rows(length), 0, &()),
+            )
+            .unwrap();
+    }
+    registry
+}
+
+fn snapshot_query(
+    sql: &str,
+    registry: &CommitmentRegistry<RistrettoPoint>,
+) -> Result<SnapshotQueryExpr<RistrettoPoint>, SnapshotQueryError> {
+    SnapshotQueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), registry)
+}
+
+fn prove_and_verify(sql: &str) -> OwnedTable<Curve25519Scalar> {
+    let registry = registry(&[2, 4, 5]);
+    let expr = snapshot_query(sql, &registry).unwrap();
+    let proof = SnapshotQueryProof::<InnerProductProof>::new(&expr, &accessor(), &());
+    let data = proof.verify(&expr, &()).unwrap();
+    expr.query()
+        .result()
+        .transform_results(data.table.try_into().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn we_can_prove_and_verify_queries_of_earlier_snapshots() {
+    assert_eq!(
+        prove_and_verify("SELECT a, b FROM t AS OF 0"),
+        owned_table([bigint("a", [1, 2]), varchar("b", ["x", "y"])])
+    );
+    assert_eq!(
+        prove_and_verify("SELECT a FROM sxt.t AS OF 1 WHERE b = 'y'"),
+        owned_table([bigint("a", [2, 4])])
+    );
+    assert_eq!(
+        prove_and_verify("SELECT b, count(*) AS n FROM t AS OF 1 GROUP BY b ORDER BY b"),
+        owned_table([varchar("b", ["x", "y", "z"]), bigint("n", [1, 2, 1])])
+    );
+}
+
+#[test]
+fn we_can_prove_and_verify_queries_of_the_latest_snapshot() {
+    assert_eq!(
+        prove_and_verify("SELECT a FROM t WHERE b = 'x'"),
+        owned_table([bigint("a", [1, 5])])
+    );
+    assert_eq!(
+        prove_and_verify("SELECT a FROM t AS OF 2 WHERE b = 'x'"),
+        owned_table([bigint("a", [1, 5])])
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_proof_of_a_different_snapshot() {
+    let registry = registry(&[2, 4, 5]);
+    let expr = snapshot_query("SELECT a FROM t AS OF 0", &registry).unwrap();
+    let proof = SnapshotQueryProof::<InnerProductProof>::new(&expr, &accessor(), &());
+    assert_eq!(proof.version(), 0);
+    let other_expr = snapshot_query("SELECT a FROM t AS OF 1", &registry).unwrap();
+    assert!(matches!(
+        proof.verify(&other_expr, &()),
+        Err(SnapshotQueryError::SnapshotMismatch {
+            proven_version: 0,
+            expected_version: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_proof_against_a_snapshot_with_other_rows() {
+    let expr = snapshot_query("SELECT a FROM t AS OF 0", &registry(&[2, 4, 5])).unwrap();
+    let proof = SnapshotQueryProof::<InnerProductProof>::new(&expr, &accessor(), &());
+    // The verifier retained a version 0 with more rows than the prover's
+    let verifier_expr = snapshot_query("SELECT a FROM t AS OF 0", &registry(&[4, 5])).unwrap();
+    assert!(matches!(
+        proof.verify(&verifier_expr, &()),
+        Err(SnapshotQueryError::RangeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_resolve_snapshots_that_are_not_retained() {
+    let registry = registry(&[2, 4, 5]);
+    assert!(matches!(
+        snapshot_query("SELECT a FROM t AS OF 3", &registry),
+        Err(SnapshotQueryError::VersionNotFound { version: 3, .. })
+    ));
+    assert!(matches!(
+        snapshot_query("SELECT a FROM other", &registry),
+        Err(SnapshotQueryError::TableNotFound(_))
+    ));
+    assert!(matches!(
+        snapshot_query("SELECT c FROM t AS OF 0", &registry),
+        Err(SnapshotQueryError::ConversionError(_))
+    ));
+}
+
+#[test]
+fn we_cannot_plan_a_snapshot_query_as_an_ordinary_query() {
+    let accessor = accessor();
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(
+            "SELECT a FROM t AS OF 0".parse().unwrap(),
+            ident("sxt"),
+            &accessor,
+        ),
+        Err(ConversionError::UnboundSnapshot)
+    ));
+}
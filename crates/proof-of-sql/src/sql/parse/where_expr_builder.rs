@@ -4,7 +4,7 @@ use crate::{
         commitment::Commitment,
         database::{ColumnRef, ColumnType},
     },
-    sql::ast::{ProvableExpr, ProvableExprPlan},
+    sql::ast::{OverflowPolicy, ProvableExpr, ProvableExprPlan},
 };
 use indexmap::IndexMap;
 use proof_of_sql_parser::{intermediate_ast::Expression, Identifier};
@@ -21,6 +21,11 @@ impl<'a> WhereExprBuilder<'a> {
             builder: ProvableExprPlanBuilder::new(column_mapping),
         }
     }
+    /// Sets how the arithmetic expressions in the where clause handle overflows.
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.builder = self.builder.with_overflow_policy(overflow_policy);
+        self
+    }
     /// Builds a `proof_of_sql::sql::ast::ProvableExprPlan` from a `proof_of_sql_parser::intermediate_ast::Expression` that is
     /// intended to be used as the where clause in a filter expression or group by expression.
     pub fn build<C: Commitment>(
@@ -0,0 +1,185 @@
+use super::{
+    typed_expression::{from_exact_values, to_exact_values},
+    PostprocessingError, PostprocessingResult, PostprocessingStep,
+};
+use crate::base::{
+    database::{OwnedColumn, OwnedTable},
+    scalar::Scalar,
+};
+use core::f64::consts::PI;
+use num_bigint::BigInt;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// A mechanism that makes the release of aggregates differentially private by adding random noise.
+///
+/// The noise is scaled by the sensitivity of each column, which is the most that adding or
+/// removing a single record of the queried table can change any value of the column.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NoiseMechanism {
+    /// The Laplace mechanism, which is `epsilon`-differentially private.
+    ///
+    /// The noise has a Laplace distribution with scale `sensitivity / epsilon`.
+    Laplace {
+        /// The privacy budget, which must be positive
+        epsilon: f64,
+    },
+    /// The Gaussian mechanism, which is `(epsilon, delta)`-differentially private.
+    ///
+    /// The noise has a normal distribution with standard deviation
+    /// `sensitivity * sqrt(2 * ln(1.25 / delta)) / epsilon`.
+    Gaussian {
+        /// The privacy budget, which must be between 0 and 1
+        epsilon: f64,
+        /// The probability with which the privacy budget may be exceeded, which must be between
+        /// 0 and 1
+        delta: f64,
+    },
+}
+
+/// Adds differentially private noise to columns of a table, usually the outputs of aggregations.
+///
+/// This step is only ever applied after the result has been verified, so the noise is not part of
+/// any proof: the verifier checks the exact result and then releases a noisy version of it. The
+/// noise is added independently to each value and rounded to the scale of the column, so the
+/// column types of the table do not change.
+///
+/// The noise is derived deterministically from the seed, the column name and the row index. The
+/// same seed therefore always produces the same noise, which makes releases reproducible, but the
+/// privacy guarantee only holds if the seed is chosen at random and kept secret.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DifferentialPrivacyPostprocessing {
+    /// The mechanism that the noise is drawn with
+    mechanism: NoiseMechanism,
+    /// The columns to add noise to, along with their sensitivities
+    column_sensitivities: Vec<(Identifier, f64)>,
+    /// The seed of the noise
+    seed: u64,
+}
+
+fn is_positive(value: f64) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn is_in_unit_interval(value: f64) -> bool {
+    value > 0.0 && value < 1.0
+}
+
+/// Returns a description of the first parameter that is out of range, if any.
+fn find_invalid_parameter(
+    mechanism: NoiseMechanism,
+    column_sensitivities: &[(Identifier, f64)],
+) -> Option<String> {
+    match mechanism {
+        NoiseMechanism::Laplace { epsilon } if !is_positive(epsilon) => {
+            return Some(format!("epsilon must be positive, got {epsilon}"));
+        }
+        // The standard analysis of the Gaussian mechanism only holds for epsilon < 1
+        NoiseMechanism::Gaussian { epsilon, .. } if !is_in_unit_interval(epsilon) => {
+            return Some(format!("epsilon must be between 0 and 1, got {epsilon}"));
+        }
+        NoiseMechanism::Gaussian { delta, .. } if !is_in_unit_interval(delta) => {
+            return Some(format!("delta must be between 0 and 1, got {delta}"));
+        }
+        _ => {}
+    }
+    column_sensitivities
+        .iter()
+        .find(|(_, sensitivity)| !is_positive(*sensitivity))
+        .map(|(id, sensitivity)| {
+            format!("the sensitivity of {id} must be positive, got {sensitivity}")
+        })
+}
+
+impl DifferentialPrivacyPostprocessing {
+    /// Create a new `DifferentialPrivacyPostprocessing` node.
+    ///
+    /// Returns an error if a parameter of the mechanism or a sensitivity is out of range.
+    pub fn try_new(
+        mechanism: NoiseMechanism,
+        column_sensitivities: Vec<(Identifier, f64)>,
+        seed: u64,
+    ) -> PostprocessingResult<Self> {
+        if let Some(message) = find_invalid_parameter(mechanism, &column_sensitivities) {
+            return Err(PostprocessingError::InvalidPrivacyParameter(message));
+        }
+        Ok(Self {
+            mechanism,
+            column_sensitivities,
+            seed,
+        })
+    }
+
+    /// Returns a number drawn uniformly from the open interval `(0, 1)`.
+    fn uniform(&self, id: Identifier, row: u64, draw: u8) -> f64 {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&self.seed.to_le_bytes());
+        hasher.update(&(id.as_str().len() as u64).to_le_bytes());
+        hasher.update(id.as_str().as_bytes());
+        hasher.update(&row.to_le_bytes());
+        hasher.update(&[draw]);
+        let hash = hasher.finalize();
+        let bits = u64::from_le_bytes(
+            hash.as_bytes()[..8]
+                .try_into()
+                .expect("the first 8 bytes are a u64"),
+        );
+        // The top 53 bits fill the mantissa, and the half step keeps the result away from 0 and 1.
+        ((bits >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    }
+
+    /// Returns the noise for a value of a column with the given sensitivity.
+    fn noise(&self, id: Identifier, row: u64, sensitivity: f64) -> f64 {
+        match self.mechanism {
+            NoiseMechanism::Laplace { epsilon } => {
+                // Inverse transform sampling
+                let u = self.uniform(id, row, 0) - 0.5;
+                -sensitivity / epsilon * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+            }
+            NoiseMechanism::Gaussian { epsilon, delta } => {
+                // Box-Muller transform
+                let standard_deviation = sensitivity * (2.0 * (1.25 / delta).ln()).sqrt() / epsilon;
+                let (u1, u2) = (self.uniform(id, row, 0), self.uniform(id, row, 1));
+                standard_deviation * (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+            }
+        }
+    }
+
+    fn add_noise<S: Scalar>(
+        &self,
+        id: Identifier,
+        sensitivity: f64,
+        column: &OwnedColumn<S>,
+    ) -> PostprocessingResult<OwnedColumn<S>> {
+        let column_type = column.column_type();
+        let (values, scale) = to_exact_values(column, "differential privacy noise")?;
+        let unit = 10f64.powi(scale.into());
+        let noisy_values = values
+            .into_iter()
+            .enumerate()
+            .map(|(row, value)| {
+                let noise = (self.noise(id, row as u64, sensitivity) * unit).round();
+                // Casting saturates, so noise that does not fit has to be rejected beforehand
+                if noise.abs() >= i128::MAX as f64 {
+                    return Err(PostprocessingError::ArithmeticOverflow(column_type));
+                }
+                Ok(value + BigInt::from(noise as i128))
+            })
+            .collect::<PostprocessingResult<_>>()?;
+        from_exact_values(noisy_values, column_type)
+    }
+}
+
+impl<S: Scalar> PostprocessingStep<S> for DifferentialPrivacyPostprocessing {
+    /// Add noise to the declared columns of the given `OwnedTable`.
+    fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
+        let mut cols = owned_table.into_inner();
+        for &(id, sensitivity) in &self.column_sensitivities {
+            let column = cols
+                .get_mut(&id)
+                .ok_or_else(|| PostprocessingError::ColumnNotFound(id.to_string()))?;
+            *column = self.add_noise(id, sensitivity, column)?;
+        }
+        Ok(OwnedTable::try_new(cols)?)
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, ColumnType, OwnedColumn, OwnedTable},
+        math::decimal::Precision,
+        scalar::Curve25519Scalar,
+    },
+    sql::postprocessing::{
+        apply_postprocessing_steps, test_utility::*, DifferentialPrivacyPostprocessing,
+        NoiseMechanism, PostprocessingError, PostprocessingStep,
+    },
+};
+use num_bigint::BigInt;
+use proof_of_sql_parser::utility::ident;
+
+const LAPLACE: NoiseMechanism = NoiseMechanism::Laplace { epsilon: 1.0 };
+
+fn bigint_values(table: &OwnedTable<Curve25519Scalar>, name: &str) -> Vec<f64> {
+    match &table.inner_table()[&ident(name)] {
+        OwnedColumn::BigInt(values) => values.iter().map(|&v| v as f64).collect(),
+        column => panic!("expected a bigint column, got {:?}", column.column_type()),
+    }
+}
+
+fn decimal_values(table: &OwnedTable<Curve25519Scalar>, name: &str) -> Vec<f64> {
+    match &table.inner_table()[&ident(name)] {
+        OwnedColumn::Decimal75(_, _, values) => values
+            .iter()
+            .map(|&v| {
+                let v: BigInt = v.into();
+                i64::try_from(v).unwrap() as f64
+            })
+            .collect(),
+        column => panic!("expected a decimal column, got {:?}", column.column_type()),
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[test]
+fn we_can_add_the_same_noise_for_the_same_seed() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("total", [100, 200, 300]),
+        decimal75("average", 10, 2, [1050, 2075, 3000]),
+        varchar("name", ["a", "b", "c"]),
+    ]);
+    let columns = [("total", 10.0), ("average", 1.0)];
+    let noisy_table =
+        apply_postprocessing_steps(table.clone(), &[differential_privacy(LAPLACE, &columns, 7)])
+            .unwrap();
+    let same_noisy_table =
+        apply_postprocessing_steps(table.clone(), &[differential_privacy(LAPLACE, &columns, 7)])
+            .unwrap();
+    let other_noisy_table =
+        apply_postprocessing_steps(table.clone(), &[differential_privacy(LAPLACE, &columns, 8)])
+            .unwrap();
+    assert_eq!(noisy_table, same_noisy_table);
+    assert_ne!(noisy_table, other_noisy_table);
+    assert_ne!(noisy_table, table);
+
+    // Only the declared columns change, and the noise is rounded to their scale
+    assert_eq!(
+        noisy_table.inner_table()[&ident("name")],
+        table.inner_table()[&ident("name")]
+    );
+    assert_eq!(
+        noisy_table.inner_table()[&ident("average")].column_type(),
+        ColumnType::Decimal75(Precision::new(10).unwrap(), 2)
+    );
+    assert_eq!(
+        noisy_table.inner_table()[&ident("total")].column_type(),
+        ColumnType::BigInt
+    );
+}
+
+#[test]
+fn we_can_add_laplace_noise_with_the_declared_scale() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", vec![0i64; 10_000])]);
+    let mechanism = NoiseMechanism::Laplace { epsilon: 0.5 };
+    let postprocessing = [differential_privacy(mechanism, &[("a", 500.0)], 42)];
+    let noise = bigint_values(
+        &apply_postprocessing_steps(table, &postprocessing).unwrap(),
+        "a",
+    );
+    // The Laplace distribution with scale b has mean 0 and mean absolute deviation b = 1000
+    let absolute_noise: Vec<f64> = noise.iter().map(|v| v.abs()).collect();
+    assert!(mean(&noise).abs() < 100.0);
+    assert!((mean(&absolute_noise) - 1000.0).abs() < 50.0);
+}
+
+#[test]
+fn we_can_add_gaussian_noise_with_the_declared_standard_deviation() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([decimal75("a", 20, 2, vec![0i64; 10_000])]);
+    let mechanism = NoiseMechanism::Gaussian {
+        epsilon: 0.5,
+        delta: 1e-5,
+    };
+    let postprocessing = [differential_privacy(mechanism, &[("a", 1.0)], 42)];
+    // The noise is in units of 0.01
+    let noise = decimal_values(
+        &apply_postprocessing_steps(table, &postprocessing).unwrap(),
+        "a",
+    );
+    let expected_standard_deviation = 100.0 * (2.0 * (1.25f64 / 1e-5).ln()).sqrt() / 0.5;
+    let variance = mean(&noise.iter().map(|v| v * v).collect::<Vec<_>>());
+    assert!(mean(&noise).abs() < expected_standard_deviation / 10.0);
+    assert!((variance.sqrt() / expected_standard_deviation - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn we_cannot_declare_invalid_privacy_parameters() {
+    let a = ident("a");
+    for (mechanism, sensitivity) in [
+        (NoiseMechanism::Laplace { epsilon: 0.0 }, 1.0),
+        (NoiseMechanism::Laplace { epsilon: f64::NAN }, 1.0),
+        (NoiseMechanism::Laplace { epsilon: 1.0 }, -1.0),
+        (NoiseMechanism::Laplace { epsilon: 1.0 }, f64::INFINITY),
+        (
+            NoiseMechanism::Gaussian {
+                epsilon: 1.5,
+                delta: 1e-5,
+            },
+            1.0,
+        ),
+        (
+            NoiseMechanism::Gaussian {
+                epsilon: 0.5,
+                delta: 0.0,
+            },
+            1.0,
+        ),
+    ] {
+        assert!(matches!(
+            DifferentialPrivacyPostprocessing::try_new(mechanism, vec![(a, sensitivity)], 0),
+            Err(PostprocessingError::InvalidPrivacyParameter(_))
+        ));
+    }
+}
+
+#[test]
+fn we_cannot_add_noise_to_missing_or_non_numeric_columns() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([varchar("name", ["a"]), smallint("small", [0_i16])]);
+    let add_noise = |column: &str, sensitivity: f64| {
+        DifferentialPrivacyPostprocessing::try_new(LAPLACE, vec![(ident(column), sensitivity)], 0)
+            .unwrap()
+            .apply(table.clone())
+    };
+    assert!(matches!(
+        add_noise("missing", 1.0),
+        Err(PostprocessingError::ColumnNotFound(_))
+    ));
+    assert!(matches!(
+        add_noise("name", 1.0),
+        Err(PostprocessingError::UnsupportedColumnType { .. })
+    ));
+    // The noise has to fit into the type of the column
+    assert_eq!(
+        add_noise("small", 1e9),
+        Err(PostprocessingError::ArithmeticOverflow(
+            ColumnType::SmallInt
+        ))
+    );
+}
@@ -1,3 +1,4 @@
+use crate::base::database::ColumnType;
 use proof_of_sql_parser::Identifier;
 use thiserror::Error;
 
@@ -28,6 +29,26 @@ pub enum PostprocessingError {
     /// Nested aggregation in `GROUP BY` clause
     #[error("Nested aggregation in `GROUP BY` clause: {0}")]
     NestedAggregationInGroupByClause(String),
+    /// Errors in operations on columns
+    #[error(transparent)]
+    ColumnOperationError(#[from] crate::base::database::ColumnOperationError),
+    /// An operation of a `TypedExpression` was applied to a column of the wrong type
+    #[error("Cannot apply {operation} to a column of type {column_type}")]
+    UnsupportedColumnType {
+        /// The operation that was applied
+        operation: &'static str,
+        /// The type of the column
+        column_type: ColumnType,
+    },
+    /// The result of an operation does not fit into its type
+    #[error("Overflow: the result does not fit into {0}")]
+    ArithmeticOverflow(ColumnType),
+    /// A differential privacy mechanism was declared with an invalid parameter
+    #[error("Invalid differential privacy parameter: {0}")]
+    InvalidPrivacyParameter(String),
+    /// A timestamp that cannot be represented as a date
+    #[error("Timestamp {0} is out of range")]
+    TimestampOutOfRange(i64),
 }
 
 /// Result type for postprocessing
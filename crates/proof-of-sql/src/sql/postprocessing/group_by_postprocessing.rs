@@ -33,12 +33,18 @@ pub struct GroupByPostprocessing {
 /// Otherwise we need two layers of aggregation functions to be nested.
 fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => false,
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Exists { .. }
+        | Expression::Wildcard => false,
         Expression::Aggregation { expr, .. } => is_agg || contains_nested_aggregation(expr, true),
         Expression::Binary { left, right, .. } => {
             contains_nested_aggregation(left, is_agg) || contains_nested_aggregation(right, is_agg)
         }
         Expression::Unary { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::Function { args, .. } => args
+            .iter()
+            .any(|arg| contains_nested_aggregation(arg, is_agg)),
     }
 }
 
@@ -46,6 +52,7 @@ fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
 fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Identifier> {
     match expr {
         Expression::Column(identifier) => IndexSet::from([*identifier]),
+        Expression::Exists { outer_column, .. } => IndexSet::from([*outer_column]),
         Expression::Literal(_) | Expression::Aggregation { .. } | Expression::Wildcard => {
             IndexSet::new()
         }
@@ -56,6 +63,10 @@ fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Identifier> {
             left_identifiers
         }
         Expression::Unary { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::Function { args, .. } => args
+            .iter()
+            .flat_map(get_free_identifiers_from_expr)
+            .collect(),
     }
 }
 
@@ -69,7 +80,10 @@ fn get_aggregate_and_remainder_expressions(
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Identifier>,
 ) -> Expression {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => expr.clone(),
+        Expression::Column(_)
+        | Expression::Literal(_)
+        | Expression::Exists { .. }
+        | Expression::Wildcard => expr.clone(),
         Expression::Aggregation { op, expr } => {
             let key = (op, (*expr).clone());
             if !aggregation_expr_map.contains_key(&key) {
@@ -100,6 +114,13 @@ fn get_aggregate_and_remainder_expressions(
                 expr: Box::new(remainder),
             }
         }
+        Expression::Function { name, args } => Expression::Function {
+            name,
+            args: args
+                .into_iter()
+                .map(|arg| get_aggregate_and_remainder_expressions(arg, aggregation_expr_map))
+                .collect(),
+        },
     }
 }
 
@@ -25,7 +25,27 @@ pub use select_postprocessing::SelectPostprocessing;
 #[cfg(test)]
 mod select_postprocessing_test;
 
+mod typed_expression;
+pub use typed_expression::{ArithmeticOperator, RoundingMode, TimestampField, TypedExpression};
+#[cfg(test)]
+mod typed_expression_test;
+
+mod typed_select_postprocessing;
+pub use typed_select_postprocessing::TypedSelectPostprocessing;
+#[cfg(test)]
+mod typed_select_postprocessing_test;
+
+mod differential_privacy_postprocessing;
+pub use differential_privacy_postprocessing::{DifferentialPrivacyPostprocessing, NoiseMechanism};
+#[cfg(test)]
+mod differential_privacy_postprocessing_test;
+
 mod slice_postprocessing;
 pub use slice_postprocessing::SlicePostprocessing;
 #[cfg(test)]
 mod slice_postprocessing_test;
+
+mod postprocessed_query_proof;
+pub use postprocessed_query_proof::{PostprocessedQueryError, PostprocessedQueryProof};
+#[cfg(all(test, feature = "blitzar"))]
+mod postprocessed_query_proof_test;
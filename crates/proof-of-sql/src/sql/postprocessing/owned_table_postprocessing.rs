@@ -1,11 +1,13 @@
 use super::{
-    GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult, PostprocessingStep,
-    SelectPostprocessing, SlicePostprocessing,
+    DifferentialPrivacyPostprocessing, GroupByPostprocessing, OrderByPostprocessing,
+    PostprocessingResult, PostprocessingStep, SelectPostprocessing, SlicePostprocessing,
+    TypedSelectPostprocessing,
 };
 use crate::base::{database::OwnedTable, scalar::Scalar};
+use serde::{Deserialize, Serialize};
 
 /// An enum for nodes that can apply postprocessing to a `OwnedTable`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OwnedTablePostprocessing {
     /// Slice the `OwnedTable` with the given `SlicePostprocessing`.
     Slice(SlicePostprocessing),
@@ -15,6 +17,10 @@ pub enum OwnedTablePostprocessing {
     Select(SelectPostprocessing),
     /// Aggregate the `OwnedTable` with the given `GroupByPostprocessing`.
     GroupBy(GroupByPostprocessing),
+    /// Compute typed expressions over the `OwnedTable` with the given `TypedSelectPostprocessing`.
+    TypedSelect(TypedSelectPostprocessing),
+    /// Add noise to the `OwnedTable` with the given `DifferentialPrivacyPostprocessing`.
+    DifferentialPrivacy(DifferentialPrivacyPostprocessing),
 }
 
 impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
@@ -25,6 +31,12 @@ impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
             OwnedTablePostprocessing::OrderBy(order_by_expr) => order_by_expr.apply(owned_table),
             OwnedTablePostprocessing::Select(select_expr) => select_expr.apply(owned_table),
             OwnedTablePostprocessing::GroupBy(group_by_expr) => group_by_expr.apply(owned_table),
+            OwnedTablePostprocessing::TypedSelect(typed_select_expr) => {
+                typed_select_expr.apply(owned_table)
+            }
+            OwnedTablePostprocessing::DifferentialPrivacy(differential_privacy_expr) => {
+                differential_privacy_expr.apply(owned_table)
+            }
         }
     }
 }
@@ -46,6 +58,16 @@ impl OwnedTablePostprocessing {
     pub fn new_group_by(group_by_postprocessing: GroupByPostprocessing) -> Self {
         Self::GroupBy(group_by_postprocessing)
     }
+    /// Create a new `OwnedTablePostprocessing` with the given `TypedSelectPostprocessing`.
+    pub fn new_typed_select(typed_select_expr: TypedSelectPostprocessing) -> Self {
+        Self::TypedSelect(typed_select_expr)
+    }
+    /// Create a new `OwnedTablePostprocessing` with the given `DifferentialPrivacyPostprocessing`.
+    pub fn new_differential_privacy(
+        differential_privacy_expr: DifferentialPrivacyPostprocessing,
+    ) -> Self {
+        Self::DifferentialPrivacy(differential_privacy_expr)
+    }
 }
 
 /// Apply a list of postprocessing steps to an `OwnedTable`.
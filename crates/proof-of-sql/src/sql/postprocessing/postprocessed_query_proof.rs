@@ -0,0 +1,79 @@
+use super::{apply_postprocessing_steps, OwnedTablePostprocessing, PostprocessingError};
+use crate::{
+    base::{
+        commitment::CommitmentEvaluationProof,
+        database::{CommitmentAccessor, DataAccessor},
+    },
+    sql::proof::{ProofExpr, QueryData, QueryError, VerifiableQueryResult},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when verifying a [`PostprocessedQueryProof`].
+#[derive(Error, Debug)]
+pub enum PostprocessedQueryError {
+    /// The proof failed to verify.
+    #[error(transparent)]
+    VerificationError(#[from] QueryError),
+    /// The proof verified, but the postprocessing could not be applied to the verified result.
+    #[error(transparent)]
+    PostprocessingError(#[from] PostprocessingError),
+}
+
+/// A [`VerifiableQueryResult`] along with the postprocessing steps of its query, e.g. the
+/// `ORDER BY`, `LIMIT` and non-provable `SELECT` expressions.
+///
+/// The steps are serialized with the proof, so the verifier replays exactly the steps that the
+/// prover planned rather than re-deriving them from the SQL text, which would tie it to the
+/// version of the planner that the prover ran. Note that the steps are not proven: a verifier
+/// that does not trust the source of the proof should pin them, e.g. by comparing
+/// [`PostprocessedQueryProof::postprocessing_hash`] to a hash that was published with the query.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PostprocessedQueryProof<CP: CommitmentEvaluationProof> {
+    /// The result and its proof.
+    pub result: VerifiableQueryResult<CP>,
+    /// The steps that are applied to the verified result, in order.
+    pub postprocessing: Vec<OwnedTablePostprocessing>,
+}
+
+impl<CP: CommitmentEvaluationProof> PostprocessedQueryProof<CP> {
+    /// Prove the query and attach its postprocessing steps.
+    pub fn new(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        postprocessing: Vec<OwnedTablePostprocessing>,
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Self {
+        Self {
+            result: VerifiableQueryResult::new(expr, accessor, setup),
+            postprocessing,
+        }
+    }
+
+    /// Returns the Blake3 hash of the postprocessing steps.
+    pub fn postprocessing_hash(&self) -> [u8; 32] {
+        let bytes = postcard::to_allocvec(&self.postprocessing)
+            .expect("postprocessing steps should be serializable");
+        *blake3::hash(&bytes).as_bytes()
+    }
+
+    /// Verify the result and then apply the postprocessing steps to it.
+    ///
+    /// Upon success, this function returns the verified result after postprocessing.
+    pub fn verify(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<QueryData<CP::Scalar>, PostprocessedQueryError> {
+        let query_data = self.result.verify(expr, accessor, setup)?;
+        let table = apply_postprocessing_steps(query_data.table, &self.postprocessing)?;
+        Ok(QueryData {
+            column_checksums: query_data
+                .column_checksums
+                .map(|_| table.column_checksums()),
+            table,
+            ..query_data
+        })
+    }
+}
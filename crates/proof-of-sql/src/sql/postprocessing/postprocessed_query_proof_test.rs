@@ -0,0 +1,98 @@
+use super::{
+    test_utility::*, PostprocessedQueryError, PostprocessedQueryProof, PostprocessingError,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::parse::QueryExpr,
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::{intermediate_ast::OrderByDirection::Desc, utility::ident};
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [5, 1, 4, 2, 3]),
+            varchar("b", ["e", "a", "d", "b", "c"]),
+        ]),
+        0,
+    );
+    accessor
+}
+
+fn query(sql: &str) -> QueryExpr<RistrettoPoint> {
+    QueryExpr::try_new(sql.parse().unwrap(), ident("sxt"), &accessor()).unwrap()
+}
+
+#[test]
+fn we_can_replay_the_serialized_postprocessing_of_a_proof() {
+    let accessor = accessor();
+    let query = query("SELECT a, b FROM t WHERE a > 1");
+    let proof = PostprocessedQueryProof::<InnerProductProof>::new(
+        query.proof_expr(),
+        vec![orders(&["a"], &[Desc]), slice(Some(2), None)],
+        &accessor,
+        &(),
+    );
+    let bytes = postcard::to_allocvec(&proof).unwrap();
+    let deserialized: PostprocessedQueryProof<InnerProductProof> =
+        postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(deserialized.postprocessing, proof.postprocessing);
+    assert_eq!(
+        deserialized.postprocessing_hash(),
+        proof.postprocessing_hash()
+    );
+
+    let table = deserialized
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    assert_eq!(
+        table,
+        owned_table([bigint("a", [5, 4]), varchar("b", ["e", "d"])])
+    );
+}
+
+#[test]
+fn the_postprocessing_hash_depends_on_the_steps() {
+    let accessor = accessor();
+    let query = query("SELECT a, b FROM t");
+    let proof = |postprocessing| {
+        PostprocessedQueryProof::<InnerProductProof>::new(
+            query.proof_expr(),
+            postprocessing,
+            &accessor,
+            &(),
+        )
+    };
+    assert_ne!(
+        proof(vec![slice(Some(2), None)]).postprocessing_hash(),
+        proof(vec![slice(Some(3), None)]).postprocessing_hash()
+    );
+    assert_ne!(
+        proof(vec![]).postprocessing_hash(),
+        proof(vec![slice(Some(2), None)]).postprocessing_hash()
+    );
+}
+
+#[test]
+fn we_cannot_replay_postprocessing_that_does_not_fit_the_result() {
+    let accessor = accessor();
+    let query = query("SELECT a FROM t");
+    let proof = PostprocessedQueryProof::<InnerProductProof>::new(
+        query.proof_expr(),
+        vec![orders(&["b"], &[Desc])],
+        &accessor,
+        &(),
+    );
+    assert!(matches!(
+        proof.verify(query.proof_expr(), &accessor, &()),
+        Err(PostprocessedQueryError::PostprocessingError(
+            PostprocessingError::ColumnNotFound(_)
+        ))
+    ));
+}
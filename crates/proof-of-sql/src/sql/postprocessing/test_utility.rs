@@ -34,3 +34,30 @@ pub fn orders(cols: &[&str], directions: &[OrderByDirection]) -> OwnedTablePostp
         .collect();
     OwnedTablePostprocessing::new_order_by(OrderByPostprocessing::new(by_exprs))
 }
+
+pub fn typed_select_expr(exprs: &[(TypedExpression, &str)]) -> OwnedTablePostprocessing {
+    OwnedTablePostprocessing::new_typed_select(TypedSelectPostprocessing::new(
+        exprs
+            .iter()
+            .map(|(expr, alias)| (expr.clone(), ident(alias)))
+            .collect(),
+    ))
+}
+
+pub fn differential_privacy(
+    mechanism: NoiseMechanism,
+    column_sensitivities: &[(&str, f64)],
+    seed: u64,
+) -> OwnedTablePostprocessing {
+    OwnedTablePostprocessing::new_differential_privacy(
+        DifferentialPrivacyPostprocessing::try_new(
+            mechanism,
+            column_sensitivities
+                .iter()
+                .map(|(col, sensitivity)| (ident(col), *sensitivity))
+                .collect(),
+            seed,
+        )
+        .unwrap(),
+    )
+}
@@ -0,0 +1,395 @@
+use super::{PostprocessingError, PostprocessingResult};
+use crate::base::{
+    database::{
+        try_add_subtract_column_types, try_divide_column_types, try_multiply_column_types,
+        ColumnOperationError, ColumnType, OwnedColumn, OwnedTable,
+    },
+    math::decimal::{Precision, MAX_SUPPORTED_PRECISION},
+    scalar::Scalar,
+};
+use chrono::{DateTime, Datelike, FixedOffset};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Signed, Zero};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// How to round a value that has more fractional digits than its target scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RoundingMode {
+    /// Round toward zero, i.e. truncate
+    Down,
+    /// Round away from zero
+    Up,
+    /// Round toward negative infinity
+    Floor,
+    /// Round toward positive infinity
+    Ceiling,
+    /// Round to the nearest value, with ties rounded away from zero
+    HalfUp,
+    /// Round to the nearest value, with ties rounded to the even neighbor
+    HalfEven,
+}
+
+/// A binary arithmetic operator of a [`TypedExpression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ArithmeticOperator {
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `*`
+    Multiply,
+    /// `/`, rounding the quotient to the scale of the result type with the given mode
+    Divide(RoundingMode),
+}
+
+impl ArithmeticOperator {
+    fn binary_operator(&self) -> BinaryOperator {
+        match self {
+            ArithmeticOperator::Add => BinaryOperator::Add,
+            ArithmeticOperator::Subtract => BinaryOperator::Subtract,
+            ArithmeticOperator::Multiply => BinaryOperator::Multiply,
+            ArithmeticOperator::Divide(_) => BinaryOperator::Division,
+        }
+    }
+}
+
+/// A part of a timestamp that can be extracted by a [`TypedExpression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TimestampField {
+    /// The year, e.g. 2024
+    Year,
+    /// The month, from 1 to 12
+    Month,
+    /// The day of the month, from 1 to 31
+    Day,
+}
+
+/// An expression over the columns of a verified result table whose result type is known from
+/// the types of its operands.
+///
+/// Numeric values are computed exactly. Decimal operands are rescaled rather than converted, the
+/// precision and scale of the result follow the same rules as the provable arithmetic, and a
+/// result that does not fit into its type is an error rather than a wrapped or truncated value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TypedExpression {
+    /// An expression that is evaluated with [`OwnedTable::evaluate`], e.g. a column or a literal
+    Expression(Expression),
+    /// A decimal-aware arithmetic operation
+    Arithmetic {
+        /// The operator
+        op: ArithmeticOperator,
+        /// The left hand side of the operation
+        lhs: Box<TypedExpression>,
+        /// The right hand side of the operation
+        rhs: Box<TypedExpression>,
+    },
+    /// Round a numeric expression to a decimal with the given scale
+    Round {
+        /// The expression to round
+        expr: Box<TypedExpression>,
+        /// The scale of the result, which may be negative to round to tens, hundreds, etc.
+        scale: i8,
+        /// How to round values that have more fractional digits than `scale`
+        rounding_mode: RoundingMode,
+    },
+    /// Cast a numeric expression to a numeric type, i.e. an integer or a decimal type
+    Cast {
+        /// The expression to cast
+        expr: Box<TypedExpression>,
+        /// The type of the result
+        column_type: ColumnType,
+        /// How to round values that have more fractional digits than the scale of `column_type`
+        rounding_mode: RoundingMode,
+    },
+    /// Extract a part of a timestamp expression, in the timezone of the timestamp
+    Extract {
+        /// The part to extract
+        field: TimestampField,
+        /// The timestamp expression
+        expr: Box<TypedExpression>,
+    },
+}
+
+impl TypedExpression {
+    /// Evaluate the expression on the table.
+    pub fn evaluate<S: Scalar>(
+        &self,
+        owned_table: &OwnedTable<S>,
+    ) -> PostprocessingResult<OwnedColumn<S>> {
+        match self {
+            TypedExpression::Expression(expr) => Ok(owned_table.evaluate(expr)?),
+            TypedExpression::Arithmetic { op, lhs, rhs } => evaluate_arithmetic(
+                *op,
+                &lhs.evaluate(owned_table)?,
+                &rhs.evaluate(owned_table)?,
+            ),
+            TypedExpression::Round {
+                expr,
+                scale,
+                rounding_mode,
+            } => evaluate_round(&expr.evaluate(owned_table)?, *scale, *rounding_mode),
+            TypedExpression::Cast {
+                expr,
+                column_type,
+                rounding_mode,
+            } => evaluate_cast(&expr.evaluate(owned_table)?, *column_type, *rounding_mode),
+            TypedExpression::Extract { field, expr } => {
+                evaluate_extract(&expr.evaluate(owned_table)?, *field)
+            }
+        }
+    }
+}
+
+/// Returns the exact values of a numeric column as integers, along with their scale.
+pub(super) fn to_exact_values<S: Scalar>(
+    column: &OwnedColumn<S>,
+    operation: &'static str,
+) -> PostprocessingResult<(Vec<BigInt>, i8)> {
+    Ok(match column {
+        OwnedColumn::SmallInt(col) => (col.iter().map(|&v| BigInt::from(v)).collect(), 0),
+        OwnedColumn::Int(col) => (col.iter().map(|&v| BigInt::from(v)).collect(), 0),
+        OwnedColumn::BigInt(col) => (col.iter().map(|&v| BigInt::from(v)).collect(), 0),
+        OwnedColumn::Int128(col) => (col.iter().map(|&v| BigInt::from(v)).collect(), 0),
+        OwnedColumn::Decimal75(_, scale, col) => (col.iter().map(|&v| v.into()).collect(), *scale),
+        _ => {
+            return Err(PostprocessingError::UnsupportedColumnType {
+                operation,
+                column_type: column.column_type(),
+            })
+        }
+    })
+}
+
+/// Builds a column of the given numeric type from exact values that are already at its scale.
+pub(super) fn from_exact_values<S: Scalar>(
+    values: Vec<BigInt>,
+    column_type: ColumnType,
+) -> PostprocessingResult<OwnedColumn<S>> {
+    fn try_convert<T: TryFrom<BigInt>>(
+        values: Vec<BigInt>,
+        column_type: ColumnType,
+    ) -> PostprocessingResult<Vec<T>> {
+        values
+            .into_iter()
+            .map(|v| {
+                T::try_from(v).map_err(|_| PostprocessingError::ArithmeticOverflow(column_type))
+            })
+            .collect()
+    }
+    Ok(match column_type {
+        ColumnType::SmallInt => OwnedColumn::SmallInt(try_convert(values, column_type)?),
+        ColumnType::Int => OwnedColumn::Int(try_convert(values, column_type)?),
+        ColumnType::BigInt => OwnedColumn::BigInt(try_convert(values, column_type)?),
+        ColumnType::Int128 => OwnedColumn::Int128(try_convert(values, column_type)?),
+        ColumnType::Decimal75(precision, scale) => {
+            let bound = BigUint::from(10u8).pow(precision.value() as u32);
+            if values.iter().any(|v| v.magnitude() >= &bound) {
+                return Err(PostprocessingError::ArithmeticOverflow(column_type));
+            }
+            OwnedColumn::Decimal75(precision, scale, try_convert(values, column_type)?)
+        }
+        _ => {
+            return Err(PostprocessingError::UnsupportedColumnType {
+                operation: "arithmetic",
+                column_type,
+            })
+        }
+    })
+}
+
+/// Divide `numerator` by `denominator`, which must be non-zero, rounding with the given mode.
+fn divide_and_round(numerator: &BigInt, denominator: &BigInt, mode: RoundingMode) -> BigInt {
+    // Both are truncated toward zero, so the remainder has the sign of the numerator
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.is_zero() {
+        return quotient;
+    }
+    let is_negative = numerator.is_negative() != denominator.is_negative();
+    let away_from_zero = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::Floor => is_negative,
+        RoundingMode::Ceiling => !is_negative,
+        RoundingMode::HalfUp | RoundingMode::HalfEven => {
+            let twice_remainder = remainder.magnitude() << 1usize;
+            match twice_remainder.cmp(denominator.magnitude()) {
+                Ordering::Less => false,
+                Ordering::Greater => true,
+                Ordering::Equal => mode == RoundingMode::HalfUp || !(&quotient % 2).is_zero(),
+            }
+        }
+    };
+    match (away_from_zero, is_negative) {
+        (false, _) => quotient,
+        (true, false) => quotient + 1,
+        (true, true) => quotient - 1,
+    }
+}
+
+fn power_of_ten(exponent: u32) -> BigInt {
+    BigInt::from(10).pow(exponent)
+}
+
+/// Change the scale of a value, rounding with the given mode if the scale decreases.
+fn rescale(value: &BigInt, from_scale: i16, to_scale: i16, mode: RoundingMode) -> BigInt {
+    let shift = to_scale - from_scale;
+    if shift >= 0 {
+        value * power_of_ten(shift as u32)
+    } else {
+        divide_and_round(value, &power_of_ten(shift.unsigned_abs() as u32), mode)
+    }
+}
+
+fn evaluate_arithmetic<S: Scalar>(
+    op: ArithmeticOperator,
+    lhs: &OwnedColumn<S>,
+    rhs: &OwnedColumn<S>,
+) -> PostprocessingResult<OwnedColumn<S>> {
+    if lhs.len() != rhs.len() {
+        return Err(ColumnOperationError::DifferentColumnLength(lhs.len(), rhs.len()).into());
+    }
+    let (lhs_type, rhs_type) = (lhs.column_type(), rhs.column_type());
+    let result_type = match op {
+        ArithmeticOperator::Add | ArithmeticOperator::Subtract => {
+            try_add_subtract_column_types(lhs_type, rhs_type, op.binary_operator())?
+        }
+        ArithmeticOperator::Multiply => try_multiply_column_types(lhs_type, rhs_type)?,
+        ArithmeticOperator::Divide(_) => try_divide_column_types(lhs_type, rhs_type)?,
+    };
+    let result_scale = i16::from(result_type.scale().unwrap_or(0));
+    let (lhs_values, lhs_scale) = to_exact_values(lhs, "arithmetic")?;
+    let (rhs_values, rhs_scale) = to_exact_values(rhs, "arithmetic")?;
+    let (lhs_scale, rhs_scale) = (i16::from(lhs_scale), i16::from(rhs_scale));
+    // The result type never has fewer fractional digits than an exact sum or product
+    let to_result_scale = |v: &BigInt, scale| rescale(v, scale, result_scale, RoundingMode::Down);
+    let values = lhs_values
+        .iter()
+        .zip(&rhs_values)
+        .map(|(l, r)| match op {
+            ArithmeticOperator::Add => {
+                Ok(to_result_scale(l, lhs_scale) + to_result_scale(r, rhs_scale))
+            }
+            ArithmeticOperator::Subtract => {
+                Ok(to_result_scale(l, lhs_scale) - to_result_scale(r, rhs_scale))
+            }
+            ArithmeticOperator::Multiply => Ok(to_result_scale(&(l * r), lhs_scale + rhs_scale)),
+            ArithmeticOperator::Divide(mode) => {
+                if r.is_zero() {
+                    return Err(ColumnOperationError::DivisionByZero.into());
+                }
+                // l / r has scale lhs_scale - rhs_scale, so shift the numerator by the rest
+                let shift = result_scale - lhs_scale + rhs_scale;
+                Ok(if shift >= 0 {
+                    divide_and_round(&(l * power_of_ten(shift as u32)), r, mode)
+                } else {
+                    divide_and_round(l, &(r * power_of_ten(shift.unsigned_abs() as u32)), mode)
+                })
+            }
+        })
+        .collect::<PostprocessingResult<Vec<_>>>()?;
+    from_exact_values(values, result_type)
+}
+
+fn evaluate_round<S: Scalar>(
+    column: &OwnedColumn<S>,
+    scale: i8,
+    rounding_mode: RoundingMode,
+) -> PostprocessingResult<OwnedColumn<S>> {
+    let (values, from_scale) = to_exact_values(column, "round")?;
+    let precision = column
+        .column_type()
+        .precision_value()
+        .expect("numeric columns have precision");
+    let (from_scale, to_scale) = (i16::from(from_scale), i16::from(scale));
+    // Rounding up may carry into a new digit, e.g. 9.96 becomes 10.0
+    let carry = i16::from(to_scale < from_scale);
+    let precision_value = (i16::from(precision) - from_scale + to_scale + carry)
+        .clamp(1, MAX_SUPPORTED_PRECISION.into()) as u8;
+    let result_type = ColumnType::Decimal75(
+        Precision::new(precision_value).expect("precision is clamped to the supported range"),
+        scale,
+    );
+    let values = values
+        .iter()
+        .map(|v| rescale(v, from_scale, to_scale, rounding_mode))
+        .collect();
+    from_exact_values(values, result_type)
+}
+
+fn evaluate_cast<S: Scalar>(
+    column: &OwnedColumn<S>,
+    column_type: ColumnType,
+    rounding_mode: RoundingMode,
+) -> PostprocessingResult<OwnedColumn<S>> {
+    let to_scale = match column_type {
+        ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt | ColumnType::Int128 => 0,
+        ColumnType::Decimal75(_, scale) => i16::from(scale),
+        _ => {
+            return Err(PostprocessingError::UnsupportedColumnType {
+                operation: "cast",
+                column_type,
+            })
+        }
+    };
+    let (values, from_scale) = to_exact_values(column, "cast")?;
+    let from_scale = i16::from(from_scale);
+    let values = values
+        .iter()
+        .map(|v| rescale(v, from_scale, to_scale, rounding_mode))
+        .collect();
+    from_exact_values(values, column_type)
+}
+
+/// Returns the date and time of a timestamp in its timezone.
+fn to_date_time(
+    timestamp: i64,
+    unit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+) -> PostprocessingResult<DateTime<FixedOffset>> {
+    let units_per_second = match unit {
+        PoSQLTimeUnit::Second => 1,
+        PoSQLTimeUnit::Millisecond => 1_000,
+        PoSQLTimeUnit::Microsecond => 1_000_000,
+        PoSQLTimeUnit::Nanosecond => 1_000_000_000,
+    };
+    let seconds = timestamp.div_euclid(units_per_second);
+    let nanoseconds = timestamp.rem_euclid(units_per_second) * (1_000_000_000 / units_per_second);
+    let offset = match timezone {
+        PoSQLTimeZone::Utc => 0,
+        PoSQLTimeZone::FixedOffset(offset) => offset,
+    };
+    DateTime::from_timestamp(seconds, nanoseconds as u32)
+        .zip(FixedOffset::east_opt(offset))
+        .map(|(date_time, offset)| date_time.with_timezone(&offset))
+        .ok_or(PostprocessingError::TimestampOutOfRange(timestamp))
+}
+
+fn evaluate_extract<S: Scalar>(
+    column: &OwnedColumn<S>,
+    field: TimestampField,
+) -> PostprocessingResult<OwnedColumn<S>> {
+    let OwnedColumn::TimestampTZ(unit, timezone, timestamps) = column else {
+        return Err(PostprocessingError::UnsupportedColumnType {
+            operation: "extract",
+            column_type: column.column_type(),
+        });
+    };
+    let values = timestamps
+        .iter()
+        .map(|&timestamp| {
+            let date_time = to_date_time(timestamp, *unit, *timezone)?;
+            Ok(match field {
+                TimestampField::Year => date_time.year(),
+                TimestampField::Month => date_time.month() as i32,
+                TimestampField::Day => date_time.day() as i32,
+            })
+        })
+        .collect::<PostprocessingResult<Vec<_>>>()?;
+    Ok(OwnedColumn::Int(values))
+}
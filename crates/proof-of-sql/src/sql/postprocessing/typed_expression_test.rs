@@ -0,0 +1,296 @@
+use super::{
+    ArithmeticOperator, PostprocessingError, RoundingMode, TimestampField, TypedExpression,
+};
+use crate::base::{
+    database::{owned_table_utility::*, ColumnOperationError, ColumnType, OwnedTable},
+    math::decimal::Precision,
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::{
+    intermediate_ast::BinaryOperator,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    utility::col,
+};
+
+fn column(name: &str) -> Box<TypedExpression> {
+    Box::new(TypedExpression::Expression(*col(name)))
+}
+
+fn arithmetic(op: ArithmeticOperator, lhs: &str, rhs: &str) -> TypedExpression {
+    TypedExpression::Arithmetic {
+        op,
+        lhs: column(lhs),
+        rhs: column(rhs),
+    }
+}
+
+fn round(name: &str, scale: i8, rounding_mode: RoundingMode) -> TypedExpression {
+    TypedExpression::Round {
+        expr: column(name),
+        scale,
+        rounding_mode,
+    }
+}
+
+fn cast(name: &str, column_type: ColumnType, rounding_mode: RoundingMode) -> TypedExpression {
+    TypedExpression::Cast {
+        expr: column(name),
+        column_type,
+        rounding_mode,
+    }
+}
+
+fn extract(field: TimestampField, name: &str) -> TypedExpression {
+    TypedExpression::Extract {
+        field,
+        expr: column(name),
+    }
+}
+
+#[test]
+fn we_can_add_and_subtract_decimals_with_different_scales() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([decimal75("a", 5, 2, [125, -300]), bigint("b", [1_i64, 2])]);
+    // 1.25 + 1 = 2.25, -3.00 + 2 = -1.00
+    let sum = arithmetic(ArithmeticOperator::Add, "a", "b");
+    let (_, expected) = decimal75("c", 22, 2, [225, -100]);
+    assert_eq!(sum.evaluate(&table).unwrap(), expected);
+    // 1 - 1.25 = -0.25, 2 - -3.00 = 5.00
+    let difference = arithmetic(ArithmeticOperator::Subtract, "b", "a");
+    let (_, expected) = decimal75("c", 22, 2, [-25, 500]);
+    assert_eq!(difference.evaluate(&table).unwrap(), expected);
+}
+
+#[test]
+fn we_can_multiply_and_divide_decimals() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 5, 2, [125, -300]),
+        decimal75("b", 3, 1, [15, 5]),
+    ]);
+    // 1.25 * 1.5 = 1.875, -3.00 * 0.5 = -1.500
+    let product = arithmetic(ArithmeticOperator::Multiply, "a", "b");
+    let (_, expected) = decimal75("c", 9, 3, [1875, -1500]);
+    assert_eq!(product.evaluate(&table).unwrap(), expected);
+    // 1.25 / 1.5 = 0.8333..., -3.00 / 0.5 = -6
+    let quotient = arithmetic(ArithmeticOperator::Divide(RoundingMode::HalfUp), "a", "b");
+    let (_, expected) = decimal75("c", 10, 6, [833_333, -6_000_000]);
+    assert_eq!(quotient.evaluate(&table).unwrap(), expected);
+    let quotient = arithmetic(ArithmeticOperator::Divide(RoundingMode::Up), "a", "b");
+    let (_, expected) = decimal75("c", 10, 6, [833_334, -6_000_000]);
+    assert_eq!(quotient.evaluate(&table).unwrap(), expected);
+}
+
+#[test]
+fn we_can_divide_integers_with_all_rounding_modes() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [7_i64, -7, 5, 6, -9]),
+        int("b", [2, 2, 2, 4, 4]),
+    ]);
+    // 3.5, -3.5, 2.5, 1.5, -2.25
+    for (mode, expected) in [
+        (RoundingMode::Down, [3_i64, -3, 2, 1, -2]),
+        (RoundingMode::Up, [4, -4, 3, 2, -3]),
+        (RoundingMode::Floor, [3, -4, 2, 1, -3]),
+        (RoundingMode::Ceiling, [4, -3, 3, 2, -2]),
+        (RoundingMode::HalfUp, [4, -4, 3, 2, -2]),
+        (RoundingMode::HalfEven, [4, -4, 2, 2, -2]),
+    ] {
+        let quotient = arithmetic(ArithmeticOperator::Divide(mode), "a", "b");
+        let (_, expected) = bigint("c", expected);
+        assert_eq!(quotient.evaluate(&table).unwrap(), expected);
+    }
+}
+
+#[test]
+fn we_can_round_decimals() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 5, 2, [125, -135, 999, -1]),
+        bigint("b", [1250_i64, -1350, 49, -50]),
+    ]);
+    for (name, scale, mode, expected) in [
+        // 1.25, -1.35, 9.99, -0.01 with room for a carry into a new digit
+        (
+            "a",
+            1,
+            RoundingMode::HalfEven,
+            decimal75("c", 5, 1, [12, -14, 100, 0]),
+        ),
+        (
+            "a",
+            1,
+            RoundingMode::HalfUp,
+            decimal75("c", 5, 1, [13, -14, 100, 0]),
+        ),
+        (
+            "a",
+            1,
+            RoundingMode::Floor,
+            decimal75("c", 5, 1, [12, -14, 99, -1]),
+        ),
+        // Increasing the scale is exact
+        (
+            "a",
+            3,
+            RoundingMode::Down,
+            decimal75("c", 6, 3, [1250, -1350, 9990, -10]),
+        ),
+        // Negative scales round to hundreds
+        (
+            "b",
+            -2,
+            RoundingMode::HalfUp,
+            decimal75("c", 18, -2, [13, -14, 0, -1]),
+        ),
+    ] {
+        let rounded = round(name, scale, mode).evaluate(&table).unwrap();
+        assert_eq!(rounded, expected.1);
+    }
+}
+
+#[test]
+fn we_can_cast_decimals_with_all_rounding_modes() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("a", 5, 3, [2500, -2500, 3500, 1499, -1]),
+        bigint("b", [1_i64, -2, 3, 4, 5]),
+    ]);
+    let decimal =
+        |precision, scale| ColumnType::Decimal75(Precision::new(precision).unwrap(), scale);
+    for (mode, expected) in [
+        // 2.5, -2.5, 3.5, 1.499, -0.001
+        (RoundingMode::HalfEven, [2, -2, 4, 1, 0]),
+        (RoundingMode::HalfUp, [3, -3, 4, 1, 0]),
+        (RoundingMode::Down, [2, -2, 3, 1, 0]),
+        (RoundingMode::Up, [3, -3, 4, 2, -1]),
+        (RoundingMode::Floor, [2, -3, 3, 1, -1]),
+        (RoundingMode::Ceiling, [3, -2, 4, 2, 0]),
+    ] {
+        let (_, expected) = bigint("c", expected);
+        let cast_to_bigint = cast("a", ColumnType::BigInt, mode);
+        assert_eq!(cast_to_bigint.evaluate(&table).unwrap(), expected);
+    }
+    let (_, expected) = decimal75("c", 4, 2, [250, -250, 350, 150, 0]);
+    let cast_to_decimal = cast("a", decimal(4, 2), RoundingMode::HalfEven);
+    assert_eq!(cast_to_decimal.evaluate(&table).unwrap(), expected);
+    // Increasing the scale is exact
+    let (_, expected) = decimal75("c", 10, 2, [100, -200, 300, 400, 500]);
+    let cast_to_decimal = cast("b", decimal(10, 2), RoundingMode::Down);
+    assert_eq!(cast_to_decimal.evaluate(&table).unwrap(), expected);
+
+    // The result has to fit into the type
+    assert_eq!(
+        cast("b", decimal(2, 2), RoundingMode::Down).evaluate(&table),
+        Err(PostprocessingError::ArithmeticOverflow(decimal(2, 2)))
+    );
+    assert_eq!(
+        cast("b", ColumnType::VarChar, RoundingMode::Down).evaluate(&table),
+        Err(PostprocessingError::UnsupportedColumnType {
+            operation: "cast",
+            column_type: ColumnType::VarChar,
+        })
+    );
+}
+
+#[test]
+fn we_cannot_wrap_around_on_overflow() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        smallint("a", [i16::MAX, 1]),
+        smallint("b", [1_i16, 1]),
+        int128("c", [i128::MAX, 1]),
+        int128("d", [2_i128, 1]),
+    ]);
+    let sum = arithmetic(ArithmeticOperator::Add, "a", "b");
+    assert_eq!(
+        sum.evaluate(&table),
+        Err(PostprocessingError::ArithmeticOverflow(
+            ColumnType::SmallInt
+        ))
+    );
+    let product = arithmetic(ArithmeticOperator::Multiply, "c", "d");
+    assert_eq!(
+        product.evaluate(&table),
+        Err(PostprocessingError::ArithmeticOverflow(ColumnType::Int128))
+    );
+}
+
+#[test]
+fn we_cannot_divide_by_zero() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([decimal75("a", 5, 2, [125, -300]), bigint("b", [1_i64, 0])]);
+    let quotient = arithmetic(ArithmeticOperator::Divide(RoundingMode::Down), "a", "b");
+    assert_eq!(
+        quotient.evaluate(&table),
+        Err(PostprocessingError::ColumnOperationError(
+            ColumnOperationError::DivisionByZero
+        ))
+    );
+}
+
+#[test]
+fn we_can_extract_parts_of_timestamps_in_their_timezone() {
+    // 1970-01-01 00:00:00 and 2024-02-29 23:30:00 in UTC
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        timestamptz(
+            "utc",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [0, 1_709_249_400],
+        ),
+        timestamptz(
+            "cet",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::FixedOffset(3600),
+            [0, 1_709_249_400],
+        ),
+        timestamptz(
+            "millis",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            [-1, 1_709_249_400_000],
+        ),
+    ]);
+    for (field, name, expected) in [
+        (TimestampField::Year, "utc", [1970, 2024]),
+        (TimestampField::Month, "utc", [1, 2]),
+        (TimestampField::Day, "utc", [1, 29]),
+        (TimestampField::Year, "cet", [1970, 2024]),
+        (TimestampField::Month, "cet", [1, 3]),
+        (TimestampField::Day, "cet", [1, 1]),
+        (TimestampField::Year, "millis", [1969, 2024]),
+        (TimestampField::Month, "millis", [12, 2]),
+        (TimestampField::Day, "millis", [31, 29]),
+    ] {
+        let (_, expected) = int("c", expected);
+        assert_eq!(extract(field, name).evaluate(&table).unwrap(), expected);
+    }
+}
+
+#[test]
+fn we_cannot_apply_typed_expressions_to_unsupported_types() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [1_i64, 2]), varchar("b", ["x", "y"])]);
+    assert_eq!(
+        extract(TimestampField::Year, "a").evaluate(&table),
+        Err(PostprocessingError::UnsupportedColumnType {
+            operation: "extract",
+            column_type: ColumnType::BigInt,
+        })
+    );
+    assert_eq!(
+        round("b", 0, RoundingMode::HalfUp).evaluate(&table),
+        Err(PostprocessingError::UnsupportedColumnType {
+            operation: "round",
+            column_type: ColumnType::VarChar,
+        })
+    );
+    let sum = arithmetic(ArithmeticOperator::Add, "a", "b");
+    assert_eq!(
+        sum.evaluate(&table),
+        Err(PostprocessingError::ColumnOperationError(
+            ColumnOperationError::BinaryOperationInvalidColumnType {
+                operator: BinaryOperator::Add,
+                left_type: ColumnType::BigInt,
+                right_type: ColumnType::VarChar,
+            }
+        ))
+    );
+}
@@ -0,0 +1,36 @@
+use super::{PostprocessingResult, PostprocessingStep, TypedExpression};
+use crate::base::{
+    database::{OwnedColumn, OwnedTable},
+    scalar::Scalar,
+};
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// The select expression used to compute typed expressions over the columns of a table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypedSelectPostprocessing {
+    /// The typed expressions we select, along with their aliases
+    aliased_typed_exprs: Vec<(TypedExpression, Identifier)>,
+}
+
+impl TypedSelectPostprocessing {
+    /// Create a new `TypedSelectPostprocessing` node.
+    pub fn new(aliased_typed_exprs: Vec<(TypedExpression, Identifier)>) -> Self {
+        Self {
+            aliased_typed_exprs,
+        }
+    }
+}
+
+impl<S: Scalar> PostprocessingStep<S> for TypedSelectPostprocessing {
+    /// Apply the typed select transformation to the given `OwnedTable`.
+    fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
+        let cols: IndexMap<Identifier, OwnedColumn<S>> = self
+            .aliased_typed_exprs
+            .iter()
+            .map(|(expr, alias)| Ok((*alias, expr.evaluate(&owned_table)?)))
+            .collect::<PostprocessingResult<_>>()?;
+        Ok(OwnedTable::try_new(cols)?)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable},
+        scalar::Curve25519Scalar,
+    },
+    sql::postprocessing::{
+        apply_postprocessing_steps, test_utility::*, ArithmeticOperator, OwnedTablePostprocessing,
+        RoundingMode, TypedExpression,
+    },
+};
+use proof_of_sql_parser::utility::col;
+
+#[test]
+fn we_can_select_typed_expressions() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        decimal75("price", 5, 2, [1999, 250]),
+        int("quantity", [3, 4]),
+        varchar("item", ["a", "b"]),
+    ]);
+    let total = TypedExpression::Arithmetic {
+        op: ArithmeticOperator::Multiply,
+        lhs: Box::new(TypedExpression::Expression(*col("price"))),
+        rhs: Box::new(TypedExpression::Expression(*col("quantity"))),
+    };
+    let rounded_total = TypedExpression::Round {
+        expr: Box::new(total.clone()),
+        scale: 0,
+        rounding_mode: RoundingMode::HalfEven,
+    };
+    let postprocessing: [OwnedTablePostprocessing; 1] = [typed_select_expr(&[
+        (TypedExpression::Expression(*col("item")), "item"),
+        (total, "total"),
+        (rounded_total, "rounded_total"),
+    ])];
+    // 19.99 * 3 = 59.97, 2.50 * 4 = 10.00
+    let expected_table = owned_table([
+        varchar("item", ["a", "b"]),
+        decimal75("total", 16, 2, [5997, 1000]),
+        decimal75("rounded_total", 15, 0, [60, 10]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_cannot_select_typed_expressions_of_missing_columns() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([int("quantity", [3, 4])]);
+    let price = TypedExpression::Expression(*col("price"));
+    let postprocessing: [OwnedTablePostprocessing; 1] = [typed_select_expr(&[(price, "price")])];
+    assert!(apply_postprocessing_steps(table, &postprocessing).is_err());
+}
@@ -0,0 +1,107 @@
+use super::ProofExpr;
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    database::{AccessorError, ColumnRef, CommitmentAccessor, DataAccessor, TableRef},
+    polynomial::MultilinearExtension,
+    proof::{MessageLabel, TranscriptProtocol},
+};
+use indexmap::IndexMap;
+use merlin::Transcript;
+use num_traits::Zero;
+use thiserror::Error;
+
+/// Errors from checking that the data of an accessor matches the commitments to it.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AccessorConsistencyError {
+    /// A referenced table or column could not be read.
+    #[error(transparent)]
+    AccessorError(#[from] AccessorError),
+    /// The rows of a table in the accessor are not the rows that were committed to.
+    #[error("table {table_ref} has {data_length} rows at offset {data_offset} in the accessor, but {commitment_length} rows at offset {commitment_offset} in the commitments")]
+    TableSpanMismatch {
+        /// The table whose rows differ
+        table_ref: TableRef,
+        /// The offset of the table in the accessor
+        data_offset: usize,
+        /// The length of the table in the accessor
+        data_length: usize,
+        /// The offset of the table in the commitments
+        commitment_offset: usize,
+        /// The length of the table in the commitments
+        commitment_length: usize,
+    },
+    /// The referenced columns of a table do not match their commitments.
+    #[error("the referenced columns of table {table_ref} do not match their commitments")]
+    CommitmentMismatch {
+        /// The table whose columns do not match
+        table_ref: TableRef,
+    },
+}
+
+/// Check that the columns that `expr` references have the same data in `accessor` as was
+/// committed to in `commitments`.
+///
+/// A proof against stale or corrupted commitments can only fail to verify, so provers can run
+/// this before proving to fail fast. Rather than recomputing the commitment of every referenced
+/// column, the columns of each table are folded with random weights into a single column, whose
+/// commitment is compared with the same combination of the column commitments. This costs one
+/// commitment computation per table, and catches any mismatch except with negligible probability.
+///
+/// Note: the weights are drawn from a transcript over the column references, so they are
+/// reproducible. This is a check against mistakes, not against a prover that chooses the data
+/// to pass it.
+pub fn verify_accessor_consistency<C: Commitment>(
+    expr: &impl ProofExpr<C>,
+    accessor: &impl DataAccessor<C::Scalar>,
+    commitments: &impl CommitmentAccessor<C>,
+    setup: &C::PublicSetup<'_>,
+) -> Result<(), AccessorConsistencyError> {
+    let column_refs = expr.get_column_references();
+    let mut tables: IndexMap<TableRef, Vec<ColumnRef>> = IndexMap::new();
+    for column_ref in &column_refs {
+        tables
+            .entry(column_ref.table_ref())
+            .or_default()
+            .push(*column_ref);
+    }
+
+    let mut transcript = Transcript::new(MessageLabel::AccessorConsistency.as_bytes());
+    transcript.append_auto(MessageLabel::AccessorConsistency, &column_refs);
+    for (table_ref, columns) in tables {
+        let data_offset = accessor.try_get_offset(table_ref)?;
+        let data_length = accessor.try_get_length(table_ref)?;
+        let commitment_offset = commitments.try_get_offset(table_ref)?;
+        let commitment_length = commitments.try_get_length(table_ref)?;
+        if (data_offset, data_length) != (commitment_offset, commitment_length) {
+            return Err(AccessorConsistencyError::TableSpanMismatch {
+                table_ref,
+                data_offset,
+                data_length,
+                commitment_offset,
+                commitment_length,
+            });
+        }
+
+        let mut weights = vec![Zero::zero(); columns.len()];
+        transcript.challenge_scalars(&mut weights, MessageLabel::AccessorConsistencyChallenge);
+        let mut folded_column = vec![Zero::zero(); data_length];
+        let mut expected_commitment = C::default();
+        for (column, weight) in columns.into_iter().zip(&weights) {
+            accessor
+                .try_get_column(column)?
+                .mul_add(&mut folded_column, weight);
+            expected_commitment += *weight * commitments.get_commitment(column);
+        }
+        let mut folded_commitment = [C::default()];
+        C::compute_commitments(
+            &mut folded_commitment,
+            &[CommittableColumn::from(&folded_column[..])],
+            data_offset,
+            setup,
+        );
+        if folded_commitment[0] != expected_commitment {
+            return Err(AccessorConsistencyError::CommitmentMismatch { table_ref });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use super::{verify_accessor_consistency, AccessorConsistencyError};
+use crate::{
+    base::{
+        commitment::{InnerProductProof, QueryCommitments, TableCommitment},
+        database::{
+            owned_table_utility::*, AccessorError, OwnedTable, OwnedTableTestAccessor, TableRef,
+            TestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn table(a: &[i64], b: &[&str], c: &[i64]) -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("a", a.to_vec()),
+        varchar("b", b.to_vec()),
+        bigint("c", c.to_vec()),
+    ])
+}
+
+fn accessor(
+    t: TableRef,
+    table: OwnedTable<Curve25519Scalar>,
+    offset: usize,
+) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::new_empty_with_setup(());
+    accessor.add_table(t, table, offset);
+    accessor
+}
+
+fn commitments(
+    t: TableRef,
+    table: &OwnedTable<Curve25519Scalar>,
+    offset: usize,
+) -> QueryCommitments<RistrettoPoint> {
+    [(
+        t,
+        TableCommitment::from_owned_table_with_offset(table, offset, &()),
+    )]
+    .into_iter()
+    .collect()
+}
+
+/// `SELECT a FROM t WHERE b = 'x'`, which does not reference `c`.
+fn expr(
+    t: TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> ProofPlan<RistrettoPoint> {
+    dense_filter(
+        cols_expr_plan(t, &["a"], accessor),
+        tab(t),
+        equal(column(t, "b", accessor), const_varchar("x")),
+    )
+}
+
+#[test]
+fn we_can_check_an_accessor_that_matches_its_commitments() {
+    let t = "sxt.t".parse().unwrap();
+    let data = table(&[1, 2, 3], &["x", "y", "x"], &[4, 5, 6]);
+    let accessor = accessor(t, data.clone(), 5);
+    assert_eq!(
+        verify_accessor_consistency(
+            &expr(t, &accessor),
+            &accessor,
+            &commitments(t, &data, 5),
+            &()
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        verify_accessor_consistency(&expr(t, &accessor), &accessor, &accessor, &()),
+        Ok(())
+    );
+}
+
+#[test]
+fn we_can_check_an_empty_table() {
+    let t = "sxt.t".parse().unwrap();
+    let data = table(&[], &[], &[]);
+    let accessor = accessor(t, data.clone(), 0);
+    assert_eq!(
+        verify_accessor_consistency(
+            &expr(t, &accessor),
+            &accessor,
+            &commitments(t, &data, 0),
+            &()
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn we_detect_a_referenced_column_that_does_not_match_its_commitment() {
+    let t = "sxt.t".parse().unwrap();
+    let committed = table(&[1, 2, 3], &["x", "y", "x"], &[4, 5, 6]);
+    for data in [
+        table(&[1, 2, 4], &["x", "y", "x"], &[4, 5, 6]),
+        table(&[1, 2, 3], &["x", "x", "x"], &[4, 5, 6]),
+        table(&[2, 1, 3], &["x", "y", "x"], &[4, 5, 6]),
+    ] {
+        let accessor = accessor(t, data, 0);
+        assert_eq!(
+            verify_accessor_consistency(
+                &expr(t, &accessor),
+                &accessor,
+                &commitments(t, &committed, 0),
+                &()
+            ),
+            Err(AccessorConsistencyError::CommitmentMismatch { table_ref: t })
+        );
+    }
+}
+
+#[test]
+fn columns_that_are_not_referenced_are_not_checked() {
+    let t = "sxt.t".parse().unwrap();
+    let committed = table(&[1, 2, 3], &["x", "y", "x"], &[4, 5, 6]);
+    let accessor = accessor(t, table(&[1, 2, 3], &["x", "y", "x"], &[0, 0, 0]), 0);
+    assert_eq!(
+        verify_accessor_consistency(
+            &expr(t, &accessor),
+            &accessor,
+            &commitments(t, &committed, 0),
+            &()
+        ),
+        Ok(())
+    );
+}
+
+#[test]
+fn we_detect_commitments_to_other_rows_of_the_table() {
+    let t = "sxt.t".parse().unwrap();
+    let committed = table(&[1, 2], &["x", "y"], &[4, 5]);
+    let accessor = accessor(t, table(&[1, 2, 3], &["x", "y", "x"], &[4, 5, 6]), 0);
+    assert_eq!(
+        verify_accessor_consistency(
+            &expr(t, &accessor),
+            &accessor,
+            &commitments(t, &committed, 0),
+            &()
+        ),
+        Err(AccessorConsistencyError::TableSpanMismatch {
+            table_ref: t,
+            data_offset: 0,
+            data_length: 3,
+            commitment_offset: 0,
+            commitment_length: 2,
+        })
+    );
+    let committed = table(&[1, 2, 3], &["x", "y", "x"], &[4, 5, 6]);
+    assert_eq!(
+        verify_accessor_consistency(
+            &expr(t, &accessor),
+            &accessor,
+            &commitments(t, &committed, 1),
+            &()
+        ),
+        Err(AccessorConsistencyError::TableSpanMismatch {
+            table_ref: t,
+            data_offset: 0,
+            data_length: 3,
+            commitment_offset: 1,
+            commitment_length: 3,
+        })
+    );
+}
+
+#[test]
+fn we_cannot_check_a_table_without_commitments() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, table(&[1], &["x"], &[4]), 0);
+    assert_eq!(
+        verify_accessor_consistency(
+            &expr(t, &accessor),
+            &accessor,
+            &QueryCommitments::<RistrettoPoint>::new(),
+            &()
+        ),
+        Err(AccessorConsistencyError::AccessorError(
+            AccessorError::TableNotFound { table_ref: t }
+        ))
+    );
+}
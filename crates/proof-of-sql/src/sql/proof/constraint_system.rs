@@ -0,0 +1,157 @@
+use super::{query_proof::evaluate_constraint_system, EvaluationContext, ProofExpr};
+use crate::base::{commitment::Commitment, database::DataAccessor};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// The way a [`Constraint`] has to hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstraintType {
+    /// The constraint is zero at every row
+    Identity,
+    /// The constraint sums to zero across every row
+    ZeroSum,
+}
+
+/// An MLE that a [`Constraint`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MleRef {
+    /// An MLE that the verifier has the commitment to, e.g. a column of the table, numbered in
+    /// the order that the prover produces them
+    Anchored(usize),
+    /// An intermediate MLE, by the index of its commitment in the proof
+    Intermediate(usize),
+    /// An MLE whose evaluation the verifier computes itself, e.g. from the evaluations of other
+    /// MLEs and the challenges, numbered in the order that the constraints refer to them
+    Derived(usize),
+}
+
+/// A term of a [`Constraint`], which is a coefficient times a product of MLEs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintTerm {
+    /// The coefficient, as an integer if it fits in an `i64` and as a signed hexadecimal scalar
+    /// otherwise
+    pub coefficient: String,
+    /// The MLEs that are multiplied, which are empty for a constant term
+    pub mles: Vec<MleRef>,
+}
+
+/// A sumcheck identity of the proof of a query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Constraint {
+    /// The path of the plan nodes that produced the constraint, e.g. `DenseFilter/Not/Equals`
+    pub plan_node: String,
+    /// The way the constraint has to hold
+    pub constraint_type: ConstraintType,
+    /// The largest number of MLEs in a term
+    pub degree: usize,
+    /// The terms, which add up to the constraint
+    pub terms: Vec<ConstraintTerm>,
+}
+
+/// The sumcheck identities that the proof of a query consists of, for review by external
+/// auditors.
+///
+/// The constraints are exported by running the prover up to its sumcheck, so that they are
+/// exactly the ones that the prover produces for the plan. They are human-readable through
+/// [`fmt::Display`] and machine-parsable through serde, e.g. with [`ConstraintSystem::to_json`].
+///
+/// The constraints of the plan only depend on the data through the bit distributions of sign
+/// checks. The coefficients can include the challenges of the exported run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConstraintSystem {
+    /// The number of rows of the MLEs
+    pub table_length: usize,
+    /// The number of variables of the sumcheck polynomial
+    pub num_sumcheck_variables: usize,
+    /// The number of [`MleRef::Anchored`] MLEs
+    pub num_anchored_mles: usize,
+    /// The number of [`MleRef::Intermediate`] MLEs
+    pub num_intermediate_mles: usize,
+    /// The number of [`MleRef::Derived`] MLEs
+    pub num_derived_mles: usize,
+    /// The constraints, in the order that they are combined into the sumcheck polynomial
+    pub constraints: Vec<Constraint>,
+}
+
+impl ConstraintSystem {
+    /// Export the constraints of the proof of `expr` over the data of `accessor`.
+    pub fn new<C: Commitment>(
+        expr: &(impl ProofExpr<C> + Serialize),
+        accessor: &impl DataAccessor<C::Scalar>,
+        context: &EvaluationContext,
+    ) -> Self {
+        evaluate_constraint_system(expr, accessor, context)
+    }
+
+    /// Encode the constraint system as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("constraint systems are always serializable")
+    }
+}
+
+impl fmt::Display for MleRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MleRef::Anchored(index) => write!(f, "anchored_{index}"),
+            MleRef::Intermediate(index) => write!(f, "intermediate_{index}"),
+            MleRef::Derived(index) => write!(f, "derived_{index}"),
+        }
+    }
+}
+
+impl fmt::Display for ConstraintTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.mles.is_empty() {
+            return write!(f, "{}", self.coefficient);
+        }
+        if self.coefficient != "1" {
+            write!(f, "{} * ", self.coefficient)?;
+        }
+        for (index, mle) in self.mles.iter().enumerate() {
+            if index > 0 {
+                write!(f, " * ")?;
+            }
+            write!(f, "{mle}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.plan_node)?;
+        if self.constraint_type == ConstraintType::ZeroSum {
+            write!(f, "sum(")?;
+        }
+        for (index, term) in self.terms.iter().enumerate() {
+            if index > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{term}")?;
+        }
+        if self.constraint_type == ConstraintType::ZeroSum {
+            write!(f, ")")?;
+        }
+        write!(f, " = 0 (degree {})", self.degree)
+    }
+}
+
+impl fmt::Display for ConstraintSystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} constraints over {} rows ({} sumcheck variables) of {} anchored, {} intermediate \
+             and {} derived MLEs",
+            self.constraints.len(),
+            self.table_length,
+            self.num_sumcheck_variables,
+            self.num_anchored_mles,
+            self.num_intermediate_mles,
+            self.num_derived_mles,
+        )?;
+        for (index, constraint) in self.constraints.iter().enumerate() {
+            writeln!(f, "#{index} {constraint}")?;
+        }
+        Ok(())
+    }
+}
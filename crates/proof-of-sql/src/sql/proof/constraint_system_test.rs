@@ -0,0 +1,102 @@
+use super::{ConstraintSystem, ConstraintType, EvaluationContext, MleRef};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef},
+    },
+    sql::ast::test_utility::*,
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn accessor(t: TableRef) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", [1_i64, 4, 5, 2, 5]),
+            bigint("b", [1_i64, 2, 3, 4, 5]),
+        ]),
+        0,
+        (),
+    )
+}
+
+#[test]
+fn we_can_export_the_constraints_of_a_dense_filter() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let ast = dense_filter(
+        cols_expr_plan::<RistrettoPoint>(t, &["b"], &accessor),
+        tab(t),
+        equal(column(t, "a", &accessor), const_bigint(5)),
+    );
+    let constraint_system = ConstraintSystem::new(&ast, &accessor, &EvaluationContext::new());
+    assert_eq!(constraint_system.table_length, 5);
+    assert_eq!(constraint_system.num_sumcheck_variables, 3);
+    assert_eq!(constraint_system.num_anchored_mles, 2);
+    assert_eq!(constraint_system.num_intermediate_mles, 4);
+    assert_eq!(constraint_system.num_derived_mles, 5);
+
+    let constraints = &constraint_system.constraints;
+    assert_eq!(constraints.len(), 5);
+    assert_eq!(constraints[0].plan_node, "DenseFilter/Equals");
+    assert_eq!(constraints[0].constraint_type, ConstraintType::Identity);
+    assert_eq!(constraints[0].degree, 2);
+    assert_eq!(constraints[0].terms[0].coefficient, "1");
+    assert_eq!(
+        constraints[0].terms[0].mles,
+        [MleRef::Derived(0), MleRef::Derived(1)]
+    );
+    assert_eq!(constraints[2].plan_node, "DenseFilter");
+    assert_eq!(constraints[2].constraint_type, ConstraintType::ZeroSum);
+    assert_eq!(
+        constraint_system.to_string(),
+        "5 constraints over 5 rows (3 sumcheck variables) of 2 anchored, 4 intermediate and 5 \
+         derived MLEs\n\
+         #0 [DenseFilter/Equals] derived_0 * derived_1 = 0 (degree 2)\n\
+         #1 [DenseFilter/Equals] intermediate_1 + -1 * derived_0 * intermediate_0 = 0 (degree 2)\n\
+         #2 [DenseFilter] sum(intermediate_2 * derived_1 + -1 * intermediate_3) = 0 (degree 2)\n\
+         #3 [DenseFilter] intermediate_2 * derived_2 + -1 = 0 (degree 2)\n\
+         #4 [DenseFilter] intermediate_3 * derived_3 + -1 * derived_4 = 0 (degree 2)\n"
+    );
+}
+
+#[test]
+fn we_attribute_constraints_to_nested_plan_nodes() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let ast = dense_filter(
+        cols_expr_plan::<RistrettoPoint>(t, &["a", "b"], &accessor),
+        tab(t),
+        and(
+            not(lte(column(t, "a", &accessor), column(t, "b", &accessor))),
+            equal(column(t, "b", &accessor), const_bigint(2)),
+        ),
+    );
+    let constraint_system = ConstraintSystem::new(&ast, &accessor, &EvaluationContext::new());
+    let plan_nodes: Vec<_> = constraint_system
+        .constraints
+        .iter()
+        .map(|constraint| constraint.plan_node.as_str())
+        .collect();
+    assert!(plan_nodes.contains(&"DenseFilter/And/Not/Inequality"));
+    assert!(plan_nodes.contains(&"DenseFilter/And/Equals"));
+    assert!(plan_nodes.contains(&"DenseFilter/And"));
+    assert_eq!(plan_nodes.last(), Some(&"DenseFilter"));
+}
+
+#[test]
+fn we_can_export_a_constraint_system_as_json() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t);
+    let ast = dense_filter(
+        cols_expr_plan::<RistrettoPoint>(t, &["a"], &accessor),
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(3)),
+    );
+    let constraint_system = ConstraintSystem::new(&ast, &accessor, &EvaluationContext::new());
+    let json = constraint_system.to_json();
+    assert!(json.contains("\"plan_node\": \"DenseFilter/Equals\""));
+    assert!(json.contains("\"constraint_type\": \"ZeroSum\""));
+    let decoded: ConstraintSystem = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, constraint_system);
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Values that a query may use but that are not part of the query text, such as the current time.
+///
+/// The prover binds these values when it proves a query, e.g. `NOW()` becomes a timestamp
+/// literal, and they are absorbed into the proof transcript. The verifier reads them from the
+/// [`VerifiableQueryResult`](super::VerifiableQueryResult) so that it can check them against its
+/// own policy (e.g. that the bound time is close to its own clock) before planning and verifying
+/// the query with the same context.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvaluationContext {
+    current_time: Option<i64>,
+    block_height: Option<i64>,
+}
+
+impl EvaluationContext {
+    /// Create an empty `EvaluationContext`. Queries that use context variables cannot be
+    /// planned with it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `NOW()` to the given Unix timestamp in seconds.
+    pub fn with_current_time(mut self, unix_seconds: i64) -> Self {
+        self.current_time = Some(unix_seconds);
+        self
+    }
+
+    /// Bind `BLOCK_HEIGHT()` to the given chain height.
+    pub fn with_block_height(mut self, block_height: i64) -> Self {
+        self.block_height = Some(block_height);
+        self
+    }
+
+    /// Returns the Unix timestamp in seconds that `NOW()` is bound to, if any.
+    pub fn current_time(&self) -> Option<i64> {
+        self.current_time
+    }
+
+    /// Returns the chain height that `BLOCK_HEIGHT()` is bound to, if any.
+    pub fn block_height(&self) -> Option<i64> {
+        self.block_height
+    }
+}
@@ -3,7 +3,7 @@ use core::ops::Range;
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 /// Indexes of a table for use in the ProvableQueryResult
 pub enum Indexes {
     /// Sparse indexes. (i.e. explicitly specified indexes)
@@ -84,6 +84,36 @@ impl Indexes {
         }
     }
 
+    /// Get the indexes at the given positions.
+    pub(crate) fn slice(&self, positions: Range<usize>) -> Self {
+        match self {
+            Self::Sparse(vec) => Self::Sparse(vec[positions].to_vec()),
+            Self::Dense(range) => {
+                let start = range.start + positions.start as u64;
+                Self::Dense(start..start + positions.len() as u64)
+            }
+        }
+    }
+
+    /// Concatenate indexes that were split with [`Indexes::slice`].
+    ///
+    /// Contiguous dense indexes stay dense, so this restores the indexes that were split.
+    pub(crate) fn concat<'a>(parts: impl IntoIterator<Item = &'a Indexes>) -> Self {
+        let mut parts = parts.into_iter();
+        let Some(mut indexes) = parts.next().cloned() else {
+            return Self::default();
+        };
+        for part in parts {
+            indexes = match (indexes, part) {
+                (Self::Dense(left), Self::Dense(right)) if left.end == right.start => {
+                    Self::Dense(left.start..right.end)
+                }
+                (left, right) => Self::Sparse(left.iter().chain(right.iter()).collect()),
+            };
+        }
+        indexes
+    }
+
     /// Evaluates the mle that is 1 at the indexes and 0 elsewhere at the given evaluation point.
     /// This returne None for Sparse indexes and the actual value for Dense indexes.
     pub fn evaluate_at_point<S: Scalar>(&self, evaluation_point: &[S]) -> Option<S> {
@@ -213,3 +213,20 @@ fn we_can_evaluate_indexes_at_an_evaluation_point() {
         )
     );
 }
+
+#[test]
+fn we_can_slice_and_concat_indexes() {
+    let ix = Indexes::Sparse(vec![1, 3, 4, 7]);
+    let parts = [ix.slice(0..1), ix.slice(1..3), ix.slice(3..4)];
+    assert_eq!(parts[1], Indexes::Sparse(vec![3, 4]));
+    assert_eq!(Indexes::concat(&parts), ix);
+
+    let ix = Indexes::Dense(2..7);
+    let parts = [ix.slice(0..2), ix.slice(2..5)];
+    assert_eq!(parts[1], Indexes::Dense(4..7));
+    assert_eq!(Indexes::concat(&parts), ix);
+
+    let parts = [Indexes::Dense(0..2), Indexes::Sparse(vec![5])];
+    assert_eq!(Indexes::concat(&parts), Indexes::Sparse(vec![0, 1, 5]));
+    assert_eq!(Indexes::concat(&[]), Indexes::default());
+}
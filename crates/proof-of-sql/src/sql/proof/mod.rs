@@ -15,6 +15,11 @@ mod composite_polynomial_builder_test;
 mod proof_counts;
 pub(crate) use proof_counts::ProofCounts;
 
+mod phase_span;
+pub use phase_span::scheme_name;
+#[cfg(test)]
+mod phase_span_test;
+
 mod verification_builder;
 pub(crate) use verification_builder::VerificationBuilder;
 #[cfg(test)]
@@ -25,6 +30,7 @@ pub(crate) use provable_result_column::ProvableResultColumn;
 
 mod provable_query_result;
 pub(crate) use provable_query_result::ProvableQueryResult;
+pub use provable_query_result::ProvableQueryResultLimits;
 #[cfg(test)]
 mod provable_query_result_test;
 
@@ -40,6 +46,9 @@ mod proof_exprs;
 pub use proof_exprs::ProofExpr;
 pub(crate) use proof_exprs::{HonestProver, ProverEvaluate, ProverHonestyMarker};
 
+mod evaluation_context;
+pub use evaluation_context::EvaluationContext;
+
 mod query_proof;
 #[cfg(not(feature = "test"))]
 pub(crate) use query_proof::QueryProof;
@@ -48,6 +57,23 @@ pub use query_proof::QueryProof;
 #[cfg(all(test, feature = "blitzar"))]
 mod query_proof_test;
 
+mod verification_report;
+pub(crate) use verification_report::record_check;
+#[cfg(not(feature = "verification-report"))]
+pub(crate) use verification_report::{VerificationCheck, VerificationReport};
+#[cfg(feature = "verification-report")]
+pub use verification_report::{VerificationCheck, VerificationReport};
+#[cfg(all(test, feature = "blitzar", feature = "verification-report"))]
+mod verification_report_test;
+
+mod proof_version;
+pub use proof_version::{
+    ProofVersion, MIN_SUPPORTED_PROOF_PROTOCOL_VERSION, MIN_VERIFIER_PROTOCOL_VERSION,
+    PROOF_PROTOCOL_VERSION,
+};
+#[cfg(test)]
+mod proof_version_test;
+
 mod query_result;
 pub use query_result::{QueryData, QueryError, QueryResult};
 
@@ -61,6 +87,35 @@ pub use verifiable_query_result::VerifiableQueryResult;
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test;
 
+mod verified_rows;
+pub use verified_rows::VerifiedRows;
+#[cfg(all(test, feature = "blitzar"))]
+mod verified_rows_test;
+
+mod verifiable_batch_result;
+pub use verifiable_batch_result::{BatchQueryError, VerifiableBatchResult};
+#[cfg(all(test, feature = "blitzar"))]
+mod verifiable_batch_result_test;
+
+mod paginated_query_result;
+pub use paginated_query_result::{
+    PaginatedQueryResult, PaginationError, ResultCommitment, ResultPage,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod paginated_query_result_test;
+
+mod result_sample;
+pub use result_sample::{sample_row_indexes, ResultSample, SamplingError};
+#[cfg(all(test, feature = "blitzar"))]
+mod result_sample_test;
+
+#[cfg(feature = "signed-proofs")]
+mod signed_query_proof;
+#[cfg(feature = "signed-proofs")]
+pub use signed_query_proof::{ProofEnvelope, SignedProofError, SignedQueryProof};
+#[cfg(all(test, feature = "blitzar", feature = "signed-proofs"))]
+mod signed_query_proof_test;
+
 #[cfg(all(test, feature = "blitzar"))]
 mod verifiable_query_result_test_utility;
 #[cfg(all(test, feature = "blitzar"))]
@@ -78,3 +133,45 @@ mod indexes_test;
 
 mod result_builder;
 pub(crate) use result_builder::ResultBuilder;
+
+mod result_stream;
+pub use result_stream::{
+    ResultStreamDecoder, ResultStreamEncoder, ResultStreamError, ResultStreamMessage,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod result_stream_test;
+
+mod prover_cost;
+pub use prover_cost::{AdmissionError, ProverAdmissionPolicy, ProverCostEstimate};
+#[cfg(test)]
+mod prover_cost_test;
+
+mod accessor_consistency;
+pub use accessor_consistency::{verify_accessor_consistency, AccessorConsistencyError};
+#[cfg(all(test, feature = "blitzar"))]
+mod accessor_consistency_test;
+
+mod prover_cache;
+pub(crate) use prover_cache::{
+    ColumnLiteralDifference, DerivedMleKey, DerivedMleKind, DerivedMles,
+};
+pub use prover_cache::{ProverCache, ProverCacheStats};
+#[cfg(all(test, feature = "blitzar"))]
+mod prover_cache_test;
+
+mod shared_witness;
+pub(crate) use shared_witness::{
+    committable_column_digest, SharedColumnAccessor, SharedCommitments,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod shared_witness_test;
+
+mod prover_checkpoint;
+pub use prover_checkpoint::{ProverCheckpoint, ProverCheckpointError};
+#[cfg(all(test, feature = "blitzar"))]
+mod prover_checkpoint_test;
+
+mod constraint_system;
+pub use constraint_system::{Constraint, ConstraintSystem, ConstraintTerm, ConstraintType, MleRef};
+#[cfg(all(test, feature = "blitzar"))]
+mod constraint_system_test;
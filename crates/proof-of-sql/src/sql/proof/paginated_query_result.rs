@@ -0,0 +1,369 @@
+use super::{
+    EvaluationContext, ProofExpr, ProofVersion, ProvableQueryResult, QueryData, QueryError,
+    QueryProof, VerifiableQueryResult,
+};
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    database::{ColumnField, CommitmentAccessor, OwnedTable},
+    scalar::Scalar,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The prefix of the hash of a page, which separates it from the hash of an inner node.
+const PAGE_HASH_PREFIX: u8 = 0;
+/// The prefix of the hash of an inner node of the Merkle tree over the pages.
+const NODE_HASH_PREFIX: u8 = 1;
+
+/// Errors that can occur when checking the pages of a [`PaginatedQueryResult`].
+#[derive(Error, Debug)]
+pub enum PaginationError {
+    /// Pages must have at least one row.
+    #[error("the page size must be positive")]
+    ZeroPageSize,
+    /// The page is not one of the pages of the committed result.
+    #[error("page {index} is not part of a result with {num_pages} pages")]
+    PageOutOfRange {
+        /// The index of the page
+        index: usize,
+        /// The number of pages of the committed result
+        num_pages: usize,
+    },
+    /// The page does not have as many rows as the page at its index should have.
+    #[error("page {index} has {actual} rows rather than {expected}")]
+    PageLengthMismatch {
+        /// The index of the page
+        index: usize,
+        /// The number of rows the page should have
+        expected: usize,
+        /// The number of rows the page has
+        actual: usize,
+    },
+    /// The page and its Merkle path do not hash to the committed root.
+    #[error("page {0} does not match the result commitment")]
+    PageMismatch(usize),
+    /// The pages were not received in order.
+    #[error("expected page {expected} but received page {actual}")]
+    UnexpectedPage {
+        /// The index of the page that should have come next
+        expected: usize,
+        /// The index of the page that was received
+        actual: usize,
+    },
+    /// Some pages of the result were never received.
+    #[error("received {received} of the {num_pages} pages of the result")]
+    MissingPages {
+        /// The number of pages that were received
+        received: usize,
+        /// The number of pages of the committed result
+        num_pages: usize,
+    },
+    /// The pages could not be decoded or the result failed to verify.
+    #[error(transparent)]
+    QueryError(#[from] QueryError),
+}
+
+/// A commitment to a query result that is split into pages.
+///
+/// The commitment is the root of a Blake3 Merkle tree whose leaves are the pages, so a single
+/// page can be checked against it with the Merkle path that comes with the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultCommitment {
+    num_rows: usize,
+    page_size: usize,
+    root: [u8; 32],
+}
+
+impl ResultCommitment {
+    /// Returns the number of rows of the result.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of rows of every page but the last.
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Returns the number of pages of the result. An empty result has a single empty page.
+    pub fn num_pages(&self) -> usize {
+        self.num_rows.div_ceil(self.page_size.max(1)).max(1)
+    }
+
+    /// Returns the root of the Merkle tree over the pages.
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// Check that the page is a slice of the committed result.
+    ///
+    /// This only shows that the page is part of the result that the prover committed to. The
+    /// result itself is verified once every page has been received, see
+    /// [`PaginatedQueryResult::verify`].
+    pub fn verify_page(&self, page: &ResultPage) -> Result<(), PaginationError> {
+        if self.page_size == 0 {
+            return Err(PaginationError::ZeroPageSize);
+        }
+        let num_pages = self.num_pages();
+        if page.index >= num_pages {
+            return Err(PaginationError::PageOutOfRange {
+                index: page.index,
+                num_pages,
+            });
+        }
+        let expected = self
+            .page_size
+            .min(self.num_rows - page.index * self.page_size);
+        if page.num_rows() != expected {
+            return Err(PaginationError::PageLengthMismatch {
+                index: page.index,
+                expected,
+                actual: page.num_rows(),
+            });
+        }
+        match merkle_root_from_path(page.hash(), page.index, num_pages, &page.path) {
+            Some(root) if root == self.root => Ok(()),
+            _ => Err(PaginationError::PageMismatch(page.index)),
+        }
+    }
+}
+
+/// A page of a query result, which holds a range of its rows in intermediate form, along with the
+/// Merkle path that links it to the [`ResultCommitment`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ResultPage {
+    index: usize,
+    result: ProvableQueryResult,
+    path: Vec<[u8; 32]>,
+}
+
+impl ResultPage {
+    /// Returns the index of the page.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of rows of the page.
+    pub fn num_rows(&self) -> usize {
+        self.result.indexes().len()
+    }
+
+    /// Decode the rows of the page, e.g. to process them before the rest of the result arrives.
+    ///
+    /// The fields are those of the query, see [`ProofExpr::get_column_result_fields`].
+    pub fn to_owned_table<S: Scalar>(
+        &self,
+        column_result_fields: &[ColumnField],
+    ) -> Result<OwnedTable<S>, QueryError> {
+        if self.result.num_columns() != column_result_fields.len() {
+            return Err(QueryError::MiscellaneousDecodingError);
+        }
+        self.result.to_owned_table(column_result_fields)
+    }
+
+    /// Returns the rows of the page in intermediate form.
+    pub(super) fn result(&self) -> &ProvableQueryResult {
+        &self.result
+    }
+
+    fn hash(&self) -> [u8; 32] {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&[PAGE_HASH_PREFIX]);
+        hasher.update(&(self.index as u64).to_le_bytes());
+        let result = postcard::to_allocvec(&self.result).expect("results are always serializable");
+        hasher.update(&result);
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// A query result that is sent in pages, for results that are too large for a single response.
+///
+/// The prover sends the proof of the result along with a [`ResultCommitment`] to its pages. The
+/// client can then fetch the pages one at a time and check each of them against the commitment
+/// with [`ResultCommitment::verify_page`]. Once every page has arrived,
+/// [`PaginatedQueryResult::verify`] joins them and verifies the result as a whole, just like
+/// [`VerifiableQueryResult::verify`] would.
+///
+/// Note: Because the struct is deserialized from untrusted data, it cannot maintain any invariant
+/// on its data members; hence, they are all public so as to allow for easy manipulation for
+/// testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PaginatedQueryResult<CP: CommitmentEvaluationProof> {
+    /// The commitment to the pages of the result.
+    pub commitment: ResultCommitment,
+    /// The proof that the result is valid.
+    pub proof: Option<QueryProof<CP>>,
+    /// The values the prover bound to the query's context variables, such as `NOW()`.
+    #[serde(default)]
+    pub context: EvaluationContext,
+    /// The version of the prover that created the proof.
+    #[serde(default)]
+    pub version: ProofVersion,
+}
+
+impl<CP: CommitmentEvaluationProof> PaginatedQueryResult<CP> {
+    /// Split a `VerifiableQueryResult` into pages of at most `page_size` rows.
+    ///
+    /// Returns the paginated result, which is sent up front, along with the pages, which the client
+    /// fetches separately.
+    pub fn new(
+        result: VerifiableQueryResult<CP>,
+        expr: &impl ProofExpr<CP::Commitment>,
+        page_size: usize,
+    ) -> Result<(Self, Vec<ResultPage>), PaginationError> {
+        let column_result_fields = expr.get_column_result_fields();
+        let provable_result = result
+            .provable_result
+            .unwrap_or_else(|| ProvableQueryResult::new_empty(column_result_fields.len()));
+        let (commitment, pages) =
+            commit_pages::<CP::Scalar>(&provable_result, &column_result_fields, page_size)?;
+        let paginated_result = Self {
+            commitment,
+            proof: result.proof,
+            context: result.context,
+            version: result.version,
+        };
+        Ok((paginated_result, pages))
+    }
+
+    /// Check the pages against the result commitment, join them and verify the result.
+    ///
+    /// The pages must be given in order. Upon success, this function returns the finalized form of
+    /// the query result.
+    ///
+    /// Note: This does NOT transform the result!
+    pub fn verify(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        pages: impl IntoIterator<Item = ResultPage>,
+    ) -> Result<QueryData<CP::Scalar>, PaginationError> {
+        let mut page_results = Vec::new();
+        for (expected, page) in pages.into_iter().enumerate() {
+            if page.index != expected {
+                return Err(PaginationError::UnexpectedPage {
+                    expected,
+                    actual: page.index,
+                });
+            }
+            self.commitment.verify_page(&page)?;
+            page_results.push(page.result);
+        }
+        let num_pages = self.commitment.num_pages();
+        if page_results.len() != num_pages {
+            return Err(PaginationError::MissingPages {
+                received: page_results.len(),
+                num_pages,
+            });
+        }
+        let provable_result = ProvableQueryResult::join_rows::<CP::Scalar>(
+            &page_results,
+            &expr.get_column_result_fields(),
+        )?;
+        let result = VerifiableQueryResult {
+            provable_result: self.proof.is_some().then_some(provable_result),
+            proof: self.proof.clone(),
+            context: self.context.clone(),
+            version: self.version.clone(),
+        };
+        Ok(result.verify(expr, accessor, setup)?)
+    }
+}
+
+/// Split the result into pages of at most `page_size` rows and commit to them.
+pub(super) fn commit_pages<S: Scalar>(
+    provable_result: &ProvableQueryResult,
+    column_result_fields: &[ColumnField],
+    page_size: usize,
+) -> Result<(ResultCommitment, Vec<ResultPage>), PaginationError> {
+    if page_size == 0 {
+        return Err(PaginationError::ZeroPageSize);
+    }
+    let num_rows = provable_result.indexes().len();
+    let mut pages: Vec<_> = provable_result
+        .split_rows::<S>(column_result_fields, page_size)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| ResultPage {
+            index,
+            result,
+            path: Vec::new(),
+        })
+        .collect();
+    let levels = merkle_levels(pages.iter().map(ResultPage::hash).collect());
+    for page in &mut pages {
+        page.path = merkle_path(&levels, page.index);
+    }
+    let commitment = ResultCommitment {
+        num_rows,
+        page_size,
+        root: levels.last().expect("there is always a page")[0],
+    };
+    Ok((commitment, pages))
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_HASH_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Build the levels of the Merkle tree over the leaves, from the leaves up to the root.
+///
+/// A node without a sibling is moved up to the next level as is.
+fn merkle_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+        let next_level = level
+            .chunks(2)
+            .map(|nodes| match nodes {
+                [left, right] => node_hash(left, right),
+                [node] => *node,
+                _ => unreachable!("chunks have one or two nodes"),
+            })
+            .collect();
+        levels.push(next_level);
+    }
+    levels
+}
+
+/// Returns the siblings of the leaf at `index` on the way up to the root.
+fn merkle_path(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut path = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            path.push(*sibling);
+        }
+        index /= 2;
+    }
+    path
+}
+
+/// Returns the root of a Merkle tree with `num_leaves` leaves that has the leaf at `index` with
+/// the given path, or `None` if the path does not have the right length.
+fn merkle_root_from_path(
+    leaf: [u8; 32],
+    mut index: usize,
+    num_leaves: usize,
+    path: &[[u8; 32]],
+) -> Option<[u8; 32]> {
+    let mut hash = leaf;
+    let mut siblings = path.iter();
+    let mut width = num_leaves;
+    while width > 1 {
+        if index ^ 1 < width {
+            let sibling = siblings.next()?;
+            hash = if index % 2 == 0 {
+                node_hash(&hash, sibling)
+            } else {
+                node_hash(sibling, &hash)
+            };
+        }
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none().then_some(hash)
+}
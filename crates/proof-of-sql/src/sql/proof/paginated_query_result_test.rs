@@ -0,0 +1,199 @@
+use super::{PaginatedQueryResult, PaginationError, ProofExpr, ResultPage, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn accessor_and_query() -> (
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+    ProofPlan<RistrettoPoint>,
+) {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]),
+            varchar("b", ["a", "bb", "c", "dd", "e", "ff", "g", "hh", "i", "jj"]),
+        ]),
+        0,
+        (),
+    );
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(3)),
+    );
+    (accessor, ast)
+}
+
+fn paginate(
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    ast: &ProofPlan<RistrettoPoint>,
+    page_size: usize,
+) -> (PaginatedQueryResult<InnerProductProof>, Vec<ResultPage>) {
+    let result = VerifiableQueryResult::<InnerProductProof>::new(ast, accessor, &());
+    PaginatedQueryResult::new(result, ast, page_size).unwrap()
+}
+
+#[test]
+fn we_can_verify_a_result_that_is_split_into_pages() {
+    let (accessor, ast) = accessor_and_query();
+    let expected_res = owned_table([
+        bigint("a", [3, 4, 5, 6, 7, 8, 9, 10]),
+        varchar("b", ["c", "dd", "e", "ff", "g", "hh", "i", "jj"]),
+    ]);
+    for page_size in [1, 3, 4, 8, 20] {
+        let (paginated_res, pages) = paginate(&accessor, &ast, page_size);
+        assert_eq!(paginated_res.commitment.num_rows(), 8);
+        assert_eq!(paginated_res.commitment.page_size(), page_size);
+        assert_eq!(paginated_res.commitment.num_pages(), pages.len());
+        assert_eq!(pages.len(), 8_usize.div_ceil(page_size));
+        for page in &pages {
+            paginated_res.commitment.verify_page(page).unwrap();
+        }
+        let res = paginated_res
+            .verify(&ast, &accessor, &(), pages)
+            .unwrap()
+            .table;
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_decode_a_page_before_the_rest_of_the_result_arrives() {
+    let (accessor, ast) = accessor_and_query();
+    let (paginated_res, pages) = paginate(&accessor, &ast, 3);
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[2].index(), 2);
+    assert_eq!(pages[2].num_rows(), 2);
+    paginated_res.commitment.verify_page(&pages[2]).unwrap();
+    let fields = ast.get_column_result_fields();
+    let expected_res: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [9, 10]), varchar("b", ["i", "jj"])]);
+    assert_eq!(pages[2].to_owned_table(&fields).unwrap(), expected_res);
+    assert!(pages[2]
+        .to_owned_table::<Curve25519Scalar>(&fields[..1])
+        .is_err());
+}
+
+#[test]
+fn we_can_verify_an_empty_result_as_a_single_page() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([bigint("a", [0_i64; 0])]),
+        0,
+        (),
+    );
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(3)),
+    );
+    let (paginated_res, pages) = paginate(&accessor, &ast, 4);
+    assert_eq!(paginated_res.commitment.num_rows(), 0);
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].num_rows(), 0);
+    let res = paginated_res
+        .verify(&ast, &accessor, &(), pages)
+        .unwrap()
+        .table;
+    assert_eq!(res, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+#[test]
+fn we_cannot_split_a_result_into_empty_pages() {
+    let (accessor, ast) = accessor_and_query();
+    let result = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    assert!(matches!(
+        PaginatedQueryResult::new(result, &ast, 0),
+        Err(PaginationError::ZeroPageSize)
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_tampered_page() {
+    let (accessor, ast) = accessor_and_query();
+    let (paginated_res, mut pages) = paginate(&accessor, &ast, 3);
+
+    let mut serialized = serde_json::to_value(&pages[1]).unwrap();
+    let byte = serialized["result"]["data"][0].as_u64().unwrap();
+    serialized["result"]["data"][0] = (byte ^ 1).into();
+    pages[1] = serde_json::from_value(serialized).unwrap();
+    assert!(matches!(
+        paginated_res.commitment.verify_page(&pages[1]),
+        Err(PaginationError::PageMismatch(1))
+    ));
+    assert!(matches!(
+        paginated_res.verify(&ast, &accessor, &(), pages),
+        Err(PaginationError::PageMismatch(1))
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_page_with_the_path_of_another_page() {
+    let (accessor, ast) = accessor_and_query();
+    let (paginated_res, pages) = paginate(&accessor, &ast, 3);
+
+    let mut serialized = serde_json::to_value(&pages[0]).unwrap();
+    serialized["path"] = serde_json::to_value(&pages[1]).unwrap()["path"].clone();
+    let page: ResultPage = serde_json::from_value(serialized).unwrap();
+    assert!(matches!(
+        paginated_res.commitment.verify_page(&page),
+        Err(PaginationError::PageMismatch(0))
+    ));
+
+    let mut serialized = serde_json::to_value(&pages[2]).unwrap();
+    serialized["index"] = 3.into();
+    let page: ResultPage = serde_json::from_value(serialized).unwrap();
+    assert!(matches!(
+        paginated_res.commitment.verify_page(&page),
+        Err(PaginationError::PageOutOfRange {
+            index: 3,
+            num_pages: 3
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_pages_that_are_out_of_order_or_missing() {
+    let (accessor, ast) = accessor_and_query();
+    let (paginated_res, pages) = paginate(&accessor, &ast, 3);
+
+    let mut swapped_pages = pages.clone();
+    swapped_pages.swap(0, 1);
+    assert!(matches!(
+        paginated_res.verify(&ast, &accessor, &(), swapped_pages),
+        Err(PaginationError::UnexpectedPage {
+            expected: 0,
+            actual: 1
+        })
+    ));
+    assert!(matches!(
+        paginated_res.verify(&ast, &accessor, &(), pages[..2].to_vec()),
+        Err(PaginationError::MissingPages {
+            received: 2,
+            num_pages: 3
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_pages_of_a_different_result() {
+    let (accessor, ast) = accessor_and_query();
+    let (paginated_res, _) = paginate(&accessor, &ast, 3);
+    let (_, other_pages) = paginate(&accessor, &ast, 4);
+    assert!(matches!(
+        paginated_res.verify(&ast, &accessor, &(), other_pages),
+        Err(PaginationError::PageLengthMismatch {
+            index: 0,
+            expected: 3,
+            actual: 4
+        })
+    ));
+}
@@ -0,0 +1,76 @@
+use super::ProofExpr;
+use crate::base::commitment::Commitment;
+
+/// The fields that the span of every phase of proving or verifying a query records, so that the
+/// traces of different queries and commitment schemes can be compared.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct PhaseFields {
+    /// The number of rows of the table span that is queried.
+    pub rows: usize,
+    /// The number of columns that the queries reference.
+    pub columns: usize,
+    /// The commitment scheme, see [`scheme_name`].
+    pub scheme: &'static str,
+}
+
+impl PhaseFields {
+    /// The fields of the phases of a proof of the queries over `rows` rows.
+    pub fn new<C: Commitment>(exprs: &[impl ProofExpr<C>], rows: usize) -> Self {
+        Self {
+            rows,
+            columns: exprs
+                .iter()
+                .map(|expr| expr.get_column_references().len())
+                .sum(),
+            scheme: scheme_name::<C>(),
+        }
+    }
+}
+
+/// Returns the name of a commitment type without its module path, e.g. `DoryCommitment`, which
+/// identifies its commitment scheme in traces.
+pub fn scheme_name<C: Commitment>() -> &'static str {
+    let name = core::any::type_name::<C>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Create a debug span for a phase of proving or verifying a query, which records the
+/// [`PhaseFields`].
+#[cfg(feature = "tracing")]
+macro_rules! phase_span {
+    ($name:literal, $fields:expr) => {{
+        let fields: &PhaseFields = &$fields;
+        tracing::debug_span!(
+            $name,
+            rows = fields.rows,
+            columns = fields.columns,
+            scheme = fields.scheme
+        )
+    }};
+}
+
+/// Without the `tracing` feature, phases are not recorded and their spans do nothing.
+#[cfg(not(feature = "tracing"))]
+macro_rules! phase_span {
+    ($name:literal, $fields:expr) => {{
+        let _: &PhaseFields = &$fields;
+        $crate::sql::proof::phase_span::DisabledPhaseSpan
+    }};
+}
+pub(super) use phase_span;
+
+/// The span of a phase in builds without the `tracing` feature, which mirrors the parts of
+/// `tracing::Span` that phases use.
+#[cfg(not(feature = "tracing"))]
+pub(super) struct DisabledPhaseSpan;
+
+#[cfg(not(feature = "tracing"))]
+impl DisabledPhaseSpan {
+    pub fn entered(self) -> Self {
+        self
+    }
+
+    pub fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        f()
+    }
+}
@@ -0,0 +1,9 @@
+use super::scheme_name;
+use crate::proof_primitive::dory::DoryCommitment;
+use curve25519_dalek::RistrettoPoint;
+
+#[test]
+fn the_scheme_name_is_the_commitment_type_without_its_path() {
+    assert_eq!(scheme_name::<RistrettoPoint>(), "RistrettoPoint");
+    assert_eq!(scheme_name::<DoryCommitment>(), "DoryCommitment");
+}
@@ -1,14 +1,21 @@
 use super::{
-    CompositePolynomialBuilder, SumcheckRandomScalars, SumcheckSubpolynomial,
-    SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+    committable_column_digest, CompositePolynomialBuilder, Constraint, ConstraintSystem,
+    ConstraintTerm, ConstraintType, DerivedMleKey, DerivedMles, MleRef, ProverCache,
+    SharedCommitments, SumcheckRandomScalars, SumcheckSubpolynomial, SumcheckSubpolynomialTerm,
+    SumcheckSubpolynomialType,
 };
 use crate::base::{
     bit::BitDistribution,
     commitment::{Commitment, CommittableColumn, VecCommitmentExt},
     polynomial::{CompositePolynomial, MultilinearExtension},
+    proof::{ProverDeadline, ProverError},
     scalar::Scalar,
 };
 use num_traits::Zero;
+use std::{collections::HashMap, ffi::c_void, sync::Arc};
+
+/// The number of intermediate MLEs committed to between checks of the prover's deadline.
+const COMMITMENT_CHUNK_SIZE: usize = 16;
 
 /// Track components used to form a query's proof
 pub struct ProofBuilder<'a, S: Scalar> {
@@ -17,7 +24,13 @@ pub struct ProofBuilder<'a, S: Scalar> {
     bit_distributions: Vec<BitDistribution>,
     commitment_descriptor: Vec<CommittableColumn<'a>>,
     pcs_proof_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
+    /// The indexes in `pcs_proof_mles` of the intermediate MLEs.
+    intermediate_mle_indexes: Vec<usize>,
     sumcheck_subpolynomials: Vec<SumcheckSubpolynomial<'a, S>>,
+    /// The path of plan nodes that produced each subpolynomial, for [`ConstraintSystem`]s.
+    sumcheck_subpolynomial_plan_nodes: Vec<String>,
+    /// The path of plan nodes that are currently being evaluated.
+    plan_node_path: Vec<&'static str>,
     /// The challenges used in creation of the constraints in the proof.
     /// Specifically, these are the challenges that the verifier sends to
     /// the prover after the prover sends the result, but before the prover
@@ -26,6 +39,9 @@ pub struct ProofBuilder<'a, S: Scalar> {
     /// Note: this vector is treated as a stack and the first
     /// challenge is the last entry in the vector.
     post_result_challenges: Vec<S>,
+    prover_cache: Option<&'a ProverCache<S>>,
+    /// The first overflow that a gadget reported, see [`ProofBuilder::report_overflow`].
+    overflow: Option<ProverError>,
 }
 
 impl<'a, S: Scalar> ProofBuilder<'a, S> {
@@ -40,11 +56,21 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
             bit_distributions: Vec::new(),
             commitment_descriptor: Vec::new(),
             pcs_proof_mles: Vec::new(),
+            intermediate_mle_indexes: Vec::new(),
             sumcheck_subpolynomials: Vec::new(),
+            sumcheck_subpolynomial_plan_nodes: Vec::new(),
+            plan_node_path: Vec::new(),
             post_result_challenges,
+            prover_cache: None,
+            overflow: None,
         }
     }
 
+    /// Reuse intermediate MLEs from, and store them in, the given cache.
+    pub fn set_prover_cache(&mut self, prover_cache: &'a ProverCache<S>) {
+        self.prover_cache = Some(prover_cache);
+    }
+
     pub fn table_length(&self) -> usize {
         self.table_length
     }
@@ -57,6 +83,18 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         self.sumcheck_subpolynomials.len()
     }
 
+    /// Look up intermediate MLEs that were derived by an earlier query.
+    pub fn cached_derived_mles(&self, key: &DerivedMleKey) -> Option<Arc<DerivedMles<S>>> {
+        self.prover_cache?.get(key)
+    }
+
+    /// Make intermediate MLEs available to later queries. `mles` is only called if they are cached.
+    pub fn cache_derived_mles(&self, key: DerivedMleKey, mles: impl FnOnce() -> DerivedMles<S>) {
+        if let Some(prover_cache) = self.prover_cache {
+            prover_cache.insert_with(key, mles);
+        }
+    }
+
     /// Produce a bit distribution that describes which bits are constant
     /// and which bits varying in a column of data
     pub fn produce_bit_distribution(&mut self, dist: BitDistribution) {
@@ -79,9 +117,36 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         data: impl MultilinearExtension<S> + Into<CommittableColumn<'a>> + Copy + 'a,
     ) {
         self.commitment_descriptor.push(data.into());
+        self.intermediate_mle_indexes
+            .push(self.pcs_proof_mles.len());
         self.produce_anchored_mle(data);
     }
 
+    /// Report that `gadget` computed a value in `row` that does not fit in the type of its result.
+    ///
+    /// The prover gives up on the proof with the first overflow that was reported once every
+    /// expression has been evaluated.
+    pub fn report_overflow(&mut self, gadget: &'static str, row: usize) {
+        self.overflow
+            .get_or_insert(ProverError::Overflow { gadget, row });
+    }
+
+    /// Returns the first overflow that a gadget reported, if any.
+    pub fn take_overflow(&mut self) -> Option<ProverError> {
+        self.overflow.take()
+    }
+
+    /// Attribute the subpolynomials produced until [`ProofBuilder::exit_plan_node`] to the plan
+    /// node `name`, which is nested in the nodes that were entered before it.
+    pub fn enter_plan_node(&mut self, name: &'static str) {
+        self.plan_node_path.push(name);
+    }
+
+    /// Leave the plan node that was entered last.
+    pub fn exit_plan_node(&mut self) {
+        self.plan_node_path.pop();
+    }
+
     /// Produce a subpolynomial to be aggegated into sumcheck where the sum across binary
     /// values of the variables is zero.
     pub fn produce_sumcheck_subpolynomial(
@@ -91,13 +156,75 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
     ) {
         self.sumcheck_subpolynomials
             .push(SumcheckSubpolynomial::new(subpolynomial_type, terms));
+        self.sumcheck_subpolynomial_plan_nodes
+            .push(self.plan_node_path.join("/"));
+    }
+
+    /// Describe the subpolynomials that were produced as constraints on the MLEs of the proof.
+    pub fn constraint_system(&self) -> ConstraintSystem {
+        let mut mle_refs: HashMap<*const c_void, MleRef> = HashMap::new();
+        let mut num_anchored_mles = 0;
+        let mut intermediate_mle_indexes = self.intermediate_mle_indexes.iter().peekable();
+        for (index, mle) in self.pcs_proof_mles.iter().enumerate() {
+            let mle_ref = if intermediate_mle_indexes.next_if_eq(&&index).is_some() {
+                MleRef::Intermediate(index - num_anchored_mles)
+            } else {
+                num_anchored_mles += 1;
+                MleRef::Anchored(num_anchored_mles - 1)
+            };
+            mle_refs.entry(mle.id()).or_insert(mle_ref);
+        }
+        let mut num_derived_mles = 0;
+        let constraints = self
+            .sumcheck_subpolynomials
+            .iter()
+            .zip(&self.sumcheck_subpolynomial_plan_nodes)
+            .map(|(subpolynomial, plan_node)| {
+                let terms: Vec<_> = subpolynomial
+                    .terms()
+                    .iter()
+                    .map(|(coefficient, mles)| ConstraintTerm {
+                        coefficient: format_coefficient(*coefficient),
+                        mles: mles
+                            .iter()
+                            .map(|mle| {
+                                *mle_refs.entry(mle.id()).or_insert_with(|| {
+                                    num_derived_mles += 1;
+                                    MleRef::Derived(num_derived_mles - 1)
+                                })
+                            })
+                            .collect(),
+                    })
+                    .collect();
+                Constraint {
+                    plan_node: plan_node.clone(),
+                    constraint_type: match subpolynomial.subpolynomial_type() {
+                        SumcheckSubpolynomialType::Identity => ConstraintType::Identity,
+                        SumcheckSubpolynomialType::ZeroSum => ConstraintType::ZeroSum,
+                    },
+                    degree: terms.iter().map(|term| term.mles.len()).max().unwrap_or(0),
+                    terms,
+                }
+            })
+            .collect();
+        ConstraintSystem {
+            table_length: self.table_length,
+            num_sumcheck_variables: self.num_sumcheck_variables,
+            num_anchored_mles,
+            num_intermediate_mles: self.intermediate_mle_indexes.len(),
+            num_derived_mles,
+            constraints,
+        }
     }
 
     /// Compute commitments of all the interemdiate MLEs used in sumcheck
-    #[tracing::instrument(
-        name = "ProofBuilder::commit_intermediate_mles",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "ProofBuilder::commit_intermediate_mles",
+            level = "debug",
+            skip_all
+        )
     )]
     pub fn commit_intermediate_mles<C: Commitment>(
         &self,
@@ -111,12 +238,79 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         )
     }
 
+    /// Compute commitments of all the intermediate MLEs used in sumcheck, checking the deadline
+    /// before committing to every chunk of MLEs.
+    ///
+    /// The commitments are the same as the ones computed by
+    /// [`ProofBuilder::commit_intermediate_mles`].
+    pub fn commit_intermediate_mles_with_deadline<C: Commitment>(
+        &self,
+        offset_generators: usize,
+        setup: &C::PublicSetup<'_>,
+        deadline: &ProverDeadline,
+    ) -> Result<Vec<C>, ProverError> {
+        commit_with_deadline(
+            &self.commitment_descriptor,
+            offset_generators,
+            setup,
+            deadline,
+        )
+    }
+
+    /// Compute commitments of all the intermediate MLEs used in sumcheck, reusing the commitments
+    /// that other proofs computed for the same MLEs.
+    ///
+    /// The commitments are the same as the ones computed by
+    /// [`ProofBuilder::commit_intermediate_mles`].
+    pub fn commit_intermediate_mles_shared<C: Commitment>(
+        &self,
+        offset_generators: usize,
+        setup: &C::PublicSetup<'_>,
+        deadline: &ProverDeadline,
+        shared_commitments: &SharedCommitments<C>,
+    ) -> Result<Vec<C>, ProverError> {
+        let known_commitments =
+            shared_commitments.lookup(&self.commitment_descriptor, offset_generators);
+        let missing_columns: Vec<_> = self
+            .commitment_descriptor
+            .iter()
+            .zip(&known_commitments)
+            .filter(|(_, (_, commitment))| commitment.is_none())
+            .map(|(column, _)| column.clone())
+            .collect();
+        let mut missing_commitments =
+            commit_with_deadline(&missing_columns, offset_generators, setup, deadline)?.into_iter();
+        Ok(known_commitments
+            .into_iter()
+            .map(|(key, commitment)| {
+                commitment.unwrap_or_else(|| {
+                    let commitment = missing_commitments
+                        .next()
+                        .expect("there is a commitment for every missing column");
+                    shared_commitments.insert(key, commitment);
+                    commitment
+                })
+            })
+            .collect())
+    }
+
+    /// Returns digests of the intermediate MLEs, in the order that they are committed to.
+    pub fn commitment_digests(&self) -> Vec<[u8; 32]> {
+        self.commitment_descriptor
+            .iter()
+            .map(committable_column_digest)
+            .collect()
+    }
+
     /// Given random multipliers, construct an aggregatated sumcheck polynomial from all
     /// the individual subpolynomials.
-    #[tracing::instrument(
-        name = "ProofBuilder::make_sumcheck_polynomial",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "ProofBuilder::make_sumcheck_polynomial",
+            level = "debug",
+            skip_all
+        )
     )]
     pub fn make_sumcheck_polynomial(
         &self,
@@ -138,10 +332,13 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
 
     /// Given the evaluation vector, compute evaluations of all the MLEs used in sumcheck except
     /// for those that correspond to result columns sent to the verifier.
-    #[tracing::instrument(
-        name = "ProofBuilder::evaluate_pcs_proof_mles",
-        level = "debug",
-        skip_all
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "ProofBuilder::evaluate_pcs_proof_mles",
+            level = "debug",
+            skip_all
+        )
     )]
     pub fn evaluate_pcs_proof_mles(&self, evaluation_vec: &[S]) -> Vec<S> {
         let mut res = Vec::with_capacity(self.pcs_proof_mles.len());
@@ -153,7 +350,10 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
 
     /// Given random multipliers, multiply and add together all of the MLEs used in sumcheck except
     /// for those that correspond to result columns sent to the verifier.
-    #[tracing::instrument(name = "ProofBuilder::fold_pcs_proof_mles", level = "debug", skip_all)]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ProofBuilder::fold_pcs_proof_mles", level = "debug", skip_all)
+    )]
     pub fn fold_pcs_proof_mles(&self, multipliers: &[S]) -> Vec<S> {
         assert_eq!(multipliers.len(), self.pcs_proof_mles.len());
         let mut res = vec![Zero::zero(); self.table_length];
@@ -177,3 +377,30 @@ impl<'a, S: Scalar> ProofBuilder<'a, S> {
         self.post_result_challenges.pop().unwrap()
     }
 }
+
+/// Formats a coefficient as a signed integer if it is small enough, and in hexadecimal otherwise.
+fn format_coefficient<S: Scalar>(coefficient: S) -> String {
+    match TryInto::<i64>::try_into(coefficient) {
+        Ok(coefficient) => coefficient.to_string(),
+        Err(_) => format!("{coefficient:+}"),
+    }
+}
+
+/// Compute commitments of the columns, checking the deadline before committing to every chunk of
+/// columns.
+fn commit_with_deadline<C: Commitment>(
+    committable_columns: &[CommittableColumn],
+    offset_generators: usize,
+    setup: &C::PublicSetup<'_>,
+    deadline: &ProverDeadline,
+) -> Result<Vec<C>, ProverError> {
+    let mut commitments = vec![C::default(); committable_columns.len()];
+    for (commitments, committable_columns) in commitments
+        .chunks_mut(COMMITMENT_CHUNK_SIZE)
+        .zip(committable_columns.chunks(COMMITMENT_CHUNK_SIZE))
+    {
+        deadline.check()?;
+        C::compute_commitments(commitments, committable_columns, offset_generators, setup);
+    }
+    Ok(commitments)
+}
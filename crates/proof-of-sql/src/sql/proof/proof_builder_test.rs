@@ -3,6 +3,7 @@ use crate::{
     base::{
         database::{ColumnField, ColumnType},
         polynomial::{compute_evaluation_vector, CompositePolynomial, MultilinearExtension},
+        proof::{ProverDeadline, ProverError},
         scalar::{compute_commitment_for_testing, Curve25519Scalar},
     },
     sql::proof::{Indexes, ResultBuilder, SumcheckSubpolynomialType},
@@ -46,6 +47,29 @@ fn we_can_compute_commitments_for_intermediate_mles_using_a_non_zero_offset() {
     );
 }
 
+#[test]
+fn we_can_compute_commitments_for_intermediate_mles_in_chunks_with_a_deadline() {
+    let mles: Vec<_> = (0..20_i64).map(|i| [i, 2 * i]).collect();
+    let mut builder = ProofBuilder::<Curve25519Scalar>::new(2, 1, Vec::new());
+    for mle in &mles {
+        builder.produce_intermediate_mle(&mle[..]);
+    }
+    let deadline = ProverDeadline::new();
+    let commitments: Vec<RistrettoPoint> = builder
+        .commit_intermediate_mles_with_deadline(123, &(), &deadline)
+        .unwrap();
+    assert_eq!(commitments, builder.commit_intermediate_mles(123, &()));
+    assert_eq!(deadline.steps_taken(), 2);
+
+    let deadline = ProverDeadline::new().with_step_budget(1);
+    assert_eq!(
+        builder
+            .commit_intermediate_mles_with_deadline::<RistrettoPoint>(123, &(), &deadline)
+            .unwrap_err(),
+        ProverError::StepBudgetExhausted { step_budget: 1 }
+    );
+}
+
 #[test]
 fn we_can_evaluate_pcs_proof_mles() {
     let mle1 = [1, 2];
@@ -18,6 +18,7 @@ pub struct ProofCounts {
 }
 
 impl ProofCounts {
+    #[cfg(feature = "tracing")]
     #[tracing::instrument(name = "ProofCounts::annotate_trace", level = "debug", skip_all)]
     pub fn annotate_trace(&self) {
         tracing::info!(
@@ -44,6 +44,22 @@ pub trait ProofExpr<C: Commitment>: Debug + Send + Sync + ProverEvaluate<C::Scal
 
     /// Return all the columns referenced in the Query
     fn get_column_references(&self) -> IndexSet<ColumnRef>;
+
+    /// The number of rows of the input table that the query selects, given its verified result.
+    ///
+    /// This is the number of rows of the result, unless the query aggregates the selected rows.
+    fn get_selected_row_count(&self, result: &OwnedTable<C::Scalar>) -> usize {
+        result.num_rows()
+    }
+
+    /// Whether [`ProofExpr::verifier_evaluate`] and [`ProofExpr::get_selected_row_count`] read the
+    /// decoded result.
+    ///
+    /// If not, the verifier passes `None` as the result and the number of rows of the result is
+    /// the number of selected rows, so that the result can be decoded lazily after verification.
+    fn requires_result_table(&self) -> bool {
+        true
+    }
 }
 
 pub trait ProverEvaluate<S: Scalar> {
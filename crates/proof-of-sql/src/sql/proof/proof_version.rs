@@ -0,0 +1,77 @@
+use super::QueryError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The version of the proof protocol that this crate creates and verifies.
+///
+/// This is bumped whenever a proof created by this crate could fail to verify with an older
+/// version of the crate, or the other way around.
+pub const PROOF_PROTOCOL_VERSION: u32 = 1;
+
+/// The oldest protocol version of the proofs that this crate can verify.
+pub const MIN_SUPPORTED_PROOF_PROTOCOL_VERSION: u32 = 1;
+
+/// The oldest verifier protocol version that can verify the proofs that this crate creates.
+pub const MIN_VERIFIER_PROTOCOL_VERSION: u32 = 1;
+
+/// The version of the prover that created a proof, which is sent along with the proof.
+///
+/// Before verifying a proof, the verifier checks that it can verify proofs of its protocol
+/// version and that it is at least the minimal verifier version that the proof requires.
+/// Otherwise, verification fails with [`QueryError::IncompatibleVersion`] rather than with an
+/// error about the proof itself, which would be hard to tell apart from a dishonest prover in a
+/// deployment where provers and verifiers are upgraded separately.
+///
+/// Note: the version is not part of the transcript. A prover that lies about it can only make a
+/// valid proof fail the version check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofVersion {
+    /// The version of the crate, e.g. `0.15.0`. This is informational only.
+    pub crate_version: String,
+    /// The protocol version.
+    pub protocol_version: u32,
+    /// The oldest verifier protocol version that can verify the proofs of this version.
+    pub min_verifier_version: u32,
+}
+
+impl ProofVersion {
+    /// Returns the version of this crate.
+    pub fn current() -> Self {
+        Self {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            protocol_version: PROOF_PROTOCOL_VERSION,
+            min_verifier_version: MIN_VERIFIER_PROTOCOL_VERSION,
+        }
+    }
+
+    /// Checks that this crate can verify a proof of this version.
+    pub fn check_compatibility(&self) -> Result<(), QueryError> {
+        if self.protocol_version < MIN_SUPPORTED_PROOF_PROTOCOL_VERSION
+            || self.min_verifier_version > PROOF_PROTOCOL_VERSION
+        {
+            Err(QueryError::IncompatibleVersion {
+                proof: self.clone(),
+                verifier: Self::current(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Proofs that were serialized without a version are treated as proofs of this crate.
+impl Default for ProofVersion {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl fmt::Display for ProofVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (protocol {}, verifiable by protocol {} and later)",
+            self.crate_version, self.protocol_version, self.min_verifier_version
+        )
+    }
+}
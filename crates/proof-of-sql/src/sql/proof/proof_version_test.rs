@@ -0,0 +1,63 @@
+use super::{
+    ProofVersion, QueryError, MIN_SUPPORTED_PROOF_PROTOCOL_VERSION, PROOF_PROTOCOL_VERSION,
+};
+
+#[test]
+fn we_can_verify_proofs_of_the_current_version() {
+    let version = ProofVersion::current();
+    assert_eq!(version.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(version.protocol_version, PROOF_PROTOCOL_VERSION);
+    assert_eq!(ProofVersion::default(), version);
+    assert!(version.check_compatibility().is_ok());
+}
+
+#[test]
+fn we_can_verify_proofs_of_newer_provers_that_older_verifiers_support() {
+    let version = ProofVersion {
+        crate_version: "99.0.0".to_string(),
+        protocol_version: PROOF_PROTOCOL_VERSION + 1,
+        min_verifier_version: PROOF_PROTOCOL_VERSION,
+    };
+    assert!(version.check_compatibility().is_ok());
+}
+
+#[test]
+fn we_cannot_verify_proofs_that_require_a_newer_verifier() {
+    let version = ProofVersion {
+        crate_version: "99.0.0".to_string(),
+        protocol_version: PROOF_PROTOCOL_VERSION + 1,
+        min_verifier_version: PROOF_PROTOCOL_VERSION + 1,
+    };
+    match version.check_compatibility() {
+        Err(QueryError::IncompatibleVersion { proof, verifier }) => {
+            assert_eq!(proof, version);
+            assert_eq!(verifier, ProofVersion::current());
+        }
+        _ => panic!("expected an incompatible version"),
+    }
+}
+
+#[test]
+fn we_cannot_verify_proofs_of_unsupported_older_provers() {
+    let version = ProofVersion {
+        crate_version: "0.0.1".to_string(),
+        protocol_version: MIN_SUPPORTED_PROOF_PROTOCOL_VERSION - 1,
+        min_verifier_version: 0,
+    };
+    assert!(matches!(
+        version.check_compatibility(),
+        Err(QueryError::IncompatibleVersion { .. })
+    ));
+}
+
+#[test]
+fn incompatible_version_errors_name_both_versions() {
+    let version = ProofVersion {
+        crate_version: "99.0.0".to_string(),
+        protocol_version: 7,
+        min_verifier_version: 7,
+    };
+    let message = version.check_compatibility().unwrap_err().to_string();
+    assert!(message.contains("99.0.0 (protocol 7, verifiable by protocol 7 and later)"));
+    assert!(message.contains(&ProofVersion::current().to_string()));
+}
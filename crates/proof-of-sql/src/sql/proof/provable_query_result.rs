@@ -9,6 +9,59 @@ use crate::base::{
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
 
+/// Limits on the size of a [`ProvableQueryResult`] that the verifier is willing to decode.
+///
+/// Because the result is deserialized from untrusted data, it can claim an arbitrarily large
+/// number of rows. The limits are checked before anything is allocated for the decoded result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvableQueryResultLimits {
+    max_rows: usize,
+    max_bytes: usize,
+}
+
+impl Default for ProvableQueryResultLimits {
+    fn default() -> Self {
+        Self {
+            max_rows: Self::DEFAULT_MAX_ROWS,
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl ProvableQueryResultLimits {
+    /// The default maximum number of rows in a query result.
+    pub const DEFAULT_MAX_ROWS: usize = 1 << 24;
+    /// The default maximum number of bytes of encoded data in a query result.
+    pub const DEFAULT_MAX_BYTES: usize = 1 << 30;
+
+    /// Create the default `ProvableQueryResultLimits`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of rows in a query result.
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Set the maximum number of bytes of encoded data in a query result.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Returns the maximum number of rows in a query result.
+    pub fn max_rows(&self) -> usize {
+        self.max_rows
+    }
+
+    /// Returns the maximum number of bytes of encoded data in a query result.
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+}
+
 /// An intermediate form of a query result that can be transformed
 /// to either the finalized query result form or a query error
 #[derive(Default, Clone, Serialize, Deserialize)]
@@ -27,6 +80,10 @@ impl ProvableQueryResult {
     pub fn indexes(&self) -> &Indexes {
         &self.indexes
     }
+    /// The encoded data of the columns, one column after the other.
+    pub(super) fn data(&self) -> &[u8] {
+        &self.data
+    }
     /// A mutable reference to a the indexes in the result. Because the struct is deserialized from untrusted data, it
     /// cannot maintain any invariant on its data members; hence, this function is available to allow for easy manipulation for testing.
     #[cfg(test)]
@@ -45,8 +102,9 @@ impl ProvableQueryResult {
     pub fn data_mut(&mut self) -> &mut Vec<u8> {
         &mut self.data
     }
-    /// This function is available to allow for easy creation for testing.
-    #[cfg(test)]
+    /// Form a result from its encoded parts, e.g. ones that were streamed with a
+    /// [`super::ResultStreamEncoder`]. This function is also available to allow for easy creation
+    /// for testing.
     pub fn new_from_raw_data(num_columns: u64, indexes: Indexes, data: Vec<u8>) -> Self {
         Self {
             num_columns,
@@ -55,6 +113,15 @@ impl ProvableQueryResult {
         }
     }
 
+    /// An empty result with the given number of columns
+    pub(crate) fn new_empty(num_columns: usize) -> Self {
+        ProvableQueryResult {
+            num_columns: num_columns as u64,
+            indexes: Indexes::default(),
+            data: Vec::new(),
+        }
+    }
+
     /// Form intermediate query result from index rows and result columns
     pub fn new<'a>(
         indexes: &'a Indexes,
@@ -76,6 +143,121 @@ impl ProvableQueryResult {
         }
     }
 
+    /// Check that the result does not claim more rows or data than the limits allow.
+    ///
+    /// This must be called before the result is evaluated or decoded.
+    pub fn check_limits(&self, limits: &ProvableQueryResultLimits) -> Result<(), QueryError> {
+        let num_rows = self.indexes.len();
+        if num_rows > limits.max_rows {
+            return Err(QueryError::TooManyRows {
+                num_rows,
+                max_rows: limits.max_rows,
+            });
+        }
+        let num_bytes = self.data.len();
+        if num_bytes > limits.max_bytes {
+            return Err(QueryError::TooManyBytes {
+                num_bytes,
+                max_bytes: limits.max_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    /// Split the result into pages of at most `page_size` rows, each of which is a result of its
+    /// own. An empty result is split into a single empty page.
+    pub(crate) fn split_rows<S: Scalar>(
+        &self,
+        column_result_fields: &[ColumnField],
+        page_size: usize,
+    ) -> Result<Vec<Self>, QueryError> {
+        assert_eq!(self.num_columns as usize, column_result_fields.len());
+        let num_rows = self.indexes.len();
+        let page_positions: Vec<_> = (0..num_rows.max(1))
+            .step_by(page_size)
+            .map(|start| start..(start + page_size).min(num_rows))
+            .collect();
+        let mut page_data = vec![Vec::new(); page_positions.len()];
+        let mut offset = 0;
+        for field in column_result_fields {
+            for (positions, data) in page_positions.iter().zip(&mut page_data) {
+                let num_read =
+                    encoded_len::<S>(field.data_type(), &self.data[offset..], positions.len())?;
+                data.extend_from_slice(&self.data[offset..offset + num_read]);
+                offset += num_read;
+            }
+        }
+        if offset != self.data.len() {
+            return Err(QueryError::MiscellaneousDecodingError);
+        }
+        Ok(page_positions
+            .into_iter()
+            .zip(page_data)
+            .map(|(positions, data)| ProvableQueryResult {
+                num_columns: self.num_columns,
+                indexes: self.indexes.slice(positions),
+                data,
+            })
+            .collect())
+    }
+
+    /// Join pages that were split with [`ProvableQueryResult::split_rows`] back into a single
+    /// result.
+    pub(crate) fn join_rows<S: Scalar>(
+        pages: &[Self],
+        column_result_fields: &[ColumnField],
+    ) -> Result<Self, QueryError> {
+        if pages
+            .iter()
+            .any(|page| page.num_columns() != column_result_fields.len())
+        {
+            return Err(QueryError::MiscellaneousDecodingError);
+        }
+        let mut data = Vec::with_capacity(pages.iter().map(|page| page.data.len()).sum());
+        let mut offsets = vec![0; pages.len()];
+        for field in column_result_fields {
+            for (page, offset) in pages.iter().zip(&mut offsets) {
+                let num_read =
+                    encoded_len::<S>(field.data_type(), &page.data[*offset..], page.indexes.len())?;
+                data.extend_from_slice(&page.data[*offset..*offset + num_read]);
+                *offset += num_read;
+            }
+        }
+        if pages
+            .iter()
+            .zip(offsets)
+            .any(|(page, offset)| offset != page.data.len())
+        {
+            return Err(QueryError::MiscellaneousDecodingError);
+        }
+        Ok(ProvableQueryResult {
+            num_columns: column_result_fields.len() as u64,
+            indexes: Indexes::concat(pages.iter().map(|page| &page.indexes)),
+            data,
+        })
+    }
+
+    /// Returns the offset of the encoded data of each column.
+    pub(super) fn column_offsets<S: Scalar>(
+        &self,
+        column_result_fields: &[ColumnField],
+    ) -> Result<Vec<usize>, QueryError> {
+        if self.num_columns() != column_result_fields.len() {
+            return Err(QueryError::MiscellaneousDecodingError);
+        }
+        let mut offsets = Vec::with_capacity(column_result_fields.len());
+        let mut offset = 0;
+        for field in column_result_fields {
+            offsets.push(offset);
+            offset +=
+                encoded_len::<S>(field.data_type(), &self.data[offset..], self.indexes.len())?;
+        }
+        if offset != self.data.len() {
+            return Err(QueryError::MiscellaneousDecodingError);
+        }
+        Ok(offsets)
+    }
+
     /// Given an evaluation vector, compute the evaluation of the intermediate result
     /// columns as spare multilinear extensions
     pub fn evaluate<S: Scalar>(
@@ -203,3 +385,28 @@ impl ProvableQueryResult {
         Ok(owned_table)
     }
 }
+
+/// Returns the number of bytes that the first `n` elements of a column of the given type take up
+/// in the encoded data.
+fn encoded_len<S: Scalar>(
+    column_type: ColumnType,
+    data: &[u8],
+    n: usize,
+) -> Result<usize, QueryError> {
+    (0..n).try_fold(0, |len, _| {
+        let (_, num_read): (S, usize) = match column_type {
+            ColumnType::Boolean => decode_and_convert::<bool, S>(&data[len..]),
+            ColumnType::SmallInt => decode_and_convert::<i16, S>(&data[len..]),
+            ColumnType::Int => decode_and_convert::<i32, S>(&data[len..]),
+            ColumnType::BigInt | ColumnType::TimestampTZ(_, _) => {
+                decode_and_convert::<i64, S>(&data[len..])
+            }
+            ColumnType::Int128 => decode_and_convert::<i128, S>(&data[len..]),
+            ColumnType::Decimal75(_, _) | ColumnType::Scalar => {
+                decode_and_convert::<S, S>(&data[len..])
+            }
+            ColumnType::VarChar => decode_and_convert::<&str, S>(&data[len..]),
+        }?;
+        Ok(len + num_read)
+    })
+}
@@ -1,4 +1,4 @@
-use super::{ProvableQueryResult, ProvableResultColumn, QueryError};
+use super::{ProvableQueryResult, ProvableQueryResultLimits, ProvableResultColumn, QueryError};
 use crate::{
     base::{
         database::{ColumnField, ColumnType},
@@ -470,3 +470,99 @@ fn we_cannot_convert_a_provable_result_with_invalid_string_data() {
         .to_owned_table::<Curve25519Scalar>(&column_fields)
         .is_err());
 }
+
+#[test]
+fn we_can_check_that_a_provable_result_is_within_the_limits() {
+    let values: [i64; 3] = [10, 11, 12];
+    let cols: [Box<dyn ProvableResultColumn>; 1] = [Box::new(values)];
+    let res = ProvableQueryResult::new(&Indexes::Sparse(vec![0, 2]), &cols);
+    assert!(res
+        .check_limits(&ProvableQueryResultLimits::default())
+        .is_ok());
+    let limits = ProvableQueryResultLimits::new()
+        .with_max_rows(2)
+        .with_max_bytes(2);
+    assert!(res.check_limits(&limits).is_ok());
+}
+
+#[test]
+fn we_cannot_decode_a_provable_result_that_claims_too_many_rows() {
+    let res = ProvableQueryResult::new_from_raw_data(1, Indexes::Dense(0..u64::MAX), vec![0]);
+    assert!(matches!(
+        res.check_limits(&ProvableQueryResultLimits::default()),
+        Err(QueryError::TooManyRows {
+            max_rows: ProvableQueryResultLimits::DEFAULT_MAX_ROWS,
+            ..
+        })
+    ));
+    let res = ProvableQueryResult::new_from_raw_data(1, Indexes::Sparse(vec![0, 1, 2]), vec![0]);
+    let limits = ProvableQueryResultLimits::new().with_max_rows(2);
+    assert!(matches!(
+        res.check_limits(&limits),
+        Err(QueryError::TooManyRows {
+            num_rows: 3,
+            max_rows: 2
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_decode_a_provable_result_that_has_too_much_data() {
+    let values: [i64; 3] = [10, 11, 12];
+    let cols: [Box<dyn ProvableResultColumn>; 1] = [Box::new(values)];
+    let res = ProvableQueryResult::new(&Indexes::Sparse(vec![0, 1, 2]), &cols);
+    let limits = ProvableQueryResultLimits::new().with_max_bytes(2);
+    assert!(matches!(
+        res.check_limits(&limits),
+        Err(QueryError::TooManyBytes {
+            num_bytes: 3,
+            max_bytes: 2
+        })
+    ));
+}
+
+#[test]
+fn we_can_split_a_provable_result_into_pages_and_join_them_back() {
+    let values: [i64; 5] = [10, -11, 12, 300, -1];
+    let strings = ["a", "bc", "", "def", "g"];
+    let cols: [Box<dyn ProvableResultColumn>; 2] = [Box::new(values), Box::new(strings)];
+    let res = ProvableQueryResult::new(&Indexes::Sparse(vec![0, 2, 3, 5, 6]), &cols);
+    let column_fields = vec![
+        ColumnField::new("a1".parse().unwrap(), ColumnType::BigInt),
+        ColumnField::new("a2".parse().unwrap(), ColumnType::VarChar),
+    ];
+    let pages = res
+        .split_rows::<Curve25519Scalar>(&column_fields, 2)
+        .unwrap();
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[1].indexes(), &Indexes::Sparse(vec![3, 5]));
+    let page = ProvableQueryResult::new(&Indexes::Sparse(vec![3, 5]), &cols);
+    assert_eq!(
+        pages[1]
+            .to_owned_table::<Curve25519Scalar>(&column_fields)
+            .unwrap(),
+        page.to_owned_table::<Curve25519Scalar>(&column_fields)
+            .unwrap()
+    );
+
+    let joined =
+        ProvableQueryResult::join_rows::<Curve25519Scalar>(&pages, &column_fields).unwrap();
+    assert_eq!(joined.indexes(), res.indexes());
+    assert_eq!(
+        postcard::to_allocvec(&joined).unwrap(),
+        postcard::to_allocvec(&res).unwrap()
+    );
+}
+
+#[test]
+fn we_cannot_join_pages_with_too_much_data() {
+    let values: [i64; 2] = [10, 11];
+    let cols: [Box<dyn ProvableResultColumn>; 1] = [Box::new(values)];
+    let mut res = ProvableQueryResult::new(&Indexes::Sparse(vec![0, 1]), &cols);
+    res.data_mut().push(3);
+    let column_fields = vec![ColumnField::new("a1".parse().unwrap(), ColumnType::BigInt)];
+    assert!(res
+        .split_rows::<Curve25519Scalar>(&column_fields, 1)
+        .is_err());
+    assert!(ProvableQueryResult::join_rows::<Curve25519Scalar>(&[res], &column_fields).is_err());
+}
@@ -0,0 +1,242 @@
+use crate::base::{
+    bit::BitDistribution,
+    database::{ColumnRef, ColumnType, TableRef},
+    scalar::Scalar,
+};
+use indexmap::IndexMap;
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+};
+
+/// The difference between a column and a literal, e.g. `a - 5`, as computed when the prover
+/// compares the column with the literal.
+///
+/// Intermediate MLEs derived from such a difference only depend on the column's data, so they can
+/// be reused by later queries as long as the table doesn't change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ColumnLiteralDifference {
+    /// The column being compared
+    pub column: ColumnRef,
+    /// The type of the literal, which determines how the two sides are scaled
+    pub literal_type: ColumnType,
+    /// The scalar value of the literal
+    pub literal: [u64; 4],
+    /// Whether the difference is `literal - column` rather than `column - literal`
+    pub literal_first: bool,
+}
+
+/// The transform that derives intermediate MLEs from a [`ColumnLiteralDifference`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum DerivedMleKind {
+    /// The witness that proves which entries of the difference are zero
+    EqualsZero,
+    /// The bit decomposition that proves the sign of each entry of the difference
+    Sign,
+}
+
+/// Identifies cached intermediate MLEs, up to the version of the table they were derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct DerivedMleKey {
+    pub difference: ColumnLiteralDifference,
+    pub kind: DerivedMleKind,
+}
+
+/// Intermediate MLEs that the prover can reuse across queries.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DerivedMles<S: Scalar> {
+    /// See [`DerivedMleKind::EqualsZero`]
+    EqualsZero {
+        lhs_pseudo_inv: Vec<S>,
+        selection_not: Vec<bool>,
+    },
+    /// See [`DerivedMleKind::Sign`]. The varying bits are stored one after another.
+    Sign {
+        dist: BitDistribution,
+        bits: Vec<bool>,
+    },
+}
+
+impl<S: Scalar> DerivedMles<S> {
+    fn size_in_bytes(&self) -> usize {
+        match self {
+            DerivedMles::EqualsZero {
+                lhs_pseudo_inv,
+                selection_not,
+            } => lhs_pseudo_inv.len() * mem::size_of::<S>() + selection_not.len(),
+            DerivedMles::Sign { bits, .. } => mem::size_of::<BitDistribution>() + bits.len(),
+        }
+    }
+}
+
+/// Statistics about the usage of a [`ProverCache`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProverCacheStats {
+    /// The number of lookups that found cached MLEs
+    pub hits: u64,
+    /// The number of lookups that had to compute the MLEs
+    pub misses: u64,
+    /// The number of cached entries
+    pub num_entries: usize,
+    /// The approximate memory used by the cached entries
+    pub size_in_bytes: usize,
+}
+
+struct ProverCacheState<S: Scalar> {
+    capacity_in_bytes: usize,
+    table_versions: IndexMap<TableRef, u64>,
+    /// Entries in least recently used order
+    entries: IndexMap<(u64, DerivedMleKey), Arc<DerivedMles<S>>>,
+    size_in_bytes: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl<S: Scalar> ProverCacheState<S> {
+    fn remove_entries_where(&mut self, mut f: impl FnMut(&(u64, DerivedMleKey)) -> bool) {
+        let mut size_in_bytes = self.size_in_bytes;
+        self.entries.retain(|key, mles| {
+            let remove = f(key);
+            if remove {
+                size_in_bytes -= mles.size_in_bytes();
+            }
+            !remove
+        });
+        self.size_in_bytes = size_in_bytes;
+    }
+
+    fn evict_to_capacity(&mut self) {
+        while self.size_in_bytes > self.capacity_in_bytes {
+            let Some((_, mles)) = self.entries.shift_remove_index(0) else {
+                break;
+            };
+            self.size_in_bytes -= mles.size_in_bytes();
+        }
+    }
+}
+
+/// A cache of intermediate MLEs that the prover derives from table columns, shared across queries.
+///
+/// Dashboard-style workloads prove the same comparisons, e.g. `WHERE region = 'eu'` or
+/// `WHERE amount >= 100`, against tables that rarely change. The witnesses for such comparisons
+/// (equality indicators and bit decompositions) are expensive to compute, but only depend on the
+/// column and the literal, so the prover can reuse them as long as the table has not changed.
+///
+/// Entries are keyed by the table version, the column, and the transform applied to it. Only
+/// tables whose version has been registered with [`ProverCache::set_table_version`] are cached;
+/// the caller is responsible for bumping the version whenever the table's data changes. When the
+/// cache exceeds its capacity, the least recently used entries are evicted.
+///
+/// Caching does not change the proof: a proof created with the cache is identical to one created
+/// without it.
+pub struct ProverCache<S: Scalar> {
+    state: Mutex<ProverCacheState<S>>,
+}
+
+impl<S: Scalar> ProverCache<S> {
+    /// Create an empty cache that holds at most approximately `capacity_in_bytes` bytes of MLEs.
+    pub fn new(capacity_in_bytes: usize) -> Self {
+        Self {
+            state: Mutex::new(ProverCacheState {
+                capacity_in_bytes,
+                table_versions: IndexMap::new(),
+                entries: IndexMap::new(),
+                size_in_bytes: 0,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, ProverCacheState<S>> {
+        self.state
+            .lock()
+            .expect("the prover cache should not be poisoned")
+    }
+
+    /// Enable caching for a table at the given version.
+    ///
+    /// Entries that were derived from other versions of the table are evicted.
+    pub fn set_table_version(&self, table_ref: TableRef, version: u64) {
+        let mut state = self.state();
+        if state.table_versions.insert(table_ref, version) != Some(version) {
+            state.remove_entries_where(|(entry_version, key)| {
+                key.difference.column.table_ref() == table_ref && *entry_version != version
+            });
+        }
+    }
+
+    /// Returns the version of a table that is currently cached, if any.
+    pub fn table_version(&self, table_ref: TableRef) -> Option<u64> {
+        self.state().table_versions.get(&table_ref).copied()
+    }
+
+    /// Disable caching for a table and evict all of its entries.
+    pub fn invalidate_table(&self, table_ref: TableRef) {
+        let mut state = self.state();
+        state.table_versions.shift_remove(&table_ref);
+        state.remove_entries_where(|(_, key)| key.difference.column.table_ref() == table_ref);
+    }
+
+    /// Evict all entries. Registered table versions are kept.
+    pub fn clear(&self) {
+        let mut state = self.state();
+        state.entries.clear();
+        state.size_in_bytes = 0;
+    }
+
+    /// Returns statistics about the usage of the cache.
+    pub fn stats(&self) -> ProverCacheStats {
+        let state = self.state();
+        ProverCacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            num_entries: state.entries.len(),
+            size_in_bytes: state.size_in_bytes,
+        }
+    }
+
+    /// Look up the MLEs for `key` at the current version of its table.
+    ///
+    /// Returns `None` without counting a miss if the table is not cached.
+    pub(crate) fn get(&self, key: &DerivedMleKey) -> Option<Arc<DerivedMles<S>>> {
+        let mut state = self.state();
+        let version = *state
+            .table_versions
+            .get(&key.difference.column.table_ref())?;
+        match state.entries.shift_remove(&(version, *key)) {
+            Some(mles) => {
+                // Reinsert the entry so that it becomes the most recently used one
+                state.entries.insert((version, *key), mles.clone());
+                state.hits += 1;
+                Some(mles)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Cache the MLEs for `key` at the current version of its table.
+    ///
+    /// `mles` is only called if the table is cached. Nothing is cached if the MLEs are larger
+    /// than the capacity.
+    pub(crate) fn insert_with(&self, key: DerivedMleKey, mles: impl FnOnce() -> DerivedMles<S>) {
+        let mut state = self.state();
+        let Some(&version) = state.table_versions.get(&key.difference.column.table_ref()) else {
+            return;
+        };
+        let mles = mles();
+        let size_in_bytes = mles.size_in_bytes();
+        if size_in_bytes > state.capacity_in_bytes {
+            return;
+        }
+        if let Some(old) = state.entries.shift_remove(&(version, key)) {
+            state.size_in_bytes -= old.size_in_bytes();
+        }
+        state.entries.insert((version, key), Arc::new(mles));
+        state.size_in_bytes += size_in_bytes;
+        state.evict_to_capacity();
+    }
+}
@@ -0,0 +1,186 @@
+use super::{EvaluationContext, ProverCache, ProverCacheStats, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn sample_accessor(t: TableRef) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 2]),
+        varchar("b", ["x", "y", "z", "y"]),
+    ]);
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ())
+}
+
+fn prove_and_verify(
+    ast: &ProofPlan<RistrettoPoint>,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    prover_cache: &ProverCache<Curve25519Scalar>,
+) -> (Vec<u8>, OwnedTable<Curve25519Scalar>) {
+    let verifiable_res = VerifiableQueryResult::<InnerProductProof>::new_with_prover_cache(
+        ast,
+        accessor,
+        &(),
+        EvaluationContext::default(),
+        prover_cache,
+    );
+    let res = verifiable_res.verify(ast, accessor, &()).unwrap().table;
+    (postcard::to_allocvec(&verifiable_res).unwrap(), res)
+}
+
+#[test]
+fn we_can_reuse_equality_mles_across_queries_without_changing_the_proof() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        equal(column(t, "b", &accessor), const_varchar("y")),
+    );
+    let uncached = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    let uncached = postcard::to_allocvec(&uncached).unwrap();
+    let expected_res = owned_table([bigint("a", [2, 2]), varchar("b", ["y", "y"])]);
+
+    let prover_cache = ProverCache::new(1 << 20);
+    prover_cache.set_table_version(t, 1);
+    let (proof, res) = prove_and_verify(&ast, &accessor, &prover_cache);
+    assert_eq!(res, expected_res);
+    assert_eq!(proof, uncached);
+    let stats = prover_cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.num_entries), (0, 1, 1));
+
+    let (proof, res) = prove_and_verify(&ast, &accessor, &prover_cache);
+    assert_eq!(res, expected_res);
+    assert_eq!(proof, uncached);
+    let stats = prover_cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.num_entries), (1, 1, 1));
+}
+
+#[test]
+fn we_can_reuse_inequality_mles_for_equivalent_comparisons() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let prover_cache = ProverCache::new(1 << 20);
+    prover_cache.set_table_version(t, 1);
+    let expected_res = owned_table([bigint("a", [1, 2, 2])]);
+
+    // a <= 2 and 2 >= a both prove the sign of a - 2
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        lte(column(t, "a", &accessor), const_bigint(2)),
+    );
+    assert_eq!(
+        prove_and_verify(&ast, &accessor, &prover_cache).1,
+        expected_res
+    );
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        gte(const_bigint(2), column(t, "a", &accessor)),
+    );
+    assert_eq!(
+        prove_and_verify(&ast, &accessor, &prover_cache).1,
+        expected_res
+    );
+    let stats = prover_cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.num_entries), (2, 2, 2));
+
+    // a >= 2 proves the sign of 2 - a, which must not be confused with a - 2
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(2)),
+    );
+    assert_eq!(
+        prove_and_verify(&ast, &accessor, &prover_cache).1,
+        owned_table([bigint("a", [2, 3, 2])])
+    );
+    let stats = prover_cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.num_entries), (2, 4, 4));
+}
+
+#[test]
+fn we_do_not_cache_mles_of_tables_without_a_version() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let prover_cache = ProverCache::new(1 << 20);
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(column(t, "a", &accessor), const_bigint(2)),
+    );
+    prove_and_verify(&ast, &accessor, &prover_cache);
+    prove_and_verify(&ast, &accessor, &prover_cache);
+    assert_eq!(prover_cache.stats(), ProverCacheStats::default());
+    assert_eq!(prover_cache.table_version(t), None);
+}
+
+#[test]
+fn we_evict_mles_of_other_table_versions() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let prover_cache = ProverCache::new(1 << 20);
+    let ast = dense_filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(column(t, "a", &accessor), const_bigint(2)),
+    );
+
+    prover_cache.set_table_version(t, 1);
+    prove_and_verify(&ast, &accessor, &prover_cache);
+    assert_eq!(prover_cache.stats().num_entries, 1);
+
+    // Registering the same version again keeps the entries
+    prover_cache.set_table_version(t, 1);
+    assert_eq!(prover_cache.stats().num_entries, 1);
+
+    prover_cache.set_table_version(t, 2);
+    assert_eq!(prover_cache.table_version(t), Some(2));
+    assert_eq!(prover_cache.stats().num_entries, 0);
+    assert_eq!(prover_cache.stats().size_in_bytes, 0);
+    prove_and_verify(&ast, &accessor, &prover_cache);
+    assert_eq!(prover_cache.stats().misses, 2);
+
+    prover_cache.invalidate_table(t);
+    assert_eq!(prover_cache.table_version(t), None);
+    assert_eq!(prover_cache.stats().num_entries, 0);
+}
+
+#[test]
+fn we_evict_the_least_recently_used_mles_when_the_cache_is_full() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let equals = |value| {
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(value)),
+        )
+    };
+
+    // Each entry holds 4 pseudo-inverses and 4 booleans, so the cache fits only one of them
+    let entry_size = 4 * std::mem::size_of::<Curve25519Scalar>() + 4;
+    let prover_cache = ProverCache::new(entry_size);
+    prover_cache.set_table_version(t, 1);
+
+    prove_and_verify(&equals(1), &accessor, &prover_cache);
+    prove_and_verify(&equals(2), &accessor, &prover_cache);
+    let stats = prover_cache.stats();
+    assert_eq!((stats.num_entries, stats.size_in_bytes), (1, entry_size));
+
+    // a = 1 was evicted, a = 2 was not
+    prove_and_verify(&equals(2), &accessor, &prover_cache);
+    prove_and_verify(&equals(1), &accessor, &prover_cache);
+    let stats = prover_cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 3));
+
+    prover_cache.clear();
+    assert_eq!(prover_cache.stats().num_entries, 0);
+    assert_eq!(prover_cache.table_version(t), Some(1));
+}
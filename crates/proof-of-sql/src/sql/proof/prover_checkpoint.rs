@@ -0,0 +1,64 @@
+use super::EvaluationContext;
+use crate::base::{commitment::Commitment, proof::ProverError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The state of a prover after it has committed to the intermediate MLEs of a proof, but before it
+/// has run sumcheck.
+///
+/// Committing to the intermediate MLEs is the most expensive part of proving a query, so a prover
+/// that may be interrupted, e.g. because it runs on a spot instance, can persist a checkpoint and
+/// finish the proof later with `VerifiableQueryResult::resume_from_checkpoint`. The checkpoint
+/// records digests of the committed MLEs and a fingerprint of the transcript, so the prover
+/// refuses to resume from a checkpoint of a different query, database snapshot or context, and the
+/// resumed proof is the same as one created without a checkpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverCheckpoint<C: Commitment> {
+    /// The values the prover bound to the query's context variables.
+    pub(super) context: EvaluationContext,
+    /// The commitments to the intermediate MLEs.
+    pub(super) commitments: Vec<C>,
+    /// The digests of the intermediate MLEs, in the order of their commitments.
+    pub(super) mle_digests: Vec<[u8; 32]>,
+    /// A challenge drawn from the transcript once it has absorbed the commitments.
+    pub(super) transcript_fingerprint: [u8; 32],
+}
+
+impl<C: Commitment> ProverCheckpoint<C> {
+    /// A checkpoint of a query over an empty table, whose result has no proof to resume.
+    pub(super) fn new_empty(context: EvaluationContext) -> Self {
+        Self {
+            context,
+            commitments: Vec::new(),
+            mle_digests: Vec::new(),
+            transcript_fingerprint: [0; 32],
+        }
+    }
+
+    /// Returns the values the prover bound to the query's context variables.
+    pub fn context(&self) -> &EvaluationContext {
+        &self.context
+    }
+}
+
+/// Errors that can occur when resuming a proof from a [`ProverCheckpoint`].
+#[derive(Error, Debug)]
+pub enum ProverCheckpointError {
+    /// The prover gave up before finishing the proof.
+    #[error(transparent)]
+    ProverError(#[from] ProverError),
+    /// The checkpoint has a different number of intermediate MLEs than the query.
+    #[error("the checkpoint commits to {checkpoint} intermediate MLEs rather than {expected}")]
+    WitnessCountMismatch {
+        /// The number of commitments in the checkpoint
+        checkpoint: usize,
+        /// The number of intermediate MLEs of the query
+        expected: usize,
+    },
+    /// An intermediate MLE differs from the one that the checkpoint commits to.
+    #[error("intermediate MLE {0} differs from the one that the checkpoint commits to")]
+    WitnessMismatch(usize),
+    /// The transcript differs from the one that the checkpoint was taken from.
+    #[error("the transcript differs from the one that the checkpoint was taken from")]
+    TranscriptMismatch,
+}
@@ -0,0 +1,163 @@
+use super::{EvaluationContext, ProverCheckpoint, ProverCheckpointError, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        proof::ProverDeadline,
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn sample_accessor(t: TableRef, a: [i64; 4]) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", a), varchar("b", ["x", "y", "z", "y"])]);
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ())
+}
+
+fn sample_query(
+    t: TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> ProofPlan<RistrettoPoint> {
+    dense_filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        equal(column(t, "b", accessor), const_varchar("y")),
+    )
+}
+
+fn take_checkpoint(
+    ast: &ProofPlan<RistrettoPoint>,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    context: EvaluationContext,
+) -> ProverCheckpoint<RistrettoPoint> {
+    VerifiableQueryResult::<InnerProductProof>::new_checkpoint(
+        ast,
+        accessor,
+        &(),
+        context,
+        &ProverDeadline::default(),
+    )
+    .unwrap()
+}
+
+fn resume(
+    ast: &ProofPlan<RistrettoPoint>,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    checkpoint: &ProverCheckpoint<RistrettoPoint>,
+) -> Result<VerifiableQueryResult<InnerProductProof>, ProverCheckpointError> {
+    VerifiableQueryResult::resume_from_checkpoint(
+        ast,
+        accessor,
+        &(),
+        checkpoint,
+        &ProverDeadline::default(),
+    )
+}
+
+#[test]
+fn we_can_resume_a_persisted_checkpoint_with_the_same_proof_as_an_uninterrupted_prover() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t, [1, 2, 3, 2]);
+    let ast = sample_query(t, &accessor);
+    let context = EvaluationContext::new().with_block_height(7);
+    let bytes = postcard::to_allocvec(&take_checkpoint(&ast, &accessor, context)).unwrap();
+    let checkpoint: ProverCheckpoint<RistrettoPoint> = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(checkpoint.context(), &context);
+
+    let verifiable_res = resume(&ast, &accessor, &checkpoint).unwrap();
+    let expected =
+        VerifiableQueryResult::<InnerProductProof>::new_with_context(&ast, &accessor, &(), context);
+    assert_eq!(
+        postcard::to_allocvec(&verifiable_res).unwrap(),
+        postcard::to_allocvec(&expected).unwrap()
+    );
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(
+        res,
+        owned_table([bigint("a", [2, 2]), varchar("b", ["y", "y"])])
+    );
+}
+
+#[test]
+fn we_cannot_resume_a_checkpoint_of_other_data() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t, [1, 2, 3, 2]);
+    let checkpoint = take_checkpoint(&sample_query(t, &accessor), &accessor, Default::default());
+    let other_accessor = sample_accessor(t, [1, 2, 3, 4]);
+    let other_ast = sample_query(t, &other_accessor);
+    assert!(resume(&other_ast, &other_accessor, &checkpoint).is_err());
+}
+
+#[test]
+fn we_cannot_resume_a_checkpoint_whose_transcript_differs() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t, [1, 2, 3, 2]);
+    let ast = sample_query(t, &accessor);
+    let mut checkpoint = take_checkpoint(&ast, &accessor, EvaluationContext::new());
+    checkpoint.transcript_fingerprint[0] ^= 1;
+    assert!(matches!(
+        resume(&ast, &accessor, &checkpoint),
+        Err(ProverCheckpointError::TranscriptMismatch)
+    ));
+
+    // The context is absorbed into the transcript
+    let mut checkpoint = take_checkpoint(&ast, &accessor, EvaluationContext::new());
+    checkpoint.context = EvaluationContext::new().with_current_time(1);
+    assert!(matches!(
+        resume(&ast, &accessor, &checkpoint),
+        Err(ProverCheckpointError::TranscriptMismatch)
+    ));
+}
+
+#[test]
+fn we_cannot_resume_a_checkpoint_with_tampered_commitments() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t, [1, 2, 3, 2]);
+    let ast = sample_query(t, &accessor);
+    let mut checkpoint = take_checkpoint(&ast, &accessor, EvaluationContext::new());
+    assert!(!checkpoint.commitments.is_empty());
+    checkpoint.commitments[0] = RistrettoPoint::default();
+    assert!(matches!(
+        resume(&ast, &accessor, &checkpoint),
+        Err(ProverCheckpointError::TranscriptMismatch)
+    ));
+
+    let mut checkpoint = take_checkpoint(&ast, &accessor, EvaluationContext::new());
+    checkpoint.mle_digests[0] = [0; 32];
+    assert!(matches!(
+        resume(&ast, &accessor, &checkpoint),
+        Err(ProverCheckpointError::WitnessMismatch(0))
+    ));
+
+    let mut checkpoint = take_checkpoint(&ast, &accessor, EvaluationContext::new());
+    let num_commitments = checkpoint.commitments.len();
+    checkpoint.commitments.pop();
+    assert!(matches!(
+        resume(&ast, &accessor, &checkpoint),
+        Err(ProverCheckpointError::WitnessCountMismatch { checkpoint, expected })
+            if checkpoint + 1 == num_commitments && expected == num_commitments
+    ));
+}
+
+#[test]
+fn we_can_resume_a_checkpoint_of_a_query_over_an_empty_table() {
+    let t = "sxt.t".parse().unwrap();
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]);
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = sample_query(t, &accessor);
+    let checkpoint = take_checkpoint(&ast, &accessor, EvaluationContext::new());
+    let bytes = postcard::to_allocvec(&checkpoint).unwrap();
+    let checkpoint: ProverCheckpoint<RistrettoPoint> = postcard::from_bytes(&bytes).unwrap();
+
+    let verifiable_res = resume(&ast, &accessor, &checkpoint).unwrap();
+    assert!(verifiable_res.provable_result.is_none());
+    assert!(verifiable_res.proof.is_none());
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(
+        res,
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])])
+    );
+}
@@ -0,0 +1,116 @@
+use super::ProofExpr;
+use crate::base::{commitment::Commitment, database::MetadataAccessor, math::log2_up};
+use serde::{Deserialize, Serialize};
+use std::cmp;
+use thiserror::Error;
+
+/// A rough, row-count-scaled estimate of the resources needed to prove a query.
+///
+/// This is computed from metadata alone, before any data is read, so that services
+/// can refuse oversized queries before starting to prove them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverCostEstimate {
+    /// The number of rows in the input table span.
+    pub table_length: usize,
+    /// The number of variables of the sumcheck polynomial.
+    pub num_sumcheck_variables: usize,
+    /// The approximate number of scalars the prover has to commit to with multi-scalar multiplications.
+    pub msm_size: usize,
+    /// The approximate number of bytes needed to hold the MLEs of the proof in memory.
+    pub memory_bytes: usize,
+}
+
+impl ProverCostEstimate {
+    /// Estimate the cost of proving `expr` against the table spans described by `accessor`.
+    ///
+    /// The number of MLEs in a proof grows with the number of columns the query touches, so
+    /// the estimate scales the table length by the number of referenced and result columns.
+    pub fn new<C: Commitment>(expr: &impl ProofExpr<C>, accessor: &dyn MetadataAccessor) -> Self {
+        let table_length = expr.get_length(accessor);
+        if table_length == 0 {
+            return Self::default();
+        }
+        let num_columns =
+            expr.get_column_references().len() + expr.get_column_result_fields().len();
+        let msm_size = table_length.saturating_mul(num_columns);
+        Self {
+            table_length,
+            num_sumcheck_variables: cmp::max(log2_up(table_length), 1),
+            msm_size,
+            memory_bytes: msm_size.saturating_mul(core::mem::size_of::<C::Scalar>()),
+        }
+    }
+}
+
+/// Errors from checking a [`ProverCostEstimate`] against a [`ProverAdmissionPolicy`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AdmissionError {
+    /// The input table span is too long.
+    #[error("Table length {actual} exceeds the admission limit of {limit}")]
+    TableLengthExceeded {
+        /// The estimated value
+        actual: usize,
+        /// The configured limit
+        limit: usize,
+    },
+    /// The estimated MSM size is too large.
+    #[error("Estimated MSM size {actual} exceeds the admission limit of {limit}")]
+    MsmSizeExceeded {
+        /// The estimated value
+        actual: usize,
+        /// The configured limit
+        limit: usize,
+    },
+    /// The estimated memory usage is too large.
+    #[error("Estimated memory usage of {actual} bytes exceeds the admission limit of {limit}")]
+    MemoryExceeded {
+        /// The estimated value
+        actual: usize,
+        /// The configured limit
+        limit: usize,
+    },
+}
+
+/// Limits on the estimated cost of a query that a prover is willing to accept.
+///
+/// Each limit is optional; a `None` limit is never exceeded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProverAdmissionPolicy {
+    /// The maximum number of rows in the input table span.
+    pub max_table_length: Option<usize>,
+    /// The maximum estimated MSM size.
+    pub max_msm_size: Option<usize>,
+    /// The maximum estimated memory usage in bytes.
+    pub max_memory_bytes: Option<usize>,
+}
+
+impl ProverAdmissionPolicy {
+    /// Check whether a query with the given estimated cost should be admitted.
+    pub fn check(&self, estimate: &ProverCostEstimate) -> Result<(), AdmissionError> {
+        if let Some(limit) = self.max_table_length {
+            if estimate.table_length > limit {
+                return Err(AdmissionError::TableLengthExceeded {
+                    actual: estimate.table_length,
+                    limit,
+                });
+            }
+        }
+        if let Some(limit) = self.max_msm_size {
+            if estimate.msm_size > limit {
+                return Err(AdmissionError::MsmSizeExceeded {
+                    actual: estimate.msm_size,
+                    limit,
+                });
+            }
+        }
+        if let Some(limit) = self.max_memory_bytes {
+            if estimate.memory_bytes > limit {
+                return Err(AdmissionError::MemoryExceeded {
+                    actual: estimate.memory_bytes,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+}
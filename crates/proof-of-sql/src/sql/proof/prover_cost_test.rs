@@ -0,0 +1,104 @@
+use super::{AdmissionError, ProverAdmissionPolicy, ProverCostEstimate};
+use crate::{
+    base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    proof_primitive::dory::{DoryCommitment, DoryEvaluationProof},
+    sql::ast::test_utility::*,
+};
+
+#[test]
+fn we_can_estimate_the_cost_of_a_filter_query() {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty();
+    accessor.add_table(
+        t,
+        owned_table([bigint("a", [1, 2, 3, 4, 5]), bigint("b", [1, 0, 1, 0, 1])]),
+        0,
+    );
+    let expr = dense_filter::<DoryCommitment>(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(column(t, "b", &accessor), const_bigint(1)),
+    );
+    let estimate = ProverCostEstimate::new(&expr, &accessor);
+    assert_eq!(estimate.table_length, 5);
+    assert_eq!(estimate.num_sumcheck_variables, 3);
+    // Two referenced columns and one result column.
+    assert_eq!(estimate.msm_size, 15);
+    assert_eq!(estimate.memory_bytes, 15 * 32);
+}
+
+#[test]
+fn the_cost_of_a_query_on_an_empty_table_is_zero() {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty();
+    accessor.add_table(t, owned_table([bigint("a", [0; 0])]), 0);
+    let expr = dense_filter::<DoryCommitment>(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        const_bool(true),
+    );
+    assert_eq!(
+        ProverCostEstimate::new(&expr, &accessor),
+        ProverCostEstimate::default()
+    );
+}
+
+#[test]
+fn the_default_admission_policy_admits_everything() {
+    let estimate = ProverCostEstimate {
+        table_length: usize::MAX,
+        num_sumcheck_variables: 64,
+        msm_size: usize::MAX,
+        memory_bytes: usize::MAX,
+    };
+    assert_eq!(ProverAdmissionPolicy::default().check(&estimate), Ok(()));
+}
+
+#[test]
+fn the_admission_policy_rejects_queries_exceeding_any_limit() {
+    let estimate = ProverCostEstimate {
+        table_length: 1000,
+        num_sumcheck_variables: 10,
+        msm_size: 3000,
+        memory_bytes: 96000,
+    };
+    let policy = ProverAdmissionPolicy {
+        max_table_length: Some(1000),
+        max_msm_size: Some(3000),
+        max_memory_bytes: Some(96000),
+    };
+    assert_eq!(policy.check(&estimate), Ok(()));
+    assert_eq!(
+        ProverAdmissionPolicy {
+            max_table_length: Some(999),
+            ..policy
+        }
+        .check(&estimate),
+        Err(AdmissionError::TableLengthExceeded {
+            actual: 1000,
+            limit: 999
+        })
+    );
+    assert_eq!(
+        ProverAdmissionPolicy {
+            max_msm_size: Some(2999),
+            ..policy
+        }
+        .check(&estimate),
+        Err(AdmissionError::MsmSizeExceeded {
+            actual: 3000,
+            limit: 2999
+        })
+    );
+    assert_eq!(
+        ProverAdmissionPolicy {
+            max_memory_bytes: Some(95999),
+            ..policy
+        }
+        .check(&estimate),
+        Err(AdmissionError::MemoryExceeded {
+            actual: 96000,
+            limit: 95999
+        })
+    );
+}
@@ -1,15 +1,22 @@
+// Without the `tracing` feature, the spans of phases are no-ops that are still dropped explicitly.
+#![cfg_attr(not(feature = "tracing"), allow(clippy::drop_non_drop))]
 use super::{
-    CountBuilder, ProofBuilder, ProofCounts, ProofExpr, ProvableQueryResult, QueryResult,
-    SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilder,
+    phase_span::{phase_span, PhaseFields},
+    record_check, ConstraintSystem, CountBuilder, EvaluationContext, Indexes, ProofBuilder,
+    ProofCounts, ProofExpr, ProvableQueryResult, ProvableQueryResultLimits, ProverCache,
+    ProverCheckpoint, ProverCheckpointError, QueryError, QueryResult, ResultStreamEncoder,
+    SharedCommitments, SumcheckMleEvaluations, SumcheckRandomScalars, VerificationBuilder,
+    VerificationCheck, VerificationReport, VerifiedRows,
 };
 use crate::{
     base::{
         bit::BitDistribution,
         commitment::{Commitment, CommitmentEvaluationProof},
-        database::{CommitmentAccessor, DataAccessor},
+        database::{ColumnField, CommitmentAccessor, DataAccessor, OwnedTable},
         math::log2_up,
         polynomial::{compute_evaluation_vector, CompositePolynomialInfo},
-        proof::{MessageLabel, ProofError, TranscriptProtocol},
+        proof::{MessageLabel, ProofError, ProverDeadline, ProverError, TranscriptProtocol},
+        scalar::Scalar,
     },
     proof_primitive::sumcheck::SumcheckProof,
     sql::proof::{QueryData, ResultBuilder},
@@ -18,7 +25,9 @@ use bumpalo::Bump;
 use merlin::Transcript;
 use num_traits::Zero;
 use serde::{Deserialize, Serialize};
-use std::cmp;
+#[cfg(feature = "verification-report")]
+use std::time::Instant;
+use std::{cmp, slice};
 
 /// The proof for a query.
 ///
@@ -39,52 +48,266 @@ pub struct QueryProof<CP: CommitmentEvaluationProof> {
     pub evaluation_proof: CP,
 }
 
+// Only `VerifiableQueryResult` uses `QueryProof` unless it is exported with the `test` feature.
+#[cfg_attr(not(feature = "test"), allow(dead_code))]
 impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
     /// Create a new `QueryProof`.
-    #[tracing::instrument(name = "QueryProof::new", level = "debug", skip_all)]
     pub fn new(
         expr: &(impl ProofExpr<CP::Commitment> + Serialize),
         accessor: &impl DataAccessor<CP::Scalar>,
         setup: &CP::ProverPublicSetup<'_>,
     ) -> (Self, ProvableQueryResult) {
-        let table_length = expr.get_length(accessor);
-        let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
-        let generator_offset = expr.get_offset(accessor);
-        assert!(num_sumcheck_variables > 0);
+        Self::new_with_context(expr, accessor, setup, &EvaluationContext::default())
+    }
 
-        let alloc = Bump::new();
-        let mut result_builder = ResultBuilder::new(table_length);
-        expr.result_evaluate(&mut result_builder, &alloc, accessor);
-        let provable_result = result_builder.make_provable_query_result();
+    /// Create a new `QueryProof` whose transcript absorbs the given evaluation context.
+    pub fn new_with_context(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: &EvaluationContext,
+    ) -> (Self, ProvableQueryResult) {
+        Self::new_impl(
+            expr,
+            accessor,
+            setup,
+            context,
+            None,
+            None,
+            &ProverDeadline::default(),
+        )
+        .expect("the default deadline never expires")
+    }
 
-        // construct a transcript for the proof
-        let mut transcript: Transcript =
-            make_transcript(expr, &provable_result, table_length, generator_offset);
+    /// Create a new `QueryProof`, giving up with a [`ProverError`] once the deadline expires.
+    ///
+    /// The proof is the same as the one created by [`QueryProof::new_with_context`].
+    pub fn new_with_deadline(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: &EvaluationContext,
+        deadline: &ProverDeadline,
+    ) -> Result<(Self, ProvableQueryResult), ProverError> {
+        Self::new_impl(expr, accessor, setup, context, None, None, deadline)
+    }
 
-        // These are the challenges that will be consumed by the proof
-        // Specifically, these are the challenges that the verifier sends to
-        // the prover after the prover sends the result, but before the prover
-        // send commitments to the intermediate witness columns.
-        // Note: the last challenge in the vec is the first one that is consumed.
-        let mut post_result_challenges =
-            vec![Zero::zero(); result_builder.num_post_result_challenges()];
-        transcript.challenge_scalars(
-            &mut post_result_challenges,
-            MessageLabel::PostResultChallenges,
-        );
+    /// Create a new `QueryProof`, reusing intermediate MLEs from earlier queries.
+    ///
+    /// The proof is the same as the one created by [`QueryProof::new_with_context`].
+    pub fn new_with_prover_cache(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: &EvaluationContext,
+        prover_cache: &ProverCache<CP::Scalar>,
+    ) -> (Self, ProvableQueryResult) {
+        Self::new_impl(
+            expr,
+            accessor,
+            setup,
+            context,
+            Some(prover_cache),
+            None,
+            &ProverDeadline::default(),
+        )
+        .expect("the default deadline never expires")
+    }
 
-        let mut builder =
-            ProofBuilder::new(table_length, num_sumcheck_variables, post_result_challenges);
-        expr.prover_evaluate(&mut builder, &alloc, accessor);
+    /// Run the prover up to and including its commitments to the intermediate MLEs, and record
+    /// its state so that the proof can be finished later by [`QueryProof::resume_impl`].
+    pub(super) fn checkpoint_impl(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+        deadline: &ProverDeadline,
+    ) -> Result<ProverCheckpoint<CP::Commitment>, ProverError> {
+        let alloc = Bump::new();
+        let mut state = evaluate_witness::<CP::Commitment>(
+            slice::from_ref(expr),
+            accessor,
+            &alloc,
+            &context,
+            None,
+            None,
+            deadline,
+        )?;
+        let generator_offset = state.generator_offset;
+        let commitments = state.commit_intermediate_mles(|builder| {
+            builder.commit_intermediate_mles_with_deadline(generator_offset, setup, deadline)
+        })?;
+        state.absorb_commitments(&commitments);
+        Ok(ProverCheckpoint {
+            transcript_fingerprint: state.transcript_fingerprint(),
+            mle_digests: state.builder.commitment_digests(),
+            commitments,
+            context,
+        })
+    }
 
-        let num_sumcheck_variables = builder.num_sumcheck_variables();
-        let table_length = builder.table_length();
+    /// Finish a proof from a checkpoint, reusing its commitments to the intermediate MLEs.
+    ///
+    /// The intermediate MLEs are evaluated again, which is cheap compared to committing to them,
+    /// and checked against the checkpoint along with the state of the transcript. The proof is
+    /// the same as the one created without a checkpoint.
+    pub(super) fn resume_impl(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        checkpoint: &ProverCheckpoint<CP::Commitment>,
+        deadline: &ProverDeadline,
+    ) -> Result<(Self, ProvableQueryResult), ProverCheckpointError> {
+        let alloc = Bump::new();
+        let context = &checkpoint.context;
+        let mut state = evaluate_witness::<CP::Commitment>(
+            slice::from_ref(expr),
+            accessor,
+            &alloc,
+            context,
+            None,
+            None,
+            deadline,
+        )?;
+        let mle_digests = state.builder.commitment_digests();
+        if mle_digests.len() != checkpoint.mle_digests.len()
+            || mle_digests.len() != checkpoint.commitments.len()
+        {
+            return Err(ProverCheckpointError::WitnessCountMismatch {
+                checkpoint: checkpoint.commitments.len(),
+                expected: mle_digests.len(),
+            });
+        }
+        if let Some(index) = mle_digests
+            .iter()
+            .zip(&checkpoint.mle_digests)
+            .position(|(digest, checkpoint_digest)| digest != checkpoint_digest)
+        {
+            return Err(ProverCheckpointError::WitnessMismatch(index));
+        }
+        state.absorb_commitments(&checkpoint.commitments);
+        if state.transcript_fingerprint() != checkpoint.transcript_fingerprint {
+            return Err(ProverCheckpointError::TranscriptMismatch);
+        }
+        let commitments = checkpoint.commitments.clone();
+        let proof = Self::prove_committed_witness(state, commitments, setup, deadline)?;
+        Ok(single_result(proof))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "QueryProof::new", level = "debug", skip_all)
+    )]
+    pub(super) fn new_impl(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: &EvaluationContext,
+        prover_cache: Option<&ProverCache<CP::Scalar>>,
+        shared_commitments: Option<&SharedCommitments<CP::Commitment>>,
+        deadline: &ProverDeadline,
+    ) -> Result<(Self, ProvableQueryResult), ProverError> {
+        let alloc = Bump::new();
+        let mut state = evaluate_witness::<CP::Commitment>(
+            slice::from_ref(expr),
+            accessor,
+            &alloc,
+            context,
+            prover_cache,
+            None,
+            deadline,
+        )?;
+        let generator_offset = state.generator_offset;
 
         // commit to any intermediate MLEs
-        let commitments = builder.commit_intermediate_mles(generator_offset, setup);
+        let commitments = state.commit_intermediate_mles(|builder| match shared_commitments {
+            Some(shared_commitments) => builder.commit_intermediate_mles_shared(
+                generator_offset,
+                setup,
+                deadline,
+                shared_commitments,
+            ),
+            None => {
+                builder.commit_intermediate_mles_with_deadline(generator_offset, setup, deadline)
+            }
+        })?;
 
         // add the commitments and bit distributions to the proof
-        extend_transcript(&mut transcript, &commitments, builder.bit_distributions());
+        state.absorb_commitments(&commitments);
+
+        Self::prove_committed_witness(state, commitments, setup, deadline).map(single_result)
+    }
+
+    /// Create a new `QueryProof`, streaming the result through the encoder before the rest of the
+    /// proof is generated.
+    pub(super) fn streaming_impl(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: &EvaluationContext,
+        result_stream: &ResultStreamEncoder,
+        deadline: &ProverDeadline,
+    ) -> Result<(Self, ProvableQueryResult), ProverError> {
+        let alloc = Bump::new();
+        let mut state = evaluate_witness::<CP::Commitment>(
+            slice::from_ref(expr),
+            accessor,
+            &alloc,
+            context,
+            None,
+            Some(result_stream),
+            deadline,
+        )?;
+        let generator_offset = state.generator_offset;
+        let commitments = state.commit_intermediate_mles(|builder| {
+            builder.commit_intermediate_mles_with_deadline(generator_offset, setup, deadline)
+        })?;
+        state.absorb_commitments(&commitments);
+        Self::prove_committed_witness(state, commitments, setup, deadline).map(single_result)
+    }
+
+    /// Create a single proof of a batch of queries over the same rows, along with their results.
+    ///
+    /// The queries share the transcript, the sumcheck and the evaluation proof, so the proof is
+    /// about as large as the proof of a single query. The caller checks that every query has the
+    /// same length and offset.
+    pub(super) fn new_batch_impl<E: ProofExpr<CP::Commitment> + Serialize>(
+        exprs: &[E],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: &EvaluationContext,
+        deadline: &ProverDeadline,
+    ) -> Result<(Self, Vec<ProvableQueryResult>), ProverError> {
+        let alloc = Bump::new();
+        let mut state = evaluate_witness::<CP::Commitment>(
+            exprs, accessor, &alloc, context, None, None, deadline,
+        )?;
+        let generator_offset = state.generator_offset;
+        let commitments = state.commit_intermediate_mles(|builder| {
+            builder.commit_intermediate_mles_with_deadline(generator_offset, setup, deadline)
+        })?;
+        state.absorb_commitments(&commitments);
+        Self::prove_committed_witness(state, commitments, setup, deadline)
+    }
+
+    /// Finish the proof once the transcript has absorbed the commitments to the intermediate MLEs.
+    fn prove_committed_witness(
+        state: WitnessState<'_, CP::Scalar>,
+        commitments: Vec<CP::Commitment>,
+        setup: &CP::ProverPublicSetup<'_>,
+        deadline: &ProverDeadline,
+    ) -> Result<(Self, Vec<ProvableQueryResult>), ProverError> {
+        let WitnessState {
+            builder,
+            mut transcript,
+            provable_results,
+            generator_offset,
+            phase_fields,
+        } = state;
+        let num_sumcheck_variables = builder.num_sumcheck_variables();
+        let table_length = builder.table_length();
+
+        let sumcheck_span = phase_span!("QueryProof::sumcheck", phase_fields).entered();
 
         // construct the sumcheck polynomial
         let num_random_scalars = num_sumcheck_variables + builder.num_sumcheck_subpolynomials();
@@ -98,12 +321,22 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         // create the sumcheck proof -- this is the main part of proving a query
         let mut evaluation_point = vec![Zero::zero(); poly.num_variables];
-        let sumcheck_proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, &poly);
+        let sumcheck_proof = SumcheckProof::create_with_deadline(
+            &mut transcript,
+            &mut evaluation_point,
+            &poly,
+            deadline,
+        )?;
+        drop(sumcheck_span);
 
         // evaluate the MLEs used in sumcheck except for the result columns
-        let mut evaluation_vec = vec![Zero::zero(); table_length];
-        compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
-        let pcs_proof_evaluations = builder.evaluate_pcs_proof_mles(&evaluation_vec);
+        deadline.check()?;
+        let pcs_proof_evaluations =
+            phase_span!("QueryProof::evaluate_pcs_proof_mles", phase_fields).in_scope(|| {
+                let mut evaluation_vec = vec![Zero::zero(); table_length];
+                compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
+                builder.evaluate_pcs_proof_mles(&evaluation_vec)
+            });
 
         // commit to the MLE evaluations
         transcript
@@ -116,7 +349,10 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &mut random_scalars,
             MessageLabel::QueryMleEvaluationsChallenge,
         );
+        let evaluation_proof_span =
+            phase_span!("QueryProof::evaluation_proof", phase_fields).entered();
         let folded_mle = builder.fold_pcs_proof_mles(&random_scalars);
+        deadline.check()?;
 
         // finally, form the inner product proof of the MLEs' evaluations
         let evaluation_proof = CP::new(
@@ -126,6 +362,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             generator_offset as u64,
             setup,
         );
+        drop(evaluation_proof_span);
 
         let proof = Self {
             bit_distributions: builder.bit_distributions().to_vec(),
@@ -134,10 +371,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             pcs_proof_evaluations,
             evaluation_proof,
         };
-        (proof, provable_result)
+        Ok((proof, provable_results))
     }
 
-    #[tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)]
     /// Verify a `QueryProof`. Note: This does NOT transform the result!
     pub fn verify(
         &self,
@@ -146,32 +382,192 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         result: &ProvableQueryResult,
         setup: &CP::VerifierPublicSetup<'_>,
     ) -> QueryResult<CP::Scalar> {
-        let table_length = expr.get_length(accessor);
-        let generator_offset = expr.get_offset(accessor);
+        self.verify_with_context(expr, accessor, result, setup, &EvaluationContext::default())
+    }
+
+    /// Verify a `QueryProof` that was created with the given evaluation context.
+    /// Note: This does NOT transform the result!
+    pub fn verify_with_context(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup<'_>,
+        context: &EvaluationContext,
+    ) -> QueryResult<CP::Scalar> {
+        self.verify_with_limits(
+            expr,
+            accessor,
+            result,
+            setup,
+            context,
+            &ProvableQueryResultLimits::default(),
+        )
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "QueryProof::verify", level = "debug", skip_all, err)
+    )]
+    /// Verify a `QueryProof` that was created with the given evaluation context, rejecting a
+    /// result that is larger than the given limits before it is decoded.
+    /// Note: This does NOT transform the result!
+    pub fn verify_with_limits(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup<'_>,
+        context: &EvaluationContext,
+        limits: &ProvableQueryResultLimits,
+    ) -> QueryResult<CP::Scalar> {
+        let exprs = slice::from_ref(expr);
+        let results = slice::from_ref(result);
+        self.verify_impl(exprs, accessor, results, setup, context, limits, true, None)
+            .map(|verified| verified.into_query_data().remove(0))
+    }
+
+    /// Verify a `QueryProof` like [`QueryProof::verify_with_limits`], but return the rows of the
+    /// result to be decoded lazily rather than decoding them into an [`OwnedTable`].
+    ///
+    /// The result is only decoded during verification if the verifier of the query reads it,
+    /// see [`ProofExpr::requires_result_table`].
+    /// Note: This does NOT transform the result!
+    pub fn verify_rows<'a>(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &'a ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup<'_>,
+        context: &EvaluationContext,
+        limits: &ProvableQueryResultLimits,
+    ) -> Result<VerifiedRows<'a, CP::Scalar>, QueryError> {
+        let exprs = slice::from_ref(expr);
+        let results = slice::from_ref(result);
+        let verified = self.verify_impl(
+            exprs, accessor, results, setup, context, limits, false, None,
+        )?;
+        VerifiedRows::new(
+            result,
+            expr.get_column_result_fields(),
+            verified.verification_hash,
+            verified.selected_rows[0],
+        )
+    }
+
+    /// Verify a `QueryProof` of a batch of queries over the same rows, created by
+    /// [`QueryProof::new_batch_impl`], returning the verified result of each query in order.
+    /// Note: This does NOT transform the results!
+    pub(super) fn verify_batch_impl<E: ProofExpr<CP::Commitment> + Serialize>(
+        &self,
+        exprs: &[E],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: &[ProvableQueryResult],
+        setup: &CP::VerifierPublicSetup<'_>,
+        context: &EvaluationContext,
+        limits: &ProvableQueryResultLimits,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, QueryError> {
+        self.verify_impl(exprs, accessor, results, setup, context, limits, true, None)
+            .map(VerifiedResults::into_query_data)
+    }
+
+    /// Verify a `QueryProof` like [`QueryProof::verify_with_limits`], and report which checks
+    /// passed, the challenges the verifier drew, the size of the proof and how long verification
+    /// took.
+    /// Note: This does NOT transform the result!
+    #[cfg(feature = "verification-report")]
+    pub fn verify_with_report(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        result: &ProvableQueryResult,
+        setup: &CP::VerifierPublicSetup<'_>,
+        context: &EvaluationContext,
+        limits: &ProvableQueryResultLimits,
+    ) -> (QueryResult<CP::Scalar>, VerificationReport<CP::Scalar>) {
+        let mut report = VerificationReport::new();
+        report.proof_size = postcard::to_allocvec(self).map_or(0, |proof| proof.len());
+        let start = Instant::now();
+        let query_result = self
+            .verify_impl(
+                slice::from_ref(expr),
+                accessor,
+                slice::from_ref(result),
+                setup,
+                context,
+                limits,
+                true,
+                Some(&mut report),
+            )
+            .map(|verified| verified.into_query_data().remove(0));
+        report.duration = start.elapsed();
+        (query_result, report)
+    }
+
+    /// Verify the proof of the results of the queries.
+    ///
+    /// If `decode_results` is not set, a result is only decoded if the verifier of its query
+    /// reads it.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_impl(
+        &self,
+        exprs: &[impl ProofExpr<CP::Commitment> + Serialize],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: &[ProvableQueryResult],
+        setup: &CP::VerifierPublicSetup<'_>,
+        context: &EvaluationContext,
+        limits: &ProvableQueryResultLimits,
+        decode_results: bool,
+        mut report: Option<&mut VerificationReport<CP::Scalar>>,
+    ) -> Result<VerifiedResults<CP::Scalar>, QueryError> {
+        let limits_check = results
+            .iter()
+            .try_for_each(|result| result.check_limits(limits));
+        let check = VerificationCheck::ResultLimits;
+        record_check(report.as_deref_mut(), check, limits_check)?;
+
+        let table_length = exprs[0].get_length(accessor);
+        let generator_offset = exprs[0].get_offset(accessor);
         let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
         assert!(num_sumcheck_variables > 0);
+        let phase_fields = PhaseFields::new(exprs, table_length);
 
         // validate bit decompositions
-        for dist in self.bit_distributions.iter() {
-            if !dist.is_valid() {
-                Err(ProofError::VerificationError("invalid bit distributions"))?;
-            }
-        }
+        let valid = self.bit_distributions.iter().all(BitDistribution::is_valid);
+        let bit_distributions_check = ensure(valid, "invalid bit distributions");
+        let check = VerificationCheck::BitDistributions;
+        record_check(report.as_deref_mut(), check, bit_distributions_check)?;
 
         // count terms
         let counts = {
             let mut builder = CountBuilder::new(&self.bit_distributions);
-            expr.count(&mut builder, accessor)?;
-            builder.counts()
-        }?;
+            exprs
+                .iter()
+                .try_for_each(|expr| expr.count(&mut builder, accessor))
+                .and_then(|()| builder.counts())
+        };
+        let counts = record_check(
+            report.as_deref_mut(),
+            VerificationCheck::ProofCounts,
+            counts,
+        )?;
 
         // verify sizes
-        if !self.validate_sizes(&counts, result) {
-            Err(ProofError::VerificationError("invalid proof size"))?;
-        }
+        let column_result_fields: Vec<_> = exprs
+            .iter()
+            .map(ProofExpr::get_column_result_fields)
+            .collect();
+        let valid = self.validate_sizes(&counts, results, &column_result_fields);
+        let sizes_check = ensure(valid, "invalid proof size");
+        record_check(
+            report.as_deref_mut(),
+            VerificationCheck::ProofSize,
+            sizes_check,
+        )?;
 
         // construct a transcript for the proof
-        let mut transcript = make_transcript(expr, result, table_length, generator_offset);
+        let mut transcript =
+            make_transcript(exprs, results, table_length, generator_offset, context);
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -183,6 +579,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &mut post_result_challenges,
             MessageLabel::PostResultChallenges,
         );
+        if let Some(report) = report.as_deref_mut() {
+            report.post_result_challenges = post_result_challenges.clone();
+        }
 
         // add the commitments and bit disctibutions to the proof
         extend_transcript(&mut transcript, &self.commitments, &self.bit_distributions);
@@ -191,6 +590,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let num_random_scalars = num_sumcheck_variables + counts.sumcheck_subpolynomials;
         let mut random_scalars = vec![Zero::zero(); num_random_scalars];
         transcript.challenge_scalars(&mut random_scalars, MessageLabel::QuerySumcheckChallenge);
+        if let Some(report) = report.as_deref_mut() {
+            report.sumcheck_random_scalars = random_scalars.clone();
+        }
         let sumcheck_random_scalars =
             SumcheckRandomScalars::new(&random_scalars, table_length, num_sumcheck_variables);
 
@@ -201,11 +603,14 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             max_multiplicands: core::cmp::max(counts.sumcheck_max_multiplicands, 2),
             num_variables: num_sumcheck_variables,
         };
-        let subclaim = self.sumcheck_proof.verify_without_evaluation(
-            &mut transcript,
-            poly_info,
-            &Zero::zero(),
-        )?;
+        let subclaim = phase_span!("QueryProof::verify_sumcheck", phase_fields).in_scope(|| {
+            self.sumcheck_proof
+                .verify_without_evaluation(&mut transcript, poly_info, &Zero::zero())
+        });
+        let subclaim = record_check(report.as_deref_mut(), VerificationCheck::Sumcheck, subclaim)?;
+        if let Some(report) = report.as_deref_mut() {
+            report.evaluation_point = subclaim.evaluation_point.clone();
+        }
 
         // commit to mle evaluations
         transcript.append_canonical_serialize(
@@ -220,24 +625,39 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &mut evaluation_random_scalars,
             MessageLabel::QueryMleEvaluationsChallenge,
         );
+        if let Some(report) = report.as_deref_mut() {
+            report.evaluation_random_scalars = evaluation_random_scalars.clone();
+        }
 
-        let column_result_fields = expr.get_column_result_fields();
-
-        // compute the evaluation of the result MLEs
-        let result_evaluations = result.evaluate(
-            &subclaim.evaluation_point,
-            table_length,
-            &column_result_fields[..],
-        )?;
-
-        // pass over the provable AST to fill in the verification builder
+        // compute the evaluation of the result MLEs and decode the results
+        let decoded_results = exprs
+            .iter()
+            .zip(results)
+            .zip(&column_result_fields)
+            .map(|((expr, result), fields)| {
+                let result_evaluations =
+                    result.evaluate(&subclaim.evaluation_point, table_length, fields)?;
+                let owned_table_result = (decode_results || expr.requires_result_table())
+                    .then(|| result.to_owned_table(fields))
+                    .transpose()?;
+                Ok((result_evaluations, owned_table_result))
+            })
+            .collect::<Result<Vec<_>, QueryError>>();
+        let check = VerificationCheck::ResultDecoding;
+        let (result_evaluations, owned_table_results): (Vec<_>, Vec<_>) =
+            record_check(report.as_deref_mut(), check, decoded_results)?
+                .into_iter()
+                .unzip();
+
+        // pass over the provable ASTs to fill in the verification builder, switching to the
+        // result of each query before its AST is evaluated
         let sumcheck_evaluations = SumcheckMleEvaluations::new(
             table_length,
             &subclaim.evaluation_point,
             &sumcheck_random_scalars,
             &self.pcs_proof_evaluations,
-            &result_evaluations,
-            result.indexes(),
+            &[],
+            &Indexes::default(),
         );
         let mut builder = VerificationBuilder::new(
             generator_offset,
@@ -248,51 +668,127 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             &evaluation_random_scalars,
             post_result_challenges,
         );
-        let owned_table_result = result.to_owned_table(&column_result_fields[..])?;
-        expr.verifier_evaluate(&mut builder, accessor, Some(&owned_table_result))?;
+        let verifier_evaluation = phase_span!("QueryProof::verifier_evaluate", phase_fields)
+            .in_scope(|| {
+                (0..exprs.len()).try_for_each(|i| {
+                    let indexes_evaluation = results[i]
+                        .indexes()
+                        .evaluate_at_point(&subclaim.evaluation_point);
+                    builder.set_result_evaluations(&result_evaluations[i], indexes_evaluation);
+                    exprs[i].verifier_evaluate(
+                        &mut builder,
+                        accessor,
+                        owned_table_results[i].as_ref(),
+                    )
+                })
+            });
+        let check = VerificationCheck::VerifierEvaluation;
+        record_check(report.as_deref_mut(), check, verifier_evaluation)?;
 
         // perform the evaluation check of the sumcheck polynomial
-        if builder.sumcheck_evaluation() != subclaim.expected_evaluation {
-            Err(ProofError::VerificationError(
-                "sumcheck evaluation check failed",
-            ))?;
-        }
+        let sumcheck_evaluation_check = ensure(
+            builder.sumcheck_evaluation() == subclaim.expected_evaluation,
+            "sumcheck evaluation check failed",
+        );
+        let check = VerificationCheck::SumcheckEvaluation;
+        record_check(report.as_deref_mut(), check, sumcheck_evaluation_check)?;
 
         // finally, check the MLE evaluations with the inner product proof
         let product = builder.folded_pcs_proof_evaluation();
-        self.evaluation_proof
-            .verify_batched_proof(
-                &mut transcript,
-                builder.pcs_proof_commitments(),
-                builder.inner_product_multipliers(),
-                &product,
-                &subclaim.evaluation_point,
-                generator_offset as u64,
-                table_length,
-                setup,
-            )
-            .map_err(|_e| {
-                ProofError::VerificationError("Inner product proof of MLE evaluations failed")
-            })?;
+        let evaluation_proof_check =
+            phase_span!("QueryProof::verify_evaluation_proof", phase_fields)
+                .in_scope(|| {
+                    self.evaluation_proof.verify_batched_proof(
+                        &mut transcript,
+                        builder.pcs_proof_commitments(),
+                        builder.inner_product_multipliers(),
+                        &product,
+                        &subclaim.evaluation_point,
+                        generator_offset as u64,
+                        table_length,
+                        setup,
+                    )
+                })
+                .map_err(|_e| {
+                    ProofError::VerificationError("Inner product proof of MLE evaluations failed")
+                });
+        let check = VerificationCheck::EvaluationProof;
+        record_check(report.as_deref_mut(), check, evaluation_proof_check)?;
 
         let mut verification_hash = [0u8; 32];
         transcript.challenge_bytes(
             MessageLabel::VerificationHash.as_bytes(),
             &mut verification_hash,
         );
-        Ok(QueryData {
-            table: owned_table_result,
+        let selected_rows = exprs
+            .iter()
+            .zip(results)
+            .zip(&owned_table_results)
+            .map(|((expr, result), table)| match table {
+                Some(table) => expr.get_selected_row_count(table),
+                None => result.indexes().len(),
+            })
+            .collect();
+        Ok(VerifiedResults {
+            tables: owned_table_results,
+            selected_rows,
             verification_hash,
         })
     }
 
-    fn validate_sizes(&self, counts: &ProofCounts, result: &ProvableQueryResult) -> bool {
-        result.num_columns() == counts.result_columns
+    fn validate_sizes(
+        &self,
+        counts: &ProofCounts,
+        results: &[ProvableQueryResult],
+        column_result_fields: &[Vec<ColumnField>],
+    ) -> bool {
+        results.len() == column_result_fields.len()
+            && results
+                .iter()
+                .zip(column_result_fields)
+                .all(|(result, fields)| result.num_columns() == fields.len())
+            && results
+                .iter()
+                .map(ProvableQueryResult::num_columns)
+                .sum::<usize>()
+                == counts.result_columns
             && self.commitments.len() == counts.intermediate_mles
             && self.pcs_proof_evaluations.len() == counts.intermediate_mles + counts.anchored_mles
     }
 }
 
+/// What the verifier learned from a proof about the results of its queries.
+struct VerifiedResults<S: Scalar> {
+    /// The decoded result of each query, unless it was not decoded during verification
+    tables: Vec<Option<OwnedTable<S>>>,
+    /// The number of rows that each query selected
+    selected_rows: Vec<usize>,
+    verification_hash: [u8; 32],
+}
+
+impl<S: Scalar> VerifiedResults<S> {
+    /// Returns the verified result of each query, all of which have to be decoded.
+    fn into_query_data(self) -> Vec<QueryData<S>> {
+        self.tables
+            .into_iter()
+            .zip(self.selected_rows)
+            .map(|(table, selected_rows)| QueryData {
+                table: table.expect("the results are decoded"),
+                verification_hash: self.verification_hash,
+                column_checksums: None,
+                selected_rows,
+            })
+            .collect()
+    }
+}
+
+/// Returns a verification error with the message unless the condition holds.
+fn ensure(condition: bool, message: &'static str) -> Result<(), ProofError> {
+    condition
+        .then_some(())
+        .ok_or(ProofError::VerificationError(message))
+}
+
 /// Creates a transcript using the Merlin library.
 ///
 /// This function is used to produce a transcript for a proof expression
@@ -312,24 +808,182 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 ///
 /// * `generator_offset` - The offset of the generator used in the proof, as a `usize`.
 ///
+/// * `context` - A reference to the `EvaluationContext` whose values the prover bound
+///   into the query.
+///
 /// # Returns
 /// This function returns a `merlin::Transcript`. The transcript is a record
 /// of all the operations and data involved in creating a proof.
 /// ```
-fn make_transcript<C: Commitment>(
+/// The state of the prover once it has evaluated the query, before it commits to the intermediate
+/// MLEs.
+struct WitnessState<'a, S: Scalar> {
+    builder: ProofBuilder<'a, S>,
+    transcript: Transcript,
+    provable_results: Vec<ProvableQueryResult>,
+    generator_offset: usize,
+    phase_fields: PhaseFields,
+}
+
+impl<S: Scalar> WitnessState<'_, S> {
+    /// Commit to the intermediate MLEs in the span of that phase of the prover.
+    fn commit_intermediate_mles<C>(
+        &self,
+        commit: impl FnOnce(&ProofBuilder<'_, S>) -> Result<Vec<C>, ProverError>,
+    ) -> Result<Vec<C>, ProverError> {
+        phase_span!("QueryProof::commit_intermediate_mles", self.phase_fields)
+            .in_scope(|| commit(&self.builder))
+    }
+
+    /// Add the commitments to the intermediate MLEs and the bit distributions to the transcript.
+    fn absorb_commitments<C: Serialize>(&mut self, commitments: &[C]) {
+        extend_transcript(
+            &mut self.transcript,
+            &commitments,
+            self.builder.bit_distributions(),
+        );
+    }
+
+    /// Returns a challenge drawn from a copy of the transcript, which identifies its state without
+    /// changing the challenges of the proof.
+    fn transcript_fingerprint(&self) -> [u8; 32] {
+        let mut fingerprint = [0; 32];
+        self.transcript
+            .clone()
+            .challenge_bytes(MessageLabel::ProverCheckpoint.as_bytes(), &mut fingerprint);
+        fingerprint
+    }
+}
+
+/// Compute the results of the queries and the intermediate MLEs of their proof.
+///
+/// The queries have to be over the same rows, so that their proofs share a single sumcheck.
+fn evaluate_witness<'a, C: Commitment>(
+    exprs: &[impl ProofExpr<C> + Serialize],
+    accessor: &'a dyn DataAccessor<C::Scalar>,
+    alloc: &'a Bump,
+    context: &EvaluationContext,
+    prover_cache: Option<&'a ProverCache<C::Scalar>>,
+    result_stream: Option<&ResultStreamEncoder>,
+    deadline: &ProverDeadline,
+) -> Result<WitnessState<'a, C::Scalar>, ProverError> {
+    let table_length = exprs[0].get_length(accessor);
+    let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
+    let generator_offset = exprs[0].get_offset(accessor);
+    assert!(num_sumcheck_variables > 0);
+    let phase_fields = PhaseFields::new(exprs, table_length);
+
+    let result_evaluate_span = phase_span!("QueryProof::result_evaluate", phase_fields).entered();
+    let mut provable_results = Vec::with_capacity(exprs.len());
+    let mut num_post_result_challenges = 0;
+    for expr in exprs {
+        let mut result_builder = ResultBuilder::new(table_length);
+        expr.result_evaluate(&mut result_builder, alloc, accessor);
+        provable_results.push(match result_stream {
+            Some(result_stream) => result_builder.stream_provable_query_result(result_stream)?,
+            None => result_builder.make_provable_query_result(),
+        });
+        num_post_result_challenges += result_builder.num_post_result_challenges();
+    }
+    drop(result_evaluate_span);
+    deadline.check()?;
+
+    // construct a transcript for the proof
+    let mut transcript: Transcript = make_transcript(
+        exprs,
+        &provable_results,
+        table_length,
+        generator_offset,
+        context,
+    );
+
+    // These are the challenges that will be consumed by the proof
+    // Specifically, these are the challenges that the verifier sends to
+    // the prover after the prover sends the result, but before the prover
+    // send commitments to the intermediate witness columns.
+    // Note: the last challenge in the vec is the first one that is consumed.
+    let mut post_result_challenges = vec![Zero::zero(); num_post_result_challenges];
+    transcript.challenge_scalars(
+        &mut post_result_challenges,
+        MessageLabel::PostResultChallenges,
+    );
+
+    let mut builder =
+        ProofBuilder::new(table_length, num_sumcheck_variables, post_result_challenges);
+    if let Some(prover_cache) = prover_cache {
+        builder.set_prover_cache(prover_cache);
+    }
+    phase_span!("QueryProof::prover_evaluate", phase_fields).in_scope(|| {
+        for expr in exprs {
+            expr.prover_evaluate(&mut builder, alloc, accessor);
+        }
+    });
+    if let Some(overflow) = builder.take_overflow() {
+        return Err(overflow);
+    }
+    deadline.check()?;
+
+    Ok(WitnessState {
+        builder,
+        transcript,
+        provable_results,
+        generator_offset,
+        phase_fields,
+    })
+}
+
+/// Run the prover up to its sumcheck, and describe the constraints that it produces.
+pub(super) fn evaluate_constraint_system<C: Commitment>(
     expr: &(impl ProofExpr<C> + Serialize),
-    result: &ProvableQueryResult,
+    accessor: &impl DataAccessor<C::Scalar>,
+    context: &EvaluationContext,
+) -> ConstraintSystem {
+    let alloc = Bump::new();
+    let deadline = ProverDeadline::default();
+    evaluate_witness::<C>(
+        slice::from_ref(expr),
+        accessor,
+        &alloc,
+        context,
+        None,
+        None,
+        &deadline,
+    )
+    .expect("the default deadline never expires")
+    .builder
+    .constraint_system()
+}
+
+fn make_transcript<C: Commitment>(
+    exprs: &[impl ProofExpr<C> + Serialize],
+    results: &[ProvableQueryResult],
     table_length: usize,
     generator_offset: usize,
+    context: &EvaluationContext,
 ) -> merlin::Transcript {
     let mut transcript = Transcript::new(MessageLabel::QueryProof.as_bytes());
-    transcript.append_auto(MessageLabel::QueryResultData, result);
-    transcript.append_auto(MessageLabel::ProofExpr, expr);
+    for result in results {
+        transcript.append_auto(MessageLabel::QueryResultData, result);
+    }
+    for expr in exprs {
+        transcript.append_auto(MessageLabel::ProofExpr, expr);
+    }
     transcript.append_auto(MessageLabel::TableLength, &table_length);
     transcript.append_auto(MessageLabel::GeneratorOffset, &generator_offset);
+    transcript.append_auto(MessageLabel::EvaluationContext, context);
     transcript
 }
 
+/// Returns the proof of a single query along with its result.
+fn single_result<T>(
+    (proof, mut results): (T, Vec<ProvableQueryResult>),
+) -> (T, ProvableQueryResult) {
+    let result = results
+        .pop()
+        .expect("a proof of a single query has a single result");
+    (proof, result)
+}
+
 fn extend_transcript<C: serde::Serialize>(
     transcript: &mut Transcript,
     commitments: &C,
@@ -9,14 +9,17 @@ use crate::{
             ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
             OwnedTable, OwnedTableTestAccessor, TestAccessor, UnimplementedTestAccessor,
         },
-        proof::ProofError,
+        proof::{ProofError, ProverDeadline, ProverError},
         scalar::{Curve25519Scalar, Scalar},
     },
-    sql::proof::{Indexes, QueryData, ResultBuilder, SumcheckSubpolynomialType},
+    sql::proof::{
+        EvaluationContext, Indexes, QueryData, QueryError, ResultBuilder, SumcheckSubpolynomialType,
+    },
 };
 use bumpalo::Bump;
 use indexmap::IndexSet;
 use serde::Serialize;
+use std::time::Instant;
 
 /// Type to allow us to prove and verify an artificial polynomial where we prove
 /// that every entry in the result is zero
@@ -112,12 +115,77 @@ fn verify_a_trivial_query_proof_with_given_offset(n: usize, offset_generators: u
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof.verify(&expr, &accessor, &result, &()).unwrap();
     assert_ne!(verification_hash, [0; 32]);
     let expected_result = owned_table([bigint("a1", [0])]);
     assert_eq!(table, expected_result);
 }
 
+#[test]
+fn column_checksums_of_a_verified_result_detect_changes_to_the_table() {
+    let expr = TrivialTestProofExpr {
+        length: 2,
+        ..Default::default()
+    };
+    let accessor = UnimplementedTestAccessor::new_empty();
+    let (proof, result) = QueryProof::<InnerProductProof>::new(&expr, &accessor, &());
+    let query_data = proof.verify(&expr, &accessor, &result, &()).unwrap();
+    assert!(query_data.column_checksums.is_none());
+    let mut query_data = query_data.with_column_checksums();
+    assert!(query_data.check_column_checksums().is_ok());
+    query_data.table = owned_table([bigint("a1", [1])]);
+    assert!(matches!(
+        query_data.check_column_checksums(),
+        Err(QueryError::ColumnChecksumMismatch)
+    ));
+}
+
+#[test]
+fn we_can_only_prove_a_query_before_the_deadline_expires() {
+    let expr = TrivialTestProofExpr {
+        length: 4,
+        ..Default::default()
+    };
+    let accessor = UnimplementedTestAccessor::new_empty();
+    let context = EvaluationContext::default();
+
+    let deadline = ProverDeadline::new().with_step_budget(100);
+    let (proof, result) = QueryProof::<InnerProductProof>::new_with_deadline(
+        &expr,
+        &accessor,
+        &(),
+        &context,
+        &deadline,
+    )
+    .unwrap();
+    assert!(proof.verify(&expr, &accessor, &result, &()).is_ok());
+
+    let deadline = ProverDeadline::new().with_step_budget(deadline.steps_taken() - 1);
+    assert!(matches!(
+        QueryProof::<InnerProductProof>::new_with_deadline(
+            &expr,
+            &accessor,
+            &(),
+            &context,
+            &deadline,
+        ),
+        Err(ProverError::StepBudgetExhausted { .. })
+    ));
+
+    let deadline = ProverDeadline::new().with_deadline(Instant::now());
+    assert!(matches!(
+        QueryProof::<InnerProductProof>::new_with_deadline(
+            &expr,
+            &accessor,
+            &(),
+            &context,
+            &deadline,
+        ),
+        Err(ProverError::Timeout)
+    ));
+}
+
 #[test]
 fn we_can_verify_a_trivial_query_proof_with_a_zero_offset() {
     for n in 1..5 {
@@ -298,6 +366,7 @@ fn verify_a_proof_with_an_anchored_commitment_and_given_offset(offset_generators
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof.verify(&expr, &accessor, &result, &()).unwrap();
     assert_ne!(verification_hash, [0; 32]);
     let expected_result = owned_table([bigint("a1", [9, 25])]);
@@ -495,6 +564,7 @@ fn verify_a_proof_with_an_intermediate_commitment_and_given_offset(offset_genera
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof.verify(&expr, &accessor, &result, &()).unwrap();
     assert_ne!(verification_hash, [0; 32]);
     let expected_result = owned_table([bigint("a1", [81, 625])]);
@@ -684,6 +754,7 @@ fn verify_a_proof_with_a_post_result_challenge_and_given_offset(offset_generator
     let QueryData {
         verification_hash,
         table,
+        ..
     } = proof.verify(&expr, &accessor, &result, &()).unwrap();
     assert_ne!(verification_hash, [0; 32]);
     let expected_result = owned_table([bigint("a1", [9, 25])]);
@@ -1,9 +1,12 @@
+use super::ProofVersion;
 use crate::base::{
-    database::{OwnedTable, OwnedTableError},
+    database::{ColumnType, OwnedTable, OwnedTableError},
     proof::ProofError,
     scalar::Scalar,
 };
 use arrow::{error::ArrowError, record_batch::RecordBatch};
+use indexmap::IndexMap;
+use proof_of_sql_parser::Identifier;
 use thiserror::Error;
 
 /// Verifiable query errors
@@ -17,6 +20,22 @@ pub enum QueryError {
     /// This just means that the database was supposed to respond with a string that was not valid UTF-8.
     #[error("String decode error")]
     InvalidString,
+    /// The query result claims more rows than the verifier is willing to decode.
+    #[error("The query result has {num_rows} rows, which exceeds the limit of {max_rows}")]
+    TooManyRows {
+        /// The number of rows the query result claims to have.
+        num_rows: usize,
+        /// The maximum number of rows allowed.
+        max_rows: usize,
+    },
+    /// The query result has more encoded data than the verifier is willing to decode.
+    #[error("The query result has {num_bytes} bytes, which exceeds the limit of {max_bytes}")]
+    TooManyBytes {
+        /// The number of bytes of encoded data in the query result.
+        num_bytes: usize,
+        /// The maximum number of bytes allowed.
+        max_bytes: usize,
+    },
     /// Decoding errors other than overflow and invalid string.
     #[error("Miscellaneous decoding error")]
     MiscellaneousDecodingError,
@@ -26,6 +45,38 @@ pub enum QueryError {
     /// Miscellaneous evaluation error.
     #[error("Miscellaneous evaluation error")]
     MiscellaneousEvaluationError,
+    /// The result was derived from fewer rows than the verifier requires.
+    #[error(
+        "The query result was derived from {selected_rows} rows, fewer than the required \
+         {min_selected_rows}"
+    )]
+    TooFewSelectedRows {
+        /// The number of rows the query selected.
+        selected_rows: usize,
+        /// The minimum number of rows required.
+        min_selected_rows: usize,
+    },
+    /// The table no longer matches the column checksums taken when it was verified.
+    #[error("Column checksum mismatch")]
+    ColumnChecksumMismatch,
+    /// The proof was created by a prover whose version the verifier does not support.
+    #[error(
+        "The proof has version {proof}, which the verifier of version {verifier} cannot verify"
+    )]
+    IncompatibleVersion {
+        /// The version of the prover that created the proof.
+        proof: ProofVersion,
+        /// The version of the verifier.
+        verifier: ProofVersion,
+    },
+    /// The query result does not have the schema that the verifier expected.
+    #[error("The query result has the schema {actual:?}, but {expected:?} was expected")]
+    SchemaMismatch {
+        /// The names and types of the columns that the verifier expected.
+        expected: Vec<(Identifier, ColumnType)>,
+        /// The names and types of the columns of the query result.
+        actual: Vec<(Identifier, ColumnType)>,
+    },
     /// The proof failed to verify.
     #[error(transparent)]
     ProofError(#[from] ProofError),
@@ -45,9 +96,48 @@ pub struct QueryData<S: Scalar> {
     /// Additionally, there is a 32-byte verification hash that is included with this table.
     /// This hash provides evidence that the verification has been run.
     pub verification_hash: [u8; 32],
+    /// Optional Blake3 checksums of the verified table's columns, keyed by column name.
+    ///
+    /// These are not part of the proof. They allow consumers that pass the table across process
+    /// boundaries after verification to detect corruption of the data.
+    pub column_checksums: Option<IndexMap<Identifier, [u8; 32]>>,
+    /// The number of rows of the queried table that the query selected, e.g. the rows that
+    /// satisfy its `WHERE` clause.
+    ///
+    /// This is verified along with the table, and it is available even when the query only
+    /// returns aggregates, so that clients can reject results derived from too few rows.
+    pub selected_rows: usize,
 }
 
 impl<S: Scalar> QueryData<S> {
+    /// Computes and attaches the column checksums of the verified table.
+    pub fn with_column_checksums(mut self) -> Self {
+        self.column_checksums = Some(self.table.column_checksums());
+        self
+    }
+
+    /// Checks that the result was derived from at least `min_selected_rows` rows of the table.
+    pub fn check_min_selected_rows(&self, min_selected_rows: usize) -> Result<(), QueryError> {
+        if self.selected_rows < min_selected_rows {
+            Err(QueryError::TooFewSelectedRows {
+                selected_rows: self.selected_rows,
+                min_selected_rows,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks the table against the attached column checksums, if there are any.
+    pub fn check_column_checksums(&self) -> Result<(), QueryError> {
+        match &self.column_checksums {
+            Some(checksums) if *checksums != self.table.column_checksums() => {
+                Err(QueryError::ColumnChecksumMismatch)
+            }
+            _ => Ok(()),
+        }
+    }
+
     #[cfg(test)]
     pub fn into_record_batch(self) -> RecordBatch {
         self.try_into().unwrap()
@@ -1,4 +1,8 @@
-use super::{Indexes, ProvableQueryResult, ProvableResultColumn};
+use super::{
+    result_stream::chunk_indexes, Indexes, ProvableQueryResult, ProvableResultColumn,
+    ResultStreamEncoder, ResultStreamMessage,
+};
+use crate::base::proof::ProverError;
 
 /// Track the result created by a query
 pub struct ResultBuilder<'a> {
@@ -44,6 +48,38 @@ impl<'a> ResultBuilder<'a> {
         ProvableQueryResult::new(&self.result_index_vector, &self.result_columns)
     }
 
+    /// Construct the intermediate query result to be sent to the verifier, streaming it through
+    /// the encoder in column chunks as it is encoded.
+    pub(super) fn stream_provable_query_result(
+        &self,
+        encoder: &ResultStreamEncoder,
+    ) -> Result<ProvableQueryResult, ProverError> {
+        let num_columns = self.result_columns.len() as u64;
+        encoder.send(ResultStreamMessage::Header {
+            num_columns,
+            indexes: self.result_index_vector.clone(),
+        })?;
+        let chunks = chunk_indexes(&self.result_index_vector, encoder.chunk_rows());
+        let mut data = Vec::new();
+        for (column_index, column) in self.result_columns.iter().enumerate() {
+            for chunk in &chunks {
+                let mut chunk_data = vec![0u8; column.num_bytes(chunk)];
+                column.write(&mut chunk_data, chunk);
+                data.extend_from_slice(&chunk_data);
+                encoder.send(ResultStreamMessage::Chunk {
+                    column: column_index as u64,
+                    data: chunk_data,
+                })?;
+            }
+        }
+        encoder.send(ResultStreamMessage::End)?;
+        Ok(ProvableQueryResult::new_from_raw_data(
+            num_columns,
+            self.result_index_vector.clone(),
+            data,
+        ))
+    }
+
     /// The number of challenges used in the proof.
     /// Specifically, these are the challenges that the verifier sends to
     /// the prover after the prover sends the result, but before the prover
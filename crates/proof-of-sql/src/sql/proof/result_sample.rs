@@ -0,0 +1,124 @@
+use super::{
+    paginated_query_result::commit_pages, PaginationError, ProofExpr, ProvableQueryResult,
+    QueryError, ResultCommitment, ResultPage, VerifiableQueryResult,
+};
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    database::{ColumnField, OwnedTable},
+    proof::{MessageLabel, TranscriptProtocol},
+    scalar::Scalar,
+};
+use indexmap::IndexSet;
+use merlin::Transcript;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when checking a [`ResultSample`].
+#[derive(Error, Debug)]
+pub enum SamplingError {
+    /// Rows can only be sampled from a result that is committed to row by row.
+    #[error("the result is committed to in pages of {0} rows rather than row by row")]
+    NotCommittedByRow(usize),
+    /// The sample does not open the rows that the commitment chooses.
+    #[error("the sample opens rows {actual:?} rather than the chosen rows {expected:?}")]
+    SampleMismatch {
+        /// The rows that the commitment chooses
+        expected: Vec<usize>,
+        /// The rows that the sample opens
+        actual: Vec<usize>,
+    },
+    /// A row of the sample does not match the commitment.
+    #[error(transparent)]
+    PaginationError(#[from] PaginationError),
+}
+
+/// Openings of randomly chosen rows of a query result, for spot audits of large results.
+///
+/// The result is committed to row by row with a [`ResultCommitment`], whose page size is `1`, and
+/// the rows are chosen by a transcript over the commitment, so the prover cannot pick which rows
+/// are opened. An auditor that trusts the commitment, e.g. because it was published by a verifier
+/// that verified the whole result, can then check the sample against it with
+/// [`ResultSample::verify`] without receiving or verifying the rest of the result.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ResultSample {
+    rows: Vec<ResultPage>,
+}
+
+impl ResultSample {
+    /// Commit to the rows of a proven result and open `num_samples` of them.
+    ///
+    /// A result with fewer rows than `num_samples` has all of its rows opened.
+    pub fn new<CP: CommitmentEvaluationProof>(
+        result: &VerifiableQueryResult<CP>,
+        expr: &impl ProofExpr<CP::Commitment>,
+        num_samples: usize,
+    ) -> Result<(ResultCommitment, Self), PaginationError> {
+        let column_result_fields = expr.get_column_result_fields();
+        let provable_result = result
+            .provable_result
+            .clone()
+            .unwrap_or_else(|| ProvableQueryResult::new_empty(column_result_fields.len()));
+        let (commitment, pages) =
+            commit_pages::<CP::Scalar>(&provable_result, &column_result_fields, 1)?;
+        let mut pages: Vec<_> = pages.into_iter().map(Some).collect();
+        let rows = sample_row_indexes(&commitment, num_samples)
+            .into_iter()
+            .map(|index| pages[index].take().expect("sampled rows are distinct"))
+            .collect();
+        Ok((commitment, Self { rows }))
+    }
+
+    /// Returns the indexes of the opened rows, in the order they were chosen.
+    pub fn row_indexes(&self) -> Vec<usize> {
+        self.rows.iter().map(ResultPage::index).collect()
+    }
+
+    /// Check that the sample opens the `num_samples` rows that the commitment chooses, and that
+    /// each of them is a row of the committed result.
+    pub fn verify(
+        &self,
+        commitment: &ResultCommitment,
+        num_samples: usize,
+    ) -> Result<(), SamplingError> {
+        if commitment.page_size() != 1 {
+            return Err(SamplingError::NotCommittedByRow(commitment.page_size()));
+        }
+        let expected = sample_row_indexes(commitment, num_samples);
+        let actual = self.row_indexes();
+        if actual != expected {
+            return Err(SamplingError::SampleMismatch { expected, actual });
+        }
+        for row in &self.rows {
+            commitment.verify_page(row)?;
+        }
+        Ok(())
+    }
+
+    /// Decode the opened rows, in the order of [`ResultSample::row_indexes`].
+    ///
+    /// The fields are those of the query, see [`ProofExpr::get_column_result_fields`].
+    pub fn to_owned_table<S: Scalar>(
+        &self,
+        column_result_fields: &[ColumnField],
+    ) -> Result<OwnedTable<S>, QueryError> {
+        let rows: Vec<_> = self.rows.iter().map(|row| row.result().clone()).collect();
+        ProvableQueryResult::join_rows::<S>(&rows, column_result_fields)?
+            .to_owned_table(column_result_fields)
+    }
+}
+
+/// Returns the indexes of `num_samples` distinct rows of the committed result, or of all of its
+/// rows if it has fewer, chosen by a transcript over the commitment.
+pub fn sample_row_indexes(commitment: &ResultCommitment, num_samples: usize) -> Vec<usize> {
+    let num_rows = commitment.num_rows();
+    let num_samples = num_samples.min(num_rows);
+    let mut transcript = Transcript::new(MessageLabel::ResultSample.as_bytes());
+    transcript.append_auto(MessageLabel::ResultCommitment, commitment);
+    let mut row_indexes = IndexSet::with_capacity(num_samples);
+    while row_indexes.len() < num_samples {
+        let mut challenge = [0; 8];
+        transcript.challenge_label_bytes(MessageLabel::ResultSampleChallenge, &mut challenge);
+        row_indexes.insert((u64::from_le_bytes(challenge) % num_rows as u64) as usize);
+    }
+    row_indexes.into_iter().collect()
+}
@@ -0,0 +1,124 @@
+use super::{
+    sample_row_indexes, PaginatedQueryResult, ProofExpr, ResultCommitment, ResultSample,
+    SamplingError, VerifiableQueryResult,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor},
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+const A: [i64; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+const B: [&str; 10] = ["a", "bb", "c", "dd", "e", "ff", "g", "hh", "i", "jj"];
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([bigint("a", A), varchar("b", B)]),
+        0,
+        (),
+    )
+}
+
+fn query_a_at_least(
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    min_a: i64,
+) -> ProofPlan<RistrettoPoint> {
+    let t = "sxt.t".parse().unwrap();
+    dense_filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        gte(column(t, "a", accessor), const_bigint(min_a)),
+    )
+}
+
+fn prove_and_sample(
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    ast: &ProofPlan<RistrettoPoint>,
+    num_samples: usize,
+) -> (ResultCommitment, ResultSample) {
+    let result = VerifiableQueryResult::<InnerProductProof>::new(ast, accessor, &());
+    ResultSample::new(&result, ast, num_samples).unwrap()
+}
+
+#[test]
+fn we_can_verify_a_sample_of_the_rows_of_a_result() {
+    let accessor = accessor();
+    let ast = query_a_at_least(&accessor, 3);
+    let (commitment, sample) = prove_and_sample(&accessor, &ast, 3);
+    assert_eq!(commitment.num_rows(), 8);
+    sample.verify(&commitment, 3).unwrap();
+
+    let row_indexes = sample.row_indexes();
+    assert_eq!(row_indexes, sample_row_indexes(&commitment, 3));
+    assert_eq!(row_indexes.len(), 3);
+    assert!(row_indexes.iter().all(|&index| index < 8));
+    assert_ne!(row_indexes[0], row_indexes[1]);
+    assert_ne!(row_indexes[0], row_indexes[2]);
+    assert_ne!(row_indexes[1], row_indexes[2]);
+
+    // The result has the rows of the table from the third one on
+    let res: OwnedTable<Curve25519Scalar> = sample
+        .to_owned_table(&ast.get_column_result_fields())
+        .unwrap();
+    let expected_res = owned_table([
+        bigint("a", row_indexes.iter().map(|&index| A[index + 2])),
+        varchar("b", row_indexes.iter().map(|&index| B[index + 2])),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_open_every_row_of_a_result_with_fewer_rows_than_samples() {
+    let accessor = accessor();
+    let ast = query_a_at_least(&accessor, 3);
+    let (commitment, sample) = prove_and_sample(&accessor, &ast, 20);
+    sample.verify(&commitment, 20).unwrap();
+    let mut row_indexes = sample.row_indexes();
+    row_indexes.sort_unstable();
+    assert_eq!(row_indexes, (0..8).collect::<Vec<_>>());
+
+    let ast = query_a_at_least(&accessor, 100);
+    let (commitment, sample) = prove_and_sample(&accessor, &ast, 3);
+    sample.verify(&commitment, 3).unwrap();
+    assert!(sample.row_indexes().is_empty());
+    let res: OwnedTable<Curve25519Scalar> = sample
+        .to_owned_table(&ast.get_column_result_fields())
+        .unwrap();
+    assert_eq!(res.num_rows(), 0);
+}
+
+#[test]
+fn we_cannot_verify_a_sample_of_other_rows() {
+    let accessor = accessor();
+    let ast = query_a_at_least(&accessor, 3);
+    let (commitment, sample) = prove_and_sample(&accessor, &ast, 3);
+    assert!(matches!(
+        sample.verify(&commitment, 2),
+        Err(SamplingError::SampleMismatch { .. })
+    ));
+
+    // Samples of a different result
+    let other_ast = query_a_at_least(&accessor, 2);
+    let (other_commitment, other_sample) = prove_and_sample(&accessor, &other_ast, 3);
+    assert_eq!(other_commitment.num_rows(), 9);
+    assert!(sample.verify(&other_commitment, 3).is_err());
+    assert!(other_sample.verify(&commitment, 3).is_err());
+}
+
+#[test]
+fn we_cannot_sample_rows_of_a_result_that_is_committed_in_pages() {
+    let accessor = accessor();
+    let ast = query_a_at_least(&accessor, 3);
+    let result = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    let (paginated_result, _) = PaginatedQueryResult::new(result, &ast, 2).unwrap();
+    let (_, sample) = prove_and_sample(&accessor, &ast, 3);
+    assert!(matches!(
+        sample.verify(&paginated_result.commitment, 3),
+        Err(SamplingError::NotCommittedByRow(2))
+    ));
+}
@@ -0,0 +1,180 @@
+use super::{Indexes, ProvableQueryResult, ProvableQueryResultLimits, QueryError};
+use crate::base::proof::ProverError;
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use thiserror::Error;
+
+/// A message of a [`ProvableQueryResult`] that is streamed by a [`ResultStreamEncoder`].
+///
+/// A stream consists of a [`ResultStreamMessage::Header`], the chunks of the first column, the
+/// chunks of the second column, and so on, followed by a [`ResultStreamMessage::End`]. The stream
+/// of a query over an empty table only consists of the [`ResultStreamMessage::End`], just like
+/// such a query has no result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResultStreamMessage {
+    /// The shape of the result, which is sent before any of its data
+    Header {
+        /// The number of columns of the result
+        num_columns: u64,
+        /// The indexes of the rows of the result
+        indexes: Indexes,
+    },
+    /// The encoded values of consecutive rows of a column
+    Chunk {
+        /// The index of the column
+        column: u64,
+        /// The encoded values
+        data: Vec<u8>,
+    },
+    /// The end of the stream
+    End,
+}
+
+/// Errors that can occur when decoding a streamed [`ProvableQueryResult`].
+#[derive(Error, Debug)]
+pub enum ResultStreamError {
+    /// A message arrived that the stream does not allow at its position.
+    #[error("unexpected message in the result stream: {0}")]
+    UnexpectedMessage(&'static str),
+    /// The stream ended before its [`ResultStreamMessage::End`].
+    #[error("the result stream ended early")]
+    Incomplete,
+    /// The result is larger than the limits allow.
+    #[error(transparent)]
+    QueryError(#[from] QueryError),
+}
+
+/// Streams the result of a query while it is proven, so that services can send the result while
+/// the proof is generated rather than after it.
+///
+/// Each column of the result is sent as soon as it is encoded, in chunks of at most `chunk_rows`
+/// rows. The messages go through a buffer of bounded capacity: once the receiver falls that far
+/// behind, the prover waits for it to catch up, so that the encoded result is never buffered in
+/// full.
+pub struct ResultStreamEncoder {
+    sender: SyncSender<ResultStreamMessage>,
+    chunk_rows: usize,
+}
+
+impl ResultStreamEncoder {
+    /// Create an encoder along with the receiver of its messages, which buffers at most
+    /// `capacity` messages.
+    pub fn new(chunk_rows: usize, capacity: usize) -> (Self, Receiver<ResultStreamMessage>) {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        let encoder = Self {
+            sender,
+            chunk_rows: chunk_rows.max(1),
+        };
+        (encoder, receiver)
+    }
+
+    /// Returns the largest number of rows in a chunk.
+    pub fn chunk_rows(&self) -> usize {
+        self.chunk_rows
+    }
+
+    /// Send a message, waiting for the receiver to make room for it if the buffer is full.
+    pub(super) fn send(&self, message: ResultStreamMessage) -> Result<(), ProverError> {
+        self.sender
+            .send(message)
+            .map_err(|_| ProverError::ResultStreamClosed)
+    }
+}
+
+/// Splits the indexes into consecutive chunks of at most `chunk_rows` rows.
+pub(super) fn chunk_indexes(indexes: &Indexes, chunk_rows: usize) -> Vec<Indexes> {
+    match indexes {
+        Indexes::Sparse(indexes) => indexes
+            .chunks(chunk_rows)
+            .map(|chunk| Indexes::Sparse(chunk.to_vec()))
+            .collect(),
+        Indexes::Dense(range) => (range.start..range.end)
+            .step_by(chunk_rows)
+            .map(|start| Indexes::Dense(start..range.end.min(start + chunk_rows as u64)))
+            .collect(),
+    }
+}
+
+/// Reassembles a [`ProvableQueryResult`] from the messages of a [`ResultStreamEncoder`].
+///
+/// The messages come from an untrusted prover, so the result is checked against the limits as it
+/// arrives. The reassembled result still has to be verified along with its proof, see
+/// [`crate::sql::proof::VerifiableQueryResult`].
+pub struct ResultStreamDecoder {
+    limits: ProvableQueryResultLimits,
+    result: Option<(u64, Indexes, Vec<u8>)>,
+    column: u64,
+    ended: bool,
+}
+
+impl ResultStreamDecoder {
+    /// Create a decoder that rejects results that claim more rows or data than the limits allow.
+    pub fn new(limits: ProvableQueryResultLimits) -> Self {
+        Self {
+            limits,
+            result: None,
+            column: 0,
+            ended: false,
+        }
+    }
+
+    /// Add the next message of the stream. Returns whether the stream is complete.
+    pub fn push(&mut self, message: ResultStreamMessage) -> Result<bool, ResultStreamError> {
+        if self.ended {
+            return Err(ResultStreamError::UnexpectedMessage(
+                "message after the end",
+            ));
+        }
+        match message {
+            ResultStreamMessage::Header {
+                num_columns,
+                indexes,
+            } => {
+                if self.result.is_some() {
+                    return Err(ResultStreamError::UnexpectedMessage("second header"));
+                }
+                let num_rows = indexes.len();
+                if num_rows > self.limits.max_rows() {
+                    return Err(QueryError::TooManyRows {
+                        num_rows,
+                        max_rows: self.limits.max_rows(),
+                    }
+                    .into());
+                }
+                self.result = Some((num_columns, indexes, Vec::new()));
+            }
+            ResultStreamMessage::Chunk { column, data } => {
+                let Some((num_columns, _, result_data)) = &mut self.result else {
+                    return Err(ResultStreamError::UnexpectedMessage(
+                        "chunk before the header",
+                    ));
+                };
+                if column < self.column || column >= *num_columns {
+                    return Err(ResultStreamError::UnexpectedMessage("chunk out of order"));
+                }
+                let num_bytes = result_data.len() + data.len();
+                if num_bytes > self.limits.max_bytes() {
+                    return Err(QueryError::TooManyBytes {
+                        num_bytes,
+                        max_bytes: self.limits.max_bytes(),
+                    }
+                    .into());
+                }
+                self.column = column;
+                result_data.extend_from_slice(&data);
+            }
+            ResultStreamMessage::End => self.ended = true,
+        }
+        Ok(self.ended)
+    }
+
+    /// Returns the reassembled result, or `None` if the stream is of a query over an empty table.
+    pub fn finish(self) -> Result<Option<ProvableQueryResult>, ResultStreamError> {
+        if !self.ended {
+            return Err(ResultStreamError::Incomplete);
+        }
+        Ok(self.result.map(|(num_columns, indexes, data)| {
+            ProvableQueryResult::new_from_raw_data(num_columns, indexes, data)
+        }))
+    }
+}
@@ -0,0 +1,201 @@
+use super::{
+    EvaluationContext, Indexes, ProvableQueryResultLimits, QueryError, ResultStreamDecoder,
+    ResultStreamEncoder, ResultStreamError, ResultStreamMessage, VerifiableQueryResult,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef},
+        proof::{ProverDeadline, ProverError},
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+use std::thread;
+
+fn accessor(t: TableRef, num_rows: i64) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", (0..num_rows).map(|i| i % 3)),
+            varchar("b", (0..num_rows).map(|i| format!("s{i}"))),
+        ]),
+        0,
+        (),
+    )
+}
+
+fn query(
+    t: TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> ProofPlan<RistrettoPoint> {
+    dense_filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        equal(column(t, "a", accessor), const_bigint(1)),
+    )
+}
+
+fn prove_streaming(
+    ast: &ProofPlan<RistrettoPoint>,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    encoder: &ResultStreamEncoder,
+) -> Result<VerifiableQueryResult<InnerProductProof>, ProverError> {
+    VerifiableQueryResult::new_streaming(
+        ast,
+        accessor,
+        &(),
+        EvaluationContext::new(),
+        encoder,
+        &ProverDeadline::default(),
+    )
+}
+
+#[test]
+fn we_can_verify_a_result_that_was_streamed_while_it_was_proven() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 20);
+    let ast = query(t, &accessor);
+    // The buffer is smaller than the result, so the prover has to wait for the receiver
+    let (encoder, receiver) = ResultStreamEncoder::new(2, 1);
+    let consumer = thread::spawn(move || {
+        let mut decoder = ResultStreamDecoder::new(ProvableQueryResultLimits::new());
+        let mut num_chunks = 0;
+        for message in receiver {
+            if matches!(message, ResultStreamMessage::Chunk { .. }) {
+                num_chunks += 1;
+            }
+            if decoder.push(message).unwrap() {
+                break;
+            }
+        }
+        (decoder.finish().unwrap(), num_chunks)
+    });
+    let verifiable_result = prove_streaming(&ast, &accessor, &encoder).unwrap();
+    let (streamed_result, num_chunks) = consumer.join().unwrap();
+
+    // 7 rows of 2 columns in chunks of 2 rows
+    assert_eq!(num_chunks, 8);
+    assert_eq!(
+        postcard::to_allocvec(&streamed_result).unwrap(),
+        postcard::to_allocvec(&verifiable_result.provable_result).unwrap()
+    );
+    let streamed = VerifiableQueryResult {
+        provable_result: streamed_result,
+        ..verifiable_result
+    };
+    let res = streamed.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(
+        res,
+        owned_table([
+            bigint("a", [1; 7]),
+            varchar("b", ["s1", "s4", "s7", "s10", "s13", "s16", "s19"]),
+        ])
+    );
+}
+
+#[test]
+fn a_query_over_an_empty_table_streams_no_result() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 0);
+    let ast = query(t, &accessor);
+    let (encoder, receiver) = ResultStreamEncoder::new(2, 4);
+    let verifiable_result = prove_streaming(&ast, &accessor, &encoder).unwrap();
+    assert!(verifiable_result.provable_result.is_none());
+
+    let mut decoder = ResultStreamDecoder::new(ProvableQueryResultLimits::new());
+    assert!(decoder.push(receiver.recv().unwrap()).unwrap());
+    assert!(decoder.finish().unwrap().is_none());
+}
+
+#[test]
+fn the_prover_stops_once_the_receiver_hangs_up() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 20);
+    let ast = query(t, &accessor);
+    let (encoder, receiver) = ResultStreamEncoder::new(2, 1);
+    drop(receiver);
+    assert!(matches!(
+        prove_streaming(&ast, &accessor, &encoder),
+        Err(ProverError::ResultStreamClosed)
+    ));
+}
+
+#[test]
+fn we_cannot_decode_a_stream_out_of_order() {
+    let header = ResultStreamMessage::Header {
+        num_columns: 2,
+        indexes: Indexes::Sparse(vec![0, 2]),
+    };
+    let chunk = |column| ResultStreamMessage::Chunk {
+        column,
+        data: vec![0; 4],
+    };
+
+    let mut decoder = ResultStreamDecoder::new(ProvableQueryResultLimits::new());
+    assert!(matches!(
+        decoder.push(chunk(0)),
+        Err(ResultStreamError::UnexpectedMessage(_))
+    ));
+
+    let mut decoder = ResultStreamDecoder::new(ProvableQueryResultLimits::new());
+    decoder.push(header.clone()).unwrap();
+    assert!(matches!(
+        decoder.push(header.clone()),
+        Err(ResultStreamError::UnexpectedMessage(_))
+    ));
+    decoder.push(chunk(1)).unwrap();
+    assert!(matches!(
+        decoder.push(chunk(0)),
+        Err(ResultStreamError::UnexpectedMessage(_))
+    ));
+    assert!(matches!(
+        decoder.push(chunk(2)),
+        Err(ResultStreamError::UnexpectedMessage(_))
+    ));
+
+    let mut decoder = ResultStreamDecoder::new(ProvableQueryResultLimits::new());
+    decoder.push(header).unwrap();
+    assert!(matches!(
+        decoder.finish(),
+        Err(ResultStreamError::Incomplete)
+    ));
+}
+
+#[test]
+fn we_cannot_decode_a_stream_that_exceeds_the_limits() {
+    let limits = ProvableQueryResultLimits::new()
+        .with_max_rows(2)
+        .with_max_bytes(6);
+    let mut decoder = ResultStreamDecoder::new(limits);
+    assert!(matches!(
+        decoder.push(ResultStreamMessage::Header {
+            num_columns: 1,
+            indexes: Indexes::Dense(0..3),
+        }),
+        Err(ResultStreamError::QueryError(QueryError::TooManyRows {
+            num_rows: 3,
+            max_rows: 2
+        }))
+    ));
+
+    let mut decoder = ResultStreamDecoder::new(limits);
+    decoder
+        .push(ResultStreamMessage::Header {
+            num_columns: 1,
+            indexes: Indexes::Dense(0..2),
+        })
+        .unwrap();
+    let chunk = ResultStreamMessage::Chunk {
+        column: 0,
+        data: vec![0; 4],
+    };
+    decoder.push(chunk.clone()).unwrap();
+    assert!(matches!(
+        decoder.push(chunk),
+        Err(ResultStreamError::QueryError(QueryError::TooManyBytes {
+            num_bytes: 8,
+            max_bytes: 6
+        }))
+    ));
+}
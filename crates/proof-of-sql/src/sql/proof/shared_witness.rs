@@ -0,0 +1,139 @@
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    database::{
+        AccessorResult, Column, ColumnRef, ColumnType, DataAccessor, MetadataAccessor, TableRef,
+    },
+    scalar::Scalar,
+};
+use indexmap::{map::Entry, IndexMap};
+use std::sync::{Mutex, MutexGuard};
+
+/// A [`DataAccessor`] that reads every column of the underlying accessor at most once, so that
+/// the proofs of several queries share the columns' MLEs.
+pub(crate) struct SharedColumnAccessor<'a, S: Scalar, A: DataAccessor<S>> {
+    accessor: &'a A,
+    columns: Mutex<IndexMap<ColumnRef, Column<'a, S>>>,
+}
+
+impl<'a, S: Scalar, A: DataAccessor<S>> SharedColumnAccessor<'a, S, A> {
+    pub fn new(accessor: &'a A) -> Self {
+        Self {
+            accessor,
+            columns: Mutex::new(IndexMap::new()),
+        }
+    }
+}
+
+impl<S: Scalar, A: DataAccessor<S>> MetadataAccessor for SharedColumnAccessor<'_, S, A> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.accessor.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.accessor.get_offset(table_ref)
+    }
+
+    fn try_get_length(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.accessor.try_get_length(table_ref)
+    }
+
+    fn try_get_offset(&self, table_ref: TableRef) -> AccessorResult<usize> {
+        self.accessor.try_get_offset(table_ref)
+    }
+}
+
+impl<S: Scalar, A: DataAccessor<S>> DataAccessor<S> for SharedColumnAccessor<'_, S, A> {
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        self.columns
+            .lock()
+            .expect("the shared columns should not be poisoned")
+            .entry(column)
+            .or_insert_with(|| self.accessor.get_column(column))
+            .clone()
+    }
+
+    fn try_get_column(&self, column: ColumnRef) -> AccessorResult<Column<S>> {
+        match self
+            .columns
+            .lock()
+            .expect("the shared columns should not be poisoned")
+            .entry(column)
+        {
+            Entry::Occupied(entry) => Ok(entry.get().clone()),
+            Entry::Vacant(entry) => Ok(entry.insert(self.accessor.try_get_column(column)?).clone()),
+        }
+    }
+}
+
+/// Commitments to intermediate MLEs that are shared across the proofs of several queries.
+///
+/// Commitments are keyed by the generator offset and a digest of the committed data, so a
+/// commitment is only reused for the exact same MLE.
+pub(crate) struct SharedCommitments<C: Commitment> {
+    commitments: Mutex<IndexMap<(usize, [u8; 32]), C>>,
+}
+
+impl<C: Commitment> SharedCommitments<C> {
+    pub fn new() -> Self {
+        Self {
+            commitments: Mutex::new(IndexMap::new()),
+        }
+    }
+
+    fn commitments(&self) -> MutexGuard<'_, IndexMap<(usize, [u8; 32]), C>> {
+        self.commitments
+            .lock()
+            .expect("the shared commitments should not be poisoned")
+    }
+
+    /// Returns the keys of the columns at the given offset along with their known commitments.
+    pub fn lookup(
+        &self,
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+    ) -> Vec<((usize, [u8; 32]), Option<C>)> {
+        let commitments = self.commitments();
+        committable_columns
+            .iter()
+            .map(|column| {
+                let key = (offset, committable_column_digest(column));
+                (key, commitments.get(&key).copied())
+            })
+            .collect()
+    }
+
+    /// Store the commitment for the key.
+    pub fn insert(&self, key: (usize, [u8; 32]), commitment: C) {
+        self.commitments().insert(key, commitment);
+    }
+}
+
+/// Returns the Blake3 digest of the type and data of the column.
+pub(crate) fn committable_column_digest(column: &CommittableColumn) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&postcard::to_allocvec(&ColumnType::from(column)).unwrap());
+    hasher.update(&(column.len() as u64).to_le_bytes());
+    match column {
+        CommittableColumn::Boolean(col) => {
+            hasher.update(&col.iter().map(|&b| b as u8).collect::<Vec<_>>());
+        }
+        CommittableColumn::SmallInt(col) => {
+            hasher.update(bytemuck::cast_slice(col));
+        }
+        CommittableColumn::Int(col) => {
+            hasher.update(bytemuck::cast_slice(col));
+        }
+        CommittableColumn::BigInt(col) | CommittableColumn::TimestampTZ(_, _, col) => {
+            hasher.update(bytemuck::cast_slice(col));
+        }
+        CommittableColumn::Int128(col) => {
+            hasher.update(bytemuck::cast_slice(col));
+        }
+        CommittableColumn::Decimal75(_, _, col)
+        | CommittableColumn::Scalar(col)
+        | CommittableColumn::VarChar(col) => {
+            hasher.update(bytemuck::cast_slice(col));
+        }
+    }
+    *hasher.finalize().as_bytes()
+}
@@ -0,0 +1,115 @@
+use super::{SharedColumnAccessor, SharedCommitments, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::{Commitment, CommittableColumn, InnerProductProof},
+        database::{
+            owned_table_utility::*, ColumnRef, ColumnType, DataAccessor, OwnedTable,
+            OwnedTableTestAccessor, TableRef,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+    utility::ident,
+};
+
+fn sample_accessor(t: TableRef) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 2, 3, 2]),
+        varchar("b", ["x", "y", "z", "y"]),
+    ]);
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ())
+}
+
+#[test]
+fn we_can_prove_many_queries_with_the_same_proofs_as_one_at_a_time() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let asts = [
+        dense_filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            equal(column(t, "b", &accessor), const_varchar("y")),
+        ),
+        dense_filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(column(t, "b", &accessor), const_varchar("y")),
+        ),
+        dense_filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(2)),
+        ),
+    ];
+    let verifiable_results =
+        VerifiableQueryResult::<InnerProductProof>::prove_many(&asts, &accessor, &());
+    assert_eq!(verifiable_results.len(), asts.len());
+    for (ast, verifiable_res) in asts.iter().zip(&verifiable_results) {
+        let expected = VerifiableQueryResult::<InnerProductProof>::new(ast, &accessor, &());
+        assert_eq!(
+            postcard::to_allocvec(verifiable_res).unwrap(),
+            postcard::to_allocvec(&expected).unwrap()
+        );
+    }
+    let expected_results = [
+        owned_table([bigint("a", [2, 2]), varchar("b", ["y", "y"])]),
+        owned_table([bigint("a", [2, 2])]),
+        owned_table([varchar("b", ["y", "z", "y"])]),
+    ];
+    for ((ast, verifiable_res), expected_res) in
+        asts.iter().zip(&verifiable_results).zip(expected_results)
+    {
+        let res = verifiable_res.verify(ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_prove_no_queries() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let asts: [ProofPlan<RistrettoPoint>; 0] = [];
+    let verifiable_results =
+        VerifiableQueryResult::<InnerProductProof>::prove_many(&asts, &accessor, &());
+    assert!(verifiable_results.is_empty());
+}
+
+#[test]
+fn we_can_share_the_columns_of_an_accessor() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    let shared_accessor = SharedColumnAccessor::<Curve25519Scalar, _>::new(&accessor);
+    let column = ColumnRef::new(t, ident("b"), ColumnType::VarChar);
+    assert_eq!(
+        shared_accessor.get_column(column),
+        accessor.get_column(column)
+    );
+    assert_eq!(
+        shared_accessor.get_column(column),
+        shared_accessor.get_column(column)
+    );
+}
+
+#[test]
+fn we_only_share_commitments_to_the_same_data_at_the_same_offset() {
+    let shared_commitments = SharedCommitments::<RistrettoPoint>::new();
+    let columns = [
+        CommittableColumn::BigInt(&[1, 2, 3]),
+        CommittableColumn::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &[1, 2, 3]),
+    ];
+    let lookup = shared_commitments.lookup(&columns, 0);
+    assert_ne!(lookup[0].0, lookup[1].0);
+    assert!(lookup.iter().all(|(_, commitment)| commitment.is_none()));
+
+    let mut commitments = [RistrettoPoint::default(); 2];
+    RistrettoPoint::compute_commitments(&mut commitments, &columns, 0, &());
+    shared_commitments.insert(lookup[0].0, commitments[0]);
+    let lookup = shared_commitments.lookup(&columns, 0);
+    assert_eq!(lookup[0].1, Some(commitments[0]));
+    assert_eq!(lookup[1].1, None);
+    assert_eq!(shared_commitments.lookup(&columns[..1], 1)[0].1, None);
+}
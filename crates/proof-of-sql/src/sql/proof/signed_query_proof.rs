@@ -0,0 +1,164 @@
+use super::{ProofExpr, QueryData, QueryError, VerifiableQueryResult};
+use crate::base::{commitment::CommitmentEvaluationProof, database::CommitmentAccessor};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The domain separator that is prepended to the envelope before it is signed.
+const ENVELOPE_DOMAIN: &[u8] = b"proof-of-sql signed query proof v1";
+
+/// Errors that can occur when verifying a [`SignedQueryProof`].
+#[derive(Error, Debug)]
+pub enum SignedProofError {
+    /// The envelope does not describe the query, commitments or result that were given.
+    #[error("the proof envelope does not match the query, commitments or result")]
+    EnvelopeMismatch,
+    /// The signature is not a valid signature of the envelope by the signer.
+    #[error("the prover signature of the proof envelope is invalid")]
+    InvalidSignature,
+    /// The signature is valid, but the result failed to verify.
+    #[error(transparent)]
+    QueryError(#[from] QueryError),
+}
+
+/// A summary of a proof, which is what a prover signs to attest to the proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofEnvelope {
+    query_hash: [u8; 32],
+    commitments_fingerprint: [u8; 32],
+    result_hash: [u8; 32],
+}
+
+impl ProofEnvelope {
+    /// Create the envelope of a proof of the query against the commitments in the accessor.
+    pub fn new<CP: CommitmentEvaluationProof>(
+        result: &VerifiableQueryResult<CP>,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+    ) -> Self {
+        let query_hash = blake3::hash(
+            &postcard::to_allocvec(expr).expect("proof expressions are always serializable"),
+        );
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&(expr.get_length(accessor) as u64).to_le_bytes());
+        hasher.update(&(expr.get_offset(accessor) as u64).to_le_bytes());
+        for column in expr.get_column_references() {
+            let commitment = accessor.get_commitment(column);
+            hasher.update(
+                &postcard::to_allocvec(&commitment).expect("commitments are always serializable"),
+            );
+        }
+        let commitments_fingerprint = hasher.finalize();
+
+        let result_hash = blake3::hash(
+            &postcard::to_allocvec(result).expect("query results are always serializable"),
+        );
+        Self {
+            query_hash: *query_hash.as_bytes(),
+            commitments_fingerprint: *commitments_fingerprint.as_bytes(),
+            result_hash: *result_hash.as_bytes(),
+        }
+    }
+
+    /// Returns the Blake3 hash of the query.
+    pub fn query_hash(&self) -> [u8; 32] {
+        self.query_hash
+    }
+
+    /// Returns the Blake3 hash of the table range and the commitments to the columns that the
+    /// query references.
+    pub fn commitments_fingerprint(&self) -> [u8; 32] {
+        self.commitments_fingerprint
+    }
+
+    /// Returns the Blake3 hash of the result, along with its proof.
+    pub fn result_hash(&self) -> [u8; 32] {
+        self.result_hash
+    }
+
+    /// Returns the message that is signed.
+    fn message(&self) -> Vec<u8> {
+        [
+            ENVELOPE_DOMAIN,
+            &self.query_hash,
+            &self.commitments_fingerprint,
+            &self.result_hash,
+        ]
+        .concat()
+    }
+}
+
+/// A [`VerifiableQueryResult`] that is signed by the prover that created it.
+///
+/// The signature attests that the prover created this exact proof for the query against the
+/// given commitments, so a proof can be attributed to the operator that holds the signing key.
+/// Note that the signature says nothing about whether the result is correct, which is checked
+/// by the proof as usual.
+///
+/// Note: Because the struct is deserialized from untrusted data, it cannot maintain any invariant
+/// on its data members; hence, they are all public so as to allow for easy manipulation for
+/// testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SignedQueryProof<CP: CommitmentEvaluationProof> {
+    /// The result and its proof.
+    pub result: VerifiableQueryResult<CP>,
+    /// The envelope that was signed.
+    pub envelope: ProofEnvelope,
+    /// The public key of the prover.
+    pub signer: VerifyingKey,
+    /// The signature of the envelope by the prover.
+    pub signature: Signature,
+}
+
+impl<CP: CommitmentEvaluationProof> SignedQueryProof<CP> {
+    /// Sign the result of the query with the key of the prover.
+    pub fn sign(
+        result: VerifiableQueryResult<CP>,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let envelope = ProofEnvelope::new(&result, expr, accessor);
+        let signature = signing_key.sign(&envelope.message());
+        Self {
+            result,
+            envelope,
+            signer: signing_key.verifying_key(),
+            signature,
+        }
+    }
+
+    /// Check that the envelope describes the query, commitments and result, and that it was
+    /// signed by [`SignedQueryProof::signer`].
+    ///
+    /// This does not verify the result.
+    pub fn verify_signature(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+    ) -> Result<(), SignedProofError> {
+        if ProofEnvelope::new(&self.result, expr, accessor) != self.envelope {
+            return Err(SignedProofError::EnvelopeMismatch);
+        }
+        self.signer
+            .verify_strict(&self.envelope.message(), &self.signature)
+            .map_err(|_| SignedProofError::InvalidSignature)
+    }
+
+    /// Verify the signature and then the result.
+    ///
+    /// Upon success, the result was signed by [`SignedQueryProof::signer`] and this function
+    /// returns the finalized form of the query result.
+    ///
+    /// Note: This does NOT transform the result!
+    pub fn verify(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<QueryData<CP::Scalar>, SignedProofError> {
+        self.verify_signature(expr, accessor)?;
+        Ok(self.result.verify(expr, accessor, setup)?)
+    }
+}
@@ -0,0 +1,121 @@
+use super::{SignedProofError, SignedQueryProof, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+use ed25519_dalek::SigningKey;
+
+fn accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            varchar("b", ["x", "y", "z", "w"]),
+        ]),
+        0,
+        (),
+    )
+}
+
+fn query(
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    value: i64,
+) -> ProofPlan<RistrettoPoint> {
+    let t = "sxt.t".parse().unwrap();
+    dense_filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        gte(column(t, "a", accessor), const_bigint(value)),
+    )
+}
+
+fn signed_proof(
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+    ast: &ProofPlan<RistrettoPoint>,
+    signing_key: &SigningKey,
+) -> SignedQueryProof<InnerProductProof> {
+    let result = VerifiableQueryResult::<InnerProductProof>::new(ast, accessor, &());
+    SignedQueryProof::sign(result, ast, accessor, signing_key)
+}
+
+#[test]
+fn we_can_verify_a_signed_proof_and_attribute_it_to_its_prover() {
+    let accessor = accessor();
+    let ast = query(&accessor, 3);
+    let signing_key = SigningKey::from_bytes(&[1; 32]);
+    let proof = signed_proof(&accessor, &ast, &signing_key);
+    assert_eq!(proof.signer, signing_key.verifying_key());
+
+    let res = proof.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [3, 4]), varchar("b", ["z", "w"])]);
+    assert_eq!(res, expected_res);
+
+    let serialized = postcard::to_allocvec(&proof).unwrap();
+    let deserialized: SignedQueryProof<InnerProductProof> =
+        postcard::from_bytes(&serialized).unwrap();
+    assert_eq!(deserialized.envelope, proof.envelope);
+    deserialized.verify_signature(&ast, &accessor).unwrap();
+}
+
+#[test]
+fn we_cannot_verify_a_proof_with_a_signature_by_another_prover() {
+    let accessor = accessor();
+    let ast = query(&accessor, 3);
+    let mut proof = signed_proof(&accessor, &ast, &SigningKey::from_bytes(&[1; 32]));
+    proof.signer = SigningKey::from_bytes(&[2; 32]).verifying_key();
+    assert!(matches!(
+        proof.verify(&ast, &accessor, &()),
+        Err(SignedProofError::InvalidSignature)
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_signed_proof_against_another_query_or_table() {
+    let accessor = accessor();
+    let ast = query(&accessor, 3);
+    let proof = signed_proof(&accessor, &ast, &SigningKey::from_bytes(&[1; 32]));
+    assert!(matches!(
+        proof.verify_signature(&query(&accessor, 2), &accessor),
+        Err(SignedProofError::EnvelopeMismatch)
+    ));
+
+    let mut other_accessor = accessor.clone();
+    other_accessor.add_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 5]),
+            varchar("b", ["x", "y", "z", "w"]),
+        ]),
+        0,
+    );
+    assert!(matches!(
+        proof.verify_signature(&ast, &other_accessor),
+        Err(SignedProofError::EnvelopeMismatch)
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_signed_proof_whose_result_was_replaced() {
+    let accessor = accessor();
+    let ast = query(&accessor, 3);
+    let signing_key = SigningKey::from_bytes(&[1; 32]);
+    let mut proof = signed_proof(&accessor, &ast, &signing_key);
+    let other_ast = query(&accessor, 2);
+    proof.result = VerifiableQueryResult::new(&other_ast, &accessor, &());
+    assert!(matches!(
+        proof.verify_signature(&ast, &accessor),
+        Err(SignedProofError::EnvelopeMismatch)
+    ));
+
+    // A prover can sign a result that does not verify, but the proof still rejects it
+    let proof = SignedQueryProof::sign(proof.result, &ast, &accessor, &signing_key);
+    proof.verify_signature(&ast, &accessor).unwrap();
+    assert!(matches!(
+        proof.verify(&ast, &accessor, &()),
+        Err(SignedProofError::QueryError(_))
+    ));
+}
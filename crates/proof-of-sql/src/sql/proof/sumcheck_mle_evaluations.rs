@@ -1,7 +1,8 @@
 use super::{Indexes, SumcheckRandomScalars};
 use crate::base::{
     polynomial::{
-        compute_truncated_lagrange_basis_inner_product, compute_truncated_lagrange_basis_sum,
+        compute_truncated_lagrange_basis_index_sum, compute_truncated_lagrange_basis_inner_product,
+        compute_truncated_lagrange_basis_sum,
     },
     scalar::Scalar,
 };
@@ -17,6 +18,10 @@ pub struct SumcheckMleEvaluations<'a, S: Scalar> {
     ///     x_i = 1, if i < table_length;
     ///         = 0, otherwise
     pub one_evaluation: S,
+    /// The evaluation (at the random point generated by sumcheck) of an MLE {x_i} where
+    ///     x_i = i, if i < table_length;
+    ///         = 0, otherwise
+    pub row_index_evaluation: S,
 
     /// The evaluation (at the random point generated by sumcheck) of the MLE that is 1 at the result indexes and 0 elsewhere.
     /// This is only computed if the result indexes are dense, and is None otherwise.
@@ -61,6 +66,8 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             sumcheck_random_scalars.entrywise_point,
         );
         let one_evaluation = compute_truncated_lagrange_basis_sum(table_length, evaluation_point);
+        let row_index_evaluation =
+            compute_truncated_lagrange_basis_index_sum(table_length, evaluation_point);
 
         let result_indexes_evaluation = result_indexes.evaluate_at_point(evaluation_point);
 
@@ -68,6 +75,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             table_length,
             num_sumcheck_variables: evaluation_point.len(),
             one_evaluation,
+            row_index_evaluation,
             random_evaluation,
             pcs_proof_evaluations,
             result_evaluations,
@@ -41,6 +41,12 @@ fn we_can_track_the_evaluation_of_mles_used_within_sumcheck() {
         + (evaluation_point[0]) * (Curve25519Scalar::one() - evaluation_point[1])
         + (Curve25519Scalar::one() - evaluation_point[0]) * (evaluation_point[1]);
     assert_eq!(evals.one_evaluation, expected_eval);
+
+    let expected_eval = (evaluation_point[0]) * (Curve25519Scalar::one() - evaluation_point[1])
+        + Curve25519Scalar::from(2u64)
+            * (Curve25519Scalar::one() - evaluation_point[0])
+            * (evaluation_point[1]);
+    assert_eq!(evals.row_index_evaluation, expected_eval);
     // Because the Indexes are sparse, this should not be evaluated.
     assert_eq!(evals.result_indexes_evaluation, None);
 }
@@ -38,6 +38,16 @@ impl<'a, S: Scalar> SumcheckSubpolynomial<'a, S> {
         }
     }
 
+    /// Returns the terms of the subpolynomial
+    pub fn terms(&self) -> &[SumcheckSubpolynomialTerm<'a, S>] {
+        &self.terms
+    }
+
+    /// Returns the type of the subpolynomial
+    pub fn subpolynomial_type(&self) -> &SumcheckSubpolynomialType {
+        &self.subpolynomial_type
+    }
+
     /// Combine the subpolynomial into a combined composite polynomial
     pub fn compose(
         &self,
@@ -0,0 +1,180 @@
+use super::{
+    verifiable_query_result::{check_column_references, make_empty_query_result},
+    EvaluationContext, ProofExpr, ProvableQueryResult, ProvableQueryResultLimits, QueryData,
+    QueryError, QueryProof, SharedColumnAccessor,
+};
+use crate::base::{
+    commitment::{Commitment, CommitmentEvaluationProof},
+    database::{AccessorError, CommitmentAccessor, DataAccessor, MetadataAccessor},
+    proof::{ProofError, ProverDeadline},
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when proving or verifying a batch of queries.
+#[derive(Debug, Error)]
+pub enum BatchQueryError {
+    /// A batch needs at least one query.
+    #[error("a batch needs at least one query")]
+    EmptyBatch,
+    /// The queries of a batch have to be over the same rows.
+    #[error("the queries of a batch have to be over the same rows")]
+    MismatchedRows,
+    /// A query of the batch references a table or column that the prover doesn't have.
+    #[error(transparent)]
+    AccessorError(#[from] AccessorError),
+    /// A query of the batch failed to verify.
+    #[error(transparent)]
+    QueryError(#[from] QueryError),
+}
+
+/// The results of a batch of sql queries along with a single proof that all of them are valid.
+///
+/// Dashboards typically issue many small queries against the same table. Proving them as a batch
+/// shares the transcript, the sumcheck and the opening of the commitments between the queries, so
+/// the proof of the batch is about as large and as quick to verify as the proof of a single query.
+/// The queries have to be over the same rows, i.e. the same snapshot of the same table.
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct VerifiableBatchResult<CP: CommitmentEvaluationProof> {
+    /// The results of the queries in intermediate form, in the order of the queries.
+    pub provable_results: Vec<ProvableQueryResult>,
+    /// The proof that the query results are valid.
+    pub proof: Option<QueryProof<CP>>,
+    /// The values the prover bound to the queries' context variables, such as `NOW()`.
+    ///
+    /// The verifier should check these against its own policy before trusting the results.
+    #[serde(default)]
+    pub context: EvaluationContext,
+}
+
+/// Checks that the batch is not empty and that all of its queries are over the same rows.
+fn check_batch<C: Commitment>(
+    exprs: &[impl ProofExpr<C>],
+    accessor: &impl MetadataAccessor,
+) -> Result<(), BatchQueryError> {
+    let (first, rest) = exprs.split_first().ok_or(BatchQueryError::EmptyBatch)?;
+    let rows = (first.get_length(accessor), first.get_offset(accessor));
+    if rest
+        .iter()
+        .all(|expr| (expr.get_length(accessor), expr.get_offset(accessor)) == rows)
+    {
+        Ok(())
+    } else {
+        Err(BatchQueryError::MismatchedRows)
+    }
+}
+
+impl<CP: CommitmentEvaluationProof> VerifiableBatchResult<CP> {
+    /// Form a `VerifiableBatchResult` from the query expressions of a batch.
+    ///
+    /// This function both computes the results of the queries and constructs a single proof of
+    /// their validity.
+    pub fn new<E: ProofExpr<CP::Commitment> + Serialize>(
+        exprs: &[E],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Result<Self, BatchQueryError> {
+        Self::new_with_context(exprs, accessor, setup, EvaluationContext::default())
+    }
+
+    /// Form a `VerifiableBatchResult` from the query expressions of a batch that were planned
+    /// with the given evaluation context.
+    ///
+    /// The context is bound into the proof and returned to the verifier along with the results.
+    pub fn new_with_context<E: ProofExpr<CP::Commitment> + Serialize>(
+        exprs: &[E],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+    ) -> Result<Self, BatchQueryError> {
+        let accessor = &SharedColumnAccessor::new(accessor);
+        for expr in exprs {
+            check_column_references(expr, accessor)?;
+        }
+        check_batch(exprs, accessor)?;
+
+        // handle the empty case
+        if exprs[0].is_empty(accessor) {
+            return Ok(VerifiableBatchResult {
+                provable_results: Vec::new(),
+                proof: None,
+                context,
+            });
+        }
+
+        let deadline = ProverDeadline::default();
+        let (proof, results) =
+            QueryProof::new_batch_impl(exprs, accessor, setup, &context, &deadline)
+                .expect("the default deadline never expires");
+        Ok(Self {
+            provable_results: results,
+            proof: Some(proof),
+            context,
+        })
+    }
+
+    /// Verify a `VerifiableBatchResult`. Upon success, this function returns the finalized form
+    /// of the result of each query, in the order of the queries.
+    ///
+    /// The results share a single verification hash.
+    ///
+    /// Note: This does NOT transform the results!
+    pub fn verify<E: ProofExpr<CP::Commitment> + Serialize>(
+        &self,
+        exprs: &[E],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, BatchQueryError> {
+        self.verify_with_limits(
+            exprs,
+            accessor,
+            setup,
+            &ProvableQueryResultLimits::default(),
+        )
+    }
+
+    /// Verify a `VerifiableBatchResult`, rejecting a result that claims more rows or data than
+    /// the given limits allow before it is decoded. The limits apply to each result on its own.
+    ///
+    /// Note: This does NOT transform the results!
+    pub fn verify_with_limits<E: ProofExpr<CP::Commitment> + Serialize>(
+        &self,
+        exprs: &[E],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        limits: &ProvableQueryResultLimits,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, BatchQueryError> {
+        check_batch(exprs, accessor)?;
+
+        // handle the empty case
+        if exprs[0].is_empty(accessor) {
+            if !self.provable_results.is_empty() || self.proof.is_some() {
+                return Err(QueryError::from(ProofError::VerificationError(
+                    "zero sumcheck variables but non-empty result",
+                )))?;
+            }
+            return Ok(exprs
+                .iter()
+                .map(|expr| make_empty_query_result(expr.get_column_result_fields()))
+                .collect::<Result<_, _>>()?);
+        }
+
+        let Some(proof) = &self.proof else {
+            return Err(QueryError::from(ProofError::VerificationError(
+                "non-zero sumcheck variables but empty result",
+            )))?;
+        };
+        Ok(proof.verify_batch_impl(
+            exprs,
+            accessor,
+            &self.provable_results,
+            setup,
+            &self.context,
+            limits,
+        )?)
+    }
+}
@@ -0,0 +1,170 @@
+use super::{BatchQueryError, VerifiableBatchResult, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, AccessorError, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn accessor(t: TableRef, num_rows: i64) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", (0..num_rows).map(|i| i % 3)),
+            bigint("b", 0..num_rows),
+            varchar("c", (0..num_rows).map(|i| format!("s{i}"))),
+        ]),
+        0,
+        (),
+    )
+}
+
+/// A dashboard's worth of queries, whose proofs need result indexes, post-result challenges or
+/// neither.
+fn queries(
+    t: TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Vec<ProofPlan<RistrettoPoint>> {
+    vec![
+        filter(
+            cols_result(t, &["b", "c"], accessor),
+            tab(t),
+            equal(column(t, "a", accessor), const_bigint(1)),
+        ),
+        dense_filter(
+            cols_expr_plan(t, &["c"], accessor),
+            tab(t),
+            equal(column(t, "a", accessor), const_bigint(2)),
+        ),
+        group_by(
+            cols_expr(t, &["a"], accessor),
+            vec![sum_expr(column(t, "b", accessor), "sum_b")],
+            "__count__",
+            tab(t),
+            const_bool(true),
+        ),
+        projection(cols_expr_plan(t, &["a"], accessor), tab(t)),
+    ]
+}
+
+#[test]
+fn we_can_verify_a_batch_of_queries_with_a_single_proof() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 10);
+    let exprs = queries(t, &accessor);
+    let batch_result =
+        VerifiableBatchResult::<InnerProductProof>::new(&exprs, &accessor, &()).unwrap();
+    assert_eq!(batch_result.provable_results.len(), exprs.len());
+    let query_data = batch_result.verify(&exprs, &accessor, &()).unwrap();
+    assert_eq!(query_data.len(), exprs.len());
+
+    // Each result is the same as the result of proving its query on its own
+    for (expr, data) in exprs.iter().zip(&query_data) {
+        let expected = VerifiableQueryResult::<InnerProductProof>::new(expr, &accessor, &())
+            .verify(expr, &accessor, &())
+            .unwrap();
+        assert_eq!(data.table, expected.table);
+        assert_eq!(data.selected_rows, expected.selected_rows);
+        assert_eq!(data.verification_hash, query_data[0].verification_hash);
+    }
+    assert_eq!(
+        query_data[0].table,
+        owned_table([bigint("b", [1, 4, 7]), varchar("c", ["s1", "s4", "s7"])])
+    );
+}
+
+#[test]
+fn we_can_verify_a_batch_of_queries_on_an_empty_table() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 0);
+    let exprs = queries(t, &accessor);
+    let batch_result =
+        VerifiableBatchResult::<InnerProductProof>::new(&exprs, &accessor, &()).unwrap();
+    assert!(batch_result.provable_results.is_empty());
+    assert!(batch_result.proof.is_none());
+    let query_data = batch_result.verify(&exprs, &accessor, &()).unwrap();
+    assert_eq!(query_data.len(), exprs.len());
+    assert_eq!(
+        query_data[0].table,
+        owned_table([bigint("b", [0; 0]), varchar("c", [""; 0])])
+    );
+}
+
+#[test]
+fn we_cannot_batch_no_queries_or_queries_over_different_rows() {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let mut accessor = accessor(t, 10);
+    accessor.add_table(u, owned_table([bigint("a", [1, 2, 3])]), 0);
+    let no_exprs: [ProofPlan<RistrettoPoint>; 0] = [];
+    assert!(matches!(
+        VerifiableBatchResult::<InnerProductProof>::new(&no_exprs, &accessor, &()),
+        Err(BatchQueryError::EmptyBatch)
+    ));
+
+    let exprs = [
+        projection(cols_expr_plan(t, &["a"], &accessor), tab(t)),
+        projection(cols_expr_plan(u, &["a"], &accessor), tab(u)),
+    ];
+    assert!(matches!(
+        VerifiableBatchResult::<InnerProductProof>::new(&exprs, &accessor, &()),
+        Err(BatchQueryError::MismatchedRows)
+    ));
+    let batch_result =
+        VerifiableBatchResult::<InnerProductProof>::new(&exprs[..1], &accessor, &()).unwrap();
+    assert!(matches!(
+        batch_result.verify(&exprs, &accessor, &()),
+        Err(BatchQueryError::MismatchedRows)
+    ));
+}
+
+#[test]
+fn we_cannot_batch_queries_that_reference_missing_columns() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 10);
+    let mut schema_accessor = accessor.clone();
+    schema_accessor.add_table(t, owned_table([bigint("a", 0..10), bigint("d", 0..10)]), 0);
+    let exprs = [
+        projection(cols_expr_plan(t, &["a"], &accessor), tab(t)),
+        projection(cols_expr_plan(t, &["d"], &schema_accessor), tab(t)),
+    ];
+    assert!(matches!(
+        VerifiableBatchResult::<InnerProductProof>::new(&exprs, &accessor, &()),
+        Err(BatchQueryError::AccessorError(
+            AccessorError::ColumnNotFound { .. }
+        ))
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_batch_whose_results_were_tampered_with() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 10);
+    let exprs = queries(t, &accessor);
+    let batch_result =
+        VerifiableBatchResult::<InnerProductProof>::new(&exprs, &accessor, &()).unwrap();
+
+    // The results are bound to their queries, so they cannot be reordered
+    let mut reordered = batch_result.clone();
+    reordered.provable_results.swap(0, 1);
+    assert!(reordered.verify(&exprs, &accessor, &()).is_err());
+
+    // Every result is checked, not just the first one
+    let mut tampered = batch_result.clone();
+    let result = tampered.provable_results.last_mut().unwrap();
+    let last_byte = result.data_mut().last_mut().unwrap();
+    *last_byte = last_byte.wrapping_add(1);
+    assert!(tampered.verify(&exprs, &accessor, &()).is_err());
+
+    // A result cannot be dropped from the batch, nor a query
+    let mut dropped = batch_result.clone();
+    dropped.provable_results.pop();
+    assert!(dropped.verify(&exprs, &accessor, &()).is_err());
+    assert!(batch_result
+        .verify(&exprs[..exprs.len() - 1], &accessor, &())
+        .is_err());
+}
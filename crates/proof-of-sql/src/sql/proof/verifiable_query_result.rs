@@ -1,12 +1,19 @@
-use super::{ProofExpr, ProvableQueryResult, QueryData, QueryProof, QueryResult};
+use super::{
+    EvaluationContext, ProofExpr, ProofVersion, ProvableQueryResult, ProvableQueryResultLimits,
+    ProverCache, ProverCheckpoint, ProverCheckpointError, QueryData, QueryError, QueryProof,
+    QueryResult, ResultStreamEncoder, ResultStreamMessage, SharedColumnAccessor, SharedCommitments,
+    VerifiedRows,
+};
 use crate::base::{
-    commitment::CommitmentEvaluationProof,
+    commitment::{Commitment, CommitmentEvaluationProof},
     database::{
-        ColumnField, ColumnType, CommitmentAccessor, DataAccessor, OwnedColumn, OwnedTable,
+        AccessorError, ColumnField, ColumnType, CommitmentAccessor, DataAccessor, OwnedColumn,
+        OwnedTable,
     },
-    proof::ProofError,
+    proof::{ProofError, ProverDeadline, ProverError},
     scalar::Scalar,
 };
+use proof_of_sql_parser::Identifier;
 use serde::{Deserialize, Serialize};
 
 /// The result of an sql query along with a proof that the query is valid. The
@@ -71,6 +78,15 @@ pub struct VerifiableQueryResult<CP: CommitmentEvaluationProof> {
     pub provable_result: Option<ProvableQueryResult>,
     /// The proof that the query result is valid.
     pub proof: Option<QueryProof<CP>>,
+    /// The values the prover bound to the query's context variables, such as `NOW()`.
+    ///
+    /// The verifier should check these against its own policy before trusting the result.
+    #[serde(default)]
+    pub context: EvaluationContext,
+    /// The version of the prover that created the proof, which the verifier checks before
+    /// verifying anything else.
+    #[serde(default)]
+    pub version: ProofVersion,
 }
 
 impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
@@ -78,27 +94,255 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
     ///
     /// This function both computes the result of a query and constructs a proof of the results
     /// validity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query references a table or column that the accessor doesn't have. Use
+    /// [`VerifiableQueryResult::new_with_deadline`] to get an error instead.
     pub fn new(
         expr: &(impl ProofExpr<CP::Commitment> + Serialize),
         accessor: &impl DataAccessor<CP::Scalar>,
         setup: &CP::ProverPublicSetup<'_>,
     ) -> Self {
+        Self::new_with_context(expr, accessor, setup, EvaluationContext::default())
+    }
+
+    /// Form a `VerifiableQueryResult` from a query expression that was planned with the given
+    /// evaluation context.
+    ///
+    /// The context is bound into the proof and returned to the verifier along with the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query references a table or column that the accessor doesn't have.
+    pub fn new_with_context(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+    ) -> Self {
+        Self::new_impl(
+            expr,
+            accessor,
+            setup,
+            context,
+            None,
+            None,
+            &ProverDeadline::default(),
+        )
+        .expect("the query should only reference existing columns")
+    }
+
+    /// Form a `VerifiableQueryResult` from a query expression that was planned with the given
+    /// evaluation context, giving up with a [`ProverError`] once the deadline expires.
+    ///
+    /// This protects the prover from running indefinitely on queries that are unexpectedly
+    /// expensive to prove.
+    pub fn new_with_deadline(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+        deadline: &ProverDeadline,
+    ) -> Result<Self, ProverError> {
+        Self::new_impl(expr, accessor, setup, context, None, None, deadline)
+    }
+
+    /// Form a `VerifiableQueryResult` from a query expression, reusing intermediate MLEs that
+    /// the prover derived for earlier queries.
+    ///
+    /// See [`ProverCache`] for which tables are cached.
+    pub fn new_with_prover_cache(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+        prover_cache: &ProverCache<CP::Scalar>,
+    ) -> Self {
+        Self::new_impl(
+            expr,
+            accessor,
+            setup,
+            context,
+            Some(prover_cache),
+            None,
+            &ProverDeadline::default(),
+        )
+        .expect("the query should only reference existing columns")
+    }
+
+    /// Form a `VerifiableQueryResult` from a query expression, streaming the result through the
+    /// encoder while the proof is generated.
+    ///
+    /// The result is sent before the prover commits to its intermediate MLEs, so that a service
+    /// can transmit it while the rest of the proof is generated. The streamed result can be
+    /// reassembled with a [`super::ResultStreamDecoder`] and is the same as the `provable_result`
+    /// of the returned `VerifiableQueryResult`. Fails with [`ProverError::ResultStreamClosed`] if
+    /// the receiver of the stream hangs up.
+    pub fn new_streaming(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+        result_stream: &ResultStreamEncoder,
+        deadline: &ProverDeadline,
+    ) -> Result<Self, ProverError> {
+        let accessor = &SharedColumnAccessor::new(accessor);
+        check_column_references(expr, accessor)?;
+        if expr.is_empty(accessor) {
+            result_stream.send(ResultStreamMessage::End)?;
+            return Ok(VerifiableQueryResult {
+                provable_result: None,
+                proof: None,
+                context,
+                version: ProofVersion::current(),
+            });
+        }
+        let (proof, res) =
+            QueryProof::streaming_impl(expr, accessor, setup, &context, result_stream, deadline)?;
+        Ok(Self {
+            provable_result: Some(res),
+            proof: Some(proof),
+            context,
+            version: ProofVersion::current(),
+        })
+    }
+
+    /// Run the prover for a query expression up to and including its commitments to the
+    /// intermediate MLEs, giving up with a [`ProverError`] once the deadline expires.
+    ///
+    /// The returned checkpoint can be persisted and turned into a `VerifiableQueryResult` later
+    /// with [`VerifiableQueryResult::resume_from_checkpoint`].
+    pub fn new_checkpoint(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+        deadline: &ProverDeadline,
+    ) -> Result<ProverCheckpoint<CP::Commitment>, ProverError> {
+        let accessor = &SharedColumnAccessor::new(accessor);
+        check_column_references(expr, accessor)?;
+        if expr.is_empty(accessor) {
+            return Ok(ProverCheckpoint::new_empty(context));
+        }
+        QueryProof::checkpoint_impl(expr, accessor, setup, context, deadline)
+    }
+
+    /// Finish a `VerifiableQueryResult` from a checkpoint taken by
+    /// [`VerifiableQueryResult::new_checkpoint`].
+    ///
+    /// The query expression and accessor have to be the same as the ones the checkpoint was taken
+    /// with, which is checked against the checkpoint. The result is the same as the one created by
+    /// [`VerifiableQueryResult::new_with_context`] with the context of the checkpoint.
+    pub fn resume_from_checkpoint(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        checkpoint: &ProverCheckpoint<CP::Commitment>,
+        deadline: &ProverDeadline,
+    ) -> Result<Self, ProverCheckpointError> {
+        let context = *checkpoint.context();
+        let accessor = &SharedColumnAccessor::new(accessor);
+        check_column_references(expr, accessor).map_err(ProverError::from)?;
+        if expr.is_empty(accessor) {
+            return Ok(VerifiableQueryResult {
+                provable_result: None,
+                proof: None,
+                context,
+                version: ProofVersion::current(),
+            });
+        }
+        let (proof, res) = QueryProof::resume_impl(expr, accessor, setup, checkpoint, deadline)?;
+        Ok(Self {
+            provable_result: Some(res),
+            proof: Some(proof),
+            context,
+            version: ProofVersion::current(),
+        })
+    }
+
+    /// Form `VerifiableQueryResult`s for several queries against the same snapshot of the
+    /// database, sharing the prover's work across the queries.
+    ///
+    /// Every column is only read from the accessor once, and intermediate MLEs along with their
+    /// commitments are only computed once when several queries need them, e.g. because they
+    /// filter on the same comparison. Each result is the same as the one created by
+    /// [`VerifiableQueryResult::new`] and is verified on its own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a query references a table or column that the accessor doesn't have.
+    pub fn prove_many<E: ProofExpr<CP::Commitment> + Serialize>(
+        exprs: &[E],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> Vec<Self> {
+        let accessor = SharedColumnAccessor::new(accessor);
+        // The snapshot does not change while the queries are proven, so every table can be cached
+        let prover_cache = ProverCache::new(usize::MAX);
+        for expr in exprs {
+            for column in expr.get_column_references() {
+                prover_cache.set_table_version(column.table_ref(), 0);
+            }
+        }
+        let shared_commitments = SharedCommitments::new();
+        exprs
+            .iter()
+            .map(|expr| {
+                Self::new_impl(
+                    expr,
+                    &accessor,
+                    setup,
+                    EvaluationContext::default(),
+                    Some(&prover_cache),
+                    Some(&shared_commitments),
+                    &ProverDeadline::default(),
+                )
+                .expect("the query should only reference existing columns")
+            })
+            .collect()
+    }
+
+    fn new_impl(
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+        context: EvaluationContext,
+        prover_cache: Option<&ProverCache<CP::Scalar>>,
+        shared_commitments: Option<&SharedCommitments<CP::Commitment>>,
+        deadline: &ProverDeadline,
+    ) -> Result<Self, ProverError> {
         // a query must have at least one result column; if not, it should
         // have been rejected at the parsing stage.
 
+        let accessor = &SharedColumnAccessor::new(accessor);
+        check_column_references(expr, accessor)?;
+
         // handle the empty case
         if expr.is_empty(accessor) {
-            return VerifiableQueryResult {
+            return Ok(VerifiableQueryResult {
                 provable_result: None,
                 proof: None,
-            };
+                context,
+                version: ProofVersion::current(),
+            });
         }
 
-        let (proof, res) = QueryProof::new(expr, accessor, setup);
-        Self {
+        let (proof, res) = QueryProof::new_impl(
+            expr,
+            accessor,
+            setup,
+            &context,
+            prover_cache,
+            shared_commitments,
+            deadline,
+        )?;
+        Ok(Self {
             provable_result: Some(res),
             proof: Some(proof),
-        }
+            context,
+            version: ProofVersion::current(),
+        })
     }
 
     /// Verify a `VerifiableQueryResult`. Upon success, this function returns the finalized form of
@@ -114,6 +358,54 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
         accessor: &impl CommitmentAccessor<CP::Commitment>,
         setup: &CP::VerifierPublicSetup<'_>,
     ) -> QueryResult<CP::Scalar> {
+        self.verify_with_limits(expr, accessor, setup, &ProvableQueryResultLimits::default())
+    }
+
+    /// Verify a `VerifiableQueryResult` like [`VerifiableQueryResult::verify`], but first check
+    /// that the query result has the names and types of columns that the caller expects.
+    ///
+    /// Fails with [`super::QueryError::SchemaMismatch`] before verifying the proof otherwise, e.g.
+    /// when the query that the caller planned drifted from the one its downstream code was
+    /// written for.
+    ///
+    /// Note: This does NOT transform the result!
+    pub fn verify_with_expected_schema(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        expected: &[(Identifier, ColumnType)],
+    ) -> QueryResult<CP::Scalar> {
+        let actual: Vec<_> = expr
+            .get_column_result_fields()
+            .iter()
+            .map(|field| (field.name(), field.data_type()))
+            .collect();
+        if actual != expected {
+            return Err(QueryError::SchemaMismatch {
+                expected: expected.to_vec(),
+                actual,
+            });
+        }
+        self.verify(expr, accessor, setup)
+    }
+
+    /// Verify a `VerifiableQueryResult`, rejecting a result that claims more rows or data than the
+    /// given limits allow before it is decoded.
+    ///
+    /// Fails with [`super::QueryError::IncompatibleVersion`] if the result was created by a prover
+    /// whose version this crate cannot verify.
+    ///
+    /// Note: This does NOT transform the result!
+    pub fn verify_with_limits(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        limits: &ProvableQueryResultLimits,
+    ) -> QueryResult<CP::Scalar> {
+        self.version.check_compatibility()?;
+
         // a query must have at least one result column; if not, it should
         // have been rejected at the parsing stage.
 
@@ -136,16 +428,71 @@ impl<CP: CommitmentEvaluationProof> VerifiableQueryResult<CP> {
             ))?;
         }
 
-        self.proof.as_ref().unwrap().verify(
+        self.proof.as_ref().unwrap().verify_with_limits(
             expr,
             accessor,
             self.provable_result.as_ref().unwrap(),
             setup,
+            &self.context,
+            limits,
         )
     }
+
+    /// Verify a `VerifiableQueryResult` like [`VerifiableQueryResult::verify_with_limits`], but
+    /// return an iterator that decodes the verified rows one at a time rather than a table.
+    ///
+    /// The rows are only returned once every check of the proof has passed, so consumers can pass
+    /// them on to a streaming sink without materializing the whole result.
+    ///
+    /// Note: This does NOT transform the result!
+    pub fn verify_rows(
+        &self,
+        expr: &(impl ProofExpr<CP::Commitment> + Serialize),
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        setup: &CP::VerifierPublicSetup<'_>,
+        limits: &ProvableQueryResultLimits,
+    ) -> Result<VerifiedRows<'_, CP::Scalar>, QueryError> {
+        self.version.check_compatibility()?;
+
+        if expr.is_empty(accessor) {
+            if self.provable_result.is_some() || self.proof.is_some() {
+                return Err(ProofError::VerificationError(
+                    "zero sumcheck variables but non-empty result",
+                ))?;
+            }
+            return Ok(VerifiedRows::new_empty(expr.get_column_result_fields()));
+        }
+
+        match (&self.provable_result, &self.proof) {
+            (Some(provable_result), Some(proof)) => proof.verify_rows(
+                expr,
+                accessor,
+                provable_result,
+                setup,
+                &self.context,
+                limits,
+            ),
+            _ => Err(ProofError::VerificationError(
+                "non-zero sumcheck variables but empty result",
+            ))?,
+        }
+    }
+}
+
+/// Checks that the accessor has every column that the query references, so that the query can be
+/// proven without panicking.
+pub(super) fn check_column_references<C: Commitment, S: Scalar>(
+    expr: &impl ProofExpr<C>,
+    accessor: &impl DataAccessor<S>,
+) -> Result<(), AccessorError> {
+    expr.get_column_references()
+        .into_iter()
+        .try_for_each(|column| accessor.try_get_column(column).map(|_| ()))
 }
 
-fn make_empty_query_result<S: Scalar>(result_fields: Vec<ColumnField>) -> QueryResult<S> {
+pub(super) fn make_empty_query_result<S: Scalar>(
+    result_fields: Vec<ColumnField>,
+) -> QueryResult<S> {
     let table = OwnedTable::try_new(
         result_fields
             .iter()
@@ -172,5 +519,7 @@ fn make_empty_query_result<S: Scalar>(result_fields: Vec<ColumnField>) -> QueryR
     Ok(QueryData {
         table,
         verification_hash: Default::default(),
+        column_checksums: None,
+        selected_rows: 0,
     })
 }
@@ -1,21 +1,29 @@
 use super::{
-    CountBuilder, ProofBuilder, ProofExpr, ProverEvaluate, VerifiableQueryResult,
-    VerificationBuilder,
+    CountBuilder, EvaluationContext, ProofBuilder, ProofExpr, ProverEvaluate,
+    VerifiableQueryResult, VerificationBuilder,
 };
 use crate::{
     base::{
         commitment::{Commitment, InnerProductProof},
         database::{
             owned_table_utility::{bigint, owned_table},
-            ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
-            OwnedTable, TestAccessor, UnimplementedTestAccessor,
+            AccessorError, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+            MetadataAccessor, OwnedTable, OwnedTableTestAccessor, TestAccessor,
+            UnimplementedTestAccessor,
         },
-        proof::ProofError,
+        proof::{ProofError, ProverDeadline, ProverError},
         scalar::Scalar,
     },
-    sql::proof::{QueryData, ResultBuilder},
+    sql::{
+        ast::{
+            test_utility::{cols_expr_plan, projection, tab},
+            ProofPlan,
+        },
+        proof::{ProofVersion, QueryData, QueryError, ResultBuilder},
+    },
 };
 use bumpalo::Bump;
+use curve25519_dalek::RistrettoPoint;
 use indexmap::IndexSet;
 use serde::Serialize;
 
@@ -71,7 +79,7 @@ impl<C: Commitment> ProofExpr<C> for EmptyTestQueryExpr {
     }
 
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
-        unimplemented!("no real usage for this function yet")
+        IndexSet::new()
     }
 }
 
@@ -86,6 +94,7 @@ fn we_can_verify_queries_on_an_empty_table() {
     let QueryData {
         verification_hash: _,
         table,
+        ..
     } = res.verify(&expr, &accessor, &()).unwrap();
     let expected_res = owned_table([bigint("a1", [0; 0])]);
     assert_eq!(table, expected_res);
@@ -101,6 +110,115 @@ fn empty_verification_fails_if_the_result_contains_non_null_members() {
     let res = VerifiableQueryResult::<InnerProductProof> {
         provable_result: Some(Default::default()),
         proof: None,
+        context: Default::default(),
+        version: Default::default(),
     };
     assert!(res.verify(&expr, &accessor, &()).is_err());
 }
+
+#[test]
+fn verification_fails_with_the_versions_if_the_proof_requires_a_newer_verifier() {
+    let expr = EmptyTestQueryExpr {
+        columns: 1,
+        ..Default::default()
+    };
+    let accessor = UnimplementedTestAccessor::new_empty();
+    let mut res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert_eq!(res.version, ProofVersion::current());
+    res.version.protocol_version += 1;
+    res.version.min_verifier_version = res.version.protocol_version;
+    let res: VerifiableQueryResult<InnerProductProof> =
+        postcard::from_bytes(&postcard::to_allocvec(&res).unwrap()).unwrap();
+    match res.verify(&expr, &accessor, &()) {
+        Err(QueryError::IncompatibleVersion { proof, verifier }) => {
+            assert_eq!(proof, res.version);
+            assert_eq!(verifier, ProofVersion::current());
+        }
+        _ => panic!("expected an incompatible version"),
+    }
+}
+
+#[test]
+fn we_cannot_prove_queries_that_reference_missing_tables_or_columns() {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([bigint("a", [1, 2, 3])]),
+        0,
+        (),
+    );
+    // The queries are planned against a schema that the prover's accessor is out of sync with
+    let mut schema_accessor = accessor.clone();
+    schema_accessor.add_table(
+        t,
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [1, 2, 3])]),
+        0,
+    );
+    schema_accessor.add_table(u, owned_table([bigint("a", [1, 2, 3])]), 0);
+    let prove = |expr: &ProofPlan<RistrettoPoint>| {
+        VerifiableQueryResult::<InnerProductProof>::new_with_deadline(
+            expr,
+            &accessor,
+            &(),
+            EvaluationContext::default(),
+            &ProverDeadline::default(),
+        )
+    };
+
+    let missing_column = projection(cols_expr_plan(t, &["b"], &schema_accessor), tab(t));
+    assert!(matches!(
+        prove(&missing_column),
+        Err(ProverError::Accessor(AccessorError::ColumnNotFound { .. }))
+    ));
+    let missing_table = projection(cols_expr_plan(u, &["a"], &schema_accessor), tab(u));
+    assert!(matches!(
+        prove(&missing_table),
+        Err(ProverError::Accessor(AccessorError::TableNotFound { .. }))
+    ));
+    let expr = projection(cols_expr_plan(t, &["a"], &schema_accessor), tab(t));
+    assert!(prove(&expr).is_ok());
+}
+
+#[test]
+fn we_can_verify_a_result_against_the_expected_schema() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]),
+        0,
+        (),
+    );
+    let expr = projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t));
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let schema = [
+        ("a".parse().unwrap(), ColumnType::BigInt),
+        ("b".parse().unwrap(), ColumnType::BigInt),
+    ];
+    let table = res
+        .verify_with_expected_schema(&expr, &accessor, &(), &schema)
+        .unwrap()
+        .table;
+    assert_eq!(
+        table,
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])])
+    );
+
+    let drifted_schemas = [
+        vec![schema[1], schema[0]],
+        vec![schema[0]],
+        vec![schema[0], ("b".parse().unwrap(), ColumnType::Int)],
+    ];
+    for expected in drifted_schemas {
+        match res.verify_with_expected_schema(&expr, &accessor, &(), &expected) {
+            Err(QueryError::SchemaMismatch {
+                expected: mismatched,
+                actual,
+            }) => {
+                assert_eq!(mismatched, expected);
+                assert_eq!(actual, schema);
+            }
+            _ => panic!("expected a schema mismatch"),
+        }
+    }
+}
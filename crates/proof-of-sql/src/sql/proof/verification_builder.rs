@@ -104,6 +104,23 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         self.mle_evaluations.result_evaluations[index]
     }
 
+    /// Switch to the result of the next query in a batch, whose result MLEs are consumed next
+    ///
+    /// The result MLEs of the previous query must all have been consumed.
+    pub fn set_result_evaluations(
+        &mut self,
+        result_evaluations: &'a [C::Scalar],
+        result_indexes_evaluation: Option<C::Scalar>,
+    ) {
+        assert_eq!(
+            self.consumed_result_mles,
+            self.mle_evaluations.result_evaluations.len()
+        );
+        self.mle_evaluations.result_evaluations = result_evaluations;
+        self.mle_evaluations.result_indexes_evaluation = result_indexes_evaluation;
+        self.consumed_result_mles = 0;
+    }
+
     /// Produce the evaluation of a subpolynomial used in sumcheck
     pub fn produce_sumcheck_subpolynomial_evaluation(&mut self, eval: &C::Scalar) {
         self.sumcheck_evaluation +=
@@ -0,0 +1,88 @@
+use crate::base::scalar::Scalar;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+/// A check that the verifier performs on a proof, in the order the checks are performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationCheck {
+    /// Whether the result is within the size limits.
+    ResultLimits,
+    /// Whether the bit distributions are valid.
+    BitDistributions,
+    /// Whether the query can be planned against the bit distributions of the proof.
+    ProofCounts,
+    /// Whether the proof has as many commitments and evaluations as the query requires.
+    ProofSize,
+    /// Whether the rounds of the sumcheck proof are consistent.
+    Sumcheck,
+    /// Whether the result can be decoded and evaluated.
+    ResultDecoding,
+    /// Whether the query accepts the evaluations of the proof.
+    VerifierEvaluation,
+    /// Whether the evaluation of the sumcheck polynomial matches the sumcheck proof.
+    SumcheckEvaluation,
+    /// Whether the evaluation proof of the MLEs is valid.
+    EvaluationProof,
+}
+
+/// A detailed account of the verification of a proof.
+///
+/// This is meant for audits and for debugging verification failures, and is only available with
+/// the `verification-report` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "verification-report"), allow(dead_code))]
+pub struct VerificationReport<S: Scalar> {
+    /// The checks that passed, in order.
+    pub passed_checks: Vec<VerificationCheck>,
+    /// The check that failed, if verification failed.
+    pub failed_check: Option<VerificationCheck>,
+    /// The challenges that were drawn after the result was sent.
+    pub post_result_challenges: Vec<S>,
+    /// The random scalars that were drawn for sumcheck.
+    pub sumcheck_random_scalars: Vec<S>,
+    /// The point the sumcheck proof reduced the query to.
+    pub evaluation_point: Vec<S>,
+    /// The random scalars that fold the MLE evaluations for the evaluation proof.
+    pub evaluation_random_scalars: Vec<S>,
+    /// The size of the serialized proof, in bytes.
+    pub proof_size: usize,
+    /// The time that verification took.
+    pub duration: Duration,
+}
+
+#[cfg_attr(not(feature = "verification-report"), allow(dead_code))]
+impl<S: Scalar> VerificationReport<S> {
+    /// Create an empty report.
+    pub(crate) fn new() -> Self {
+        Self {
+            passed_checks: Vec::new(),
+            failed_check: None,
+            post_result_challenges: Vec::new(),
+            sumcheck_random_scalars: Vec::new(),
+            evaluation_point: Vec::new(),
+            evaluation_random_scalars: Vec::new(),
+            proof_size: 0,
+            duration: Duration::ZERO,
+        }
+    }
+
+    /// Returns whether every check passed.
+    pub fn is_success(&self) -> bool {
+        self.failed_check.is_none()
+    }
+}
+
+/// Record the outcome of the check in the report, if there is one.
+pub(crate) fn record_check<S: Scalar, T, E>(
+    report: Option<&mut VerificationReport<S>>,
+    check: VerificationCheck,
+    result: Result<T, E>,
+) -> Result<T, E> {
+    if let Some(report) = report {
+        match &result {
+            Ok(_) => report.passed_checks.push(check),
+            Err(_) => report.failed_check = Some(check),
+        }
+    }
+    result
+}
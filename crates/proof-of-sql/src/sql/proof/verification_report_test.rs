@@ -0,0 +1,120 @@
+use super::{
+    EvaluationContext, ProvableQueryResult, ProvableQueryResultLimits, QueryProof, QueryResult,
+    VerificationCheck, VerificationReport,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn sample_accessor(t: TableRef) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [1, 2, 3, 2]), bigint("b", [5, 6, 7, 8])]);
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ())
+}
+
+fn sample_ast(
+    t: TableRef,
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> ProofPlan<RistrettoPoint> {
+    dense_filter(
+        cols_expr_plan(t, &["b"], accessor),
+        tab(t),
+        gte(column(t, "a", accessor), const_bigint(2)),
+    )
+}
+
+fn verify_with_report(
+    proof: &QueryProof<InnerProductProof>,
+    result: &ProvableQueryResult,
+) -> (
+    QueryResult<Curve25519Scalar>,
+    VerificationReport<Curve25519Scalar>,
+) {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    proof.verify_with_report(
+        &sample_ast(t, &accessor),
+        &accessor,
+        result,
+        &(),
+        &EvaluationContext::default(),
+        &ProvableQueryResultLimits::default(),
+    )
+}
+
+fn prove() -> (QueryProof<InnerProductProof>, ProvableQueryResult) {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = sample_accessor(t);
+    QueryProof::new(&sample_ast(t, &accessor), &accessor, &())
+}
+
+#[test]
+fn we_can_report_on_the_verification_of_a_valid_proof() {
+    let (proof, result) = prove();
+    let (query_result, report) = verify_with_report(&proof, &result);
+    let expected: OwnedTable<Curve25519Scalar> = owned_table([bigint("b", [6, 7, 8])]);
+    assert_eq!(query_result.unwrap().table, expected);
+    assert!(report.is_success());
+    assert_eq!(
+        report.passed_checks,
+        [
+            VerificationCheck::ResultLimits,
+            VerificationCheck::BitDistributions,
+            VerificationCheck::ProofCounts,
+            VerificationCheck::ProofSize,
+            VerificationCheck::Sumcheck,
+            VerificationCheck::ResultDecoding,
+            VerificationCheck::VerifierEvaluation,
+            VerificationCheck::SumcheckEvaluation,
+            VerificationCheck::EvaluationProof,
+        ]
+    );
+    assert_eq!(
+        report.proof_size,
+        postcard::to_allocvec(&proof).unwrap().len()
+    );
+    assert_eq!(report.evaluation_point.len(), 2);
+    assert_eq!(
+        report.evaluation_random_scalars.len(),
+        proof.pcs_proof_evaluations.len()
+    );
+    assert!(!report.sumcheck_random_scalars.is_empty());
+}
+
+#[test]
+fn the_report_names_the_check_that_failed_for_a_proof_of_the_wrong_size() {
+    let (mut proof, result) = prove();
+    proof.commitments.pop();
+    let (query_result, report) = verify_with_report(&proof, &result);
+    assert!(query_result.is_err());
+    assert!(!report.is_success());
+    assert_eq!(
+        report.passed_checks,
+        [
+            VerificationCheck::ResultLimits,
+            VerificationCheck::BitDistributions,
+            VerificationCheck::ProofCounts,
+        ]
+    );
+    assert_eq!(report.failed_check, Some(VerificationCheck::ProofSize));
+    assert!(report.evaluation_point.is_empty());
+}
+
+#[test]
+fn the_report_names_the_check_that_failed_for_a_tampered_evaluation() {
+    let (mut proof, result) = prove();
+    proof.pcs_proof_evaluations[0] += Curve25519Scalar::from(1u64);
+    let (query_result, report) = verify_with_report(&proof, &result);
+    assert!(query_result.is_err());
+    assert!(report.passed_checks.contains(&VerificationCheck::Sumcheck));
+    assert!(matches!(
+        report.failed_check,
+        Some(VerificationCheck::SumcheckEvaluation | VerificationCheck::EvaluationProof)
+    ));
+}
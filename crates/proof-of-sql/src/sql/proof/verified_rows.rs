@@ -0,0 +1,139 @@
+use super::{ProvableQueryResult, ProvableResultElement, QueryError};
+use crate::base::{
+    database::{ColumnField, ColumnType, LiteralValue},
+    scalar::Scalar,
+};
+use std::marker::PhantomData;
+
+/// The rows of a verified query result, which are decoded one at a time from the encoded result.
+///
+/// Unlike [`super::QueryData`], this does not hold the whole result in an
+/// [`crate::base::database::OwnedTable`], so that consumers that pass the rows on to a streaming
+/// sink only hold one decoded row at a time. It is only created after the proof of the result is
+/// verified, see [`super::VerifiableQueryResult::verify_rows`].
+///
+/// Every row has a value for each of the [`VerifiedRows::fields`], in order.
+pub struct VerifiedRows<'a, S: Scalar> {
+    fields: Vec<ColumnField>,
+    data: &'a [u8],
+    offsets: Vec<usize>,
+    remaining_rows: usize,
+    verification_hash: [u8; 32],
+    selected_rows: usize,
+    _scalar: PhantomData<S>,
+}
+
+impl<'a, S: Scalar> VerifiedRows<'a, S> {
+    /// Prepare to decode the rows of a result that was verified.
+    ///
+    /// This finds where the encoded data of each column starts, which checks that the data has
+    /// the given number of rows of every column.
+    pub(super) fn new(
+        result: &'a ProvableQueryResult,
+        fields: Vec<ColumnField>,
+        verification_hash: [u8; 32],
+        selected_rows: usize,
+    ) -> Result<Self, QueryError> {
+        let offsets = result.column_offsets::<S>(&fields)?;
+        Ok(Self {
+            fields,
+            data: result.data(),
+            offsets,
+            remaining_rows: result.indexes().len(),
+            verification_hash,
+            selected_rows,
+            _scalar: PhantomData,
+        })
+    }
+
+    /// The rows of a query over an empty table, of which there are none.
+    pub(super) fn new_empty(fields: Vec<ColumnField>) -> Self {
+        Self {
+            offsets: vec![0; fields.len()],
+            fields,
+            data: &[],
+            remaining_rows: 0,
+            verification_hash: Default::default(),
+            selected_rows: 0,
+            _scalar: PhantomData,
+        }
+    }
+
+    /// The names and types of the columns of the rows.
+    pub fn fields(&self) -> &[ColumnField] {
+        &self.fields
+    }
+
+    /// The verification hash of the result. See [`super::QueryData::verification_hash`].
+    pub fn verification_hash(&self) -> [u8; 32] {
+        self.verification_hash
+    }
+
+    /// The number of rows of the queried table that the query selected. See
+    /// [`super::QueryData::selected_rows`].
+    pub fn selected_rows(&self) -> usize {
+        self.selected_rows
+    }
+}
+
+impl<S: Scalar> Iterator for VerifiedRows<'_, S> {
+    type Item = Vec<LiteralValue<S>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_rows == 0 {
+            return None;
+        }
+        self.remaining_rows -= 1;
+        Some(
+            self.fields
+                .iter()
+                .zip(&mut self.offsets)
+                .map(|(field, offset)| {
+                    let (value, num_read) = decode_value(field.data_type(), &self.data[*offset..])
+                        .expect("every value was decoded when the result was verified");
+                    *offset += num_read;
+                    value
+                })
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining_rows, Some(self.remaining_rows))
+    }
+}
+
+impl<S: Scalar> ExactSizeIterator for VerifiedRows<'_, S> {}
+
+/// Decode a value of the given type from the start of the encoded data of a column.
+fn decode_value<S: Scalar>(
+    column_type: ColumnType,
+    data: &[u8],
+) -> Result<(LiteralValue<S>, usize), QueryError> {
+    match column_type {
+        ColumnType::Boolean => decode(data, LiteralValue::Boolean),
+        ColumnType::SmallInt => decode(data, LiteralValue::SmallInt),
+        ColumnType::Int => decode(data, LiteralValue::Int),
+        ColumnType::BigInt => decode(data, LiteralValue::BigInt),
+        ColumnType::Int128 => decode(data, LiteralValue::Int128),
+        ColumnType::VarChar => decode(data, |value: String| {
+            let hash = value.as_str().into();
+            LiteralValue::VarChar((value, hash))
+        }),
+        ColumnType::Scalar => decode(data, LiteralValue::Scalar),
+        ColumnType::Decimal75(precision, scale) => decode(data, |value| {
+            LiteralValue::Decimal75(precision, scale, value)
+        }),
+        ColumnType::TimestampTZ(unit, zone) => {
+            decode(data, |value| LiteralValue::TimeStampTZ(unit, zone, value))
+        }
+    }
+}
+
+fn decode<'a, T: ProvableResultElement<'a>, S: Scalar>(
+    data: &'a [u8],
+    to_value: impl FnOnce(T) -> LiteralValue<S>,
+) -> Result<(LiteralValue<S>, usize), QueryError> {
+    let (value, num_read) = T::decode(data)?;
+    Ok((to_value(value), num_read))
+}
@@ -0,0 +1,128 @@
+use super::{ProvableQueryResultLimits, VerifiableQueryResult};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, LiteralValue, OwnedTableTestAccessor, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::ast::{test_utility::*, ProofPlan},
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn accessor(t: TableRef, num_rows: i64) -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", (0..num_rows).map(|i| i % 3)),
+            bigint("b", 0..num_rows),
+            varchar("c", (0..num_rows).map(|i| format!("s{i}"))),
+        ]),
+        0,
+        (),
+    )
+}
+
+fn row(b: i64, c: &str) -> Vec<LiteralValue<Curve25519Scalar>> {
+    vec![
+        LiteralValue::BigInt(b),
+        LiteralValue::VarChar((c.to_string(), c.into())),
+    ]
+}
+
+#[test]
+fn we_can_iterate_over_the_verified_rows_of_a_filter() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 10);
+    let exprs: [ProofPlan<RistrettoPoint>; 2] = [
+        filter(
+            cols_result(t, &["b", "c"], &accessor),
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(1)),
+        ),
+        dense_filter(
+            cols_expr_plan(t, &["b", "c"], &accessor),
+            tab(t),
+            equal(column(t, "a", &accessor), const_bigint(1)),
+        ),
+    ];
+    for expr in &exprs {
+        let res = VerifiableQueryResult::<InnerProductProof>::new(expr, &accessor, &());
+        let query_data = res.verify(expr, &accessor, &()).unwrap();
+        let rows = res
+            .verify_rows(expr, &accessor, &(), &ProvableQueryResultLimits::default())
+            .unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows.fields()
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect::<Vec<_>>(),
+            ["b", "c"]
+        );
+        assert_eq!(rows.verification_hash(), query_data.verification_hash);
+        assert_eq!(rows.selected_rows(), 3);
+        assert_eq!(
+            rows.collect::<Vec<_>>(),
+            [row(1, "s1"), row(4, "s4"), row(7, "s7")]
+        );
+    }
+}
+
+#[test]
+fn the_verified_rows_of_a_group_by_count_the_selected_rows() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 10);
+    let expr: ProofPlan<RistrettoPoint> = group_by(
+        cols_expr(t, &["a"], &accessor),
+        vec![sum_expr(column(t, "b", &accessor), "sum_b")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let rows = res
+        .verify_rows(&expr, &accessor, &(), &ProvableQueryResultLimits::default())
+        .unwrap();
+    assert_eq!(rows.selected_rows(), 10);
+    assert_eq!(
+        rows.collect::<Vec<_>>(),
+        [
+            [0, 18, 4].map(LiteralValue::BigInt),
+            [1, 12, 3].map(LiteralValue::BigInt),
+            [2, 15, 3].map(LiteralValue::BigInt),
+        ]
+    );
+}
+
+#[test]
+fn there_are_no_verified_rows_of_a_query_over_an_empty_table() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 0);
+    let expr: ProofPlan<RistrettoPoint> = projection(cols_expr_plan(t, &["b"], &accessor), tab(t));
+    let res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    let rows = res
+        .verify_rows(&expr, &accessor, &(), &ProvableQueryResultLimits::default())
+        .unwrap();
+    assert_eq!(rows.fields().len(), 1);
+    assert_eq!(rows.count(), 0);
+}
+
+#[test]
+fn we_cannot_iterate_over_the_rows_of_a_tampered_result() {
+    let t = "sxt.t".parse().unwrap();
+    let accessor = accessor(t, 10);
+    let expr: ProofPlan<RistrettoPoint> = projection(cols_expr_plan(t, &["b"], &accessor), tab(t));
+    let mut res = VerifiableQueryResult::<InnerProductProof>::new(&expr, &accessor, &());
+    assert!(res
+        .verify_rows(
+            &expr,
+            &accessor,
+            &(),
+            &ProvableQueryResultLimits::default().with_max_rows(9)
+        )
+        .is_err());
+    res.provable_result.as_mut().unwrap().data_mut()[0] += 1;
+    assert!(res
+        .verify_rows(&expr, &accessor, &(), &ProvableQueryResultLimits::default())
+        .is_err());
+}
@@ -45,6 +45,12 @@ pub use select_expr::SelectExpr;
 #[cfg(test)]
 mod select_expr_test;
 
+mod net_retractions_expr;
+pub use net_retractions_expr::NetRetractionsExpr;
+
+#[cfg(test)]
+mod net_retractions_expr_test;
+
 mod group_by_expr;
 #[cfg(test)]
 pub(crate) use group_by_expr::group_by_map_i128_to_utf8;
@@ -0,0 +1,83 @@
+use crate::{base::database::MULTIPLICITY_COLUMN_NAME, sql::transform::RecordBatchExpr};
+use arrow::{
+    array::{ArrayRef, Int64Array, UInt64Array},
+    compute::take,
+    datatypes::{FieldRef, Schema},
+    record_batch::{RecordBatch, RecordBatchOptions},
+    row::{RowConverter, SortField},
+};
+use dyn_partial_eq::DynPartialEq;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// A node that nets the retractions of a retractable table out of a query result.
+///
+/// The result of a query against a retractable table has a [`MULTIPLICITY_COLUMN_NAME`] column.
+/// Every distinct row is repeated as many times as its net multiplicity, in the order that the
+/// rows first appear, and the multiplicity column is dropped. Rows whose net multiplicity is not
+/// positive are dropped entirely.
+///
+/// See [`crate::base::database::retraction_rows`].
+#[derive(Debug, Default, DynPartialEq, PartialEq, Serialize, Deserialize)]
+pub struct NetRetractionsExpr {}
+
+impl NetRetractionsExpr {
+    /// Create a new `NetRetractionsExpr` node.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[typetag::serde]
+impl RecordBatchExpr for NetRetractionsExpr {
+    /// Net the retractions out of the `RecordBatch` and drop its multiplicity column.
+    fn apply_transformation(&self, record_batch: RecordBatch) -> Option<RecordBatch> {
+        let schema = record_batch.schema();
+        let multiplicity_index = schema.index_of(MULTIPLICITY_COLUMN_NAME).ok()?;
+        let multiplicities = record_batch
+            .column(multiplicity_index)
+            .as_any()
+            .downcast_ref::<Int64Array>()?;
+        let (fields, columns): (Vec<FieldRef>, Vec<ArrayRef>) = schema
+            .fields()
+            .iter()
+            .cloned()
+            .zip(record_batch.columns().iter().cloned())
+            .enumerate()
+            .filter(|(index, _)| *index != multiplicity_index)
+            .map(|(_, field_and_column)| field_and_column)
+            .unzip();
+
+        let converter = RowConverter::new(
+            columns
+                .iter()
+                .map(|column| SortField::new(column.data_type().clone()))
+                .collect(),
+        )
+        .ok()?;
+        let rows = converter.convert_columns(&columns).ok()?;
+        let mut net_multiplicities = IndexMap::new();
+        for (index, row) in rows.iter().enumerate() {
+            let (_, net_multiplicity) = net_multiplicities.entry(row).or_insert((index, 0i64));
+            *net_multiplicity = net_multiplicity.checked_add(multiplicities.value(index))?;
+        }
+        let indices = UInt64Array::from_iter_values(net_multiplicities.into_values().flat_map(
+            |(index, net_multiplicity)| {
+                std::iter::repeat(index as u64).take(net_multiplicity.max(0) as usize)
+            },
+        ));
+
+        let netted_columns = columns
+            .iter()
+            .map(|column| take(column.as_ref(), &indices, None))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        RecordBatch::try_new_with_options(
+            Arc::new(Schema::new(fields)),
+            netted_columns,
+            &RecordBatchOptions::new().with_row_count(Some(indices.len())),
+        )
+        .ok()
+    }
+}
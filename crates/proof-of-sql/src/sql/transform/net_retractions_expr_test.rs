@@ -0,0 +1,52 @@
+use crate::{
+    record_batch,
+    sql::transform::test_utility::{composite_result, net_retractions, slice},
+};
+
+#[test]
+fn we_can_net_retractions_out_of_a_record_batch() {
+    let data = record_batch!(
+        "a" => [1_i64, 2, 1, 3, 2, 1, 4],
+        "b" => ["x", "y", "x", "z", "y", "x", "w"],
+        "__multiplicity__" => [1_i64, 1, 1, 1, -1, -1, -1]
+    );
+    let result_expr = composite_result(vec![net_retractions()]);
+    let data = result_expr.transform_results(data).unwrap();
+    assert_eq!(
+        data,
+        record_batch!(
+            "a" => [1_i64, 3],
+            "b" => ["x", "z"]
+        )
+    );
+}
+
+#[test]
+fn we_can_repeat_records_that_were_inserted_several_times() {
+    let data = record_batch!(
+        "a" => [5_i64, 6, 5, 5],
+        "__multiplicity__" => [1_i64, 1, 1, 1]
+    );
+    let result_expr = composite_result(vec![net_retractions(), slice(3, 0)]);
+    let data = result_expr.transform_results(data).unwrap();
+    assert_eq!(data, record_batch!("a" => [5_i64, 5, 5]));
+}
+
+#[test]
+fn we_can_net_out_every_row_of_a_record_batch() {
+    let data = record_batch!(
+        "a" => [1_i64, 1],
+        "__multiplicity__" => [1_i64, -1]
+    );
+    let result_expr = composite_result(vec![net_retractions()]);
+    let data = result_expr.transform_results(data).unwrap();
+    assert_eq!(data.num_rows(), 0);
+    assert_eq!(data.schema(), record_batch!("a" => [0_i64; 0]).schema());
+}
+
+#[test]
+fn we_cannot_net_retractions_without_a_multiplicity_column() {
+    let data = record_batch!("a" => [1_i64, 1]);
+    let result_expr = composite_result(vec![net_retractions()]);
+    assert!(result_expr.transform_results(data).is_none());
+}
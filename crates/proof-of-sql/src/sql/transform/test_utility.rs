@@ -44,6 +44,10 @@ pub fn slice(limit: u64, offset: i64) -> Box<dyn RecordBatchExpr> {
     Box::new(SliceExpr::new(limit, offset))
 }
 
+pub fn net_retractions() -> Box<dyn RecordBatchExpr> {
+    Box::new(NetRetractionsExpr::new())
+}
+
 pub fn composite_result(transformations: Vec<Box<dyn RecordBatchExpr>>) -> ResultExpr {
     let mut composition = CompositionExpr::default();
 
@@ -2,8 +2,19 @@
 use ark_std::test_rng;
 use arrow::record_batch::RecordBatch;
 use curve25519_dalek::RistrettoPoint;
+#[cfg(all(feature = "blitzar", feature = "varchar-normalization"))]
+use proof_of_sql::base::database::VarCharNormalization;
 #[cfg(feature = "blitzar")]
-use proof_of_sql::base::commitment::InnerProductProof;
+use proof_of_sql::base::{
+    commitment::{
+        InnerProductProof, PartitionedTableCommitment, QueryCommitments, TableCommitment,
+    },
+    database::{
+        insertion_rows, retraction_rows, ColumnCipher, DecryptingDataAccessor, EncryptedTableStore,
+    },
+};
+#[cfg(feature = "bn254")]
+use proof_of_sql::proof_primitive::pedersen::{PedersenEvaluationProof, PedersenSetup};
 use proof_of_sql::{
     base::{
         database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
@@ -15,8 +26,8 @@ use proof_of_sql::{
     },
     record_batch,
     sql::{
-        parse::{ConversionError, QueryExpr},
-        proof::{QueryError, QueryProof},
+        parse::{ConversionError, ExistsQueryExpr, ExistsQueryProof, QueryExpr},
+        proof::{QueryError, QueryProof, VerifiableQueryResult},
     },
 };
 
@@ -45,6 +56,248 @@ fn we_can_prove_a_minimal_filter_query_with_curve25519() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+#[cfg(all(feature = "blitzar", feature = "varchar-normalization"))]
+fn we_can_prove_an_equality_query_against_a_normalized_varchar_column() {
+    let table_ref = "sxt.table".parse().unwrap();
+    let precomposed = "caf\u{e9}";
+    let decomposed = "cafe\u{301}";
+
+    // The same logical string arrives in two different forms
+    let ingested: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("id", [1, 2, 3]),
+        varchar("name", [decomposed, "tea", precomposed]),
+    ]);
+    let table_commitment: TableCommitment<RistrettoPoint> =
+        TableCommitment::from_owned_table_with_offset_and_varchar_normalization(
+            &ingested,
+            0,
+            VarCharNormalization::Nfc,
+            &(),
+        );
+    let query_commitments = QueryCommitments::from_iter([(table_ref, table_commitment)]);
+
+    // The prover stores the strings the way they were committed
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        table_ref,
+        owned_table([
+            bigint("id", [1, 2, 3]),
+            varchar("name", [precomposed, "tea", precomposed]),
+        ]),
+        0,
+    );
+
+    // Both forms of the literal match both rows
+    for literal in [precomposed, decomposed] {
+        let query = QueryExpr::try_new(
+            format!("SELECT id FROM table WHERE name = '{literal}';")
+                .parse()
+                .unwrap(),
+            "sxt".parse().unwrap(),
+            &query_commitments,
+        )
+        .unwrap();
+        let (proof, serialized_result) =
+            QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &query_commitments,
+                &serialized_result,
+                &(),
+            )
+            .unwrap()
+            .table;
+        assert_eq!(owned_table_result, owned_table([bigint("id", [1, 3])]));
+    }
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_query_over_only_the_relevant_partitions_of_a_table() {
+    let table_ref = "sxt.table".parse().unwrap();
+    let partitions: [OwnedTable<Curve25519Scalar>; 3] = [
+        owned_table([bigint("day", [1, 1, 2]), bigint("amount", [10, 11, 12])]),
+        owned_table([bigint("day", [3, 4]), bigint("amount", [13, 14])]),
+        owned_table([bigint("day", [5]), bigint("amount", [15])]),
+    ];
+    let mut offset = 0;
+    let partition_commitments: Vec<TableCommitment<RistrettoPoint>> = partitions
+        .iter()
+        .map(|partition| {
+            let partition_commitment =
+                TableCommitment::from_owned_table_with_offset(partition, offset, &());
+            offset += partition.num_rows();
+            partition_commitment
+        })
+        .collect();
+    let partitioned_table =
+        PartitionedTableCommitment::try_new("day".parse().unwrap(), partition_commitments).unwrap();
+
+    // The prover only holds the rows of the partitions that may be relevant, at their offset
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        table_ref,
+        owned_table([bigint("day", [3, 4]), bigint("amount", [13, 14])]),
+        3,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT amount FROM table WHERE day >= 3 AND day <= 4;"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let kept_partitions = query.proof_expr().prune_partitions(&partitioned_table);
+    assert_eq!(kept_partitions, 1..2);
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+
+    // The verifier checks that no relevant partition was dropped before verifying the proof
+    let table_commitment = query
+        .proof_expr()
+        .verify_partition_pruning(&partitioned_table, kept_partitions)
+        .unwrap();
+    let query_commitments = QueryCommitments::from_iter([(table_ref, table_commitment)]);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &query_commitments,
+            &serialized_result,
+            &(),
+        )
+        .unwrap()
+        .table;
+    assert_eq!(
+        owned_table_result,
+        owned_table([bigint("amount", [13, 14])])
+    );
+    assert!(query
+        .proof_expr()
+        .verify_partition_pruning(&partitioned_table, 2..3)
+        .is_err());
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_queries_against_a_retractable_table() {
+    let table_ref = "sxt.table".parse().unwrap();
+    let insertions: OwnedTable<Curve25519Scalar> = insertion_rows(owned_table([
+        bigint("id", [1, 2, 3, 4]),
+        varchar("name", ["a", "b", "c", "b"]),
+    ]))
+    .unwrap();
+    let retractions: OwnedTable<Curve25519Scalar> =
+        retraction_rows(owned_table([bigint("id", [2]), varchar("name", ["b"])])).unwrap();
+
+    // Deleting a record only appends rows to the commitment
+    let mut table_commitment =
+        TableCommitment::<RistrettoPoint>::from_owned_table_with_offset(&insertions, 0, &());
+    table_commitment
+        .append_owned_table(&retractions, &())
+        .unwrap();
+    let query_commitments = QueryCommitments::from_iter([(table_ref, table_commitment)]);
+
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        table_ref,
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 2]),
+            varchar("name", ["a", "b", "c", "b", "b"]),
+            bigint("__multiplicity__", [1, 1, 1, 1, -1]),
+        ]),
+        0,
+    );
+    for (sql, expected_result) in [
+        (
+            "SELECT * FROM table WHERE id >= 2;",
+            owned_table([bigint("id", [3, 4]), varchar("name", ["c", "b"])]),
+        ),
+        (
+            "SELECT name, COUNT(*) AS num FROM table GROUP BY name ORDER BY name;",
+            owned_table([varchar("name", ["a", "b", "c"]), bigint("num", [1, 1, 1])]),
+        ),
+    ] {
+        let query = QueryExpr::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &query_commitments,
+        )
+        .unwrap();
+        let (proof, serialized_result) =
+            QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &query_commitments,
+                &serialized_result,
+                &(),
+            )
+            .unwrap()
+            .table;
+        let owned_table_result: OwnedTable<Curve25519Scalar> = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(owned_table_result, expected_result);
+    }
+}
+
+/// A toy cipher that xors every byte with the same key.
+#[cfg(feature = "blitzar")]
+struct XorCipher(u8);
+
+#[cfg(feature = "blitzar")]
+impl ColumnCipher for XorCipher {
+    fn key_id(&self) -> &str {
+        "xor"
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        plaintext.iter().map(|byte| byte ^ self.0).collect()
+    }
+
+    fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        (key_id == self.key_id()).then(|| self.encrypt(ciphertext))
+    }
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_queries_against_an_encrypted_table() {
+    let table_ref = "sxt.table".parse().unwrap();
+    let cipher = XorCipher(0x5a);
+    let mut store = EncryptedTableStore::<RistrettoPoint>::new();
+    store.add_table(
+        table_ref,
+        &owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "z"])]),
+        0,
+        &cipher,
+        &(),
+    );
+    let query = QueryExpr::try_new(
+        "SELECT * FROM table WHERE a >= 2;".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &store,
+    )
+    .unwrap();
+    let (proof, serialized_result) = QueryProof::<InnerProductProof>::new(
+        query.proof_expr(),
+        &DecryptingDataAccessor::new(&store, &cipher),
+        &(),
+    );
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &store, &serialized_result, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [2, 3]), varchar("b", ["y", "z"])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 fn we_can_prove_a_minimal_filter_query_with_dory() {
     let public_parameters = PublicParameters::rand(4, &mut test_rng());
@@ -81,6 +334,44 @@ fn we_can_prove_a_minimal_filter_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_a_query_with_aliased_result_columns_with_dory() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 2, 3]), bigint("b", [4, 5, 6])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a AS x, b + 1 AS y FROM table WHERE a >= 2"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("x", [2, 3]), bigint("y", [6, 7])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_basic_equality_query_with_curve25519() {
@@ -811,3 +1102,205 @@ fn we_can_prove_a_query_with_overflow_with_dory() {
         Err(QueryError::Overflow)
     ));
 }
+
+#[test]
+fn we_can_prove_a_query_with_exists_subqueries_with_dory() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.customers".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            varchar("name", ["ann", "bob", "cat", "dan"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("customer", [2, 4, 2]),
+            bigint("amount", [10, 20, 30]),
+        ]),
+        0,
+    );
+    let expr = ExistsQueryExpr::try_new(
+        "SELECT name FROM customers WHERE NOT EXISTS \
+         (SELECT 1 FROM orders WHERE orders.customer = customers.id)"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let proof =
+        ExistsQueryProof::<DoryEvaluationProof>::new(&expr, &accessor, &dory_prover_setup).unwrap();
+    let (query, data) = proof
+        .verify(&expr, &accessor, &dory_verifier_setup)
+        .unwrap();
+    let transformed_result: RecordBatch = query
+        .result()
+        .transform_results(data.table.try_into().unwrap())
+        .unwrap();
+    let expected_result: RecordBatch = record_batch!("name" => ["ann", "cat"]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_over_an_empty_table_with_dory() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    // The offset is beyond the generators of the setup, which is fine for a table without rows
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [0; 0]), varchar("b", [""; 0])]),
+        1 << 20,
+    );
+    for sql in [
+        "SELECT * FROM table WHERE a = 1",
+        "SELECT a * 2 AS c, b FROM table",
+        "SELECT b, count(*) AS n FROM table GROUP BY b",
+    ] {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let bytes = postcard::to_allocvec(&verifiable_result).unwrap();
+        let verifiable_result: VerifiableQueryResult<DoryEvaluationProof> =
+            postcard::from_bytes(&bytes).unwrap();
+        let owned_table_result = verifiable_result
+            .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+            .unwrap()
+            .table;
+        assert_eq!(owned_table_result.num_rows(), 0);
+        let transformed_result: RecordBatch = query
+            .result()
+            .transform_results(owned_table_result.try_into().unwrap())
+            .unwrap();
+        assert_eq!(transformed_result.num_rows(), 0);
+    }
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_prove_a_basic_group_by_query_with_bn254() {
+    let setup = PedersenSetup::new(8);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<PedersenEvaluationProof>::new_empty_with_setup(&setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 2, 3]),
+            bigint("c", [-2, 2, 1, 0, 1]),
+        ]),
+        2,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, sum(2 * b + 1) as d, count(*) as e FROM table WHERE c >= 0 group by a"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<PedersenEvaluationProof>::new(query.proof_expr(), &accessor, &&setup);
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &&setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("d", [1, 16, 5]),
+        bigint("e", [1, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_prove_a_complex_query_with_bn254() {
+    let setup = PedersenSetup::new(8);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<PedersenEvaluationProof>::new_empty_with_setup(&setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            smallint("a", [1_i16, 2, 3]),
+            int("b", [1, 0, 1]),
+            bigint("c", [3, 3, -3]),
+            varchar("d", ["hello", "world", "hi"]),
+            decimal75("e", 1, 0, [1, 2, 3]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a + b as t, d, e FROM table WHERE c = 3 and (d = 'world' or a <= 1)"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<PedersenEvaluationProof>::new(
+        query.proof_expr(),
+        &accessor,
+        &&setup,
+    );
+    let bytes = postcard::to_allocvec(&verifiable_result).unwrap();
+    let verifiable_result: VerifiableQueryResult<PedersenEvaluationProof> =
+        postcard::from_bytes(&bytes).unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &&setup)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        int("t", [2, 2]),
+        varchar("d", ["hello", "world"]),
+        decimal75("e", 1, 0, [1, 2]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn we_can_prove_a_query_with_overflow_with_bn254() {
+    let setup = PedersenSetup::new(4);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<PedersenEvaluationProof>::new_empty_with_setup(&setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [i64::MIN]), smallint("b", [1_i16])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a - b as c from table".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<PedersenEvaluationProof>::new(query.proof_expr(), &accessor, &&setup);
+    assert!(matches!(
+        proof.verify(query.proof_expr(), &accessor, &serialized_result, &&setup),
+        Err(QueryError::Overflow)
+    ));
+}
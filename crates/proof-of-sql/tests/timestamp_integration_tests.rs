@@ -1,6 +1,11 @@
 #![cfg(feature = "test")]
 #[cfg(feature = "blitzar")]
-use proof_of_sql::base::commitment::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+#[cfg(feature = "blitzar")]
+use proof_of_sql::base::{
+    commitment::{InnerProductProof, QueryCommitments, TableCommitment, TimestampRangeIndex},
+    scalar::Curve25519Scalar,
+};
 use proof_of_sql::{
     base::database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
     proof_primitive::dory::{
@@ -8,10 +13,11 @@ use proof_of_sql::{
         DoryVerifierPublicSetup, ProverSetup, PublicParameters, VerifierSetup,
     },
     sql::{
-        parse::QueryExpr,
-        proof::{QueryProof, VerifiableQueryResult},
+        parse::{ConversionError, QueryExpr},
+        proof::{EvaluationContext, QueryProof, VerifiableQueryResult},
     },
 };
+use proof_of_sql_parser::intermediate_ast::ContextVariable;
 use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
 #[test]
@@ -460,3 +466,173 @@ fn we_can_prove_timestamp_inequality_queries_with_multiple_columns() {
     ]);
     assert_eq!(owned_table_result, expected_result);
 }
+
+#[test]
+fn we_can_prove_a_query_using_context_variables_bound_by_the_prover_with_dory() {
+    let public_parameters = PublicParameters::rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4]),
+            timestamptz(
+                "expiry",
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::Utc,
+                [500, 1000, 1500, 2000],
+            ),
+            bigint("height", [10, 60, 40, 70]),
+        ]),
+        0,
+    );
+    let sql = "SELECT id FROM table WHERE expiry > NOW() AND height <= BLOCK_HEIGHT();";
+
+    // The query cannot be planned unless its context variables are bound
+    assert_eq!(
+        QueryExpr::<DoryCommitment>::try_new(
+            sql.parse().unwrap(),
+            "sxt".parse().unwrap(),
+            &accessor
+        )
+        .unwrap_err(),
+        ConversionError::UnboundContextVariable(ContextVariable::Now)
+    );
+
+    // The prover binds the context when it plans and proves the query
+    let prover_context = EvaluationContext::new()
+        .with_current_time(1000)
+        .with_block_height(50);
+    let query = QueryExpr::try_new_with_context(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+        prover_context,
+    )
+    .unwrap();
+    let verifiable_result = VerifiableQueryResult::<DoryEvaluationProof>::new_with_context(
+        query.proof_expr(),
+        &accessor,
+        &dory_prover_setup,
+        prover_context,
+    );
+
+    // The verifier checks the bound context against its policy, then plans the query with it
+    let context = verifiable_result.context;
+    assert!(context.current_time().unwrap().abs_diff(1010) <= 60);
+    let query = QueryExpr::try_new_with_context(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+        context,
+    )
+    .unwrap();
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &dory_verifier_setup)
+        .unwrap()
+        .table;
+    assert_eq!(owned_table_result, owned_table([bigint("id", [3])]));
+
+    // A result whose reported context differs from the one bound into the proof is rejected
+    let mut tampered_result = verifiable_result.clone();
+    tampered_result.context = prover_context.with_current_time(1100);
+    let tampered_query = QueryExpr::try_new_with_context(
+        sql.parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+        tampered_result.context,
+    )
+    .unwrap();
+    assert!(tampered_result
+        .verify(tampered_query.proof_expr(), &accessor, &dory_verifier_setup)
+        .is_err());
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_time_range_query_over_only_the_relevant_blocks_of_a_range_index() {
+    let table_ref = "sxt.table".parse().unwrap();
+    let table = owned_table::<Curve25519Scalar>([
+        timestamptz(
+            "times",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            (0..12).map(|i| 100 * i),
+        ),
+        bigint("amount", 10..22),
+    ]);
+    let index = TimestampRangeIndex::<RistrettoPoint>::try_from_owned_table_with_offset(
+        &table,
+        0,
+        "times".parse().unwrap(),
+        3,
+        &(),
+    )
+    .unwrap();
+    index
+        .verify_table_commitment(&TableCommitment::from_owned_table_with_offset(
+            &table,
+            0,
+            &(),
+        ))
+        .unwrap();
+
+    // The prover only holds the rows of the blocks that may be relevant, at their offset
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        table_ref,
+        owned_table([
+            timestamptz(
+                "times",
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::Utc,
+                [300, 400, 500, 600, 700, 800],
+            ),
+            bigint("amount", 13..19),
+        ]),
+        3,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT amount FROM table WHERE times >= timestamp '1970-01-01T00:05:50Z' \
+         AND times <= timestamp '1970-01-01T00:10:50Z';"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let kept_blocks = query.proof_expr().prune_partitions(index.blocks());
+    assert_eq!(kept_blocks, 1..3);
+    assert_eq!(index.rows(kept_blocks.clone()), 3..9);
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+
+    // The verifier checks that no skipped block could contain matches before verifying the proof
+    let table_commitment = query
+        .proof_expr()
+        .verify_partition_pruning(index.blocks(), kept_blocks)
+        .unwrap();
+    let query_commitments = QueryCommitments::from_iter([(table_ref, table_commitment)]);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &query_commitments,
+            &serialized_result,
+            &(),
+        )
+        .unwrap()
+        .table;
+    assert_eq!(
+        owned_table_result,
+        owned_table([bigint("amount", [14, 15, 16])])
+    );
+    assert!(query
+        .proof_expr()
+        .verify_partition_pruning(index.blocks(), 2..4)
+        .is_err());
+}